@@ -17,6 +17,19 @@ extern "C" {
                                                                        const char*   schema_json)
                                                   );
 
+    extern indy_error_t indy_issuer_create_typed_schema(indy_handle_t command_handle,
+                                                        const char *  issuer_did,
+                                                        const char *  name,
+                                                        const char *  version,
+                                                        const char *  attr_names,
+                                                        const char *  attr_types_json,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   id,
+                                                                             const char*   schema_json)
+                                                        );
+
     extern indy_error_t indy_issuer_create_and_store_credential_def(indy_handle_t command_handle,
                                                                     indy_handle_t wallet_handle,
                                                                     const char *  issuer_did,
@@ -74,6 +87,24 @@ extern "C" {
                                                                                  const char*   cred_offer_json)
                                                             );
     
+    extern indy_error_t indy_issuer_validate_attributes_for_schema(indy_handle_t command_handle,
+                                                                   const char *  schema_json,
+                                                                   const char *  cred_values_json,
+
+                                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                                        indy_error_t  err,
+                                                                                        const char*   problems_json)
+                                                                   );
+
+    extern indy_error_t indy_issuer_encode_credential_values(indy_handle_t command_handle,
+                                                             const char *  schema_json,
+                                                             const char *  raw_values_json,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   cred_values_json)
+                                                             );
+
     extern indy_error_t indy_issuer_create_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
                                                       const char *  cred_offer_json,
@@ -88,7 +119,31 @@ extern "C" {
                                                                            const char*   cred_revoc_id,
                                                                            const char*   revoc_reg_delta_json)
                                                       );
+
+    extern indy_error_t indy_issuer_create_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  issuance_requests_json,
+                                                       const char *  rev_reg_id,
+                                                       indy_handle_t blob_storage_reader_handle,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   credentials_json)
+                                                       );
+
+    extern indy_error_t indy_issuer_cancel_credentials(indy_handle_t issuance_command_handle);
     
+    extern indy_error_t indy_issuer_reissue_credential(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  cred_offer_json,
+                                                       const char *  cred_req_json,
+                                                       const char *  old_cred_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   cred_json)
+                                                       );
+
     extern indy_error_t indy_issuer_revoke_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
                                                       indy_handle_t blob_storage_reader_handle,
@@ -100,6 +155,17 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
 
+    extern indy_error_t indy_issuer_revoke_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       indy_handle_t blob_storage_reader_handle,
+                                                       const char *  rev_reg_id,
+                                                       const char *  cred_revoc_ids_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
+
 /*    extern indy_error_t indy_issuer_recover_credential(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        indy_handle_t blob_storage_reader_handle,
@@ -121,6 +187,62 @@ extern "C" {
                                                                                           const char*   merged_rev_reg_delta)
                                                                      );
 
+    extern indy_error_t indy_issuer_mark_revocation_registry_rotated(indy_handle_t command_handle,
+                                                                     indy_handle_t wallet_handle,
+                                                                     const char *  rev_reg_id,
+
+                                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                                          indy_error_t  err)
+                                                                     );
+
+    extern indy_error_t indy_issuer_list_revocation_registries(indy_handle_t command_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  cred_def_id,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   rev_regs_json)
+                                                               );
+
+    extern indy_error_t indy_issuer_get_revocation_registry_capacity(indy_handle_t command_handle,
+                                                                     indy_handle_t wallet_handle,
+                                                                     const char *  rev_reg_id,
+                                                                     float         threshold,
+
+                                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                                          indy_error_t  err,
+                                                                                          const char*   capacity_json)
+                                                                     );
+
+    extern indy_error_t indy_issuer_get_unused_revocation_indices(indy_handle_t command_handle,
+                                                                  indy_handle_t wallet_handle,
+                                                                  const char *  rev_reg_id,
+
+                                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                                       indy_error_t  err,
+                                                                                       const char*   unused_indices_json)
+                                                                  );
+
+    extern indy_error_t indy_issuer_export_revocation_registry(indy_handle_t command_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  rev_reg_id,
+                                                               const char *  export_config_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   rev_reg_snapshot)
+                                                               );
+
+    extern indy_error_t indy_issuer_import_revocation_registry(indy_handle_t command_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  import_config_json,
+                                                               const char *  rev_reg_snapshot,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   rev_reg_id)
+                                                               );
+
     extern indy_error_t indy_prover_create_master_secret(indy_handle_t command_handle,
                                                          indy_handle_t wallet_handle,
                                                          const char *  master_secret_id,
@@ -144,6 +266,17 @@ extern "C" {
                                                                                const char*   cred_req_metadata_json)
                                                           );
 
+    extern indy_error_t indy_prover_create_credential_reqs(indy_handle_t command_handle,
+                                                           indy_handle_t wallet_handle,
+                                                           const char *  prover_did,
+                                                           const char *  offered_credentials_json,
+                                                           const char *  master_secret_id,
+
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err,
+                                                                                const char*   cred_reqs_json)
+                                                           );
+
     extern indy_error_t indy_prover_store_credential(indy_handle_t command_handle,
                                                      indy_handle_t wallet_handle,
                                                      const char *  cred_id,
@@ -183,6 +316,15 @@ extern "C" {
                                                                         const char*   credential_json)
                                                    );
 
+    extern indy_error_t indy_prover_get_credential_fingerprint(indy_handle_t command_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  cred_id,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   fingerprint)
+                                                               );
+
     extern indy_error_t indy_prover_search_credentials(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        const char *  query_json,
@@ -246,6 +388,36 @@ extern "C" {
                                                                                                 indy_error_t  err)
                                                                            );
 
+    extern indy_error_t indy_prover_explain_unsatisfiable_proof_req(indy_handle_t command_handle,
+                                                                    indy_handle_t wallet_handle,
+                                                                    const char *  proof_request_json,
+                                                                    const char *  rev_reg_deltas_json,
+
+                                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                                         indy_error_t  err,
+                                                                                         const char*   explanation_json)
+                                                                    );
+
+    extern indy_error_t indy_prover_get_proof_plan(indy_handle_t command_handle,
+                                                   indy_handle_t wallet_handle,
+                                                   const char *  proof_request_json,
+                                                   const char *  rev_reg_deltas_json,
+
+                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                        indy_error_t  err,
+                                                                        const char*   requested_credentials_json)
+                                                   );
+
+    extern indy_error_t indy_prover_preview_disclosure(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  proof_request_json,
+                                                       const char *  requested_credentials_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   preview_json)
+                                                       );
+
     extern indy_error_t indy_prover_create_proof(indy_handle_t command_handle,
                                                  indy_handle_t wallet_handle,
                                                  const char *  proof_req_json,
@@ -260,6 +432,8 @@ extern "C" {
                                                                       const char*   proof_json)
                                                  );
 
+    extern indy_error_t indy_prover_cancel_proof(indy_handle_t proof_command_handle);
+
 
     extern indy_error_t indy_verifier_verify_proof(indy_handle_t command_handle,
                                                    const char *  proof_request_json,
@@ -274,6 +448,45 @@ extern "C" {
                                                                         indy_bool_t   valid )
                                                    );
 
+    extern indy_error_t indy_verifier_verify_proof_with_result(indy_handle_t command_handle,
+                                                               const char *  proof_request_json,
+                                                               const char *  proof_json,
+                                                               const char *  schemas_json,
+                                                               const char *  credential_defs_jsons,
+                                                               const char *  rev_reg_defs_json,
+                                                               const char *  rev_regs_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   result_json)
+                                                               );
+
+    extern indy_error_t indy_verifier_verify_proof_detailed(indy_handle_t command_handle,
+                                                            const char *  proof_request_json,
+                                                            const char *  proof_json,
+                                                            const char *  schemas_json,
+                                                            const char *  credential_defs_jsons,
+                                                            const char *  rev_reg_defs_json,
+                                                            const char *  rev_regs_json,
+
+                                                            void           (*cb)(indy_handle_t command_handle_,
+                                                                                 indy_error_t  err,
+                                                                                 const char*   report_json)
+                                                            );
+
+
+    extern indy_error_t indy_verifier_verify_proof_dry_run(indy_handle_t command_handle,
+                                                           const char *  proof_request_json,
+                                                           const char *  proof_json,
+                                                           const char *  schemas_json,
+                                                           const char *  credential_defs_jsons,
+                                                           const char *  rev_reg_defs_json,
+                                                           const char *  rev_regs_json,
+
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err)
+                                                           );
+
 
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
@@ -301,6 +514,42 @@ extern "C" {
                                                                           const char*   updated_rev_state_json)
                                                      );
 
+    extern indy_error_t indy_prover_update_witness(indy_handle_t command_handle,
+                                                   indy_handle_t blob_storage_reader_handle,
+                                                   const char *  rev_state_json,
+                                                   const char *  rev_reg_def_json,
+                                                   const char *  rev_reg_deltas_json,
+                                                   indy_u64_t    timestamp,
+                                                   const char *  cred_rev_id,
+
+                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                        indy_error_t  err,
+                                                                        const char*   updated_rev_state_json)
+                                                   );
+
+    extern indy_error_t indy_prover_detect_revoked_credentials(indy_handle_t command_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  rev_reg_deltas_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   revoked_credentials_json)
+                                                               );
+
+    extern indy_error_t indy_prover_clear_witness_cache(indy_handle_t command_handle,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err)
+                                                        );
+
+    extern indy_error_t indy_prover_get_max_provable_timestamp(indy_handle_t command_handle,
+                                                               const char *  rev_reg_id,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   timestamp_json)
+                                                               );
+
 
     extern indy_error_t indy_generate_nonce(indy_handle_t command_handle,
                                             void           (*cb)(indy_handle_t command_handle_,
@@ -308,6 +557,20 @@ extern "C" {
                                                                  const char*   nonce)
                                             );
 
+    extern indy_error_t indy_verifier_get_proof_required_identifiers(indy_handle_t command_handle,
+                                                                     const char *  proof_json,
+
+                                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                                          indy_error_t  err,
+                                                                                          const char*   required_identifiers_json)
+                                                                     );
+
+    extern indy_error_t indy_verifier_set_audit_callback(const void*  context,
+
+                                                         void           (*cb)(const void*  context,
+                                                                              const char*  audit_record_json)
+                                                         );
+
     extern indy_error_t indy_to_unqualified(indy_handle_t command_handle,
                                             const char *  entity,
                                             void           (*cb)(indy_handle_t command_handle_,
@@ -315,6 +578,76 @@ extern "C" {
                                                                  const char*   res)
                                             );
 
+    extern indy_error_t indy_build_revocation_registry_id(indy_handle_t command_handle,
+                                                          const char *  issuer_did,
+                                                          const char *  cred_def_id,
+                                                          const char *  tag,
+                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                               indy_error_t  err,
+                                                                               const char*   rev_reg_id)
+                                                          );
+
+    extern indy_error_t indy_build_non_revoked_interval(indy_handle_t command_handle,
+                                                        unsigned long long timestamp,
+                                                        unsigned long long tolerance,
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   interval_json)
+                                                        );
+
+    extern indy_error_t indy_merge_proof_requests(indy_handle_t command_handle,
+                                                  const char *  proof_request_json,
+                                                  const char *  other_proof_request_json,
+                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       const char*   proof_request_json)
+                                                  );
+
+    extern indy_error_t indy_get_revocation_status(indy_handle_t command_handle,
+                                                   indy_handle_t blob_storage_reader_handle,
+                                                   const char *  rev_reg_def_json,
+                                                   const char *  rev_reg_delta_json,
+                                                   const char *  cred_rev_id,
+                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                        indy_error_t  err,
+                                                                        indy_bool_t   revoked)
+                                                   );
+
+    extern indy_error_t indy_get_witness_dependent_indices(indy_handle_t command_handle,
+                                                           const char *  rev_reg_def_json,
+                                                           const char *  rev_reg_delta_json,
+                                                           const char *  cred_rev_id,
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err,
+                                                                                const char*   indices_json)
+                                                           );
+
+    extern indy_error_t indy_proof_request_could_be_satisfied_by_cred_def(indy_handle_t command_handle,
+                                                                          const char *  proof_request_json,
+                                                                          const char *  cred_def_json,
+                                                                          const char *  schema_json,
+                                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                                               indy_error_t  err,
+                                                                                               indy_bool_t   satisfiable)
+                                                                          );
+
+    extern indy_error_t indy_estimate_credential_storage(indy_handle_t command_handle,
+                                                         indy_u32_t    schema_attr_count,
+                                                         indy_bool_t   revocable,
+                                                         indy_u32_t    count,
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err,
+                                                                              indy_u64_t    size)
+                                                         );
+
+    extern indy_error_t indy_compare_schemas(indy_handle_t command_handle,
+                                             const char *  schema_json,
+                                             const char *  other_schema_json,
+                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                  indy_error_t  err,
+                                                                  const char*   schema_diff_json)
+                                             );
+
 #ifdef __cplusplus
 }
 #endif
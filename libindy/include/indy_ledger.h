@@ -68,6 +68,38 @@ extern "C" {
                                                                  const char*   request_result_json)
                                            );
 
+    /// Publishes read request message to validator pool (no signing) and rejects outdated reply.
+    ///
+    /// Works like indy_submit_request, but the reply is accepted only if its state proof
+    /// was signed by the pool not earlier than `freshness_threshold` seconds ago.
+    /// Use it for reads where stale data is dangerous, e.g. revocation registry deltas:
+    /// an outdated delta may not contain recent revocations.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// request_json: Request data json.
+    /// freshness_threshold: max age of state proof timestamp of the reply (in sec).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    /// LedgerStaleState - reply has no state proof or its state proof is older than freshness_threshold
+
+    extern indy_error_t indy_submit_request_with_freshness(indy_handle_t command_handle,
+                                                           indy_handle_t pool_handle,
+                                                           const char *  request_json,
+                                                           indy_u64_t    freshness_threshold,
+
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err,
+                                                                                const char*   request_result_json)
+                                                          );
+
     /// Send action to particular nodes of validator pool.
     ///
     /// The list of requests can be send:
@@ -54,6 +54,9 @@ typedef enum
     // IO Error
     CommonIOError = 114,
 
+    // Operation was cancelled by the caller before completion
+    CommonCancelled = 130,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -132,9 +135,13 @@ typedef enum
     // Item not found on ledger.
     LedgerNotFound = 309,
 
+    // State proof of ledger reply is older than requested freshness threshold
+    LedgerStaleState = 310,
+
     // Revocation registry is full and creation of new registry is necessary
     AnoncredsRevocationRegistryFullError = 400,
 
+    // Revocation index is out of registry bounds or its tail is missing in tails file
     AnoncredsInvalidUserRevocId = 401,
 
     // Attempt to generate master secret with dupplicated name
@@ -147,6 +154,9 @@ typedef enum
     // Attempt to create credential definition with duplicated did schema pair
     AnoncredsCredDefAlreadyExistsError = 407,
 
+    // Signature of received credential or its correctness proof doesn't match credential values
+    AnoncredsInvalidCredentialSignature = 408,
+
     // Crypto errors
     // Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
@@ -260,6 +260,8 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "progress_step": optional<number> Number of records between progress reports of indy_export_wallet_with_progress.
+    ///                      0 (default) reports the end of export only.
     ///   }
     ///
     /// #Returns
@@ -275,6 +277,28 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Exports opened wallet reporting the progress of export
+    ///
+    /// #Params:
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// export_config: JSON containing settings for input operation (see indy_export_wallet).
+    /// progress_cb: called with the number of exported records after every "progress_step" records and once more at the end.
+    ///              total is the number of records in the wallet.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_wallet_with_progress(indy_handle_t  command_handle,
+                                                         indy_handle_t  wallet_handle,
+                                                         const char*    export_config_json,
+                                                         void           (*progress_cb)(indy_handle_t command_handle_, indy_u64_t processed, indy_i64_t total),
+                                                         void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
 
     /// Creates a new secure wallet and then imports its content
     /// according to fields provided in import_config
@@ -314,6 +338,14 @@ extern "C" {
     /// {
     ///   "path": <string>, path of the file that contains exported wallet content
     ///   "key": <string>, key used for export of the wallet
+    ///   "on_conflict": optional<string> What to do with a record that is already in the wallet:
+    ///                  error - fail with WalletItemAlreadyExists (used by default)
+    ///                  skip - keep the record in the wallet
+    ///                  overwrite - replace value and tags of the record in the wallet
+    ///                  With skip or overwrite the records are imported into the wallet if it already exists,
+    ///                  so an interrupted import can be resumed. Such a wallet is kept if import fails.
+    ///   "progress_step": optional<number> Number of records between progress reports of indy_import_wallet_with_progress.
+    ///                    0 (default) reports the end of import only.
     /// }
     ///
     /// #Returns
@@ -330,6 +362,30 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Imports wallet content as indy_import_wallet does reporting the progress of import
+    ///
+    /// #Params
+    /// config: Wallet configuration json (see indy_import_wallet).
+    /// credentials: Wallet credentials json (see indy_import_wallet).
+    /// import_config: Import settings json (see indy_import_wallet).
+    /// progress_cb: called with the number of imported records after every "progress_step" records and once more at the end.
+    ///              total is the number of exported records or -1 if the export file doesn't contain it.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_with_progress(indy_handle_t  command_handle,
+                                                         const char*    config,
+                                                         const char*    credentials,
+                                                         const char*    import_config_json,
+                                                         void           (*progress_cb)(indy_handle_t command_handle_, indy_u64_t processed, indy_i64_t total),
+                                                         void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
     /// Closes opened wallet and frees allocated resources.
     ///
     /// #Params
@@ -5,24 +5,30 @@ use commands::anoncreds::AnoncredsCommand;
 use commands::anoncreds::issuer::IssuerCommand;
 use commands::anoncreds::prover::ProverCommand;
 use commands::anoncreds::verifier::VerifierCommand;
-use domain::anoncreds::schema::{Schema, AttributeNames, Schemas};
+use services::anoncreds::helpers::RedactedAttributes;
+use services::anoncreds::verifier::{set_verification_audit_sink, VerificationAuditSink};
+use domain::anoncreds::schema::{Schema, AttributeNames, AttributeTypes, Schemas};
 use domain::crypto::did::DidValue;
 use domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions};
 use domain::anoncreds::credential_offer::CredentialOffer;
-use domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
+use domain::anoncreds::credential_request::{CredentialIssuanceRequest, CredentialRequest, CredentialRequestMetadata, OfferedCredential};
 use domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
 use domain::anoncreds::credential::{Credential, CredentialValues};
-use domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
-use domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
-use domain::anoncreds::proof::Proof;
+use domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryExportConfig, RevocationRegistryId, RevocationRegistryDefinitions};
+use domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltas};
+use domain::anoncreds::proof::{Proof, VerificationAuditRecord};
 use domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
 use domain::anoncreds::requested_credential::RequestedCredentials;
 use domain::anoncreds::revocation_registry::RevocationRegistries;
 use domain::anoncreds::revocation_state::{RevocationState, RevocationStates};
+use utils::cancellation::CancellationToken;
 use utils::ctypes;
 
-use libc::c_char;
+use libc::{c_char, c_void};
+#[cfg(feature = "encoding_conformance_tests")]
+use std::collections::HashMap;
 use std::ptr;
+use serde_json;
 
 use utils::validation::Validatable;
 
@@ -94,6 +100,7 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
                     name,
                     version,
                     attrs,
+                    None,
                     Box::new(move |result| {
                         let (err, id, schema_json) = prepare_result_2!(result, String::new(), String::new());
                         trace!("ursa_cl_credential_public_key_to_json: id: {:?}, schema_json: {:?}", id, schema_json);
@@ -110,6 +117,94 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
     res
 }
 
+/// Create credential schema entity with declared attribute types.
+///
+/// Works as indy_issuer_create_schema, but the schema also declares types of its attributes.
+/// The type of an attribute drives how its raw value is encoded (see indy_issuer_encode_credential_values)
+/// and whether predicates can be requested over it: string attributes can't be used in predicates.
+///
+/// Note that Schema ledger transactions don't carry attribute types, so a schema read from the ledger is untyped.
+/// Keep the returned schema json and pass it to the other anoncreds functions to apply the types.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// issuer_did: DID of schema issuer
+/// name: a name the schema
+/// version: a version of the schema
+/// attrs: a list of schema attributes descriptions (the number of attributes should be less or equal than 125)
+///     `["attr1", "attr2"]`
+/// attr_types_json: types of schema attributes, attributes without a type keep the default encoding
+///     {
+///         "<attr>": "integer" | "date" | "string",
+///         ...
+///     }
+///     - "integer": 32-bit signed integer encoded as is
+///     - "date": `YYYY-MM-DD` date encoded as the number of days since the Unix epoch
+///     - "string": any value encoded as its hash, even if it looks like a number
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// schema_id: identifier of created schema
+/// schema_json: schema as json:
+/// {
+///     id: identifier of schema
+///     attrNames: array of attribute name strings
+///     attributeTypes: map of attribute types
+///     name: schema's name string
+///     version: schema's version string,
+///     ver: version of the Schema json
+/// }
+///
+/// #Errors
+/// Common*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_create_typed_schema(command_handle: CommandHandle,
+                                              issuer_did: *const c_char,
+                                              name: *const c_char,
+                                              version: *const c_char,
+                                              attrs: *const c_char,
+                                              attr_types_json: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                   schema_id: *const c_char, schema_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_typed_schema: >>> issuer_did: {:?}, name: {:?}, version: {:?}, attrs: {:?}, attr_types_json: {:?}",
+           issuer_did, name, version, attrs, attr_types_json);
+
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam2, DidValue);
+    check_useful_c_str!(name, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(version, ErrorCode::CommonInvalidParam4);
+    check_useful_validatable_json!(attrs, ErrorCode::CommonInvalidParam5, AttributeNames);
+    check_useful_json!(attr_types_json, ErrorCode::CommonInvalidParam6, AttributeTypes);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_issuer_create_typed_schema: entity >>> issuer_did: {:?}, name: {:?}, version: {:?}, attrs: {:?}, attr_types_json: {:?}",
+           issuer_did, name, version, attrs, attr_types_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateSchema(
+                    issuer_did,
+                    name,
+                    version,
+                    attrs,
+                    Some(attr_types_json),
+                    Box::new(move |result| {
+                        let (err, id, schema_json) = prepare_result_2!(result, String::new(), String::new());
+                        trace!("indy_issuer_create_typed_schema: id: {:?}, schema_json: {:?}", id, schema_json);
+                        let id = ctypes::string_to_cstring(id);
+                        let schema_json = ctypes::string_to_cstring(schema_json);
+                        cb(command_handle, err, id.as_ptr(), schema_json.as_ptr())
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_typed_schema: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create credential definition entity that encapsulates credentials issuer DID, credential schema, secrets used for signing credentials
 /// and secrets used for credentials revocation.
 ///
@@ -122,6 +217,9 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
 /// Note: Use combination of `indy_issuer_rotate_credential_def_start` and `indy_issuer_rotate_credential_def_apply` functions
 /// to generate new keys for an existing credential definition.
 ///
+/// Keys are generated in the crypto thread pool (see `crypto_thread_pool_size` of `indy_set_runtime_config`),
+/// so other commands are not blocked meanwhile.
+///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
@@ -364,6 +462,9 @@ pub extern fn indy_issuer_rotate_credential_def_apply(command_handle: CommandHan
 /// revocation registry and intended to be distributed out of leger (REVOC_REG_DEF transaction will still contain uri and hash of tails).
 /// This call requires access to pre-configured blob storage writer instance handle that will allow to write generated tails.
 ///
+/// The accumulator is built in the crypto thread pool (see `crypto_thread_pool_size` of `indy_set_runtime_config`),
+/// so other commands are not blocked meanwhile.
+///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
@@ -380,7 +481,7 @@ pub extern fn indy_issuer_rotate_credential_def_apply(command_handle: CommandHan
 ///         1) ISSUANCE_BY_DEFAULT: all indices are assumed to be issued and initial accumulator is calculated over all indices;
 ///            Revocation Registry is updated only during revocation.
 ///         2) ISSUANCE_ON_DEMAND: nothing is issued initially accumulator is 1 (used by default);
-///     "max_cred_num": maximum number of credentials the new registry can process (optional, default 100000)
+///     "max_cred_num": maximum number of credentials the new registry can process (optional, default 100000, at most 2147483647)
 /// }
 /// tails_writer_handle: handle of blob storage to store tails (returned by `indy_open_blob_storage_writer`).
 /// cb: Callback that takes command result as parameter.
@@ -533,6 +634,107 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
     res
 }
 
+/// Checks credential values against the schema they are going to be issued against before calling
+/// indy_issuer_create_credential. Every schema attribute must have a value with decimal `encoded` part
+/// and no other attributes are allowed. Attribute names are compared case-insensitively, ignoring spaces.
+/// Values of typed attributes (see indy_issuer_create_typed_schema) must be encoded according to their types.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// schema_json: schema as a json (see indy_issuer_create_schema)
+/// cred_values_json: credential values as a json (see cred_values_json of indy_issuer_create_credential)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// problems_json: list of found problems, empty if credential values match the schema
+///     [{
+///         "attr": string, - attribute name in canonical form
+///         "problem": "missing" | "invalid_encoding" | "not_in_schema"
+///     }]
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_issuer_validate_attributes_for_schema(command_handle: CommandHandle,
+                                                         schema_json: *const c_char,
+                                                         cred_values_json: *const c_char,
+                                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                              problems_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_validate_attributes_for_schema: >>> schema_json: {:?}, cred_values_json: {:?}", schema_json, cred_values_json);
+
+    check_useful_validatable_json!(schema_json, ErrorCode::CommonInvalidParam2, Schema);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam3, CredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_validate_attributes_for_schema: entities >>> schema_json: {:?}, cred_values_json: {:?}", schema_json, RedactedAttributes(&cred_values_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ValidateAttributesForSchema(
+                    schema_json,
+                    cred_values_json,
+                    boxed_callback_string!("indy_issuer_validate_attributes_for_schema", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_validate_attributes_for_schema: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds credential values for indy_issuer_create_credential from raw attribute values.
+/// Every value is encoded according to the type of its schema attribute (see indy_issuer_create_typed_schema),
+/// values of untyped attributes keep the default encoding: 32-bit integers as is, anything else as a hash.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// schema_json: schema as a json (see indy_issuer_create_typed_schema)
+/// raw_values_json: raw attribute values as a json
+///     {
+///         "<attr>": "<raw value>",
+///         ...
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_values_json: credential values as a json (see cred_values_json of indy_issuer_create_credential)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_issuer_encode_credential_values(command_handle: CommandHandle,
+                                                   schema_json: *const c_char,
+                                                   raw_values_json: *const c_char,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                        cred_values_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_encode_credential_values: >>> schema_json: {:?}, raw_values_json: {:?}", schema_json, raw_values_json);
+
+    check_useful_validatable_json!(schema_json, ErrorCode::CommonInvalidParam2, Schema);
+    check_useful_json!(raw_values_json, ErrorCode::CommonInvalidParam3, HashMap<String, String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_encode_credential_values: entities >>> schema_json: {:?}, raw_values_json: {:?}", schema_json, RedactedAttributes(&raw_values_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::EncodeCredentialValues(
+                    schema_json,
+                    raw_values_json,
+                    boxed_callback_sensitive_string!("indy_issuer_encode_credential_values", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_encode_credential_values: <<< res: {:?}", res);
+
+    res
+}
+
 /// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request.
 ///
 /// Cred Request must match Cred Offer. The credential definition and revocation registry definition
@@ -611,7 +813,7 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
 
     trace!("indy_issuer_create_credential: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, \
-    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), RedactedAttributes(&cred_values_json), secret!(&rev_reg_id), blob_storage_reader_handle);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
@@ -626,7 +828,7 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
                     Box::new(move |result| {
                         let (err, cred_json, revoc_id, revoc_reg_delta_json) = prepare_result_3!(result, String::new(), None, None);
                         trace!("indy_issuer_create_credential: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}",
-                               secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
+                               sensitive!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
                         let cred_json = ctypes::string_to_cstring(cred_json);
                         let revoc_id = revoc_id.map(ctypes::string_to_cstring);
                         let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
@@ -643,6 +845,170 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     res
 }
 
+/// Issues a batch of credentials of one credential definition the same way as `indy_issuer_create_credential`.
+///
+/// Credential definition and revocation registry are loaded from the wallet once for the whole batch.
+/// The batch is atomic: if any credential can't be issued (e.g. revocation registry gets full),
+/// no credential is returned and the revocation registry stored in the wallet stays unchanged.
+/// A running batch can be cancelled with `indy_issuer_cancel_credentials`: credentials issued before
+/// the cancellation are returned and the revocation registry is stored with their indices only.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// issuance_requests_json: credentials to issue
+///     [{
+///         "cred_offer": <cred offer created by indy_issuer_create_credential_offer>,
+///         "cred_req": <credential request created by indy_prover_create_credential_req>,
+///         "cred_values": <credential values, see indy_issuer_create_credential>
+///     }]
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// credentials_json: issued credentials in the order of requests
+///     {
+///         "credentials": [{
+///             "cred": <credential json, see indy_issuer_create_credential>,
+///             "cred_rev_id": Optional<string>
+///         }],
+///         "rev_reg_delta": Optional<revocation registry delta json with all newly issued credentials>,
+///         "cancelled": <bool> - whether the batch has been cancelled before all credentials were issued
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             issuance_requests_json: *const c_char,
+                                             rev_reg_id: *const c_char,
+                                             blob_storage_reader_handle: IndyHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credentials: >>> wallet_handle: {:?}, issuance_requests_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, issuance_requests_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(issuance_requests_json, ErrorCode::CommonInvalidParam3, Vec<CredentialIssuanceRequest>);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credentials: entities >>> wallet_handle: {:?}, issuance_requests_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, secret!(&issuance_requests_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentials(
+                    wallet_handle,
+                    issuance_requests_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    CancellationToken::register(command_handle),
+                    boxed_callback_sensitive_string!("indy_issuer_create_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Cancels batch issuance started by `indy_issuer_create_credentials`.
+/// The credential being issued is completed, no further credentials are issued and the callback
+/// of the batch gets the credentials issued so far with `cancelled` flag set.
+///
+/// #Params
+/// issuance_command_handle: command handle passed to `indy_issuer_create_credentials`.
+///
+/// #Returns
+/// Success if batch issuance for the handle is in progress
+/// CommonInvalidParam1 if there is no batch issuance for the handle (e.g. it is already finished)
+#[no_mangle]
+pub extern fn indy_issuer_cancel_credentials(issuance_command_handle: CommandHandle) -> ErrorCode {
+    trace!("indy_issuer_cancel_credentials: >>> issuance_command_handle: {:?}", issuance_command_handle);
+
+    let res = if CancellationToken::cancel(issuance_command_handle) {
+        ErrorCode::Success
+    } else {
+        ErrorCode::CommonInvalidParam1
+    };
+
+    trace!("indy_issuer_cancel_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Re-issues a credential under a new credential definition keeping its attribute values.
+///
+/// Intended for credential definition rotation: the issuer signs the values of a credential
+/// issued under its previous credential definition with the keys of the new one, so the holder
+/// doesn't need to provide attribute values again. The holder's master secret is bound
+/// through the credential request created for the new credential offer.
+/// Re-issued credentials are not revocable.
+///
+/// The issuer wallet keeps the values of every issued credential, and only these values are re-issued:
+/// the credential is rejected if it wasn't issued from this wallet or if its values were changed.
+/// Credentials issued by earlier library versions can't be re-issued.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer for the new credential definition
+/// cred_req_json: a credential request created by indy_prover_create_credential_req for cred_offer_json
+/// old_cred_json: credential issued by this issuer under the previous credential definition of the same schema
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: Credential json containing signed credential values (see indy_issuer_create_credential)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_reissue_credential(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cred_offer_json: *const c_char,
+                                             cred_req_json: *const c_char,
+                                             old_cred_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  cred_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_reissue_credential: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, old_cred_json: {:?}",
+           wallet_handle, cred_offer_json, cred_req_json, old_cred_json);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
+    check_useful_validatable_json!(old_cred_json, ErrorCode::CommonInvalidParam5, Credential);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_reissue_credential: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, old_cred_json: {:?}",
+           wallet_handle, secret!(&cred_offer_json), secret!(&cred_req_json), sensitive!(&old_cred_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ReissueCredential(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_req_json,
+                    old_cred_json,
+                    boxed_callback_sensitive_string!("indy_issuer_reissue_credential", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_reissue_credential: <<< res: {:?}", res);
+
+    res
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -710,29 +1076,36 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
     res
 }
 
-/*/// Recover a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
+/// Revoke several credentials identified by their cred_revoc_ids (returned by indy_issuer_create_credential)
+/// in one step.
 ///
 /// The corresponding credential definition and revocation registry must be already
 /// created an stored into the wallet.
 ///
-/// This call returns revoc registry delta as json file intended to be shared as REVOC_REG_ENTRY transaction.
-/// Note that it is possible to accumulate deltas to reduce ledger load.
+/// All ids are checked before the accumulator is touched: if any of them can't be revoked
+/// nothing is revoked and the registry stored in the wallet is left unchanged.
+///
+/// This call returns a single revoc registry delta covering all revoked credentials
+/// as json file intended to be shared as REVOC_REG_ENTRY transaction.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
 /// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
 /// rev_reg_id: id of revocation registry stored in wallet
-/// cred_revoc_id: local id for revocation info related to issued credential
+/// cred_revoc_ids_json: json array of local ids for revocation info related to issued credentials
+///     ["cred_revoc_id1", "cred_revoc_id2", ...]
+///     Every item may also be a compact list of ids and inclusive ranges, e.g. ["3,5,10-14"].
+///     Duplicate ids are revoked once.
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// revoc_reg_delta_json: Revocation registry delta json with a recovered credential
+/// revoc_reg_delta_json: Revocation registry delta json with revoked credentials
 /// {
 ///     value: {
-///         prevAccum: string - previous accumulator value.
-///         accum: string - current accumulator value.
-///         issued: array<number> an array of issued indices.
+///         prevAccum: string - accumulator value before the first revocation.
+///         accum: string - accumulator value after the last revocation.
+///         revoked: array<number> an array of revoked indices.
 ///     },
 ///     ver: string - version revocation registry delta json
 /// }
@@ -742,45 +1115,112 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
+pub extern fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
                                              wallet_handle: WalletHandle,
                                              blob_storage_reader_cfg_handle: IndyHandle,
                                              rev_reg_id: *const c_char,
-                                             cred_revoc_id: *const c_char,
+                                             cred_revoc_ids_json: *const c_char,
                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                  revoc_reg_delta_json: *const c_char,
-                                             )>) -> ErrorCode {
-    check_useful_c_str!(rev_reg_id, ErrorCode::CommonInvalidParam4);
-    check_useful_c_str!(cred_revoc_id, ErrorCode::CommonInvalidParam5);
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credentials: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_ids_json);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_json!(cred_revoc_ids_json, ErrorCode::CommonInvalidParam5, Vec<String>);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
+    trace!("indy_issuer_revoke_credentials: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(&cred_revoc_ids_json));
+
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Issuer(
-                IssuerCommand::RecoverCredential(
+                IssuerCommand::RevokeCredentials(
                     wallet_handle,
                     blob_storage_reader_cfg_handle,
                     rev_reg_id,
-                    cred_revoc_id,
-                    Box::new(move |result| {
-                        let (err, revoc_reg_update_json) = prepare_result_1!(result, String::new());
-                        let revoc_reg_update_json = ctypes::string_to_cstring(revoc_reg_update_json);
-                        cb(command_handle, err, revoc_reg_update_json.as_ptr())
-                    })
+                    cred_revoc_ids_json,
+                    boxed_callback_string!("indy_issuer_revoke_credentials", cb, command_handle)
                 ))));
 
-    prepare_result!(result)
-}*/
+    let res = prepare_result!(result);
 
-/// Merge two revocation registry deltas (returned by indy_issuer_create_credential or indy_issuer_revoke_credential) to accumulate common delta.
-/// Send common delta to ledger to reduce the load.
+    trace!("indy_issuer_revoke_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/*/// Recover a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
+///
+/// The corresponding credential definition and revocation registry must be already
+/// created an stored into the wallet.
+///
+/// This call returns revoc registry delta as json file intended to be shared as REVOC_REG_ENTRY transaction.
+/// Note that it is possible to accumulate deltas to reduce ledger load.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
-/// rev_reg_delta_json: revocation registry delta.
-/// {
-///     value: {
-///         prevAccum: string - previous accumulator value.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
+/// rev_reg_id: id of revocation registry stored in wallet
+/// cred_revoc_id: local id for revocation info related to issued credential
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_delta_json: Revocation registry delta json with a recovered credential
+/// {
+///     value: {
+///         prevAccum: string - previous accumulator value.
+///         accum: string - current accumulator value.
+///         issued: array<number> an array of issued indices.
+///     },
+///     ver: string - version revocation registry delta json
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             blob_storage_reader_cfg_handle: IndyHandle,
+                                             rev_reg_id: *const c_char,
+                                             cred_revoc_id: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  revoc_reg_delta_json: *const c_char,
+                                             )>) -> ErrorCode {
+    check_useful_c_str!(rev_reg_id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(cred_revoc_id, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RecoverCredential(
+                    wallet_handle,
+                    blob_storage_reader_cfg_handle,
+                    rev_reg_id,
+                    cred_revoc_id,
+                    Box::new(move |result| {
+                        let (err, revoc_reg_update_json) = prepare_result_1!(result, String::new());
+                        let revoc_reg_update_json = ctypes::string_to_cstring(revoc_reg_update_json);
+                        cb(command_handle, err, revoc_reg_update_json.as_ptr())
+                    })
+                ))));
+
+    prepare_result!(result)
+}*/
+
+/// Merge two revocation registry deltas (returned by indy_issuer_create_credential or indy_issuer_revoke_credential) to accumulate common delta.
+/// Send common delta to ledger to reduce the load.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// rev_reg_delta_json: revocation registry delta.
+/// {
+///     value: {
+///         prevAccum: string - previous accumulator value.
 ///         accum: string - current accumulator value.
 ///         issued: array<number> an array of issued indices.
 ///         revoked: array<number> an array of revoked indices.
@@ -839,848 +1279,674 @@ pub extern fn indy_issuer_merge_revocation_registry_deltas(command_handle: Comma
     res
 }
 
-/// Creates a master secret with a given id and stores it in the wallet.
-/// The id must be unique.
+/// Marks revocation registry as rotated (replaced by a new registry of the same credential definition).
+/// Issuance of new credentials into rotated registry is rejected,
+/// while revocation of already issued credentials is still possible.
+///
+/// Registry lifecycle: Active -> Full (set automatically once the last index is issued) -> Rotated.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// master_secret_id: (optional, if not present random one will be generated) new master id
-///
-/// #Returns
-/// out_master_secret_id: Id of generated master secret
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// cb: Callback that takes command result as parameter.
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_create_master_secret(command_handle: CommandHandle,
-                                               wallet_handle: WalletHandle,
-                                               master_secret_id: *const c_char,
-                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                    out_master_secret_id: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_create_master_secret: >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+pub extern fn indy_issuer_mark_revocation_registry_rotated(command_handle: CommandHandle,
+                                                           wallet_handle: WalletHandle,
+                                                           rev_reg_id: *const c_char,
+                                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_mark_revocation_registry_rotated: >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
 
-    check_useful_opt_c_str!(master_secret_id, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_create_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+    trace!("indy_issuer_mark_revocation_registry_rotated: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::CreateMasterSecret(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::MarkRevocationRegistryRotated(
                     wallet_handle,
-                    master_secret_id,
-                    boxed_callback_string!("indy_prover_create_master_secret", cb, command_handle)
+                    rev_reg_id,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_mark_revocation_registry_rotated: ");
+                        cb(command_handle, err)
+                    })
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_create_master_secret: <<< res: {:?}", res);
+    trace!("indy_issuer_mark_revocation_registry_rotated: <<< res: {:?}", res);
 
     res
 }
 
-/// Creates a credential request for the given credential offer.
-///
-/// The method creates a blinded master secret for a master secret identified by a provided name.
-/// The master secret identified by the name must be already stored in the secure wallet (see prover_create_master_secret)
-/// The blinded master secret is a part of the credential request.
+/// Lists revocation registries stored in the wallet for the given credential definition.
 ///
 /// #Params
-/// command_handle: command handle to map callback to user context
-/// wallet_handle: wallet handle (created by open_wallet)
-/// prover_did: a DID of the prover
-/// cred_offer_json: credential offer as a json containing information about the issuer and a credential
-///     {
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///          ...
-///         Other fields that contains data structures internal to Ursa.
-///         These fields should not be parsed and are likely to change in future versions.
-///     }
-/// cred_def_json: credential definition json related to <cred_def_id> in <cred_offer_json>
-/// master_secret_id: the id of the master secret stored in the wallet
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_def_id: id of credential definition stored in the wallet
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// cred_req_json: Credential request json for creation of credential by Issuer
-///     {
-///      "prover_did" : string,
-///      "cred_def_id" : string,
-///         // Fields below can depend on Cred Def type
-///      "blinded_ms" : <blinded_master_secret>,
-///                     (opaque type that contains data structures internal to Ursa.
-///                      It should not be parsed and are likely to change in future versions).
-///      "blinded_ms_correctness_proof" : <blinded_ms_correctness_proof>,
-///                     (opaque type that contains data structures internal to Ursa.
-///                      It should not be parsed and are likely to change in future versions).
-///      "nonce": string
-///    }
-/// cred_req_metadata_json: Credential request metadata json for further processing of received form Issuer credential.
-///     Credential request metadata contains data structures internal to Ursa.
-///     Credential request metadata mustn't be shared with Issuer.
+/// rev_regs_json: list of registries ordered by id
+///     [{
+///         "rev_reg_id": string,
+///         "state": "Active" | "Full" | "Rotated",
+///         "issued": <number of assigned indices>,
+///         "max_cred_num": <registry capacity>,
+///         "utilization": <issued / max_cred_num>
+///     }]
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_create_credential_req(command_handle: CommandHandle,
-                                                wallet_handle: WalletHandle,
-                                                prover_did: *const c_char,
-                                                cred_offer_json: *const c_char,
-                                                cred_def_json: *const c_char,
-                                                master_secret_id: *const c_char,
-                                                cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                     cred_req_json: *const c_char,
-                                                                     cred_req_metadata_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_create_credential_req: >>> wallet_handle: {:?}, prover_did: {:?}, cred_offer_json: {:?}, cred_def_json: {:?}, master_secret_id: {:?}",
-           wallet_handle, prover_did, cred_offer_json, cred_def_json, master_secret_id);
+pub extern fn indy_issuer_list_revocation_registries(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     cred_def_id: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          rev_regs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_list_revocation_registries: >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
 
-    check_useful_validatable_string!(prover_did, ErrorCode::CommonInvalidParam3, DidValue);
-    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam4, CredentialOffer);
-    check_useful_validatable_json!(cred_def_json, ErrorCode::CommonInvalidParam5, CredentialDefinition);
-    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam6);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_create_credential_req: entities >>> wallet_handle: {:?}, prover_did: {:?}, cred_offer_json: {:?}, cred_def_json: {:?}, master_secret_id: {:?}",
-           wallet_handle, prover_did, cred_offer_json, cred_def_json, master_secret_id);
+    trace!("indy_issuer_list_revocation_registries: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::CreateCredentialRequest(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ListRevocationRegistries(
                     wallet_handle,
-                    prover_did,
-                    cred_offer_json,
-                    cred_def_json,
-                    master_secret_id,
-                    Box::new(move |result| {
-                        let (err, cred_req_json, cred_req_metadata_json) = prepare_result_2!(result, String::new(), String::new());
-                        trace!("indy_prover_create_credential_req: cred_req_json: {:?}, cred_req_metadata_json: {:?}", cred_req_json, cred_req_metadata_json);
-                        let cred_req_json = ctypes::string_to_cstring(cred_req_json);
-                        let cred_req_metadata_json = ctypes::string_to_cstring(cred_req_metadata_json);
-                        cb(command_handle, err, cred_req_json.as_ptr(), cred_req_metadata_json.as_ptr())
-                    })
+                    cred_def_id,
+                    boxed_callback_string!("indy_issuer_list_revocation_registries", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_create_credential_req: <<< res: {:?}", res);
+    trace!("indy_issuer_list_revocation_registries: <<< res: {:?}", res);
 
     res
 }
 
-/// Set credential attribute tagging policy.
-/// Writes a non-secret record marking attributes to tag, and optionally
-/// updates tags on existing credentials on the credential definition to match.
-///
-/// EXPERIMENTAL
-///
-/// The following tags are always present on write:
-///     {
-///         "schema_id": <credential schema id>,
-///         "schema_issuer_did": <credential schema issuer did>,
-///         "schema_name": <credential schema name>,
-///         "schema_version": <credential schema version>,
-///         "issuer_did": <credential issuer did>,
-///         "cred_def_id": <credential definition id>,
-///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
-///     }
-///
-/// The policy sets the following tags for each attribute it marks taggable, written to subsequent
-/// credentials and (optionally) all existing credentials on the credential definition:
-///     {
-///         "attr::<attribute name>::marker": "1",
-///         "attr::<attribute name>::value": <attribute raw value>,
-///     }
+/// Returns how much of the revocation registry is already assigned to credentials.
+/// Lets issuer create the next registry before issuance fails with AnoncredsRevocationRegistryFullError.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// cred_def_id: credential definition id
-/// tag_attrs_json: JSON array with names of attributes to tag by policy, or null for all
-/// retroactive: boolean, whether to apply policy to existing credentials on credential definition identifier
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// threshold: share of assigned indices in (0, 1] from which registry is reported as nearly full
 /// cb: Callback that takes command result as parameter.
 ///
+/// #Returns
+/// capacity_json:
+///     {
+///         "rev_reg_id": string,
+///         "state": "Active" | "Full" | "Rotated",
+///         "issued": <number of assigned indices>,
+///         "max_cred_num": <registry capacity>,
+///         "capacity_remaining": <number of indices left>,
+///         "utilization": <issued / max_cred_num>,
+///         "nearly_full": <whether utilization reached threshold>
+///     }
+///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_set_credential_attr_tag_policy(command_handle: CommandHandle,
-                                                         wallet_handle: WalletHandle,
-                                                         cred_def_id: *const c_char,
-                                                         tag_attrs_json: *const c_char,
-                                                         retroactive: bool,
-                                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
-    trace!("indy_prover_set_credential_attr_tag_policy: >>> wallet_handle: {:?}, cred_def_id: {:?}, tag_attrs_json: {:?}, retroactive: {:?}", wallet_handle, cred_def_id, tag_attrs_json, retroactive);
+pub extern fn indy_issuer_get_revocation_registry_capacity(command_handle: CommandHandle,
+                                                           wallet_handle: WalletHandle,
+                                                           rev_reg_id: *const c_char,
+                                                           threshold: f32,
+                                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                capacity_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_revocation_registry_capacity: >>> wallet_handle: {:?}, rev_reg_id: {:?}, threshold: {:?}", wallet_handle, rev_reg_id, threshold);
 
-    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
-    check_useful_opt_json!(tag_attrs_json, ErrorCode::CommonInvalidParam4, CredentialAttrTagPolicy);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_prover_set_credential_attr_tag_policy: entities >>> wallet_handle: {:?}, cred_def_id: {:?}, tag_attrs_json: {:?}, retroactive: {:?}",
-           wallet_handle, cred_def_id, tag_attrs_json, retroactive);
+    trace!("indy_issuer_get_revocation_registry_capacity: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}, threshold: {:?}", wallet_handle, rev_reg_id, threshold);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::SetCredentialAttrTagPolicy(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetRevocationRegistryCapacity(
                     wallet_handle,
-                    cred_def_id,
-                    tag_attrs_json,
-                    retroactive,
-                    Box::new(move |result| {
-                        let err = prepare_result!(result);
-                        trace!("indy_prover_set_credential_attr_tag_policy: ");
-                        cb(command_handle, err)
-                    })
+                    rev_reg_id,
+                    threshold,
+                    boxed_callback_string!("indy_issuer_get_revocation_registry_capacity", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_set_credential_attr_tag_policy: <<< res: {:?}", res);
+    trace!("indy_issuer_get_revocation_registry_capacity: <<< res: {:?}", res);
 
     res
 }
 
-/// Get credential attribute tagging policy by credential definition id.
-///
-/// EXPERIMENTAL
+/// Returns revocation indices of the registry that have never been assigned to a credential.
+/// Indices assigned to credentials revoked since are not included.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// cred_def_id: credential definition id
+/// rev_reg_id: id of revocation registry stored in the wallet
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// JSON array with all attributes that current policy marks taggable;
-/// null for default policy (tag all credential attributes).
-/// 
+/// unused_indices_json: ascending list of never assigned indices
+///     [<index>, ...]
+///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_get_credential_attr_tag_policy(command_handle: CommandHandle,
-                                                         wallet_handle: WalletHandle,
-                                                         cred_def_id: *const c_char,
-                                                         cb: Option<extern fn(command_handle_: CommandHandle,
-                                                                              err: ErrorCode,
-                                                                              catpol_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_get_credential_attr_tag_policy: >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+pub extern fn indy_issuer_get_unused_revocation_indices(command_handle: CommandHandle,
+                                                        wallet_handle: WalletHandle,
+                                                        rev_reg_id: *const c_char,
+                                                        cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                             unused_indices_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_unused_revocation_indices: >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
 
-    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_get_credential_attr_tag_policy: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+    trace!("indy_issuer_get_unused_revocation_indices: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::GetCredentialAttrTagPolicy(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetUnusedRevocationIndices(
                     wallet_handle,
-                    cred_def_id,
-                    boxed_callback_string!("indy_prover_get_credential_attr_tag_policy", cb, command_handle)
+                    rev_reg_id,
+                    boxed_callback_string!("indy_issuer_get_unused_revocation_indices", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_get_credential_attr_tag_policy: <<< res: {:?}", res);
+    trace!("indy_issuer_get_unused_revocation_indices: <<< res: {:?}", res);
 
     res
 }
 
-/// Check credential provided by Issuer for the given credential request,
-/// updates the credential by a master secret and stores in a secure wallet.
-///
-/// To support efficient and flexible search the following tags will be created for stored credential:
-///     {
-///         "schema_id": <credential schema id>,
-///         "schema_issuer_did": <credential schema issuer did>,
-///         "schema_name": <credential schema name>,
-///         "schema_version": <credential schema version>,
-///         "issuer_did": <credential issuer did>,
-///         "cred_def_id": <credential definition id>,
-///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
-///         // for every attribute in <credential values> that credential attribute tagging policy marks taggable
-///         "attr::<attribute name>::marker": "1",
-///         "attr::<attribute name>::value": <attribute raw value>,
-///     }
+/// Exports the issuer side state of a revocation registry (definition, private key, registry accumulator
+/// and issuance info) as a single snapshot encrypted the same way as wallet export.
+/// Credential definition and tails file are not included and must be backed up separately.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// cred_id: (optional, default is a random one) identifier by which credential will be stored in the wallet
-/// cred_req_metadata_json: a credential request metadata created by indy_prover_create_credential_req
-/// cred_json: credential json received from issuer
-///     {
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_def_id", Optional<string>, - identifier of revocation registry
-///         "values": - credential values
-///             {
-///                 "attr1" : {"raw": "value1", "encoded": "value1_as_int" },
-///                 "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
-///             }
-///         // Fields below can depend on Cred Def type
-///         Other fields that contains data structures internal to Ursa.
-///         These fields should not be parsed and are likely to change in future versions.
-///     }
-/// cred_def_json: credential definition json related to <cred_def_id> in <cred_json>
-/// rev_reg_def_json: revocation registry definition json related to <rev_reg_def_id> in <cred_json>
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// export_config_json: snapshot encryption settings json
+///   {
+///     "key": <string>, Key or passphrase used for snapshot key derivation.
+///     "key_derivation_method": optional<string> Algorithm to use for snapshot key derivation:
+///                              ARGON2I_MOD - derive secured key (used by default)
+///                              ARGON2I_INT - derive secured key (less secured but faster)
+///                              RAW - raw key provided (skip derivation).
+///                                RAW keys can be generated with indy_generate_wallet_key call
+///   }
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// out_cred_id: identifier by which credential is stored in the wallet
+/// rev_reg_snapshot: base64 encoded encrypted snapshot
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_store_credential(command_handle: CommandHandle,
-                                           wallet_handle: WalletHandle,
-                                           cred_id: *const c_char,
-                                           cred_req_metadata_json: *const c_char,
-                                           cred_json: *const c_char,
-                                           cred_def_json: *const c_char,
-                                           rev_reg_def_json: *const c_char,
-                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                out_cred_id: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_store_credential: >>> wallet_handle: {:?}, cred_id: {:?}, cred_req_metadata_json: {:?}, cred_json: {:?}, cred_def_json: {:?}, \
-    cred_def_json: {:?}", wallet_handle, cred_id, cred_req_metadata_json, cred_json, cred_def_json, rev_reg_def_json);
+pub extern fn indy_issuer_export_revocation_registry(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     rev_reg_id: *const c_char,
+                                                     export_config_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          rev_reg_snapshot: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_export_revocation_registry: >>> wallet_handle: {:?}, rev_reg_id: {:?}, export_config_json: {:?}", wallet_handle, rev_reg_id, export_config_json);
 
-    check_useful_opt_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
-    check_useful_validatable_json!(cred_req_metadata_json, ErrorCode::CommonInvalidParam4, CredentialRequestMetadata);
-    check_useful_validatable_json!(cred_json, ErrorCode::CommonInvalidParam5, Credential);
-    check_useful_validatable_json!(cred_def_json, ErrorCode::CommonInvalidParam6, CredentialDefinition);
-    check_useful_opt_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam7, RevocationRegistryDefinition);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
+    check_useful_json!(export_config_json, ErrorCode::CommonInvalidParam4, RevocationRegistryExportConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_prover_store_credential: entities >>> wallet_handle: {:?}, cred_id: {:?}, cred_req_metadata_json: {:?}, cred_json: {:?}, cred_def_json: {:?}, \
-    rev_reg_def_json: {:?}", wallet_handle, cred_id, cred_req_metadata_json, cred_json, cred_def_json, rev_reg_def_json);
+    trace!("indy_issuer_export_revocation_registry: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}, export_config_json: {:?}", wallet_handle, rev_reg_id, secret!(&export_config_json));
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::StoreCredential(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ExportRevocationRegistry(
                     wallet_handle,
-                    cred_id,
-                    cred_req_metadata_json,
-                    cred_json,
-                    cred_def_json,
-                    rev_reg_def_json,
-                    boxed_callback_string!("indy_prover_store_credential", cb, command_handle)
+                    rev_reg_id,
+                    export_config_json,
+                    boxed_callback_string!("indy_issuer_export_revocation_registry", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_store_credential: <<< res: {:?}", res);
+    trace!("indy_issuer_export_revocation_registry: <<< res: {:?}", res);
 
     res
 }
 
-/// Gets human readable credential by the given id.
+/// Restores a revocation registry snapshot created by indy_issuer_export_revocation_registry into the wallet.
+/// Existing records of the same registry are replaced, so issuance continues from the exported state.
+/// Snapshot older than the registry state stored in the wallet is rejected, as restoring it would issue
+/// already used revocation indices again. If the wallet fails to store the snapshot, records of the registry
+/// stored before import are put back.
+/// Fails if the snapshot was modified or the key does not match.
 ///
 /// #Params
+/// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// cred_id: Identifier by which requested credential is stored in the wallet
+/// import_config_json: snapshot decryption settings json
+///   {
+///     "key": <string>, key used for export of the snapshot
+///   }
+/// rev_reg_snapshot: snapshot returned by indy_issuer_export_revocation_registry
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// credential json:
-///     {
-///         "referent": string, - id of credential in the wallet
-///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
-///     }
+/// rev_reg_id: id of the restored revocation registry
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_get_credential(command_handle: CommandHandle,
-                                         wallet_handle: WalletHandle,
-                                         cred_id: *const c_char,
-                                         cb: Option<extern fn(
-                                             command_handle_: CommandHandle, err: ErrorCode,
-                                             credential_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_get_credential: >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+pub extern fn indy_issuer_import_revocation_registry(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     import_config_json: *const c_char,
+                                                     rev_reg_snapshot: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          rev_reg_id: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_import_revocation_registry: >>> wallet_handle: {:?}, import_config_json: {:?}, rev_reg_snapshot: {:?}", wallet_handle, import_config_json, rev_reg_snapshot);
 
-    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+    check_useful_json!(import_config_json, ErrorCode::CommonInvalidParam3, RevocationRegistryExportConfig);
+    check_useful_c_str!(rev_reg_snapshot, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_prover_get_credential: entities >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+    trace!("indy_issuer_import_revocation_registry: entities >>> wallet_handle: {:?}, import_config_json: {:?}, rev_reg_snapshot: {:?}", wallet_handle, secret!(&import_config_json), rev_reg_snapshot);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::GetCredential(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ImportRevocationRegistry(
                     wallet_handle,
-                    cred_id,
-                    boxed_callback_string!("indy_prover_get_credential", cb, command_handle)
+                    import_config_json,
+                    rev_reg_snapshot,
+                    boxed_callback_string!("indy_issuer_import_revocation_registry", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_get_credential: <<< res: {:?}", res);
+    trace!("indy_issuer_import_revocation_registry: <<< res: {:?}", res);
 
     res
 }
 
-/// Deletes credential by given id.
+/// Creates a master secret with a given id and stores it in the wallet.
+/// The id must be unique.
 ///
 /// #Params
+/// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// cred_id: Identifier by which requested credential is stored in the wallet
-/// cb: Callback that takes command result as parameter.
+/// master_secret_id: (optional, if not present random one will be generated) new master id
+///
+/// #Returns
+/// out_master_secret_id: Id of generated master secret
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_delete_credential(command_handle: CommandHandle,
-                                            wallet_handle: WalletHandle,
-                                            cred_id: *const c_char,
-                                            cb: Option<extern fn(
-                                                command_handle_: CommandHandle,
-                                                err: ErrorCode)>) -> ErrorCode {
-    trace!("indy_prover_delete_credential: >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+pub extern fn indy_prover_create_master_secret(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               master_secret_id: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                    out_master_secret_id: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_master_secret: >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
 
-    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_c_str!(master_secret_id, ErrorCode::CommonInvalidParam3);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
+    trace!("indy_prover_create_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::DeleteCredential(
+                ProverCommand::CreateMasterSecret(
                     wallet_handle,
-                    cred_id,
-                    Box::new(move |result| {
-                        let err = prepare_result!(result);
-                        trace!("indy_prover_delete_credential: ");
-                        cb(command_handle, err)
-                    })
+                    master_secret_id,
+                    boxed_callback_string!("indy_prover_create_master_secret", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_delete_credential: <<< res: {:?}", res);
+    trace!("indy_prover_create_master_secret: <<< res: {:?}", res);
 
     res
 }
 
-/// Gets human readable credentials according to the filter.
-/// If filter is NULL, then all credentials are returned.
-/// Credentials can be filtered by Issuer, credential_def and/or Schema.
+/// Creates a credential request for the given credential offer.
 ///
-/// NOTE: This method is deprecated because immediately returns all fetched credentials.
-/// Use <indy_prover_search_credentials> to fetch records by small batches.
+/// The method creates a blinded master secret for a master secret identified by a provided name.
+/// The master secret identified by the name must be already stored in the secure wallet (see prover_create_master_secret)
+/// The blinded master secret is a part of the credential request.
 ///
 /// #Params
-/// wallet_handle: wallet handle (created by open_wallet).
-/// filter_json: filter for credentials
-///        {
-///            "schema_id": string, (Optional)
-///            "schema_issuer_did": string, (Optional)
-///            "schema_name": string, (Optional)
-///            "schema_version": string, (Optional)
-///            "issuer_did": string, (Optional)
-///            "cred_def_id": string, (Optional)
-///        }
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// prover_did: a DID of the prover
+/// cred_offer_json: credential offer as a json containing information about the issuer and a credential
+///     {
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///          ...
+///         Other fields that contains data structures internal to Ursa.
+///         These fields should not be parsed and are likely to change in future versions.
+///     }
+/// cred_def_json: credential definition json related to <cred_def_id> in <cred_offer_json>
+/// master_secret_id: the id of the master secret stored in the wallet
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// credentials json
-///     [{
-///         "referent": string, - id of credential in the wallet
-///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
-///     }]
+/// cred_req_json: Credential request json for creation of credential by Issuer
+///     {
+///      "prover_did" : string,
+///      "cred_def_id" : string,
+///         // Fields below can depend on Cred Def type
+///      "blinded_ms" : <blinded_master_secret>,
+///                     (opaque type that contains data structures internal to Ursa.
+///                      It should not be parsed and are likely to change in future versions).
+///      "blinded_ms_correctness_proof" : <blinded_ms_correctness_proof>,
+///                     (opaque type that contains data structures internal to Ursa.
+///                      It should not be parsed and are likely to change in future versions).
+///      "nonce": string
+///    }
+/// cred_req_metadata_json: Credential request metadata json for further processing of received form Issuer credential.
+///     Credential request metadata contains data structures internal to Ursa.
+///     Credential request metadata mustn't be shared with Issuer.
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-#[deprecated(since = "1.6.1", note = "Please use indy_prover_search_credentials instead!")]
-pub extern fn indy_prover_get_credentials(command_handle: CommandHandle,
-                                          wallet_handle: WalletHandle,
-                                          filter_json: *const c_char,
-                                          cb: Option<extern fn(
-                                              command_handle_: CommandHandle, err: ErrorCode,
-                                              matched_credentials_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_get_credentials: >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
+pub extern fn indy_prover_create_credential_req(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                prover_did: *const c_char,
+                                                cred_offer_json: *const c_char,
+                                                cred_def_json: *const c_char,
+                                                master_secret_id: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                     cred_req_json: *const c_char,
+                                                                     cred_req_metadata_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_credential_req: >>> wallet_handle: {:?}, prover_did: {:?}, cred_offer_json: {:?}, cred_def_json: {:?}, master_secret_id: {:?}",
+           wallet_handle, prover_did, cred_offer_json, cred_def_json, master_secret_id);
 
-    check_useful_opt_c_str!(filter_json, ErrorCode::CommonInvalidParam3);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+    check_useful_validatable_string!(prover_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam4, CredentialOffer);
+    check_useful_validatable_json!(cred_def_json, ErrorCode::CommonInvalidParam5, CredentialDefinition);
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
 
-    trace!("indy_prover_get_credentials: entities >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
+    trace!("indy_prover_create_credential_req: entities >>> wallet_handle: {:?}, prover_did: {:?}, cred_offer_json: {:?}, cred_def_json: {:?}, master_secret_id: {:?}",
+           wallet_handle, prover_did, cred_offer_json, cred_def_json, master_secret_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::GetCredentials(
+                ProverCommand::CreateCredentialRequest(
                     wallet_handle,
-                    filter_json,
-                    boxed_callback_string!("indy_prover_get_credentials", cb, command_handle)
+                    prover_did,
+                    cred_offer_json,
+                    cred_def_json,
+                    master_secret_id,
+                    Box::new(move |result| {
+                        let (err, cred_req_json, cred_req_metadata_json) = prepare_result_2!(result, String::new(), String::new());
+                        trace!("indy_prover_create_credential_req: cred_req_json: {:?}, cred_req_metadata_json: {:?}", cred_req_json, cred_req_metadata_json);
+                        let cred_req_json = ctypes::string_to_cstring(cred_req_json);
+                        let cred_req_metadata_json = ctypes::string_to_cstring(cred_req_metadata_json);
+                        cb(command_handle, err, cred_req_json.as_ptr(), cred_req_metadata_json.as_ptr())
+                    })
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_get_credentials: <<< res: {:?}", res);
+    trace!("indy_prover_create_credential_req: <<< res: {:?}", res);
 
     res
 }
 
-/// Search for credentials stored in wallet.
-/// Credentials can be filtered by tags created during saving of credential.
+/// Creates credential requests for several credential offers at once
+/// the same way as `indy_prover_create_credential_req`.
 ///
-/// Instead of immediately returning of fetched credentials
-/// this call returns search_handle that can be used later
-/// to fetch records by small batches (with indy_prover_fetch_credentials).
+/// All requests are bound to the same master secret which is loaded from the wallet once for the whole batch.
+/// A failure to create a request for one offer doesn't affect other offers:
+/// the result for every offer is returned in the order of offers.
 ///
 /// #Params
-/// wallet_handle: wallet handle (created by open_wallet).
-/// query_json: Wql query filter for credentials searching based on tags.
-///     where query: indy-sdk/docs/design/011-wallet-query-language/README.md
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// prover_did: a DID of the prover
+/// offered_credentials_json: credential offers with related credential definitions
+///     [{
+///         "cred_offer": <credential offer, see indy_prover_create_credential_req>,
+///         "cred_def": <credential definition json related to <cred_def_id> in <cred_offer>>
+///     }]
+/// master_secret_id: the id of the master secret stored in the wallet
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_prover_fetch_credentials)
-/// total_count: Total count of records
+/// cred_reqs_json: results in the order of offers, each is either
+///     {
+///         "cred_req": <credential request json, see indy_prover_create_credential_req>,
+///         "cred_req_metadata": <credential request metadata json, see indy_prover_create_credential_req>
+///     }
+///     or
+///     {
+///         "error": {
+///             "code": int - error code,
+///             "message": string - error message
+///         }
+///     }
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_search_credentials(command_handle: CommandHandle,
-                                             wallet_handle: WalletHandle,
-                                             query_json: *const c_char,
-                                             cb: Option<extern fn(
-                                                 command_handle_: CommandHandle, err: ErrorCode,
-                                                 search_handle: SearchHandle,
-                                                 total_count: usize)>) -> ErrorCode {
-    trace!("indy_prover_search_credentials: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+pub extern fn indy_prover_create_credential_reqs(command_handle: CommandHandle,
+                                                 wallet_handle: WalletHandle,
+                                                 prover_did: *const c_char,
+                                                 offered_credentials_json: *const c_char,
+                                                 master_secret_id: *const c_char,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                      cred_reqs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_credential_reqs: >>> wallet_handle: {:?}, prover_did: {:?}, offered_credentials_json: {:?}, master_secret_id: {:?}",
+           wallet_handle, prover_did, offered_credentials_json, master_secret_id);
 
-    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+    check_useful_validatable_string!(prover_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_validatable_json!(offered_credentials_json, ErrorCode::CommonInvalidParam4, Vec<OfferedCredential>);
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
-    trace!("indy_prover_search_credentials: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+    trace!("indy_prover_create_credential_reqs: entities >>> wallet_handle: {:?}, prover_did: {:?}, offered_credentials_json: {:?}, master_secret_id: {:?}",
+           wallet_handle, prover_did, offered_credentials_json, master_secret_id);
+
+    let offered_credentials = offered_credentials_json
+        .into_iter()
+        .map(|offered_credential| (offered_credential.cred_offer, offered_credential.cred_def))
+        .collect();
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::SearchCredentials(
+                ProverCommand::CreateCredentialRequests(
                     wallet_handle,
-                    query_json,
-                    Box::new(move |result| {
-                        let (err, handle, total_count) = prepare_result_2!(result, 0, 0);
-                        cb(command_handle, err, handle, total_count)
-                    })
+                    prover_did,
+                    offered_credentials,
+                    master_secret_id,
+                    boxed_callback_string!("indy_prover_create_credential_reqs", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_search_credentials: <<< res: {:?}", res);
+    trace!("indy_prover_create_credential_reqs: <<< res: {:?}", res);
 
     res
 }
 
-/// Fetch next credentials for search.
+/// Set credential attribute tagging policy.
+/// Writes a non-secret record marking attributes to tag, and optionally
+/// updates tags on existing credentials on the credential definition to match.
 ///
-/// #Params
-/// search_handle: Search handle (created by indy_prover_search_credentials)
-/// count: Count of credentials to fetch
-/// cb: Callback that takes command result as parameter.
+/// EXPERIMENTAL
 ///
-/// #Returns
-/// credentials_json: List of human readable credentials:
-///     [{
-///         "referent": string, - id of credential in the wallet
-///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
-///     }]
-/// NOTE: The list of length less than the requested count means credentials search iterator is completed.
+/// The following tags are always present on write:
+///     {
+///         "schema_id": <credential schema id>,
+///         "schema_issuer_did": <credential schema issuer did>,
+///         "schema_name": <credential schema name>,
+///         "schema_version": <credential schema version>,
+///         "issuer_did": <credential issuer did>,
+///         "cred_def_id": <credential definition id>,
+///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///         "tags_version": "1", // version of this tags schema
+///     }
 ///
-/// #Errors
-/// Anoncreds*
-/// Common*
-/// Wallet*
-#[no_mangle]
-pub  extern fn indy_prover_fetch_credentials(command_handle: CommandHandle,
-                                             search_handle: SearchHandle,
-                                             count: usize,
-                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                  credentials_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_fetch_credentials: >>> search_handle: {:?}, count: {:?}", search_handle, count);
-
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
-
-    trace!("indy_prover_fetch_credentials: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
-
-    let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::FetchCredentials(
-                    search_handle,
-                    count,
-                    boxed_callback_string!("indy_prover_fetch_credentials", cb, command_handle)
-                ))));
-
-    let res = prepare_result!(result);
-
-    trace!("indy_prover_fetch_credentials: <<< res: {:?}", res);
-
-    res
-}
-
-/// Close credentials search (make search handle invalid)
+/// The policy sets the following tags for each attribute it marks taggable, written to subsequent
+/// credentials and (optionally) all existing credentials on the credential definition:
+///     {
+///         "attr::<attribute name>::marker": "1",
+///         "attr::<attribute name>::value": <attribute raw value>,
+///         // unencrypted, for 32-bit integer values of attributes the policy marks range queryable
+///         "~attr::<attribute name>::value": <attribute raw value shifted by 2^31 and padded to 10 digits>,
+///     }
 ///
 /// #Params
-/// search_handle: Search handle (created by indy_prover_search_credentials)
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_def_id: credential definition id
+/// tag_attrs_json: JSON array with names of attributes to tag by policy, or null for all,
+///     or object to also enable range queries on integer attributes:
+///     {
+///         "taggable": [string, ...], // names of attributes to tag by policy
+///         "range_queryable": [string, ...], // taggable attributes whose integer values are also tagged unencrypted
+///                                           // so `$gt`, `$gte`, `$lt` and `$lte` on "attr::<attribute name>::value" can be used
+///     }
+///     Credentials stored before range queries were opt-in carry the unencrypted tag for every integer attribute;
+///     call this function with retroactive set to true to rewrite their tags according to the policy.
+/// retroactive: boolean, whether to apply policy to existing credentials on credential definition identifier
+/// cb: Callback that takes command result as parameter.
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandle,
-                                                    search_handle: SearchHandle,
-                                                    cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
-    trace!("indy_prover_close_credentials_search: >>> search_handle: {:?}", search_handle);
+pub extern fn indy_prover_set_credential_attr_tag_policy(command_handle: CommandHandle,
+                                                         wallet_handle: WalletHandle,
+                                                         cred_def_id: *const c_char,
+                                                         tag_attrs_json: *const c_char,
+                                                         retroactive: bool,
+                                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_set_credential_attr_tag_policy: >>> wallet_handle: {:?}, cred_def_id: {:?}, tag_attrs_json: {:?}, retroactive: {:?}", wallet_handle, cred_def_id, tag_attrs_json, retroactive);
 
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_opt_json!(tag_attrs_json, ErrorCode::CommonInvalidParam4, CredentialAttrTagPolicy);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
-    trace!("indy_prover_close_credentials_search: entities >>> search_handle: {:?}", search_handle);
+    trace!("indy_prover_set_credential_attr_tag_policy: entities >>> wallet_handle: {:?}, cred_def_id: {:?}, tag_attrs_json: {:?}, retroactive: {:?}",
+           wallet_handle, cred_def_id, tag_attrs_json, retroactive);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::CloseCredentialsSearch(
-                    search_handle,
+                ProverCommand::SetCredentialAttrTagPolicy(
+                    wallet_handle,
+                    cred_def_id,
+                    tag_attrs_json,
+                    retroactive,
                     Box::new(move |result| {
                         let err = prepare_result!(result);
-                        trace!("indy_prover_close_credentials_search:");
+                        trace!("indy_prover_set_credential_attr_tag_policy: ");
                         cb(command_handle, err)
                     })
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_close_credentials_search: <<< res: {:?}", res);
+    trace!("indy_prover_set_credential_attr_tag_policy: <<< res: {:?}", res);
 
     res
 }
 
-/// Gets human readable credentials matching the given proof request.
+/// Get credential attribute tagging policy by credential definition id.
 ///
-/// NOTE: This method is deprecated because immediately returns all fetched credentials.
-/// Use <indy_prover_search_credentials_for_proof_req> to fetch records by small batches.
+/// EXPERIMENTAL
 ///
 /// #Params
+/// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// proof_request_json: proof request json
-///     {
-///         "name": string,
-///         "version": string,
-///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
-///         "requested_attributes": { // set of requested attributes
-///              "<attr_referent>": <attr_info>, // see below
-///              ...,
-///         },
-///         "requested_predicates": { // set of requested predicates
-///              "<predicate_referent>": <predicate_info>, // see below
-///              ...,
-///          },
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval for each attribute
-///                        // (applies to every attribute and predicate but can be overridden on attribute level),
-///         "ver": Optional<str>  - proof request version:
-///             - omit or "1.0" to use unqualified identifiers for restrictions
-///             - "2.0" to use fully qualified identifiers for restrictions
-///     }
+/// cred_def_id: credential definition id
 /// cb: Callback that takes command result as parameter.
 ///
-/// where
-/// attr_referent: Proof-request local identifier of requested attribute
-/// attr_info: Describes requested attribute
-///     {
-///         "name": string, // attribute name, (case insensitive and ignore spaces)
-///         "restrictions": Optional<filter_json>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
-///     }
-/// predicate_referent: Proof-request local identifier of requested attribute predicate
-/// predicate_info: Describes requested attribute predicate
-///     {
-///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": int predicate value
-///         "restrictions": Optional<filter_json>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
-///     }
-/// non_revoc_interval: Defines non-revocation interval
-///     {
-///         "from": Optional<int>, // timestamp of interval beginning
-///         "to": Optional<int>, // timestamp of interval ending
-///     }
-///  filter_json:
-///     {
-///        "schema_id": string, (Optional)
-///        "schema_issuer_did": string, (Optional)
-///        "schema_name": string, (Optional)
-///        "schema_version": string, (Optional)
-///        "issuer_did": string, (Optional)
-///        "cred_def_id": string, (Optional)
-///     }
-///
 /// #Returns
-/// credentials_json: json with credentials for the given proof request.
-///     {
-///         "attrs": {
-///             "<attr_referent>": [{ cred_info: <credential_info>, interval: Optional<non_revoc_interval> }],
-///             ...,
-///         },
-///         "predicates": {
-///             "requested_predicates": [{ cred_info: <credential_info>, timestamp: Optional<integer> }, { cred_info: <credential_2_info>, timestamp: Optional<integer> }],
-///             "requested_predicate_2_referent": [{ cred_info: <credential_2_info>, timestamp: Optional<integer> }]
-///         }
-///     }, where <credential_info> is
-///     {
-///         "referent": string, - id of credential in the wallet
-///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
-///     }
-///
+/// JSON array with all attributes that current policy marks taggable;
+/// null for default policy (tag all credential attributes).
+/// 
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
-#[deprecated(since = "1.6.1", note = "Please use indy_prover_search_credentials_for_proof_req instead!")]
 #[no_mangle]
-pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandHandle,
-                                                        wallet_handle: WalletHandle,
-                                                        proof_request_json: *const c_char,
-                                                        cb: Option<extern fn(
-                                                            command_handle_: CommandHandle, err: ErrorCode,
-                                                            credentials_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_get_credentials_for_proof_req: >>> wallet_handle: {:?}, proof_request_json: {:?}", wallet_handle, proof_request_json);
+pub extern fn indy_prover_get_credential_attr_tag_policy(command_handle: CommandHandle,
+                                                         wallet_handle: WalletHandle,
+                                                         cred_def_id: *const c_char,
+                                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                              err: ErrorCode,
+                                                                              catpol_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_credential_attr_tag_policy: >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
 
-    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_get_credentials_for_proof_req: entities >>> wallet_handle: {:?}, proof_request_json: {:?}",
-           wallet_handle, proof_request_json);
+    trace!("indy_prover_get_credential_attr_tag_policy: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::GetCredentialsForProofReq(
+                ProverCommand::GetCredentialAttrTagPolicy(
                     wallet_handle,
-                    proof_request_json,
-                    boxed_callback_string!("indy_prover_get_credentials_for_proof_req", cb, command_handle)
+                    cred_def_id,
+                    boxed_callback_string!("indy_prover_get_credential_attr_tag_policy", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_get_credentials_for_proof_req: <<< res: {:?}", res);
+    trace!("indy_prover_get_credential_attr_tag_policy: <<< res: {:?}", res);
 
     res
 }
 
-/// Search for credentials matching the given proof request.
-///
-/// Instead of immediately returning of fetched credentials
-/// this call returns search_handle that can be used later
-/// to fetch records by small batches (with indy_prover_fetch_credentials_for_proof_req).
+/// Check credential provided by Issuer for the given credential request,
+/// updates the credential by a master secret and stores in a secure wallet.
 ///
-/// #Params
-/// wallet_handle: wallet handle (created by open_wallet).
-/// proof_request_json: proof request json
-///     {
-///         "name": string,
-///         "version": string,
-///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
-///         "requested_attributes": { // set of requested attributes
-///              "<attr_referent>": <attr_info>, // see below
-///              ...,
-///         },
-///         "requested_predicates": { // set of requested predicates
-///              "<predicate_referent>": <predicate_info>, // see below
-///              ...,
-///          },
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval for each attribute
-///                        // (applies to every attribute and predicate but can be overridden on attribute level)
-///                        // (can be overridden on attribute level)
-///         "ver": Optional<str>  - proof request version:
-///             - omit or "1.0" to use unqualified identifiers for restrictions
-///             - "2.0" to use fully qualified identifiers for restrictions
-///     }
-///
-/// where
-/// attr_info: Describes requested attribute
-///     {
-///         "name": string, // attribute name, (case insensitive and ignore spaces)
-///         "restrictions": Optional<wql query>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
-///     }
-/// predicate_referent: Proof-request local identifier of requested attribute predicate
-/// predicate_info: Describes requested attribute predicate
-///     {
-///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
-///         "restrictions": Optional<wql query>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
-///     }
-/// non_revoc_interval: Defines non-revocation interval
-///     {
-///         "from": Optional<int>, // timestamp of interval beginning
-///         "to": Optional<int>, // timestamp of interval ending
-///     }
-/// extra_query_json:(Optional) List of extra queries that will be applied to correspondent attribute/predicate:
+/// To support efficient and flexible search the following tags will be created for stored credential:
 ///     {
-///         "<attr_referent>": <wql query>,
-///         "<predicate_referent>": <wql query>,
-///     }
-/// where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
-///     The list of allowed fields:
 ///         "schema_id": <credential schema id>,
 ///         "schema_issuer_did": <credential schema issuer did>,
 ///         "schema_name": <credential schema name>,
@@ -1688,206 +1954,1061 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 ///         "issuer_did": <credential issuer did>,
 ///         "cred_def_id": <credential definition id>,
 ///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///         "tags_version": "1", // version of this tags schema
+///         // for every attribute in <credential values> that credential attribute tagging policy marks taggable
+///         "attr::<attribute name>::marker": "1",
+///         "attr::<attribute name>::value": <attribute raw value>,
+///         // unencrypted, for 32-bit integer values of attributes the policy marks range queryable
+///         "~attr::<attribute name>::value": <attribute raw value shifted by 2^31 and padded to 10 digits>,
+///     }
 ///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_id: (optional, default is a random one) identifier by which credential will be stored in the wallet
+/// cred_req_metadata_json: a credential request metadata created by indy_prover_create_credential_req
+/// cred_json: credential json received from issuer
+///     {
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_def_id", Optional<string>, - identifier of revocation registry
+///         "values": - credential values
+///             {
+///                 "attr1" : {"raw": "value1", "encoded": "value1_as_int" },
+///                 "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
+///             }
+///         // Fields below can depend on Cred Def type
+///         Other fields that contains data structures internal to Ursa.
+///         These fields should not be parsed and are likely to change in future versions.
+///     }
+/// cred_def_json: credential definition json related to <cred_def_id> in <cred_json>
+/// rev_reg_def_json: revocation registry definition json related to <rev_reg_def_id> in <cred_json>
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_prover_fetch_credentials_for_proof_req)
+/// out_cred_id: identifier by which credential is stored in the wallet
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_search_credentials_for_proof_req(command_handle: CommandHandle,
-                                                           wallet_handle: WalletHandle,
-                                                           proof_request_json: *const c_char,
-                                                           extra_query_json: *const c_char,
-                                                           cb: Option<extern fn(
-                                                               command_handle_: CommandHandle, err: ErrorCode,
-                                                               search_handle: SearchHandle)>) -> ErrorCode {
-    trace!("indy_prover_search_credentials_for_proof_req: >>> wallet_handle: {:?}, proof_request_json: {:?}, extra_query_json: {:?}", wallet_handle, proof_request_json, extra_query_json);
+pub extern fn indy_prover_store_credential(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           cred_id: *const c_char,
+                                           cred_req_metadata_json: *const c_char,
+                                           cred_json: *const c_char,
+                                           cred_def_json: *const c_char,
+                                           rev_reg_def_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                out_cred_id: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_store_credential: >>> wallet_handle: {:?}, cred_id: {:?}, cred_req_metadata_json: {:?}, cred_json: {:?}, cred_def_json: {:?}, \
+    cred_def_json: {:?}", wallet_handle, cred_id, cred_req_metadata_json, cred_json, cred_def_json, rev_reg_def_json);
 
-    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
-    check_useful_opt_json!(extra_query_json, ErrorCode::CommonInvalidParam4, ProofRequestExtraQuery);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_json!(cred_req_metadata_json, ErrorCode::CommonInvalidParam4, CredentialRequestMetadata);
+    check_useful_validatable_json!(cred_json, ErrorCode::CommonInvalidParam5, Credential);
+    check_useful_validatable_json!(cred_def_json, ErrorCode::CommonInvalidParam6, CredentialDefinition);
+    check_useful_opt_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam7, RevocationRegistryDefinition);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
 
-    trace!("indy_prover_search_credentials_for_proof_req: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, extra_query_json: {:?}",
-           wallet_handle, proof_request_json, extra_query_json);
+    trace!("indy_prover_store_credential: entities >>> wallet_handle: {:?}, cred_id: {:?}, cred_req_metadata_json: {:?}, cred_json: {:?}, cred_def_json: {:?}, \
+    rev_reg_def_json: {:?}", wallet_handle, cred_id, cred_req_metadata_json, sensitive!(&cred_json), cred_def_json, rev_reg_def_json);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::SearchCredentialsForProofReq(
+                ProverCommand::StoreCredential(
                     wallet_handle,
-                    proof_request_json,
-                    extra_query_json,
-                    Box::new(move |result| {
-                        let (err, search_handle) = prepare_result_1!(result, 0);
-                        trace!("indy_prover_search_credentials_for_proof_req: search_handle: {:?}", search_handle);
-                        cb(command_handle, err, search_handle)
-                    }),
+                    cred_id,
+                    cred_req_metadata_json,
+                    cred_json,
+                    cred_def_json,
+                    rev_reg_def_json,
+                    boxed_callback_string!("indy_prover_store_credential", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_search_credentials_for_proof_req: <<< res: {:?}", res);
+    trace!("indy_prover_store_credential: <<< res: {:?}", res);
 
     res
 }
 
-/// Fetch next credentials for the requested item using proof request search
-/// handle (created by indy_prover_search_credentials_for_proof_req).
+/// Gets human readable credential by the given id.
 ///
 /// #Params
-/// search_handle: Search handle (created by indy_prover_search_credentials_for_proof_req)
-/// item_referent: Referent of attribute/predicate in the proof request
-/// count: Count of credentials to fetch
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_id: Identifier by which requested credential is stored in the wallet
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// credentials_json: List of credentials for the given proof request.
-///     [{
-///         cred_info: <credential_info>,
-///         interval: Optional<non_revoc_interval>
-///     }]
-/// where
-/// credential_info:
+/// credential json:
 ///     {
 ///         "referent": string, - id of credential in the wallet
 ///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
 ///         "schema_id": string, - identifier of schema
 ///         "cred_def_id": string, - identifier of credential definition
 ///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
-///     }
-/// non_revoc_interval:
-///     {
-///         "from": Optional<int>, // timestamp of interval beginning
-///         "to": Optional<int>, // timestamp of interval ending
+///         "cred_rev_id": Optional<string>, - identifier of credential in the revocation registry definition
+///         "attribute_names": [string] - sorted schema attribute names in canonical form (lowercase, without spaces)
 ///     }
-/// NOTE: The list of length less than the requested count means that search iterator
-/// correspondent to the requested <item_referent> is completed.
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub  extern fn indy_prover_fetch_credentials_for_proof_req(command_handle: CommandHandle,
-                                                           search_handle: SearchHandle,
-                                                           item_referent: *const c_char,
-                                                           count: usize,
-                                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                                credentials_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_fetch_credentials_for_proof_req: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+pub extern fn indy_prover_get_credential(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         cred_id: *const c_char,
+                                         cb: Option<extern fn(
+                                             command_handle_: CommandHandle, err: ErrorCode,
+                                             credential_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_credential: >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
 
-    check_useful_c_str!(item_referent, ErrorCode::CommonInvalidParam4);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_fetch_credentials_for_proof_req: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+    trace!("indy_prover_get_credential: entities >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::FetchCredentialForProofReq(
-                    search_handle,
-                    item_referent,
-                    count,
-                    boxed_callback_string!("indy_prover_fetch_credentials_for_proof_request", cb, command_handle)
+                ProverCommand::GetCredential(
+                    wallet_handle,
+                    cred_id,
+                    boxed_callback_sensitive_string!("indy_prover_get_credential", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_fetch_credentials_for_proof_req: <<< res: {:?}", res);
+    trace!("indy_prover_get_credential: <<< res: {:?}", res);
 
     res
 }
 
-/// Close credentials search for proof request (make search handle invalid)
+/// Gets a stable fingerprint of the credential stored by the given id.
+///
+/// The fingerprint is a hex encoded SHA-256 of the credential identifiers, values and primary signature.
+/// Revocation witness and registry state are not included, so the same credential stored again
+/// or updated keeps its fingerprint. Wallets can use it to find duplicates and as a UI key.
 ///
 /// #Params
-/// search_handle: Search handle (created by indy_prover_search_credentials_for_proof_req)
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_id: Identifier by which requested credential is stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// fingerprint: hex encoded fingerprint of the credential
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
-                                                                  search_handle: SearchHandle,
-                                                                  cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
-    trace!("indy_prover_close_credentials_search_for_proof_req: >>> search_handle: {:?}", search_handle);
+pub extern fn indy_prover_get_credential_fingerprint(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     cred_id: *const c_char,
+                                                     cb: Option<extern fn(
+                                                         command_handle_: CommandHandle, err: ErrorCode,
+                                                         fingerprint: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_credential_fingerprint: >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
 
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_close_credentials_search_for_proof_req: entities >>> search_handle: {:?}", search_handle);
+    trace!("indy_prover_get_credential_fingerprint: entities >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::CloseCredentialsSearchForProofReq(
-                    search_handle,
-                    Box::new(move |result| {
-                        let err = prepare_result!(result);
-                        trace!("indy_prover_close_credentials_search:");
-                        cb(command_handle, err)
-                    }),
+                ProverCommand::GetCredentialFingerprint(
+                    wallet_handle,
+                    cred_id,
+                    boxed_callback_string!("indy_prover_get_credential_fingerprint", cb, command_handle)
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_close_credentials_search_for_proof_req: <<< res: {:?}", res);
+    trace!("indy_prover_get_credential_fingerprint: <<< res: {:?}", res);
 
     res
 }
 
-/// Creates a proof according to the given proof request
-/// Either a corresponding credential with optionally revealed attributes or self-attested attribute must be provided
-/// for each requested attribute (see indy_prover_get_credentials_for_pool_req).
-/// A proof request may request multiple credentials from different schemas and different issuers.
-/// All required schemas, public keys and revocation registries must be provided.
-/// The proof request also contains nonce.
-/// The proof contains either proof or self-attested attribute value for each requested attribute.
+/// Deletes credential by given id.
 ///
 /// #Params
 /// wallet_handle: wallet handle (created by open_wallet).
-/// command_handle: command handle to map callback to user context.
-/// proof_request_json: proof request json
-///     {
-///         "name": string,
-///         "version": string,
-///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
-///         "requested_attributes": { // set of requested attributes
-///              "<attr_referent>": <attr_info>, // see below
-///              ...,
-///         },
-///         "requested_predicates": { // set of requested predicates
-///              "<predicate_referent>": <predicate_info>, // see below
-///              ...,
-///          },
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval for each attribute
-///                        // (applies to every attribute and predicate but can be overridden on attribute level)
-///                        // (can be overridden on attribute level)
-///         "ver": Optional<str>  - proof request version:
-///             - omit or "1.0" to use unqualified identifiers for restrictions
-///             - "2.0" to use fully qualified identifiers for restrictions
-///     }
-/// requested_credentials_json: either a credential or self-attested attribute for each requested attribute
-///     {
-///         "self_attested_attributes": {
-///             "self_attested_attribute_referent": string
-///         },
-///         "requested_attributes": {
-///             "requested_attribute_referent_1": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }},
-///             "requested_attribute_referent_2": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }}
-///         },
-///         "requested_predicates": {
-///             "requested_predicates_referent_1": {"cred_id": string, "timestamp": Optional<number> }},
-///         }
-///     }
+/// cred_id: Identifier by which requested credential is stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_delete_credential(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            cred_id: *const c_char,
+                                            cb: Option<extern fn(
+                                                command_handle_: CommandHandle,
+                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_delete_credential: >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+
+    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::DeleteCredential(
+                    wallet_handle,
+                    cred_id,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_prover_delete_credential: ");
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_delete_credential: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets human readable credentials according to the filter.
+/// If filter is NULL, then all credentials are returned.
+/// Credentials can be filtered by Issuer, credential_def and/or Schema.
+///
+/// NOTE: This method is deprecated because immediately returns all fetched credentials.
+/// Use <indy_prover_search_credentials> to fetch records by small batches.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// filter_json: filter for credentials
+///        {
+///            "schema_id": string, (Optional)
+///            "schema_issuer_did": string, (Optional)
+///            "schema_name": string, (Optional)
+///            "schema_version": string, (Optional)
+///            "schema_version_range": string, (Optional) comma separated bounds like ">=1.0,<2.0"
+///            "issuer_did": string, (Optional)
+///            "cred_def_id": string, (Optional)
+///        }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// credentials json
+///     [{
+///         "referent": string, - id of credential in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
+///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///     }]
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+#[deprecated(since = "1.6.1", note = "Please use indy_prover_search_credentials instead!")]
+pub extern fn indy_prover_get_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          filter_json: *const c_char,
+                                          cb: Option<extern fn(
+                                              command_handle_: CommandHandle, err: ErrorCode,
+                                              matched_credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_credentials: >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
+
+    check_useful_opt_c_str!(filter_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_get_credentials: entities >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::GetCredentials(
+                    wallet_handle,
+                    filter_json,
+                    boxed_callback_sensitive_string!("indy_prover_get_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_get_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Search for credentials stored in wallet.
+/// Credentials can be filtered by tags created during saving of credential.
+///
+/// Instead of immediately returning of fetched credentials
+/// this call returns search_handle that can be used later
+/// to fetch records by small batches (with indy_prover_fetch_credentials).
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// query_json: Wql query filter for credentials searching based on tags.
+///     where query: indy-sdk/docs/design/011-wallet-query-language/README.md
+///     `$gt`, `$gte`, `$lt` and `$lte` on "attr::<attribute name>::value" compare 32-bit integer values
+///     and match only attributes the credential attribute tagging policy marks range queryable
+///     "schema_version_range" with comma separated bounds like ">=1.0,<2.0" matches any schema version in the range
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_prover_fetch_credentials)
+/// total_count: Total count of records
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_search_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             query_json: *const c_char,
+                                             cb: Option<extern fn(
+                                                 command_handle_: CommandHandle, err: ErrorCode,
+                                                 search_handle: SearchHandle,
+                                                 total_count: usize)>) -> ErrorCode {
+    trace!("indy_prover_search_credentials: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_search_credentials: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::SearchCredentials(
+                    wallet_handle,
+                    query_json,
+                    Box::new(move |result| {
+                        let (err, handle, total_count) = prepare_result_2!(result, 0, 0);
+                        cb(command_handle, err, handle, total_count)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_search_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetch next credentials for search.
+///
+/// #Params
+/// search_handle: Search handle (created by indy_prover_search_credentials)
+/// count: Count of credentials to fetch
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// credentials_json: List of human readable credentials:
+///     [{
+///         "referent": string, - id of credential in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
+///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///     }]
+/// NOTE: The list of length less than the requested count means credentials search iterator is completed.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_prover_fetch_credentials(command_handle: CommandHandle,
+                                             search_handle: SearchHandle,
+                                             count: usize,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_fetch_credentials: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_fetch_credentials: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::FetchCredentials(
+                    search_handle,
+                    count,
+                    boxed_callback_sensitive_string!("indy_prover_fetch_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_fetch_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close credentials search (make search handle invalid)
+///
+/// #Params
+/// search_handle: Search handle (created by indy_prover_search_credentials)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandle,
+                                                    search_handle: SearchHandle,
+                                                    cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_close_credentials_search: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_close_credentials_search: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::CloseCredentialsSearch(
+                    search_handle,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_prover_close_credentials_search:");
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_close_credentials_search: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets human readable credentials matching the given proof request.
+///
+/// NOTE: This method is deprecated because immediately returns all fetched credentials.
+/// Use <indy_prover_search_credentials_for_proof_req> to fetch records by small batches.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json
+///     {
+///         "name": string,
+///         "version": string,
+///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
+///         "requested_attributes": { // set of requested attributes
+///              "<attr_referent>": <attr_info>, // see below
+///              ...,
+///         },
+///         "requested_predicates": { // set of requested predicates
+///              "<predicate_referent>": <predicate_info>, // see below
+///              ...,
+///          },
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval for each attribute
+///                        // (applies to every attribute and predicate but can be overridden on attribute level),
+///         "same_issuer": Optional<[[<referent>, ...], ...]>, // groups of attribute and predicate referents
+///                        // which must be proven by credentials signed with the same issuer key
+///                        // (credential definitions with the same primary public key modulus `n`),
+///         "linked_reveal": Optional<{"<group_name>": [<attr_referent>, ...], ...}>, // named groups of attribute referents
+///                        // which must be revealed together from the same credential or not revealed at all,
+///         "allowed_values": Optional<{"<attr_referent>": [string, ...], ...}>, // public lists of values, the attribute
+///                        // must be revealed from a credential with one of the listed raw values,
+///         "ver": Optional<str>  - proof request version:
+///             - omit or "1.0" to use unqualified identifiers for restrictions
+///             - "2.0" to use fully qualified identifiers for restrictions
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// where
+/// attr_referent: Proof-request local identifier of requested attribute
+/// attr_info: Describes requested attribute
+///     {
+///         "name": string, // attribute name, (case insensitive and ignore spaces)
+///         "restrictions": Optional<filter_json>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// predicate_referent: Proof-request local identifier of requested attribute predicate
+/// predicate_info: Describes requested attribute predicate
+///     {
+///         "name": attribute name, (case insensitive and ignore spaces)
+///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_value": int predicate value
+///         "restrictions": Optional<filter_json>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// non_revoc_interval: Defines non-revocation interval
+///     {
+///         "from": Optional<int>, // timestamp of interval beginning
+///         "to": Optional<int>, // timestamp of interval ending
+///     }
+///  filter_json:
+///     {
+///        "schema_id": string, (Optional)
+///        "schema_issuer_did": string, (Optional)
+///        "schema_name": string, (Optional)
+///        "schema_version": string, (Optional)
+///        "issuer_did": string, (Optional)
+///        "cred_def_id": string, (Optional)
+///     }
+///
+/// #Returns
+/// credentials_json: json with credentials for the given proof request.
+///     {
+///         "attrs": {
+///             "<attr_referent>": [{ cred_info: <credential_info>, interval: Optional<non_revoc_interval> }],
+///             ...,
+///         },
+///         "predicates": {
+///             "requested_predicates": [{ cred_info: <credential_info>, timestamp: Optional<integer> }, { cred_info: <credential_2_info>, timestamp: Optional<integer> }],
+///             "requested_predicate_2_referent": [{ cred_info: <credential_2_info>, timestamp: Optional<integer> }]
+///         }
+///     }, where <credential_info> is
+///     {
+///         "referent": string, - id of credential in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
+///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[deprecated(since = "1.6.1", note = "Please use indy_prover_search_credentials_for_proof_req instead!")]
+#[no_mangle]
+pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandHandle,
+                                                        wallet_handle: WalletHandle,
+                                                        proof_request_json: *const c_char,
+                                                        cb: Option<extern fn(
+                                                            command_handle_: CommandHandle, err: ErrorCode,
+                                                            credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_credentials_for_proof_req: >>> wallet_handle: {:?}, proof_request_json: {:?}", wallet_handle, proof_request_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_get_credentials_for_proof_req: entities >>> wallet_handle: {:?}, proof_request_json: {:?}",
+           wallet_handle, proof_request_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::GetCredentialsForProofReq(
+                    wallet_handle,
+                    proof_request_json,
+                    boxed_callback_sensitive_string!("indy_prover_get_credentials_for_proof_req", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_get_credentials_for_proof_req: <<< res: {:?}", res);
+
+    res
+}
+
+/// Search for credentials matching the given proof request.
+///
+/// Instead of immediately returning of fetched credentials
+/// this call returns search_handle that can be used later
+/// to fetch records by small batches (with indy_prover_fetch_credentials_for_proof_req).
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json
+///     {
+///         "name": string,
+///         "version": string,
+///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
+///         "requested_attributes": { // set of requested attributes
+///              "<attr_referent>": <attr_info>, // see below
+///              ...,
+///         },
+///         "requested_predicates": { // set of requested predicates
+///              "<predicate_referent>": <predicate_info>, // see below
+///              ...,
+///          },
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval for each attribute
+///                        // (applies to every attribute and predicate but can be overridden on attribute level)
+///                        // (can be overridden on attribute level)
+///         "allowed_values": Optional<{"<attr_referent>": [string, ...], ...}>, // public lists of values, the attribute
+///                        // must be revealed from a credential with one of the listed raw values,
+///         "ver": Optional<str>  - proof request version:
+///             - omit or "1.0" to use unqualified identifiers for restrictions
+///             - "2.0" to use fully qualified identifiers for restrictions
+///     }
+///
+/// where
+/// attr_info: Describes requested attribute
+///     {
+///         "name": string, // attribute name, (case insensitive and ignore spaces)
+///         "restrictions": Optional<wql query>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// predicate_referent: Proof-request local identifier of requested attribute predicate
+/// predicate_info: Describes requested attribute predicate
+///     {
+///         "name": attribute name, (case insensitive and ignore spaces)
+///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_value": predicate value
+///         "p_value_max": Optional<int>, // exclusive upper bound making a range predicate
+///                                       // `p_value <= value < p_value_max` (or `p_value < value` for ">"),
+///                                       // `p_type` must be ">=" or ">" and `p_value` less than `p_value_max`
+///         "scale": Optional<int>, // decimal places of attribute value stored in minor units,
+///                                 // `p_value` is multiplied by 10^scale before comparison
+///         "restrictions": Optional<wql query>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// non_revoc_interval: Defines non-revocation interval
+///     {
+///         "from": Optional<int>, // timestamp of interval beginning
+///         "to": Optional<int>, // timestamp of interval ending
+///     }
+/// extra_query_json:(Optional) List of extra queries that will be applied to correspondent attribute/predicate:
+///     {
+///         "<attr_referent>": <wql query>,
+///         "<predicate_referent>": <wql query>,
+///     }
+/// where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
+///     The list of allowed fields:
+///         "schema_id": <credential schema id>,
+///         "schema_issuer_did": <credential schema issuer did>,
+///         "schema_name": <credential schema name>,
+///         "schema_version": <credential schema version>,
+///         "schema_version_range": <comma separated bounds like ">=1.0,<2.0">, // matches any schema version in the range
+///         "issuer_did": <credential issuer did>,
+///         "cred_def_id": <credential definition id>,
+///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_prover_fetch_credentials_for_proof_req)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_search_credentials_for_proof_req(command_handle: CommandHandle,
+                                                           wallet_handle: WalletHandle,
+                                                           proof_request_json: *const c_char,
+                                                           extra_query_json: *const c_char,
+                                                           cb: Option<extern fn(
+                                                               command_handle_: CommandHandle, err: ErrorCode,
+                                                               search_handle: SearchHandle)>) -> ErrorCode {
+    trace!("indy_prover_search_credentials_for_proof_req: >>> wallet_handle: {:?}, proof_request_json: {:?}, extra_query_json: {:?}", wallet_handle, proof_request_json, extra_query_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_opt_json!(extra_query_json, ErrorCode::CommonInvalidParam4, ProofRequestExtraQuery);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_search_credentials_for_proof_req: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, extra_query_json: {:?}",
+           wallet_handle, proof_request_json, extra_query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::SearchCredentialsForProofReq(
+                    wallet_handle,
+                    proof_request_json,
+                    extra_query_json,
+                    Box::new(move |result| {
+                        let (err, search_handle) = prepare_result_1!(result, 0);
+                        trace!("indy_prover_search_credentials_for_proof_req: search_handle: {:?}", search_handle);
+                        cb(command_handle, err, search_handle)
+                    }),
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_search_credentials_for_proof_req: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetch next credentials for the requested item using proof request search
+/// handle (created by indy_prover_search_credentials_for_proof_req).
+///
+/// #Params
+/// search_handle: Search handle (created by indy_prover_search_credentials_for_proof_req)
+/// item_referent: Referent of attribute/predicate in the proof request
+/// count: Count of credentials to fetch
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// credentials_json: List of credentials for the given proof request.
+///     [{
+///         cred_info: <credential_info>,
+///         interval: Optional<non_revoc_interval>
+///     }]
+/// where
+/// credential_info:
+///     {
+///         "referent": string, - id of credential in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
+///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///     }
+/// non_revoc_interval:
+///     {
+///         "from": Optional<int>, // timestamp of interval beginning
+///         "to": Optional<int>, // timestamp of interval ending
+///     }
+/// NOTE: The list of length less than the requested count means that search iterator
+/// correspondent to the requested <item_referent> is completed.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_prover_fetch_credentials_for_proof_req(command_handle: CommandHandle,
+                                                           search_handle: SearchHandle,
+                                                           item_referent: *const c_char,
+                                                           count: usize,
+                                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_fetch_credentials_for_proof_req: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    check_useful_c_str!(item_referent, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_fetch_credentials_for_proof_req: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::FetchCredentialForProofReq(
+                    search_handle,
+                    item_referent,
+                    count,
+                    boxed_callback_sensitive_string!("indy_prover_fetch_credentials_for_proof_request", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_fetch_credentials_for_proof_req: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close credentials search for proof request (make search handle invalid)
+///
+/// #Params
+/// search_handle: Search handle (created by indy_prover_search_credentials_for_proof_req)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
+                                                                  search_handle: SearchHandle,
+                                                                  cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_close_credentials_search_for_proof_req: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_close_credentials_search_for_proof_req: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::CloseCredentialsSearchForProofReq(
+                    search_handle,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_prover_close_credentials_search:");
+                        cb(command_handle, err)
+                    }),
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_close_credentials_search_for_proof_req: <<< res: {:?}", res);
+
+    res
+}
+
+/// Explains why the given proof request can't be satisfied by credentials stored in the wallet.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json (see indy_prover_search_credentials_for_proof_req)
+/// rev_reg_deltas_json: revocation registry deltas known to the prover, used for referents requesting non-revocation
+///     {
+///         "<rev_reg_id>": <revocation registry delta json> or [<revocation registry delta json>, ...] in ledger order,
+///         ...
+///     }
+///     Pass "{}" if revocation status is unknown.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// explanation_json: reasons for every requested attribute and predicate that no stored credential can prove
+///     {
+///         "attrs": {
+///             "<attr_referent>": string, // e.g. "no credential contains attribute \"name\""
+///             ...,
+///         },
+///         "predicates": {
+///             "<predicate_referent>": string, // e.g. "no credential satisfies predicate age>=65"
+///             ...,
+///         }
+///     }
+///     Referents that can be satisfied are omitted, so empty maps mean the proof request can be satisfied.
+///     For referents with `non_revoked` interval only revocable credentials are taken into account,
+///     and the referent is reported as "registry all revoked" if all of them are revoked by `rev_reg_deltas_json`.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_explain_unsatisfiable_proof_req(command_handle: CommandHandle,
+                                                          wallet_handle: WalletHandle,
+                                                          proof_request_json: *const c_char,
+                                                          rev_reg_deltas_json: *const c_char,
+                                                          cb: Option<extern fn(
+                                                              command_handle_: CommandHandle, err: ErrorCode,
+                                                              explanation_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_explain_unsatisfiable_proof_req: >>> wallet_handle: {:?}, proof_request_json: {:?}, rev_reg_deltas_json: {:?}",
+           wallet_handle, proof_request_json, rev_reg_deltas_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_json!(rev_reg_deltas_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDeltas);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_explain_unsatisfiable_proof_req: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, rev_reg_deltas_json: {:?}",
+           wallet_handle, proof_request_json, rev_reg_deltas_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::ExplainUnsatisfiable(
+                    wallet_handle,
+                    proof_request_json,
+                    rev_reg_deltas_json,
+                    boxed_callback_string!("indy_prover_explain_unsatisfiable_proof_req", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_explain_unsatisfiable_proof_req: <<< res: {:?}", res);
+
+    res
+}
+
+/// Returns the requested credentials used by the last proof created in this wallet
+/// for the same proof request, so a repeated request can be answered without searching the wallet.
+///
+/// Proof requests are matched on their canonical json without nonce.
+/// The cached plan is dropped as soon as one of its credentials is deleted, is revoked by `rev_reg_deltas_json`
+/// or is reported revoked by indy_prover_detect_revoked_credentials. All plans of a wallet are dropped when it is closed.
+/// Only a limited number of plans is kept, the oldest ones are evicted first.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json (see indy_prover_create_proof).
+/// rev_reg_deltas_json: revocation registry deltas known to the prover (see indy_prover_detect_revoked_credentials).
+///     Pass {} to skip the revocation check.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// requested_credentials_json: requested credentials json (see indy_prover_create_proof)
+///     with all timestamps omitted, or null if there is no valid cached plan.
+///     Timestamps of revocable credentials must be set again for the new revocation states.
+///     Self-attested attributes are not kept, so they are always empty and must be provided again.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_get_proof_plan(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         proof_request_json: *const c_char,
+                                         rev_reg_deltas_json: *const c_char,
+                                         cb: Option<extern fn(
+                                             command_handle_: CommandHandle, err: ErrorCode,
+                                             requested_credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_proof_plan: >>> wallet_handle: {:?}, proof_request_json: {:?}, rev_reg_deltas_json: {:?}",
+           wallet_handle, proof_request_json, rev_reg_deltas_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_json!(rev_reg_deltas_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDeltas);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_get_proof_plan: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, rev_reg_deltas_json: {:?}",
+           wallet_handle, proof_request_json, rev_reg_deltas_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::GetProofPlan(
+                    wallet_handle,
+                    proof_request_json,
+                    rev_reg_deltas_json,
+                    boxed_callback_string!("indy_prover_get_proof_plan", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_get_proof_plan: <<< res: {:?}", res);
+
+    res
+}
+
+/// Previews what a proof built for the given requested credentials would disclose,
+/// without creating the proof.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json (see indy_prover_create_proof)
+/// requested_credentials_json: requested credentials json (see indy_prover_create_proof)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// preview_json: values and predicates that would be disclosed by the proof
+///     {
+///         "revealed_attrs": {
+///             "<attr_referent>": {"cred_id": string, "name": string, "raw": string},
+///             ...
+///         },
+///         "unrevealed_attrs": {
+///             "<attr_referent>": {"cred_id": string, "name": string},
+///             ...
+///         },
+///         "self_attested_attrs": {
+///             "<attr_referent>": string,
+///             ...
+///         },
+///         "predicates": {
+///             "<predicate_referent>": {"cred_id": string, "name": string, "p_type": string, "p_value": int, "p_value_max": Optional<int>, "scale": Optional<int>},
+///             ...
+///         }
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_preview_disclosure(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             proof_request_json: *const c_char,
+                                             requested_credentials_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  preview_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_preview_disclosure: >>> wallet_handle: {:?}, proof_request_json: {:?}, requested_credentials_json: {:?}",
+           wallet_handle, proof_request_json, requested_credentials_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(requested_credentials_json, ErrorCode::CommonInvalidParam4, RequestedCredentials);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_preview_disclosure: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, requested_credentials_json: {:?}",
+           wallet_handle, proof_request_json, sensitive!(&requested_credentials_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::PreviewDisclosure(
+                    wallet_handle,
+                    proof_request_json,
+                    requested_credentials_json,
+                    boxed_callback_sensitive_string!("indy_prover_preview_disclosure", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_preview_disclosure: <<< res: {:?}", res);
+
+    res
+}
+
+/// Creates a proof according to the given proof request
+/// Either a corresponding credential with optionally revealed attributes or self-attested attribute must be provided
+/// for each requested attribute (see indy_prover_get_credentials_for_pool_req).
+/// A proof request may request multiple credentials from different schemas and different issuers.
+/// All required schemas, public keys and revocation registries must be provided.
+/// The proof request also contains nonce.
+/// The proof contains either proof or self-attested attribute value for each requested attribute.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json
+///     {
+///         "name": string,
+///         "version": string,
+///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
+///         "requested_attributes": { // set of requested attributes
+///              "<attr_referent>": <attr_info>, // see below
+///              ...,
+///         },
+///         "requested_predicates": { // set of requested predicates
+///              "<predicate_referent>": <predicate_info>, // see below
+///              ...,
+///          },
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval for each attribute
+///                        // (applies to every attribute and predicate but can be overridden on attribute level)
+///                        // (can be overridden on attribute level)
+///         "allowed_values": Optional<{"<attr_referent>": [string, ...], ...}>, // public lists of values, the attribute
+///                        // must be revealed from a credential with one of the listed raw values,
+///         "ver": Optional<str>  - proof request version:
+///             - omit or "1.0" to use unqualified identifiers for restrictions
+///             - "2.0" to use fully qualified identifiers for restrictions
+///     }
+/// requested_credentials_json: either a credential or self-attested attribute for each requested attribute
+///     {
+///         "self_attested_attributes": {
+///             "self_attested_attribute_referent": string
+///         },
+///         "requested_attributes": {
+///             "requested_attribute_referent_1": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }},
+///             "requested_attribute_referent_2": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }}
+///         }, // attributes with the same name taken from different credentials
+///            // must not be bound to the same restrictions in proof request
+///         "requested_predicates": {
+///             "requested_predicates_referent_1": {"cred_id": string, "timestamp": Optional<number> }},
+///         }
+///     }
 /// master_secret_id: the id of the master secret stored in the wallet
 /// schemas_json: all schemas participating in the proof request
 ///     {
@@ -1895,533 +3016,1603 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 ///         <schema2_id>: <schema2>,
 ///         <schema3_id>: <schema3>,
 ///     }
-/// credential_defs_json: all credential definitions participating in the proof request
+/// credential_defs_json: all credential definitions participating in the proof request
+///     {
+///         "cred_def1_id": <credential_def1>,
+///         "cred_def2_id": <credential_def2>,
+///         "cred_def3_id": <credential_def3>,
+///     }
+/// rev_states_json: all revocation states participating in the proof request
+///     {
+///         "rev_reg_def1_id": {
+///             "timestamp1": <rev_state1>,
+///             "timestamp2": <rev_state2>,
+///         },
+///         "rev_reg_def2_id": {
+///             "timestamp3": <rev_state3>
+///         },
+///         "rev_reg_def3_id": {
+///             "timestamp4": <rev_state4>
+///         },
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// where
+/// attr_referent: Proof-request local identifier of requested attribute
+/// attr_info: Describes requested attribute
+///     {
+///         "name": string, // attribute name, (case insensitive and ignore spaces)
+///         "restrictions": Optional<wql query>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// predicate_referent: Proof-request local identifier of requested attribute predicate
+/// predicate_info: Describes requested attribute predicate
+///     {
+///         "name": attribute name, (case insensitive and ignore spaces)
+///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_value": predicate value
+///         "p_value_max": Optional<int>, // exclusive upper bound making a range predicate
+///                                       // `p_value <= value < p_value_max` (or `p_value < value` for ">"),
+///                                       // `p_type` must be ">=" or ">" and `p_value` less than `p_value_max`
+///         "scale": Optional<int>, // decimal places of attribute value stored in minor units,
+///                                 // `p_value` is multiplied by 10^scale before comparison
+///         "restrictions": Optional<wql query>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// non_revoc_interval: Defines non-revocation interval
+///     {
+///         "from": Optional<int>, // timestamp of interval beginning
+///         "to": Optional<int>, // timestamp of interval ending
+///     }
+/// where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
+///     The list of allowed fields:
+///         "schema_id": <credential schema id>,
+///         "schema_issuer_did": <credential schema issuer did>,
+///         "schema_name": <credential schema name>,
+///         "schema_version": <credential schema version>,
+///         "schema_version_range": <comma separated bounds like ">=1.0,<2.0">, // matches any schema version in the range
+///         "issuer_did": <credential issuer did>,
+///         "cred_def_id": <credential definition id>,
+///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///         "attr::<attribute name>::value": <attribute raw value>, // checked by verifier for revealed attributes only
+///
+/// #Returns
+/// Proof json
+/// For each requested attribute either a proof (with optionally revealed attribute value) or
+/// self-attested attribute value is provided.
+/// Each proof is associated with a credential and corresponding schema_id, cred_def_id, rev_reg_id and timestamp.
+/// There is also aggregated proof part common for all credential proofs.
+///     {
+///         "requested_proof": {
+///             "revealed_attrs": {
+///                 "requested_attr1_id": {sub_proof_index: number, raw: string, encoded: string},
+///                 "requested_attr4_id": {sub_proof_index: number: string, encoded: string},
+///             },
+///             "unrevealed_attrs": {
+///                 "requested_attr3_id": {sub_proof_index: number}
+///             },
+///             "self_attested_attrs": {
+///                 "requested_attr2_id": self_attested_value,
+///             },
+///             "predicates": {
+///                 "requested_predicate_1_referent": {sub_proof_index: int},
+///                 "requested_predicate_2_referent": {sub_proof_index: int},
+///             }
+///         }
+///         "proof": {
+///             "proofs": [ <credential_proof>, <credential_proof>, <credential_proof> ],
+///             "aggregated_proof": <aggregated_proof>
+///         } (opaque type that contains data structures internal to Ursa.
+///           It should not be parsed and are likely to change in future versions).
+///         "identifiers": [{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}]
+///         "nonce": Optional<string> - nonce of the proof request the proof was built for
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       proof_req_json: *const c_char,
+                                       requested_credentials_json: *const c_char,
+                                       master_secret_id: *const c_char,
+                                       schemas_json: *const c_char,
+                                       credential_defs_json: *const c_char,
+                                       rev_states_json: *const c_char,
+                                       cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                            proof_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_proof: >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}, master_secret_id: {:?}, \
+    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
+           wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+
+    check_useful_validatable_json!(proof_req_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(requested_credentials_json, ErrorCode::CommonInvalidParam4, RequestedCredentials);
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam5);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam6, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam7, CredentialDefinitions);
+    check_useful_json!(rev_states_json, ErrorCode::CommonInvalidParam8, RevocationStates);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_prover_create_proof: entities >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}, master_secret_id: {:?}, \
+    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
+           wallet_handle, proof_req_json, sensitive!(&requested_credentials_json), master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProof(
+            wallet_handle,
+            proof_req_json,
+            requested_credentials_json,
+            master_secret_id,
+            schemas_json,
+            credential_defs_json,
+            rev_states_json,
+            CancellationToken::register(command_handle),
+            boxed_callback_sensitive_string!("indy_prover_create_proof", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_create_proof: <<< res: {:?}", res);
+
+    res
+}
+
+/// Cancels proof creation started by `indy_prover_create_proof`.
+/// Proof creation is interrupted before the next sub-proof and its callback is called with `CommonCancelled` error.
+///
+/// #Params
+/// proof_command_handle: command handle passed to `indy_prover_create_proof`.
+///
+/// #Returns
+/// Success if proof creation for the handle is in progress
+/// CommonInvalidParam1 if there is no proof creation for the handle (e.g. it is already finished)
+#[no_mangle]
+pub extern fn indy_prover_cancel_proof(proof_command_handle: CommandHandle) -> ErrorCode {
+    trace!("indy_prover_cancel_proof: >>> proof_command_handle: {:?}", proof_command_handle);
+
+    let res = if CancellationToken::cancel(proof_command_handle) {
+        ErrorCode::Success
+    } else {
+        ErrorCode::CommonInvalidParam1
+    };
+
+    trace!("indy_prover_cancel_proof: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a proof (of multiple credential).
+/// All required schemas, public keys and revocation registries must be provided.
+///
+/// IMPORTANT: You must use *_id's (`schema_id`, `cred_def_id`, `rev_reg_id`) listed in `proof[identifiers]`
+/// as the keys for corresponding `schemas_json`, `credential_defs_json`, `rev_reg_defs_json`, `rev_regs_json` objects.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json
+///     {
+///         "name": string,
+///         "version": string,
+///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
+///         "requested_attributes": { // set of requested attributes
+///              "<attr_referent>": <attr_info>, // see below
+///              ...,
+///         },
+///         "requested_predicates": { // set of requested predicates
+///              "<predicate_referent>": <predicate_info>, // see below
+///              ...,
+///          },
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval for each attribute
+///                        // (can be overridden on attribute level)
+///         "max_accum_age_secs": Optional<int>, // if specified verification fails when an accumulator
+///                        // used for a non-revocation proof is older than this number of seconds
+///         "normalize": Optional<{ // steps applied in order to revealed values returned by
+///                        // `indy_verifier_verify_proof_with_result`, ignored by prover
+///              "<attr_referent>": ["lowercase" | "trim" | "nfc", ...],
+///              ...,
+///          }>,
+///         "allowed_values": Optional<{"<attr_referent>": [string, ...], ...}>, // public lists of values, the attribute
+///                        // must be revealed from a credential with one of the listed raw values,
+///         "ver": Optional<str>  - proof request version:
+///             - omit or "1.0" to use unqualified identifiers for restrictions
+///             - "2.0" to use fully qualified identifiers for restrictions
+///     }
+/// proof_json: created for request proof json
+///     {
+///         "requested_proof": {
+///             "revealed_attrs": {
+///                 "requested_attr1_id": {sub_proof_index: number, raw: string, encoded: string}, // NOTE: check that `encoded` value match to `raw` value on application level
+///                 "requested_attr4_id": {sub_proof_index: number: string, encoded: string}, // NOTE: check that `encoded` value match to `raw` value on application level
+///             },
+///             "unrevealed_attrs": {
+///                 "requested_attr3_id": {sub_proof_index: number}
+///             },
+///             "self_attested_attrs": {
+///                 "requested_attr2_id": self_attested_value,
+///             },
+///             "requested_predicates": {
+///                 "requested_predicate_1_referent": {sub_proof_index: int},
+///                 "requested_predicate_2_referent": {sub_proof_index: int},
+///             }
+///         }
+///         "proof": {
+///             "proofs": [ <credential_proof>, <credential_proof>, <credential_proof> ],
+///             "aggregated_proof": <aggregated_proof>
+///         }
+///         "identifiers": [{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}]
+///         "nonce": Optional<string> - nonce of the proof request the proof was built for
+///     }
+/// schemas_json: all schemas participating in the proof
+///     {
+///         <schema1_id>: <schema1>,
+///         <schema2_id>: <schema2>,
+///         <schema3_id>: <schema3>,
+///     }
+/// credential_defs_json: all credential definitions participating in the proof
+///     {
+///         "cred_def1_id": <credential_def1>,
+///         "cred_def2_id": <credential_def2>,
+///         "cred_def3_id": <credential_def3>,
+///     }
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof
+///     {
+///         "rev_reg_def1_id": <rev_reg_def1>,
+///         "rev_reg_def2_id": <rev_reg_def2>,
+///         "rev_reg_def3_id": <rev_reg_def3>,
+///     }
+/// rev_regs_json: all revocation registries participating in the proof
+///     keyed by the timestamps of revocation states used by prover, so a proof built against
+///     a past registry state is verified with the registry (accumulator) of that timestamp
+///     {
+///         "rev_reg_def1_id": {
+///             "timestamp1": <rev_reg1>,
+///             "timestamp2": <rev_reg2>,
+///         },
+///         "rev_reg_def2_id": {
+///             "timestamp3": <rev_reg3>
+///         },
+///         "rev_reg_def3_id": {
+///             "timestamp4": <rev_reg4>
+///         },
+///     }
+/// where
+/// attr_referent: Proof-request local identifier of requested attribute
+/// attr_info: Describes requested attribute
+///     {
+///         "name": string, // attribute name, (case insensitive and ignore spaces)
+///         "restrictions": Optional<wql query>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// predicate_referent: Proof-request local identifier of requested attribute predicate
+/// predicate_info: Describes requested attribute predicate
+///     {
+///         "name": attribute name, (case insensitive and ignore spaces)
+///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_value": predicate value
+///         "p_value_max": Optional<int>, // exclusive upper bound making a range predicate
+///                                       // `p_value <= value < p_value_max` (or `p_value < value` for ">"),
+///                                       // `p_type` must be ">=" or ">" and `p_value` less than `p_value_max`
+///         "scale": Optional<int>, // decimal places of attribute value stored in minor units,
+///                                 // `p_value` is multiplied by 10^scale before comparison
+///         "restrictions": Optional<wql query>, // see below
+///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
+///                        // If specified prover must proof non-revocation
+///                        // for date in this interval this attribute
+///                        // (overrides proof level interval)
+///                        // May be a list of intervals, any one of which must be satisfied
+///     }
+/// non_revoc_interval: Defines non-revocation interval
+///     {
+///         "from": Optional<int>, // timestamp of interval beginning
+///         "to": Optional<int>, // timestamp of interval ending
+///     }
+/// where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
+///     The list of allowed fields:
+///         "schema_id": <credential schema id>,
+///         "schema_issuer_did": <credential schema issuer did>,
+///         "schema_name": <credential schema name>,
+///         "schema_version": <credential schema version>,
+///         "schema_version_range": <comma separated bounds like ">=1.0,<2.0">, // matches any schema version in the range
+///         "issuer_did": <credential issuer did>,
+///         "cred_def_id": <credential definition id>,
+///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///         "attr::<attribute name>::value": <attribute raw value>, // checked by verifier for revealed attributes only
+///
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
+                                         proof_request_json: *const c_char,
+                                         proof_json: *const c_char,
+                                         schemas_json: *const c_char,
+                                         credential_defs_json: *const c_char,
+                                         rev_reg_defs_json: *const c_char,
+                                         rev_regs_json: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                              valid: bool)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProof(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_verifier_verify_proof: valid: {:?}", valid);
+
+                cb(command_handle, err, valid)
+            })
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a proof the same way as `indy_verifier_verify_proof` but returns how
+/// each proof request referent was satisfied.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`)
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`)
+/// schemas_json: all schemas participating in the proof
+/// credential_defs_json: all credential definitions participating in the proof
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof
+/// rev_regs_json: all revocation registries participating in the proof
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// result_json: verification result json
 ///     {
-///         "cred_def1_id": <credential_def1>,
-///         "cred_def2_id": <credential_def2>,
-///         "cred_def3_id": <credential_def3>,
+///         "valid": bool, // true - if signature is valid, false - otherwise
+///         "referents": { // filled only for a valid proof
+///             "<referent>": {
+///                 "mode": "revealed" | "unrevealed" | "predicate" | "self_attested",
+///                 "non_revoked_at": Optional<[timestamp, ...]>, // timestamps non-revocation was proven for
+///             },
+///             ...
+///         },
+///         "revealed_attrs": { // filled only for a valid proof
+///             "<attr_referent>": string, // raw value normalized as `normalize` of proof request specifies
+///             ...
+///         }
 ///     }
-/// rev_states_json: all revocation states participating in the proof request
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_result(command_handle: CommandHandle,
+                                                     proof_request_json: *const c_char,
+                                                     proof_json: *const c_char,
+                                                     schemas_json: *const c_char,
+                                                     credential_defs_json: *const c_char,
+                                                     rev_reg_defs_json: *const c_char,
+                                                     rev_regs_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_result: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_with_result: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithResult(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            boxed_callback_string!("indy_verifier_verify_proof_with_result", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_result: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a proof like `indy_verifier_verify_proof` and reports for each requested attribute
+/// and predicate whether it passed and why it failed. Intended for debugging of rejected proofs.
+///
+/// The report is built only from what the proof discloses to the verifier anyway.
+/// Once every referent passed, missing or inconsistent inputs are errors, the same as for `indy_verifier_verify_proof_dry_run`.
+/// The cryptographic proof covers all credentials at once: it's checked only if every referent passed
+/// the other checks, and its failure is reported as "signature_invalid" for every referent proven by a credential.
+/// A credential revoked after its witness was updated fails the same way as a forged one, so "revoked" is
+/// reported only if the proof lacks non-revocation proof for the requested interval.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`)
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`)
+/// schemas_json: all schemas participating in the proof
+/// credential_defs_json: all credential definitions participating in the proof
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof
+/// rev_regs_json: all revocation registries participating in the proof
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: verification report json
 ///     {
-///         "rev_reg_def1_id": {
-///             "timestamp1": <rev_state1>,
-///             "timestamp2": <rev_state2>,
-///         },
-///         "rev_reg_def2_id": {
-///             "timestamp3": <rev_state3>
-///         },
-///         "rev_reg_def3_id": {
-///             "timestamp4": <rev_state4>
+///         "valid": bool, // true - if proof is valid, false - otherwise
+///         "referents": {
+///             "<attr_referent or predicate_referent>": {
+///                 "valid": bool,
+///                 "reason": Optional<"not_revealed" | "restrictions_unsatisfied" | "predicate_unsatisfied" | "revoked" | "signature_invalid">,
+///                 "details": Optional<string>, // human readable description of the failure
+///                 "mode": Optional<"revealed" | "unrevealed" | "predicate" | "self_attested">, // for valid proof only,
+///                         // see `indy_verifier_verify_proof_with_result`
+///             },
+///             ...
 ///         },
+///         "error": Optional<string>, // why proof is rejected as a whole, e.g. nonce mismatch
 ///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_detailed(command_handle: CommandHandle,
+                                                  proof_request_json: *const c_char,
+                                                  proof_json: *const c_char,
+                                                  schemas_json: *const c_char,
+                                                  credential_defs_json: *const c_char,
+                                                  rev_reg_defs_json: *const c_char,
+                                                  rev_regs_json: *const c_char,
+                                                  cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                       report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_detailed: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_detailed: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofDetailed(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            boxed_callback_string!("indy_verifier_verify_proof_detailed", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_detailed: <<< res: {:?}", res);
+
+    res
+}
+
+/// Checks that a proof and the entities it refers to are complete and consistent
+/// without running the cryptographic verification, so a caller can tell
+/// invalid inputs (missing or mismatching schemas, credential definitions, revocation registries)
+/// apart from a proof that doesn't verify.
+///
+/// Performs every check of `indy_verifier_verify_proof` except the proof verification itself:
+/// a tampered proof built over valid inputs passes.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`)
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`)
+/// schemas_json: all schemas participating in the proof
+/// credential_defs_json: all credential definitions participating in the proof
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof
+/// rev_regs_json: all revocation registries participating in the proof
 /// cb: Callback that takes command result as parameter.
 ///
-/// where
-/// attr_referent: Proof-request local identifier of requested attribute
-/// attr_info: Describes requested attribute
+/// #Returns
+/// Success if inputs are valid
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_dry_run(command_handle: CommandHandle,
+                                                 proof_request_json: *const c_char,
+                                                 proof_json: *const c_char,
+                                                 schemas_json: *const c_char,
+                                                 credential_defs_json: *const c_char,
+                                                 rev_reg_defs_json: *const c_char,
+                                                 rev_regs_json: *const c_char,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_dry_run: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_dry_run: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofDryRun(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_verifier_verify_proof_dry_run:");
+                cb(command_handle, err)
+            })
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_dry_run: <<< res: {:?}", res);
+
+    res
+}
+
+/// Create revocation state for a credential in the particular time moment.
+///
+/// The witness is cached for the registry state the delta ends with, so creating a revocation state
+/// for the same credential and an unchanged registry again doesn't read tails (see `indy_prover_clear_witness_cache`).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// rev_reg_def_json: revocation registry definition json related to `rev_reg_id` in a credential
+/// rev_reg_delta_json: revocation registry definition delta json
+/// timestamp: time represented as a total number of seconds from Unix Epoch.
+/// cred_rev_id: user credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// revocation state json:
+///     {
+///         "rev_reg": <revocation registry>,
+///         "witness": <witness>,  (opaque type that contains data structures internal to Ursa.
+///                                 It should not be parsed and are likely to change in future versions).
+///         "timestamp" : integer
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_create_revocation_state(command_handle: CommandHandle,
+                                           blob_storage_reader_handle: IndyHandle,
+                                           rev_reg_def_json: *const c_char,
+                                           rev_reg_delta_json: *const c_char,
+                                           timestamp: u64,
+                                           cred_rev_id: *const c_char,
+                                           cb: Option<extern fn(
+                                               command_handle_: CommandHandle, err: ErrorCode,
+                                               rev_state_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_revocation_state: >>> blob_storage_reader_handle: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, timestamp: {:?}, \
+    cred_rev_id: {:?}", blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+
+    check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam3, RevocationRegistryDefinition);
+    check_useful_validatable_json!(rev_reg_delta_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDelta);
+    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_create_revocation_state: entities >>> blob_storage_reader_handle: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, timestamp: {:?}, \
+    cred_rev_id: {:?}", blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateRevocationState(
+            blob_storage_reader_handle,
+            rev_reg_def_json,
+            rev_reg_delta_json,
+            timestamp,
+            cred_rev_id,
+            boxed_callback_string!("indy_create_revocation_state", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_create_revocation_state: <<< res: {:?}", res);
+
+    res
+}
+
+/// Create new revocation state for a credential based on existed state
+/// at the particular time moment (to reduce calculation time).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// rev_state_json: revocation registry state json
+/// rev_reg_def_json: revocation registry definition json related to `rev_reg_id` in a credential
+/// rev_reg_delta_json: revocation registry definition delta json
+/// timestamp: time represented as a total number of seconds from Unix Epoch
+/// cred_rev_id: user credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// revocation state json:
 ///     {
-///         "name": string, // attribute name, (case insensitive and ignore spaces)
-///         "restrictions": Optional<wql query>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
+///         "rev_reg": <revocation registry>,
+///         "witness": <witness>,  (opaque type that contains data structures internal to Ursa.
+///                                 It should not be parsed and are likely to change in future versions).
+///         "timestamp" : integer
 ///     }
-/// predicate_referent: Proof-request local identifier of requested attribute predicate
-/// predicate_info: Describes requested attribute predicate
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_update_revocation_state(command_handle: CommandHandle,
+                                           blob_storage_reader_handle: IndyHandle,
+                                           rev_state_json: *const c_char,
+                                           rev_reg_def_json: *const c_char,
+                                           rev_reg_delta_json: *const c_char,
+                                           timestamp: u64,
+                                           cred_rev_id: *const c_char,
+                                           cb: Option<extern fn(
+                                               command_handle_: CommandHandle, err: ErrorCode,
+                                               updated_rev_state_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_update_revocation_state: >>> blob_storage_reader_handle: {:?}, rev_state_json: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, \
+    timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+
+    check_useful_validatable_json!(rev_state_json, ErrorCode::CommonInvalidParam3, RevocationState);
+    check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDefinition);
+    check_useful_validatable_json!(rev_reg_delta_json, ErrorCode::CommonInvalidParam5, RevocationRegistryDelta);
+    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_update_revocation_state: entities >>> blob_storage_reader_handle: {:?}, rev_state_json: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, \
+    timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::UpdateRevocationState(
+            blob_storage_reader_handle,
+            rev_state_json,
+            rev_reg_def_json,
+            rev_reg_delta_json,
+            timestamp,
+            cred_rev_id,
+            boxed_callback_string!("indy_update_revocation_state", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_update_revocation_state: <<< res: {:?}", res);
+
+    res
+}
+
+/// Brings stale revocation state of a credential up to date by applying
+/// an ordered run of revocation registry deltas published since the state was built.
+/// Each delta must start from the accumulator the previous one ends with.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// rev_state_json: revocation registry state json containing current witness
+/// rev_reg_def_json: revocation registry definition json related to `rev_reg_id` in a credential
+/// rev_reg_deltas_json: ordered array of revocation registry delta jsons
+/// timestamp: time represented as a total number of seconds from Unix Epoch
+/// cred_rev_id: user credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// revocation state json:
 ///     {
-///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
-///         "restrictions": Optional<wql query>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
+///         "rev_reg": <revocation registry>, // accumulator after the last delta
+///         "witness": <witness>,  (opaque type that contains data structures internal to Ursa.
+///                                 It should not be parsed and are likely to change in future versions).
+///         "timestamp" : integer
 ///     }
-/// non_revoc_interval: Defines non-revocation interval
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_prover_update_witness(command_handle: CommandHandle,
+                                         blob_storage_reader_handle: IndyHandle,
+                                         rev_state_json: *const c_char,
+                                         rev_reg_def_json: *const c_char,
+                                         rev_reg_deltas_json: *const c_char,
+                                         timestamp: u64,
+                                         cred_rev_id: *const c_char,
+                                         cb: Option<extern fn(
+                                             command_handle_: CommandHandle, err: ErrorCode,
+                                             updated_rev_state_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_update_witness: >>> blob_storage_reader_handle: {:?}, rev_state_json: {:?}, rev_reg_def_json: {:?}, rev_reg_deltas_json: {:?}, \
+    timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_deltas_json, timestamp, cred_rev_id);
+
+    check_useful_validatable_json!(rev_state_json, ErrorCode::CommonInvalidParam3, RevocationState);
+    check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDefinition);
+    check_useful_json!(rev_reg_deltas_json, ErrorCode::CommonInvalidParam5, Vec<RevocationRegistryDelta>);
+    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_prover_update_witness: entities >>> blob_storage_reader_handle: {:?}, rev_state_json: {:?}, rev_reg_def_json: {:?}, rev_reg_deltas_json: {:?}, \
+    timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_deltas_json, timestamp, cred_rev_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::UpdateWitness(
+            blob_storage_reader_handle,
+            rev_state_json,
+            rev_reg_def_json,
+            rev_reg_deltas_json,
+            timestamp,
+            cred_rev_id,
+            boxed_callback_string!("indy_prover_update_witness", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_update_witness: <<< res: {:?}", res);
+
+    res
+}
+
+/// Finds credentials stored in the wallet that are revoked by the given revocation registry deltas,
+/// so a wallet can mark them as revoked.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet).
+/// rev_reg_deltas_json: revocation registry deltas published since the last check
 ///     {
-///         "from": Optional<int>, // timestamp of interval beginning
-///         "to": Optional<int>, // timestamp of interval ending
+///         "<rev_reg_id>": <revocation registry delta json> or [<revocation registry delta json>, ...] in ledger order,
+///         ...
 ///     }
-/// where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
-///     The list of allowed fields:
-///         "schema_id": <credential schema id>,
-///         "schema_issuer_did": <credential schema issuer did>,
-///         "schema_name": <credential schema name>,
-///         "schema_version": <credential schema version>,
-///         "issuer_did": <credential issuer did>,
-///         "cred_def_id": <credential definition id>,
-///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
+///     Deltas of a registry are applied in order, so an index issued by a later delta is not revoked anymore.
+/// cb: Callback that takes command result as parameter
 ///
 /// #Returns
-/// Proof json
-/// For each requested attribute either a proof (with optionally revealed attribute value) or
-/// self-attested attribute value is provided.
-/// Each proof is associated with a credential and corresponding schema_id, cred_def_id, rev_reg_id and timestamp.
-/// There is also aggregated proof part common for all credential proofs.
-///     {
-///         "requested_proof": {
-///             "revealed_attrs": {
-///                 "requested_attr1_id": {sub_proof_index: number, raw: string, encoded: string},
-///                 "requested_attr4_id": {sub_proof_index: number: string, encoded: string},
-///             },
-///             "unrevealed_attrs": {
-///                 "requested_attr3_id": {sub_proof_index: number}
-///             },
-///             "self_attested_attrs": {
-///                 "requested_attr2_id": self_attested_value,
-///             },
-///             "predicates": {
-///                 "requested_predicate_1_referent": {sub_proof_index: int},
-///                 "requested_predicate_2_referent": {sub_proof_index: int},
-///             }
-///         }
-///         "proof": {
-///             "proofs": [ <credential_proof>, <credential_proof>, <credential_proof> ],
-///             "aggregated_proof": <aggregated_proof>
-///         } (opaque type that contains data structures internal to Ursa.
-///           It should not be parsed and are likely to change in future versions).
-///         "identifiers": [{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}]
+/// revoked_credentials_json: stored credentials whose `cred_rev_id` is left revoked by the deltas for their `rev_reg_id`
+///     [{
+///         "referent": string, // cred_id in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"},
+///         "schema_id": string,
+///         "cred_def_id": string,
+///         "rev_reg_id": string,
+///         "cred_rev_id": string
+///     }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_prover_detect_revoked_credentials(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     rev_reg_deltas_json: *const c_char,
+                                                     cb: Option<extern fn(
+                                                         command_handle_: CommandHandle, err: ErrorCode,
+                                                         revoked_credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_detect_revoked_credentials: >>> wallet_handle: {:?}, rev_reg_deltas_json: {:?}", wallet_handle, rev_reg_deltas_json);
+
+    check_useful_json!(rev_reg_deltas_json, ErrorCode::CommonInvalidParam3, RevocationRegistryDeltas);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_detect_revoked_credentials: entities >>> wallet_handle: {:?}, rev_reg_deltas_json: {:?}", wallet_handle, rev_reg_deltas_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::DetectRevoked(
+            wallet_handle,
+            rev_reg_deltas_json,
+            boxed_callback_string!("indy_prover_detect_revoked_credentials", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_detect_revoked_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Drops all witnesses cached by `indy_create_revocation_state`.
+///
+/// Witnesses are cached per credential and revocation registry state, so creating
+/// a revocation state for an unchanged registry again doesn't read tails.
+/// The size of the cache can be set by `witness_cache_size` of `indy_set_runtime_config`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// cb: Callback that takes command result as parameter
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_prover_clear_witness_cache(command_handle: CommandHandle,
+                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_clear_witness_cache: >>> ");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::ClearWitnessCache(
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_prover_clear_witness_cache:");
+                cb(command_handle, err)
+            })
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_clear_witness_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Returns the latest accumulator timestamp the prover can prove non-revocation for
+/// with its current witnesses of the given revocation registry.
+///
+/// Witnesses built by `indy_create_revocation_state` and updated by `indy_update_revocation_state`
+/// or `indy_prover_update_witness` are cached with the timestamp of the revocation state,
+/// so a witness doesn't support a later accumulator until it is updated.
+/// If witnesses of several credentials of the registry are cached, the latest of their timestamps is returned.
+/// A prover with a stale witness can use it to negotiate the non-revocation interval with the verifier
+/// instead of failing to create the proof.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// rev_reg_id: id of revocation registry
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// timestamp_json: timestamp (number) or null if no witness of the registry is cached
+/// (e.g. after `indy_prover_clear_witness_cache` or a restart).
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_prover_get_max_provable_timestamp(command_handle: CommandHandle,
+                                                     rev_reg_id: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          timestamp_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_get_max_provable_timestamp: >>> rev_reg_id: {:?}", rev_reg_id);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam2, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_prover_get_max_provable_timestamp: entities >>> rev_reg_id: {:?}", rev_reg_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::MaxProvableTimestamp(
+            rev_reg_id,
+            boxed_callback_string!("indy_prover_get_max_provable_timestamp", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_get_max_provable_timestamp: <<< res: {:?}", res);
+
+    res
+}
+
+
+///  Generates 80-bit numbers that can be used as a nonce for proof request.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// nonce: generated number as a string
+///
+#[no_mangle]
+pub extern fn indy_generate_nonce(command_handle: CommandHandle,
+                                  cb: Option<extern fn(
+                                      command_handle_: CommandHandle, err: ErrorCode,
+                                      nonce: *const c_char)>) -> ErrorCode {
+    trace!("indy_generate_nonce: >>> ");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(
+            VerifierCommand::GenerateNonce(
+                boxed_callback_string!("indy_generate_nonce", cb, command_handle)
+            ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_generate_nonce: <<< res: {:?}", res);
+
+    res
+}
+
+/// Lists ledger entities referenced by a proof, so exactly those can be fetched
+/// before calling `indy_verifier_verify_proof`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_json: created for request proof json (see indy_verifier_verify_proof)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// required_identifiers_json:
+///     {
+///         "schema_ids": [string, ...],
+///         "cred_def_ids": [string, ...],
+///         "rev_regs": [{
+///             "rev_reg_id": string,
+///             "timestamp": int, // fetch revocation registry state as of this time
+///         }, ...] // empty if no credential in the proof is checked for non-revocation
 ///     }
 ///
 /// #Errors
-/// Anoncreds*
 /// Common*
-/// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
-                                       wallet_handle: WalletHandle,
-                                       proof_req_json: *const c_char,
-                                       requested_credentials_json: *const c_char,
-                                       master_secret_id: *const c_char,
-                                       schemas_json: *const c_char,
-                                       credential_defs_json: *const c_char,
-                                       rev_states_json: *const c_char,
-                                       cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                            proof_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_create_proof: >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}, master_secret_id: {:?}, \
-    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
-           wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+pub extern fn indy_verifier_get_proof_required_identifiers(command_handle: CommandHandle,
+                                                           proof_json: *const c_char,
+                                                           cb: Option<extern fn(
+                                                               command_handle_: CommandHandle, err: ErrorCode,
+                                                               required_identifiers_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_get_proof_required_identifiers: >>> proof_json: {:?}", proof_json);
 
-    check_useful_validatable_json!(proof_req_json, ErrorCode::CommonInvalidParam3, ProofRequest);
-    check_useful_validatable_json!(requested_credentials_json, ErrorCode::CommonInvalidParam4, RequestedCredentials);
-    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam5);
-    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam6, Schemas);
-    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam7, CredentialDefinitions);
-    check_useful_json!(rev_states_json, ErrorCode::CommonInvalidParam8, RevocationStates);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam2, Proof);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
 
-    trace!("indy_prover_create_proof: entities >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}, master_secret_id: {:?}, \
-    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
-           wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+    trace!("indy_verifier_get_proof_required_identifiers: entities >>> proof_json: {:?}", proof_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProof(
-            wallet_handle,
-            proof_req_json,
-            requested_credentials_json,
-            master_secret_id,
-            schemas_json,
-            credential_defs_json,
-            rev_states_json,
-            boxed_callback_string!("indy_prover_create_proof", cb, command_handle)
-        ))));
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(
+            VerifierCommand::GetRequiredIdentifiers(
+                proof_json,
+                boxed_callback_string!("indy_verifier_get_proof_required_identifiers", cb, command_handle)
+            ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_create_proof: <<< res: {:?}", res);
+    trace!("indy_verifier_get_proof_required_identifiers: <<< res: {:?}", res);
 
     res
 }
 
-/// Verifies a proof (of multiple credential).
-/// All required schemas, public keys and revocation registries must be provided.
+/// Set a handler called after each successful proof verification
+/// (`indy_verifier_verify_proof`, `indy_verifier_verify_proof_with_result` and `indy_verifier_verify_proof_detailed`),
+/// so Verifier can persist an audit trail of what was verified.
 ///
-/// IMPORTANT: You must use *_id's (`schema_id`, `cred_def_id`, `rev_reg_id`) listed in `proof[identifiers]`
-/// as the keys for corresponding `schemas_json`, `credential_defs_json`, `rev_reg_defs_json`, `rev_regs_json` objects.
+/// The handler is called from the libindy command thread before verification result is returned.
 ///
 /// #Params
-/// wallet_handle: wallet handle (created by open_wallet).
-/// command_handle: command handle to map callback to user context.
-/// proof_request_json: proof request json
-///     {
-///         "name": string,
-///         "version": string,
-///         "nonce": string, - a big number represented as a string (use `indy_generate_nonce` function to generate 80-bit number)
-///         "requested_attributes": { // set of requested attributes
-///              "<attr_referent>": <attr_info>, // see below
-///              ...,
-///         },
-///         "requested_predicates": { // set of requested predicates
-///              "<predicate_referent>": <predicate_info>, // see below
-///              ...,
-///          },
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval for each attribute
-///                        // (can be overridden on attribute level)
-///         "ver": Optional<str>  - proof request version:
-///             - omit or "1.0" to use unqualified identifiers for restrictions
-///             - "2.0" to use fully qualified identifiers for restrictions
-///     }
-/// proof_json: created for request proof json
-///     {
-///         "requested_proof": {
-///             "revealed_attrs": {
-///                 "requested_attr1_id": {sub_proof_index: number, raw: string, encoded: string}, // NOTE: check that `encoded` value match to `raw` value on application level
-///                 "requested_attr4_id": {sub_proof_index: number: string, encoded: string}, // NOTE: check that `encoded` value match to `raw` value on application level
-///             },
-///             "unrevealed_attrs": {
-///                 "requested_attr3_id": {sub_proof_index: number}
-///             },
-///             "self_attested_attrs": {
-///                 "requested_attr2_id": self_attested_value,
-///             },
-///             "requested_predicates": {
-///                 "requested_predicate_1_referent": {sub_proof_index: int},
-///                 "requested_predicate_2_referent": {sub_proof_index: int},
-///             }
-///         }
-///         "proof": {
-///             "proofs": [ <credential_proof>, <credential_proof>, <credential_proof> ],
-///             "aggregated_proof": <aggregated_proof>
-///         }
-///         "identifiers": [{schema_id, cred_def_id, Optional<rev_reg_id>, Optional<timestamp>}]
-///     }
-/// schemas_json: all schemas participating in the proof
+/// context: pointer to some audit context that will be passed to the handler.
+/// cb: (optional) handler, unsets the current one if not specified.
+///     It receives the audit record json:
 ///     {
-///         <schema1_id>: <schema1>,
-///         <schema2_id>: <schema2>,
-///         <schema3_id>: <schema3>,
+///         "nonce": string, // nonce of the verified proof request
+///         "revealed_attrs": [string, ...], // referents of revealed attributes
+///         "predicates": [string, ...], // referents of satisfied predicates
+///         "issuer_dids": [string, ...], // DIDs of issuers of the credentials used in the proof
+///         "timestamp": int, // verification time in seconds from UNIX Epoch
 ///     }
-/// credential_defs_json: all credential definitions participating in the proof
+///     Arrays are sorted so the record json is stable.
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_verifier_set_audit_callback(context: *const c_void,
+                                               cb: Option<extern fn(context: *const c_void,
+                                                                    audit_record_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_set_audit_callback: >>> context: {:?}, cb: {:?}", context, cb);
+
+    set_verification_audit_sink(cb.map(|cb| Box::new(CallbackAuditSink { context: context as usize, cb }) as Box<dyn VerificationAuditSink>));
+
+    let res = ErrorCode::Success;
+
+    trace!("indy_verifier_set_audit_callback: <<< res: {:?}", res);
+
+    res
+}
+
+struct CallbackAuditSink {
+    context: usize,
+    cb: extern fn(context: *const c_void, audit_record_json: *const c_char),
+}
+
+impl VerificationAuditSink for CallbackAuditSink {
+    fn record(&self, record: &VerificationAuditRecord) {
+        let record_json = match serde_json::to_string(record) {
+            Ok(record_json) => record_json,
+            Err(err) => {
+                warn!("Cannot serialize VerificationAuditRecord: {:?}", err);
+                return;
+            }
+        };
+
+        let record_json = ctypes::string_to_cstring(record_json);
+        (self.cb)(self.context as *const c_void, record_json.as_ptr())
+    }
+}
+
+/// Get unqualified form (short form without method) of a fully qualified entity like DID.
+///
+/// This function should be used to the proper casting of fully qualified entity to unqualified form in the following cases:
+///     Issuer, which works with fully qualified identifiers, creates a Credential Offer for Prover, which doesn't support fully qualified identifiers.
+///     Verifier prepares a Proof Request based on fully qualified identifiers or Prover, which doesn't support fully qualified identifiers.
+///     another case when casting to unqualified form needed
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// entity: string - target entity to disqualify. Can be one of:
+///             Did
+///             SchemaId
+///             CredentialDefinitionId
+///             RevocationRegistryId
+///             CredentialOffer
+///             ProofRequest
+///
+/// #Returns
+///   res: entity either in unqualified form or original if casting isn't possible
+#[no_mangle]
+pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
+                                   entity: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        res: *const c_char)>) -> ErrorCode {
+    trace!("indy_to_unqualified: >>> entity: {:?}", entity);
+
+    check_useful_c_str!(entity, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_to_unqualified: entities >>> entity: {:?}", sensitive!(&entity));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::ToUnqualified(
+            entity,
+            Box::new(move |result| {
+                let (err, res) = prepare_result_1!(result, String::new());
+                trace!("indy_to_unqualified: did: {:?}", sensitive!(&res));
+                let res = ctypes::string_to_cstring(res);
+                cb(command_handle, err, res.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_to_unqualified: <<< res: {:?}", res);
+
+    res
+}
+
+/// Encodes raw attribute values and compares them with encodings produced by another Indy implementation.
+/// Intended for conformance testing of attribute encoding, exported only when built with `encoding_conformance_tests` feature.
+///
+/// Values are encoded as follows: 32-bit integers are encoded as themselves,
+/// any other value as the big-endian integer of its sha256 hash truncated at the first zero byte.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// values_json: raw attribute values
 ///     {
-///         "cred_def1_id": <credential_def1>,
-///         "cred_def2_id": <credential_def2>,
-///         "cred_def3_id": <credential_def3>,
+///         "attr_name" : string, // raw value
+///         ...
 ///     }
-/// rev_reg_defs_json: all revocation registry definitions participating in the proof
+/// reference_encodings_json: encodings produced by the reference implementation for the same attributes
 ///     {
-///         "rev_reg_def1_id": <rev_reg_def1>,
-///         "rev_reg_def2_id": <rev_reg_def2>,
-///         "rev_reg_def3_id": <rev_reg_def3>,
+///         "attr_name" : string, // encoded value
+///         ...
 ///     }
-/// rev_regs_json: all revocation registries participating in the proof
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// diverged_json: attributes whose encoding differs from the reference one
 ///     {
-///         "rev_reg_def1_id": {
-///             "timestamp1": <rev_reg1>,
-///             "timestamp2": <rev_reg2>,
-///         },
-///         "rev_reg_def2_id": {
-///             "timestamp3": <rev_reg3>
-///         },
-///         "rev_reg_def3_id": {
-///             "timestamp4": <rev_reg4>
+///         "attr_name" : {
+///             "raw": string,
+///             "reference": string,
+///             "encoded": string
 ///         },
+///         ...
 ///     }
-/// where
-/// attr_referent: Proof-request local identifier of requested attribute
-/// attr_info: Describes requested attribute
-///     {
-///         "name": string, // attribute name, (case insensitive and ignore spaces)
-///         "restrictions": Optional<wql query>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
-///     }
-/// predicate_referent: Proof-request local identifier of requested attribute predicate
-/// predicate_info: Describes requested attribute predicate
-///     {
-///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
-///         "restrictions": Optional<wql query>, // see below
-///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
-///                        // If specified prover must proof non-revocation
-///                        // for date in this interval this attribute
-///                        // (overrides proof level interval)
-///     }
-/// non_revoc_interval: Defines non-revocation interval
+///
+/// #Errors
+/// Common*
+#[cfg(feature = "encoding_conformance_tests")]
+#[no_mangle]
+pub extern fn indy_compare_attribute_encodings(command_handle: CommandHandle,
+                                               values_json: *const c_char,
+                                               reference_encodings_json: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    diverged_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_compare_attribute_encodings: >>> values_json: {:?}, reference_encodings_json: {:?}", values_json, reference_encodings_json);
+
+    check_useful_json!(values_json, ErrorCode::CommonInvalidParam2, HashMap<String, String>);
+    check_useful_json!(reference_encodings_json, ErrorCode::CommonInvalidParam3, HashMap<String, String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_compare_attribute_encodings: entities >>> reference_encodings_json: {:?}", reference_encodings_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::CompareEncoding(
+            values_json,
+            reference_encodings_json,
+            boxed_callback_string!("indy_compare_attribute_encodings", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_compare_attribute_encodings: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds canonical id of revocation registry of `CL_ACCUM` type for the credential definition.
+/// Id is fully qualified if issuer DID is.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// issuer_did: DID of the issuer
+/// cred_def_id: id of credential definition the registry is created for (fully qualified for fully qualified DID)
+/// tag: revocation registry tag, must not contain ':'
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// rev_reg_id: identifier of revocation registry
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_revocation_registry_id(command_handle: CommandHandle,
+                                                issuer_did: *const c_char,
+                                                cred_def_id: *const c_char,
+                                                tag: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode,
+                                                                     rev_reg_id: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_revocation_registry_id: >>> issuer_did: {:?}, cred_def_id: {:?}, tag: {:?}", issuer_did, cred_def_id, tag);
+
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam2, DidValue);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_str!(tag, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_build_revocation_registry_id: entities >>> issuer_did: {:?}, cred_def_id: {:?}, tag: {:?}", issuer_did, cred_def_id, tag);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::BuildRevocationRegistryId(
+            issuer_did,
+            cred_def_id,
+            tag,
+            boxed_callback_string!("indy_build_revocation_registry_id", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_revocation_registry_id: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds `non_revoked` interval of proof request around a ledger accumulator timestamp:
+/// {"from": timestamp - tolerance, "to": timestamp + tolerance}. `from` is 0 for timestamp less than tolerance.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// timestamp: timestamp of revocation registry entry on the ledger
+/// tolerance: seconds the interval extends to both sides of timestamp
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// interval_json: non-revocation interval json
 ///     {
-///         "from": Optional<int>, // timestamp of interval beginning
-///         "to": Optional<int>, // timestamp of interval ending
+///         "from": int,
+///         "to": int,
 ///     }
-/// where wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
-///     The list of allowed fields:
-///         "schema_id": <credential schema id>,
-///         "schema_issuer_did": <credential schema issuer did>,
-///         "schema_name": <credential schema name>,
-///         "schema_version": <credential schema version>,
-///         "issuer_did": <credential issuer did>,
-///         "cred_def_id": <credential definition id>,
-///         "rev_reg_id": <credential revocation registry id>, // "None" as string if not present
 ///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_non_revoked_interval(command_handle: CommandHandle,
+                                              timestamp: u64,
+                                              tolerance: u64,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   interval_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_non_revoked_interval: >>> timestamp: {:?}, tolerance: {:?}", timestamp, tolerance);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::BuildNonRevokedInterval(
+            timestamp,
+            tolerance,
+            boxed_callback_string!("indy_build_non_revoked_interval", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_non_revoked_interval: <<< res: {:?}", res);
+
+    res
+}
+
+/// Combines two proof requests built for the same nonce (for example several policy fragments)
+/// into one proof request asking for everything either of them asks for.
+///
+/// Referents of `other_proof_request_json` colliding with referents of `proof_request_json` are renamed
+/// to the first free `<referent>_2`, `<referent>_3`, ... in referent order; its `same_issuer` groups, `normalize`
+/// entries and `linked_reveal` groups follow the renaming (colliding group names are renamed the same way). Global `non_revoked` intervals are intersected (the most restrictive window is kept)
+/// and the smaller `max_accum_age_secs` is used. Name and version of the first proof request are kept.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// proof_request_json: proof request json (see `indy_prover_get_credentials_for_proof_req`)
+/// other_proof_request_json: proof request json of the same version and with the same nonce
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// valid: true - if signature is valid, false - otherwise
+/// proof_request_json: merged proof request json
 ///
 /// #Errors
-/// Anoncreds*
 /// Common*
-/// Wallet*
 #[no_mangle]
-pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
-                                         proof_request_json: *const c_char,
-                                         proof_json: *const c_char,
-                                         schemas_json: *const c_char,
-                                         credential_defs_json: *const c_char,
-                                         rev_reg_defs_json: *const c_char,
-                                         rev_regs_json: *const c_char,
-                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                              valid: bool)>) -> ErrorCode {
-    trace!("indy_verifier_verify_proof: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
-    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+pub extern fn indy_merge_proof_requests(command_handle: CommandHandle,
+                                        proof_request_json: *const c_char,
+                                        other_proof_request_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             proof_request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_merge_proof_requests: >>> proof_request_json: {:?}, other_proof_request_json: {:?}", proof_request_json, other_proof_request_json);
 
     check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
-    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
-    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
-    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
-    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
-    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+    check_useful_validatable_json!(other_proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_verifier_verify_proof: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
-    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+    trace!("indy_merge_proof_requests: entities >>> proof_request_json: {:?}, other_proof_request_json: {:?}", proof_request_json, other_proof_request_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProof(
+        .send(Command::Anoncreds(AnoncredsCommand::MergeProofRequests(
             proof_request_json,
-            proof_json,
-            schemas_json,
-            credential_defs_json,
-            rev_reg_defs_json,
-            rev_regs_json,
-            Box::new(move |result| {
-                let (err, valid) = prepare_result_1!(result, false);
-                trace!("indy_verifier_verify_proof: valid: {:?}", valid);
-
-                cb(command_handle, err, valid)
-            })
-        ))));
+            other_proof_request_json,
+            boxed_callback_string!("indy_merge_proof_requests", cb, command_handle)
+        )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_verifier_verify_proof: <<< res: {:?}", res);
+    trace!("indy_merge_proof_requests: <<< res: {:?}", res);
 
     res
 }
 
-/// Create revocation state for a credential in the particular time moment.
+/// Checks whether a credential is revoked in a revocation registry without building a proof.
+///
+/// The status is taken as of the accumulator `rev_reg_delta_json` ends with, so the delta has to cover
+/// the registry from its creation: request it from the ledger without `from` (see `indy_build_get_revoc_reg_delta_request`).
+/// Issued and revoked indices of the delta are checked against its accumulator using the tails file.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context
-/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails
 /// rev_reg_def_json: revocation registry definition json related to `rev_reg_id` in a credential
-/// rev_reg_delta_json: revocation registry definition delta json
-/// timestamp: time represented as a total number of seconds from Unix Epoch.
-/// cred_rev_id: user credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
+/// rev_reg_delta_json: revocation registry delta json from registry creation up to the time of interest
+/// cred_rev_id: credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
 /// cb: Callback that takes command result as parameter
 ///
 /// #Returns
-/// revocation state json:
-///     {
-///         "rev_reg": <revocation registry>,
-///         "witness": <witness>,  (opaque type that contains data structures internal to Ursa.
-///                                 It should not be parsed and are likely to change in future versions).
-///         "timestamp" : integer
-///     }
+/// revoked: true - if credential is revoked, false - otherwise
 ///
 /// #Errors
+/// AnoncredsInvalidUserRevocId - credential has never been issued in a registry with issuance on demand
 /// Common*
-/// Wallet*
-/// Anoncreds*
 #[no_mangle]
-pub extern fn indy_create_revocation_state(command_handle: CommandHandle,
-                                           blob_storage_reader_handle: IndyHandle,
-                                           rev_reg_def_json: *const c_char,
-                                           rev_reg_delta_json: *const c_char,
-                                           timestamp: u64,
-                                           cred_rev_id: *const c_char,
-                                           cb: Option<extern fn(
-                                               command_handle_: CommandHandle, err: ErrorCode,
-                                               rev_state_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_create_revocation_state: >>> blob_storage_reader_handle: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, timestamp: {:?}, \
-    cred_rev_id: {:?}", blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+pub extern fn indy_get_revocation_status(command_handle: CommandHandle,
+                                         blob_storage_reader_handle: IndyHandle,
+                                         rev_reg_def_json: *const c_char,
+                                         rev_reg_delta_json: *const c_char,
+                                         cred_rev_id: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              revoked: bool)>) -> ErrorCode {
+    trace!("indy_get_revocation_status: >>> blob_storage_reader_handle: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, cred_rev_id: {:?}",
+           blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, cred_rev_id);
 
     check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam3, RevocationRegistryDefinition);
     check_useful_validatable_json!(rev_reg_delta_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDelta);
-    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam6);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
-    trace!("indy_create_revocation_state: entities >>> blob_storage_reader_handle: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, timestamp: {:?}, \
-    cred_rev_id: {:?}", blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+    trace!("indy_get_revocation_status: entities >>> blob_storage_reader_handle: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, cred_rev_id: {:?}",
+           blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, cred_rev_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateRevocationState(
+        .send(Command::Anoncreds(AnoncredsCommand::GetRevocationStatus(
             blob_storage_reader_handle,
             rev_reg_def_json,
             rev_reg_delta_json,
-            timestamp,
             cred_rev_id,
-            boxed_callback_string!("indy_create_revocation_state", cb, command_handle)
-        ))));
+            Box::new(move |result| {
+                let (err, revoked) = prepare_result_1!(result, false);
+                trace!("indy_get_revocation_status: revoked: {:?}", revoked);
+
+                cb(command_handle, err, revoked)
+            })
+        )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_create_revocation_state: <<< res: {:?}", res);
+    trace!("indy_get_revocation_status: <<< res: {:?}", res);
 
     res
 }
 
-/// Create new revocation state for a credential based on existed state
-/// at the particular time moment (to reduce calculation time).
+/// Returns tails indices the witness of a credential is built from, so a prover can fetch
+/// only these tails instead of the whole tails file when building or updating its revocation state.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context
-/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
-/// rev_state_json: revocation registry state json
 /// rev_reg_def_json: revocation registry definition json related to `rev_reg_id` in a credential
-/// rev_reg_delta_json: revocation registry definition delta json
-/// timestamp: time represented as a total number of seconds from Unix Epoch
-/// cred_rev_id: user credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
+/// rev_reg_delta_json: revocation registry delta json the witness is built from
+/// cred_rev_id: credential revocation id in revocation registry (match to `cred_rev_id` in a credential)
 /// cb: Callback that takes command result as parameter
 ///
 /// #Returns
-/// revocation state json:
-///     {
-///         "rev_reg": <revocation registry>,
-///         "witness": <witness>,  (opaque type that contains data structures internal to Ursa.
-///                                 It should not be parsed and are likely to change in future versions).
-///         "timestamp" : integer
-///     }
+/// indices_json: sorted json array of tails indices
 ///
 /// #Errors
 /// Common*
-/// Wallet*
-/// Anoncreds*
 #[no_mangle]
-pub extern fn indy_update_revocation_state(command_handle: CommandHandle,
-                                           blob_storage_reader_handle: IndyHandle,
-                                           rev_state_json: *const c_char,
-                                           rev_reg_def_json: *const c_char,
-                                           rev_reg_delta_json: *const c_char,
-                                           timestamp: u64,
-                                           cred_rev_id: *const c_char,
-                                           cb: Option<extern fn(
-                                               command_handle_: CommandHandle, err: ErrorCode,
-                                               updated_rev_state_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_update_revocation_state: >>> blob_storage_reader_handle: {:?}, rev_state_json: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, \
-    timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
-
-    check_useful_validatable_json!(rev_state_json, ErrorCode::CommonInvalidParam3, RevocationState);
-    check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDefinition);
-    check_useful_validatable_json!(rev_reg_delta_json, ErrorCode::CommonInvalidParam5, RevocationRegistryDelta);
-    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam7);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+pub extern fn indy_get_witness_dependent_indices(command_handle: CommandHandle,
+                                                 rev_reg_def_json: *const c_char,
+                                                 rev_reg_delta_json: *const c_char,
+                                                 cred_rev_id: *const c_char,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                      err: ErrorCode,
+                                                                      indices_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_witness_dependent_indices: >>> rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, cred_rev_id: {:?}",
+           rev_reg_def_json, rev_reg_delta_json, cred_rev_id);
+
+    check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam2, RevocationRegistryDefinition);
+    check_useful_validatable_json!(rev_reg_delta_json, ErrorCode::CommonInvalidParam3, RevocationRegistryDelta);
+    check_useful_c_str!(cred_rev_id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_update_revocation_state: entities >>> blob_storage_reader_handle: {:?}, rev_state_json: {:?}, rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, \
-    timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
+    trace!("indy_get_witness_dependent_indices: entities >>> rev_reg_def_json: {:?}, rev_reg_delta_json: {:?}, cred_rev_id: {:?}",
+           rev_reg_def_json, rev_reg_delta_json, cred_rev_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::UpdateRevocationState(
-            blob_storage_reader_handle,
-            rev_state_json,
+        .send(Command::Anoncreds(AnoncredsCommand::GetWitnessDependentIndices(
             rev_reg_def_json,
             rev_reg_delta_json,
-            timestamp,
             cred_rev_id,
-            boxed_callback_string!("indy_update_revocation_state", cb, command_handle)
-        ))));
+            Box::new(move |result| {
+                let (err, indices_json) = prepare_result_1!(result, String::new());
+                trace!("indy_get_witness_dependent_indices: indices_json: {:?}", indices_json);
+                let indices_json = ctypes::string_to_cstring(indices_json);
+                cb(command_handle, err, indices_json.as_ptr())
+            })
+        )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_update_revocation_state: <<< res: {:?}", res);
+    trace!("indy_get_witness_dependent_indices: <<< res: {:?}", res);
 
     res
 }
 
+/// Checks whether a credential issued with a credential definition could ever be used to answer a proof request.
+/// Every requested attribute and predicate must be in the schema of the credential definition and their restrictions
+/// must not exclude the schema, the credential definition or its issuer.
+/// Restrictions on attribute values are only known after issuance, so they are assumed to pass.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// proof_request_json: proof request json (see `indy_prover_search_credentials_for_proof_req`)
+/// cred_def_json: credential definition json (see `indy_issuer_create_and_store_credential_def`)
+/// schema_json: schema json the credential definition is created for (see `indy_issuer_create_schema`)
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// satisfiable: true - if a credential of the credential definition could satisfy the proof request, false - otherwise
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_proof_request_could_be_satisfied_by_cred_def(command_handle: CommandHandle,
+                                                                proof_request_json: *const c_char,
+                                                                cred_def_json: *const c_char,
+                                                                schema_json: *const c_char,
+                                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                                     err: ErrorCode,
+                                                                                     satisfiable: bool)>) -> ErrorCode {
+    trace!("indy_proof_request_could_be_satisfied_by_cred_def: >>> proof_request_json: {:?}, cred_def_json: {:?}, schema_json: {:?}",
+           proof_request_json, cred_def_json, schema_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(cred_def_json, ErrorCode::CommonInvalidParam3, CredentialDefinition);
+    check_useful_validatable_json!(schema_json, ErrorCode::CommonInvalidParam4, Schema);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-///  Generates 80-bit numbers that can be used as a nonce for proof request.
+    trace!("indy_proof_request_could_be_satisfied_by_cred_def: entities >>> proof_request_json: {:?}, cred_def_json: {:?}, schema_json: {:?}",
+           proof_request_json, cred_def_json, schema_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::CouldBeSatisfiedByCredDef(
+            proof_request_json,
+            cred_def_json,
+            schema_json,
+            Box::new(move |result| {
+                let (err, satisfiable) = prepare_result_1!(result, false);
+                trace!("indy_proof_request_could_be_satisfied_by_cred_def: satisfiable: {:?}", satisfiable);
+
+                cb(command_handle, err, satisfiable)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_proof_request_could_be_satisfied_by_cred_def: <<< res: {:?}", res);
+
+    res
+}
+
+/// Estimates the size of wallet records holding `count` credentials of a schema with `schema_attr_count` attributes,
+/// e.g. to provision device storage. The estimate is within 25% of the length of the stored credential json;
+/// wallet encryption and tags are not taken into account.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context
+/// schema_attr_count: number of attributes in the schema of credentials
+/// revocable: whether credentials are issued with revocation support
+/// count: number of credentials
 /// cb: Callback that takes command result as parameter
 ///
 /// #Returns
-/// nonce: generated number as a string
+/// size: estimated size in bytes
 ///
+/// #Errors
+/// Common*
 #[no_mangle]
-pub extern fn indy_generate_nonce(command_handle: CommandHandle,
-                                  cb: Option<extern fn(
-                                      command_handle_: CommandHandle, err: ErrorCode,
-                                      nonce: *const c_char)>) -> ErrorCode {
-    trace!("indy_generate_nonce: >>> ");
+pub extern fn indy_estimate_credential_storage(command_handle: CommandHandle,
+                                               schema_attr_count: u32,
+                                               revocable: bool,
+                                               count: u32,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    size: u64)>) -> ErrorCode {
+    trace!("indy_estimate_credential_storage: >>> schema_attr_count: {:?}, revocable: {:?}, count: {:?}", schema_attr_count, revocable, count);
 
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Verifier(
-            VerifierCommand::GenerateNonce(
-                boxed_callback_string!("indy_generate_nonce", cb, command_handle)
-            ))));
+        .send(Command::Anoncreds(AnoncredsCommand::EstimateCredentialStorage(
+            schema_attr_count as usize,
+            revocable,
+            count as usize,
+            Box::new(move |result| {
+                let (err, size) = prepare_result_1!(result, 0);
+                trace!("indy_estimate_credential_storage: size: {:?}", size);
+
+                cb(command_handle, err, size)
+            })
+        )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_generate_nonce: <<< res: {:?}", res);
+    trace!("indy_estimate_credential_storage: <<< res: {:?}", res);
 
     res
 }
 
-/// Get unqualified form (short form without method) of a fully qualified entity like DID.
+/// Compares attributes and versions of two schemas, for example of a schema and its upgraded version,
+/// so an issuer can decide whether credentials issued for the former are still presentable.
 ///
-/// This function should be used to the proper casting of fully qualified entity to unqualified form in the following cases:
-///     Issuer, which works with fully qualified identifiers, creates a Credential Offer for Prover, which doesn't support fully qualified identifiers.
-///     Verifier prepares a Proof Request based on fully qualified identifiers or Prover, which doesn't support fully qualified identifiers.
-///     another case when casting to unqualified form needed
+/// Attribute names are compared in the common view (without spaces, lowercased), so renaming "First Name"
+/// to "firstname" doesn't change the attribute.
 ///
 /// #Params
-/// command_handle: Command handle to map callback to caller context.
-/// entity: string - target entity to disqualify. Can be one of:
-///             Did
-///             SchemaId
-///             CredentialDefinitionId
-///             RevocationRegistryId
-///             CredentialOffer
-///             ProofRequest
+/// command_handle: command handle to map callback to user context
+/// schema_json: schema json (see `indy_issuer_create_schema`)
+/// other_schema_json: schema json to compare with
+/// cb: Callback that takes command result as parameter
 ///
 /// #Returns
-///   res: entity either in unqualified form or original if casting isn't possible
+/// schema_diff_json: difference of `other_schema_json` from `schema_json`
+///     {
+///         "added": [<string>, ...], - sorted attribute names of `other_schema_json` only
+///         "removed": [<string>, ...], - sorted attribute names of `schema_json` only
+///         "unchanged": [<string>, ...], - sorted attribute names of both schemas
+///         "backward_compatible": <bool>, - whether no attribute has been added
+///         "version_order": <int>, - -1, 0 or 1 if version of `schema_json` is lower than, equal to or greater than
+///                                   version of `other_schema_json` (compared as dot separated numbers, so "1.10" > "1.9")
+///     }
+///
+/// #Errors
+/// Common*
 #[no_mangle]
-pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
-                                   entity: *const c_char,
+pub extern fn indy_compare_schemas(command_handle: CommandHandle,
+                                   schema_json: *const c_char,
+                                   other_schema_json: *const c_char,
                                    cb: Option<extern fn(command_handle_: CommandHandle,
                                                         err: ErrorCode,
-                                                        res: *const c_char)>) -> ErrorCode {
-    trace!("indy_to_unqualified: >>> entity: {:?}", entity);
+                                                        schema_diff_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_compare_schemas: >>> schema_json: {:?}, other_schema_json: {:?}", schema_json, other_schema_json);
 
-    check_useful_c_str!(entity, ErrorCode::CommonInvalidParam2);
+    check_useful_validatable_json!(schema_json, ErrorCode::CommonInvalidParam2, Schema);
+    check_useful_validatable_json!(other_schema_json, ErrorCode::CommonInvalidParam3, Schema);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_to_unqualified: entities >>> entity: {:?}", entity);
+    trace!("indy_compare_schemas: entities >>> schema_json: {:?}, other_schema_json: {:?}", schema_json, other_schema_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::ToUnqualified(
-            entity,
-            Box::new(move |result| {
-                let (err, res) = prepare_result_1!(result, String::new());
-                trace!("indy_to_unqualified: did: {:?}", res);
-                let res = ctypes::string_to_cstring(res);
-                cb(command_handle, err, res.as_ptr())
-            }),
+        .send(Command::Anoncreds(AnoncredsCommand::CompareSchemas(
+            schema_json,
+            other_schema_json,
+            boxed_callback_string!("indy_compare_schemas", cb, command_handle)
         )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_to_unqualified: <<< res: {:?}", res);
+    trace!("indy_compare_schemas: <<< res: {:?}", res);
 
     res
 }
-
@@ -118,6 +118,59 @@ pub extern fn indy_submit_request(command_handle: CommandHandle,
     res
 }
 
+/// Publishes read request message to validator pool (no signing) and rejects outdated reply.
+///
+/// Works like indy_submit_request, but the reply is accepted only if its state proof
+/// was signed by the pool not earlier than `freshness_threshold` seconds ago.
+/// Use it for reads where stale data is dangerous, e.g. revocation registry deltas:
+/// an outdated delta may not contain recent revocations.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// request_json: Request data json.
+/// freshness_threshold: max age of state proof timestamp of the reply (in sec).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+/// Ledger*
+/// LedgerStaleState - reply has no state proof or its state proof is older than freshness_threshold
+#[no_mangle]
+pub extern fn indy_submit_request_with_freshness(command_handle: CommandHandle,
+                                                 pool_handle: PoolHandle,
+                                                 request_json: *const c_char,
+                                                 freshness_threshold: u64,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                      err: ErrorCode,
+                                                                      request_result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_submit_request_with_freshness: >>> pool_handle: {:?}, request_json: {:?}, freshness_threshold: {:?}",
+           pool_handle, request_json, freshness_threshold);
+
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_submit_request_with_freshness: entities >>> pool_handle: {:?}, request_json: {:?}, freshness_threshold: {:?}",
+           pool_handle, request_json, freshness_threshold);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::SubmitRequestWithFreshness(
+            pool_handle,
+            request_json,
+            freshness_threshold,
+            boxed_callback_string!("indy_submit_request_with_freshness", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_submit_request_with_freshness: <<< res: {:?}", res);
+
+    res
+}
+
 /// Send action to particular nodes of validator pool.
 ///
 /// The list of requests can be send:
@@ -568,6 +621,7 @@ pub extern fn indy_build_get_attrib_request(command_handle: CommandHandle,
 ///     version: Schema's version string,
 ///     ver: Version of the Schema json
 /// }
+/// Attribute types of a typed schema (see indy_issuer_create_typed_schema) are not written to the ledger.
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
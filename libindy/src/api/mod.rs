@@ -141,6 +141,9 @@ pub enum ErrorCode
     // Caller passed invalid value as param 27 (null, invalid json and etc..)
     CommonInvalidParam27 = 129,
 
+    // Operation was cancelled by the caller before completion
+    CommonCancelled = 130,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -219,9 +222,13 @@ pub enum ErrorCode
     // Item not found on ledger.
     LedgerNotFound = 309,
 
+    // State proof of ledger reply is older than requested freshness threshold
+    LedgerStaleState = 310,
+
     // Revocation registry is full and creation of new registry is necessary
     AnoncredsRevocationRegistryFullError = 400,
 
+    // Revocation index is out of registry bounds or its tail is missing in tails file
     AnoncredsInvalidUserRevocId = 401,
 
     // Attempt to generate master secret with duplicated name
@@ -234,6 +241,9 @@ pub enum ErrorCode
     // Attempt to create credential definition with duplicated id
     AnoncredsCredDefAlreadyExistsError = 407,
 
+    // Signature of received credential or its correctness proof doesn't match credential values
+    AnoncredsInvalidCredentialSignature = 408,
+
     // Crypto errors
     // Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
@@ -272,6 +282,20 @@ pub enum ErrorCode
 ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
 ///         Capturing of backtrace can affect library performance.
 ///         NOTE: must be set before invocation of any other API functions.
+///     "proof_limits": Optional<object> - upper bounds on proof size checked by verifier before verification.
+///         {
+///             "max_sub_proofs": Optional<int> - max number of credentials used in proof (256 by default),
+///             "max_revealed_attrs": Optional<int> - max number of revealed attributes (4096 by default),
+///             "max_predicates": Optional<int> - max number of predicates (4096 by default),
+///         }
+///     "predicate_max_bits": Optional<int> - bit length of signed integers predicate values (attribute values and bounds)
+///         must fit in, checked by prover and verifier (32 by default, at most 128).
+///     "witness_cache_size": Optional<int> - max number of witnesses cached by prover for
+///         unchanged revocation registries (100 by default, 0 disables caching).
+///     "max_schema_attributes": Optional<int> - max number of schema attributes accepted on credential definition
+///         creation (125 by default, at most 125).
+///     "offline": Optional<bool> - disables ledger access: opening of pools and sending of requests fail
+///         with CommonInvalidState. Anoncreds functions don't access ledger and keep working (false by default).
 /// }
 ///
 /// #Errors
@@ -74,6 +74,15 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
 ///         ["name_of_1st_prior_node",  "name_of_2nd_prior_node", .... ]
 ///         Note: Not specified nodes will be placed in a random way.
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
+///     "retry": object (optional) - resending of requests failed with PoolLedgerTimeout:
+///         {
+///             "max_attempts": int (optional) - attempts including the first one (1 by default, i.e. no retry),
+///             "base_delay_ms": int (optional) - delay before the second attempt (500 by default, at most 3600000),
+///             "multiplier": float (optional) - growth of delay for next attempts (2.0 by default),
+///             "jitter_ms": int (optional) - max random delay added to each delay (100 by default, at most 3600000),
+///             "retry_writes": bool (optional) - retry write requests too (false by default, only reads are retried)
+///         }
+///         The error of the last attempt is returned if all attempts fail.
 /// }
 ///
 /// #Returns
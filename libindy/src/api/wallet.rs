@@ -310,6 +310,8 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "progress_step": optional<number> Number of records between progress reports of indy_export_wallet_with_progress.
+///                      0 (default) reports the end of export only.
 ///   }
 ///
 /// #Returns
@@ -335,6 +337,7 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
         .send(Command::Wallet(WalletCommand::Export(
             wallet_handle,
             export_config,
+            None,
             Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_export_wallet: cb command_handle: {:?} err: {:?}", command_handle, err);
@@ -347,6 +350,54 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Exports opened wallet reporting the progress of export
+///
+/// #Params:
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// export_config: JSON containing settings for input operation (see indy_export_wallet).
+/// progress_cb: called with the number of exported records after every "progress_step" records and once more at the end.
+///              total is the number of records in the wallet.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_export_wallet_with_progress(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               export_config: *const c_char,
+                                               progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                             processed: u64,
+                                                                             total: i64)>,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_export_wallet_with_progress: >>> wallet_handle: {:?}, export_config: {:?}", wallet_handle, export_config);
+
+    check_useful_json!(export_config, ErrorCode::CommonInvalidParam3, ExportConfig);
+    check_useful_c_callback!(progress_cb, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_export_wallet_with_progress: params wallet_handle: {:?}, export_config: {:?}", wallet_handle, secret!(&export_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Export(
+            wallet_handle,
+            export_config,
+            Some(_boxed_progress_callback(progress_cb, command_handle)),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_export_wallet_with_progress: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_export_wallet_with_progress: <<< res: {:?}", res);
+    res
+}
+
 
 /// Creates a new secure wallet and then imports its content
 /// according to fields provided in import_config
@@ -386,6 +437,14 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 /// {
 ///   "path": <string>, path of the file that contains exported wallet content
 ///   "key": <string>, key used for export of the wallet
+///   "on_conflict": optional<string> What to do with a record that is already in the wallet:
+///                  error - fail with WalletItemAlreadyExists (used by default)
+///                  skip - keep the record in the wallet
+///                  overwrite - replace value and tags of the record in the wallet
+///                  With skip or overwrite the records are imported into the wallet if it already exists,
+///                  so an interrupted import can be resumed. Such a wallet is kept if import fails.
+///   "progress_step": optional<number> Number of records between progress reports of indy_import_wallet_with_progress.
+///                    0 (default) reports the end of import only.
 /// }
 ///
 /// #Returns
@@ -417,6 +476,7 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
             config,
             credentials,
             import_config,
+            None,
             Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_import_wallet: cb command_handle: {:?}, err: {:?}", command_handle, err);
@@ -429,6 +489,70 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Imports wallet content as indy_import_wallet does reporting the progress of import
+///
+/// #Params
+/// config: Wallet configuration json (see indy_import_wallet).
+/// credentials: Wallet credentials json (see indy_import_wallet).
+/// import_config: Import settings json (see indy_import_wallet).
+/// progress_cb: called with the number of imported records after every "progress_step" records and once more at the end.
+///              total is the number of exported records or -1 if the export file doesn't contain it.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_with_progress(command_handle: CommandHandle,
+                                               config: *const c_char,
+                                               credentials: *const c_char,
+                                               import_config: *const c_char,
+                                               progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                             processed: u64,
+                                                                             total: i64)>,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_with_progress: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, import_config: {:?}, cb: {:?}",
+           command_handle, config, credentials, import_config, cb);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam4, ExportConfig);
+    check_useful_c_callback!(progress_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_import_wallet_with_progress: params config: {:?}, credentials: {:?}, import_config: {:?}",
+           config, secret!(&credentials), secret!(&import_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Import(
+            config,
+            credentials,
+            import_config,
+            Some(_boxed_progress_callback(progress_cb, command_handle)),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_with_progress: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_with_progress: <<< res: {:?}", res);
+    res
+}
+
+fn _boxed_progress_callback(progress_cb: extern fn(command_handle_: CommandHandle, processed: u64, total: i64),
+                            command_handle: CommandHandle) -> Box<dyn Fn(usize, Option<usize>) + Send> {
+    Box::new(move |processed, total| {
+        let total = total.map(|total| total as i64).unwrap_or(-1);
+        trace!("progress_cb command_handle: {:?}, processed: {:?}, total: {:?}", command_handle, processed, total);
+        progress_cb(command_handle, processed as u64, total)
+    })
+}
+
 
 /// Closes opened wallet and frees allocated resources.
 ///
@@ -2,16 +2,18 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use named_type::NamedType;
 use ursa::cl::{
     new_nonce,
     RevocationRegistryDelta as CryptoRevocationRegistryDelta,
     Witness,
 };
-use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey};
+use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey, RevocationKeyPrivate, RevocationTailsGenerator};
+use ursa::cl::RevocationRegistry as CryptoRevocationRegistry;
 
 use commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use commands::anoncreds::AnoncredsCommand;
-use domain::anoncreds::credential::{CredentialValues, Credential};
+use domain::anoncreds::credential::{CredentialValues, Credential, IssuedCredentialValues};
 use domain::anoncreds::credential_definition::{
     CredentialDefinition,
     CredentialDefinitionConfig,
@@ -24,7 +26,7 @@ use domain::anoncreds::credential_definition::{
     CredentialDefinitionId
 };
 use domain::anoncreds::credential_offer::CredentialOffer;
-use domain::anoncreds::credential_request::CredentialRequest;
+use domain::anoncreds::credential_request::{CredentialIssuanceRequest, CredentialRequest};
 use domain::anoncreds::revocation_registry::{
     RevocationRegistry,
     RevocationRegistryV1,
@@ -37,23 +39,31 @@ use domain::anoncreds::revocation_registry_definition::{
     RevocationRegistryDefinitionPrivate,
     RevocationRegistryDefinitionV1,
     RevocationRegistryDefinitionValue,
+    RevocationRegistryDefinitionValuePublicKeys,
+    RevocationRegistryExportConfig,
     RevocationRegistryInfo,
-    RevocationRegistryId
+    RevocationRegistryId,
+    RevocationRegistrySnapshot,
+    RevocationRegistryStatus,
+    RegistryState
 };
 use domain::anoncreds::revocation_registry_delta::{
     RevocationRegistryDelta,
     RevocationRegistryDeltaV1,
 };
-use domain::anoncreds::schema::{AttributeNames, Schema, SchemaV1, SchemaId};
+use domain::anoncreds::schema::{AttributeNames, AttributeTypes, Schema, SchemaV1, SchemaId};
 use domain::crypto::did::DidValue;
 use domain::wallet::Tags;
 use errors::prelude::*;
 use services::anoncreds::AnoncredsService;
-use services::anoncreds::helpers::parse_cred_rev_id;
+use services::anoncreds::helpers::{parse_cred_rev_id, parse_cred_rev_ids, RedactedAttributes};
 use services::blob_storage::BlobStorageService;
 use services::crypto::CryptoService;
 use services::pool::PoolService;
-use services::wallet::{RecordOptions, WalletService};
+use services::wallet::{KeyDerivationData, RecordOptions, SearchOptions, WalletService};
+use utils::cancellation::CancellationToken;
+use utils::crypto::base64;
+use utils::validation::Validatable;
 
 use super::tails::{SDKTailsAccessor, store_tails_from_generator};
 use api::{WalletHandle, CommandHandle, next_command_handle};
@@ -64,6 +74,7 @@ pub enum IssuerCommand {
         String, // name
         String, // version
         AttributeNames, // attribute names
+        Option<AttributeTypes>, // attribute types
         BoxedCallbackStringStringSend),
     CreateAndStoreCredentialDefinition(
         WalletHandle,
@@ -112,10 +123,32 @@ pub enum IssuerCommand {
         RevocationRegistryConfig, // config
         i32, // tails writer handle
         Box<dyn Fn(IndyResult<(String, String, String)>) + Send>),
+    CreateAndStoreRevocationRegistryContinue(
+        WalletHandle,
+        RegistryType, // type
+        String, // tag
+        CredentialDefinitionId, // credential definition id
+        RevocationRegistryId, // revocation registry id
+        IssuanceType, // issuance type
+        u32, // max credential number
+        i32, // tails writer handle
+        IndyResult<(RevocationRegistryDefinitionValuePublicKeys,
+                    RevocationKeyPrivate,
+                    CryptoRevocationRegistry,
+                    RevocationTailsGenerator)>,
+        CommandHandle),
     CreateCredentialOffer(
         WalletHandle,
         CredentialDefinitionId, // credential definition id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    ValidateAttributesForSchema(
+        Schema, // schema
+        CredentialValues, // credential values
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeCredentialValues(
+        Schema, // schema
+        HashMap<String, String>, // raw values
+        Box<dyn Fn(IndyResult<String>) + Send>),
     CreateCredential(
         WalletHandle,
         CredentialOffer, // credential offer
@@ -124,12 +157,31 @@ pub enum IssuerCommand {
         Option<RevocationRegistryId>, // revocation registry id
         Option<i32>, // blob storage reader config handle
         Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    CreateCredentials(
+        WalletHandle,
+        Vec<CredentialIssuanceRequest>, // credential issuance requests
+        Option<RevocationRegistryId>, // revocation registry id
+        Option<i32>, // blob storage reader config handle
+        CancellationToken,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ReissueCredential(
+        WalletHandle,
+        CredentialOffer, // credential offer for the new credential definition
+        CredentialRequest, // credential request for the new credential definition
+        Credential, // credential issued under the previous credential definition
+        Box<dyn Fn(IndyResult<String>) + Send>),
     RevokeCredential(
         WalletHandle,
         i32, // blob storage reader config handle
         RevocationRegistryId, //revocation registry id
         String, //credential revoc id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    RevokeCredentials(
+        WalletHandle,
+        i32, // blob storage reader config handle
+        RevocationRegistryId, //revocation registry id
+        Vec<String>, //credential revoc ids
+        Box<dyn Fn(IndyResult<String>) + Send>),
     /*    RecoverCredential(
             WalletHandle,
             i32, // blob storage reader config handle
@@ -140,6 +192,33 @@ pub enum IssuerCommand {
         RevocationRegistryDelta, //revocation registry delta
         RevocationRegistryDelta, //other revocation registry delta
         Box<dyn Fn(IndyResult<String>) + Send>),
+    MarkRevocationRegistryRotated(
+        WalletHandle,
+        RevocationRegistryId, //revocation registry id
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    ListRevocationRegistries(
+        WalletHandle,
+        CredentialDefinitionId, // credential definition id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocationRegistryCapacity(
+        WalletHandle,
+        RevocationRegistryId, //revocation registry id
+        f32, // nearly full threshold
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetUnusedRevocationIndices(
+        WalletHandle,
+        RevocationRegistryId, //revocation registry id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ExportRevocationRegistry(
+        WalletHandle,
+        RevocationRegistryId, //revocation registry id
+        RevocationRegistryExportConfig, //export config
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ImportRevocationRegistry(
+        WalletHandle,
+        RevocationRegistryExportConfig, //import config
+        String, //revocation registry snapshot
+        Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 pub struct IssuerCommandExecutor {
@@ -150,6 +229,7 @@ pub struct IssuerCommandExecutor {
     pub crypto_service: Rc<CryptoService>,
     pending_str_str_callbacks: RefCell<HashMap<CommandHandle, BoxedCallbackStringStringSend>>,
     pending_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    pending_str_str_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String, String)>) + Send>>>,
 }
 
 impl IssuerCommandExecutor {
@@ -166,14 +246,15 @@ impl IssuerCommandExecutor {
             crypto_service,
             pending_str_str_callbacks: RefCell::new(HashMap::new()),
             pending_str_callbacks: RefCell::new(HashMap::new()),
+            pending_str_str_str_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
     pub fn execute(&self, command: IssuerCommand) {
         match command {
-            IssuerCommand::CreateSchema(issuer_did, name, version, attrs, cb) => {
+            IssuerCommand::CreateSchema(issuer_did, name, version, attrs, attr_types, cb) => {
                 debug!(target: "issuer_command_executor", "CreateSchema command received");
-                cb(self.create_schema(&issuer_did, &name, &version, attrs));
+                cb(self.create_schema(&issuer_did, &name, &version, attrs, attr_types));
             }
             IssuerCommand::CreateAndStoreCredentialDefinition(wallet_handle, issuer_did, schema, tag, type_, config, cb) => {
                 debug!(target: "issuer_command_executor", "CreateAndStoreCredentialDefinition command received");
@@ -199,26 +280,53 @@ impl IssuerCommandExecutor {
             IssuerCommand::CreateAndStoreRevocationRegistry(wallet_handle, issuer_did, type_, tag, cred_def_id, config,
                                                             tails_writer_handle, cb) => {
                 debug!(target: "issuer_command_executor", "CreateAndStoreRevocationRegistryRegistry command received");
-                cb(self.create_and_store_revocation_registry(wallet_handle,
-                                                             &issuer_did,
-                                                             type_.as_ref().map(String::as_str),
-                                                             &tag,
-                                                             &cred_def_id,
-                                                             &config,
-                                                             tails_writer_handle));
+                self.create_and_store_revocation_registry(wallet_handle,
+                                                          &issuer_did,
+                                                          type_.as_ref().map(String::as_str),
+                                                          &tag,
+                                                          &cred_def_id,
+                                                          &config,
+                                                          tails_writer_handle,
+                                                          cb);
+            }
+            IssuerCommand::CreateAndStoreRevocationRegistryContinue(wallet_handle, rev_reg_type, tag, cred_def_id, rev_reg_id, issuance_type,
+                                                                    max_cred_num, tails_writer_handle, result, cb_id) => {
+                debug!(target: "issuer_command_executor", "CreateAndStoreRevocationRegistryContinue command received");
+                self._create_and_store_revocation_registry_continue(cb_id, wallet_handle, rev_reg_type, &tag, &cred_def_id, &rev_reg_id,
+                                                                    issuance_type, max_cred_num, tails_writer_handle, result);
             }
             IssuerCommand::CreateCredentialOffer(wallet_handle, cred_def_id, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredentialOffer command received");
                 cb(self.create_credential_offer(wallet_handle, &cred_def_id));
             }
+            IssuerCommand::ValidateAttributesForSchema(schema, cred_values, cb) => {
+                debug!(target: "issuer_command_executor", "ValidateAttributesForSchema command received");
+                cb(self.validate_attributes_for_schema(&SchemaV1::from(schema), &cred_values));
+            }
+            IssuerCommand::EncodeCredentialValues(schema, raw_values, cb) => {
+                debug!(target: "issuer_command_executor", "EncodeCredentialValues command received");
+                cb(self.encode_credential_values(&SchemaV1::from(schema), &raw_values));
+            }
             IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredential command received");
                 cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle));
             }
+            IssuerCommand::CreateCredentials(wallet_handle, issuance_requests, rev_reg_id, blob_storage_reader_handle, cancellation, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentials command received");
+                cb(self.new_credentials(wallet_handle, &issuance_requests, rev_reg_id.as_ref(), blob_storage_reader_handle, &cancellation));
+            }
+            IssuerCommand::ReissueCredential(wallet_handle, cred_offer, cred_req, old_credential, cb) => {
+                debug!(target: "issuer_command_executor", "ReissueCredential command received");
+                cb(self.reissue_credential(wallet_handle, &cred_offer, &cred_req, &old_credential));
+            }
             IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
             }
+            IssuerCommand::RevokeCredentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_ids, cb) => {
+                debug!(target: "issuer_command_executor", "RevokeCredentials command received");
+                cb(self.revoke_credentials(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_ids));
+            }
             /*            IssuerCommand::RecoverCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                             debug!(target: "issuer_command_executor", "RecoverCredential command received");
                             cb(self.recovery_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
@@ -228,6 +336,30 @@ impl IssuerCommandExecutor {
                 cb(self.merge_revocation_registry_deltas(&mut RevocationRegistryDeltaV1::from(rev_reg_delta),
                                                          &RevocationRegistryDeltaV1::from(other_rev_reg_delta)));
             }
+            IssuerCommand::MarkRevocationRegistryRotated(wallet_handle, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "MarkRevocationRegistryRotated command received");
+                cb(self.mark_revocation_registry_rotated(wallet_handle, &rev_reg_id));
+            }
+            IssuerCommand::ListRevocationRegistries(wallet_handle, cred_def_id, cb) => {
+                debug!(target: "issuer_command_executor", "ListRevocationRegistries command received");
+                cb(self.list_revocation_registries(wallet_handle, &cred_def_id));
+            }
+            IssuerCommand::GetRevocationRegistryCapacity(wallet_handle, rev_reg_id, threshold, cb) => {
+                debug!(target: "issuer_command_executor", "GetRevocationRegistryCapacity command received");
+                cb(self.get_revocation_registry_capacity(wallet_handle, &rev_reg_id, threshold));
+            }
+            IssuerCommand::GetUnusedRevocationIndices(wallet_handle, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetUnusedRevocationIndices command received");
+                cb(self.get_unused_revocation_indices(wallet_handle, &rev_reg_id));
+            }
+            IssuerCommand::ExportRevocationRegistry(wallet_handle, rev_reg_id, export_config, cb) => {
+                debug!(target: "issuer_command_executor", "ExportRevocationRegistry command received");
+                cb(self.export_revocation_registry(wallet_handle, &rev_reg_id, &export_config));
+            }
+            IssuerCommand::ImportRevocationRegistry(wallet_handle, import_config, snapshot, cb) => {
+                debug!(target: "issuer_command_executor", "ImportRevocationRegistry command received");
+                cb(self.import_revocation_registry(wallet_handle, &import_config, &snapshot));
+            }
         };
     }
 
@@ -235,8 +367,9 @@ impl IssuerCommandExecutor {
                      issuer_did: &DidValue,
                      name: &str,
                      version: &str,
-                     attrs: AttributeNames) -> IndyResult<(String, String)> {
-        debug!("create_schema >>> issuer_did: {:?}, name: {:?}, version: {:?}, attrs: {:?}", issuer_did, name, version, attrs);
+                     attrs: AttributeNames,
+                     attr_types: Option<AttributeTypes>) -> IndyResult<(String, String)> {
+        debug!("create_schema >>> issuer_did: {:?}, name: {:?}, version: {:?}, attrs: {:?}, attr_types: {:?}", issuer_did, name, version, attrs, attr_types);
 
         self.crypto_service.validate_did(issuer_did)?;
 
@@ -248,8 +381,12 @@ impl IssuerCommandExecutor {
             version: version.to_string(),
             attr_names: attrs,
             seq_no: None,
+            attribute_types: attr_types,
         });
 
+        schema.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
         let schema_json = serde_json::to_string(&schema)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Schema")?;
 
@@ -547,10 +684,62 @@ impl IssuerCommandExecutor {
                                             tag: &str,
                                             cred_def_id: &CredentialDefinitionId,
                                             config: &RevocationRegistryConfig,
-                                            tails_writer_handle: i32) -> IndyResult<(String, String, String)> {
+                                            tails_writer_handle: i32,
+                                            cb: Box<dyn Fn(IndyResult<(String, String, String)>) + Send>) {
         debug!("create_and_store_revocation_registry >>> wallet_handle: {:?}, issuer_did: {:?}, type_: {:?}, tag: {:?}, cred_def_id: {:?}, config: {:?}, \
                tails_handle: {:?}", wallet_handle, issuer_did, type_, tag, cred_def_id, config, tails_writer_handle);
 
+        let (rev_reg_type, rev_reg_id) =
+            try_cb!(self._prepare_create_and_store_revocation_registry(issuer_did, type_, tag, cred_def_id), cb);
+
+        if let (Ok(rev_reg_def), Ok(rev_reg)) = (self.wallet_service.get_indy_record_value::<RevocationRegistryDefinition>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value()),
+                                                 self.wallet_service.get_indy_record_value::<RevocationRegistry>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())) {
+            return cb(Ok((cred_def_id.0.to_string(), rev_reg_def, rev_reg)));
+        }
+
+        let cred_def: CredentialDefinition = try_cb!(self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value()), cb);
+        let cred_def = CredentialDefinitionV1::from(cred_def);
+
+        let issuance_type = config.issuance_type.clone().unwrap_or(IssuanceType::ISSUANCE_ON_DEMAND);
+        let max_cred_num = config.max_cred_num.unwrap_or(100000);
+
+        let cb_id = next_command_handle();
+        self.pending_str_str_str_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let tag = tag.to_string();
+        let cred_def_id = cred_def_id.clone();
+        let issuer_did = issuer_did.clone();
+
+        // building of the accumulator is CPU-heavy, so it is performed in the crypto thread pool
+        ::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let res = ::services::anoncreds::issuer::Issuer::new_revocation_registry(&cred_def,
+                                                                                     max_cred_num,
+                                                                                     issuance_type.to_bool(),
+                                                                                     &issuer_did);
+            CommandExecutor::instance().send(
+                Command::Anoncreds(
+                    AnoncredsCommand::Issuer(
+                        IssuerCommand::CreateAndStoreRevocationRegistryContinue(
+                            wallet_handle,
+                            rev_reg_type,
+                            tag,
+                            cred_def_id,
+                            rev_reg_id,
+                            issuance_type,
+                            max_cred_num,
+                            tails_writer_handle,
+                            res,
+                            cb_id,
+                        ))
+                )).unwrap();
+        });
+    }
+
+    fn _prepare_create_and_store_revocation_registry(&self,
+                                                     issuer_did: &DidValue,
+                                                     type_: Option<&str>,
+                                                     tag: &str,
+                                                     cred_def_id: &CredentialDefinitionId) -> IndyResult<(RegistryType, RevocationRegistryId)> {
         match (issuer_did.get_method(), cred_def_id.get_method()) {
             (None, Some(_)) => {
                 return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "You can't use unqualified Did with fully qualified Credential Definition"));
@@ -568,23 +757,47 @@ impl IssuerCommandExecutor {
             RegistryType::CL_ACCUM
         };
 
-        let issuance_type = config.issuance_type.clone().unwrap_or(IssuanceType::ISSUANCE_ON_DEMAND);
-        let max_cred_num = config.max_cred_num.unwrap_or(100000);
-
         let rev_reg_id = RevocationRegistryId::new(&issuer_did, &cred_def_id, &rev_reg_type.to_str(), tag);
 
-        if let (Ok(rev_reg_def), Ok(rev_reg)) = (self.wallet_service.get_indy_record_value::<RevocationRegistryDefinition>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value()),
-                                                 self.wallet_service.get_indy_record_value::<RevocationRegistry>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())) {
-            return Ok((cred_def_id.0.to_string(), rev_reg_def, rev_reg));
-        }
+        Ok((rev_reg_type, rev_reg_id))
+    }
 
-        let cred_def: CredentialDefinition = self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+    fn _create_and_store_revocation_registry_continue(&self,
+                                                      cb_id: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      rev_reg_type: RegistryType,
+                                                      tag: &str,
+                                                      cred_def_id: &CredentialDefinitionId,
+                                                      rev_reg_id: &RevocationRegistryId,
+                                                      issuance_type: IssuanceType,
+                                                      max_cred_num: u32,
+                                                      tails_writer_handle: i32,
+                                                      result: IndyResult<(RevocationRegistryDefinitionValuePublicKeys,
+                                                                          RevocationKeyPrivate,
+                                                                          CryptoRevocationRegistry,
+                                                                          RevocationTailsGenerator)>) {
+        let cb = self.pending_str_str_str_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        cb(result
+            .and_then(|result| {
+                self._complete_create_and_store_revocation_registry(wallet_handle, rev_reg_type, tag, cred_def_id, rev_reg_id,
+                                                                    issuance_type, max_cred_num, tails_writer_handle, result)
+            }))
+    }
 
-        let (revoc_public_keys, revoc_key_private, revoc_registry, mut revoc_tails_generator) =
-            self.anoncreds_service.issuer.new_revocation_registry(&CredentialDefinitionV1::from(cred_def),
-                                                                  max_cred_num,
-                                                                  issuance_type.to_bool(),
-                                                                  &issuer_did)?;
+    fn _complete_create_and_store_revocation_registry(&self,
+                                                      wallet_handle: WalletHandle,
+                                                      rev_reg_type: RegistryType,
+                                                      tag: &str,
+                                                      cred_def_id: &CredentialDefinitionId,
+                                                      rev_reg_id: &RevocationRegistryId,
+                                                      issuance_type: IssuanceType,
+                                                      max_cred_num: u32,
+                                                      tails_writer_handle: i32,
+                                                      res: (RevocationRegistryDefinitionValuePublicKeys,
+                                                            RevocationKeyPrivate,
+                                                            CryptoRevocationRegistry,
+                                                            RevocationTailsGenerator)) -> IndyResult<(String, String, String)> {
+        let (revoc_public_keys, revoc_key_private, revoc_registry, mut revoc_tails_generator) = res;
 
         let (tails_location, tails_hash) =
             store_tails_from_generator(self.blob_storage_service.clone(), tails_writer_handle, &mut revoc_tails_generator)?;
@@ -628,6 +841,7 @@ impl IssuerCommandExecutor {
             id: rev_reg_id.clone(),
             curr_id: 0,
             used_ids: HashSet::new(),
+            state: RegistryState::Active,
         };
 
         self.wallet_service.add_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info, &HashMap::new())?;
@@ -635,7 +849,7 @@ impl IssuerCommandExecutor {
         debug!("create_and_store_revocation_registry <<< rev_reg_id: {:?}, revoc_reg_def_json: {:?}, revoc_reg_json: {:?}",
                rev_reg_id, revoc_reg_def_json, revoc_reg_json);
 
-        Ok((rev_reg_id.0, revoc_reg_def_json, revoc_reg_json))
+        Ok((rev_reg_id.0.clone(), revoc_reg_def_json, revoc_reg_json))
     }
 
     fn create_credential_offer(&self,
@@ -666,6 +880,36 @@ impl IssuerCommandExecutor {
         Ok(credential_offer_json)
     }
 
+    fn validate_attributes_for_schema(&self,
+                                      schema: &SchemaV1,
+                                      cred_values: &CredentialValues) -> IndyResult<String> {
+        debug!("validate_attributes_for_schema >>> schema: {:?}, cred_values: {:?}", schema, RedactedAttributes(cred_values));
+
+        let problems = ::services::anoncreds::issuer::Issuer::validate_attributes_for_schema(schema, cred_values);
+
+        let problems_json = serde_json::to_string(&problems)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize attribute problems")?;
+
+        debug!("validate_attributes_for_schema <<< problems_json: {:?}", problems_json);
+
+        Ok(problems_json)
+    }
+
+    fn encode_credential_values(&self,
+                                schema: &SchemaV1,
+                                raw_values: &HashMap<String, String>) -> IndyResult<String> {
+        debug!("encode_credential_values >>> schema: {:?}, raw_values: {:?}", schema, RedactedAttributes(raw_values));
+
+        let cred_values = ::services::anoncreds::issuer::Issuer::encode_attributes(schema, raw_values)?;
+
+        let cred_values_json = serde_json::to_string(&cred_values)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialValues")?;
+
+        debug!("encode_credential_values <<< cred_values_json: {:?}", RedactedAttributes(&cred_values));
+
+        Ok(cred_values_json)
+    }
+
     fn new_credential(&self,
                       wallet_handle: WalletHandle,
                       cred_offer: &CredentialOffer,
@@ -674,13 +918,88 @@ impl IssuerCommandExecutor {
                       rev_reg_id: Option<&RevocationRegistryId>,
                       blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>, Option<String>)> {
         debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
-               wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
+               wallet_handle, secret!(&cred_offer), secret!(&cred_request), RedactedAttributes(cred_values), rev_reg_id, blob_storage_reader_handle);
+
+        let (mut credentials, rev_reg_delta, _) =
+            self._new_credentials(wallet_handle, &[(cred_offer, cred_request, cred_values)], rev_reg_id, blob_storage_reader_handle, &|| false)?;
+
+        let (credential, cred_rev_id) = credentials.remove(0);
+
+        let cred_json = serde_json::to_string(&credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        let rev_reg_delta_json = rev_reg_delta
+            .as_ref()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", sensitive!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+
+        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+    }
 
-        let cred_def_id = match cred_offer.method_name {
+    fn new_credentials(&self,
+                       wallet_handle: WalletHandle,
+                       issuance_requests: &[CredentialIssuanceRequest],
+                       rev_reg_id: Option<&RevocationRegistryId>,
+                       blob_storage_reader_handle: Option<i32>,
+                       cancellation: &CancellationToken) -> IndyResult<String> {
+        debug!("new_credentials >>> wallet_handle: {:?}, issuance_requests: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, sensitive!(&issuance_requests), rev_reg_id, blob_storage_reader_handle);
+
+        let requests: Vec<(&CredentialOffer, &CredentialRequest, &CredentialValues)> = issuance_requests
+            .iter()
+            .map(|request| (&request.cred_offer, &request.cred_req, &request.cred_values))
+            .collect();
+
+        let (credentials, rev_reg_delta, cancelled) =
+            self._new_credentials(wallet_handle, &requests, rev_reg_id, blob_storage_reader_handle, &|| cancellation.is_cancelled())?;
+
+        let result = json!({
+            "credentials": credentials
+                .into_iter()
+                .map(|(credential, cred_rev_id)| json!({"cred": credential, "cred_rev_id": cred_rev_id}))
+                .collect::<Vec<serde_json::Value>>(),
+            "rev_reg_delta": rev_reg_delta,
+            "cancelled": cancelled,
+        });
+
+        let result_json = serde_json::to_string(&result)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize issued Credentials")?;
+
+        debug!("new_credentials <<< result_json: {:?}", sensitive!(&result_json));
+
+        Ok(result_json)
+    }
+
+    /// Issues credentials for all requests against the same credential definition and revocation registry.
+    /// Wallet entities are loaded once and the updated registry is stored only after every credential
+    /// is signed, so a failure leaves the registry untouched.
+    /// On cancellation the credentials issued so far are kept, the registry is stored with their indices only
+    /// and the returned flag is set.
+    fn _new_credentials(&self,
+                        wallet_handle: WalletHandle,
+                        requests: &[(&CredentialOffer, &CredentialRequest, &CredentialValues)],
+                        rev_reg_id: Option<&RevocationRegistryId>,
+                        blob_storage_reader_handle: Option<i32>,
+                        is_cancelled: &dyn Fn() -> bool) -> IndyResult<(Vec<(Credential, Option<String>)>, Option<RevocationRegistryDelta>, bool)> {
+        let &(first_offer, _, _) = requests.first()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Empty list of credential issuance requests"))?;
+
+        let qualified_cred_def_id = |cred_offer: &CredentialOffer| match cred_offer.method_name {
             Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
             None => cred_offer.cred_def_id.clone()
         };
 
+        let cred_def_id = qualified_cred_def_id(first_offer);
+
+        if let Some(&(cred_offer, _, _)) = requests.iter().find(|&&(cred_offer, _, _)| qualified_cred_def_id(cred_offer) != cred_def_id) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("All credentials of a batch must be issued for one credential definition, got {:?} and {:?}",
+                                       cred_def_id, cred_offer.cred_def_id)));
+        }
+
         let cred_def: CredentialDefinitionV1 =
             CredentialDefinitionV1::from(
                 self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
@@ -689,7 +1008,7 @@ impl IssuerCommandExecutor {
             self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
 
         let (rev_reg_def, mut rev_reg,
-            rev_reg_def_priv, sdk_tails_accessor, rev_reg_info) = match rev_reg_id {
+            rev_reg_def_priv, sdk_tails_accessor, mut rev_reg_info) = match rev_reg_id {
             Some(ref r_reg_id) => {
                 let rev_reg_def: RevocationRegistryDefinitionV1 =
                     RevocationRegistryDefinitionV1::from(
@@ -702,17 +1021,7 @@ impl IssuerCommandExecutor {
                 let rev_key_priv: RevocationRegistryDefinitionPrivate =
                     self.wallet_service.get_indy_object(wallet_handle, &r_reg_id.0, &RecordOptions::id_value())?;
 
-                let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
-
-                rev_reg_info.curr_id += 1;
-
-                if rev_reg_info.curr_id > rev_reg_def.value.max_cred_num {
-                    return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
-                }
-
-                if rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
-                    rev_reg_info.used_ids.insert(rev_reg_info.curr_id);
-                }
+                let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
 
                 // TODO: FIXME: Review error kind!
                 let blob_storage_reader_handle = blob_storage_reader_handle
@@ -727,72 +1036,137 @@ impl IssuerCommandExecutor {
             None => (None, None, None, None, None)
         };
 
-        let (credential_signature, signature_correctness_proof, rev_reg_delta) =
-            self.anoncreds_service.issuer.new_credential(&cred_def,
-                                                         &cred_def_priv_key.value,
-                                                         &cred_offer.nonce,
-                                                         &cred_request,
-                                                         &cred_values,
-                                                         rev_reg_info.as_ref().map(|r_reg_info| r_reg_info.curr_id),
-                                                         rev_reg_def.as_ref(),
-                                                         rev_reg.as_mut().map(|r_reg| &mut r_reg.value),
-                                                         rev_reg_def_priv.as_ref().map(|r_reg_def_priv| &r_reg_def_priv.value),
-                                                         sdk_tails_accessor.as_ref())?;
-
-        let witness =
-            if let (&Some(ref r_reg_def), &Some(ref r_reg), &Some(ref rev_tails_accessor), &Some(ref rev_reg_info)) =
-            (&rev_reg_def, &rev_reg, &sdk_tails_accessor, &rev_reg_info) {
-                let (issued, revoked) = match r_reg_def.value.issuance_type {
-                    IssuanceType::ISSUANCE_ON_DEMAND => (rev_reg_info.used_ids.clone(), HashSet::new()),
-                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), rev_reg_info.used_ids.clone())
-                };
+        let cred_rev_reg_id = match (rev_reg_id, first_offer.method_name.as_ref()) {
+            (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
+            (rev_reg_id, _) => rev_reg_id.cloned()
+        };
+
+        let mut rev_reg_deltas: Vec<CryptoRevocationRegistryDelta> = Vec::new();
 
-                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &r_reg.value, &issued, &revoked);
+        let (credentials, cancelled) = issue_until_cancelled(requests, is_cancelled, &mut |&(cred_offer, cred_request, cred_values)| {
+            if let (&Some(ref r_reg_def), &mut Some(ref mut r_reg_info)) = (&rev_reg_def, &mut rev_reg_info) {
+                ::services::anoncreds::issuer::Issuer::allocate_revocation_index(r_reg_info, r_reg_def.value.max_cred_num, &r_reg_def.value.issuance_type)?;
+            }
 
-                Some(Witness::new(rev_reg_info.curr_id, r_reg_def.value.max_cred_num,
-                                  r_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, rev_tails_accessor)?)
-            } else {
-                None
+            let (credential_signature, signature_correctness_proof, rev_reg_delta) =
+                self.anoncreds_service.issuer.new_credential(&cred_def,
+                                                             &cred_def_priv_key.value,
+                                                             &cred_offer.nonce,
+                                                             &cred_request,
+                                                             &cred_values,
+                                                             rev_reg_info.as_ref().map(|r_reg_info| r_reg_info.curr_id),
+                                                             rev_reg_def.as_ref(),
+                                                             rev_reg.as_mut().map(|r_reg| &mut r_reg.value),
+                                                             rev_reg_def_priv.as_ref().map(|r_reg_def_priv| &r_reg_def_priv.value),
+                                                             sdk_tails_accessor.as_ref())?;
+
+            rev_reg_deltas.extend(rev_reg_delta);
+
+            let witness =
+                if let (&Some(ref r_reg_def), &Some(ref r_reg), &Some(ref rev_tails_accessor), &Some(ref rev_reg_info)) =
+                (&rev_reg_def, &rev_reg, &sdk_tails_accessor, &rev_reg_info) {
+                    let (issued, revoked) = match r_reg_def.value.issuance_type {
+                        IssuanceType::ISSUANCE_ON_DEMAND => (rev_reg_info.used_ids.clone(), HashSet::new()),
+                        IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), rev_reg_info.used_ids.clone())
+                    };
+
+                    let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &r_reg.value, &issued, &revoked);
+
+                    Some(Witness::new(rev_reg_info.curr_id, r_reg_def.value.max_cred_num,
+                                      r_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, rev_tails_accessor)?)
+                } else {
+                    None
+                };
+
+            let credential = Credential {
+                schema_id: cred_offer.schema_id.clone(),
+                cred_def_id: cred_offer.cred_def_id.clone(),
+                rev_reg_id: cred_rev_reg_id.clone(),
+                values: cred_values.clone(),
+                signature: credential_signature,
+                signature_correctness_proof,
+                rev_reg: rev_reg.as_ref().map(|r_reg| r_reg.value.clone()),
+                witness,
             };
 
-        let cred_rev_reg_id = match (rev_reg_id, cred_offer.method_name.as_ref()) {
-            (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
-            (rev_reg_id, _) => rev_reg_id.cloned()
-        };
+            let cred_rev_id = rev_reg_info.as_ref().map(|r_reg_info| r_reg_info.curr_id.to_string());
 
-        let credential = Credential {
-            schema_id: cred_offer.schema_id.clone(),
-            cred_def_id: cred_offer.cred_def_id.clone(),
-            rev_reg_id: cred_rev_reg_id,
-            values: cred_values.clone(),
-            signature: credential_signature,
-            signature_correctness_proof,
-            rev_reg: rev_reg.map(|r_reg| r_reg.value),
-            witness,
+            Ok((credential, cred_rev_id))
+        })?;
+
+        let rev_reg_delta = if rev_reg_deltas.is_empty() {
+            None
+        } else {
+            let rev_reg_delta = ::services::anoncreds::issuer::Issuer::compact_revocation_registry_deltas(&rev_reg_deltas)?;
+            Some(RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: rev_reg_delta }))
         };
 
-        let cred_json = serde_json::to_string(&credential)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+        for &(ref credential, _) in credentials.iter() {
+            let issued_values = IssuedCredentialValues {
+                cred_def_id: credential.cred_def_id.clone(),
+                values: credential.values.clone(),
+            };
 
-        let rev_reg_delta_json = rev_reg_delta
-            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
-            .as_ref()
-            .map(serde_json::to_string)
-            .map_or(Ok(None), |v| v.map(Some))
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+            self.wallet_service.upsert_indy_object(wallet_handle, &self._issued_credential_values_id(credential)?, &issued_values)?;
+        }
 
-        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (credential.rev_reg, rev_reg_id, rev_reg_info.clone()) {
-            let revoc_reg = RevocationRegistry::RevocationRegistryV1(RevocationRegistryV1 { value: r_reg });
+        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (rev_reg, rev_reg_id, rev_reg_info) {
+            let revoc_reg = RevocationRegistry::RevocationRegistryV1(r_reg);
 
             self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
             self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
         };
 
-        let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
+        Ok((credentials, rev_reg_delta, cancelled))
+    }
+
+    fn reissue_credential(&self,
+                          wallet_handle: WalletHandle,
+                          cred_offer: &CredentialOffer,
+                          cred_request: &CredentialRequest,
+                          old_credential: &Credential) -> IndyResult<String> {
+        debug!("reissue_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, old_credential: {:?}",
+               wallet_handle, secret!(&cred_offer), secret!(&cred_request), sensitive!(&old_credential));
+
+        // Only credentials issued by this issuer can be migrated
+        self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &old_credential.cred_def_id.0, &RecordOptions::id_value())
+            .map_err(|err| err.extend(format!("Credential definition {:?} of the credential is not owned by the issuer", old_credential.cred_def_id)))?;
+
+        if old_credential.schema_id.to_unqualified() != cred_offer.schema_id.to_unqualified() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Credential schema {:?} doesn't match schema {:?} of the credential offer",
+                                       old_credential.schema_id, cred_offer.schema_id)));
+        }
 
-        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+        // The issuer can't verify the signature of a credential bound to the holder's master secret,
+        // so only the values it has signed itself are re-issued.
+        let issued_values: IssuedCredentialValues =
+            self.wallet_service.get_indy_opt_object(wallet_handle, &self._issued_credential_values_id(old_credential)?, &RecordOptions::id_value())?
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Credential was not issued by the issuer"))?;
 
-        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+        if issued_values.values != old_credential.values {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Credential values don't match the values signed by the issuer"));
+        }
+
+        let (cred_json, _, _) = self.new_credential(wallet_handle, cred_offer, cred_request, &issued_values.values, None, None)?;
+
+        debug!("reissue_credential <<< cred_json: {:?}", sensitive!(&cred_json));
+
+        Ok(cred_json)
+    }
+
+    // Issued credentials are identified by the primary signature value `A`,
+    // which the prover keeps as is when processing the credential signature.
+    fn _issued_credential_values_id(&self, credential: &Credential) -> IndyResult<String> {
+        let signature = serde_json::to_value(&credential.signature)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialSignature")?;
+
+        let a = signature["p_credential"]["a"].as_str()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Primary signature not found in Credential"))?;
+
+        let hash = ::utils::crypto::hash::hash(format!("{}:{}", credential.cred_def_id.to_unqualified().0, a).as_bytes())?;
+
+        Ok(hex::encode(hash))
     }
 
     fn revoke_credential(&self,
@@ -803,12 +1177,44 @@ impl IssuerCommandExecutor {
         debug!("revoke_credential >>> wallet_handle: {:?}, blob_storage_reader_handle:  {:?}, rev_reg_id: {:?}, cred_revoc_id: {:?}",
                wallet_handle, blob_storage_reader_handle, rev_reg_id, secret!(cred_revoc_id));
 
-        let cred_revoc_id = parse_cred_rev_id(cred_revoc_id)?;
+        let rev_reg_delta_json = self._revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, &[cred_revoc_id.to_string()])?;
+
+        debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
+
+        Ok(rev_reg_delta_json)
+    }
+
+    fn revoke_credentials(&self,
+                          wallet_handle: WalletHandle,
+                          blob_storage_reader_handle: i32,
+                          rev_reg_id: &RevocationRegistryId,
+                          cred_revoc_ids: &[String]) -> IndyResult<String> {
+        debug!("revoke_credentials >>> wallet_handle: {:?}, blob_storage_reader_handle:  {:?}, rev_reg_id: {:?}, cred_revoc_ids: {:?}",
+               wallet_handle, blob_storage_reader_handle, rev_reg_id, secret!(cred_revoc_ids));
 
+        let rev_reg_delta_json = self._revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_ids)?;
+
+        debug!("revoke_credentials <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
+
+        Ok(rev_reg_delta_json)
+    }
+
+    fn _revoke_credentials(&self,
+                           wallet_handle: WalletHandle,
+                           blob_storage_reader_handle: i32,
+                           rev_reg_id: &RevocationRegistryId,
+                           cred_revoc_ids: &[String]) -> IndyResult<String> {
         let revocation_registry_definition: RevocationRegistryDefinitionV1 =
             RevocationRegistryDefinitionV1::from(
                 self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
 
+        // every id may be given as a compact list of ids and ranges like "3,5,10-14"
+        let cred_revoc_ids = if cred_revoc_ids.is_empty() {
+            Vec::new()
+        } else {
+            parse_cred_rev_ids(&cred_revoc_ids.join(","), revocation_registry_definition.value.max_cred_num)?
+        };
+
         let mut rev_reg: RevocationRegistryV1 =
             RevocationRegistryV1::from(
                 self._wallet_get_rev_reg(wallet_handle, &rev_reg_id)?);
@@ -817,27 +1223,31 @@ impl IssuerCommandExecutor {
                                                        blob_storage_reader_handle,
                                                        &revocation_registry_definition)?;
 
-        if cred_revoc_id > revocation_registry_definition.value.max_cred_num + 1 {
-            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-        }
-
         let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
 
-        match revocation_registry_definition.value.issuance_type {
-            IssuanceType::ISSUANCE_ON_DEMAND => {
-                if !rev_reg_info.used_ids.remove(&cred_revoc_id) {
-                    return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-                };
+        for &cred_revoc_id in cred_revoc_ids.iter() {
+            if cred_revoc_id > revocation_registry_definition.value.max_cred_num + 1 {
+                return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
             }
-            IssuanceType::ISSUANCE_BY_DEFAULT => {
-                if !rev_reg_info.used_ids.insert(cred_revoc_id) {
-                    return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+
+            // Accumulator update doesn't know whether the index is currently valid:
+            // revoking an already revoked or not issued index would corrupt the accumulator.
+            match revocation_registry_definition.value.issuance_type {
+                IssuanceType::ISSUANCE_ON_DEMAND => {
+                    if !rev_reg_info.used_ids.remove(&cred_revoc_id) {
+                        return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+                    };
                 }
-            }
-        };
+                IssuanceType::ISSUANCE_BY_DEFAULT => {
+                    if !rev_reg_info.used_ids.insert(cred_revoc_id) {
+                        return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+                    }
+                }
+            };
+        }
 
         let rev_reg_delta =
-            self.anoncreds_service.issuer.revoke(&mut rev_reg.value, revocation_registry_definition.value.max_cred_num, cred_revoc_id, &sdk_tails_accessor)?;
+            self.anoncreds_service.issuer.revoke_batch(&mut rev_reg.value, revocation_registry_definition.value.max_cred_num, &cred_revoc_ids, &sdk_tails_accessor)?;
 
         let rev_reg_delta = RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: rev_reg_delta });
 
@@ -849,8 +1259,6 @@ impl IssuerCommandExecutor {
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
 
-        debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
-
         Ok(rev_reg_delta_json)
     }
 
@@ -930,6 +1338,224 @@ impl IssuerCommandExecutor {
         Ok(merged_rev_reg_delta_json)
     }
 
+    fn mark_revocation_registry_rotated(&self,
+                                        wallet_handle: WalletHandle,
+                                        rev_reg_id: &RevocationRegistryId) -> IndyResult<()> {
+        debug!("mark_revocation_registry_rotated >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+        let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?;
+
+        rev_reg_info.state = RegistryState::Rotated;
+
+        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
+
+        debug!("mark_revocation_registry_rotated <<<");
+
+        Ok(())
+    }
+
+    fn list_revocation_registries(&self,
+                                  wallet_handle: WalletHandle,
+                                  cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
+        debug!("list_revocation_registries >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+        let cred_def_id = cred_def_id.to_unqualified();
+
+        let mut rev_reg_defs_search =
+            self.wallet_service.search_indy_records::<RevocationRegistryDefinition>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        let mut statuses: Vec<RevocationRegistryStatus> = Vec::new();
+
+        while let Some(record) = rev_reg_defs_search.fetch_next_record()? {
+            let value = record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("RevocationRegistryDefinition not found for id: {}", record.get_id())))?;
+
+            let rev_reg_def: RevocationRegistryDefinitionV1 =
+                RevocationRegistryDefinitionV1::from(
+                    serde_json::from_str::<RevocationRegistryDefinition>(value)
+                        .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistryDefinition")?);
+
+            if rev_reg_def.cred_def_id.to_unqualified() != cred_def_id {
+                continue;
+            }
+
+            let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &RevocationRegistryId(record.get_id().to_string()))?;
+
+            statuses.push(RevocationRegistryStatus::new(&rev_reg_info, rev_reg_def.value.max_cred_num));
+        }
+
+        statuses.sort_by(|a, b| a.rev_reg_id.0.cmp(&b.rev_reg_id.0));
+
+        let res = serde_json::to_string(&statuses)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of RevocationRegistryStatus")?;
+
+        debug!("list_revocation_registries <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_revocation_registry_capacity(&self,
+                                        wallet_handle: WalletHandle,
+                                        rev_reg_id: &RevocationRegistryId,
+                                        threshold: f32) -> IndyResult<String> {
+        debug!("get_revocation_registry_capacity >>> wallet_handle: {:?}, rev_reg_id: {:?}, threshold: {:?}", wallet_handle, rev_reg_id, threshold);
+
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid nearly full threshold: {}, expected value in (0, 1]", threshold)));
+        }
+
+        let rev_reg_def: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?);
+
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?;
+
+        let mut capacity = serde_json::to_value(RevocationRegistryStatus::new(&rev_reg_info, rev_reg_def.value.max_cred_num))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryStatus")?;
+
+        capacity["nearly_full"] = json!(rev_reg_info.is_nearly_full(rev_reg_def.value.max_cred_num, threshold));
+
+        let res = capacity.to_string();
+
+        debug!("get_revocation_registry_capacity <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_unused_revocation_indices(&self,
+                                     wallet_handle: WalletHandle,
+                                     rev_reg_id: &RevocationRegistryId) -> IndyResult<String> {
+        debug!("get_unused_revocation_indices >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+        let rev_reg_def: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?);
+
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?;
+
+        let unused_indices = rev_reg_info.unused_indices(rev_reg_def.value.max_cred_num);
+
+        let res = serde_json::to_string(&unused_indices)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize unused revocation indices")?;
+
+        debug!("get_unused_revocation_indices <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn export_revocation_registry(&self,
+                                  wallet_handle: WalletHandle,
+                                  rev_reg_id: &RevocationRegistryId,
+                                  export_config: &RevocationRegistryExportConfig) -> IndyResult<String> {
+        debug!("export_revocation_registry >>> wallet_handle: {:?}, rev_reg_id: {:?}, export_config: {:?}", wallet_handle, rev_reg_id, secret!(export_config));
+
+        let snapshot = RevocationRegistrySnapshot {
+            rev_reg_def: self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?,
+            rev_reg_def_priv: self.wallet_service.get_indy_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?,
+            rev_reg: self._wallet_get_rev_reg(wallet_handle, rev_reg_id)?,
+            rev_reg_info: self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?,
+        };
+
+        let snapshot = serde_json::to_vec(&snapshot)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistrySnapshot")?;
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method);
+
+        let sealed = ::services::anoncreds::issuer::Issuer::seal_revocation_registry_snapshot(&snapshot, &key_data)?;
+
+        let res = base64::encode(&sealed);
+
+        debug!("export_revocation_registry <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn import_revocation_registry(&self,
+                                  wallet_handle: WalletHandle,
+                                  import_config: &RevocationRegistryExportConfig,
+                                  snapshot: &str) -> IndyResult<String> {
+        debug!("import_revocation_registry >>> wallet_handle: {:?}, import_config: {:?}, snapshot: {:?}", wallet_handle, secret!(import_config), snapshot);
+
+        let sealed = base64::decode(snapshot)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Cannot decode revocation registry snapshot from base64: {}", err)))?;
+
+        let snapshot = ::services::anoncreds::issuer::Issuer::open_revocation_registry_snapshot(&sealed, &import_config.key)?;
+
+        let snapshot: RevocationRegistrySnapshot = serde_json::from_slice(&snapshot)
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize RevocationRegistrySnapshot")?;
+
+        let rev_reg_id = match snapshot.rev_reg_def {
+            RevocationRegistryDefinition::RevocationRegistryDefinitionV1(ref rev_reg_def) => rev_reg_def.id.clone()
+        };
+
+        if snapshot.rev_reg_info.id != rev_reg_id {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Revocation registry snapshot is inconsistent: info is for {:?}, definition is for {:?}", snapshot.rev_reg_info.id, rev_reg_id)));
+        }
+
+        let stored = self._wallet_get_rev_reg_records(wallet_handle, &rev_reg_id)?;
+
+        if let (Some(stored_rev_reg), Some(stored_rev_reg_info)) = (stored.rev_reg.as_ref(), stored.rev_reg_info.as_ref()) {
+            // Restoring older state would hand out already issued indices again
+            // and bring back revoked credentials, so only newer or the same state is accepted.
+            let is_same = snapshot.rev_reg_info.curr_id == stored_rev_reg_info.curr_id
+                && snapshot.rev_reg_info.used_ids == stored_rev_reg_info.used_ids
+                && snapshot.rev_reg_info.state == stored_rev_reg_info.state
+                && serde_json::to_value(&snapshot.rev_reg).ok() == serde_json::to_value(stored_rev_reg).ok();
+
+            if !is_same && snapshot.rev_reg_info.curr_id <= stored_rev_reg_info.curr_id {
+                return Err(err_msg(IndyErrorKind::InvalidState,
+                                   format!("Revocation registry snapshot is older than the state stored in the wallet: snapshot curr_id {}, stored curr_id {}",
+                                           snapshot.rev_reg_info.curr_id, stored_rev_reg_info.curr_id)));
+            }
+        }
+
+        // Wallet storage has no transactions: if any write fails, the records stored before import are put back,
+        // so the registry is never left half imported.
+        if let Err(err) = self._wallet_set_rev_reg_records(wallet_handle, &rev_reg_id, &snapshot) {
+            if let Err(rollback_err) = self._wallet_restore_rev_reg_records(wallet_handle, &rev_reg_id, &stored) {
+                error!("import_revocation_registry: cannot restore revocation registry {:?} after failed import: {:?}", rev_reg_id, rollback_err);
+            }
+            return Err(err);
+        }
+
+        debug!("import_revocation_registry <<< rev_reg_id: {:?}", rev_reg_id);
+
+        Ok(rev_reg_id.0)
+    }
+
+    fn _wallet_get_rev_reg_records(&self, wallet_handle: WalletHandle, rev_reg_id: &RevocationRegistryId) -> IndyResult<StoredRevocationRegistry> {
+        Ok(StoredRevocationRegistry {
+            rev_reg_def: self.wallet_service.get_indy_opt_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?,
+            rev_reg_def_priv: self.wallet_service.get_indy_opt_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?,
+            rev_reg: self.wallet_service.get_indy_opt_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?,
+            rev_reg_info: self.wallet_service.get_indy_opt_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?,
+        })
+    }
+
+    fn _wallet_set_rev_reg_records(&self, wallet_handle: WalletHandle, rev_reg_id: &RevocationRegistryId, snapshot: &RevocationRegistrySnapshot) -> IndyResult<()> {
+        self.wallet_service.upsert_indy_object(wallet_handle, &rev_reg_id.0, &snapshot.rev_reg_def)?;
+        self.wallet_service.upsert_indy_object(wallet_handle, &rev_reg_id.0, &snapshot.rev_reg_def_priv)?;
+        self.wallet_service.upsert_indy_object(wallet_handle, &rev_reg_id.0, &snapshot.rev_reg)?;
+        // issuance info goes last: registry becomes usable from the imported state only when all of it is in place
+        self.wallet_service.upsert_indy_object(wallet_handle, &rev_reg_id.0, &snapshot.rev_reg_info)?;
+        Ok(())
+    }
+
+    fn _wallet_restore_rev_reg_records(&self, wallet_handle: WalletHandle, rev_reg_id: &RevocationRegistryId, stored: &StoredRevocationRegistry) -> IndyResult<()> {
+        self._wallet_restore_indy_object(wallet_handle, &rev_reg_id.0, &stored.rev_reg_info)?;
+        self._wallet_restore_indy_object(wallet_handle, &rev_reg_id.0, &stored.rev_reg)?;
+        self._wallet_restore_indy_object(wallet_handle, &rev_reg_id.0, &stored.rev_reg_def_priv)?;
+        self._wallet_restore_indy_object(wallet_handle, &rev_reg_id.0, &stored.rev_reg_def)
+    }
+
+    fn _wallet_restore_indy_object<T>(&self, wallet_handle: WalletHandle, id: &str, stored: &Option<T>) -> IndyResult<()>
+        where T: ::serde::Serialize + Sized + NamedType {
+        match *stored {
+            Some(ref object) => self.wallet_service.upsert_indy_object(wallet_handle, id, object).map(|_| ()),
+            None if self.wallet_service.record_exists::<T>(wallet_handle, id)? => self.wallet_service.delete_indy_record::<T>(wallet_handle, id),
+            None => Ok(())
+        }
+    }
+
     // TODO: DELETE IT
     fn _wallet_set_schema_id(&self, wallet_handle: WalletHandle, id: &str, schema_id: &SchemaId) -> IndyResult<()> {
         self.wallet_service.add_record(wallet_handle, &self.wallet_service.add_prefix("SchemaId"), id, &schema_id.0, &Tags::new())
@@ -956,3 +1582,234 @@ impl IssuerCommandExecutor {
         self.wallet_service.get_indy_object(wallet_handle, &key.0, &RecordOptions::id_value())
     }
 }
+
+/// Records of a revocation registry as they are stored in the wallet before import.
+struct StoredRevocationRegistry {
+    rev_reg_def: Option<RevocationRegistryDefinition>,
+    rev_reg_def_priv: Option<RevocationRegistryDefinitionPrivate>,
+    rev_reg: Option<RevocationRegistry>,
+    rev_reg_info: Option<RevocationRegistryInfo>,
+}
+
+/// Issues credentials for the requests in order, checking for cancellation before each of them,
+/// so the credential being issued when the batch is cancelled is always completed.
+/// Returns issued credentials and whether the batch has been cancelled before all requests were served.
+fn issue_until_cancelled<R, C>(requests: &[R],
+                               is_cancelled: &dyn Fn() -> bool,
+                               issue: &mut dyn FnMut(&R) -> IndyResult<C>) -> IndyResult<(Vec<C>, bool)> {
+    let mut credentials = Vec::with_capacity(requests.len());
+
+    for request in requests {
+        if is_cancelled() {
+            return Ok((credentials, true));
+        }
+
+        credentials.push(issue(request)?);
+    }
+
+    Ok((credentials, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+
+    mod issue_until_cancelled {
+        use super::*;
+
+        const REQUESTS: [u32; 5] = [1, 2, 3, 4, 5];
+
+        #[test]
+        fn issue_until_cancelled_works() {
+            let (issued, cancelled) = issue_until_cancelled(&REQUESTS, &|| false, &mut |&request| Ok(request)).unwrap();
+            assert_eq!(REQUESTS.to_vec(), issued);
+            assert!(!cancelled);
+        }
+
+        #[test]
+        fn issue_until_cancelled_works_for_cancellation_after_second_credential() {
+            let cancellation = Cell::new(false);
+            let mut attempted = 0;
+
+            let (issued, cancelled) = issue_until_cancelled(&REQUESTS, &|| cancellation.get(), &mut |&request| {
+                attempted += 1;
+                if attempted == 2 {
+                    // cancellation signaled while the second credential is being issued
+                    cancellation.set(true);
+                }
+                Ok(request)
+            }).unwrap();
+
+            assert_eq!(vec![1, 2], issued);
+            assert_eq!(2, attempted);
+            assert!(cancelled);
+        }
+
+        #[test]
+        fn issue_until_cancelled_works_for_cancellation_before_start() {
+            let (issued, cancelled) = issue_until_cancelled(&REQUESTS, &|| true, &mut |&request| Ok(request)).unwrap();
+            assert!(issued.is_empty());
+            assert!(cancelled);
+        }
+
+        #[test]
+        fn issue_until_cancelled_works_for_failed_credential() {
+            let res = issue_until_cancelled(&REQUESTS, &|| false, &mut |&request| {
+                if request == 3 {
+                    return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
+                }
+                Ok(request)
+            });
+            assert_kind!(IndyErrorKind::RevocationRegistryFull, res);
+        }
+    }
+
+    mod new_credentials {
+        use super::*;
+
+        use domain::anoncreds::credential::AttributeValues;
+        use domain::wallet::{Config, Credentials, KeyDerivationMethod};
+        use utils::environment;
+        use utils::test;
+
+        const WALLET_KEY: &str = "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw";
+        const ISSUER_DID: &str = "NcYxiDXkpYi6ov5FcYDi1e";
+        const MAX_CRED_NUM: u32 = 5;
+
+        fn _executor() -> IssuerCommandExecutor {
+            IssuerCommandExecutor::new(Rc::new(AnoncredsService::new()),
+                                       Rc::new(PoolService::new()),
+                                       Rc::new(BlobStorageService::new()),
+                                       Rc::new(WalletService::new()),
+                                       Rc::new(CryptoService::new()))
+        }
+
+        fn _open_wallet(executor: &IssuerCommandExecutor, name: &str) -> WalletHandle {
+            let config = Config { id: name.to_string(), storage_type: None, storage_config: None };
+            let credentials = Credentials {
+                key: WALLET_KEY.to_string(),
+                rekey: None,
+                storage_credentials: None,
+                key_derivation_method: KeyDerivationMethod::RAW,
+                rekey_derivation_method: KeyDerivationMethod::RAW,
+            };
+
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt(WALLET_KEY, &KeyDerivationMethod::RAW);
+            executor.wallet_service.create_wallet(&config, &credentials, (&key_data, &key_data.calc_master_key().unwrap())).unwrap();
+
+            let (wallet_handle, key_data, _) = executor.wallet_service.open_wallet_prepare(&config, &credentials).unwrap();
+            executor.wallet_service.open_wallet_continue(wallet_handle, (&key_data.calc_master_key().unwrap(), None)).unwrap()
+        }
+
+        // stores credential definition and revocation registry with issuance on demand, returns tails reader handle
+        fn _prepare_revocation_registry(executor: &IssuerCommandExecutor,
+                                        wallet_handle: WalletHandle,
+                                        name: &str) -> (CredentialDefinitionId, RevocationRegistryId, i32) {
+            let issuer_did = DidValue(ISSUER_DID.to_string());
+
+            let mut schema = SchemaV1 {
+                id: SchemaId::new(&issuer_did, "gvt", "1.0"),
+                name: "gvt".to_string(),
+                version: "1.0".to_string(),
+                attr_names: vec!["name".to_string(), "age".to_string()].into_iter().collect(),
+                seq_no: None,
+                attribute_types: None,
+            };
+
+            let (_, schema_id, cred_def_id, signature_type) =
+                executor._prepare_create_and_store_credential_definition(&issuer_did, &mut schema, "tag", None, None).unwrap();
+            let cred_def_res = ::services::anoncreds::issuer::Issuer::new_credential_definition(&schema.attr_names, true).unwrap();
+            executor._complete_create_and_store_credential_definition(wallet_handle, &schema, &schema_id, &cred_def_id, "tag", signature_type, cred_def_res).unwrap();
+
+            let cred_def = CredentialDefinitionV1::from(
+                executor.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value()).unwrap());
+
+            let config = json!({"base_dir": environment::tmp_path().join(name).to_str().unwrap(), "uri_pattern": ""}).to_string();
+            let tails_writer_handle = executor.blob_storage_service.open_writer("default", &config).unwrap();
+
+            let (rev_reg_type, rev_reg_id) =
+                executor._prepare_create_and_store_revocation_registry(&issuer_did, None, "tag", &cred_def_id).unwrap();
+            let rev_reg_res = ::services::anoncreds::issuer::Issuer::new_revocation_registry(&cred_def, MAX_CRED_NUM, false, &issuer_did).unwrap();
+            executor._complete_create_and_store_revocation_registry(wallet_handle, rev_reg_type, "tag", &cred_def_id, &rev_reg_id,
+                                                                    IssuanceType::ISSUANCE_ON_DEMAND, MAX_CRED_NUM, tails_writer_handle, rev_reg_res).unwrap();
+
+            let tails_reader_handle = executor.blob_storage_service.open_reader("default", &config).unwrap();
+
+            (cred_def_id, rev_reg_id, tails_reader_handle)
+        }
+
+        fn _issuance_request(executor: &IssuerCommandExecutor,
+                             wallet_handle: WalletHandle,
+                             cred_def_id: &CredentialDefinitionId) -> (CredentialOffer, CredentialRequest, CredentialValues) {
+            let cred_offer: CredentialOffer = serde_json::from_str(&executor.create_credential_offer(wallet_handle, cred_def_id).unwrap()).unwrap();
+
+            let cred_def = CredentialDefinitionV1::from(
+                executor.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value()).unwrap());
+
+            let master_secret = executor.anoncreds_service.prover.new_master_secret().unwrap();
+            let (blinded_ms, _, blinded_ms_correctness_proof) =
+                executor.anoncreds_service.prover.new_credential_request(&cred_def, &master_secret, &cred_offer).unwrap();
+
+            let cred_request = CredentialRequest {
+                prover_did: DidValue(ISSUER_DID.to_string()),
+                cred_def_id: cred_def_id.clone(),
+                blinded_ms,
+                blinded_ms_correctness_proof,
+                nonce: new_nonce().unwrap(),
+            };
+
+            let mut cred_values = CredentialValues::new();
+            cred_values.insert("name".to_string(), AttributeValues { raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string() });
+            cred_values.insert("age".to_string(), AttributeValues { raw: "28".to_string(), encoded: "28".to_string() });
+
+            (cred_offer, cred_request, cred_values)
+        }
+
+        #[test]
+        fn new_credentials_works_for_cancelled_batch() {
+            let name = "new_credentials_works_for_cancelled_batch";
+            test::cleanup_storage(name);
+            {
+                let executor = _executor();
+                let wallet_handle = _open_wallet(&executor, name);
+
+                let (cred_def_id, rev_reg_id, tails_reader_handle) = _prepare_revocation_registry(&executor, wallet_handle, name);
+
+                let requests: Vec<(CredentialOffer, CredentialRequest, CredentialValues)> =
+                    (0..4).map(|_| _issuance_request(&executor, wallet_handle, &cred_def_id)).collect();
+                let requests: Vec<(&CredentialOffer, &CredentialRequest, &CredentialValues)> =
+                    requests.iter().map(|&(ref cred_offer, ref cred_req, ref cred_values)| (cred_offer, cred_req, cred_values)).collect();
+
+                // cancellation signaled while the second credential is being issued
+                let checks = Cell::new(0);
+                let (credentials, rev_reg_delta, cancelled) =
+                    executor._new_credentials(wallet_handle, &requests, Some(&rev_reg_id), Some(tails_reader_handle), &|| {
+                        checks.set(checks.get() + 1);
+                        checks.get() > 2
+                    }).unwrap();
+
+                assert!(cancelled);
+                let cred_rev_ids: Vec<Option<String>> = credentials.into_iter().map(|(_, cred_rev_id)| cred_rev_id).collect();
+                assert_eq!(vec![Some("1".to_string()), Some("2".to_string())], cred_rev_ids);
+
+                let issued: HashSet<u32> = vec![1, 2].into_iter().collect();
+
+                let rev_reg_info = executor._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id).unwrap();
+                assert_eq!(2, rev_reg_info.curr_id);
+                assert_eq!(issued, rev_reg_info.used_ids);
+
+                let rev_reg_delta = serde_json::to_value(&RevocationRegistryDeltaV1::from(rev_reg_delta.unwrap()).value).unwrap();
+                let delta_issued: HashSet<u32> = serde_json::from_value(rev_reg_delta["issued"].clone()).unwrap();
+                assert_eq!(issued, delta_issued);
+                assert!(rev_reg_delta["revoked"].as_array().map(|revoked| revoked.is_empty()).unwrap_or(true));
+
+                // stored registry ends with the accumulator of the returned delta
+                let rev_reg = serde_json::to_value(&RevocationRegistryV1::from(executor._wallet_get_rev_reg(wallet_handle, &rev_reg_id).unwrap()).value).unwrap();
+                assert_eq!(rev_reg["accum"], rev_reg_delta["accum"]);
+            }
+            test::cleanup_storage(name);
+        }
+    }
+}
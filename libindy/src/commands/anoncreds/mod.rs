@@ -6,16 +6,30 @@ mod tails;
 use commands::anoncreds::issuer::{IssuerCommand, IssuerCommandExecutor};
 use commands::anoncreds::prover::{ProverCommand, ProverCommandExecutor};
 use commands::anoncreds::verifier::{VerifierCommand, VerifierCommandExecutor};
+use commands::anoncreds::tails::SDKTailsAccessor;
 
 use services::anoncreds::AnoncredsService;
 use services::blob_storage::BlobStorageService;
 use services::pool::PoolService;
 use services::wallet::WalletService;
 use services::crypto::CryptoService;
-use services::anoncreds::helpers::to_unqualified;
+use services::anoncreds::helpers::{parse_cred_rev_id, to_unqualified};
+#[cfg(feature = "encoding_conformance_tests")]
+use services::anoncreds::helpers::compare_encodings;
+use services::anoncreds::prover::Prover;
 
+use domain::anoncreds::credential::estimate_credential_storage;
+use domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionId};
+use domain::anoncreds::proof_request::{NonRevocedInterval, ProofRequest};
+use domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1, RevocationRegistryId};
+use domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
+use domain::anoncreds::schema::{compare_schema_versions, Schema, SchemaV1};
+use domain::crypto::did::DidValue;
 use errors::prelude::*;
+use utils::validation::Validatable;
 
+#[cfg(feature = "encoding_conformance_tests")]
+use std::collections::HashMap;
 use std::rc::Rc;
 
 pub enum AnoncredsCommand {
@@ -24,13 +38,57 @@ pub enum AnoncredsCommand {
     Verifier(VerifierCommand),
     ToUnqualified(
         String, // entity
-        Box<dyn Fn(IndyResult<String>) + Send>)
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    #[cfg(feature = "encoding_conformance_tests")]
+    CompareEncoding(
+        HashMap<String, String>, // values
+        HashMap<String, String>, // reference encodings
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    BuildRevocationRegistryId(
+        DidValue, // issuer did
+        CredentialDefinitionId, // credential definition id
+        String, // tag
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    BuildNonRevokedInterval(
+        u64, // timestamp
+        u64, // tolerance
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    MergeProofRequests(
+        ProofRequest, // proof request
+        ProofRequest, // other proof request
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocationStatus(
+        i32, // blob storage reader handle
+        RevocationRegistryDefinition, // revocation registry definition
+        RevocationRegistryDelta, // revocation registry delta
+        String, // credential revocation id
+        Box<dyn Fn(IndyResult<bool>) + Send>),
+    GetWitnessDependentIndices(
+        RevocationRegistryDefinition, // revocation registry definition
+        RevocationRegistryDelta, // revocation registry delta
+        String, // credential revocation id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CouldBeSatisfiedByCredDef(
+        ProofRequest, // proof request
+        CredentialDefinition, // credential definition
+        Schema, // schema
+        Box<dyn Fn(IndyResult<bool>) + Send>),
+    CompareSchemas(
+        Schema, // schema
+        Schema, // other schema
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EstimateCredentialStorage(
+        usize, // schema attribute count
+        bool, // revocable
+        usize, // credential count
+        Box<dyn Fn(IndyResult<u64>) + Send>)
 }
 
 pub struct AnoncredsCommandExecutor {
     issuer_command_cxecutor: IssuerCommandExecutor,
     prover_command_cxecutor: ProverCommandExecutor,
-    verifier_command_cxecutor: VerifierCommandExecutor
+    verifier_command_cxecutor: VerifierCommandExecutor,
+    blob_storage_service: Rc<BlobStorageService>
 }
 
 impl AnoncredsCommandExecutor {
@@ -45,8 +103,8 @@ impl AnoncredsCommandExecutor {
                 blob_storage_service.clone(), wallet_service.clone(), crypto_service.clone()),
             prover_command_cxecutor: ProverCommandExecutor::new(
                 anoncreds_service.clone(), wallet_service.clone(), crypto_service.clone(), blob_storage_service.clone()),
-            verifier_command_cxecutor: VerifierCommandExecutor::new(
-                anoncreds_service.clone()),
+            verifier_command_cxecutor: VerifierCommandExecutor::verifier_only(),
+            blob_storage_service,
         }
     }
 
@@ -68,6 +126,145 @@ impl AnoncredsCommandExecutor {
                 debug!("ToUnqualified command received");
                 cb(to_unqualified(&entity));
             }
+            #[cfg(feature = "encoding_conformance_tests")]
+            AnoncredsCommand::CompareEncoding(values, reference_encodings, cb) => {
+                debug!("CompareEncoding command received");
+                cb(self.compare_encoding(&values, &reference_encodings));
+            }
+            AnoncredsCommand::BuildRevocationRegistryId(did, cred_def_id, tag, cb) => {
+                debug!("BuildRevocationRegistryId command received");
+                cb(self.build_revocation_registry_id(&did, &cred_def_id, &tag));
+            }
+            AnoncredsCommand::BuildNonRevokedInterval(timestamp, tolerance, cb) => {
+                debug!("BuildNonRevokedInterval command received");
+                cb(self.build_non_revoked_interval(timestamp, tolerance));
+            }
+            AnoncredsCommand::MergeProofRequests(proof_req, other_proof_req, cb) => {
+                debug!("MergeProofRequests command received");
+                cb(self.merge_proof_requests(proof_req, other_proof_req));
+            }
+            AnoncredsCommand::GetRevocationStatus(blob_storage_reader_handle, rev_reg_def, rev_reg_delta, cred_rev_id, cb) => {
+                debug!("GetRevocationStatus command received");
+                cb(self.get_revocation_status(blob_storage_reader_handle,
+                                              RevocationRegistryDefinitionV1::from(rev_reg_def),
+                                              RevocationRegistryDeltaV1::from(rev_reg_delta),
+                                              &cred_rev_id));
+            }
+            AnoncredsCommand::GetWitnessDependentIndices(rev_reg_def, rev_reg_delta, cred_rev_id, cb) => {
+                debug!("GetWitnessDependentIndices command received");
+                cb(self.get_witness_dependent_indices(RevocationRegistryDefinitionV1::from(rev_reg_def),
+                                                      RevocationRegistryDeltaV1::from(rev_reg_delta),
+                                                      &cred_rev_id));
+            }
+            AnoncredsCommand::CouldBeSatisfiedByCredDef(proof_req, cred_def, schema, cb) => {
+                debug!("CouldBeSatisfiedByCredDef command received");
+                cb(Ok(proof_req.could_be_satisfied_by_cred_def(&cred_def, &schema)));
+            }
+            AnoncredsCommand::CompareSchemas(schema, other_schema, cb) => {
+                debug!("CompareSchemas command received");
+                cb(self.compare_schemas(schema, other_schema));
+            }
+            AnoncredsCommand::EstimateCredentialStorage(schema_attr_count, revocable, count, cb) => {
+                debug!("EstimateCredentialStorage command received");
+                cb(Ok(estimate_credential_storage(schema_attr_count, revocable, count)));
+            }
         };
     }
+
+    #[cfg(feature = "encoding_conformance_tests")]
+    fn compare_encoding(&self,
+                        values: &HashMap<String, String>,
+                        reference_encodings: &HashMap<String, String>) -> IndyResult<String> {
+        let res = compare_encodings(values, reference_encodings)?;
+
+        serde_json::to_string(&res)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize diverged encodings")
+    }
+
+    fn build_revocation_registry_id(&self,
+                                    did: &DidValue,
+                                    cred_def_id: &CredentialDefinitionId,
+                                    tag: &str) -> IndyResult<String> {
+        RevocationRegistryId::build(did, cred_def_id, tag)
+            .map(|rev_reg_id| rev_reg_id.0)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))
+    }
+
+    fn build_non_revoked_interval(&self,
+                                  timestamp: u64,
+                                  tolerance: u64) -> IndyResult<String> {
+        serde_json::to_string(&NonRevocedInterval::around(timestamp, tolerance))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize NonRevocedInterval")
+    }
+
+    fn merge_proof_requests(&self,
+                            proof_req: ProofRequest,
+                            other_proof_req: ProofRequest) -> IndyResult<String> {
+        let merged = proof_req.merge(other_proof_req)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        merged.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        serde_json::to_string(&merged)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize ProofRequest")
+    }
+
+    fn compare_schemas(&self,
+                       schema: Schema,
+                       other_schema: Schema) -> IndyResult<String> {
+        let diff = schema.diff(&other_schema);
+
+        let (schema, other_schema) = (SchemaV1::from(schema), SchemaV1::from(other_schema));
+
+        let version_order = compare_schema_versions(&schema.version, &other_schema.version)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        let res = json!({
+            "added": diff.added,
+            "removed": diff.removed,
+            "unchanged": diff.unchanged,
+            "backward_compatible": diff.is_backward_compatible(),
+            "version_order": version_order as i8,
+        });
+
+        serde_json::to_string(&res)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize SchemaDiff")
+    }
+
+    fn get_revocation_status(&self,
+                             blob_storage_reader_handle: i32,
+                             rev_reg_def: RevocationRegistryDefinitionV1,
+                             rev_reg_delta: RevocationRegistryDeltaV1,
+                             cred_rev_id: &str) -> IndyResult<bool> {
+        let rev_idx = parse_cred_rev_id(cred_rev_id)?;
+
+        let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                       blob_storage_reader_handle,
+                                                       &rev_reg_def)?;
+
+        Prover::is_revoked(rev_idx,
+                           rev_reg_def.value.max_cred_num,
+                           rev_reg_def.value.issuance_type.to_bool(),
+                           &rev_reg_delta.value,
+                           &sdk_tails_accessor)
+    }
+
+    fn get_witness_dependent_indices(&self,
+                                     rev_reg_def: RevocationRegistryDefinitionV1,
+                                     rev_reg_delta: RevocationRegistryDeltaV1,
+                                     cred_rev_id: &str) -> IndyResult<String> {
+        let rev_idx = parse_cred_rev_id(cred_rev_id)?;
+
+        let mut indices: Vec<u32> = Prover::witness_dependent_indices(rev_idx,
+                                                                      rev_reg_def.value.max_cred_num,
+                                                                      rev_reg_def.value.issuance_type.to_bool(),
+                                                                      &rev_reg_delta.value)?
+            .into_iter()
+            .collect();
+        indices.sort();
+
+        serde_json::to_string(&indices)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize tails indices")
+    }
 }
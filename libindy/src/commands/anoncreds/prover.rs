@@ -1,8 +1,8 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::rc::Rc;
 
-use ursa::cl::{new_nonce, RevocationRegistry, Witness};
+use ursa::cl::{new_nonce, RevocationRegistry, RevocationRegistryDelta as CryptoRevocationRegistryDelta};
 
 use serde_json::Value;
 
@@ -13,24 +13,26 @@ use domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest
 use domain::anoncreds::credential_offer::CredentialOffer;
 use domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use domain::anoncreds::master_secret::MasterSecret;
-use domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery};
+use domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery, ProofRequestsVersion};
 use domain::anoncreds::requested_credential::RequestedCredentials;
-use domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1};
-use domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
+use domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1, RevocationRegistryId};
+use domain::anoncreds::revocation_registry_delta::{apply_revoked_indices, RevocationRegistryDelta, RevocationRegistryDeltaV1, RevocationRegistryDeltas};
 use domain::anoncreds::revocation_state::{RevocationState, RevocationStates};
 use domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, SchemaV1, SchemaId, Schemas};
 use domain::crypto::did::DidValue;
 use errors::prelude::*;
 use services::anoncreds::AnoncredsService;
-use services::anoncreds::helpers::{parse_cred_rev_id, get_non_revoc_interval};
+use services::anoncreds::helpers::{parse_cred_rev_id, get_non_revoc_intervals};
+use services::anoncreds::prover::Prover;
 use services::blob_storage::BlobStorageService;
 use services::crypto::CryptoService;
 use services::wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
+use utils::cancellation::CancellationToken;
 use utils::sequence;
 use utils::wql::Query;
 
 use super::tails::SDKTailsAccessor;
-use api::WalletHandle;
+use api::{ErrorCode, WalletHandle};
 use commands::BoxedCallbackStringStringSend;
 
 pub enum ProverCommand {
@@ -45,6 +47,12 @@ pub enum ProverCommand {
         CredentialDefinition, // credential def
         String, // master secret name
         BoxedCallbackStringStringSend),
+    CreateCredentialRequests(
+        WalletHandle,
+        DidValue, // prover did
+        Vec<(CredentialOffer, CredentialDefinition)>, // credential offers with their credential defs
+        String, // master secret name
+        Box<dyn Fn(IndyResult<String>) + Send>),
     SetCredentialAttrTagPolicy(
         WalletHandle,
         CredentialDefinitionId, // credential definition id
@@ -71,6 +79,10 @@ pub enum ProverCommand {
         WalletHandle,
         String, // credential id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    GetCredentialFingerprint(
+        WalletHandle,
+        String, // credential id
+        Box<dyn Fn(IndyResult<String>) + Send>),
     DeleteCredential(
         WalletHandle,
         String, // credential id
@@ -103,6 +115,23 @@ pub enum ProverCommand {
     CloseCredentialsSearchForProofReq(
         i32, // search handle
         Box<dyn Fn(IndyResult<()>) + Send>),
+    ExplainUnsatisfiable(
+        WalletHandle,
+        ProofRequest, // proof request
+        RevocationRegistryDeltas, // revocation registry deltas
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetProofPlan(
+        WalletHandle,
+        ProofRequest, // proof request
+        RevocationRegistryDeltas, // revocation registry deltas
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ForgetProofPlans(
+        WalletHandle),
+    PreviewDisclosure(
+        WalletHandle,
+        ProofRequest, // proof request
+        RequestedCredentials, // requested credentials
+        Box<dyn Fn(IndyResult<String>) + Send>),
     CreateProof(
         WalletHandle,
         ProofRequest, // proof request
@@ -111,6 +140,7 @@ pub enum ProverCommand {
         Schemas, // schemas
         CredentialDefinitions, // credential defs
         RevocationStates, // revocation states
+        CancellationToken,
         Box<dyn Fn(IndyResult<String>) + Send>),
     CreateRevocationState(
         i32, // blob storage reader handle
@@ -126,22 +156,85 @@ pub enum ProverCommand {
         RevocationRegistryDelta, // revocation registry delta
         u64, //timestamp
         String, //credential revocation id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    UpdateWitness(
+        i32, // tails reader _handle
+        RevocationState, // revocation state with current witness
+        RevocationRegistryDefinition, // revocation registry definition
+        Vec<RevocationRegistryDelta>, // ordered revocation registry deltas
+        u64, //timestamp
+        String, //credential revocation id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    DetectRevoked(
+        WalletHandle,
+        RevocationRegistryDeltas, // revocation registry deltas
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ClearWitnessCache(
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    MaxProvableTimestamp(
+        RevocationRegistryId, // revocation registry id
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
+const MAX_PROOF_PLANS: usize = 100;
+
+type ProofPlanKey = (WalletHandle, String);
+
+// Requested credentials of the last proof per wallet and proof request (canonical, without nonce).
+// The oldest plans are evicted when the cache is full.
+struct ProofPlans {
+    plans: HashMap<ProofPlanKey, RequestedCredentials>,
+    order: VecDeque<ProofPlanKey>,
+}
+
+impl ProofPlans {
+    fn new() -> ProofPlans {
+        ProofPlans {
+            plans: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&self, key: &ProofPlanKey) -> Option<&RequestedCredentials> {
+        self.plans.get(key)
+    }
+
+    fn insert(&mut self, key: ProofPlanKey, plan: RequestedCredentials) {
+        if self.plans.remove(&key).is_some() {
+            self.order.retain(|cached| *cached != key);
+        }
+
+        while self.order.len() >= MAX_PROOF_PLANS {
+            match self.order.pop_front() {
+                Some(oldest) => { self.plans.remove(&oldest); }
+                None => break
+            }
+        }
+
+        self.order.push_back(key.clone());
+        self.plans.insert(key, plan);
+    }
+
+    fn retain<F>(&mut self, f: F) where F: Fn(&ProofPlanKey, &RequestedCredentials) -> bool {
+        let plans = &mut self.plans;
+        plans.retain(|key, plan| f(key, plan));
+        self.order.retain(|key| plans.contains_key(key));
+    }
+}
+
 struct SearchForProofRequest {
     search: WalletSearch,
-    interval: Option<NonRevocedInterval>,
+    intervals: Vec<NonRevocedInterval>,
     predicate_info: Option<PredicateInfo>,
 }
 
 impl SearchForProofRequest {
     fn new(search: WalletSearch,
-           interval: Option<NonRevocedInterval>,
+           intervals: Vec<NonRevocedInterval>,
            predicate_info: Option<PredicateInfo>, ) -> Self {
         Self {
             search,
-            interval,
+            intervals,
             predicate_info,
         }
     }
@@ -154,6 +247,7 @@ pub struct ProverCommandExecutor {
     blob_storage_service: Rc<BlobStorageService>,
     searches: RefCell<HashMap<i32, Box<WalletSearch>>>,
     searches_for_proof_requests: RefCell<HashMap<i32, Box<HashMap<String, SearchForProofRequest>>>>,
+    proof_plans: RefCell<ProofPlans>,
 }
 
 impl ProverCommandExecutor {
@@ -168,6 +262,7 @@ impl ProverCommandExecutor {
             blob_storage_service,
             searches: RefCell::new(HashMap::new()),
             searches_for_proof_requests: RefCell::new(HashMap::new()),
+            proof_plans: RefCell::new(ProofPlans::new()),
         }
     }
 
@@ -183,6 +278,14 @@ impl ProverCommandExecutor {
                 cb(self.create_credential_request(wallet_handle, &prover_did, &credential_offer,
                                                   &CredentialDefinitionV1::from(credential_def), &master_secret_name));
             }
+            ProverCommand::CreateCredentialRequests(wallet_handle, prover_did, offered_credentials, master_secret_name, cb) => {
+                debug!(target: "prover_command_executor", "CreateCredentialRequests command received");
+                let offered_credentials = offered_credentials
+                    .into_iter()
+                    .map(|(credential_offer, credential_def)| (credential_offer, CredentialDefinitionV1::from(credential_def)))
+                    .collect::<Vec<(CredentialOffer, CredentialDefinitionV1)>>();
+                cb(self.create_credential_requests(wallet_handle, &prover_did, &offered_credentials, &master_secret_name));
+            }
             ProverCommand::SetCredentialAttrTagPolicy(wallet_handle, cred_def_id, catpol, retroactive, cb) => {
                 debug!(target: "prover_command_executor", "SetCredentialAttrTagPolicy command received");
                 cb(self.set_credential_attr_tag_policy(wallet_handle, &cred_def_id, catpol.as_ref(), retroactive));
@@ -206,6 +309,10 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "GetCredential command received");
                 cb(self.get_credential(wallet_handle, &cred_id));
             }
+            ProverCommand::GetCredentialFingerprint(wallet_handle, cred_id, cb) => {
+                debug!(target: "prover_command_executor", "GetCredentialFingerprint command received");
+                cb(self.get_credential_fingerprint(wallet_handle, &cred_id));
+            }
             ProverCommand::DeleteCredential(wallet_handle, cred_id, cb) => {
                 debug!(target: "prover_command_executor", "DeleteCredential command received");
                 cb(self.delete_credential(wallet_handle, &cred_id));
@@ -238,13 +345,30 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "CloseCredentialsSearchForProofReq command received");
                 cb(self.close_credentials_search_for_proof_req(search_handle));
             }
+            ProverCommand::ExplainUnsatisfiable(wallet_handle, proof_req, rev_reg_deltas, cb) => {
+                debug!(target: "prover_command_executor", "ExplainUnsatisfiable command received");
+                cb(self.explain_unsatisfiable(wallet_handle, &proof_req, rev_reg_deltas));
+            }
+            ProverCommand::GetProofPlan(wallet_handle, proof_req, rev_reg_deltas, cb) => {
+                debug!(target: "prover_command_executor", "GetProofPlan command received");
+                cb(self.get_proof_plan(wallet_handle, &proof_req, rev_reg_deltas));
+            }
+            ProverCommand::ForgetProofPlans(wallet_handle) => {
+                debug!(target: "prover_command_executor", "ForgetProofPlans command received");
+                self.forget_proof_plans(wallet_handle);
+            }
+            ProverCommand::PreviewDisclosure(wallet_handle, proof_req, requested_credentials, cb) => {
+                debug!(target: "prover_command_executor", "PreviewDisclosure command received");
+                cb(self.preview_disclosure(wallet_handle, &proof_req, &requested_credentials));
+            }
             ProverCommand::CreateProof(wallet_handle, proof_req, requested_credentials, master_secret_name,
-                                       schemas, cred_defs, rev_states, cb) => {
+                                       schemas, cred_defs, rev_states, cancellation, cb) => {
                 debug!(target: "prover_command_executor", "CreateProof command received");
                 cb(self.create_proof(wallet_handle, &proof_req, &requested_credentials, &master_secret_name,
                                      &schemas_map_to_schemas_v1_map(schemas),
                                      &cred_defs_map_to_cred_defs_v1_map(cred_defs),
-                                     &rev_states));
+                                     &rev_states,
+                                     &cancellation));
             }
             ProverCommand::CreateRevocationState(blob_storage_reader_handle, rev_reg_def, rev_reg_delta, timestamp, cred_rev_id, cb) => {
                 debug!(target: "prover_command_executor", "CreateRevocationState command received");
@@ -254,6 +378,22 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "UpdateRevocationState command received");
                 cb(self.update_revocation_state(blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_delta, timestamp, &cred_rev_id));
             }
+            ProverCommand::UpdateWitness(blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_deltas, timestamp, cred_rev_id, cb) => {
+                debug!(target: "prover_command_executor", "UpdateWitness command received");
+                cb(self.update_witness(blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_deltas, timestamp, &cred_rev_id));
+            }
+            ProverCommand::DetectRevoked(wallet_handle, rev_reg_deltas, cb) => {
+                debug!(target: "prover_command_executor", "DetectRevoked command received");
+                cb(self.detect_revoked(wallet_handle, rev_reg_deltas));
+            }
+            ProverCommand::ClearWitnessCache(cb) => {
+                debug!(target: "prover_command_executor", "ClearWitnessCache command received");
+                cb(self.clear_witness_cache());
+            }
+            ProverCommand::MaxProvableTimestamp(rev_reg_id, cb) => {
+                debug!(target: "prover_command_executor", "MaxProvableTimestamp command received");
+                cb(self.max_provable_timestamp(&rev_reg_id));
+            }
         };
     }
 
@@ -294,6 +434,58 @@ impl ProverCommandExecutor {
 
         let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
 
+        let (credential_request, credential_request_metadata) =
+            self._create_credential_request(prover_did, cred_offer, cred_def, &master_secret, master_secret_id)?;
+
+        let cred_req_json = serde_json::to_string(&credential_request)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialRequest")?;
+
+        let cred_req_metadata_json = serde_json::to_string(&credential_request_metadata)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialRequestMetadata")?;
+
+        debug!("create_credential_request <<< cred_req_json: {:?}, cred_req_metadata_json: {:?}", cred_req_json, cred_req_metadata_json);
+
+        Ok((cred_req_json, cred_req_metadata_json))
+    }
+
+    fn create_credential_requests(&self,
+                                  wallet_handle: WalletHandle,
+                                  prover_did: &DidValue,
+                                  offered_credentials: &[(CredentialOffer, CredentialDefinitionV1)],
+                                  master_secret_id: &str) -> IndyResult<String> {
+        debug!("create_credential_requests >>> wallet_handle: {:?}, prover_did: {:?}, offered_credentials: {:?}, master_secret_id: {:?}",
+               wallet_handle, prover_did, offered_credentials, master_secret_id);
+
+        self.crypto_service.validate_did(&prover_did)?;
+
+        let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
+
+        let results = offered_credentials
+            .iter()
+            .map(|&(ref cred_offer, ref cred_def)| {
+                match self._create_credential_request(prover_did, cred_offer, cred_def, &master_secret, master_secret_id) {
+                    Ok((credential_request, credential_request_metadata)) =>
+                        json!({"cred_req": credential_request, "cred_req_metadata": credential_request_metadata}),
+                    Err(err) =>
+                        json!({"error": {"code": ErrorCode::from(err.kind()) as i32, "message": err.to_string()}})
+                }
+            })
+            .collect::<Vec<serde_json::Value>>();
+
+        let results_json = serde_json::to_string(&results)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialRequests")?;
+
+        debug!("create_credential_requests <<< results_json: {:?}", results_json);
+
+        Ok(results_json)
+    }
+
+    fn _create_credential_request(&self,
+                                  prover_did: &DidValue,
+                                  cred_offer: &CredentialOffer,
+                                  cred_def: &CredentialDefinitionV1,
+                                  master_secret: &MasterSecret,
+                                  master_secret_id: &str) -> IndyResult<(CredentialRequest, CredentialRequestMetadata)> {
         let (blinded_ms, ms_blinding_data, blinded_ms_correctness_proof) =
             self.anoncreds_service.prover.new_credential_request(cred_def,
                                                                  &master_secret.value,
@@ -315,15 +507,7 @@ impl ProverCommandExecutor {
             master_secret_name: master_secret_id.to_string()
         };
 
-        let cred_req_json = serde_json::to_string(&credential_request)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialRequest")?;
-
-        let cred_req_metadata_json = serde_json::to_string(&credential_request_metadata)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialRequestMetadata")?;
-
-        debug!("create_credential_request <<< cred_req_json: {:?}, cred_req_metadata_json: {:?}", cred_req_json, cred_req_metadata_json);
-
-        Ok((cred_req_json, cred_req_metadata_json))
+        Ok((credential_request, credential_request_metadata))
     }
 
     fn set_credential_attr_tag_policy(&self,
@@ -387,7 +571,7 @@ impl ProverCommandExecutor {
                         cred_def: &CredentialDefinitionV1,
                         rev_reg_def: Option<&RevocationRegistryDefinitionV1>) -> IndyResult<String> {
         debug!("store_credential >>> wallet_handle: {:?}, cred_id: {:?}, cred_req_metadata: {:?}, credential: {:?}, cred_def: {:?}, \
-        rev_reg_def: {:?}", wallet_handle, cred_id, cred_req_metadata, credential, cred_def, rev_reg_def);
+        rev_reg_def: {:?}", wallet_handle, cred_id, cred_req_metadata, sensitive!(&credential), cred_def, rev_reg_def);
 
         let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &cred_req_metadata.master_secret_name)?;
 
@@ -422,11 +606,11 @@ impl ProverCommandExecutor {
                        filter_json: Option<&str>) -> IndyResult<String> {
         debug!("get_credentials >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
 
-        let filter_json = filter_json.unwrap_or("{}");
+        let filter_json = self._transform_credential_query(wallet_handle, filter_json.unwrap_or("{}"))?;
         let mut credentials_info: Vec<CredentialInfo> = Vec::new();
 
         let mut credentials_search =
-            self.wallet_service.search_indy_records::<Credential>(wallet_handle, filter_json, &SearchOptions::id_value())?;
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &filter_json, &SearchOptions::id_value())?;
 
         while let Some(credential_record) = credentials_search.fetch_next_record()? {
             let (referent, credential) = self._get_credential(&credential_record)?;
@@ -436,7 +620,7 @@ impl ProverCommandExecutor {
         let credentials_info_json = serde_json::to_string(&credentials_info)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialInfo")?;
 
-        debug!("get_credentials <<< credentials_info_json: {:?}", credentials_info_json);
+        debug!("get_credentials <<< credentials_info_json: {:?}", sensitive!(&credentials_info_json));
 
         Ok(credentials_info_json)
     }
@@ -450,21 +634,43 @@ impl ProverCommandExecutor {
 
         let credential_info = self._get_credential_info(&cred_id, credential);
 
-        let credential_info_json = serde_json::to_string(&credential_info)
+        let mut credential_info_json = serde_json::to_value(&credential_info)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialInfo")?;
 
-        debug!("get_credential <<< credential_info_json: {:?}", credential_info_json);
+        // lets wallets render the credential without fetching its schema from the ledger
+        credential_info_json["attribute_names"] = json!(credential_info.attribute_names());
+
+        let credential_info_json = serde_json::to_string(&credential_info_json)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialInfo")?;
+
+        debug!("get_credential <<< credential_info_json: {:?}", sensitive!(&credential_info_json));
 
         Ok(credential_info_json)
     }
 
+    fn get_credential_fingerprint(&self,
+                                  wallet_handle: WalletHandle,
+                                  cred_id: &str) -> IndyResult<String> {
+        debug!("get_credential_fingerprint >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+
+        let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, &cred_id, &RecordOptions::id_value())?;
+
+        let fingerprint = Prover::credential_fingerprint(&credential)?;
+
+        debug!("get_credential_fingerprint <<< fingerprint: {:?}", fingerprint);
+
+        Ok(fingerprint)
+    }
+
     fn search_credentials(&self,
                           wallet_handle: WalletHandle,
                           query_json: Option<&str>) -> IndyResult<(i32, usize)> {
         debug!("search_credentials >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
 
+        let query_json = self._transform_credential_query(wallet_handle, query_json.unwrap_or("{}"))?;
+
         let credentials_search =
-            self.wallet_service.search_indy_records::<Credential>(wallet_handle, query_json.unwrap_or("{}"), &SearchOptions::id_value())?;
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query_json, &SearchOptions::id_value())?;
 
         let total_count = credentials_search.get_total_count()?.unwrap_or(0);
 
@@ -503,7 +709,7 @@ impl ProverCommandExecutor {
         let credentials_info_json = serde_json::to_string(&credentials_info)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialInfo")?;
 
-        trace!("fetch_credentials <<< credentials_info_json: {:?}", credentials_info_json);
+        trace!("fetch_credentials <<< credentials_info_json: {:?}", sensitive!(&credentials_info_json));
 
         Ok(credentials_info_json)
     }
@@ -532,29 +738,33 @@ impl ProverCommandExecutor {
         let mut credentials_for_proof_request: CredentialsForProofRequest = CredentialsForProofRequest::default();
 
         for (attr_id, requested_attr) in proof_req.requested_attributes.iter() {
-            let query = self.anoncreds_service.prover.extend_proof_request_restrictions(&proof_req_version,
-                                                                                        &requested_attr.name,
-                                                                                        &attr_id,
-                                                                                        &requested_attr.restrictions,
-                                                                                        &None)?;
-            let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_attr.non_revoked);
-
-            let credentials_for_attribute = self._query_requested_credentials(wallet_handle, &query, None, &interval)?;
+            let query = self._build_proof_request_query(wallet_handle,
+                                                        &proof_req_version,
+                                                        &requested_attr.name,
+                                                        &attr_id,
+                                                        &requested_attr.restrictions,
+                                                        &None)?;
+            let query = self.anoncreds_service.prover.restrict_to_allowed_values(query, &requested_attr.name,
+                                                                                 proof_req.allowed_values.as_ref().and_then(|allowed_values| allowed_values.get(attr_id)));
+            let intervals = get_non_revoc_intervals(&proof_req.non_revoked, &requested_attr.non_revoked);
+
+            let credentials_for_attribute = self._query_requested_credentials(wallet_handle, &query, None, &intervals)?;
 
             credentials_for_proof_request.attrs.insert(attr_id.to_string(), credentials_for_attribute);
         }
 
         for (predicate_id, requested_predicate) in proof_req.requested_predicates.iter() {
-            let query = self.anoncreds_service.prover.extend_proof_request_restrictions(&proof_req_version,
-                                                                                        &requested_predicate.name,
-                                                                                        &predicate_id,
-                                                                                        &requested_predicate.restrictions,
-                                                                                        &None)?;
+            let query = self._build_proof_request_query(wallet_handle,
+                                                        &proof_req_version,
+                                                        &requested_predicate.name,
+                                                        &predicate_id,
+                                                        &requested_predicate.restrictions,
+                                                        &None)?;
 
-            let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_predicate.non_revoked);
+            let intervals = get_non_revoc_intervals(&proof_req.non_revoked, &requested_predicate.non_revoked);
 
             let credentials_for_predicate =
-                self._query_requested_credentials(wallet_handle, &query, Some(&requested_predicate), &interval)?;
+                self._query_requested_credentials(wallet_handle, &query, Some(&requested_predicate), &intervals)?;
 
             credentials_for_proof_request.predicates.insert(predicate_id.to_string(), credentials_for_predicate);
         }
@@ -562,11 +772,59 @@ impl ProverCommandExecutor {
         let credentials_for_proof_request_json = serde_json::to_string(&credentials_for_proof_request)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialsForProofRequest")?;
 
-        debug!("get_credentials_for_proof_req <<< credentials_for_proof_request_json: {:?}", credentials_for_proof_request_json);
+        debug!("get_credentials_for_proof_req <<< credentials_for_proof_request_json: {:?}", sensitive!(&credentials_for_proof_request_json));
 
         Ok(credentials_for_proof_request_json)
     }
 
+    fn explain_unsatisfiable(&self,
+                             wallet_handle: WalletHandle,
+                             proof_request: &ProofRequest,
+                             rev_reg_deltas: RevocationRegistryDeltas) -> IndyResult<String> {
+        debug!("explain_unsatisfiable >>> wallet_handle: {:?}, proof_request: {:?}, rev_reg_deltas: {:?}", wallet_handle, proof_request, rev_reg_deltas);
+
+        let proof_req = proof_request.value();
+        let proof_req_version = proof_request.version();
+
+        let revoked = Self::_revoked_indices(rev_reg_deltas)?;
+
+        let mut attrs: HashMap<String, String> = HashMap::new();
+        let mut predicates: HashMap<String, String> = HashMap::new();
+
+        for (attr_id, requested_attr) in proof_req.requested_attributes.iter() {
+            let intervals = get_non_revoc_intervals(&proof_req.non_revoked, &requested_attr.non_revoked);
+
+            let reason = self._explain_unsatisfiable_referent(wallet_handle, &proof_req_version, attr_id,
+                                                              &requested_attr.name, &requested_attr.restrictions, None,
+                                                              &intervals, &revoked)?;
+
+            if let Some(reason) = reason {
+                attrs.insert(attr_id.to_string(), reason);
+            }
+        }
+
+        for (predicate_id, requested_predicate) in proof_req.requested_predicates.iter() {
+            let intervals = get_non_revoc_intervals(&proof_req.non_revoked, &requested_predicate.non_revoked);
+
+            let reason = self._explain_unsatisfiable_referent(wallet_handle, &proof_req_version, predicate_id,
+                                                              &requested_predicate.name, &requested_predicate.restrictions,
+                                                              Some(requested_predicate), &intervals, &revoked)?;
+
+            if let Some(reason) = reason {
+                predicates.insert(predicate_id.to_string(), reason);
+            }
+        }
+
+        let explanation_json = json!({
+            "attrs": attrs,
+            "predicates": predicates,
+        }).to_string();
+
+        debug!("explain_unsatisfiable <<< explanation_json: {:?}", explanation_json);
+
+        Ok(explanation_json)
+    }
+
     fn search_credentials_for_proof_req(&self,
                                         wallet_handle: WalletHandle,
                                         proof_request: &ProofRequest,
@@ -579,37 +837,41 @@ impl ProverCommandExecutor {
         let mut credentials_for_proof_request_search = HashMap::<String, SearchForProofRequest>::new();
 
         for (attr_id, requested_attr) in proof_req.requested_attributes.iter() {
-            let query = self.anoncreds_service.prover.extend_proof_request_restrictions(&version,
-                                                                                        &requested_attr.name,
-                                                                                        &attr_id,
-                                                                                        &requested_attr.restrictions,
-                                                                                        &extra_query)?;
+            let query = self._build_proof_request_query(wallet_handle,
+                                                        &version,
+                                                        &requested_attr.name,
+                                                        &attr_id,
+                                                        &requested_attr.restrictions,
+                                                        &extra_query)?;
+            let query = self.anoncreds_service.prover.restrict_to_allowed_values(query, &requested_attr.name,
+                                                                                 proof_req.allowed_values.as_ref().and_then(|allowed_values| allowed_values.get(attr_id)));
 
             let credentials_search =
                 self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query.to_string(), &SearchOptions::id_value())?;
 
-            let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_attr.non_revoked);
+            let intervals = get_non_revoc_intervals(&proof_req.non_revoked, &requested_attr.non_revoked);
 
             credentials_for_proof_request_search.insert(attr_id.to_string(),
                                                         SearchForProofRequest::new(
-                                                            credentials_search, interval, None));
+                                                            credentials_search, intervals, None));
         }
 
         for (predicate_id, requested_predicate) in proof_req.requested_predicates.iter() {
-            let query = self.anoncreds_service.prover.extend_proof_request_restrictions(&version,
-                                                                                        &requested_predicate.name,
-                                                                                        &predicate_id,
-                                                                                        &requested_predicate.restrictions,
-                                                                                        &extra_query)?;
+            let query = self._build_proof_request_query(wallet_handle,
+                                                        &version,
+                                                        &requested_predicate.name,
+                                                        &predicate_id,
+                                                        &requested_predicate.restrictions,
+                                                        &extra_query)?;
 
             let credentials_search =
                 self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query.to_string(), &SearchOptions::id_value())?;
 
-            let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_predicate.non_revoked);
+            let intervals = get_non_revoc_intervals(&proof_req.non_revoked, &requested_predicate.non_revoked);
 
             credentials_for_proof_request_search.insert(predicate_id.to_string(),
                                                         SearchForProofRequest::new(
-                                                            credentials_search, interval, Some(requested_predicate.clone())));
+                                                            credentials_search, intervals, Some(requested_predicate.clone())));
         }
 
         let search_handle = sequence::get_next_id();
@@ -630,12 +892,12 @@ impl ProverCommandExecutor {
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown item referent {} for CredentialsSearch handle: {}", item_referent, search_handle)))?;
 
         let requested_credentials: Vec<RequestedCredential> =
-            self._get_requested_credentials(&mut search.search, search.predicate_info.as_ref(), &search.interval, Some(count))?;
+            self._get_requested_credentials(&mut search.search, search.predicate_info.as_ref(), &search.intervals, Some(count))?;
 
         let requested_credentials_json = serde_json::to_string(&requested_credentials)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of RequestedCredential")?;
 
-        trace!("fetch_credential_for_proof_request <<< requested_credentials_json: {:?}", requested_credentials_json);
+        trace!("fetch_credential_for_proof_request <<< requested_credentials_json: {:?}", sensitive!(&requested_credentials_json));
 
         Ok(requested_credentials_json)
     }
@@ -662,7 +924,85 @@ impl ProverCommandExecutor {
             return Err(err_msg(IndyErrorKind::WalletItemNotFound, format!("Credential {} not found", cred_id)));
         }
 
-        self.wallet_service.delete_indy_record::<Credential>(wallet_handle, cred_id)
+        self.wallet_service.delete_indy_record::<Credential>(wallet_handle, cred_id)?;
+
+        self._invalidate_proof_plans(wallet_handle, cred_id);
+
+        Ok(())
+    }
+
+    fn get_proof_plan(&self,
+                      wallet_handle: WalletHandle,
+                      proof_req: &ProofRequest,
+                      rev_reg_deltas: RevocationRegistryDeltas) -> IndyResult<String> {
+        debug!("get_proof_plan >>> wallet_handle: {:?}, proof_req: {:?}, rev_reg_deltas: {:?}", wallet_handle, proof_req, rev_reg_deltas);
+
+        let proof_req_json = proof_req.canonical_json_without_nonce()
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Proof Request")?;
+        let key = (wallet_handle, proof_req_json);
+
+        let cred_ids = match self.proof_plans.borrow().get(&key) {
+            Some(plan) => plan.cred_ids(),
+            None => {
+                debug!("get_proof_plan <<< no cached plan");
+                return Ok(Value::Null.to_string());
+            }
+        };
+
+        let revoked = Self::_revoked_indices(rev_reg_deltas)?;
+
+        for cred_id in cred_ids {
+            let credential: Option<Credential> =
+                self.wallet_service.get_indy_opt_object(wallet_handle, &cred_id, &RecordOptions::id_value())?;
+
+            let is_valid = match credential {
+                Some(credential) => !Self::_is_revoked(&revoked, credential.rev_reg_id.as_ref(), credential.signature.extract_index()),
+                None => false
+            };
+
+            if !is_valid {
+                self._invalidate_proof_plans(wallet_handle, &cred_id);
+
+                debug!("get_proof_plan <<< credential {:?} of cached plan is gone or revoked", cred_id);
+                return Ok(Value::Null.to_string());
+            }
+        }
+
+        let requested_credentials_json = serde_json::to_string(&self.proof_plans.borrow().get(&key))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RequestedCredentials")?;
+
+        debug!("get_proof_plan <<< requested_credentials_json: {:?}", sensitive!(&requested_credentials_json));
+
+        Ok(requested_credentials_json)
+    }
+
+    fn forget_proof_plans(&self, wallet_handle: WalletHandle) {
+        debug!("forget_proof_plans >>> wallet_handle: {:?}", wallet_handle);
+
+        self.proof_plans.borrow_mut().retain(|&(plan_wallet_handle, _), _| plan_wallet_handle != wallet_handle);
+
+        debug!("forget_proof_plans <<<");
+    }
+
+    fn preview_disclosure(&self,
+                          wallet_handle: WalletHandle,
+                          proof_req: &ProofRequest,
+                          requested_credentials: &RequestedCredentials) -> IndyResult<String> {
+        debug!("preview_disclosure >>> wallet_handle: {:?}, proof_req: {:?}, requested_credentials: {:?}",
+               wallet_handle, proof_req, sensitive!(&requested_credentials));
+
+        let credentials = self._wallet_get_requested_credentials(wallet_handle, requested_credentials)?;
+
+        let preview = self.anoncreds_service.prover.preview_disclosure(&credentials,
+                                                                       proof_req.value(),
+                                                                       &requested_credentials)?;
+
+        let preview_json = serde_json::to_string(&preview)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DisclosurePreview")?;
+
+        debug!("preview_disclosure <<< preview_json: {:?}", sensitive!(&preview_json));
+
+        Ok(preview_json)
     }
 
     fn create_proof(&self,
@@ -672,33 +1012,15 @@ impl ProverCommandExecutor {
                     master_secret_id: &str,
                     schemas: &HashMap<SchemaId, SchemaV1>,
                     cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
-                    rev_states: &RevocationStates) -> IndyResult<String> {
+                    rev_states: &RevocationStates,
+                    cancellation: &CancellationToken) -> IndyResult<String> {
         debug!("create_proof >>> wallet_handle: {:?}, proof_req: {:?}, requested_credentials: {:?}, master_secret_id: {:?}, schemas: {:?}, \
         cred_defs: {:?}, rev_states: {:?}",
-               wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, cred_defs, rev_states);
+               wallet_handle, proof_req, sensitive!(&requested_credentials), master_secret_id, schemas, cred_defs, rev_states);
 
         let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
 
-        let cred_refs_for_attrs =
-            requested_credentials.requested_attributes
-                .values()
-                .map(|requested_attr| requested_attr.cred_id.clone())
-                .collect::<HashSet<String>>();
-
-        let cred_refs_for_predicates =
-            requested_credentials.requested_predicates
-                .values()
-                .map(|requested_predicate| requested_predicate.cred_id.clone())
-                .collect::<HashSet<String>>();
-
-        let cred_referents = cred_refs_for_attrs.union(&cred_refs_for_predicates).cloned().collect::<Vec<String>>();
-
-        let mut credentials: HashMap<String, Credential> = HashMap::new();
-
-        for cred_referent in cred_referents.into_iter() {
-            let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, &cred_referent, &RecordOptions::id_value())?;
-            credentials.insert(cred_referent, credential);
-        }
+        let credentials = self._wallet_get_requested_credentials(wallet_handle, requested_credentials)?;
 
         let proof = self.anoncreds_service.prover.create_proof(&credentials,
                                                                &proof_req,
@@ -706,12 +1028,21 @@ impl ProverCommandExecutor {
                                                                &master_secret.value,
                                                                schemas,
                                                                cred_defs,
-                                                               rev_states)?;
+                                                               rev_states,
+                                                               &|| cancellation.is_cancelled())?;
 
         let proof_json = serde_json::to_string(&proof)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")?;
 
-        debug!("create_proof <<< proof_json: {:?}", proof_json);
+        // self-attested values are supplied by the prover for every proof anew, so they aren't kept
+        let mut proof_plan = requested_credentials.without_timestamps();
+        proof_plan.self_attested_attributes.clear();
+
+        let proof_req_json = proof_req.canonical_json_without_nonce()
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Proof Request")?;
+        self.proof_plans.borrow_mut().insert((wallet_handle, proof_req_json), proof_plan);
+
+        debug!("create_proof <<< proof_json: {:?}", sensitive!(&proof_json));
 
         Ok(proof_json)
     }
@@ -735,7 +1066,13 @@ impl ProverCommandExecutor {
 
         let rev_reg_delta = RevocationRegistryDeltaV1::from(rev_reg_delta);
 
-        let witness = Witness::new(rev_idx, revoc_reg_def.value.max_cred_num, revoc_reg_def.value.issuance_type.to_bool(), &rev_reg_delta.value, &sdk_tails_accessor)?;
+        let witness = self.anoncreds_service.prover.new_witness(&revoc_reg_def.id,
+                                                                rev_idx,
+                                                                revoc_reg_def.value.max_cred_num,
+                                                                revoc_reg_def.value.issuance_type.to_bool(),
+                                                                &rev_reg_delta,
+                                                                timestamp,
+                                                                &sdk_tails_accessor)?;
 
         let revocation_state = RevocationState {
             witness,
@@ -772,6 +1109,7 @@ impl ProverCommandExecutor {
                                                        &revocation_registry_definition)?;
 
         rev_state.witness.update(rev_idx, revocation_registry_definition.value.max_cred_num, &rev_reg_delta.value, &sdk_tails_accessor)?;
+        self.anoncreds_service.prover.cache_updated_witness(&revocation_registry_definition.id, rev_idx, &rev_state.witness, timestamp);
 
         rev_state.rev_reg = RevocationRegistry::from(rev_reg_delta.value);
         rev_state.timestamp = timestamp;
@@ -784,6 +1122,95 @@ impl ProverCommandExecutor {
         Ok(rev_state_json)
     }
 
+    fn update_witness(&self,
+                      blob_storage_reader_handle: i32,
+                      mut rev_state: RevocationState,
+                      rev_reg_def: RevocationRegistryDefinition,
+                      rev_reg_deltas: Vec<RevocationRegistryDelta>,
+                      timestamp: u64,
+                      cred_rev_id: &str) -> IndyResult<String> {
+        debug!("update_witness >>> blob_storage_reader_handle: {:?}, rev_state: {:?}, rev_reg_def: {:?}, rev_reg_deltas: {:?}, timestamp: {:?}, cred_rev_id: {:?}",
+               blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_deltas, timestamp, cred_rev_id);
+
+        let revocation_registry_definition = RevocationRegistryDefinitionV1::from(rev_reg_def);
+
+        let rev_reg_deltas: Vec<CryptoRevocationRegistryDelta> = rev_reg_deltas
+            .into_iter()
+            .map(|rev_reg_delta| RevocationRegistryDeltaV1::from(rev_reg_delta).value)
+            .collect();
+
+        let rev_idx = parse_cred_rev_id(cred_rev_id)?;
+
+        let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                       blob_storage_reader_handle,
+                                                       &revocation_registry_definition)?;
+
+        rev_state.rev_reg = Prover::update_witness(&mut rev_state.witness,
+                                                   &rev_state.rev_reg,
+                                                   rev_idx,
+                                                   revocation_registry_definition.value.max_cred_num,
+                                                   &rev_reg_deltas,
+                                                   &sdk_tails_accessor)?;
+        self.anoncreds_service.prover.cache_updated_witness(&revocation_registry_definition.id, rev_idx, &rev_state.witness, timestamp);
+        rev_state.timestamp = timestamp;
+
+        let rev_state_json = serde_json::to_string(&rev_state)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationState")?;
+
+        debug!("update_witness <<< rev_state: {:?}", rev_state_json);
+
+        Ok(rev_state_json)
+    }
+
+    fn detect_revoked(&self,
+                      wallet_handle: WalletHandle,
+                      rev_reg_deltas: RevocationRegistryDeltas) -> IndyResult<String> {
+        debug!("detect_revoked >>> wallet_handle: {:?}, rev_reg_deltas: {:?}", wallet_handle, rev_reg_deltas);
+
+        let revoked = Self::_revoked_indices(rev_reg_deltas)?;
+
+        let mut revoked_credentials: Vec<CredentialInfo> = Vec::new();
+
+        let mut credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        while let Some(credential_record) = credentials_search.fetch_next_record()? {
+            let (referent, credential) = self._get_credential(&credential_record)?;
+
+            if Self::_is_revoked(&revoked, credential.rev_reg_id.as_ref(), credential.signature.extract_index()) {
+                self._invalidate_proof_plans(wallet_handle, &referent);
+                revoked_credentials.push(self._get_credential_info(&referent, credential));
+            }
+        }
+
+        let revoked_credentials_json = serde_json::to_string(&revoked_credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialInfo")?;
+
+        debug!("detect_revoked <<< revoked_credentials_json: {:?}", sensitive!(&revoked_credentials_json));
+
+        Ok(revoked_credentials_json)
+    }
+
+    fn clear_witness_cache(&self) -> IndyResult<()> {
+        debug!("clear_witness_cache >>> ");
+
+        self.anoncreds_service.prover.clear_witness_cache();
+
+        debug!("clear_witness_cache <<< ");
+
+        Ok(())
+    }
+
+    fn max_provable_timestamp(&self, rev_reg_id: &RevocationRegistryId) -> IndyResult<String> {
+        debug!("max_provable_timestamp >>> rev_reg_id: {:?}", rev_reg_id);
+
+        let timestamp_json = json!(self.anoncreds_service.prover.max_provable_timestamp(rev_reg_id)).to_string();
+
+        debug!("max_provable_timestamp <<< timestamp_json: {:?}", timestamp_json);
+
+        Ok(timestamp_json)
+    }
+
     fn _get_credential_info(&self,
                             referent: &str,
                             credential: Credential) -> CredentialInfo {
@@ -816,28 +1243,191 @@ impl ProverCommandExecutor {
         Ok((referent.to_string(), credential))
     }
 
+    fn _transform_credential_query(&self, wallet_handle: WalletHandle, query_json: &str) -> IndyResult<String> {
+        let query: Query = serde_json::from_str(query_json)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::WalletQueryError, err))?;
+
+        let query = self.anoncreds_service.prover.transform_credential_query(query)?;
+        let query = self._resolve_schema_version_ranges(wallet_handle, query)?;
+
+        Ok(query.to_string())
+    }
+
+    fn _build_proof_request_query(&self,
+                                  wallet_handle: WalletHandle,
+                                  version: &ProofRequestsVersion,
+                                  name: &str,
+                                  referent: &str,
+                                  restrictions: &Option<Query>,
+                                  extra_query: &Option<&ProofRequestExtraQuery>) -> IndyResult<Query> {
+        let query = self.anoncreds_service.prover.extend_proof_request_restrictions(version, name, referent, restrictions, extra_query)?;
+
+        self._resolve_schema_version_ranges(wallet_handle, query)
+    }
+
+    fn _resolve_schema_version_ranges(&self, wallet_handle: WalletHandle, query: Query) -> IndyResult<Query> {
+        if !Prover::has_schema_version_range(&query) {
+            return Ok(query);
+        }
+
+        let mut schema_versions: HashSet<String> = HashSet::new();
+
+        let mut credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, "{}", &SearchOptions::id_tags())?;
+
+        while let Some(credential_record) = credentials_search.fetch_next_record()? {
+            if let Some(schema_version) = credential_record.get_tags().and_then(|tags| tags.get("schema_version")) {
+                schema_versions.insert(schema_version.to_string());
+            }
+        }
+
+        self.anoncreds_service.prover.resolve_schema_version_ranges(query, &schema_versions)
+    }
+
     fn _query_requested_credentials(&self,
                                     wallet_handle: WalletHandle,
                                     query_json: &Query,
                                     predicate_info: Option<&PredicateInfo>,
-                                    interval: &Option<NonRevocedInterval>) -> IndyResult<Vec<RequestedCredential>> {
+                                    intervals: &[NonRevocedInterval]) -> IndyResult<Vec<RequestedCredential>> {
         debug!("_query_requested_credentials >>> wallet_handle: {:?}, query_json: {:?}, predicate_info: {:?}",
                wallet_handle, query_json, predicate_info);
 
-        let mut credentials_search =
-            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query_json.to_string(), &SearchOptions::id_value())?;
+        let credentials = match predicate_info {
+            Some(predicate) => {
+                let (tagged_query, untagged_query) = self.anoncreds_service.prover.build_predicate_queries(query_json, predicate)?;
+
+                let mut tagged_credentials_search =
+                    self.wallet_service.search_indy_records::<Credential>(wallet_handle, &tagged_query.to_string(), &SearchOptions::id_value())?;
+
+                let mut credentials = self._get_requested_credentials(&mut tagged_credentials_search, None, intervals, None)?;
+
+                let mut untagged_credentials_search =
+                    self.wallet_service.search_indy_records::<Credential>(wallet_handle, &untagged_query.to_string(), &SearchOptions::id_value())?;
 
-        let credentials = self._get_requested_credentials(&mut credentials_search, predicate_info, interval, None)?;
+                credentials.extend(self._get_requested_credentials(&mut untagged_credentials_search, Some(predicate), intervals, None)?);
 
-        debug!("_query_requested_credentials <<< credentials: {:?}", credentials);
+                credentials
+            }
+            None => {
+                let mut credentials_search =
+                    self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query_json.to_string(), &SearchOptions::id_value())?;
+
+                self._get_requested_credentials(&mut credentials_search, None, intervals, None)?
+            }
+        };
+
+        debug!("_query_requested_credentials <<< credentials: {:?}", sensitive!(&credentials));
 
         Ok(credentials)
     }
 
+    // Indices left revoked by the given deltas grouped by unqualified registry id
+    fn _revoked_indices(rev_reg_deltas: RevocationRegistryDeltas) -> IndyResult<HashMap<String, HashSet<u32>>> {
+        let mut revoked: HashMap<String, HashSet<u32>> = HashMap::new();
+
+        for (rev_reg_id, rev_reg_delta_history) in rev_reg_deltas {
+            let indices = revoked.entry(rev_reg_id.to_unqualified().0).or_insert_with(HashSet::new);
+
+            apply_revoked_indices(indices, &rev_reg_delta_history.into_deltas())
+                .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+        }
+
+        Ok(revoked)
+    }
+
+    fn _is_revoked(revoked: &HashMap<String, HashSet<u32>>, rev_reg_id: Option<&RevocationRegistryId>, cred_rev_id: Option<u32>) -> bool {
+        match (rev_reg_id, cred_rev_id) {
+            (Some(rev_reg_id), Some(cred_rev_id)) =>
+                revoked.get(&rev_reg_id.to_unqualified().0)
+                    .map(|indices| indices.contains(&cred_rev_id))
+                    .unwrap_or(false),
+            _ => false
+        }
+    }
+
+    fn _explain_unsatisfiable_referent(&self,
+                                       wallet_handle: WalletHandle,
+                                       version: &ProofRequestsVersion,
+                                       referent: &str,
+                                       name: &str,
+                                       restrictions: &Option<Query>,
+                                       predicate_info: Option<&PredicateInfo>,
+                                       intervals: &[NonRevocedInterval],
+                                       revoked: &HashMap<String, HashSet<u32>>) -> IndyResult<Option<String>> {
+        let query = self._build_proof_request_query(wallet_handle, version, name, referent, restrictions, &None)?;
+
+        let credentials = self._query_requested_credentials(wallet_handle, &query, predicate_info, intervals)?;
+
+        if !credentials.is_empty() {
+            return Ok(Self::_explain_not_revoked(&credentials, intervals, revoked));
+        }
+
+        let unrestricted_query = self._build_proof_request_query(wallet_handle, version, name, referent, &None, &None)?;
+
+        if self._query_requested_credentials(wallet_handle, &unrestricted_query, None, &[])?.is_empty() {
+            return Ok(Some(format!("no credential contains attribute \"{}\"", name)));
+        }
+
+        if let Some(restrictions) = restrictions {
+            if self._query_requested_credentials(wallet_handle, &query, None, &[])?.is_empty() {
+                return Ok(Some(format!("no credential with attribute \"{}\" matches restrictions {}", name, restrictions.to_string())));
+            }
+        }
+
+        match predicate_info {
+            Some(predicate) => {
+                let p_type = serde_json::to_value(&predicate.p_type)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize PredicateTypes")?;
+
+                let upper_bound = predicate.p_value_max
+                    .map(|p_value_max| format!(" and {}<{}", name, p_value_max))
+                    .unwrap_or_default();
+
+                Ok(Some(format!("no credential satisfies predicate {}{}{}{}", name, p_type.as_str().unwrap_or_default(), predicate.p_value, upper_bound)))
+            }
+            None => Ok(None)
+        }
+    }
+
+    // Non-revocation proof needs a revocable credential that is not revoked by the known registry deltas
+    fn _explain_not_revoked(credentials: &[RequestedCredential],
+                            intervals: &[NonRevocedInterval],
+                            revoked: &HashMap<String, HashSet<u32>>) -> Option<String> {
+        if intervals.is_empty() {
+            return None;
+        }
+
+        let revocable: Vec<&CredentialInfo> = credentials.iter()
+            .map(|credential| &credential.cred_info)
+            .filter(|cred_info| cred_info.rev_reg_id.is_some())
+            .collect();
+
+        if revocable.is_empty() {
+            return Some("non-revocation is requested, but no matching credential is revocable".to_string());
+        }
+
+        let all_revoked = revocable.iter().all(|cred_info| {
+            let cred_rev_id = cred_info.cred_rev_id.as_ref().and_then(|cred_rev_id| cred_rev_id.parse::<u32>().ok());
+            Self::_is_revoked(revoked, cred_info.rev_reg_id.as_ref(), cred_rev_id)
+        });
+
+        if !all_revoked {
+            return None;
+        }
+
+        let mut rev_reg_ids: Vec<&str> = revocable.iter()
+            .filter_map(|cred_info| cred_info.rev_reg_id.as_ref().map(|rev_reg_id| rev_reg_id.0.as_str()))
+            .collect();
+        rev_reg_ids.sort();
+        rev_reg_ids.dedup();
+
+        Some(format!("registry all revoked: every matching credential is revoked in {}", rev_reg_ids.join(", ")))
+    }
+
     fn _get_requested_credentials(&self,
                                   credentials_search: &mut WalletSearch,
                                   predicate_info: Option<&PredicateInfo>,
-                                  interval: &Option<NonRevocedInterval>,
+                                  intervals: &[NonRevocedInterval],
                                   max_count: Option<usize>) -> IndyResult<Vec<RequestedCredential>> {
         let mut credentials: Vec<RequestedCredential> = Vec::new();
 
@@ -859,7 +1449,8 @@ impl ProverCommandExecutor {
             credentials.push(
                 RequestedCredential {
                     cred_info: self._get_credential_info(&referent, credential),
-                    interval: interval.clone()
+                    interval: intervals.first().cloned(),
+                    intervals: if intervals.len() > 1 { intervals.to_vec() } else { Vec::new() }
                 });
 
             if let Some(mut count) = max_count {
@@ -874,8 +1465,127 @@ impl ProverCommandExecutor {
     }
 
 
+    fn _wallet_get_requested_credentials(&self,
+                                         wallet_handle: WalletHandle,
+                                         requested_credentials: &RequestedCredentials) -> IndyResult<HashMap<String, Credential>> {
+        let cred_referents = requested_credentials.cred_ids();
+
+        let mut credentials: HashMap<String, Credential> = HashMap::new();
+
+        for cred_referent in cred_referents.into_iter() {
+            let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, &cred_referent, &RecordOptions::id_value())?;
+            credentials.insert(cred_referent, credential);
+        }
+
+        Ok(credentials)
+    }
+
+    fn _invalidate_proof_plans(&self, wallet_handle: WalletHandle, cred_id: &str) {
+        self.proof_plans.borrow_mut()
+            .retain(|&(plan_wallet_handle, _), plan| plan_wallet_handle != wallet_handle || !plan.cred_ids().contains(cred_id));
+    }
+
     fn _wallet_get_master_secret(&self, wallet_handle: WalletHandle, key: &str) -> IndyResult<MasterSecret> {
         self.wallet_service.get_indy_object(wallet_handle, &key, &RecordOptions::id_value())
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use domain::anoncreds::credential::AttributeValues;
+    use domain::anoncreds::proof_request::PredicateTypes;
+    use domain::wallet::{Config, Credentials, KeyDerivationMethod};
+    use services::wallet::KeyDerivationData;
+    use utils::test;
+
+    const WALLET_KEY: &str = "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw";
+
+    fn _executor() -> ProverCommandExecutor {
+        ProverCommandExecutor::new(Rc::new(AnoncredsService::new()),
+                                   Rc::new(WalletService::new()),
+                                   Rc::new(CryptoService::new()),
+                                   Rc::new(BlobStorageService::new()))
+    }
+
+    fn _open_wallet(executor: &ProverCommandExecutor, name: &str) -> WalletHandle {
+        let config = Config { id: name.to_string(), storage_type: None, storage_config: None };
+        let credentials = Credentials {
+            key: WALLET_KEY.to_string(),
+            rekey: None,
+            storage_credentials: None,
+            key_derivation_method: KeyDerivationMethod::RAW,
+            rekey_derivation_method: KeyDerivationMethod::RAW,
+        };
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(WALLET_KEY, &KeyDerivationMethod::RAW);
+        executor.wallet_service.create_wallet(&config, &credentials, (&key_data, &key_data.calc_master_key().unwrap())).unwrap();
+
+        let (wallet_handle, key_data, _) = executor.wallet_service.open_wallet_prepare(&config, &credentials).unwrap();
+        executor.wallet_service.open_wallet_continue(wallet_handle, (&key_data.calc_master_key().unwrap(), None)).unwrap()
+    }
+
+    fn _credential(age: &str) -> Credential {
+        let mut attr_values: HashMap<String, AttributeValues> = HashMap::new();
+        attr_values.insert("age".to_string(), AttributeValues { raw: age.to_string(), encoded: age.to_string() });
+
+        serde_json::from_str::<Credential>(
+            &json!({
+                "schema_id": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0",
+                "cred_def_id": "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag",
+                "values": attr_values,
+                "signature": json!({
+                    "p_credential": json!({"m_2": "0","a": "0","e": "0","v": "0"})
+                }),
+                "signature_correctness_proof": json!({"se":"0", "c":"0"})
+            }).to_string()
+        ).unwrap()
+    }
+
+    fn _store_credential(executor: &ProverCommandExecutor, wallet_handle: WalletHandle, id: &str, age: &str, catpol: Option<&CredentialAttrTagPolicy>) {
+        let credential = _credential(age);
+        let tags = executor.anoncreds_service.prover.build_credential_tags(&credential, catpol).unwrap();
+        executor.wallet_service.add_indy_object(wallet_handle, id, &credential, &tags).unwrap();
+    }
+
+    #[test]
+    fn query_requested_credentials_works_for_predicate_filtered_by_tags() {
+        let name = "query_requested_credentials_works_for_predicate_filtered_by_tags";
+        test::cleanup_wallet(name);
+        {
+            let executor = _executor();
+            let wallet_handle = _open_wallet(&executor, name);
+
+            let mut catpol = CredentialAttrTagPolicy::from(vec!["age".to_string()]);
+            catpol.range_queryable.insert("age".to_string());
+
+            _store_credential(&executor, wallet_handle, "tagged_adult", "28", Some(&catpol));
+            _store_credential(&executor, wallet_handle, "tagged_minor", "15", Some(&catpol));
+            _store_credential(&executor, wallet_handle, "untagged_adult", "35", None);
+            _store_credential(&executor, wallet_handle, "untagged_minor", "12", None);
+
+            let query = Query::Eq("attr::age::marker".to_string(), "1".to_string());
+            let predicate = PredicateInfo {
+                name: "age".to_string(),
+                p_type: PredicateTypes::GE,
+                p_value: 18,
+                p_value_max: None,
+                scale: None,
+                restrictions: None,
+                non_revoked: None,
+            };
+
+            let credentials = executor._query_requested_credentials(wallet_handle, &query, Some(&predicate), &[]).unwrap();
+
+            let mut referents: Vec<String> = credentials.into_iter().map(|credential| credential.cred_info.referent).collect();
+            referents.sort();
+            assert_eq!(vec!["tagged_adult".to_string(), "untagged_adult".to_string()], referents);
+
+            // only the credentials without numeric value tags are decoded
+            assert_eq!(2, executor.anoncreds_service.prover.predicate_value_checks.get());
+        }
+        test::cleanup_wallet(name);
+    }
+}
@@ -1,6 +1,6 @@
 use errors::prelude::*;
 use services::blob_storage::BlobStorageService;
-use domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinitionV1;
+use domain::anoncreds::revocation_registry_definition::{tails_count, RevocationRegistryDefinitionV1};
 
 use ursa::cl::{Tail, RevocationTailsAccessor, RevocationTailsGenerator};
 use ursa::errors::prelude::{UrsaCryptoError, UrsaCryptoErrorKind};
@@ -24,14 +24,38 @@ impl SDKTailsAccessor {
         let tails_hash = rev_reg_def.value.tails_hash.from_base58()
             .map_err(|_| err_msg(IndyErrorKind::InvalidState, "Invalid base58 for Tails hash"))?;
 
-        let tails_reader_handle = tails_service.open_blob(tails_reader_handle,
-                                                          &rev_reg_def.value.tails_location,
-                                                          tails_hash.as_slice())?;
+        let tails_reader_handle = tails_service.open_verified_blob(tails_reader_handle,
+                                                                   &rev_reg_def.value.tails_location,
+                                                                   tails_hash.as_slice())?;
 
-        Ok(SDKTailsAccessor {
+        let tails_accessor = SDKTailsAccessor {
             tails_service,
             tails_reader_handle
-        })
+        };
+
+        tails_accessor.check_tails_count(rev_reg_def.value.max_cred_num)?;
+
+        Ok(tails_accessor)
+    }
+
+    // Registry of `max_cred_num` capacity is backed by exactly `2 * max_cred_num + 1` tails
+    fn check_tails_count(&self, max_cred_num: u32) -> IndyResult<()> {
+        let tails_count = tails_count(max_cred_num)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))? as usize;
+
+        let last_tail = self.tails_service.read(self.tails_reader_handle,
+                                                TAIL_SIZE,
+                                                TAIL_SIZE * (tails_count - 1) + TAILS_BLOB_TAG_SZ as usize)?;
+
+        let extra_bytes = self.tails_service.read(self.tails_reader_handle,
+                                                  1,
+                                                  TAIL_SIZE * tails_count + TAILS_BLOB_TAG_SZ as usize)?;
+
+        if last_tail.len() != TAIL_SIZE || !extra_bytes.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Registry/private data size mismatch"));
+        }
+
+        Ok(())
     }
 }
 
@@ -54,7 +78,12 @@ impl RevocationTailsAccessor for SDKTailsAccessor {
                   TAIL_SIZE,
                   TAIL_SIZE * tail_id as usize + TAILS_BLOB_TAG_SZ as usize)
             .map_err(|_|
-                UrsaCryptoError::from_msg(UrsaCryptoErrorKind::InvalidState, "Can't read tail bytes from blob storage"))?; // FIXME: IO error should be returned
+                UrsaCryptoError::from_msg(UrsaCryptoErrorKind::IOError, "Can't read tail bytes from blob storage"))?;
+
+        if tail_bytes.len() != TAIL_SIZE {
+            return Err(UrsaCryptoError::from_msg(UrsaCryptoErrorKind::InvalidRevocationAccumulatorIndex,
+                                                 format!("Tail {} is missing in tails file", tail_id)));
+        }
 
         let tail = Tail::from_bytes(tail_bytes.as_slice())?;
         accessor(&tail);
@@ -84,3 +113,64 @@ pub fn store_tails_from_generator(service: Rc<BlobStorageService>,
     debug!("store_tails_from_generator <<< res: {:?}", res);
     Ok(res)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use utils::environment;
+    use utils::test;
+
+    const MAX_CRED_NUM: u32 = 5;
+
+    fn _tails_accessor(name: &str, tails_count: usize) -> SDKTailsAccessor {
+        test::cleanup_temp(name);
+
+        let base_dir = environment::tmp_path().join(name);
+        let config = json!({"base_dir": base_dir.to_str().unwrap(), "uri_pattern": ""}).to_string();
+
+        let tails_service = Rc::new(BlobStorageService::new());
+
+        let writer_handle = tails_service.open_writer("default", &config).unwrap();
+        let blob_handle = tails_service.create_blob(writer_handle).unwrap();
+        tails_service.append(blob_handle, &[0u8, TAILS_BLOB_TAG_SZ]).unwrap();
+        tails_service.append(blob_handle, &vec![0u8; TAIL_SIZE * tails_count]).unwrap();
+        let (location, hash) = tails_service.finalize(blob_handle).unwrap();
+
+        let reader_handle = tails_service.open_reader("default", &config).unwrap();
+        let tails_reader_handle = tails_service.open_blob(reader_handle, &location, &hash).unwrap();
+
+        SDKTailsAccessor {
+            tails_service,
+            tails_reader_handle
+        }
+    }
+
+    #[test]
+    fn check_tails_count_works() {
+        let tails_accessor = _tails_accessor("check_tails_count_works", 2 * MAX_CRED_NUM as usize + 1);
+        tails_accessor.check_tails_count(MAX_CRED_NUM).unwrap();
+    }
+
+    #[test]
+    fn check_tails_count_works_for_smaller_registry() {
+        let tails_accessor = _tails_accessor("check_tails_count_works_for_smaller_registry", 2 * MAX_CRED_NUM as usize + 1);
+        let res = tails_accessor.check_tails_count(MAX_CRED_NUM + 1);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn check_tails_count_works_for_bigger_registry() {
+        let tails_accessor = _tails_accessor("check_tails_count_works_for_bigger_registry", 2 * MAX_CRED_NUM as usize + 1);
+        let res = tails_accessor.check_tails_count(MAX_CRED_NUM - 1);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn access_tail_works_for_missing_tail() {
+        let tails_accessor = _tails_accessor("access_tail_works_for_missing_tail", 2 * MAX_CRED_NUM as usize + 1);
+
+        let res = tails_accessor.access_tail(2 * MAX_CRED_NUM + 1, &mut |_| {}).map_err(IndyError::from);
+        assert_kind!(IndyErrorKind::InvalidUserRevocId, res);
+    }
+}
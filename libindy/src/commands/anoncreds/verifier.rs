@@ -19,7 +19,34 @@ pub enum VerifierCommand {
         RevocationRegistryDefinitions, // rev reg defs
         RevocationRegistries, // rev reg entries
         Box<dyn Fn(IndyResult<bool>) + Send>),
+    VerifyProofWithResult(
+        ProofRequest, // proof request
+        Proof, // proof
+        Schemas, // credential schemas
+        CredentialDefinitions, // credential defs
+        RevocationRegistryDefinitions, // rev reg defs
+        RevocationRegistries, // rev reg entries
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyProofDetailed(
+        ProofRequest, // proof request
+        Proof, // proof
+        Schemas, // credential schemas
+        CredentialDefinitions, // credential defs
+        RevocationRegistryDefinitions, // rev reg defs
+        RevocationRegistries, // rev reg entries
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyProofDryRun(
+        ProofRequest, // proof request
+        Proof, // proof
+        Schemas, // credential schemas
+        CredentialDefinitions, // credential defs
+        RevocationRegistryDefinitions, // rev reg defs
+        RevocationRegistries, // rev reg entries
+        Box<dyn Fn(IndyResult<()>) + Send>),
     GenerateNonce(
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRequiredIdentifiers(
+        Proof, // proof
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
@@ -34,6 +61,12 @@ impl VerifierCommandExecutor {
         }
     }
 
+    /// Executor for verification only deployments: proofs are verified against the entities
+    /// passed with the command, so neither wallet nor pool services are initialized.
+    pub fn verifier_only() -> VerifierCommandExecutor {
+        VerifierCommandExecutor::new(Rc::new(AnoncredsService::new()))
+    }
+
     pub fn execute(&self, command: VerifierCommand) {
         match command {
             VerifierCommand::VerifyProof(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb) => {
@@ -44,10 +77,38 @@ impl VerifierCommandExecutor {
                                      &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
                                      &rev_regs_map_to_rev_regs_local_map(rev_regs)));
             }
+            VerifierCommand::VerifyProofWithResult(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofWithResult command received");
+                cb(self.verify_proof_with_result(&proof_request.value(), proof,
+                                                 &schemas_map_to_schemas_v1_map(schemas),
+                                                 &cred_defs_map_to_cred_defs_v1_map(credential_defs),
+                                                 &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                                 &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+            }
+            VerifierCommand::VerifyProofDetailed(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofDetailed command received");
+                cb(self.verify_proof_detailed(&proof_request.value(), proof,
+                                              &schemas_map_to_schemas_v1_map(schemas),
+                                              &cred_defs_map_to_cred_defs_v1_map(credential_defs),
+                                              &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                              &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+            }
+            VerifierCommand::VerifyProofDryRun(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofDryRun command received");
+                cb(self.verify_proof_dry_run(&proof_request.value(), proof,
+                                             &schemas_map_to_schemas_v1_map(schemas),
+                                             &cred_defs_map_to_cred_defs_v1_map(credential_defs),
+                                             &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                             &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+            }
             VerifierCommand::GenerateNonce(cb) => {
                 debug!(target: "verifier_command_executor", "GenerateNonce command received");
                 cb(self.generate_nonce());
             }
+            VerifierCommand::GetRequiredIdentifiers(proof, cb) => {
+                debug!(target: "verifier_command_executor", "GetRequiredIdentifiers command received");
+                cb(self.get_required_identifiers(&proof));
+            }
         };
     }
 
@@ -74,6 +135,81 @@ impl VerifierCommandExecutor {
         Ok(result)
     }
 
+    fn verify_proof_with_result(&self,
+                                proof_req: &ProofRequestPayload,
+                                proof: Proof,
+                                schemas: &HashMap<SchemaId, SchemaV1>,
+                                cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                                rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                                rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<String> {
+        debug!("verify_proof_with_result >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
+               rev_reg_defs: {:?}, rev_regs: {:?}",
+               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        let result = self.anoncreds_service.verifier.verify_with_result(&proof,
+                                                                        &proof_req,
+                                                                        schemas,
+                                                                        cred_defs,
+                                                                        rev_reg_defs,
+                                                                        rev_regs)?;
+
+        let result_json = serde_json::to_string(&result)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize VerificationResult")?;
+
+        debug!("verify_proof_with_result <<< result_json: {:?}", result_json);
+
+        Ok(result_json)
+    }
+
+    fn verify_proof_detailed(&self,
+                             proof_req: &ProofRequestPayload,
+                             proof: Proof,
+                             schemas: &HashMap<SchemaId, SchemaV1>,
+                             cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                             rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                             rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<String> {
+        debug!("verify_proof_detailed >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
+               rev_reg_defs: {:?}, rev_regs: {:?}",
+               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        let report = self.anoncreds_service.verifier.verify_detailed(&proof,
+                                                                     &proof_req,
+                                                                     schemas,
+                                                                     cred_defs,
+                                                                     rev_reg_defs,
+                                                                     rev_regs)?;
+
+        let report_json = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize VerificationReport")?;
+
+        debug!("verify_proof_detailed <<< report_json: {:?}", report_json);
+
+        Ok(report_json)
+    }
+
+    fn verify_proof_dry_run(&self,
+                            proof_req: &ProofRequestPayload,
+                            proof: Proof,
+                            schemas: &HashMap<SchemaId, SchemaV1>,
+                            cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                            rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                            rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<()> {
+        debug!("verify_proof_dry_run >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
+               rev_reg_defs: {:?}, rev_regs: {:?}",
+               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        self.anoncreds_service.verifier.verify_inputs(&proof,
+                                                      &proof_req,
+                                                      schemas,
+                                                      cred_defs,
+                                                      rev_reg_defs,
+                                                      rev_regs)?;
+
+        debug!("verify_proof_dry_run <<<");
+
+        Ok(())
+    }
+
     fn generate_nonce(&self) -> IndyResult<String> {
         debug!("generate_nonce >>> ");
 
@@ -86,4 +222,154 @@ impl VerifierCommandExecutor {
 
         Ok(result)
     }
+
+    fn get_required_identifiers(&self, proof: &Proof) -> IndyResult<String> {
+        debug!("get_required_identifiers >>> proof: {:?}", proof);
+
+        let required_identifiers = proof.required_identifiers();
+
+        let result = serde_json::to_string(&required_identifiers)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RequiredIdentifiers")?;
+
+        debug!("get_required_identifiers <<< result: {:?}", result);
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    use ursa::cl::{new_nonce, SimpleTailsAccessor};
+
+    use domain::anoncreds::credential::{AttributeValues, Credential, CredentialValues};
+    use domain::anoncreds::credential_definition::{CredentialDefinition, SignatureType};
+    use domain::anoncreds::credential_offer::CredentialOffer;
+    use domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
+    use domain::anoncreds::requested_credential::RequestedCredentials;
+    use domain::anoncreds::schema::{AttributeNames, Schema};
+    use domain::crypto::did::DidValue;
+    use services::anoncreds::issuer::Issuer;
+
+    const SCHEMA_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0";
+    const CRED_DEF_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag";
+
+    #[test]
+    fn verifier_only_executor_verifies_proof_without_wallet() {
+        let executor = VerifierCommandExecutor::verifier_only();
+        let anoncreds_service = &executor.anoncreds_service;
+
+        let mut attr_names = AttributeNames::new();
+        attr_names.insert("name".to_string());
+
+        let schema = SchemaV1 {
+            id: SchemaId(SCHEMA_ID.to_string()),
+            name: "gvt".to_string(),
+            version: "1.0".to_string(),
+            attr_names: attr_names.clone(),
+            seq_no: None,
+            attribute_types: None,
+        };
+
+        let (cred_def_data, cred_priv_key, key_correctness_proof) = Issuer::new_credential_definition(&attr_names, false).unwrap();
+
+        let cred_def = CredentialDefinitionV1 {
+            id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+            schema_id: schema.id.clone(),
+            signature_type: SignatureType::CL,
+            tag: "tag".to_string(),
+            value: cred_def_data,
+        };
+
+        let cred_offer = CredentialOffer {
+            schema_id: schema.id.clone(),
+            cred_def_id: cred_def.id.clone(),
+            key_correctness_proof,
+            nonce: new_nonce().unwrap(),
+            method_name: None,
+        };
+
+        let master_secret = anoncreds_service.prover.new_master_secret().unwrap();
+        let (blinded_ms, blinding_factors, blinded_ms_correctness_proof) =
+            anoncreds_service.prover.new_credential_request(&cred_def, &master_secret, &cred_offer).unwrap();
+
+        let cred_request = CredentialRequest {
+            prover_did: DidValue("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string()),
+            cred_def_id: cred_def.id.clone(),
+            blinded_ms,
+            blinded_ms_correctness_proof,
+            nonce: new_nonce().unwrap(),
+        };
+
+        let mut values = CredentialValues::new();
+        values.insert("name".to_string(), AttributeValues { raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string() });
+
+        let (signature, signature_correctness_proof, _) =
+            anoncreds_service.issuer.new_credential::<SimpleTailsAccessor>(&cred_def, &cred_priv_key, &cred_offer.nonce, &cred_request,
+                                                                           &values, None, None, None, None, None).unwrap();
+
+        let mut credential = Credential {
+            schema_id: schema.id.clone(),
+            cred_def_id: cred_def.id.clone(),
+            rev_reg_id: None,
+            values,
+            signature,
+            signature_correctness_proof,
+            rev_reg: None,
+            witness: None,
+        };
+
+        let cred_request_metadata = CredentialRequestMetadata {
+            master_secret_blinding_data: blinding_factors,
+            nonce: cred_request.nonce.try_clone().unwrap(),
+            master_secret_name: "master_secret".to_string(),
+        };
+
+        anoncreds_service.prover.process_credential(&mut credential, &cred_request_metadata, &master_secret, &cred_def, None).unwrap();
+
+        let (sender, receiver) = channel();
+        executor.execute(VerifierCommand::GenerateNonce(Box::new(move |res| sender.send(res).unwrap())));
+        let nonce = receiver.recv().unwrap().unwrap();
+
+        let proof_req: ProofRequest = serde_json::from_value(json!({
+            "nonce": nonce,
+            "name": "proof_req",
+            "version": "0.1",
+            "requested_attributes": {
+                "attr1_referent": {"name": "name"}
+            },
+            "requested_predicates": {}
+        })).unwrap();
+
+        let requested_credentials: RequestedCredentials = serde_json::from_value(json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {"attr1_referent": {"cred_id": "cred_1", "revealed": true}},
+            "requested_predicates": {}
+        })).unwrap();
+
+        let mut credentials = HashMap::new();
+        credentials.insert("cred_1".to_string(), credential);
+
+        let mut schemas = HashMap::new();
+        schemas.insert(schema.id.clone(), schema);
+
+        let mut cred_defs = HashMap::new();
+        cred_defs.insert(cred_def.id.clone(), cred_def);
+
+        let proof = anoncreds_service.prover.create_proof(&credentials, &proof_req, &requested_credentials, &master_secret,
+                                                          &schemas, &cred_defs, &HashMap::new(), &|| false).unwrap();
+
+        assert_eq!("Alex", proof.requested_proof.revealed_attrs["attr1_referent"].raw);
+
+        let (sender, receiver) = channel();
+        let schemas = schemas.into_iter().map(|(id, schema)| (id, Schema::SchemaV1(schema))).collect();
+        let cred_defs = cred_defs.into_iter().map(|(id, cred_def)| (id, CredentialDefinition::CredentialDefinitionV1(cred_def))).collect();
+
+        executor.execute(VerifierCommand::VerifyProof(proof_req, proof, schemas, cred_defs, HashMap::new(), HashMap::new(),
+                                                      Box::new(move |res| sender.send(res).unwrap())));
+        assert!(receiver.recv().unwrap().unwrap());
+    }
 }
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::thread;
 
 use serde_json;
 use serde_json::Value;
@@ -22,6 +23,8 @@ use services::crypto::CryptoService;
 use services::ledger::LedgerService;
 use services::pool::{
     PoolService,
+    Retry,
+    check_freshness,
     parse_response_metadata
 };
 use services::wallet::{RecordOptions, WalletService};
@@ -42,10 +45,18 @@ pub enum LedgerCommand {
         PoolHandle, // pool handle
         String, // request json
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SubmitRequestWithFreshness(
+        PoolHandle, // pool handle
+        String, // request json
+        u64, // freshness threshold in seconds
+        Box<dyn Fn(IndyResult<String>) + Send>),
     SubmitAck(
         CommandHandle,
         IndyResult<String>, // result json or error
     ),
+    SubmitRetry(
+        CommandHandle, // handle of request waiting for retry
+    ),
     SubmitAction(
         PoolHandle, // pool handle
         String, // request json
@@ -276,6 +287,15 @@ pub struct LedgerCommandExecutor {
 
     send_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String)>)>>>,
+    pending_submits: RefCell<HashMap<CommandHandle, PendingSubmit>>,
+}
+
+struct PendingSubmit {
+    pool_handle: PoolHandle,
+    request_json: String,
+    retry: Retry,
+    freshness_threshold: Option<u64>,
+    cb: Box<dyn Fn(IndyResult<String>)>,
 }
 
 impl LedgerCommandExecutor {
@@ -290,6 +310,7 @@ impl LedgerCommandExecutor {
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_submits: RefCell::new(HashMap::new()),
         }
     }
 
@@ -303,8 +324,16 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "SubmitRequest command received");
                 self.submit_request(handle, &request_json, cb);
             }
+            LedgerCommand::SubmitRequestWithFreshness(handle, request_json, freshness_threshold, cb) => {
+                debug!(target: "ledger_command_executor", "SubmitRequestWithFreshness command received");
+                self.submit_request_with_freshness(handle, &request_json, Some(freshness_threshold), cb);
+            }
             LedgerCommand::SubmitAck(handle, result) => {
                 debug!(target: "ledger_command_executor", "SubmitAck command received");
+                let pending_submit = self.pending_submits.borrow_mut().remove(&handle);
+                if let Some(pending_submit) = pending_submit {
+                    return self._submit_request_ack(pending_submit, result.map_err(IndyError::from));
+                }
                 match self.send_callbacks.borrow_mut().remove(&handle) {
                     Some(cb) => cb(result.map_err(IndyError::from)),
                     None => {
@@ -313,6 +342,14 @@ impl LedgerCommandExecutor {
                     }
                 }
             }
+            LedgerCommand::SubmitRetry(handle) => {
+                debug!(target: "ledger_command_executor", "SubmitRetry command received");
+                let pending_submit = self.pending_submits.borrow_mut().remove(&handle);
+                match pending_submit {
+                    Some(pending_submit) => self._submit_request(pending_submit),
+                    None => error!("Can't process LedgerCommand::SubmitRetry for handle {:?} - appropriate request not found!", handle)
+                }
+            }
             LedgerCommand::SubmitAction(handle, request_json, nodes, timeout, cb) => {
                 debug!(target: "ledger_command_executor", "SubmitRequest command received");
                 self.submit_action(handle, &request_json, nodes.as_ref().map(String::as_str), timeout, cb);
@@ -591,17 +628,58 @@ impl LedgerCommandExecutor {
                       cb: Box<dyn Fn(IndyResult<String>) + Send>) {
         debug!("submit_request >>> handle: {:?}, request_json: {:?}", handle, request_json);
 
+        self.submit_request_with_freshness(handle, request_json, None, cb);
+    }
+
+    fn submit_request_with_freshness(&self,
+                                     handle: PoolHandle,
+                                     request_json: &str,
+                                     freshness_threshold: Option<u64>,
+                                     cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("submit_request_with_freshness >>> handle: {:?}, request_json: {:?}, freshness_threshold: {:?}",
+               handle, request_json, freshness_threshold);
+
         if let Err(err) = serde_json::from_str::<Request<serde_json::Value>>(&request_json) {
             return cb(Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Request is invalid json: {:?}", err))));
         }
 
-        let x: IndyResult<CommandHandle> = self.pool_service.send_tx(handle, request_json);
-        match x {
-            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, cb); }
-            Err(err) => { cb(Err(err)); }
+        let retry = try_cb!(self.pool_service.retry(handle, request_json), cb);
+
+        self._submit_request(PendingSubmit { pool_handle: handle, request_json: request_json.to_string(), retry, freshness_threshold, cb });
+    }
+
+    fn _submit_request(&self, pending_submit: PendingSubmit) {
+        match self.pool_service.send_tx(pending_submit.pool_handle, &pending_submit.request_json) {
+            Ok(cmd_id) => { self.pending_submits.borrow_mut().insert(cmd_id, pending_submit); }
+            Err(err) => { (pending_submit.cb)(Err(err)); }
         };
     }
 
+    fn _submit_request_ack(&self, mut pending_submit: PendingSubmit, result: IndyResult<String>) {
+        match pending_submit.retry.next_delay(&result) {
+            Some(delay) => {
+                debug!("_submit_request_ack: attempt {} of request failed with {:?}, retry in {:?}",
+                       pending_submit.retry.attempt() - 1, result, delay);
+
+                // waiting is done outside of command thread, the request is sent again by SubmitRetry command
+                let retry_handle = next_command_handle();
+                self.pending_submits.borrow_mut().insert(retry_handle, pending_submit);
+
+                thread::spawn(move || {
+                    thread::sleep(delay);
+                    if let Err(err) = CommandExecutor::instance().send(Command::Ledger(LedgerCommand::SubmitRetry(retry_handle))) {
+                        error!("_submit_request_ack: can't schedule retry of request: {:?}", err);
+                    }
+                });
+            }
+            None => {
+                let result = result
+                    .and_then(|response| check_freshness(&response, pending_submit.freshness_threshold).map(|_| response));
+                (pending_submit.cb)(result)
+            }
+        }
+    }
+
     fn submit_action(&self,
                      handle: PoolHandle,
                      request_json: &str,
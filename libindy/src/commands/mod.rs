@@ -25,7 +25,10 @@ use services::blob_storage::BlobStorageService;
 use services::crypto::CryptoService;
 use services::ledger::LedgerService;
 use services::payments::PaymentsService;
-use services::pool::{PoolService, set_freshness_threshold};
+use services::pool::{PoolService, set_freshness_threshold, set_offline};
+use services::anoncreds::verifier::set_proof_limits;
+use services::anoncreds::helpers::{set_max_schema_attributes, set_predicate_max_bits};
+use services::anoncreds::prover::set_witness_cache_size;
 use services::wallet::WalletService;
 
 use self::threadpool::ThreadPool;
@@ -75,6 +78,21 @@ pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(threshold) = config.freshness_threshold {
         set_freshness_threshold(threshold);
     }
+    if let Some(proof_limits) = config.proof_limits {
+        set_proof_limits(proof_limits);
+    }
+    if let Some(max_bits) = config.predicate_max_bits {
+        set_predicate_max_bits(max_bits);
+    }
+    if let Some(size) = config.witness_cache_size {
+        set_witness_cache_size(size);
+    }
+    if let Some(max_attributes) = config.max_schema_attributes {
+        set_max_schema_attributes(max_attributes);
+    }
+    if let Some(offline) = config.offline {
+        set_offline(offline);
+    }
 }
 
 pub struct CommandExecutor {
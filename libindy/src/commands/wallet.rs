@@ -4,10 +4,12 @@ use std::rc::Rc;
 
 use api::wallet::*;
 use commands::{Command, CommandExecutor};
+use commands::anoncreds::AnoncredsCommand;
+use commands::anoncreds::prover::ProverCommand;
 use domain::wallet::{Config, Credentials, ExportConfig, KeyConfig, Metadata};
 use errors::prelude::*;
 use services::crypto::CryptoService;
-use services::wallet::{KeyDerivationData, WalletService};
+use services::wallet::{EXPORT_VERSION, KeyDerivationData, WalletService};
 use utils::crypto::{chacha20poly1305_ietf, randombytes};
 use utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 use api::{WalletHandle, CallbackHandle};
@@ -68,6 +70,7 @@ pub enum WalletCommand {
                    CallbackHandle),
     Export(WalletHandle,
            ExportConfig, // export config
+           Option<Box<dyn Fn(usize, Option<usize>) + Send>>, // progress
            Box<dyn Fn(IndyResult<()>) + Send>),
     ExportContinue(WalletHandle,
                    ExportConfig, // export config
@@ -77,6 +80,7 @@ pub enum WalletCommand {
     Import(Config, // config
            Credentials, // credentials
            ExportConfig, // import config
+           Option<Box<dyn Fn(usize, Option<usize>) + Send>>, // progress
            Box<dyn Fn(IndyResult<()>) + Send>),
     ImportContinue(Config, // config
                    Credentials, // credentials
@@ -101,7 +105,8 @@ pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
-    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
+    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>,
+    pending_progress_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(usize, Option<usize>) + Send>>>
 }
 
 impl WalletCommandExecutor {
@@ -110,7 +115,8 @@ impl WalletCommandExecutor {
             wallet_service,
             crypto_service,
             open_callbacks: RefCell::new(HashMap::new()),
-            pending_callbacks: RefCell::new(HashMap::new())
+            pending_callbacks: RefCell::new(HashMap::new()),
+            pending_progress_callbacks: RefCell::new(HashMap::new())
         }
     }
 
@@ -158,17 +164,17 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "DeleteContinue command received");
                 self._delete_continue(cb_id, &config, &credentials, &metadata, key_result)
             }
-            WalletCommand::Export(wallet_handle, export_config, cb) => {
+            WalletCommand::Export(wallet_handle, export_config, progress_cb, cb) => {
                 debug!(target: "wallet_command_executor", "Export command received");
-                self._export(wallet_handle, &export_config, cb)
+                self._export(wallet_handle, &export_config, progress_cb, cb)
             }
             WalletCommand::ExportContinue(wallet_handle, export_config, key_data, key_result, cb_id) => {
                 debug!(target: "wallet_command_executor", "ExportContinue command received");
                 self._export_continue(cb_id, wallet_handle, &export_config, key_data, key_result)
             }
-            WalletCommand::Import(config, credentials, import_config, cb) => {
+            WalletCommand::Import(config, credentials, import_config, progress_cb, cb) => {
                 debug!(target: "wallet_command_executor", "Import command received");
-                self._import(&config, &credentials, &import_config, cb);
+                self._import(&config, &credentials, &import_config, progress_cb, cb);
             }
             WalletCommand::ImportContinue(config, credential, key_result, wallet_handle, cb_id) => {
                 debug!(target: "wallet_command_executor", "ImportContinue command received");
@@ -338,6 +344,11 @@ impl WalletCommandExecutor {
 
         self.wallet_service.close_wallet(wallet_handle)?;
 
+        // cached proof plans refer to credentials of the closed wallet
+        CommandExecutor::instance().send(
+            Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::ForgetProofPlans(wallet_handle)))
+        ).unwrap();
+
         trace!("_close <<< res: ()");
         Ok(())
     }
@@ -390,6 +401,7 @@ impl WalletCommandExecutor {
     fn _export(&self,
                wallet_handle: WalletHandle,
                export_config: &ExportConfig,
+               progress_cb: Option<Box<dyn Fn(usize, Option<usize>) + Send>>,
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_export >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
 
@@ -398,6 +410,10 @@ impl WalletCommandExecutor {
         let cb_id = ::utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
 
+        if let Some(progress_cb) = progress_cb {
+            self.pending_progress_callbacks.borrow_mut().insert(cb_id, progress_cb);
+        }
+
         let export_config = export_config.clone();
 
         CommandExecutor::instance().send(
@@ -425,14 +441,18 @@ impl WalletCommandExecutor {
                         key_data: KeyDerivationData,
                         key_result: DeriveKeyResult<MasterKey>) {
         let cb = get_cb!(self, cb_id);
+        let progress_cb = self.pending_progress_callbacks.borrow_mut().remove(&cb_id);
+
         cb(key_result
-            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key)))) // TODO - later add proper versioning
+            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, EXPORT_VERSION, (&key_data, &key),
+                                                              progress_cb.as_ref().map(|progress_cb| &**progress_cb as &dyn Fn(usize, Option<usize>)))))
     }
 
     fn _import(&self,
                config: &Config,
                credentials: &Credentials,
                import_config: &ExportConfig,
+               progress_cb: Option<Box<dyn Fn(usize, Option<usize>) + Send>>,
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_import >>> config: {:?}, credentials: {:?}, import_config: {:?}",
                config, secret!(credentials), secret!(import_config));
@@ -442,6 +462,10 @@ impl WalletCommandExecutor {
         let cb_id : CallbackHandle = ::utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
 
+        if let Some(progress_cb) = progress_cb {
+            self.pending_progress_callbacks.borrow_mut().insert(cb_id, progress_cb);
+        }
+
         let config = config.clone();
         let credentials = credentials.clone();
 
@@ -481,8 +505,11 @@ impl WalletCommandExecutor {
                         credential: &Credentials,
                         key_result: DeriveKeyResult<(MasterKey, MasterKey)>) {
         let cb = get_cb!(self, cb_id);
+        let progress_cb = self.pending_progress_callbacks.borrow_mut().remove(&cb_id);
+
         cb(key_result
-            .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key)))
+            .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key,
+                                                                       progress_cb.as_ref().map(|progress_cb| &**progress_cb as &dyn Fn(usize, Option<usize>)))))
     }
 
     fn _generate_key(&self,
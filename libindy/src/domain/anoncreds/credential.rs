@@ -5,7 +5,7 @@ use ursa::cl::{
     Witness
 };
 
-use super::schema::SchemaId;
+use super::schema::{attr_common_view, SchemaId};
 use super::credential_definition::CredentialDefinitionId;
 use super::revocation_registry_definition::RevocationRegistryId;
 
@@ -30,10 +30,51 @@ pub struct Credential {
 impl Credential {
     pub const QUALIFIABLE_TAGS: [&'static str; 5] = ["issuer_did", "cred_def_id", "schema_id", "schema_issuer_did", "rev_reg_id"];
     pub const EXTRA_TAG_SUFFIX: &'static str = "_short";
+    pub const TAGS_VERSION_TAG: &'static str = "tags_version";
+    pub const TAGS_VERSION: &'static str = "1";
 
     pub fn add_extra_tag_suffix(tag: &str) -> String {
         format!("{}{}", tag, Self::EXTRA_TAG_SUFFIX)
     }
+
+    pub fn to_unqualified(self) -> Credential {
+        Credential {
+            schema_id: self.schema_id.to_unqualified(),
+            cred_def_id: self.cred_def_id.to_unqualified(),
+            rev_reg_id: self.rev_reg_id.map(|rev_reg_id| rev_reg_id.to_unqualified()),
+            ..self
+        }
+    }
+}
+
+// Approximate json sizes of the credential parts, in bytes. Big numbers are serialized as decimal
+// strings: primary signature (m_2, a, e, v) and its correctness proof (se, c) for 2048-bit keys,
+// identifiers and field names included.
+const PRIMARY_CREDENTIAL_SIZE: u64 = 2_800;
+// Attribute name, raw value and encoded value (up to 256-bit decimal for non integer values).
+const CREDENTIAL_ATTRIBUTE_SIZE: u64 = 100;
+// Non-revocation signature (G1/G2 points and group order elements in hex), accumulator and witness.
+const NON_REVOCATION_CREDENTIAL_SIZE: u64 = 2_750;
+
+/// Estimates wallet storage in bytes needed for `count` credentials of a schema with
+/// `schema_attr_count` attributes. The estimate is within 25% of the byte length of the stored
+/// credential json; wallet encryption and tags are not taken into account.
+pub fn estimate_credential_storage(schema_attr_count: usize, revocable: bool, count: usize) -> u64 {
+    let mut size = PRIMARY_CREDENTIAL_SIZE + CREDENTIAL_ATTRIBUTE_SIZE * schema_attr_count as u64;
+
+    if revocable {
+        size += NON_REVOCATION_CREDENTIAL_SIZE;
+    }
+
+    size * count as u64
+}
+
+/// Credential values signed by the issuer, kept in the issuer wallet to re-issue the credential
+/// without trusting the values presented by the holder.
+#[derive(Debug, Deserialize, Serialize, NamedType)]
+pub struct IssuedCredentialValues {
+    pub cred_def_id: CredentialDefinitionId,
+    pub values: CredentialValues
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
@@ -46,6 +87,20 @@ pub struct CredentialInfo {
     pub cred_rev_id: Option<String>
 }
 
+impl CredentialInfo {
+    /// Attribute names of the schema the credential was issued against, in canonical form
+    /// (lowercase, without spaces) and sorted. Credentials hold a value for every schema attribute,
+    /// so names are taken from the stored values without fetching the schema from the ledger.
+    pub fn attribute_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.attrs.keys()
+            .map(|name| attr_common_view(name))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
 pub type ShortCredentialValues = HashMap<String, String>;
 
 pub type CredentialValues = HashMap<String, AttributeValues>;
@@ -56,6 +111,21 @@ pub struct AttributeValues {
     pub encoded: String
 }
 
+/// Mismatch between credential values and the schema they are going to be issued against.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AttributeProblem {
+    pub attr: String,
+    pub problem: AttributeProblemKind
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeProblemKind {
+    Missing,
+    InvalidEncoding,
+    NotInSchema
+}
+
 impl Validatable for CredentialValues {
     fn validate(&self) -> Result<(), String> {
         if self.is_empty() {
@@ -82,4 +152,43 @@ impl Validatable for Credential {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credential_info_attribute_names_works() {
+        let credential_info: CredentialInfo = serde_json::from_value(json!({
+            "referent": "cred_1",
+            "attrs": {"Name": "Alex", "age": "28", "Home Address": "Street 1"},
+            "schema_id": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0",
+            "cred_def_id": "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag",
+            "rev_reg_id": null,
+            "cred_rev_id": null
+        })).unwrap();
+
+        assert_eq!(vec!["age", "homeaddress", "name"], credential_info.attribute_names());
+    }
+
+    mod estimate_credential_storage {
+        use super::*;
+
+        #[test]
+        fn estimate_credential_storage_works_for_empty_batch() {
+            assert_eq!(0, estimate_credential_storage(4, true, 0));
+        }
+
+        #[test]
+        fn estimate_credential_storage_works_for_count() {
+            assert_eq!(10 * estimate_credential_storage(4, false, 1), estimate_credential_storage(4, false, 10));
+        }
+
+        #[test]
+        fn estimate_credential_storage_works_for_attributes_and_revocation() {
+            assert!(estimate_credential_storage(10, false, 1) > estimate_credential_storage(4, false, 1));
+            assert!(estimate_credential_storage(4, true, 1) > estimate_credential_storage(4, false, 1));
+        }
+    }
+}
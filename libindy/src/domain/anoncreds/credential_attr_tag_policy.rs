@@ -1,31 +1,58 @@
 use std::collections::HashSet;
 
 use serde::ser::{Serialize, Serializer, SerializeSeq};
-use serde::de::{Deserializer, Deserialize};
+use serde::de::{self, Deserializer, Deserialize};
 
 use named_type::NamedType;
 
 #[derive(Debug, NamedType)]
 pub struct CredentialAttrTagPolicy {
-    pub taggable: HashSet<String>
+    pub taggable: HashSet<String>,
+    // taggable attributes additionally tagged in unencrypted sortable form to support range queries
+    pub range_queryable: HashSet<String>
 }
 
 impl CredentialAttrTagPolicy {
     pub fn is_taggable(&self, attr_name: &str) -> bool {
         self.taggable.contains(&attr_name.to_string().replace(" ", "").to_lowercase())
     }
+
+    pub fn is_range_queryable(&self, attr_name: &str) -> bool {
+        self.range_queryable.contains(&attr_name.to_string().replace(" ", "").to_lowercase())
+    }
 }
 
 impl From<Vec<String>> for CredentialAttrTagPolicy {
     fn from(taggables: Vec<String>) -> Self {
         CredentialAttrTagPolicy {
-            taggable: taggables.into_iter().map(|a| a.replace(" ", "").to_lowercase()).collect()
+            taggable: taggables.into_iter().map(|a| a.replace(" ", "").to_lowercase()).collect(),
+            range_queryable: HashSet::new()
         }
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct CredentialAttrTagPolicyV2 {
+    taggable: Vec<String>,
+    range_queryable: Vec<String>
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CredentialAttrTagPolicyRepr {
+    Taggable(Vec<String>),
+    WithRangeQueryable(CredentialAttrTagPolicyV2)
+}
+
 impl Serialize for CredentialAttrTagPolicy {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer, {
+        if !self.range_queryable.is_empty() {
+            return CredentialAttrTagPolicyV2 {
+                taggable: self.taggable.iter().cloned().collect(),
+                range_queryable: self.range_queryable.iter().cloned().collect(),
+            }.serialize(serializer);
+        }
+
         let mut seq = serializer.serialize_seq(Some(self.taggable.len()))?;
         for ref element in &self.taggable {
             seq.serialize_element(&element)?;
@@ -36,7 +63,64 @@ impl Serialize for CredentialAttrTagPolicy {
 
 impl<'de> Deserialize<'de> for CredentialAttrTagPolicy {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de>, {
-        let attr_names = Vec::deserialize(deserializer)?;
-        Ok(CredentialAttrTagPolicy::from(attr_names))
+        match CredentialAttrTagPolicyRepr::deserialize(deserializer)? {
+            CredentialAttrTagPolicyRepr::Taggable(attr_names) => Ok(CredentialAttrTagPolicy::from(attr_names)),
+            CredentialAttrTagPolicyRepr::WithRangeQueryable(policy) => {
+                let mut catpol = CredentialAttrTagPolicy::from(policy.taggable);
+
+                for attr_name in policy.range_queryable {
+                    if !catpol.is_taggable(&attr_name) {
+                        return Err(de::Error::custom(format!("Range queryable attribute \"{}\" is not taggable", attr_name)));
+                    }
+                    catpol.range_queryable.insert(attr_name.replace(" ", "").to_lowercase());
+                }
+
+                Ok(catpol)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_works_for_taggable_attributes() {
+        let catpol: CredentialAttrTagPolicy = serde_json::from_str(r#"["name", "Age"]"#).unwrap();
+
+        assert!(catpol.is_taggable("name"));
+        assert!(catpol.is_taggable("age"));
+        assert!(!catpol.is_range_queryable("age"));
+    }
+
+    #[test]
+    fn deserialize_works_for_range_queryable_attributes() {
+        let catpol: CredentialAttrTagPolicy = serde_json::from_str(r#"{"taggable": ["name", "age"], "range_queryable": ["Age"]}"#).unwrap();
+
+        assert!(catpol.is_taggable("name"));
+        assert!(!catpol.is_range_queryable("name"));
+        assert!(catpol.is_range_queryable("age"));
+    }
+
+    #[test]
+    fn deserialize_fails_for_range_queryable_untaggable_attribute() {
+        serde_json::from_str::<CredentialAttrTagPolicy>(r#"{"taggable": ["name"], "range_queryable": ["age"]}"#).unwrap_err();
+    }
+
+    #[test]
+    fn serialize_keeps_array_form_without_range_queryable_attributes() {
+        let catpol = CredentialAttrTagPolicy::from(vec!["name".to_string()]);
+
+        assert_eq!(json!(["name"]), serde_json::to_value(&catpol).unwrap());
+    }
+
+    #[test]
+    fn serialize_deserialize_works_for_range_queryable_attributes() {
+        let catpol: CredentialAttrTagPolicy = serde_json::from_str(r#"{"taggable": ["age"], "range_queryable": ["age"]}"#).unwrap();
+
+        let catpol: CredentialAttrTagPolicy = serde_json::from_str(&serde_json::to_string(&catpol).unwrap()).unwrap();
+
+        assert!(catpol.is_range_queryable("age"));
     }
 }
@@ -18,5 +18,8 @@ impl Default for CredentialsForProofRequest {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RequestedCredential {
     pub cred_info: CredentialInfo,
-    pub interval: Option<NonRevocedInterval>
+    pub interval: Option<NonRevocedInterval>,
+    // all acceptable intervals when proof request lists several, `interval` holds the first of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub intervals: Vec<NonRevocedInterval>
 }
\ No newline at end of file
@@ -6,7 +6,9 @@ use ursa::cl::{
 };
 use super::super::crypto::did::DidValue;
 
-use super::credential_definition::CredentialDefinitionId;
+use super::credential::CredentialValues;
+use super::credential_definition::{CredentialDefinition, CredentialDefinitionId};
+use super::credential_offer::CredentialOffer;
 
 use utils::validation::Validatable;
 
@@ -26,6 +28,21 @@ pub struct CredentialRequestMetadata {
     pub master_secret_name: String
 }
 
+/// One credential of a batch issuance.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialIssuanceRequest {
+    pub cred_offer: CredentialOffer,
+    pub cred_req: CredentialRequest,
+    pub cred_values: CredentialValues,
+}
+
+/// One credential offer of a batch of credential requests.
+#[derive(Debug, Deserialize)]
+pub struct OfferedCredential {
+    pub cred_offer: CredentialOffer,
+    pub cred_def: CredentialDefinition,
+}
+
 impl Validatable for CredentialRequest {
     fn validate(&self) -> Result<(), String> {
         self.cred_def_id.validate()?;
@@ -34,4 +51,45 @@ impl Validatable for CredentialRequest {
     }
 }
 
-impl Validatable for CredentialRequestMetadata {}
\ No newline at end of file
+impl Validatable for CredentialRequestMetadata {}
+impl Validatable for CredentialIssuanceRequest {
+    fn validate(&self) -> Result<(), String> {
+        self.cred_offer.validate()?;
+        self.cred_req.validate()?;
+        Ok(())
+    }
+}
+
+impl Validatable for Vec<CredentialIssuanceRequest> {
+    fn validate(&self) -> Result<(), String> {
+        if self.is_empty() {
+            return Err(String::from("Empty list of credential issuance requests has been passed"));
+        }
+
+        for request in self {
+            request.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Validatable for OfferedCredential {
+    fn validate(&self) -> Result<(), String> {
+        self.cred_offer.validate()?;
+        self.cred_def.validate()?;
+        Ok(())
+    }
+}
+
+impl Validatable for Vec<OfferedCredential> {
+    fn validate(&self) -> Result<(), String> {
+        if self.is_empty() {
+            return Err(String::from("Empty list of credential offers has been passed"));
+        }
+
+        for offered_credential in self {
+            offered_credential.validate()?;
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use ursa::cl::Proof as CryptoProof;
 
 use super::schema::SchemaId;
+use super::proof_request::PredicateTypes;
 use super::credential_definition::CredentialDefinitionId;
 use super::revocation_registry_definition::RevocationRegistryId;
 use utils::validation::Validatable;
@@ -11,7 +12,10 @@ use utils::validation::Validatable;
 pub struct Proof {
     pub proof: CryptoProof,
     pub requested_proof: RequestedProof,
-    pub identifiers: Vec<Identifier>
+    pub identifiers: Vec<Identifier>,
+    // nonce of the proof request the proof was built for (decimal), absent in proofs of older provers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -54,4 +58,255 @@ pub struct Identifier {
     pub timestamp: Option<u64>
 }
 
-impl Validatable for Proof {}
\ No newline at end of file
+impl Proof {
+    pub fn required_identifiers(&self) -> RequiredIdentifiers {
+        RequiredIdentifiers::from_identifiers(&self.identifiers)
+    }
+
+    pub fn to_unqualified(self) -> Proof {
+        Proof {
+            identifiers: self.identifiers.into_iter().map(Identifier::to_unqualified).collect(),
+            ..self
+        }
+    }
+}
+
+impl Identifier {
+    pub fn to_unqualified(self) -> Identifier {
+        Identifier {
+            schema_id: self.schema_id.to_unqualified(),
+            cred_def_id: self.cred_def_id.to_unqualified(),
+            rev_reg_id: self.rev_reg_id.map(|rev_reg_id| rev_reg_id.to_unqualified()),
+            timestamp: self.timestamp
+        }
+    }
+}
+
+impl Validatable for Proof {}
+
+/// Ledger entities that must be fetched to verify a proof.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequiredIdentifiers {
+    pub schema_ids: HashSet<SchemaId>,
+    pub cred_def_ids: HashSet<CredentialDefinitionId>,
+    pub rev_regs: HashSet<RequiredRevocationRegistry>
+}
+
+/// Revocation registry state that must be fetched as of `timestamp`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RequiredRevocationRegistry {
+    pub rev_reg_id: RevocationRegistryId,
+    pub timestamp: u64
+}
+
+impl RequiredIdentifiers {
+    pub fn from_identifiers(identifiers: &[Identifier]) -> RequiredIdentifiers {
+        let mut required = RequiredIdentifiers::default();
+
+        for identifier in identifiers {
+            required.schema_ids.insert(identifier.schema_id.clone());
+            required.cred_def_ids.insert(identifier.cred_def_id.clone());
+            if let (&Some(ref rev_reg_id), Some(timestamp)) = (&identifier.rev_reg_id, identifier.timestamp) {
+                required.rev_regs.insert(RequiredRevocationRegistry { rev_reg_id: rev_reg_id.clone(), timestamp });
+            }
+        }
+
+        required
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DisclosurePreview {
+    pub revealed_attrs: HashMap<String, RevealedAttributePreview>,
+    pub unrevealed_attrs: HashMap<String, UnrevealedAttributePreview>,
+    pub self_attested_attrs: HashMap<String, String>,
+    pub predicates: HashMap<String, PredicatePreview>
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevealedAttributePreview {
+    pub cred_id: String,
+    pub name: String,
+    pub raw: String
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UnrevealedAttributePreview {
+    pub cred_id: String,
+    pub name: String
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PredicatePreview {
+    pub cred_id: String,
+    pub name: String,
+    pub p_type: PredicateTypes,
+    pub p_value: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value_max: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SatisfactionMode {
+    Revealed,
+    Unrevealed,
+    Predicate,
+    SelfAttested
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReferentSatisfaction {
+    pub mode: SatisfactionMode,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub non_revoked_at: Vec<u64>
+}
+
+/// Outcome of proof verification with the way each proof request referent was satisfied
+/// and revealed values normalized as the proof request asks.
+/// `referents` and `revealed_attrs` are filled only for a valid proof.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VerificationResult {
+    pub valid: bool,
+    pub referents: HashMap<String, ReferentSatisfaction>,
+    #[serde(default)]
+    pub revealed_attrs: HashMap<String, String>
+}
+
+/// Diagnostic outcome of proof verification for every requested attribute and predicate.
+/// `error` tells why a proof with all referents passed was rejected as a whole, e.g. nonce mismatch.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VerificationReport {
+    pub valid: bool,
+    pub referents: HashMap<String, ReferentVerification>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>
+}
+
+/// `mode` is set for every referent of a valid proof, the same as in `VerificationResult`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReferentVerification {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub reason: Option<VerificationFailure>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub details: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub mode: Option<SatisfactionMode>
+}
+
+impl ReferentVerification {
+    pub fn passed() -> ReferentVerification {
+        ReferentVerification { valid: true, reason: None, details: None, mode: None }
+    }
+
+    pub fn failed(reason: VerificationFailure, details: String) -> ReferentVerification {
+        ReferentVerification { valid: false, reason: Some(reason), details: Some(details), mode: None }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationFailure {
+    // referent is missing in proof
+    NotRevealed,
+    // credential doesn't match restrictions or self attested value is given for restricted attribute
+    RestrictionsUnsatisfied,
+    // proven predicate differs from requested one
+    PredicateUnsatisfied,
+    // credential isn't proven to be not revoked for requested interval
+    Revoked,
+    // revealed value or cryptographic proof doesn't match credential signature;
+    // a proof with a witness outdated by revocation can't be told apart from a forged one
+    SignatureInvalid
+}
+
+/// What a successful verification established, passed to the verification audit sink.
+/// Sets are ordered so the serialized record is stable and can be hashed or signed as is.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct VerificationAuditRecord {
+    // nonce of the verified proof request (decimal)
+    pub nonce: String,
+    // referents of revealed attributes
+    pub revealed_attrs: BTreeSet<String>,
+    // referents of satisfied predicates
+    pub predicates: BTreeSet<String>,
+    // DIDs of issuers of the credentials used in the proof
+    pub issuer_dids: BTreeSet<String>,
+    // verification time in seconds from UNIX Epoch
+    pub timestamp: u64
+}
+
+/// Upper bounds on proof size checked by verifier before any cryptographic verification.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProofLimits {
+    pub max_sub_proofs: usize,
+    pub max_revealed_attrs: usize,
+    pub max_predicates: usize
+}
+
+impl Default for ProofLimits {
+    fn default() -> Self {
+        ProofLimits {
+            max_sub_proofs: 256,
+            max_revealed_attrs: 4096,
+            max_predicates: 4096,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _identifier(name: &str, rev_reg_id: Option<&str>, timestamp: Option<u64>) -> Identifier {
+        Identifier {
+            schema_id: SchemaId(format!("NcYxiDXkpYi6ov5FcYDi1e:2:{}:1.0", name)),
+            cred_def_id: CredentialDefinitionId(format!("NcYxiDXkpYi6ov5FcYDi1e:3:CL:{}:TAG_1", name)),
+            rev_reg_id: rev_reg_id.map(|id| RevocationRegistryId(id.to_string())),
+            timestamp
+        }
+    }
+
+    #[test]
+    fn required_identifiers_works_for_two_credentials_one_revocable() {
+        let rev_reg_id = "NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:gvt:TAG_1:CL_ACCUM:TAG_1";
+
+        let identifiers = vec![
+            _identifier("gvt", Some(rev_reg_id), Some(100)),
+            _identifier("xyz", None, None),
+            _identifier("gvt", Some(rev_reg_id), Some(200)),
+        ];
+
+        let required = RequiredIdentifiers::from_identifiers(&identifiers);
+
+        assert_eq!(2, required.schema_ids.len());
+        assert!(required.schema_ids.contains(&SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string())));
+        assert!(required.schema_ids.contains(&SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:xyz:1.0".to_string())));
+
+        assert_eq!(2, required.cred_def_ids.len());
+        assert!(required.cred_def_ids.contains(&CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:gvt:TAG_1".to_string())));
+        assert!(required.cred_def_ids.contains(&CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:xyz:TAG_1".to_string())));
+
+        assert_eq!(2, required.rev_regs.len());
+        assert!(required.rev_regs.contains(&RequiredRevocationRegistry { rev_reg_id: RevocationRegistryId(rev_reg_id.to_string()), timestamp: 100 }));
+        assert!(required.rev_regs.contains(&RequiredRevocationRegistry { rev_reg_id: RevocationRegistryId(rev_reg_id.to_string()), timestamp: 200 }));
+    }
+
+    #[test]
+    fn required_identifiers_works_for_revocable_credential_without_timestamp() {
+        let rev_reg_id = "NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:gvt:TAG_1:CL_ACCUM:TAG_1";
+
+        let required = RequiredIdentifiers::from_identifiers(&[_identifier("gvt", Some(rev_reg_id), None)]);
+
+        assert!(required.rev_regs.is_empty());
+    }
+
+    #[test]
+    fn required_identifiers_works_for_empty_identifiers() {
+        assert_eq!(RequiredIdentifiers::default(), RequiredIdentifiers::from_identifiers(&[]));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use ursa::cl::Nonce;
 
@@ -6,13 +6,16 @@ use utils::validation::Validatable;
 
 use serde::{de, Deserialize, Deserializer, ser, Serialize, Serializer};
 use serde_json::Value;
+use unicode_normalization::UnicodeNormalization;
 use utils::wql::Query;
 
 use super::credential::Credential;
+use super::proof::RequestedProof;
 use super::super::crypto::did::DidValue;
-use super::credential_definition::CredentialDefinitionId;
+use super::credential_definition::{CredentialDefinition, CredentialDefinitionId, CredentialDefinitionV1};
 use super::revocation_registry_definition::RevocationRegistryId;
-use super::schema::SchemaId;
+use super::schema::{Schema, SchemaId, SchemaV1, SchemaVersionRange};
+use utils::canonical_json;
 use utils::qualifier;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -22,7 +25,57 @@ pub struct ProofRequestPayload {
     pub version: String,
     pub requested_attributes: HashMap<String, AttributeInfo>,
     pub requested_predicates: HashMap<String, PredicateInfo>,
-    pub non_revoked: Option<NonRevocedInterval>
+    pub non_revoked: Option<NonRevocedInterval>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_issuer: Option<Vec<Vec<String>>>,
+    // maximum age (in seconds at verification time) of accumulators used for non-revocation proofs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_accum_age_secs: Option<u64>,
+    // normalization steps the verifier applies to revealed values of requested attribute referents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<HashMap<String, Vec<Normalization>>>,
+    // named groups of requested attribute referents which are revealed together from the same credential or not revealed at all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_reveal: Option<HashMap<String, Vec<String>>>,
+    // public lists of values one of which requested attribute referents must reveal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_values: Option<HashMap<String, Vec<String>>>
+}
+
+impl ProofRequestPayload {
+    /// Checks that attribute referents restricted to public lists of values are revealed
+    /// from credentials with one of the listed raw values.
+    pub fn check_allowed_values(&self, requested_proof: &RequestedProof) -> Result<(), String> {
+        for (referent, allowed_values) in self.allowed_values.iter().flatten() {
+            let revealed_attr = requested_proof.revealed_attrs.get(referent)
+                .ok_or_else(|| format!("Attribute \"{}\" restricted to allowed values is not revealed", referent))?;
+
+            if !allowed_values.contains(&revealed_attr.raw) {
+                return Err(format!("Revealed value of attribute \"{}\" is not among allowed values", referent));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl Default for ProofRequestPayload {
+    fn default() -> ProofRequestPayload {
+        ProofRequestPayload {
+            nonce: Nonce::new().unwrap(),
+            name: "proof_req".to_string(),
+            version: "1.0".to_string(),
+            requested_attributes: HashMap::new(),
+            requested_predicates: HashMap::new(),
+            non_revoked: None,
+            same_issuer: None,
+            max_accum_age_secs: None,
+            normalize: None,
+            linked_reveal: None,
+            allowed_values: None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -51,6 +104,129 @@ impl ProofRequest {
             ProofRequest::ProofRequestV2(_) => ProofRequestsVersion::V2,
         }
     }
+
+    fn into_value(self) -> ProofRequestPayload {
+        match self {
+            ProofRequest::ProofRequestV1(proof_req) | ProofRequest::ProofRequestV2(proof_req) => proof_req,
+        }
+    }
+
+    /// Combines two proof requests for the same nonce into one asking for everything either of them asks for.
+    /// Referents of `other` colliding with referents of `self` are renamed to the first free `<referent>_2`, `<referent>_3`, ...
+    /// (in referent order, so the result is deterministic), and `same_issuer`, `normalize`, `linked_reveal` and `allowed_values`
+    /// of `other` follow the renaming. Attribute and predicate referents are renamed independently.
+    /// Colliding `linked_reveal` group names are renamed the same way.
+    /// Global `non_revoked` intervals are intersected and the smaller `max_accum_age_secs` is kept.
+    pub fn merge(self, other: ProofRequest) -> Result<ProofRequest, String> {
+        let version = self.version();
+
+        if version != other.version() {
+            return Err(String::from("Proof requests of different versions can't be merged"));
+        }
+
+        let mut value = self.into_value();
+        let other = other.into_value();
+
+        let nonce = value.nonce.to_dec().map_err(|err| format!("Invalid proof request nonce: {:?}", err))?;
+        if nonce != other.nonce.to_dec().map_err(|err| format!("Invalid proof request nonce: {:?}", err))? {
+            return Err(String::from("Proof requests with different nonces can't be merged"));
+        }
+
+        let other_attr_referents: HashSet<String> = other.requested_attributes.keys().cloned().collect();
+
+        // attribute and predicate referents are separate namespaces, so each one is renamed on its own
+        let renamed_attrs = _merge_referents(&mut value.requested_attributes, other.requested_attributes);
+        let renamed_predicates = _merge_referents(&mut value.requested_predicates, other.requested_predicates);
+
+        let rename_attr = |referent: String| renamed_attrs.get(&referent).cloned().unwrap_or(referent);
+        let rename_predicate = |referent: String| renamed_predicates.get(&referent).cloned().unwrap_or(referent);
+
+        // `same_issuer` referents are looked up among attributes first, as the verifier does
+        let rename_same_issuer = |referent: String|
+            if other_attr_referents.contains(&referent) { rename_attr(referent) } else { rename_predicate(referent) };
+
+        if let Some(other_groups) = other.same_issuer {
+            value.same_issuer.get_or_insert_with(Vec::new)
+                .extend(other_groups.into_iter().map(|group| group.into_iter().map(&rename_same_issuer).collect()));
+        }
+
+        if let Some(other_normalize) = other.normalize {
+            value.normalize.get_or_insert_with(HashMap::new)
+                .extend(other_normalize.into_iter().map(|(referent, steps)| (rename_attr(referent), steps)));
+        }
+
+        if let Some(other_allowed_values) = other.allowed_values {
+            value.allowed_values.get_or_insert_with(HashMap::new)
+                .extend(other_allowed_values.into_iter().map(|(referent, values)| (rename_attr(referent), values)));
+        }
+
+        if let Some(other_linked_reveal) = other.linked_reveal {
+            let other_linked_reveal = other_linked_reveal.into_iter()
+                .map(|(group, referents)| (group, referents.into_iter().map(&rename_attr).collect()))
+                .collect();
+            _merge_referents(value.linked_reveal.get_or_insert_with(HashMap::new), other_linked_reveal);
+        }
+
+        value.non_revoked = _intersect_intervals(value.non_revoked, other.non_revoked)?;
+
+        value.max_accum_age_secs = match (value.max_accum_age_secs, other.max_accum_age_secs) {
+            (Some(age), Some(other_age)) => Some(age.min(other_age)),
+            (age, other_age) => age.or(other_age)
+        };
+
+        Ok(match version {
+            ProofRequestsVersion::V1 => ProofRequest::ProofRequestV1(value),
+            ProofRequestsVersion::V2 => ProofRequest::ProofRequestV2(value),
+        })
+    }
+}
+
+// Moves `source` entries into `target` and returns the referents that had to be renamed
+fn _merge_referents<T>(target: &mut HashMap<String, T>, source: HashMap<String, T>) -> HashMap<String, String> {
+    let mut source: Vec<(String, T)> = source.into_iter().collect();
+    source.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let source_referents: Vec<String> = source.iter().map(|&(ref referent, _)| referent.clone()).collect();
+
+    let mut renamed = HashMap::new();
+
+    for (referent, info) in source {
+        if !target.contains_key(&referent) {
+            target.insert(referent, info);
+            continue;
+        }
+
+        let new_referent = (2..)
+            .map(|n| format!("{}_{}", referent, n))
+            .find(|candidate| !target.contains_key(candidate) && !source_referents.contains(candidate))
+            .unwrap();
+
+        target.insert(new_referent.clone(), info);
+        renamed.insert(referent, new_referent);
+    }
+
+    renamed
+}
+
+fn _intersect_intervals(interval: Option<NonRevocedInterval>, other: Option<NonRevocedInterval>) -> Result<Option<NonRevocedInterval>, String> {
+    match (interval, other) {
+        (Some(interval), Some(other)) => {
+            let from = interval.from.max(other.from);
+            let to = match (interval.to, other.to) {
+                (Some(to), Some(other_to)) => Some(to.min(other_to)),
+                (to, other_to) => to.or(other_to)
+            };
+
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    return Err(String::from("Non-revocation intervals of merged proof requests don't overlap"));
+                }
+            }
+
+            Ok(Some(NonRevocedInterval { from, to }))
+        }
+        (interval, other) => Ok(interval.or(other))
+    }
 }
 
 impl<'de> Deserialize<'de> for ProofRequest
@@ -116,11 +292,44 @@ pub struct NonRevocedInterval {
     pub to: Option<u64>
 }
 
+impl NonRevocedInterval {
+    pub fn contains(&self, timestamp: u64) -> bool {
+        self.from.map(|from| from <= timestamp).unwrap_or(true) &&
+            self.to.map(|to| timestamp <= to).unwrap_or(true)
+    }
+
+    /// Interval centered on a ledger accumulator `timestamp`, widened by `tolerance` seconds on both sides.
+    pub fn around(timestamp: u64, tolerance: u64) -> NonRevocedInterval {
+        NonRevocedInterval {
+            from: Some(timestamp.saturating_sub(tolerance)),
+            to: Some(timestamp.saturating_add(tolerance))
+        }
+    }
+}
+
+/// Non-revocation requirement of a requested attribute or predicate: a single interval
+/// or a list of intervals any of which the proof may satisfy.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum NonRevocedIntervals {
+    Any(Vec<NonRevocedInterval>),
+    Single(NonRevocedInterval)
+}
+
+impl NonRevocedIntervals {
+    pub fn intervals(&self) -> Vec<NonRevocedInterval> {
+        match *self {
+            NonRevocedIntervals::Any(ref intervals) => intervals.clone(),
+            NonRevocedIntervals::Single(ref interval) => vec![interval.clone()]
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 pub struct AttributeInfo {
     pub name: String,
     pub restrictions: Option<Query>,
-    pub non_revoked: Option<NonRevocedInterval>
+    pub non_revoked: Option<NonRevocedIntervals>
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
@@ -128,8 +337,77 @@ pub struct PredicateInfo {
     pub name: String,
     pub p_type: PredicateTypes,
     pub p_value: i32,
+    /// Exclusive upper bound turning the predicate into a range `p_value <(=) value < p_value_max`.
+    /// The lower bound `p_type` must be ">=" or ">".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value_max: Option<i32>,
+    /// Number of decimal places of the attribute value: credential stores the value in minor units
+    /// (e.g. cents for `scale` 2), while `p_value` is given in natural units.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>,
     pub restrictions: Option<Query>,
-    pub non_revoked: Option<NonRevocedInterval>
+    pub non_revoked: Option<NonRevocedIntervals>
+}
+
+impl PredicateInfo {
+    /// Bound to compare credential value with: `p_value * 10^scale`.
+    pub fn scaled_p_value(&self) -> Result<i32, String> {
+        self._scale(self.p_value)
+    }
+
+    /// Comparisons the credential value must satisfy: `p_type` with the scaled `p_value`
+    /// and, for a range predicate, `<` with the scaled `p_value_max`.
+    pub fn constraints(&self) -> Result<Vec<(PredicateTypes, i32)>, String> {
+        let mut constraints = vec![(self.p_type.clone(), self.scaled_p_value()?)];
+
+        if let Some(p_value_max) = self.p_value_max {
+            match self.p_type {
+                PredicateTypes::GE | PredicateTypes::GT => {}
+                PredicateTypes::LE | PredicateTypes::LT =>
+                    return Err(format!("Range predicate for \"{}\" must have \">=\" or \">\" lower bound, got {}", self.name, self.p_type))
+            }
+
+            if self.p_value >= p_value_max {
+                return Err(format!("Range predicate for \"{}\" must have min {} less than max {}", self.name, self.p_value, p_value_max));
+            }
+
+            constraints.push((PredicateTypes::LT, self._scale(p_value_max)?));
+        }
+
+        Ok(constraints)
+    }
+
+    fn _scale(&self, value: i32) -> Result<i32, String> {
+        match self.scale {
+            Some(scale) => 10i32.checked_pow(scale)
+                .and_then(|multiplier| value.checked_mul(multiplier))
+                .ok_or_else(|| format!("Predicate value {} scaled by 10^{} is out of 32-bit integer range", value, scale)),
+            None => Ok(value)
+        }
+    }
+}
+
+/// Declarative normalization of a revealed attribute value, applied in the listed order.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalization {
+    Lowercase,
+    Trim,
+    Nfc
+}
+
+impl Normalization {
+    pub fn apply(&self, value: &str) -> String {
+        match *self {
+            Normalization::Lowercase => value.to_lowercase(),
+            Normalization::Trim => value.trim().to_string(),
+            Normalization::Nfc => value.nfc().collect()
+        }
+    }
+
+    pub fn apply_all(steps: &[Normalization], value: &str) -> String {
+        steps.iter().fold(value.to_string(), |value, step| step.apply(&value))
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
@@ -184,15 +462,56 @@ impl Validatable for ProofRequest {
             if let Some(ref restrictions) = requested_attribute.restrictions {
                 _process_operator(&restrictions, &version)?;
             }
+            _check_non_revoked(&requested_attribute.non_revoked)?;
         }
 
         for (_, requested_predicate) in value.requested_predicates.iter() {
             if requested_predicate.name.is_empty() {
                 return Err(format!("Proof Request validation failed: there is empty requested attribute: {:?}", requested_predicate));
             }
+            requested_predicate.constraints()
+                .map_err(|err| format!("Proof Request validation failed: {}", err))?;
             if let Some(ref restrictions) = requested_predicate.restrictions {
                 _process_operator(&restrictions, &version)?;
             }
+            _check_non_revoked(&requested_predicate.non_revoked)?;
+        }
+
+        for group in value.same_issuer.iter().flatten() {
+            if group.len() < 2 {
+                return Err(format!("Proof Request validation failed: `same_issuer` group must contain at least two referents: {:?}", group));
+            }
+            for referent in group {
+                if !value.requested_attributes.contains_key(referent) && !value.requested_predicates.contains_key(referent) {
+                    return Err(format!("Proof Request validation failed: `same_issuer` referent \"{}\" is not requested", referent));
+                }
+            }
+        }
+
+        for (group, referents) in value.linked_reveal.iter().flatten() {
+            if referents.len() < 2 {
+                return Err(format!("Proof Request validation failed: `linked_reveal` group \"{}\" must contain at least two referents", group));
+            }
+            for referent in referents {
+                if !value.requested_attributes.contains_key(referent) {
+                    return Err(format!("Proof Request validation failed: `linked_reveal` referent \"{}\" is not a requested attribute", referent));
+                }
+            }
+        }
+
+        for referent in value.normalize.iter().flat_map(HashMap::keys) {
+            if !value.requested_attributes.contains_key(referent) {
+                return Err(format!("Proof Request validation failed: `normalize` referent \"{}\" is not a requested attribute", referent));
+            }
+        }
+
+        for (referent, allowed_values) in value.allowed_values.iter().flatten() {
+            if !value.requested_attributes.contains_key(referent) {
+                return Err(format!("Proof Request validation failed: `allowed_values` referent \"{}\" is not a requested attribute", referent));
+            }
+            if allowed_values.is_empty() {
+                return Err(format!("Proof Request validation failed: `allowed_values` of referent \"{}\" are empty", referent));
+            }
         }
 
         Ok(())
@@ -221,6 +540,103 @@ impl ProofRequest {
             }
         }
     }
+
+    /// Serializes proof request with object keys sorted recursively.
+    /// Must be used for any signing or hashing of proof request.
+    pub fn canonical_json(&self) -> serde_json::Result<String> {
+        canonical_json::to_string(self)
+    }
+
+    /// Canonical json of the proof request without its nonce,
+    /// so it is the same for every repetition of the same request.
+    pub fn canonical_json_without_nonce(&self) -> serde_json::Result<String> {
+        let mut value = serde_json::to_value(self)?;
+
+        if let Some(map) = value.as_object_mut() {
+            map.remove("nonce");
+        }
+
+        canonical_json::to_string_from_value(value)
+    }
+
+    /// Whether a credential issued with `cred_def` for `schema` could ever be used for this request:
+    /// every requested attribute and predicate must be in `schema` and its restrictions must not exclude
+    /// the schema, the credential definition or its issuer. Restrictions on attribute values
+    /// are only known after issuance, so they are assumed to pass.
+    pub fn could_be_satisfied_by_cred_def(&self, cred_def: &CredentialDefinition, schema: &Schema) -> bool {
+        let cred_def = match cred_def { CredentialDefinition::CredentialDefinitionV1(cred_def) => cred_def };
+        let schema = match schema { Schema::SchemaV1(schema) => schema };
+        let proof_request = self.value();
+
+        let could_be_satisfied = |name: &str, restrictions: &Option<Query>| {
+            schema.has_attribute(name) &&
+                restrictions.as_ref()
+                    .map(|restrictions| _match_cred_def(restrictions, cred_def, schema) != Some(false))
+                    .unwrap_or(true)
+        };
+
+        proof_request.requested_attributes.values()
+            .all(|attribute| could_be_satisfied(&attribute.name, &attribute.restrictions)) &&
+            proof_request.requested_predicates.values()
+                .all(|predicate| could_be_satisfied(&predicate.name, &predicate.restrictions))
+    }
+}
+
+// `None` if the result depends on values of credential
+fn _match_cred_def(query: &Query, cred_def: &CredentialDefinitionV1, schema: &SchemaV1) -> Option<bool> {
+    match query {
+        Query::Eq(ref tag_name, ref tag_value) => _match_cred_def_tag(tag_name, tag_value, cred_def, schema),
+        Query::Neq(ref tag_name, ref tag_value) => _match_cred_def_tag(tag_name, tag_value, cred_def, schema).map(|res| !res),
+        Query::In(ref tag_name, ref tag_values) => {
+            _any(tag_values.iter().map(|tag_value| _match_cred_def_tag(tag_name, tag_value, cred_def, schema)).collect())
+        }
+        Query::And(ref queries) => _all(queries.iter().map(|query| _match_cred_def(query, cred_def, schema)).collect()),
+        Query::Or(ref queries) => _any(queries.iter().map(|query| _match_cred_def(query, cred_def, schema)).collect()),
+        Query::Not(ref query) => _match_cred_def(query, cred_def, schema).map(|res| !res),
+        _ => None
+    }
+}
+
+fn _match_cred_def_tag(tag_name: &str, tag_value: &str, cred_def: &CredentialDefinitionV1, schema: &SchemaV1) -> Option<bool> {
+    let matches = |value: Option<String>| value.map(|value|
+        _convert_value_to_unqualified(tag_name, &value) == _convert_value_to_unqualified(tag_name, tag_value));
+
+    match tag_name {
+        "schema_id" => matches(Some(schema.id.0.clone())),
+        "schema_issuer_did" => matches(schema.id.parts().map(|(did, _, _)| did.0)),
+        "schema_name" => matches(Some(schema.name.clone())),
+        "schema_version" => matches(Some(schema.version.clone())),
+        SchemaVersionRange::TAG => SchemaVersionRange::parse(tag_value).ok().map(|range| range.contains(&schema.version)),
+        "cred_def_id" => matches(Some(cred_def.id.0.clone())),
+        "issuer_did" => matches(cred_def.id.issuer_did().map(|did| did.0)),
+        tag_name if tag_name.starts_with("attr::") && tag_name.ends_with("::marker") => {
+            Some(schema.has_attribute(&tag_name["attr::".len()..tag_name.len() - "::marker".len()]))
+        }
+        tag_name if tag_name.starts_with("attr::") && tag_name.ends_with("::value") => {
+            if schema.has_attribute(&tag_name["attr::".len()..tag_name.len() - "::value".len()]) { None } else { Some(false) }
+        }
+        _ => None
+    }
+}
+
+fn _all(results: Vec<Option<bool>>) -> Option<bool> {
+    if results.contains(&Some(false)) {
+        Some(false)
+    } else if results.iter().all(|res| *res == Some(true)) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn _any(results: Vec<Option<bool>>) -> Option<bool> {
+    if results.contains(&Some(true)) {
+        Some(true)
+    } else if results.iter().all(|res| *res == Some(false)) {
+        Some(false)
+    } else {
+        None
+    }
 }
 
 fn _convert_query_to_unqualified(query: &Query) -> Query {
@@ -266,6 +682,14 @@ fn _convert_value_to_unqualified(tag_name: &str, tag_value: &str) -> String {
     }
 }
 
+fn _check_non_revoked(non_revoked: &Option<NonRevocedIntervals>) -> Result<(), String> {
+    match *non_revoked {
+        Some(NonRevocedIntervals::Any(ref intervals)) if intervals.is_empty() =>
+            Err(String::from("Proof Request validation failed: `non_revoked` list of intervals is empty")),
+        _ => Ok(())
+    }
+}
+
 fn _process_operator(restriction_op: &Query, version: &ProofRequestsVersion) -> Result<(), String> {
     match restriction_op {
         Query::Eq(ref tag_name, ref tag_value) |
@@ -304,6 +728,10 @@ fn _check_restriction(tag_name: &str, tag_value: &str, version: &ProofRequestsVe
         return Err("Proof Request validation failed: fully qualified identifiers can not be used for Proof Request of the first version. \
                     Please, set \"ver\":\"2.0\" to use fully qualified identifiers.".to_string());
     }
+    if tag_name == SchemaVersionRange::TAG {
+        SchemaVersionRange::parse(tag_value)
+            .map_err(|err| format!("Proof Request validation failed: {}", err))?;
+    }
     Ok(())
 }
 
@@ -341,6 +769,8 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 0,
+                p_value_max: None,
+                scale: None,
                 restrictions: Some(Query::And(vec![
                     Query::Eq("schema_issuer_did".to_string(), DID_QUALIFIED.to_string()),
                     Query::Eq("rev_reg_id".to_string(), REV_REG_ID_QUALIFIED.to_string()),
@@ -349,12 +779,10 @@ mod tests {
             });
 
             let proof_request = ProofRequest::ProofRequestV2(ProofRequestPayload {
-                nonce: Nonce::new().unwrap(),
                 name: "proof_request_to_unqualified".to_string(),
-                version: "1.0".to_string(),
                 requested_attributes,
                 requested_predicates,
-                non_revoked: None,
+                ..Default::default()
             });
 
             let mut expected_requested_attributes: HashMap<String, AttributeInfo> = HashMap::new();
@@ -374,6 +802,8 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 0,
+                p_value_max: None,
+                scale: None,
                 restrictions: Some(Query::And(vec![
                     Query::Eq("schema_issuer_did".to_string(), DID_UNQUALIFIED.to_string()),
                     Query::Eq("rev_reg_id".to_string(), REV_REG_ID_UNQUALIFIED.to_string()),
@@ -387,4 +817,606 @@ mod tests {
             assert_eq!(ProofRequestsVersion::V2, proof_request.version());
         }
     }
-}
\ No newline at end of file
+
+    mod validate {
+        use super::*;
+
+        fn _proof_request(same_issuer: Option<Vec<Vec<String>>>) -> ProofRequest {
+            let mut requested_attributes: HashMap<String, AttributeInfo> = HashMap::new();
+            requested_attributes.insert("attr1_referent".to_string(), AttributeInfo { name: "name".to_string(), restrictions: None, non_revoked: None });
+            requested_attributes.insert("attr2_referent".to_string(), AttributeInfo { name: "status".to_string(), restrictions: None, non_revoked: None });
+
+            ProofRequest::ProofRequestV1(ProofRequestPayload {
+                name: "proof_request".to_string(),
+                requested_attributes,
+                same_issuer,
+                ..Default::default()
+            })
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_same_issuer() {
+            _proof_request(Some(vec![vec!["attr1_referent".to_string(), "attr2_referent".to_string()]])).validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_same_issuer_single_referent() {
+            _proof_request(Some(vec![vec!["attr1_referent".to_string()]])).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_same_issuer_unknown_referent() {
+            _proof_request(Some(vec![vec!["attr1_referent".to_string(), "attr3_referent".to_string()]])).validate().unwrap_err();
+        }
+
+        fn _proof_request_with_normalize(referent: &str) -> ProofRequest {
+            let mut proof_request = _proof_request(None);
+
+            if let ProofRequest::ProofRequestV1(ref mut payload) = proof_request {
+                let mut normalize: HashMap<String, Vec<Normalization>> = HashMap::new();
+                normalize.insert(referent.to_string(), vec![Normalization::Lowercase, Normalization::Trim]);
+                payload.normalize = Some(normalize);
+            }
+
+            proof_request
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_normalize() {
+            _proof_request_with_normalize("attr1_referent").validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_normalize_unknown_referent() {
+            _proof_request_with_normalize("attr3_referent").validate().unwrap_err();
+        }
+
+        fn _proof_request_with_allowed_values(referent: &str, values: &[&str]) -> ProofRequest {
+            let mut proof_request = _proof_request(None);
+
+            if let ProofRequest::ProofRequestV1(ref mut payload) = proof_request {
+                let mut allowed_values: HashMap<String, Vec<String>> = HashMap::new();
+                allowed_values.insert(referent.to_string(), values.iter().map(|value| value.to_string()).collect());
+                payload.allowed_values = Some(allowed_values);
+            }
+
+            proof_request
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_allowed_values() {
+            _proof_request_with_allowed_values("attr1_referent", &["10001", "10002"]).validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_allowed_values_unknown_referent() {
+            _proof_request_with_allowed_values("attr3_referent", &["10001"]).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_empty_allowed_values() {
+            _proof_request_with_allowed_values("attr1_referent", &[]).validate().unwrap_err();
+        }
+
+        fn _proof_request_with_linked_reveal(referents: &[&str]) -> ProofRequest {
+            let mut proof_request = _proof_request(None);
+
+            if let ProofRequest::ProofRequestV1(ref mut payload) = proof_request {
+                let mut linked_reveal: HashMap<String, Vec<String>> = HashMap::new();
+                linked_reveal.insert("identity".to_string(), referents.iter().map(|referent| referent.to_string()).collect());
+                payload.linked_reveal = Some(linked_reveal);
+            }
+
+            proof_request
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_linked_reveal() {
+            _proof_request_with_linked_reveal(&["attr1_referent", "attr2_referent"]).validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_linked_reveal_single_referent() {
+            _proof_request_with_linked_reveal(&["attr1_referent"]).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_linked_reveal_unknown_referent() {
+            _proof_request_with_linked_reveal(&["attr1_referent", "attr3_referent"]).validate().unwrap_err();
+        }
+
+        fn _proof_request_with_range(p_type: PredicateTypes, p_value: i32, p_value_max: i32) -> ProofRequest {
+            let mut proof_request = _proof_request(None);
+
+            if let ProofRequest::ProofRequestV1(ref mut payload) = proof_request {
+                payload.requested_predicates.insert("predicate1_referent".to_string(), PredicateInfo {
+                    name: "age".to_string(),
+                    p_type,
+                    p_value,
+                    p_value_max: Some(p_value_max),
+                    scale: None,
+                    restrictions: None,
+                    non_revoked: None,
+                });
+            }
+
+            proof_request
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_range_predicate() {
+            _proof_request_with_range(PredicateTypes::GE, 18, 65).validate().unwrap();
+            _proof_request_with_range(PredicateTypes::GT, 18, 65).validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_range_predicate_min_equal_to_max() {
+            let err = _proof_request_with_range(PredicateTypes::GE, 18, 18).validate().unwrap_err();
+            assert!(err.contains("min 18 less than max 18"));
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_range_predicate_min_greater_than_max() {
+            _proof_request_with_range(PredicateTypes::GE, 65, 18).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_range_predicate_with_upper_bound_type() {
+            _proof_request_with_range(PredicateTypes::LE, 18, 65).validate().unwrap_err();
+        }
+    }
+
+    mod serialization {
+        use super::*;
+
+        #[test]
+        fn proof_request_serialization_works_without_linked_reveal() {
+            let proof_request: ProofRequest = serde_json::from_value(json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {"attr1_referent": {"name": "name"}},
+                "requested_predicates": {}
+            })).unwrap();
+
+            assert!(proof_request.value().linked_reveal.is_none());
+            assert!(serde_json::to_value(&proof_request).unwrap().get("linked_reveal").is_none());
+        }
+
+        #[test]
+        fn proof_request_serialization_works_for_linked_reveal() {
+            let proof_request: ProofRequest = serde_json::from_value(json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {"attr1_referent": {"name": "name"}, "attr2_referent": {"name": "surname"}},
+                "requested_predicates": {},
+                "linked_reveal": {"identity": ["attr1_referent", "attr2_referent"]}
+            })).unwrap();
+
+            assert_eq!(json!({"identity": ["attr1_referent", "attr2_referent"]}),
+                       serde_json::to_value(&proof_request).unwrap()["linked_reveal"]);
+        }
+    }
+
+    mod canonical_json {
+        use super::*;
+
+        #[test]
+        fn proof_request_canonical_json_works_for_different_keys_order() {
+            let proof_req_1: ProofRequest = serde_json::from_str(r#"{
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name", "restrictions": {"schema_name": "gvt", "issuer_did": "NcYxiDXkpYi6ov5FcYDi1e"}},
+                    "attr2_referent": {"name": "sex", "non_revoked": {"from": 10, "to": 20}}
+                },
+                "requested_predicates": {
+                    "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18}
+                }
+            }"#).unwrap();
+
+            let proof_req_2: ProofRequest = serde_json::from_str(r#"{
+                "requested_predicates": {
+                    "predicate1_referent": {"p_value": 18, "p_type": ">=", "name": "age"}
+                },
+                "requested_attributes": {
+                    "attr2_referent": {"non_revoked": {"to": 20, "from": 10}, "name": "sex"},
+                    "attr1_referent": {"restrictions": {"issuer_did": "NcYxiDXkpYi6ov5FcYDi1e", "schema_name": "gvt"}, "name": "name"}
+                },
+                "version": "0.1",
+                "name": "proof_req_1",
+                "nonce": "123432421212"
+            }"#).unwrap();
+
+            let canonical_json = proof_req_1.canonical_json().unwrap();
+            assert_eq!(canonical_json, proof_req_2.canonical_json().unwrap());
+            assert!(canonical_json.find("\"name\"").unwrap() < canonical_json.find("\"nonce\"").unwrap());
+        }
+
+        #[test]
+        fn proof_request_canonical_json_without_nonce_works() {
+            let proof_req_json = |nonce: &str| json!({
+                "nonce": nonce,
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name"}
+                },
+                "requested_predicates": {}
+            });
+
+            let proof_req_1: ProofRequest = serde_json::from_value(proof_req_json("123432421212")).unwrap();
+            let proof_req_2: ProofRequest = serde_json::from_value(proof_req_json("987654321")).unwrap();
+
+            let canonical_json = proof_req_1.canonical_json_without_nonce().unwrap();
+            assert_eq!(canonical_json, proof_req_2.canonical_json_without_nonce().unwrap());
+            assert!(!canonical_json.contains("nonce"));
+            assert_ne!(proof_req_1.canonical_json().unwrap(), proof_req_2.canonical_json().unwrap());
+        }
+    }
+
+    mod normalization {
+        use super::*;
+
+        #[test]
+        fn normalization_works() {
+            assert_eq!("alex@x.com", Normalization::apply_all(&[Normalization::Lowercase, Normalization::Trim], "  Alex@X.COM "));
+            assert_eq!("  alex@x.com ", Normalization::apply_all(&[Normalization::Lowercase], "  Alex@X.COM "));
+            assert_eq!("Alex", Normalization::apply_all(&[], "Alex"));
+        }
+
+        #[test]
+        fn normalization_works_for_nfc() {
+            // "e" followed by combining acute accent composes into the single code point "é"
+            assert_eq!("Ren\u{e9}", Normalization::apply_all(&[Normalization::Nfc], "Rene\u{301}"));
+            assert_eq!("Ren\u{e9}", Normalization::apply_all(&[Normalization::Nfc], "Ren\u{e9}"));
+        }
+
+        #[test]
+        fn normalization_deserialize_works() {
+            let steps: Vec<Normalization> = serde_json::from_str(r#"["lowercase", "trim", "nfc"]"#).unwrap();
+            assert_eq!(vec![Normalization::Lowercase, Normalization::Trim, Normalization::Nfc], steps);
+
+            serde_json::from_str::<Vec<Normalization>>(r#"["uppercase"]"#).unwrap_err();
+        }
+    }
+
+    mod non_revoced_interval {
+        use super::*;
+
+        #[test]
+        fn non_revoced_interval_around_works() {
+            assert_eq!(NonRevocedInterval { from: Some(90), to: Some(110) }, NonRevocedInterval::around(100, 10));
+        }
+
+        #[test]
+        fn non_revoced_interval_around_works_for_timestamp_less_than_tolerance() {
+            assert_eq!(NonRevocedInterval { from: Some(0), to: Some(15) }, NonRevocedInterval::around(5, 10));
+        }
+    }
+
+    mod non_revoced_intervals {
+        use super::*;
+
+        #[test]
+        fn non_revoced_intervals_deserialize_works_for_single_interval() {
+            let info: AttributeInfo = serde_json::from_str(r#"{"name":"name","restrictions":null,"non_revoked":{"from":10,"to":20}}"#).unwrap();
+            assert_eq!(Some(NonRevocedIntervals::Single(NonRevocedInterval { from: Some(10), to: Some(20) })), info.non_revoked);
+        }
+
+        #[test]
+        fn non_revoced_intervals_deserialize_works_for_list() {
+            let info: AttributeInfo = serde_json::from_str(r#"{"name":"name","restrictions":null,"non_revoked":[{"from":10,"to":20},{"from":30,"to":40}]}"#).unwrap();
+            assert_eq!(vec![NonRevocedInterval { from: Some(10), to: Some(20) }, NonRevocedInterval { from: Some(30), to: Some(40) }],
+                       info.non_revoked.unwrap().intervals());
+        }
+
+        #[test]
+        fn non_revoced_intervals_serialize_works_for_single_interval() {
+            let non_revoked = NonRevocedIntervals::Single(NonRevocedInterval { from: None, to: Some(20) });
+            assert_eq!(json!({"from": null, "to": 20}), serde_json::to_value(&non_revoked).unwrap());
+        }
+
+        #[test]
+        fn non_revoced_interval_contains_works() {
+            let interval = NonRevocedInterval { from: Some(10), to: Some(20) };
+            assert!(interval.contains(10));
+            assert!(interval.contains(20));
+            assert!(!interval.contains(21));
+            assert!(NonRevocedInterval { from: None, to: None }.contains(0));
+        }
+    }
+
+    mod merge {
+        use super::*;
+
+        fn _attr(name: &str) -> AttributeInfo {
+            AttributeInfo { name: name.to_string(), restrictions: None, non_revoked: None }
+        }
+
+        fn _proof_request(nonce: &str, attrs: Vec<(&str, &str)>, non_revoked: Option<NonRevocedInterval>) -> ProofRequest {
+            ProofRequest::ProofRequestV1(ProofRequestPayload {
+                nonce: Nonce::from_dec(nonce).unwrap(),
+                name: "proof_request".to_string(),
+                requested_attributes: attrs.into_iter().map(|(referent, name)| (referent.to_string(), _attr(name))).collect(),
+                non_revoked,
+                ..Default::default()
+            })
+        }
+
+        #[test]
+        fn merge_works_for_distinct_referents() {
+            let merged = _proof_request("123", vec![("attr1_referent", "name")], None)
+                .merge(_proof_request("123", vec![("attr2_referent", "age")], None)).unwrap();
+
+            let attrs = &merged.value().requested_attributes;
+            assert_eq!(2, attrs.len());
+            assert_eq!("name", attrs["attr1_referent"].name);
+            assert_eq!("age", attrs["attr2_referent"].name);
+        }
+
+        #[test]
+        fn merge_works_for_colliding_referents() {
+            let mut other = _proof_request("123", vec![("attr1_referent", "age"), ("attr1_referent_2", "height")], None);
+            if let ProofRequest::ProofRequestV1(ref mut value) = other {
+                value.same_issuer = Some(vec![vec!["attr1_referent".to_string(), "attr1_referent_2".to_string()]]);
+            }
+
+            let merged = _proof_request("123", vec![("attr1_referent", "name")], None).merge(other).unwrap();
+
+            let value = merged.value();
+            assert_eq!("name", value.requested_attributes["attr1_referent"].name);
+            assert_eq!("height", value.requested_attributes["attr1_referent_2"].name);
+            assert_eq!("age", value.requested_attributes["attr1_referent_3"].name);
+            assert_eq!(Some(vec![vec!["attr1_referent_3".to_string(), "attr1_referent_2".to_string()]]), value.same_issuer);
+        }
+
+        #[test]
+        fn merge_works_for_same_referent_of_attribute_and_predicate() {
+            let predicate = PredicateInfo {
+                name: "age".to_string(),
+                p_type: PredicateTypes::GE,
+                p_value: 18,
+                p_value_max: None,
+                scale: None,
+                restrictions: None,
+                non_revoked: None,
+            };
+
+            let mut proof_request = _proof_request("123", vec![("referent", "name")], None);
+            if let ProofRequest::ProofRequestV1(ref mut value) = proof_request {
+                value.requested_predicates.insert("referent".to_string(), predicate.clone());
+            }
+
+            let mut other = _proof_request("123", vec![("referent", "surname"), ("other_referent", "height")], None);
+            if let ProofRequest::ProofRequestV1(ref mut value) = other {
+                value.requested_predicates.insert("referent".to_string(), predicate.clone());
+                value.requested_predicates.insert("referent_2".to_string(), predicate);
+                value.same_issuer = Some(vec![vec!["referent".to_string(), "other_referent".to_string()]]);
+                value.allowed_values = Some(vec![("referent".to_string(), vec!["Doe".to_string()])].into_iter().collect());
+            }
+
+            let merged = proof_request.merge(other).unwrap();
+
+            let value = merged.value();
+            assert_eq!("surname", value.requested_attributes["referent_2"].name);
+            assert!(value.requested_predicates.contains_key("referent_3"));
+            assert_eq!(Some(vec![vec!["referent_2".to_string(), "other_referent".to_string()]]), value.same_issuer);
+            assert!(value.allowed_values.as_ref().unwrap().contains_key("referent_2"));
+        }
+
+        #[test]
+        fn merge_works_for_linked_reveal() {
+            let linked_reveal = |referents: Vec<&str>| {
+                let mut linked_reveal: HashMap<String, Vec<String>> = HashMap::new();
+                linked_reveal.insert("identity".to_string(), referents.into_iter().map(String::from).collect());
+                Some(linked_reveal)
+            };
+
+            let mut proof_request = _proof_request("123", vec![("attr1_referent", "name"), ("attr2_referent", "surname")], None);
+            if let ProofRequest::ProofRequestV1(ref mut value) = proof_request {
+                value.linked_reveal = linked_reveal(vec!["attr1_referent", "attr2_referent"]);
+            }
+
+            let mut other = _proof_request("123", vec![("attr1_referent", "age"), ("attr3_referent", "height")], None);
+            if let ProofRequest::ProofRequestV1(ref mut value) = other {
+                value.linked_reveal = linked_reveal(vec!["attr1_referent", "attr3_referent"]);
+            }
+
+            let merged = proof_request.merge(other).unwrap();
+
+            let linked_reveal = merged.value().linked_reveal.as_ref().unwrap();
+            assert_eq!(2, linked_reveal.len());
+            assert_eq!(vec!["attr1_referent".to_string(), "attr2_referent".to_string()], linked_reveal["identity"]);
+            assert_eq!(vec!["attr1_referent_2".to_string(), "attr3_referent".to_string()], linked_reveal["identity_2"]);
+        }
+
+        #[test]
+        fn merge_works_for_non_revoked_intervals() {
+            let merged = _proof_request("123", vec![("attr1_referent", "name")], Some(NonRevocedInterval { from: Some(10), to: Some(100) }))
+                .merge(_proof_request("123", vec![("attr2_referent", "age")], Some(NonRevocedInterval { from: Some(50), to: None }))).unwrap();
+
+            assert_eq!(Some(NonRevocedInterval { from: Some(50), to: Some(100) }), merged.value().non_revoked);
+        }
+
+        #[test]
+        fn merge_fails_for_disjoint_non_revoked_intervals() {
+            _proof_request("123", vec![("attr1_referent", "name")], Some(NonRevocedInterval { from: Some(10), to: Some(20) }))
+                .merge(_proof_request("123", vec![("attr2_referent", "age")], Some(NonRevocedInterval { from: Some(30), to: None })))
+                .unwrap_err();
+        }
+
+        #[test]
+        fn merge_fails_for_different_nonces() {
+            _proof_request("123", vec![("attr1_referent", "name")], None)
+                .merge(_proof_request("456", vec![("attr2_referent", "age")], None))
+                .unwrap_err();
+        }
+    }
+
+    mod check_allowed_values {
+        use super::*;
+        use super::super::super::proof::RevealedAttributeInfo;
+
+        fn _proof_request() -> ProofRequestPayload {
+            let mut allowed_values: HashMap<String, Vec<String>> = HashMap::new();
+            allowed_values.insert("attr1_referent".to_string(), vec!["10001".to_string(), "10002".to_string()]);
+
+            ProofRequestPayload {
+                allowed_values: Some(allowed_values),
+                ..Default::default()
+            }
+        }
+
+        fn _requested_proof(revealed_value: Option<&str>) -> RequestedProof {
+            let mut requested_proof = RequestedProof::default();
+            if let Some(value) = revealed_value {
+                requested_proof.revealed_attrs.insert("attr1_referent".to_string(),
+                                                      RevealedAttributeInfo { sub_proof_index: 0, raw: value.to_string(), encoded: value.to_string() });
+            }
+            requested_proof
+        }
+
+        #[test]
+        fn check_allowed_values_works() {
+            _proof_request().check_allowed_values(&_requested_proof(Some("10002"))).unwrap();
+        }
+
+        #[test]
+        fn check_allowed_values_fails_for_value_out_of_list() {
+            _proof_request().check_allowed_values(&_requested_proof(Some("20001"))).unwrap_err();
+        }
+
+        #[test]
+        fn check_allowed_values_fails_for_not_revealed_attribute() {
+            _proof_request().check_allowed_values(&_requested_proof(None)).unwrap_err();
+        }
+    }
+
+    mod could_be_satisfied_by_cred_def {
+        use super::*;
+        use super::super::super::credential_definition::{CredentialDefinitionData, SignatureType};
+        use super::super::super::schema::AttributeNames;
+        use ursa::cl::issuer::Issuer as CryptoIssuer;
+
+        const ISSUER_DID: &str = "NcYxiDXkpYi6ov5FcYDi1e";
+        const SCHEMA_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0";
+        const CRED_DEF_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag";
+
+        fn _schema() -> Schema {
+            let mut attr_names = AttributeNames::new();
+            attr_names.insert("name".to_string());
+            attr_names.insert("age".to_string());
+
+            Schema::SchemaV1(SchemaV1 {
+                id: SchemaId(SCHEMA_ID.to_string()),
+                name: "gvt".to_string(),
+                version: "1.0".to_string(),
+                attr_names,
+                seq_no: None,
+                attribute_types: None,
+            })
+        }
+
+        fn _cred_def() -> CredentialDefinition {
+            let mut credential_schema_builder = CryptoIssuer::new_credential_schema_builder().unwrap();
+            credential_schema_builder.add_attr("name").unwrap();
+            credential_schema_builder.add_attr("age").unwrap();
+            let credential_schema = credential_schema_builder.finalize().unwrap();
+
+            let mut non_credential_schema_builder = CryptoIssuer::new_non_credential_schema_builder().unwrap();
+            non_credential_schema_builder.add_attr("master_secret").unwrap();
+            let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+            let (credential_public_key, _, _) =
+                CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+            CredentialDefinition::CredentialDefinitionV1(CredentialDefinitionV1 {
+                id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+                schema_id: SchemaId(SCHEMA_ID.to_string()),
+                signature_type: SignatureType::CL,
+                tag: "tag".to_string(),
+                value: CredentialDefinitionData {
+                    primary: credential_public_key.get_primary_key().unwrap().try_clone().unwrap(),
+                    revocation: None,
+                },
+            })
+        }
+
+        fn _proof_request(attr: &str, attr_restrictions: Option<Query>, predicate: &str) -> ProofRequest {
+            let mut requested_attributes = HashMap::new();
+            requested_attributes.insert("attr1_referent".to_string(), AttributeInfo {
+                name: attr.to_string(),
+                restrictions: attr_restrictions,
+                non_revoked: None,
+            });
+
+            let mut requested_predicates = HashMap::new();
+            requested_predicates.insert("predicate1_referent".to_string(), PredicateInfo {
+                name: predicate.to_string(),
+                p_type: PredicateTypes::GE,
+                p_value: 18,
+                p_value_max: None,
+                scale: None,
+                restrictions: Some(Query::Eq("issuer_did".to_string(), ISSUER_DID.to_string())),
+                non_revoked: None,
+            });
+
+            ProofRequest::ProofRequestV2(ProofRequestPayload {
+                name: "proof_request".to_string(),
+                requested_attributes,
+                requested_predicates,
+                ..Default::default()
+            })
+        }
+
+        #[test]
+        fn could_be_satisfied_by_cred_def_works() {
+            let restrictions = Query::And(vec![
+                Query::Eq("cred_def_id".to_string(), CRED_DEF_ID.to_string()),
+                Query::Eq("schema_version_range".to_string(), ">=1.0,<2.0".to_string()),
+                Query::Eq("attr::name::value".to_string(), "Alex".to_string()),
+            ]);
+
+            assert!(_proof_request("Name", Some(restrictions), "age").could_be_satisfied_by_cred_def(&_cred_def(), &_schema()));
+        }
+
+        #[test]
+        fn could_be_satisfied_by_cred_def_works_for_qualified_restrictions() {
+            let restrictions = Query::Eq("schema_id".to_string(), "schema:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string());
+
+            assert!(_proof_request("name", Some(restrictions), "age").could_be_satisfied_by_cred_def(&_cred_def(), &_schema()));
+        }
+
+        #[test]
+        fn could_be_satisfied_by_cred_def_works_for_predicate_attribute_not_in_schema() {
+            assert!(!_proof_request("name", None, "height").could_be_satisfied_by_cred_def(&_cred_def(), &_schema()));
+        }
+
+        #[test]
+        fn could_be_satisfied_by_cred_def_works_for_other_issuer() {
+            let restrictions = Query::In("issuer_did".to_string(), vec!["CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string()]);
+
+            assert!(!_proof_request("name", Some(restrictions), "age").could_be_satisfied_by_cred_def(&_cred_def(), &_schema()));
+        }
+
+        #[test]
+        fn could_be_satisfied_by_cred_def_works_for_excluded_schema() {
+            let restrictions = Query::Not(Box::new(Query::Eq("schema_name".to_string(), "gvt".to_string())));
+
+            assert!(!_proof_request("name", Some(restrictions), "age").could_be_satisfied_by_cred_def(&_cred_def(), &_schema()));
+        }
+
+        #[test]
+        fn could_be_satisfied_by_cred_def_works_for_value_of_attribute_not_in_schema() {
+            let restrictions = Query::Or(vec![
+                Query::Eq("attr::height::value".to_string(), "180".to_string()),
+                Query::Eq("schema_version".to_string(), "2.0".to_string()),
+            ]);
+
+            assert!(!_proof_request("name", Some(restrictions), "age").could_be_satisfied_by_cred_def(&_cred_def(), &_schema()));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use utils::validation::Validatable;
 
@@ -22,6 +22,32 @@ pub struct ProvingCredentialKey {
     pub timestamp: Option<u64>
 }
 
+impl RequestedCredentials {
+    /// Ids of all wallet credentials the requested credentials refer to.
+    pub fn cred_ids(&self) -> HashSet<String> {
+        self.requested_attributes.values().map(|requested_attr| requested_attr.cred_id.clone())
+            .chain(self.requested_predicates.values().map(|requested_predicate| requested_predicate.cred_id.clone()))
+            .collect()
+    }
+
+    /// Copy with all timestamps dropped: they refer to revocation states of one particular proof.
+    pub fn without_timestamps(&self) -> RequestedCredentials {
+        RequestedCredentials {
+            self_attested_attributes: self.self_attested_attributes.clone(),
+            requested_attributes: self.requested_attributes.iter()
+                .map(|(referent, requested_attr)| (referent.clone(), RequestedAttribute {
+                    cred_id: requested_attr.cred_id.clone(),
+                    timestamp: None,
+                    revealed: requested_attr.revealed,
+                }))
+                .collect(),
+            requested_predicates: self.requested_predicates.iter()
+                .map(|(referent, key)| (referent.clone(), ProvingCredentialKey { cred_id: key.cred_id.clone(), timestamp: None }))
+                .collect(),
+        }
+    }
+}
+
 impl Validatable for RequestedCredentials {
     fn validate(&self) -> Result<(), String> {
         if self.self_attested_attributes.is_empty() && self.requested_attributes.is_empty() && self.requested_predicates.is_empty() {
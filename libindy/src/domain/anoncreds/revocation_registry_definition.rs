@@ -1,8 +1,10 @@
 use ursa::cl::{RevocationKeyPublic, RevocationKeyPrivate};
 
 use super::DELIMITER;
-use super::credential_definition::CredentialDefinitionId;
+use super::credential_definition::{CredentialDefinitionId, CredentialDefinitionV1};
+use super::revocation_registry::RevocationRegistry;
 use super::super::crypto::did::DidValue;
+use super::super::wallet::KeyDerivationMethod;
 
 use std::collections::{HashMap, HashSet};
 use named_type::NamedType;
@@ -19,6 +21,16 @@ lazy_static! {
     static ref QUALIFIED_REV_REG_ID: Regex = Regex::new("(^revreg:(?P<method>[a-z0-9]+):)?(?P<did>.+):4:(?P<cred_def_id>.+):(?P<rev_reg_type>.+):(?P<tag>.+)$").unwrap();
 }
 
+// Largest registry capacity whose `2 * max_cred_num + 1` tails count still fits into u32
+pub const MAX_CRED_NUM: u32 = (::std::u32::MAX - 1) / 2;
+
+// Number of tails backing a registry of `max_cred_num` capacity
+pub fn tails_count(max_cred_num: u32) -> Result<u32, String> {
+    max_cred_num.checked_mul(2)
+        .and_then(|count| count.checked_add(1))
+        .ok_or_else(|| format!("max_cred_num too large: {}, supported maximum is {}", max_cred_num, MAX_CRED_NUM))
+}
+
 #[derive(Deserialize, Debug, Serialize)]
 pub struct RevocationRegistryConfig {
     pub issuance_type: Option<IssuanceType>,
@@ -78,6 +90,23 @@ pub struct RevocationRegistryDefinitionV1 {
     pub value: RevocationRegistryDefinitionValue
 }
 
+impl RevocationRegistryDefinitionV1 {
+    /// Checks that the registry was created for this credential definition and that
+    /// the credential definition carries revocation keys, reporting the first mismatch found.
+    pub fn validate_cred_def(&self, cred_def: &CredentialDefinitionV1) -> Result<(), String> {
+        if self.cred_def_id.to_unqualified() != cred_def.id.to_unqualified() {
+            return Err(format!("Revocation Registry Definition {} references Credential Definition {}, not {}",
+                               self.id.0, self.cred_def_id.0, cred_def.id.0));
+        }
+
+        if cred_def.value.revocation.is_none() {
+            return Err(format!("Credential Definition {} does not support revocation", cred_def.id.0));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, NamedType)]
 #[serde(tag = "ver")]
 pub enum RevocationRegistryDefinition {
@@ -111,7 +140,91 @@ pub struct RevocationRegistryDefinitionPrivate {
 pub struct RevocationRegistryInfo {
     pub id: RevocationRegistryId,
     pub curr_id: u32,
-    pub used_ids: HashSet<u32>
+    pub used_ids: HashSet<u32>,
+    #[serde(default)]
+    pub state: RegistryState
+}
+
+impl RevocationRegistryInfo {
+    pub fn is_full(&self, max_cred_num: u32) -> bool {
+        self.curr_id >= max_cred_num
+    }
+
+    pub fn capacity_remaining(&self, max_cred_num: u32) -> u32 {
+        max_cred_num.saturating_sub(self.curr_id)
+    }
+
+    // `threshold` is the share of assigned indices (0.0..=1.0) from which the registry counts as nearly full.
+    pub fn is_nearly_full(&self, max_cred_num: u32, threshold: f32) -> bool {
+        self.is_full(max_cred_num) || self.curr_id as f32 / max_cred_num as f32 >= threshold
+    }
+
+    // Indices are assigned sequentially, so every index up to `curr_id` was assigned once,
+    // whether it is still valid or revoked since.
+    pub fn unused_indices(&self, max_cred_num: u32) -> Vec<u32> {
+        (self.curr_id + 1..=max_cred_num).collect()
+    }
+}
+
+/// Issuer side overview of a revocation registry: lifecycle state and share of assigned indices.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RevocationRegistryStatus {
+    pub rev_reg_id: RevocationRegistryId,
+    pub state: RegistryState,
+    pub issued: u32,
+    pub max_cred_num: u32,
+    pub capacity_remaining: u32,
+    pub utilization: f64
+}
+
+impl RevocationRegistryStatus {
+    pub fn new(rev_reg_info: &RevocationRegistryInfo, max_cred_num: u32) -> RevocationRegistryStatus {
+        RevocationRegistryStatus {
+            rev_reg_id: rev_reg_info.id.clone(),
+            state: rev_reg_info.state,
+            issued: rev_reg_info.curr_id,
+            max_cred_num,
+            capacity_remaining: rev_reg_info.capacity_remaining(max_cred_num),
+            utilization: if max_cred_num == 0 { 0.0 } else { f64::from(rev_reg_info.curr_id) / f64::from(max_cred_num) }
+        }
+    }
+}
+
+/// Public and private state of an issuer's revocation registry exported as one piece for backup.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevocationRegistrySnapshot {
+    pub rev_reg_def: RevocationRegistryDefinition,
+    pub rev_reg_def_priv: RevocationRegistryDefinitionPrivate,
+    pub rev_reg: RevocationRegistry,
+    pub rev_reg_info: RevocationRegistryInfo,
+}
+
+/// Key of revocation registry snapshot encryption, derived the same way as the key of wallet export.
+/// On import `key_derivation_method` is taken from the snapshot.
+#[derive(Debug, Deserialize)]
+pub struct RevocationRegistryExportConfig {
+    pub key: String,
+    #[serde(default = "default_key_derivation_method")]
+    pub key_derivation_method: KeyDerivationMethod
+}
+
+fn default_key_derivation_method() -> KeyDerivationMethod {
+    KeyDerivationMethod::ARGON2I_MOD
+}
+
+/// Issuer side lifecycle of revocation registry: Active -> Full -> Rotated.
+/// Credentials can be issued only into `Active` registry.
+#[derive(Deserialize, Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum RegistryState {
+    Active,
+    Full,
+    Rotated
+}
+
+impl Default for RegistryState {
+    fn default() -> Self {
+        RegistryState::Active
+    }
 }
 
 qualifiable_type!(RevocationRegistryId);
@@ -127,6 +240,28 @@ impl RevocationRegistryId {
         }
     }
 
+    /// Builds canonical id of `CL_ACCUM` registry. Id is fully qualified if `did` is,
+    /// in that case credential definition id must be fully qualified as well.
+    pub fn build(did: &DidValue, cred_def_id: &CredentialDefinitionId, tag: &str) -> Result<RevocationRegistryId, String> {
+        cred_def_id.validate()?;
+
+        if tag.is_empty() || tag.contains(DELIMITER) {
+            return Err(format!("Revocation Registry tag must be non empty and must not contain {:?}: {:?}", DELIMITER, tag));
+        }
+
+        let cred_def_id = match did.get_method() {
+            Some(_) if !cred_def_id.is_fully_qualified() =>
+                return Err(format!("Credential Definition Id must be fully qualified for DID {:?}: {:?}", did.0, cred_def_id.0)),
+            Some(_) => cred_def_id.clone(),
+            None => cred_def_id.to_unqualified()
+        };
+
+        let id = RevocationRegistryId::new(did, &cred_def_id, CL_ACCUM, tag);
+        id.validate()?;
+
+        Ok(id)
+    }
+
     pub fn parts(&self) -> Option<(DidValue, CredentialDefinitionId, String, String)> {
         match QUALIFIED_REV_REG_ID.captures(&self.0) {
             Some(caps) => {
@@ -150,6 +285,7 @@ impl Validatable for RevocationRegistryConfig {
             if num_ == 0 {
                 return Err(String::from("RevocationRegistryConfig validation failed: `max_cred_num` must be greater than 0"));
             }
+            tails_count(num_).map_err(|err| format!("RevocationRegistryConfig validation failed: {}", err))?;
         }
         Ok(())
     }
@@ -241,6 +377,41 @@ mod tests {
         }
     }
 
+    mod build {
+        use super::*;
+
+        #[test]
+        fn test_build_rev_reg_id_as_unqualified() {
+            assert_eq!(_rev_reg_id_unqualified(), RevocationRegistryId::build(&_did(), &_cred_def_id_unqualified(), &_tag()).unwrap());
+        }
+
+        #[test]
+        fn test_build_rev_reg_id_as_qualified() {
+            assert_eq!(_rev_reg_id_qualified(), RevocationRegistryId::build(&_did_qualified(), &_cred_def_id_qualified(), &_tag()).unwrap());
+        }
+
+        #[test]
+        fn test_build_rev_reg_id_for_unqualified_did_and_qualified_cred_def_id() {
+            assert_eq!(_rev_reg_id_unqualified(), RevocationRegistryId::build(&_did(), &_cred_def_id_qualified(), &_tag()).unwrap());
+        }
+
+        #[test]
+        fn test_build_rev_reg_id_for_qualified_did_and_unqualified_cred_def_id() {
+            RevocationRegistryId::build(&_did_qualified(), &_cred_def_id_unqualified(), &_tag()).unwrap_err();
+        }
+
+        #[test]
+        fn test_build_rev_reg_id_for_invalid_cred_def_id() {
+            RevocationRegistryId::build(&_did(), &CredentialDefinitionId("gvt".to_string()), &_tag()).unwrap_err();
+        }
+
+        #[test]
+        fn test_build_rev_reg_id_for_invalid_tag() {
+            RevocationRegistryId::build(&_did(), &_cred_def_id_unqualified(), "").unwrap_err();
+            RevocationRegistryId::build(&_did(), &_cred_def_id_unqualified(), "TAG:1").unwrap_err();
+        }
+    }
+
     mod validate {
         use super::*;
 
@@ -253,5 +424,141 @@ mod tests {
         fn test_validate_rev_reg_id_as_fully_qualified() {
             _rev_reg_id_qualified().validate().unwrap();
         }
+
+        #[test]
+        fn test_validate_config_for_max_cred_num_near_overflow() {
+            RevocationRegistryConfig { issuance_type: None, max_cred_num: Some(MAX_CRED_NUM) }.validate().unwrap();
+            RevocationRegistryConfig { issuance_type: None, max_cred_num: Some(MAX_CRED_NUM + 1) }.validate().unwrap_err();
+        }
+    }
+
+    mod tails_count {
+        use super::*;
+
+        #[test]
+        fn tails_count_works() {
+            assert_eq!(11, tails_count(5).unwrap());
+            assert_eq!(::std::u32::MAX, tails_count(MAX_CRED_NUM).unwrap());
+        }
+
+        #[test]
+        fn tails_count_works_for_overflow() {
+            tails_count(MAX_CRED_NUM + 1).unwrap_err();
+            tails_count(::std::u32::MAX).unwrap_err();
+        }
+    }
+
+    mod capacity {
+        use super::*;
+
+        fn _rev_reg_info(curr_id: u32) -> RevocationRegistryInfo {
+            RevocationRegistryInfo {
+                id: _rev_reg_id_unqualified(),
+                curr_id,
+                used_ids: HashSet::new(),
+                state: RegistryState::Active,
+            }
+        }
+
+        #[test]
+        fn capacity_remaining_works() {
+            assert_eq!(10, _rev_reg_info(0).capacity_remaining(10));
+            assert_eq!(3, _rev_reg_info(7).capacity_remaining(10));
+            assert_eq!(0, _rev_reg_info(10).capacity_remaining(10));
+        }
+
+        #[test]
+        fn is_nearly_full_works() {
+            assert!(!_rev_reg_info(7).is_nearly_full(10, 0.8));
+            assert!(_rev_reg_info(8).is_nearly_full(10, 0.8));
+            assert!(_rev_reg_info(10).is_nearly_full(10, 1.0));
+            assert!(!_rev_reg_info(0).is_nearly_full(10, 0.0001));
+        }
+
+        #[test]
+        fn status_works() {
+            let status = RevocationRegistryStatus::new(&_rev_reg_info(4), 5);
+
+            assert_eq!(4, status.issued);
+            assert_eq!(1, status.capacity_remaining);
+            assert_eq!(0.8, status.utilization);
+        }
+    }
+
+    mod validate_cred_def {
+        use super::*;
+        use super::super::super::credential_definition::{CredentialDefinitionData, SignatureType};
+        use super::super::super::schema::SchemaId;
+        use ursa::cl::CredentialPublicKey;
+        use ursa::cl::issuer::Issuer as CryptoIssuer;
+
+        fn _credential_public_key(support_revocation: bool) -> CredentialPublicKey {
+            let mut credential_schema_builder = CryptoIssuer::new_credential_schema_builder().unwrap();
+            credential_schema_builder.add_attr("name").unwrap();
+            let credential_schema = credential_schema_builder.finalize().unwrap();
+
+            let mut non_credential_schema_builder = CryptoIssuer::new_non_credential_schema_builder().unwrap();
+            non_credential_schema_builder.add_attr("master_secret").unwrap();
+            let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+            let (credential_public_key, _, _) =
+                CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, support_revocation).unwrap();
+            credential_public_key
+        }
+
+        fn _cred_def(support_revocation: bool) -> CredentialDefinitionV1 {
+            let credential_public_key = _credential_public_key(support_revocation);
+
+            CredentialDefinitionV1 {
+                id: _cred_def_id_unqualified(),
+                schema_id: SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+                signature_type: SignatureType::CL,
+                tag: "tag".to_string(),
+                value: CredentialDefinitionData {
+                    primary: credential_public_key.get_primary_key().unwrap().try_clone().unwrap(),
+                    revocation: credential_public_key.get_revocation_key().unwrap().clone(),
+                },
+            }
+        }
+
+        fn _rev_reg_def(cred_def_id: CredentialDefinitionId) -> RevocationRegistryDefinitionV1 {
+            let (accum_key, _, _, _) =
+                CryptoIssuer::new_revocation_registry_def(&_credential_public_key(true), 5, false).unwrap();
+
+            RevocationRegistryDefinitionV1 {
+                id: _rev_reg_id_unqualified(),
+                revoc_def_type: RegistryType::CL_ACCUM,
+                tag: _tag(),
+                cred_def_id,
+                value: RevocationRegistryDefinitionValue {
+                    issuance_type: IssuanceType::ISSUANCE_ON_DEMAND,
+                    max_cred_num: 5,
+                    public_keys: RevocationRegistryDefinitionValuePublicKeys { accum_key },
+                    tails_hash: "hash".to_string(),
+                    tails_location: "location".to_string(),
+                },
+            }
+        }
+
+        #[test]
+        fn validate_cred_def_works() {
+            _rev_reg_def(_cred_def_id_unqualified()).validate_cred_def(&_cred_def(true)).unwrap();
+            _rev_reg_def(_cred_def_id_qualified()).validate_cred_def(&_cred_def(true)).unwrap();
+        }
+
+        #[test]
+        fn validate_cred_def_works_for_non_revocable_cred_def() {
+            let rev_reg_def = _rev_reg_def(_cred_def_id_unqualified());
+            let cred_def = _cred_def(false);
+
+            assert!(rev_reg_def.validate_cred_def(&cred_def).unwrap_err().contains("does not support revocation"));
+        }
+
+        #[test]
+        fn validate_cred_def_works_for_other_cred_def() {
+            let rev_reg_def = _rev_reg_def(CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:other".to_string()));
+
+            assert!(rev_reg_def.validate_cred_def(&_cred_def(true)).unwrap_err().contains("references Credential Definition"));
+        }
     }
 }
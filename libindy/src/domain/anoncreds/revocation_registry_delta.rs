@@ -1,5 +1,10 @@
 use ursa::cl::RevocationRegistryDelta as RegistryDelta;
 
+use super::revocation_registry_definition::RevocationRegistryId;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use sha2::{Digest, Sha256};
+
 use utils::validation::Validatable;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +13,55 @@ pub struct RevocationRegistryDeltaV1 {
     pub value: RegistryDelta
 }
 
+impl RevocationRegistryDeltaV1 {
+    /// Revocation indices revoked by this delta.
+    pub fn revoked(&self) -> Result<HashSet<u32>, String> {
+        self._indices("revoked")
+    }
+
+    /// Revocation indices issued by this delta.
+    pub fn issued(&self) -> Result<HashSet<u32>, String> {
+        self._indices("issued")
+    }
+
+    fn _indices(&self, name: &str) -> Result<HashSet<u32>, String> {
+        let value = serde_json::to_value(&self.value)
+            .map_err(|err| format!("Cannot serialize RevocationRegistryDelta: {}", err))?;
+
+        match value.get(name) {
+            Some(indices) if !indices.is_null() => serde_json::from_value(indices.clone())
+                .map_err(|err| format!("Invalid {} indices of RevocationRegistryDelta: {}", name, err)),
+            _ => Ok(HashSet::new())
+        }
+    }
+
+    /// Hex encoded SHA-256 of the registry entry: the accumulator it ends with and the issued and revoked
+    /// indices it records, sorted so equal entries always give equal digests. The previous accumulator
+    /// is included when present, so the digest of an entry also commits to the entry it continues.
+    pub fn entry_digest(&self) -> Result<String, String> {
+        let value = serde_json::to_value(&self.value)
+            .map_err(|err| format!("Cannot serialize RevocationRegistryDelta: {}", err))?;
+
+        let indices = |name: &str| -> Result<BTreeSet<u32>, String> {
+            match value.get(name) {
+                Some(indices) if !indices.is_null() => serde_json::from_value(indices.clone())
+                    .map_err(|err| format!("Invalid {} indices of RevocationRegistryDelta: {}", name, err)),
+                _ => Ok(BTreeSet::new())
+            }
+        };
+
+        // json objects are key ordered, so serialization is canonical
+        let canonical = serde_json::to_string(&json!({
+            "prevAccum": value.get("prevAccum").cloned().unwrap_or(serde_json::Value::Null),
+            "accum": value["accum"],
+            "issued": indices("issued")?,
+            "revoked": indices("revoked")?,
+        })).map_err(|err| format!("Cannot serialize RevocationRegistryDelta entry: {}", err))?;
+
+        Ok(hex::encode(Sha256::digest(canonical.as_bytes())))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "ver")]
 pub enum RevocationRegistryDelta {
@@ -23,4 +77,138 @@ impl From<RevocationRegistryDelta> for RevocationRegistryDeltaV1 {
     }
 }
 
-impl Validatable for RevocationRegistryDelta {}
\ No newline at end of file
+/// Deltas published for one revocation registry: either a single delta or a list of deltas in ledger order.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RevocationRegistryDeltaHistory {
+    Delta(RevocationRegistryDelta),
+    Deltas(Vec<RevocationRegistryDelta>),
+}
+
+impl RevocationRegistryDeltaHistory {
+    pub fn into_deltas(self) -> Vec<RevocationRegistryDeltaV1> {
+        match self {
+            RevocationRegistryDeltaHistory::Delta(delta) => vec![delta.into()],
+            RevocationRegistryDeltaHistory::Deltas(deltas) => deltas.into_iter().map(RevocationRegistryDeltaV1::from).collect(),
+        }
+    }
+}
+
+pub type RevocationRegistryDeltas = HashMap<RevocationRegistryId, RevocationRegistryDeltaHistory>;
+
+/// Indices left revoked after applying `deltas` of one registry in order,
+/// starting from `revoked`: an index issued again by a later delta is no longer revoked.
+pub fn apply_revoked_indices(revoked: &mut HashSet<u32>, deltas: &[RevocationRegistryDeltaV1]) -> Result<(), String> {
+    for delta in deltas {
+        for index in delta.issued()? {
+            revoked.remove(&index);
+        }
+
+        revoked.extend(delta.revoked()?);
+    }
+
+    Ok(())
+}
+
+impl Validatable for RevocationRegistryDelta {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ursa::cl::{RevocationRegistry, SimpleTailsAccessor};
+    use ursa::cl::issuer::Issuer as CryptoIssuer;
+
+    const MAX_CRED_NUM: u32 = 5;
+
+    fn _rev_reg() -> (RevocationRegistry, SimpleTailsAccessor) {
+        let mut credential_schema_builder = CryptoIssuer::new_credential_schema_builder().unwrap();
+        credential_schema_builder.add_attr("name").unwrap();
+        let credential_schema = credential_schema_builder.finalize().unwrap();
+
+        let mut non_credential_schema_builder = CryptoIssuer::new_non_credential_schema_builder().unwrap();
+        non_credential_schema_builder.add_attr("master_secret").unwrap();
+        let non_credential_schema = non_credential_schema_builder.finalize().unwrap();
+
+        let (credential_public_key, _, _) =
+            CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let (_, _, rev_reg, mut rev_tails_generator) =
+            CryptoIssuer::new_revocation_registry_def(&credential_public_key, MAX_CRED_NUM, true).unwrap();
+
+        (rev_reg, SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap())
+    }
+
+    fn _delta(rev_reg: &RevocationRegistry, issued: &[u32]) -> RevocationRegistryDeltaV1 {
+        _delta_with_revoked(rev_reg, issued, &[])
+    }
+
+    fn _delta_with_revoked(rev_reg: &RevocationRegistry, issued: &[u32], revoked: &[u32]) -> RevocationRegistryDeltaV1 {
+        RevocationRegistryDeltaV1 {
+            value: RegistryDelta::from_parts(None, rev_reg, &issued.iter().cloned().collect(), &revoked.iter().cloned().collect())
+        }
+    }
+
+    #[test]
+    fn apply_revoked_indices_works() {
+        let (rev_reg, _) = _rev_reg();
+
+        let mut revoked = HashSet::new();
+        apply_revoked_indices(&mut revoked, &[_delta_with_revoked(&rev_reg, &[1, 2, 3], &[]),
+                                              _delta_with_revoked(&rev_reg, &[], &[1, 2])]).unwrap();
+
+        assert_eq!(vec![1, 2].into_iter().collect::<HashSet<u32>>(), revoked);
+    }
+
+    #[test]
+    fn apply_revoked_indices_works_for_index_issued_again() {
+        let (rev_reg, _) = _rev_reg();
+
+        let mut revoked = HashSet::new();
+        apply_revoked_indices(&mut revoked, &[_delta_with_revoked(&rev_reg, &[], &[1, 2]),
+                                              _delta_with_revoked(&rev_reg, &[2], &[])]).unwrap();
+
+        assert_eq!(vec![1].into_iter().collect::<HashSet<u32>>(), revoked);
+    }
+
+    #[test]
+    fn revocation_registry_delta_history_works_for_single_delta_and_list() {
+        let (rev_reg, _) = _rev_reg();
+        let delta = serde_json::to_value(RevocationRegistryDelta::RevocationRegistryDeltaV1(_delta(&rev_reg, &[1]))).unwrap();
+
+        let history: RevocationRegistryDeltaHistory = serde_json::from_value(delta.clone()).unwrap();
+        assert_eq!(1, history.into_deltas().len());
+
+        let history: RevocationRegistryDeltaHistory = serde_json::from_value(json!([delta.clone(), delta])).unwrap();
+        assert_eq!(2, history.into_deltas().len());
+    }
+
+    #[test]
+    fn entry_digest_works_for_equal_entries() {
+        let (rev_reg, _) = _rev_reg();
+
+        assert_eq!(_delta(&rev_reg, &[1, 2, 3]).entry_digest().unwrap(),
+                   _delta(&rev_reg, &[3, 1, 2]).entry_digest().unwrap());
+    }
+
+    #[test]
+    fn entry_digest_works_for_different_indices() {
+        let (rev_reg, _) = _rev_reg();
+
+        assert_ne!(_delta(&rev_reg, &[1, 2]).entry_digest().unwrap(),
+                   _delta(&rev_reg, &[1, 2, 3]).entry_digest().unwrap());
+    }
+
+    #[test]
+    fn entry_digest_works_for_revoke() {
+        let (mut rev_reg, rev_tails_accessor) = _rev_reg();
+        let before = _delta(&rev_reg, &[]).entry_digest().unwrap();
+
+        let revoke_delta = RevocationRegistryDeltaV1 {
+            value: CryptoIssuer::revoke_credential(&mut rev_reg, MAX_CRED_NUM, 1, &rev_tails_accessor).unwrap()
+        };
+
+        assert_ne!(before, _delta(&rev_reg, &[]).entry_digest().unwrap());
+        assert_ne!(before, revoke_delta.entry_digest().unwrap());
+    }
+}
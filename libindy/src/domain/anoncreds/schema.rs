@@ -2,7 +2,7 @@ use super::DELIMITER;
 
 use super::super::crypto::did::DidValue;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use named_type::NamedType;
 
 use utils::validation::Validatable;
@@ -19,6 +19,85 @@ pub struct SchemaV1 {
     #[serde(rename = "attrNames")]
     pub attr_names: AttributeNames,
     pub seq_no: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute_types: Option<AttributeTypes>,
+}
+
+/// Type of a schema attribute that drives how its raw value is encoded.
+/// Attributes without a declared type keep the legacy encoding.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeType {
+    /// 32-bit signed integer encoded as is.
+    Integer,
+    /// Calendar date in `YYYY-MM-DD` format encoded as the number of days since the Unix epoch.
+    Date,
+    /// Arbitrary string always encoded as a hash, even if it looks like a number.
+    String,
+}
+
+pub type AttributeTypes = HashMap<String, AttributeType>;
+
+impl AttributeType {
+    /// Whether values of this type can be compared by predicates.
+    pub fn is_comparable(&self) -> bool {
+        match *self {
+            AttributeType::Integer | AttributeType::Date => true,
+            AttributeType::String => false,
+        }
+    }
+}
+
+impl SchemaV1 {
+    pub fn has_attribute(&self, attr: &str) -> bool {
+        let attr = attr_common_view(attr);
+
+        self.attr_names.iter().any(|name| attr_common_view(name) == attr)
+    }
+
+    pub fn attribute_type(&self, attr: &str) -> Option<&AttributeType> {
+        let attr = attr_common_view(attr);
+
+        self.attribute_types.as_ref()
+            .and_then(|types| types.iter()
+                .find(|&(name, _)| attr_common_view(name) == attr)
+                .map(|(_, type_)| type_))
+    }
+
+    /// Compares attributes of this schema with attributes of `other` (e.g. its upgraded version).
+    /// Attribute names are compared and reported in the common view (without spaces, lowercased),
+    /// so renaming "First Name" to "firstname" doesn't change the attribute.
+    pub fn diff(&self, other: &SchemaV1) -> SchemaDiff {
+        let attrs = self.attr_names.iter().map(|attr| attr_common_view(attr)).collect::<BTreeSet<String>>();
+        let other_attrs = other.attr_names.iter().map(|attr| attr_common_view(attr)).collect::<BTreeSet<String>>();
+
+        SchemaDiff {
+            added: other_attrs.difference(&attrs).cloned().collect(),
+            removed: attrs.difference(&other_attrs).cloned().collect(),
+            unchanged: attrs.intersection(&other_attrs).cloned().collect(),
+        }
+    }
+}
+
+/// Difference between attributes of two schemas, names are sorted and in the common view.
+#[derive(Debug, PartialEq)]
+pub struct SchemaDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl SchemaDiff {
+    /// Whether credentials issued for the old schema can still be presented for any attribute
+    /// of the new one, that is no attribute has been added.
+    pub fn is_backward_compatible(&self) -> bool {
+        self.added.is_empty()
+    }
+}
+
+/// Canonical form of an attribute name: without spaces and lowercased.
+pub fn attr_common_view(attr: &str) -> String {
+    attr.replace(" ", "").to_lowercase()
 }
 
 #[derive(Debug, Serialize, Deserialize, NamedType)]
@@ -28,6 +107,14 @@ pub enum Schema {
     SchemaV1(SchemaV1)
 }
 
+impl Schema {
+    pub fn diff(&self, other: &Schema) -> SchemaDiff {
+        match (self, other) {
+            (Schema::SchemaV1(schema), Schema::SchemaV1(other)) => schema.diff(other)
+        }
+    }
+}
+
 impl From<Schema> for SchemaV1 {
     fn from(schema: Schema) -> Self {
         match schema {
@@ -50,6 +137,13 @@ impl Validatable for Schema {
             Schema::SchemaV1(schema) => {
                 schema.attr_names.validate()?;
                 schema.id.validate()?;
+                if let Some(ref attribute_types) = schema.attribute_types {
+                    let attr_names = schema.attr_names.iter().map(|attr| attr_common_view(attr)).collect::<HashSet<String>>();
+
+                    if let Some(attr) = attribute_types.keys().find(|attr| !attr_names.contains(&attr_common_view(attr))) {
+                        return Err(format!("Type is declared for attribute \"{}\" that is not in Schema attributes", attr));
+                    }
+                }
                 Ok(())
             }
         }
@@ -141,6 +235,96 @@ impl Validatable for SchemaId {
     }
 }
 
+/// Conjunction of comparisons against dot separated numeric schema versions, like `>=1.0,<2.0`.
+/// Missing version components are treated as zeros, so `1` equals `1.0`.
+#[derive(Debug, PartialEq)]
+pub struct SchemaVersionRange(Vec<(VersionComparison, Vec<u64>)>);
+
+#[derive(Debug, PartialEq)]
+enum VersionComparison {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl SchemaVersionRange {
+    pub const TAG: &'static str = "schema_version_range";
+
+    pub fn parse(range: &str) -> Result<SchemaVersionRange, String> {
+        let bounds = range.split(',')
+            .map(|bound| {
+                let bound = bound.trim();
+
+                let (comparison, version) =
+                    if bound.starts_with(">=") { (VersionComparison::Gte, &bound[2..]) }
+                    else if bound.starts_with("<=") { (VersionComparison::Lte, &bound[2..]) }
+                    else if bound.starts_with('>') { (VersionComparison::Gt, &bound[1..]) }
+                    else if bound.starts_with('<') { (VersionComparison::Lt, &bound[1..]) }
+                    else if bound.starts_with('=') { (VersionComparison::Eq, &bound[1..]) }
+                    else { return Err(format!("Invalid schema version range \"{}\": bound \"{}\" has no comparison operator", range, bound)); };
+
+                let version = _parse_version(version.trim())
+                    .ok_or(format!("Invalid schema version range \"{}\": bound \"{}\" has invalid version", range, bound))?;
+
+                Ok((comparison, version))
+            })
+            .collect::<Result<Vec<(VersionComparison, Vec<u64>)>, String>>()?;
+
+        Ok(SchemaVersionRange(bounds))
+    }
+
+    /// Versions that are not dot separated numbers are never contained.
+    pub fn contains(&self, version: &str) -> bool {
+        let version = match _parse_version(version) {
+            Some(version) => version,
+            None => return false
+        };
+
+        self.0.iter().all(|&(ref comparison, ref bound)| {
+            let ordering = _compare_versions(&version, bound);
+
+            match *comparison {
+                VersionComparison::Eq => ordering == ::std::cmp::Ordering::Equal,
+                VersionComparison::Gt => ordering == ::std::cmp::Ordering::Greater,
+                VersionComparison::Gte => ordering != ::std::cmp::Ordering::Less,
+                VersionComparison::Lt => ordering == ::std::cmp::Ordering::Less,
+                VersionComparison::Lte => ordering != ::std::cmp::Ordering::Greater,
+            }
+        })
+    }
+}
+
+/// Compares dot separated numeric schema versions component by component, so `1.10` is greater than `1.9`.
+/// Missing components are treated as zeros, so `1` equals `1.0`.
+pub fn compare_schema_versions(version: &str, other: &str) -> Result<::std::cmp::Ordering, String> {
+    let parse = |version: &str| _parse_version(version)
+        .ok_or(format!("Invalid schema version \"{}\": expected dot separated numbers", version));
+
+    Ok(_compare_versions(&parse(version)?, &parse(other)?))
+}
+
+fn _parse_version(version: &str) -> Option<Vec<u64>> {
+    version.split('.')
+        .map(|part| {
+            if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            part.parse::<u64>().ok()
+        })
+        .collect()
+}
+
+fn _compare_versions(version: &[u64], other: &[u64]) -> ::std::cmp::Ordering {
+    let len = ::std::cmp::max(version.len(), other.len());
+
+    (0..len)
+        .map(|i| version.get(i).unwrap_or(&0).cmp(other.get(i).unwrap_or(&0)))
+        .find(|ordering| *ordering != ::std::cmp::Ordering::Equal)
+        .unwrap_or(::std::cmp::Ordering::Equal)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,4 +432,160 @@ mod tests {
             id.validate().unwrap_err();
         }
     }
-}
\ No newline at end of file
+
+    mod attribute_types {
+        use super::*;
+
+        fn _typed_schema_json() -> &'static str {
+            r#"{
+                "ver": "1.0",
+                "id": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0",
+                "name": "gvt",
+                "version": "1.0",
+                "attrNames": ["name", "age", "birthdate"],
+                "seqNo": null,
+                "attributeTypes": {"name": "string", "age": "integer", "Birth Date": "date"}
+            }"#
+        }
+
+        #[test]
+        fn schema_attribute_types_deserialize_works() {
+            let schema: Schema = serde_json::from_str(_typed_schema_json()).unwrap();
+            let schema = SchemaV1::from(schema);
+
+            assert_eq!(Some(&AttributeType::String), schema.attribute_type("name"));
+            assert_eq!(Some(&AttributeType::Integer), schema.attribute_type("Age"));
+            assert_eq!(Some(&AttributeType::Date), schema.attribute_type("birthdate"));
+            assert_eq!(None, schema.attribute_type("height"));
+        }
+
+        #[test]
+        fn schema_without_attribute_types_serializes_without_field() {
+            let schema: Schema = serde_json::from_str(r#"{"ver":"1.0","id":"NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0","name":"gvt","version":"1.0","attrNames":["name"],"seqNo":null}"#).unwrap();
+
+            let json = serde_json::to_value(&schema).unwrap();
+            assert!(json.get("attributeTypes").is_none());
+        }
+
+        #[test]
+        fn schema_validate_works_for_attribute_types() {
+            let schema: Schema = serde_json::from_str(_typed_schema_json()).unwrap();
+            schema.validate().unwrap();
+        }
+
+        #[test]
+        fn schema_validate_fails_for_type_of_unknown_attribute() {
+            let mut schema = SchemaV1::from(serde_json::from_str::<Schema>(_typed_schema_json()).unwrap());
+            schema.attribute_types.as_mut().unwrap().insert("height".to_string(), AttributeType::Integer);
+
+            Schema::SchemaV1(schema).validate().unwrap_err();
+        }
+    }
+
+    mod diff {
+        use super::*;
+
+        fn _schema(version: &str, attr_names: &[&str]) -> SchemaV1 {
+            SchemaV1 {
+                id: SchemaId::new(&_did(), "gvt", version),
+                name: "gvt".to_string(),
+                version: version.to_string(),
+                attr_names: attr_names.iter().map(|attr| attr.to_string()).collect(),
+                seq_no: None,
+                attribute_types: None,
+            }
+        }
+
+        #[test]
+        fn schema_diff_works() {
+            let schema = _schema("1.0", &["name", "age", "First Name", "sex"]);
+            let upgraded = _schema("1.1", &["name", "age", "firstname", "height"]);
+
+            let diff = schema.diff(&upgraded);
+
+            assert_eq!(vec!["height".to_string()], diff.added);
+            assert_eq!(vec!["sex".to_string()], diff.removed);
+            assert_eq!(vec!["age".to_string(), "firstname".to_string(), "name".to_string()], diff.unchanged);
+            assert!(!diff.is_backward_compatible());
+        }
+
+        #[test]
+        fn schema_diff_works_for_removed_attribute_only() {
+            let schema = _schema("1.0", &["name", "age", "sex"]);
+            let upgraded = _schema("2.0", &["Name", "age"]);
+
+            let diff = Schema::SchemaV1(schema).diff(&Schema::SchemaV1(upgraded));
+
+            assert!(diff.added.is_empty());
+            assert_eq!(vec!["sex".to_string()], diff.removed);
+            assert!(diff.is_backward_compatible());
+        }
+
+        #[test]
+        fn schema_diff_works_for_same_schema() {
+            let schema = _schema("1.0", &["name", "age"]);
+
+            let diff = schema.diff(&schema);
+
+            assert!(diff.added.is_empty());
+            assert!(diff.removed.is_empty());
+            assert_eq!(vec!["age".to_string(), "name".to_string()], diff.unchanged);
+        }
+    }
+
+    mod compare_schema_versions {
+        use super::*;
+
+        use std::cmp::Ordering;
+
+        #[test]
+        fn compare_schema_versions_works() {
+            assert_eq!(Ordering::Less, compare_schema_versions("1.0", "1.1").unwrap());
+            assert_eq!(Ordering::Greater, compare_schema_versions("1.10", "1.9").unwrap());
+            assert_eq!(Ordering::Greater, compare_schema_versions("2.0", "1.99.99").unwrap());
+            assert_eq!(Ordering::Equal, compare_schema_versions("1", "1.0.0").unwrap());
+        }
+
+        #[test]
+        fn compare_schema_versions_fails_for_invalid_version() {
+            assert!(compare_schema_versions("1.0", "1.x").is_err());
+            assert!(compare_schema_versions("", "1.0").is_err());
+            assert!(compare_schema_versions("1..0", "1.0").is_err());
+        }
+    }
+
+    mod schema_version_range {
+        use super::*;
+
+        #[test]
+        fn schema_version_range_works() {
+            let range = SchemaVersionRange::parse(">=1.0,<2.0").unwrap();
+
+            assert!(range.contains("1.0"));
+            assert!(range.contains("1"));
+            assert!(range.contains("1.2"));
+            assert!(range.contains("1.10.3"));
+            assert!(!range.contains("0.9"));
+            assert!(!range.contains("2.0"));
+            assert!(!range.contains("2.1"));
+            assert!(!range.contains("1.x"));
+        }
+
+        #[test]
+        fn schema_version_range_works_for_exact_version() {
+            let range = SchemaVersionRange::parse("=1.2").unwrap();
+
+            assert!(range.contains("1.2.0"));
+            assert!(!range.contains("1.2.1"));
+        }
+
+        #[test]
+        fn schema_version_range_parse_fails_for_malformed_range() {
+            assert!(SchemaVersionRange::parse("").is_err());
+            assert!(SchemaVersionRange::parse("1.0").is_err());
+            assert!(SchemaVersionRange::parse(">=1.0,").is_err());
+            assert!(SchemaVersionRange::parse(">=1..0").is_err());
+            assert!(SchemaVersionRange::parse("=>1.0").is_err());
+        }
+    }
+}
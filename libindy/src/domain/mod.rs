@@ -8,11 +8,35 @@ pub mod cache;
 
 use utils::validation::Validatable;
 
+use self::anoncreds::proof::ProofLimits;
+use self::anoncreds::schema::MAX_ATTRIBUTES_COUNT;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IndyConfig {
     pub crypto_thread_pool_size: Option<usize>,
     pub collect_backtrace: Option<bool>,
-    pub freshness_threshold: Option<u64>
+    pub freshness_threshold: Option<u64>,
+    pub proof_limits: Option<ProofLimits>,
+    pub predicate_max_bits: Option<u32>,
+    pub witness_cache_size: Option<usize>,
+    pub max_schema_attributes: Option<usize>,
+    pub offline: Option<bool>
 }
 
-impl Validatable for IndyConfig {}
\ No newline at end of file
+impl Validatable for IndyConfig {
+    fn validate(&self) -> Result<(), String> {
+        match self.predicate_max_bits {
+            // predicate values are checked as signed integers of at most 128 bits
+            Some(max_bits) if max_bits == 0 || max_bits > 128 =>
+                return Err(format!("Invalid predicate_max_bits {}: must be in range 1..128", max_bits)),
+            _ => {}
+        }
+
+        match self.max_schema_attributes {
+            // schemas can't have more attributes anyway
+            Some(max_attributes) if max_attributes == 0 || max_attributes > MAX_ATTRIBUTES_COUNT =>
+                Err(format!("Invalid max_schema_attributes {}: must be in range 1..{}", max_attributes, MAX_ATTRIBUTES_COUNT)),
+            _ => Ok(())
+        }
+    }
+}
\ No newline at end of file
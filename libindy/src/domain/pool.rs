@@ -5,6 +5,7 @@ pub const POOL_ACK_TIMEOUT: i64 = 20;
 pub const POOL_REPLY_TIMEOUT: i64 = 60;
 pub const MAX_REQ_PER_POOL_CON: usize = 5;
 pub const NUMBER_READ_NODES: u8 = 2;
+pub const MAX_RETRY_DELAY_MS: u64 = 3_600_000;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PoolConfig {
@@ -33,6 +34,33 @@ pub struct PoolOpenConfig {
     pub preordered_nodes: Vec<String>,
     #[serde(default = "PoolOpenConfig::default_number_read_nodes")]
     pub number_read_nodes: u8,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+/// Resending of requests failed with timeout. Delay before attempt `n + 1` is
+/// `base_delay_ms * multiplier ^ (n - 1)` plus random jitter up to `jitter_ms`.
+/// Only read requests are retried unless `retry_writes` is set.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub multiplier: f64,
+    pub jitter_ms: u64,
+    pub retry_writes: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            base_delay_ms: 500,
+            multiplier: 2.0,
+            jitter_ms: 100,
+            retry_writes: false,
+        }
+    }
 }
 
 impl Validatable for PoolOpenConfig {
@@ -52,6 +80,18 @@ impl Validatable for PoolOpenConfig {
         if self.number_read_nodes == 0 {
             return Err(String::from("`number_read_nodes` must be greater than 0"));
         }
+        if self.retry.max_attempts == 0 {
+            return Err(String::from("`retry.max_attempts` must be greater than 0"));
+        }
+        if self.retry.multiplier < 1.0 {
+            return Err(String::from("`retry.multiplier` must be at least 1"));
+        }
+        if self.retry.base_delay_ms > MAX_RETRY_DELAY_MS {
+            return Err(format!("`retry.base_delay_ms` must not be greater than {}", MAX_RETRY_DELAY_MS));
+        }
+        if self.retry.jitter_ms > MAX_RETRY_DELAY_MS {
+            return Err(format!("`retry.jitter_ms` must not be greater than {}", MAX_RETRY_DELAY_MS));
+        }
         Ok(())
     }
 }
@@ -65,6 +105,7 @@ impl Default for PoolOpenConfig {
             conn_active_timeout: PoolOpenConfig::default_conn_active_timeout(),
             preordered_nodes: PoolOpenConfig::default_preordered_nodes(),
             number_read_nodes: PoolOpenConfig::default_number_read_nodes(),
+            retry: RetryPolicy::default(),
         }
     }
 }
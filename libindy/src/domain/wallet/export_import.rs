@@ -36,7 +36,10 @@ pub struct Header {
     // Export time in seconds from UNIX Epoch
     pub time: u64,
     // Version of header
-    pub version: u32
+    pub version: u32,
+    // Number of exported records, absent in files of version 0
+    #[serde(default)]
+    pub record_count: Option<usize>
 }
 
 // Note that we use externally tagged enum serialization and header will be represented as:
@@ -51,6 +54,7 @@ pub struct Header {
 //   },
 //   "time": ..,
 //   "version": ..,
+//   "record_count": ..,
 // }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,4 +68,22 @@ pub struct Record {
     pub value: String,
     // Wallet record tags
     pub tags: HashMap<String, String>,
-}
\ No newline at end of file
+}
+
+// What to do on import with a record that is already in the wallet
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    // fail with WalletItemAlreadyExists
+    Error,
+    // keep the record in the wallet, e.g. to resume an interrupted import
+    Skip,
+    // replace value and tags of the record in the wallet
+    Overwrite,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        ConflictPolicy::Error
+    }
+}
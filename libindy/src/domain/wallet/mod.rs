@@ -5,6 +5,8 @@ use std::collections::HashMap;
 
 use utils::validation::Validatable;
 
+use self::export_import::ConflictPolicy;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub id: String,
@@ -40,7 +42,13 @@ pub struct ExportConfig {
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
-    pub key_derivation_method: KeyDerivationMethod
+    pub key_derivation_method: KeyDerivationMethod,
+    // used on import only
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+    // number of records between progress reports, 0 reports the end only
+    #[serde(default)]
+    pub progress_step: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,6 +30,8 @@ pub enum IndyErrorKind {
     InvalidParam(u32),
     #[fail(display = "IO error")]
     IOError,
+    #[fail(display = "Operation cancelled")]
+    Cancelled,
     // Anoncreds errors
     #[fail(display = "Duplicated master secret")]
     MasterSecretDuplicateName,
@@ -43,6 +45,8 @@ pub enum IndyErrorKind {
     CredentialRevoked,
     #[fail(display = "Credential definition already exists")]
     CredDefAlreadyExists,
+    #[fail(display = "Invalid credential signature")]
+    InvalidCredentialSignature,
     // Ledger errors
     #[fail(display = "No consensus")]
     NoConsensus,
@@ -50,6 +54,8 @@ pub enum IndyErrorKind {
     InvalidTransaction,
     #[fail(display = "Item not found on ledger")]
     LedgerItemNotFound,
+    #[fail(display = "Ledger state is stale")]
+    LedgerStaleState,
     // Pool errors
     #[fail(display = "Pool not created")]
     PoolNotCreated,
@@ -294,15 +300,18 @@ impl From<IndyErrorKind> for ErrorCode {
                     _ => ErrorCode::CommonInvalidState
                 },
             IndyErrorKind::IOError => ErrorCode::CommonIOError,
+            IndyErrorKind::Cancelled => ErrorCode::CommonCancelled,
             IndyErrorKind::MasterSecretDuplicateName => ErrorCode::AnoncredsMasterSecretDuplicateNameError,
             IndyErrorKind::ProofRejected => ErrorCode::AnoncredsProofRejected,
             IndyErrorKind::RevocationRegistryFull => ErrorCode::AnoncredsRevocationRegistryFullError,
             IndyErrorKind::InvalidUserRevocId => ErrorCode::AnoncredsInvalidUserRevocId,
             IndyErrorKind::CredentialRevoked => ErrorCode::AnoncredsCredentialRevoked,
             IndyErrorKind::CredDefAlreadyExists => ErrorCode::AnoncredsCredDefAlreadyExistsError,
+            IndyErrorKind::InvalidCredentialSignature => ErrorCode::AnoncredsInvalidCredentialSignature,
             IndyErrorKind::NoConsensus => ErrorCode::LedgerNoConsensusError,
             IndyErrorKind::InvalidTransaction => ErrorCode::LedgerInvalidTransaction,
             IndyErrorKind::LedgerItemNotFound => ErrorCode::LedgerNotFound,
+            IndyErrorKind::LedgerStaleState => ErrorCode::LedgerStaleState,
             IndyErrorKind::PoolNotCreated => ErrorCode::PoolLedgerNotCreatedError,
             IndyErrorKind::InvalidPoolHandle => ErrorCode::PoolLedgerInvalidPoolHandle,
             IndyErrorKind::PoolTerminated => ErrorCode::PoolLedgerTerminated,
@@ -384,15 +393,18 @@ impl From<ErrorCode> for IndyErrorKind {
             ErrorCode::CommonInvalidParam26 => IndyErrorKind::InvalidParam(26),
             ErrorCode::CommonInvalidParam27 => IndyErrorKind::InvalidParam(27),
             ErrorCode::CommonIOError => IndyErrorKind::IOError,
+            ErrorCode::CommonCancelled => IndyErrorKind::Cancelled,
             ErrorCode::AnoncredsMasterSecretDuplicateNameError => IndyErrorKind::MasterSecretDuplicateName,
             ErrorCode::AnoncredsProofRejected => IndyErrorKind::ProofRejected,
             ErrorCode::AnoncredsRevocationRegistryFullError => IndyErrorKind::RevocationRegistryFull,
             ErrorCode::AnoncredsInvalidUserRevocId => IndyErrorKind::InvalidUserRevocId,
             ErrorCode::AnoncredsCredentialRevoked => IndyErrorKind::CredentialRevoked,
             ErrorCode::AnoncredsCredDefAlreadyExistsError => IndyErrorKind::CredDefAlreadyExists,
+            ErrorCode::AnoncredsInvalidCredentialSignature => IndyErrorKind::InvalidCredentialSignature,
             ErrorCode::LedgerNoConsensusError => IndyErrorKind::NoConsensus,
             ErrorCode::LedgerInvalidTransaction => IndyErrorKind::InvalidTransaction,
             ErrorCode::LedgerNotFound => IndyErrorKind::LedgerItemNotFound,
+            ErrorCode::LedgerStaleState => IndyErrorKind::LedgerStaleState,
             ErrorCode::PoolLedgerNotCreatedError => IndyErrorKind::PoolNotCreated,
             ErrorCode::PoolLedgerInvalidPoolHandle => IndyErrorKind::InvalidPoolHandle,
             ErrorCode::PoolLedgerTerminated => IndyErrorKind::PoolTerminated,
@@ -477,3 +489,30 @@ pub fn get_current_error_c_json() -> *const c_char {
 
     value
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ursa_errors_of_revocation_registry_are_mapped_to_distinct_codes() {
+        let full_err: IndyError = UrsaCryptoError::from_msg(UrsaCryptoErrorKind::RevocationAccumulatorIsFull, "Revocation accumulator is full").into();
+        let missing_index_err: IndyError = UrsaCryptoError::from_msg(UrsaCryptoErrorKind::InvalidRevocationAccumulatorIndex, "Tail 11 is missing in tails file").into();
+
+        assert_eq!(ErrorCode::AnoncredsRevocationRegistryFullError, ErrorCode::from(full_err.kind()));
+        assert_eq!(ErrorCode::AnoncredsInvalidUserRevocId, ErrorCode::from(missing_index_err.kind()));
+        assert_ne!(ErrorCode::from(full_err.kind()), ErrorCode::from(missing_index_err.kind()));
+    }
+
+    #[test]
+    fn invalid_credential_signature_is_mapped_to_own_code() {
+        assert_eq!(ErrorCode::AnoncredsInvalidCredentialSignature, ErrorCode::from(IndyErrorKind::InvalidCredentialSignature));
+        assert_eq!(IndyErrorKind::InvalidCredentialSignature, IndyErrorKind::from(ErrorCode::AnoncredsInvalidCredentialSignature));
+    }
+
+    #[test]
+    fn ledger_stale_state_is_mapped_to_own_code() {
+        assert_eq!(ErrorCode::LedgerStaleState, ErrorCode::from(IndyErrorKind::LedgerStaleState));
+        assert_eq!(IndyErrorKind::LedgerStaleState, IndyErrorKind::from(ErrorCode::LedgerStaleState));
+    }
+}
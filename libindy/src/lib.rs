@@ -49,6 +49,7 @@ extern crate sha3;
 extern crate zeroize;
 
 extern crate regex;
+extern crate unicode_normalization;
 
 // Note that to use macroses from util inside of other modules it must be loaded first!
 #[macro_use]
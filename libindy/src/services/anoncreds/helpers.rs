@@ -1,20 +1,75 @@
 use errors::prelude::*;
 
-use domain::anoncreds::credential::AttributeValues;
-use domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, NonRevocedInterval};
-use ursa::cl::{issuer, verifier, CredentialSchema, NonCredentialSchema, MasterSecret, CredentialValues, SubProofRequest};
+use domain::anoncreds::credential::{AttributeValues, Credential};
+use domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, NonRevocedInterval, NonRevocedIntervals};
+use ursa::bn::BigNumber;
+use ursa::cl::{issuer, verifier, CredentialPrimaryPublicKey, CredentialSchema, NonCredentialSchema, MasterSecret, CredentialValues, SubProofRequest};
 
 use domain::crypto::did::DidValue;
-use domain::anoncreds::schema::SchemaId;
+use domain::anoncreds::schema::{AttributeType, SchemaId, SchemaV1, MAX_ATTRIBUTES_COUNT};
+pub use domain::anoncreds::schema::attr_common_view;
 use domain::anoncreds::credential_definition::CredentialDefinitionId;
 use domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
 use domain::anoncreds::credential_offer::CredentialOffer;
 use domain::anoncreds::proof_request::ProofRequest;
+use domain::anoncreds::proof::Proof;
 
-use std::collections::{HashSet, HashMap};
+use std::collections::{BTreeSet, HashSet, HashMap};
+use std::fmt;
+use std::sync::Mutex;
 
-pub fn attr_common_view(attr: &str) -> String {
-    attr.replace(" ", "").to_lowercase()
+pub const DEFAULT_PREDICATE_MAX_BITS: u32 = 32;
+
+lazy_static! {
+    static ref PREDICATE_MAX_BITS: Mutex<u32> = Mutex::new(DEFAULT_PREDICATE_MAX_BITS);
+    static ref MAX_SCHEMA_ATTRIBUTES: Mutex<usize> = Mutex::new(MAX_ATTRIBUTES_COUNT);
+}
+
+pub fn set_predicate_max_bits(max_bits: u32) {
+    *PREDICATE_MAX_BITS.lock().unwrap() = max_bits;
+}
+
+pub fn predicate_max_bits() -> u32 {
+    *PREDICATE_MAX_BITS.lock().unwrap()
+}
+
+pub fn set_max_schema_attributes(max_attributes: usize) {
+    *MAX_SCHEMA_ATTRIBUTES.lock().unwrap() = max_attributes;
+}
+
+pub fn max_schema_attributes() -> usize {
+    *MAX_SCHEMA_ATTRIBUTES.lock().unwrap()
+}
+
+/// Debug view of credential attributes for logs.
+/// Attribute names stay visible while each raw value is replaced with a prefix of its sha256 hash,
+/// unless `log_sensitive` feature is enabled.
+pub struct RedactedAttributes<'a, V: 'a>(pub &'a HashMap<String, V>);
+
+pub trait RawAttributeValue {
+    fn raw_value(&self) -> &str;
+}
+
+impl RawAttributeValue for AttributeValues {
+    fn raw_value(&self) -> &str { &self.raw }
+}
+
+impl RawAttributeValue for String {
+    fn raw_value(&self) -> &str { self }
+}
+
+impl<'a, V> fmt::Debug for RedactedAttributes<'a, V> where V: RawAttributeValue + fmt::Debug {
+    #[cfg(feature = "log_sensitive")]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+
+    #[cfg(not(feature = "log_sensitive"))]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(attr, value)| (attr, ::utils::logger::redact(value.raw_value()))))
+            .finish()
+    }
 }
 
 pub fn build_credential_schema(attrs: &HashSet<String>) -> IndyResult<CredentialSchema> {
@@ -32,6 +87,55 @@ pub fn build_credential_schema(attrs: &HashSet<String>) -> IndyResult<Credential
     Ok(res)
 }
 
+/// Adds attributes to credential schema in the given order.
+/// Attributes with the same common view (e.g. "First Name" and "firstname") are rejected
+/// instead of being collapsed into one.
+pub fn build_credential_schema_ordered(attrs: &[String]) -> IndyResult<CredentialSchema> {
+    trace!("build_credential_schema_ordered >>> attrs: {:?}", attrs);
+
+    let mut added: HashMap<String, &str> = HashMap::new();
+    let mut credential_schema_builder = issuer::Issuer::new_credential_schema_builder()?;
+
+    for attr in attrs {
+        let common_view = attr_common_view(attr);
+
+        if let Some(other) = added.insert(common_view.clone(), attr) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Attribute \"{}\" duplicates attribute \"{}\"", attr, other)));
+        }
+
+        credential_schema_builder.add_attr(&common_view)?;
+    }
+
+    let res = credential_schema_builder.finalize()?;
+
+    trace!("build_credential_schema_ordered <<< res: {:?}", res);
+
+    Ok(res)
+}
+
+/// Checks that the credential public key has a key in `r` for every attribute,
+/// so a schema not matching the credential definition is reported by attribute name
+/// instead of failing inside the signature.
+pub fn check_public_key_attributes<'a, I>(primary: &CredentialPrimaryPublicKey, attrs: I) -> IndyResult<()> where I: IntoIterator<Item = &'a String> {
+    let primary = serde_json::to_value(primary)
+        .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialPrimaryPublicKey")?;
+
+    let r = primary["r"].as_object()
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Credential public key has no attribute keys"))?;
+
+    let mut missing: Vec<&String> = attrs.into_iter()
+        .filter(|attr| !r.contains_key(&attr_common_view(attr)))
+        .collect();
+    missing.sort();
+
+    match missing.first() {
+        Some(attr) => Err(err_msg(IndyErrorKind::InvalidStructure,
+                                  format!("Attribute \"{}\" has no key in credential definition public key", attr))),
+        None => Ok(())
+    }
+}
+
 pub fn build_non_credential_schema() -> IndyResult<NonCredentialSchema> {
     trace!("build_non_credential_schema");
 
@@ -44,7 +148,7 @@ pub fn build_non_credential_schema() -> IndyResult<NonCredentialSchema> {
 }
 
 pub fn build_credential_values(credential_values: &HashMap<String, AttributeValues>, master_secret: Option<&MasterSecret>) -> IndyResult<CredentialValues> {
-    trace!("build_credential_values >>> credential_values: {:?}", credential_values);
+    trace!("build_credential_values >>> credential_values: {:?}", RedactedAttributes(credential_values));
 
     let mut credential_values_builder = issuer::Issuer::new_credential_values_builder()?;
     for (attr, values) in credential_values {
@@ -56,11 +160,245 @@ pub fn build_credential_values(credential_values: &HashMap<String, AttributeValu
 
     let res = credential_values_builder.finalize()?;
 
-    trace!("build_credential_values <<< res: {:?}", res);
+    trace!("build_credential_values <<< res: {:?}", sensitive!(&res));
+
+    Ok(res)
+}
+
+/// Rejects attributes whose encoded value is zero unless the raw value is zero itself.
+///
+/// A zero encoding makes the attribute's contribution to the signature neutral, so the
+/// attribute would be silently dropped. Integer raw values are encoded as themselves, so
+/// raw `"0"` encoded as `"0"` remains valid.
+pub fn check_zero_encoded_values(credential_values: &HashMap<String, AttributeValues>) -> IndyResult<()> {
+    trace!("check_zero_encoded_values >>> credential_values: {:?}", RedactedAttributes(credential_values));
+
+    for (attr, values) in credential_values {
+        if _is_decimal_zero(&values.encoded) && !_is_decimal_zero(&values.raw) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Attribute encodes to zero: {}", attr)));
+        }
+    }
+
+    trace!("check_zero_encoded_values <<<");
+
+    Ok(())
+}
+
+/// Rejects attributes whose encoded value isn't a canonical decimal.
+///
+/// Implementations parse leading zeros and whitespace differently (or refuse them),
+/// so a credential signed over such a value may not verify elsewhere.
+pub fn check_canonical_encoded_values(credential_values: &HashMap<String, AttributeValues>) -> IndyResult<()> {
+    trace!("check_canonical_encoded_values >>> credential_values: {:?}", RedactedAttributes(credential_values));
+
+    for (attr, values) in credential_values {
+        if !is_canonical_decimal(&values.encoded) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Encoded value of attribute {} is not a canonical decimal", attr)));
+        }
+    }
+
+    trace!("check_canonical_encoded_values <<<");
+
+    Ok(())
+}
+
+/// Optional minus sign followed by digits without leading zeros; zero is only "0".
+pub fn is_canonical_decimal(value: &str) -> bool {
+    let digits = if value.starts_with('-') { &value[1..] } else { value };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    match digits {
+        "0" => value == "0",
+        _ => !digits.starts_with('0')
+    }
+}
+
+fn _is_decimal_zero(value: &str) -> bool {
+    let value = value.trim();
+    let digits = value.trim_start_matches('-');
+    !digits.is_empty() && digits.chars().all(|c| c == '0')
+}
+
+/// Encodes raw attribute value the way other Indy implementations do it:
+/// 32-bit integers are encoded as themselves, any other value as the big-endian
+/// integer of its sha256 hash truncated at the first zero byte.
+pub fn encode_attribute(raw: &str) -> IndyResult<String> {
+    if let Ok(value) = raw.parse::<i32>() {
+        return Ok(value.to_string());
+    }
+
+    let hash = ::utils::crypto::hash::hash(raw.as_bytes())?;
+    let prefix: Vec<u8> = hash.into_iter().take_while(|&byte| byte != 0).collect();
+
+    let encoded = BigNumber::from_bytes(&prefix)?.to_dec()?;
+
+    Ok(encoded)
+}
+
+/// How raw attribute values are turned into integers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncodeMode {
+    /// `encode_attribute`: 32-bit integers as themselves, other values as their sha256 hash
+    /// truncated at the first zero byte.
+    Default,
+    /// Every value, including 32-bit integers, as the integer of its full 32-byte sha256 hash.
+    /// Incompatible with `Default`: encodings differ for 32-bit integers and for values
+    /// whose hash contains a zero byte, so credentials encoded in different modes don't interoperate.
+    FixedHash,
+}
+
+pub fn encode_attribute_with_mode(raw: &str, mode: EncodeMode) -> IndyResult<String> {
+    match mode {
+        EncodeMode::Default => encode_attribute(raw),
+        EncodeMode::FixedHash => _hash_attribute(raw)
+    }
+}
+
+fn _hash_attribute(raw: &str) -> IndyResult<String> {
+    let hash = ::utils::crypto::hash::hash(raw.as_bytes())?;
+
+    let encoded = BigNumber::from_bytes(&hash)?.to_dec()?;
+
+    Ok(encoded)
+}
+
+/// Encodes raw value according to declared schema attribute type.
+/// Untyped attributes fall back to `encode_attribute`.
+pub fn encode_typed_attribute(raw: &str, attr_type: Option<&AttributeType>) -> IndyResult<String> {
+    match attr_type {
+        None => encode_attribute_with_mode(raw, EncodeMode::Default),
+        Some(&AttributeType::Integer) => {
+            raw.parse::<i32>()
+                .map(|value| value.to_string())
+                .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Value of integer attribute is not 32-bit integer"))
+        }
+        Some(&AttributeType::Date) => {
+            _days_since_epoch(raw)
+                .map(|days| days.to_string())
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Value of date attribute is not a YYYY-MM-DD date"))
+        }
+        Some(&AttributeType::String) => encode_attribute_with_mode(raw, EncodeMode::FixedHash)
+    }
+}
+
+fn _days_since_epoch(date: &str) -> Option<i64> {
+    let parts = date.split('-').collect::<Vec<&str>>();
+
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2
+        || !parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+
+    let year = parts[0].parse::<i64>().ok()?;
+    let month = parts[1].parse::<i64>().ok()?;
+    let day = parts[2].parse::<i64>().ok()?;
+
+    let is_leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let month_days = [31, if is_leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+    if month < 1 || month > 12 || day < 1 || day > month_days[(month - 1) as usize] {
+        return None;
+    }
+
+    // Days from civil algorithm with years starting in March, so leap day is the last day of a year.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    Some(era * 146_097 + doe - 719_468)
+}
+
+/// Predicates compare encoded values, so they are meaningless for attributes declared as strings.
+pub fn check_predicate_attribute_type(schema: &SchemaV1, predicate: &PredicateInfo) -> IndyResult<()> {
+    match schema.attribute_type(&predicate.name) {
+        Some(attr_type) if !attr_type.is_comparable() =>
+            Err(err_msg(IndyErrorKind::InvalidStructure,
+                        format!("Predicate can't be applied to string attribute \"{}\" of schema {}", predicate.name, schema.id.0))),
+        _ => Ok(())
+    }
+}
+
+/// Encodes each raw value and returns the attributes whose encoding diverges from the reference one.
+/// Intended for conformance testing against other Indy implementations.
+#[cfg(any(test, feature = "encoding_conformance_tests"))]
+pub fn compare_encodings(values: &HashMap<String, String>,
+                         reference_encodings: &HashMap<String, String>) -> IndyResult<serde_json::Value> {
+    trace!("compare_encodings >>> values: {:?}, reference_encodings: {:?}", RedactedAttributes(values), reference_encodings);
+
+    let mut diverged = serde_json::Map::new();
+
+    for (attr, raw) in values {
+        let reference = reference_encodings.get(attr)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Reference encoding not found for attribute: {}", attr)))?;
+
+        let encoded = encode_attribute(raw)?;
+
+        if encoded != *reference {
+            diverged.insert(attr.clone(), json!({"raw": raw, "reference": reference, "encoded": encoded}));
+        }
+    }
+
+    let res = serde_json::Value::Object(diverged);
+
+    trace!("compare_encodings <<< res: {:?}", sensitive!(&res));
 
     Ok(res)
 }
 
+/// Checks that decimal `value` used in a predicate of attribute `name` fits in a signed integer of `max_bits` bits,
+/// the range predicate proofs support.
+pub fn check_predicate_bit_length(name: &str, value: &str, max_bits: u32) -> IndyResult<()> {
+    match _bit_length_problem(value, max_bits)? {
+        Some(problem) => Err(err_msg(IndyErrorKind::InvalidStructure,
+                                     format!("Predicate value \"{}\" of attribute \"{}\" {}", value, name, problem))),
+        None => Ok(())
+    }
+}
+
+/// Same check as `check_predicate_bit_length` for the encoded value of a credential attribute.
+/// The value isn't put in the error, as for integer attributes it's the raw value.
+pub fn check_attribute_bit_length(name: &str, encoded: &str, max_bits: u32) -> IndyResult<()> {
+    match _bit_length_problem(encoded, max_bits)? {
+        Some(problem) => Err(err_msg(IndyErrorKind::InvalidStructure,
+                                     format!("Credential value of attribute \"{}\" {}", name, problem))),
+        None => Ok(())
+    }
+}
+
+fn _bit_length_problem(value: &str, max_bits: u32) -> IndyResult<Option<String>> {
+    let (negative, magnitude) = match value.trim() {
+        value if value.starts_with('-') => (true, &value[1..]),
+        value => (false, value)
+    };
+
+    if magnitude.is_empty() || !magnitude.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(Some("is not an integer".to_string()));
+    }
+
+    // 2^(max_bits - 1) bounds magnitude of negative values inclusively and of non-negative ones exclusively
+    let bound = max_bits.checked_sub(1)
+        .and_then(|shift| 1u128.checked_shl(shift))
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Invalid predicate max bit length {}: must be in range 1..128", max_bits)))?;
+
+    let fits = magnitude.parse::<u128>()
+        .map(|magnitude| if negative { magnitude <= bound } else { magnitude < bound })
+        .unwrap_or(false);
+
+    if !fits {
+        return Ok(Some(format!("doesn't fit in {} bits", max_bits)));
+    }
+
+    Ok(None)
+}
+
 pub fn build_sub_proof_request(attrs_for_credential: &[AttributeInfo],
                                predicates_for_credential: &[PredicateInfo]) -> IndyResult<SubProofRequest> {
     trace!("build_sub_proof_request >>> attrs_for_credential: {:?}, predicates_for_credential: {:?}", attrs_for_credential, predicates_for_credential);
@@ -72,9 +410,16 @@ pub fn build_sub_proof_request(attrs_for_credential: &[AttributeInfo],
     }
 
     for predicate in predicates_for_credential {
-        let p_type = format!("{}", predicate.p_type);
+        // a range predicate is proven as two inequalities over the same attribute
+        let constraints = predicate.constraints()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
 
-        sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.name), &p_type, predicate.p_value)?;
+        for (p_type, p_value) in constraints {
+            // bounds are i32, so this only rejects them when `predicate_max_bits` is configured below 32
+            check_predicate_bit_length(&predicate.name, &p_value.to_string(), predicate_max_bits())?;
+
+            sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.name), &format!("{}", p_type), p_value)?;
+        }
     }
 
     let res = sub_proof_request_builder.finalize()?;
@@ -95,6 +440,46 @@ pub fn parse_cred_rev_id(cred_rev_id: &str) -> IndyResult<u32> {
     Ok(res)
 }
 
+/// Parses a compact list of credential revocation ids like "3,5,10-14".
+/// Ranges are inclusive, the result is sorted and contains every id once.
+/// Every id must be in 1..=max_cred_num.
+pub fn parse_cred_rev_ids(cred_rev_ids: &str, max_cred_num: u32) -> IndyResult<Vec<u32>> {
+    trace!("parse_cred_rev_ids >>> cred_rev_ids: {:?}, max_cred_num: {:?}", cred_rev_ids, max_cred_num);
+
+    let parse = |id: &str| id.trim().parse::<u32>()
+        .to_indy(IndyErrorKind::InvalidStructure, format!("Cannot parse CredentialRevocationId {:?} in {:?}", id.trim(), cred_rev_ids));
+
+    let mut ids: BTreeSet<u32> = BTreeSet::new();
+
+    for item in cred_rev_ids.split(',') {
+        let (from, to) = match item.find('-') {
+            Some(pos) => (parse(&item[..pos])?, parse(&item[pos + 1..])?),
+            None => {
+                let id = parse(item)?;
+                (id, id)
+            }
+        };
+
+        if from > to {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Invalid CredentialRevocationId range {:?}: start is greater than end", item.trim())));
+        }
+
+        if from == 0 || to > max_cred_num {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId,
+                               format!("CredentialRevocationId {:?} is out of RevocationRegistry bounds 1..{}", item.trim(), max_cred_num)));
+        }
+
+        ids.extend(from..=to);
+    }
+
+    let res: Vec<u32> = ids.into_iter().collect();
+
+    trace!("parse_cred_rev_ids <<< res: {:?}", res);
+
+    Ok(res)
+}
+
 pub fn get_non_revoc_interval(global_interval: &Option<NonRevocedInterval>, local_interval: &Option<NonRevocedInterval>) -> Option<NonRevocedInterval> {
     trace!("get_non_revoc_interval >>> global_interval: {:?}, local_interval: {:?}", global_interval, local_interval);
 
@@ -105,8 +490,24 @@ pub fn get_non_revoc_interval(global_interval: &Option<NonRevocedInterval>, loca
     interval
 }
 
+/// Intervals any of which non-revocation may be proven for: local requirement if set, global one otherwise.
+pub fn get_non_revoc_intervals(global_interval: &Option<NonRevocedInterval>, local_intervals: &Option<NonRevocedIntervals>) -> Vec<NonRevocedInterval> {
+    trace!("get_non_revoc_intervals >>> global_interval: {:?}, local_intervals: {:?}", global_interval, local_intervals);
+
+    let intervals = match *local_intervals {
+        Some(NonRevocedIntervals::Any(ref intervals)) => intervals.clone(),
+        Some(NonRevocedIntervals::Single(ref interval)) => get_non_revoc_interval(global_interval, &Some(interval.clone())).into_iter().collect(),
+        None => get_non_revoc_interval(global_interval, &None).into_iter().collect()
+    };
+
+    trace!("get_non_revoc_intervals <<< intervals: {:?}", intervals);
+
+    intervals
+}
+
 pub fn to_unqualified(entity: &str) -> IndyResult<String> {
-    info!("to_unqualified >>> entity: {:?}", entity);
+    // entity can be a whole Credential or Proof with attribute values
+    info!("to_unqualified >>> entity: {:?}", sensitive!(&entity));
 
     if entity.starts_with(DidValue::PREFIX) {
         return Ok(DidValue(entity.to_string()).to_unqualified().0);
@@ -136,6 +537,18 @@ pub fn to_unqualified(entity: &str) -> IndyResult<String> {
             .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidState, format!("Cannot serialize Proof Request: {:?}", err)));
     }
 
+    if let Ok(credential) = ::serde_json::from_str::<Credential>(&entity) {
+        let credential = credential.to_unqualified();
+        return serde_json::to_string(&credential)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidState, format!("Cannot serialize Credential: {:?}", err)));
+    }
+
+    if let Ok(proof) = ::serde_json::from_str::<Proof>(&entity) {
+        let proof = proof.to_unqualified();
+        return serde_json::to_string(&proof)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidState, format!("Cannot serialize Proof: {:?}", err)));
+    }
+
     Ok(entity.to_string())
 }
 
@@ -163,6 +576,493 @@ mod tests{
         assert_eq!(None, res);
     }
 
+    #[test]
+    fn get_non_revoc_intervals_works_for_single_interval() {
+        assert_eq!(vec![_interval()], get_non_revoc_intervals(&Some(_interval()), &None));
+        assert_eq!(vec![_interval()], get_non_revoc_intervals(&None, &Some(NonRevocedIntervals::Single(_interval()))));
+        assert!(get_non_revoc_intervals(&None, &None).is_empty());
+    }
+
+    #[test]
+    fn get_non_revoc_intervals_works_for_list() {
+        let other = NonRevocedInterval { from: Some(200), to: Some(300) };
+
+        let res = get_non_revoc_intervals(&Some(_interval()), &Some(NonRevocedIntervals::Any(vec![_interval(), other.clone()])));
+        assert_eq!(vec![_interval(), other], res);
+    }
+
+    mod parse_cred_rev_ids {
+        use super::*;
+
+        const MAX_CRED_NUM: u32 = 20;
+
+        #[test]
+        fn parse_cred_rev_ids_works() {
+            assert_eq!(vec![3], parse_cred_rev_ids("3", MAX_CRED_NUM).unwrap());
+            assert_eq!(vec![3, 5], parse_cred_rev_ids("5,3", MAX_CRED_NUM).unwrap());
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_ranges() {
+            assert_eq!(vec![3, 5, 10, 11, 12, 13, 14], parse_cred_rev_ids("3,5,10-14", MAX_CRED_NUM).unwrap());
+            assert_eq!(vec![7], parse_cred_rev_ids("7-7", MAX_CRED_NUM).unwrap());
+            assert_eq!(vec![1, 2, 3], parse_cred_rev_ids(" 1 - 2 , 3 ", MAX_CRED_NUM).unwrap());
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_duplicates() {
+            assert_eq!(vec![2, 3, 4, 5, 6], parse_cred_rev_ids("4,2-5,3,5-6,4", MAX_CRED_NUM).unwrap());
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_bounds() {
+            assert_eq!(vec![1, MAX_CRED_NUM], parse_cred_rev_ids(&format!("1,{}", MAX_CRED_NUM), MAX_CRED_NUM).unwrap());
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_zero() {
+            assert_kind!(IndyErrorKind::InvalidUserRevocId, parse_cred_rev_ids("0", MAX_CRED_NUM));
+            assert_kind!(IndyErrorKind::InvalidUserRevocId, parse_cred_rev_ids("0-3", MAX_CRED_NUM));
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_out_of_bounds() {
+            assert_kind!(IndyErrorKind::InvalidUserRevocId, parse_cred_rev_ids("21", MAX_CRED_NUM));
+            assert_kind!(IndyErrorKind::InvalidUserRevocId, parse_cred_rev_ids("3,18-21", MAX_CRED_NUM));
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_reversed_range() {
+            assert_kind!(IndyErrorKind::InvalidStructure, parse_cred_rev_ids("14-10", MAX_CRED_NUM));
+        }
+
+        #[test]
+        fn parse_cred_rev_ids_works_for_invalid_format() {
+            assert_kind!(IndyErrorKind::InvalidStructure, parse_cred_rev_ids("", MAX_CRED_NUM));
+            assert_kind!(IndyErrorKind::InvalidStructure, parse_cred_rev_ids("1,,2", MAX_CRED_NUM));
+            assert_kind!(IndyErrorKind::InvalidStructure, parse_cred_rev_ids("-5", MAX_CRED_NUM));
+            assert_kind!(IndyErrorKind::InvalidStructure, parse_cred_rev_ids("1-2-3", MAX_CRED_NUM));
+            assert_kind!(IndyErrorKind::InvalidStructure, parse_cred_rev_ids("a", MAX_CRED_NUM));
+        }
+    }
+
+    mod build_credential_schema {
+        use super::*;
+
+        #[test]
+        fn build_credential_schema_ordered_works() {
+            build_credential_schema_ordered(&["name".to_string(), "First Name".to_string(), "age".to_string()]).unwrap();
+        }
+
+        #[test]
+        fn build_credential_schema_ordered_works_for_duplicate_attribute() {
+            let res = build_credential_schema_ordered(&["name".to_string(), "name".to_string()]);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn build_credential_schema_ordered_works_for_same_common_view() {
+            let res = build_credential_schema_ordered(&["First Name".to_string(), "firstname".to_string()]);
+            let err = res.unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+            assert!(err.to_string().contains("firstname"));
+        }
+
+        #[test]
+        fn build_credential_schema_works_for_same_common_view() {
+            let attrs: HashSet<String> = ["First Name".to_string(), "firstname".to_string()].iter().cloned().collect();
+            build_credential_schema(&attrs).unwrap();
+        }
+    }
+
+    mod check_predicate_attribute_type {
+        use super::*;
+        use domain::anoncreds::proof_request::PredicateTypes;
+
+        fn _schema() -> SchemaV1 {
+            let mut attribute_types = HashMap::new();
+            attribute_types.insert("name".to_string(), AttributeType::String);
+            attribute_types.insert("age".to_string(), AttributeType::Integer);
+
+            SchemaV1 {
+                id: SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+                name: "gvt".to_string(),
+                version: "1.0".to_string(),
+                attr_names: vec!["name".to_string(), "age".to_string(), "height".to_string()].into_iter().collect(),
+                seq_no: None,
+                attribute_types: Some(attribute_types),
+            }
+        }
+
+        fn _predicate(name: &str) -> PredicateInfo {
+            PredicateInfo { name: name.to_string(), p_type: PredicateTypes::GE, p_value: 18, p_value_max: None, scale: None, restrictions: None, non_revoked: None }
+        }
+
+        #[test]
+        fn check_predicate_attribute_type_works_for_integer_and_untyped() {
+            check_predicate_attribute_type(&_schema(), &_predicate("age")).unwrap();
+            check_predicate_attribute_type(&_schema(), &_predicate("height")).unwrap();
+        }
+
+        #[test]
+        fn check_predicate_attribute_type_fails_for_string() {
+            let res = check_predicate_attribute_type(&_schema(), &_predicate("Name"));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod check_predicate_bit_length {
+        use super::*;
+
+        #[test]
+        fn check_predicate_bit_length_works() {
+            check_predicate_bit_length("age", "28", DEFAULT_PREDICATE_MAX_BITS).unwrap();
+            check_predicate_bit_length("age", "2147483647", DEFAULT_PREDICATE_MAX_BITS).unwrap();
+            check_predicate_bit_length("age", "-2147483648", DEFAULT_PREDICATE_MAX_BITS).unwrap();
+        }
+
+        #[test]
+        fn check_predicate_bit_length_fails_for_40_bit_value() {
+            // 2^39
+            let res = check_predicate_bit_length("age", "549755813888", DEFAULT_PREDICATE_MAX_BITS);
+
+            let err = res.unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+            assert!(err.to_string().contains("549755813888"));
+            assert!(err.to_string().contains("32 bits"));
+        }
+
+        #[test]
+        fn check_predicate_bit_length_fails_for_value_out_of_configured_length() {
+            check_predicate_bit_length("age", "127", 8).unwrap();
+            let res = check_predicate_bit_length("age", "128", 8);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_predicate_bit_length_fails_for_hashed_encoding() {
+            let res = check_predicate_bit_length("name", "1139481716457488690172217916278103335", DEFAULT_PREDICATE_MAX_BITS);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_predicate_bit_length_works_for_128_bits() {
+            check_predicate_bit_length("age", "-170141183460469231731687303715884105728", 128).unwrap();
+            check_predicate_bit_length("age", "170141183460469231731687303715884105727", 128).unwrap();
+        }
+
+        #[test]
+        fn check_predicate_bit_length_fails_for_invalid_max_bits() {
+            let res = check_predicate_bit_length("age", "28", 0);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let res = check_predicate_bit_length("age", "28", 129);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_attribute_bit_length_fails_without_value_in_error() {
+            let err = check_attribute_bit_length("age", "549755813888", DEFAULT_PREDICATE_MAX_BITS).unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+            assert!(err.to_string().contains("age"));
+            assert!(!err.to_string().contains("549755813888"));
+
+            let err = check_attribute_bit_length("age", "28.5", DEFAULT_PREDICATE_MAX_BITS).unwrap_err();
+            assert!(!err.to_string().contains("28.5"));
+        }
+
+        #[test]
+        fn check_predicate_bit_length_fails_for_not_integer() {
+            let res = check_predicate_bit_length("age", "28.5", DEFAULT_PREDICATE_MAX_BITS);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod build_sub_proof_request {
+        use super::*;
+
+        use domain::anoncreds::proof_request::PredicateTypes;
+
+        fn _range_predicate(p_value: i32, p_value_max: i32) -> PredicateInfo {
+            PredicateInfo { name: "age".to_string(), p_type: PredicateTypes::GE, p_value, p_value_max: Some(p_value_max), scale: None, restrictions: None, non_revoked: None }
+        }
+
+        #[test]
+        fn build_sub_proof_request_works_for_range_predicate() {
+            build_sub_proof_request(&[], &[_range_predicate(18, 65)]).unwrap();
+        }
+
+        #[test]
+        fn build_sub_proof_request_fails_for_range_predicate_min_equal_to_max() {
+            let res = build_sub_proof_request(&[], &[_range_predicate(18, 18)]);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod check_zero_encoded_values {
+        use super::*;
+
+        fn _values(raw: &str, encoded: &str) -> HashMap<String, AttributeValues> {
+            let mut values = HashMap::new();
+            values.insert("name".to_string(), AttributeValues { raw: raw.to_string(), encoded: encoded.to_string() });
+            values
+        }
+
+        #[test]
+        fn check_zero_encoded_values_works() {
+            check_zero_encoded_values(&_values("Alex", "1139481716457488690172217916278103335")).unwrap();
+        }
+
+        #[test]
+        fn check_zero_encoded_values_works_for_raw_zero() {
+            check_zero_encoded_values(&_values("0", "0")).unwrap();
+        }
+
+        #[test]
+        fn check_zero_encoded_values_fails_for_non_zero_raw() {
+            let res = check_zero_encoded_values(&_values("\u{0}Alex", "0"));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_zero_encoded_values_fails_for_empty_raw() {
+            let res = check_zero_encoded_values(&_values("", "000"));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod check_canonical_encoded_values {
+        use super::*;
+
+        fn _values(encoded: &str) -> HashMap<String, AttributeValues> {
+            let mut values = HashMap::new();
+            values.insert("age".to_string(), AttributeValues { raw: "28".to_string(), encoded: encoded.to_string() });
+            values
+        }
+
+        #[test]
+        fn check_canonical_encoded_values_works() {
+            check_canonical_encoded_values(&_values("28")).unwrap();
+            check_canonical_encoded_values(&_values("-28")).unwrap();
+            check_canonical_encoded_values(&_values("0")).unwrap();
+            check_canonical_encoded_values(&_values("1139481716457488690172217916278103335")).unwrap();
+        }
+
+        #[test]
+        fn check_canonical_encoded_values_fails_for_whitespace() {
+            let err = check_canonical_encoded_values(&_values(" 28")).unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+            assert!(err.to_string().contains("age"));
+
+            let res = check_canonical_encoded_values(&_values("28 "));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_canonical_encoded_values_fails_for_leading_zeros() {
+            let err = check_canonical_encoded_values(&_values("0028")).unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+            assert!(err.to_string().contains("age"));
+            assert!(!err.to_string().contains("0028"));
+
+            let res = check_canonical_encoded_values(&_values("00"));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_canonical_encoded_values_fails_for_negative_zero_and_sign_only() {
+            let res = check_canonical_encoded_values(&_values("-0"));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let res = check_canonical_encoded_values(&_values("-"));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod encode_attribute {
+        use super::*;
+
+        fn _gvt_raw_values() -> HashMap<String, String> {
+            let mut values = HashMap::new();
+            values.insert("sex".to_string(), "male".to_string());
+            values.insert("name".to_string(), "Alex".to_string());
+            values.insert("height".to_string(), "175".to_string());
+            values.insert("age".to_string(), "28".to_string());
+            values
+        }
+
+        fn _gvt_encoded_values() -> HashMap<String, String> {
+            let mut values = HashMap::new();
+            values.insert("sex".to_string(), "5944657099558967239210949258394887428692050081607692519917050011144233115103".to_string());
+            values.insert("name".to_string(), "1139481716457488690172217916278103335".to_string());
+            values.insert("height".to_string(), "175".to_string());
+            values.insert("age".to_string(), "28".to_string());
+            values
+        }
+
+        #[test]
+        fn encode_attribute_works_for_int32() {
+            assert_eq!("28", encode_attribute("28").unwrap());
+            assert_eq!("-5", encode_attribute("-5").unwrap());
+            assert_eq!("2147483647", encode_attribute("2147483647").unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_works_for_string() {
+            assert_eq!("5944657099558967239210949258394887428692050081607692519917050011144233115103", encode_attribute("male").unwrap());
+            assert_ne!("2147483648", encode_attribute("2147483648").unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_works_for_hash_with_zero_byte() {
+            // sha256("Alex") has a zero byte after the first 15 bytes
+            assert_eq!("1139481716457488690172217916278103335", encode_attribute("Alex").unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_works_deterministically() {
+            for raw in &["Alex", "male", "28", "2147483648", ""] {
+                let encoded = encode_attribute(raw).unwrap();
+
+                for _ in 0..100 {
+                    assert_eq!(encoded, encode_attribute(raw).unwrap());
+                }
+            }
+        }
+
+        #[test]
+        fn encode_attribute_with_mode_works_for_fixed_hash() {
+            assert_eq!("83761840706354868391674207739241454863743470852830526299004654280720761327142",
+                       encode_attribute_with_mode("5435", EncodeMode::FixedHash).unwrap());
+            assert_eq!("99262857098057710338306967609588410025648622308394250666849665532448612202874",
+                       encode_attribute_with_mode("Alex", EncodeMode::FixedHash).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_with_mode_works_for_fixed_hash_against_default() {
+            // "5435" is a 32-bit integer and sha256("Alex") contains a zero byte, sha256("male") doesn't
+            assert_ne!(encode_attribute("5435").unwrap(), encode_attribute_with_mode("5435", EncodeMode::FixedHash).unwrap());
+            assert_ne!(encode_attribute("Alex").unwrap(), encode_attribute_with_mode("Alex", EncodeMode::FixedHash).unwrap());
+            assert_eq!(encode_attribute("male").unwrap(), encode_attribute_with_mode("male", EncodeMode::FixedHash).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_with_mode_works_for_default() {
+            assert_eq!("5435", encode_attribute_with_mode("5435", EncodeMode::Default).unwrap());
+        }
+
+        #[test]
+        fn encode_typed_attribute_works_for_untyped() {
+            assert_eq!(encode_attribute("28").unwrap(), encode_typed_attribute("28", None).unwrap());
+            assert_eq!(encode_attribute("Alex").unwrap(), encode_typed_attribute("Alex", None).unwrap());
+        }
+
+        #[test]
+        fn encode_typed_attribute_works_for_integer() {
+            assert_eq!("28", encode_typed_attribute("28", Some(&AttributeType::Integer)).unwrap());
+            assert_eq!("-5", encode_typed_attribute("-5", Some(&AttributeType::Integer)).unwrap());
+
+            let res = encode_typed_attribute("Alex", Some(&AttributeType::Integer));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let res = encode_typed_attribute("2147483648", Some(&AttributeType::Integer));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn encode_typed_attribute_works_for_string() {
+            assert_eq!(encode_attribute_with_mode("Alex", EncodeMode::FixedHash).unwrap(), encode_typed_attribute("Alex", Some(&AttributeType::String)).unwrap());
+            assert_ne!("28", encode_typed_attribute("28", Some(&AttributeType::String)).unwrap());
+        }
+
+        #[test]
+        fn encode_typed_attribute_works_for_date() {
+            assert_eq!("0", encode_typed_attribute("1970-01-01", Some(&AttributeType::Date)).unwrap());
+            assert_eq!("-1", encode_typed_attribute("1969-12-31", Some(&AttributeType::Date)).unwrap());
+            assert_eq!("11016", encode_typed_attribute("2000-02-29", Some(&AttributeType::Date)).unwrap());
+            assert_eq!("17897", encode_typed_attribute("2019-01-01", Some(&AttributeType::Date)).unwrap());
+
+            for invalid in &["2019-02-29", "2019-13-01", "2019-1-01", "01.01.2019", "Alex"] {
+                let res = encode_typed_attribute(invalid, Some(&AttributeType::Date));
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+            }
+        }
+
+        #[test]
+        fn compare_encodings_works_for_gvt_values() {
+            let diverged = compare_encodings(&_gvt_raw_values(), &_gvt_encoded_values()).unwrap();
+            assert_eq!(json!({}), diverged);
+        }
+
+        #[test]
+        fn compare_encodings_works_for_diverging_references() {
+            let mut reference_encodings = _gvt_encoded_values();
+            reference_encodings.insert("name".to_string(), "99262857098057710338306967609588410025648622308394250666849665532448612202874".to_string());
+
+            let diverged = compare_encodings(&_gvt_raw_values(), &reference_encodings).unwrap();
+
+            let expected = json!({
+                "name": {
+                    "raw": "Alex",
+                    "reference": "99262857098057710338306967609588410025648622308394250666849665532448612202874",
+                    "encoded": "1139481716457488690172217916278103335"
+                }
+            });
+            assert_eq!(expected, diverged);
+        }
+
+        #[test]
+        fn compare_encodings_fails_for_missing_reference() {
+            let mut reference_encodings = _gvt_encoded_values();
+            reference_encodings.remove("age");
+
+            let res = compare_encodings(&_gvt_raw_values(), &reference_encodings);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    #[cfg(not(feature = "log_sensitive"))]
+    mod redacted_attributes {
+        use super::*;
+
+        const RAW_VALUE: &str = "Alexander Sensitive";
+        const ENCODED_VALUE: &str = "987654321012345678";
+
+        #[test]
+        fn redacted_attributes_works() {
+            let mut values = HashMap::new();
+            values.insert("name".to_string(), AttributeValues { raw: RAW_VALUE.to_string(), encoded: ENCODED_VALUE.to_string() });
+
+            let redacted = format!("{:?}", RedactedAttributes(&values));
+
+            assert!(redacted.contains("\"name\": \"sha256:"));
+            assert!(!redacted.contains(RAW_VALUE));
+            assert!(!redacted.contains(ENCODED_VALUE));
+        }
+
+        #[test]
+        fn redacted_attributes_works_for_short_values() {
+            let mut values = HashMap::new();
+            values.insert("name".to_string(), RAW_VALUE.to_string());
+
+            let redacted = format!("{:?}", RedactedAttributes(&values));
+
+            assert!(redacted.contains("\"name\": \"sha256:"));
+            assert!(!redacted.contains(RAW_VALUE));
+        }
+
+        #[test]
+        fn sensitive_works() {
+            let mut values = HashMap::new();
+            values.insert("name".to_string(), AttributeValues { raw: RAW_VALUE.to_string(), encoded: ENCODED_VALUE.to_string() });
+
+            let logged = format!("store_credential >>> values: {:?}", sensitive!(&values));
+
+            assert!(!logged.contains(RAW_VALUE));
+            assert!(!logged.contains(ENCODED_VALUE));
+        }
+    }
+
     mod to_unqualified {
         use super::*;
         
@@ -199,5 +1099,77 @@ mod tests{
             assert_eq!(REV_REG_ID_UNQUALIFIED, to_unqualified(REV_REG_ID_QUALIFIED).unwrap());
             assert_eq!(REV_REG_ID_UNQUALIFIED, to_unqualified(REV_REG_ID_UNQUALIFIED).unwrap());
         }
+
+        fn _credential_json(schema_id: &str, cred_def_id: &str, rev_reg_id: &str) -> String {
+            json!({
+                "schema_id": schema_id,
+                "cred_def_id": cred_def_id,
+                "rev_reg_id": rev_reg_id,
+                "values": {
+                    "age": {"raw": "28", "encoded": "28"}
+                },
+                "signature": {
+                    "p_credential": {"m_2": "1234567890", "a": "98765432109876543210", "e": "1", "v": "123456789012345678901234567890"}
+                },
+                "signature_correctness_proof": {"se": "1234567890", "c": "987654321"}
+            }).to_string()
+        }
+
+        #[test]
+        fn test_to_unqualified_for_credential() {
+            let qualified = _credential_json(SCHEMA_ID_QUALIFIED, CRED_DEF_ID_QUALIFIED, REV_REG_ID_QUALIFIED);
+            let unqualified = _credential_json(SCHEMA_ID_UNQUALIFIED, CRED_DEF_ID_UNQUALIFIED, REV_REG_ID_UNQUALIFIED);
+
+            let credential: Credential = serde_json::from_str(&to_unqualified(&qualified).unwrap()).unwrap();
+            assert_eq!(SCHEMA_ID_UNQUALIFIED, credential.schema_id.0);
+            assert_eq!(CRED_DEF_ID_UNQUALIFIED, credential.cred_def_id.0);
+            assert_eq!(REV_REG_ID_UNQUALIFIED, credential.rev_reg_id.unwrap().0);
+
+            let credential: Credential = serde_json::from_str(&to_unqualified(&unqualified).unwrap()).unwrap();
+            assert_eq!(SCHEMA_ID_UNQUALIFIED, credential.schema_id.0);
+            assert_eq!(CRED_DEF_ID_UNQUALIFIED, credential.cred_def_id.0);
+            assert_eq!(REV_REG_ID_UNQUALIFIED, credential.rev_reg_id.unwrap().0);
+        }
+
+        fn _proof_json(schema_id: &str, cred_def_id: &str, rev_reg_id: &str) -> String {
+            json!({
+                "proof": {
+                    "proofs": [],
+                    "aggregated_proof": {"c_hash": "1234567890", "c_list": []}
+                },
+                "requested_proof": {
+                    "revealed_attrs": {
+                        "attr1_referent": {"sub_proof_index": 0, "raw": "Alex", "encoded": "1139481716457488690172217916278103335"}
+                    },
+                    "self_attested_attrs": {},
+                    "unrevealed_attrs": {},
+                    "predicates": {}
+                },
+                "identifiers": [{
+                    "schema_id": schema_id,
+                    "cred_def_id": cred_def_id,
+                    "rev_reg_id": rev_reg_id,
+                    "timestamp": 1234
+                }]
+            }).to_string()
+        }
+
+        #[test]
+        fn test_to_unqualified_for_proof() {
+            let qualified = _proof_json(SCHEMA_ID_QUALIFIED, CRED_DEF_ID_QUALIFIED, REV_REG_ID_QUALIFIED);
+            let unqualified = _proof_json(SCHEMA_ID_UNQUALIFIED, CRED_DEF_ID_UNQUALIFIED, REV_REG_ID_UNQUALIFIED);
+
+            let proof: Proof = serde_json::from_str(&to_unqualified(&qualified).unwrap()).unwrap();
+            assert_eq!(SCHEMA_ID_UNQUALIFIED, proof.identifiers[0].schema_id.0);
+            assert_eq!(CRED_DEF_ID_UNQUALIFIED, proof.identifiers[0].cred_def_id.0);
+            assert_eq!(REV_REG_ID_UNQUALIFIED, proof.identifiers[0].rev_reg_id.as_ref().unwrap().0);
+            assert_eq!(Some(1234), proof.identifiers[0].timestamp);
+            assert_eq!("Alex", proof.requested_proof.revealed_attrs["attr1_referent"].raw);
+
+            let proof: Proof = serde_json::from_str(&to_unqualified(&unqualified).unwrap()).unwrap();
+            assert_eq!(SCHEMA_ID_UNQUALIFIED, proof.identifiers[0].schema_id.0);
+            assert_eq!(CRED_DEF_ID_UNQUALIFIED, proof.identifiers[0].cred_def_id.0);
+            assert_eq!(REV_REG_ID_UNQUALIFIED, proof.identifiers[0].rev_reg_id.as_ref().unwrap().0);
+        }
     }
 }
\ No newline at end of file
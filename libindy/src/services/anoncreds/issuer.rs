@@ -14,14 +14,25 @@ use ursa::cl::{
 use ursa::cl::issuer::Issuer as CryptoIssuer;
 
 use domain::anoncreds::schema::AttributeNames;
-use domain::anoncreds::credential::CredentialValues;
+use domain::anoncreds::credential::{AttributeProblem, AttributeProblemKind, AttributeValues, CredentialValues};
+use domain::anoncreds::schema::SchemaV1;
 use domain::anoncreds::credential_definition::{CredentialDefinitionData, CredentialDefinitionV1 as CredentialDefinition};
 use domain::anoncreds::credential_request::CredentialRequest;
-use domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryDefinitionValuePublicKeys};
+use domain::anoncreds::revocation_registry_definition::{IssuanceType, RegistryState, RevocationRegistryDefinitionV1, RevocationRegistryDefinitionValuePublicKeys, RevocationRegistryInfo};
 use domain::crypto::did::DidValue;
 use errors::prelude::*;
 use services::anoncreds::helpers::*;
 
+use services::wallet::KeyDerivationData;
+use utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
+
+use std::collections::{HashMap, HashSet};
+
+const SNAPSHOT_VERSION: u8 = 2;
+const SNAPSHOT_KEY_RAW: u8 = 0;
+const SNAPSHOT_KEY_ARGON2I_MOD: u8 = 1;
+const SNAPSHOT_KEY_ARGON2I_INT: u8 = 2;
+
 pub struct Issuer {}
 
 impl Issuer {
@@ -35,6 +46,13 @@ impl Issuer {
                                                                               CredentialKeyCorrectnessProof)> {
         trace!("new_credential_definition >>> attr_names: {:?}, support_revocation: {:?}", attr_names, support_revocation);
 
+        let max_attributes = max_schema_attributes();
+
+        if attr_names.len() > max_attributes {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Schema has {} attributes, at most {} are supported", attr_names.len(), max_attributes)));
+        }
+
         let credential_schema = build_credential_schema(attr_names)?;
         let non_credential_schema = build_non_credential_schema()?;
 
@@ -52,8 +70,7 @@ impl Issuer {
         Ok((credential_definition_value, credential_private_key, credential_key_correctness_proof))
     }
 
-    pub fn new_revocation_registry(&self,
-                                   cred_def: &CredentialDefinition,
+    pub fn new_revocation_registry(cred_def: &CredentialDefinition,
                                    max_cred_num: u32,
                                    issuance_by_default: bool,
                                    issuer_did: &DidValue) -> IndyResult<(RevocationRegistryDefinitionValuePublicKeys,
@@ -94,13 +111,16 @@ impl Issuer {
                                                                                 Option<RevocationRegistryDelta>)> where RTA: RevocationTailsAccessor {
         trace!("new_credential >>> cred_def: {:?}, cred_priv_key: {:?}, cred_issuance_blinding_nonce: {:?}, cred_request: {:?},\
                cred_values: {:?}, rev_idx: {:?}, rev_reg_def: {:?}, rev_reg: {:?}, rev_key_priv: {:?}",
-               cred_def, secret!(&cred_priv_key), secret!(&cred_issuance_blinding_nonce), secret!(&cred_request), secret!(&cred_values), secret!(&rev_idx),
+               cred_def, secret!(&cred_priv_key), secret!(&cred_issuance_blinding_nonce), secret!(&cred_request), RedactedAttributes(cred_values), secret!(&rev_idx),
                rev_reg_def, rev_reg, secret!(&rev_key_priv));
 
+        check_zero_encoded_values(&cred_values)?;
+        check_canonical_encoded_values(&cred_values)?;
+
         let credential_values = build_credential_values(&cred_values, None)?;
         let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
 
-        let (credential_signature, signature_correctness_proof, rev_reg_delta) =
+        let signed: IndyResult<_> =
             match rev_idx {
                 Some(rev_idx) => {
                     let rev_reg = rev_reg
@@ -128,22 +148,31 @@ impl Issuer {
                                                              rev_reg_def.value.issuance_type.to_bool(),
                                                              rev_reg,
                                                              rev_key_priv,
-                                                             rev_tails_accessor)?
+                                                             rev_tails_accessor)
+                        .map_err(IndyError::from)
                 }
                 None => {
-                    let (signature, correctness_proof) =
-                        CryptoIssuer::sign_credential(&cred_request.prover_did.0,
-                                                      &cred_request.blinded_ms,
-                                                      &cred_request.blinded_ms_correctness_proof,
+                    CryptoIssuer::sign_credential(&cred_request.prover_did.0,
+                                                  &cred_request.blinded_ms,
+                                                  &cred_request.blinded_ms_correctness_proof,
                                                       cred_issuance_blinding_nonce,
-                                                      &cred_request.nonce,
-                                                      &credential_values,
-                                                      &credential_pub_key,
-                                                      &cred_priv_key)?;
-                    (signature, correctness_proof, None)
+                                                  &cred_request.nonce,
+                                                  &credential_values,
+                                                  &credential_pub_key,
+                                                  &cred_priv_key)
+                        .map(|(signature, correctness_proof)| (signature, correctness_proof, None))
+                        .map_err(IndyError::from)
                 }
             };
 
+        // The primary key only exposes its attributes through serialization, so a schema
+        // not matching the credential definition is diagnosed once signing has failed.
+        let (credential_signature, signature_correctness_proof, rev_reg_delta) =
+            signed.map_err(|err| match check_public_key_attributes(&cred_def.value.primary, cred_values.keys()) {
+                Err(mismatch) => mismatch,
+                Ok(()) => err
+            })?;
+
         trace!("new_credential <<< credential_signature {:?}, signature_correctness_proof {:?}, rev_reg_delta {:?}",
                secret!(&credential_signature), secret!(&signature_correctness_proof), rev_reg_delta);
 
@@ -164,6 +193,37 @@ impl Issuer {
         Ok(rev_reg_delta)
     }
 
+    /// Revokes every index of `rev_idxs` and returns a single delta equivalent to revoking them one by one:
+    /// `prevAccum` of the registry before the call, `accum` after it and all indices in `revoked`.
+    pub fn revoke_batch<RTA>(&self,
+                             rev_reg: &mut RevocationRegistry,
+                             max_cred_num: u32,
+                             rev_idxs: &[u32],
+                             rev_tails_accessor: &RTA) -> IndyResult<RevocationRegistryDelta> where RTA: RevocationTailsAccessor {
+        trace!("revoke_batch >>> rev_reg: {:?}, max_cred_num: {:?}, rev_idxs: {:?}", rev_reg, max_cred_num, secret!(&rev_idxs));
+
+        let unique: HashSet<&u32> = rev_idxs.iter().collect();
+        if unique.len() != rev_idxs.len() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Revocation ids to revoke contain duplicates"));
+        }
+
+        // revoke on a copy, so a failure in the middle of the batch leaves the registry untouched
+        let mut next_rev_reg = rev_reg.clone();
+
+        let rev_reg_deltas = rev_idxs
+            .iter()
+            .map(|rev_idx| self.revoke(&mut next_rev_reg, max_cred_num, *rev_idx, rev_tails_accessor))
+            .collect::<IndyResult<Vec<RevocationRegistryDelta>>>()?;
+
+        let rev_reg_delta = Issuer::compact_revocation_registry_deltas(&rev_reg_deltas)?;
+
+        *rev_reg = next_rev_reg;
+
+        trace!("revoke_batch <<< rev_reg_delta {:?}", rev_reg_delta);
+
+        Ok(rev_reg_delta)
+    }
+
     #[allow(dead_code)]
     pub fn recovery<RTA>(&self,
                          rev_reg: &mut RevocationRegistry,
@@ -178,4 +238,500 @@ impl Issuer {
 
         Ok(rev_reg_delta)
     }
+
+    /// Folds a consecutive run of deltas into a single equivalent delta.
+    /// The result keeps `prevAccum` of the first delta and `accum` of the last one.
+    pub fn compact_revocation_registry_deltas(deltas: &[RevocationRegistryDelta]) -> IndyResult<RevocationRegistryDelta> {
+        trace!("compact_revocation_registry_deltas >>> deltas: {:?}", deltas);
+
+        let (first, rest) = deltas.split_first()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Nothing to compact: empty list of revocation registry deltas"))?;
+
+        let mut rev_reg_delta = first.clone();
+
+        for other in rest {
+            rev_reg_delta.merge(other)?;
+        }
+
+        trace!("compact_revocation_registry_deltas <<< rev_reg_delta: {:?}", rev_reg_delta);
+
+        Ok(rev_reg_delta)
+    }
+
+    /// Reserves the next revocation index of the registry for a new credential.
+    /// Registry is moved to `Full` state as soon as the last index is taken.
+    pub fn allocate_revocation_index(rev_reg_info: &mut RevocationRegistryInfo,
+                                     max_cred_num: u32,
+                                     issuance_type: &IssuanceType) -> IndyResult<u32> {
+        trace!("allocate_revocation_index >>> rev_reg_info: {:?}, max_cred_num: {:?}, issuance_type: {:?}", rev_reg_info, max_cred_num, issuance_type);
+
+        match rev_reg_info.state {
+            RegistryState::Active if !rev_reg_info.is_full(max_cred_num) => {}
+            RegistryState::Rotated =>
+                return Err(err_msg(IndyErrorKind::InvalidState, format!("Revocation registry {} is rotated", rev_reg_info.id.0))),
+            _ =>
+                return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"))
+        }
+
+        rev_reg_info.curr_id += 1;
+
+        if *issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
+            rev_reg_info.used_ids.insert(rev_reg_info.curr_id);
+        }
+
+        if rev_reg_info.is_full(max_cred_num) {
+            rev_reg_info.state = RegistryState::Full;
+        }
+
+        trace!("allocate_revocation_index <<< curr_id: {:?}", rev_reg_info.curr_id);
+
+        Ok(rev_reg_info.curr_id)
+    }
+
+    /// Builds credential values from raw ones, encoding each according to its schema attribute type.
+    pub fn encode_attributes(schema: &SchemaV1, raw_values: &HashMap<String, String>) -> IndyResult<CredentialValues> {
+        trace!("encode_attributes >>> schema: {:?}, raw_values: {:?}", schema, RedactedAttributes(raw_values));
+
+        let mut values: CredentialValues = HashMap::new();
+
+        for (attr, raw) in raw_values {
+            let encoded = encode_typed_attribute(raw, schema.attribute_type(attr))
+                .map_err(|err| err.extend(format!("Cannot encode attribute \"{}\"", attr)))?;
+
+            values.insert(attr.clone(), AttributeValues { raw: raw.clone(), encoded });
+        }
+
+        trace!("encode_attributes <<< values: {:?}", RedactedAttributes(&values));
+
+        Ok(values)
+    }
+
+    /// Checks that credential values contain every schema attribute with decimal encoded value and nothing else.
+    /// Values of typed attributes must also be encoded according to their types.
+    /// All found problems are reported at once, one per attribute; an empty list means values match the schema.
+    pub fn validate_attributes_for_schema(schema: &SchemaV1, attributes: &CredentialValues) -> Vec<AttributeProblem> {
+        trace!("validate_attributes_for_schema >>> schema: {:?}, attributes: {:?}", schema, RedactedAttributes(attributes));
+
+        let schema_attrs: HashSet<String> = schema.attr_names.iter().map(|attr| attr_common_view(attr)).collect();
+        let values: HashMap<String, &AttributeValues> = attributes.iter().map(|(attr, values)| (attr_common_view(attr), values)).collect();
+
+        let mut problems: Vec<AttributeProblem> = Vec::new();
+
+        for attr in schema_attrs.iter() {
+            let problem = match values.get(attr) {
+                None => AttributeProblemKind::Missing,
+                Some(values) if !is_canonical_decimal(&values.encoded) => AttributeProblemKind::InvalidEncoding,
+                Some(values) => match schema.attribute_type(attr) {
+                    Some(attr_type) if encode_typed_attribute(&values.raw, Some(attr_type)).ok().as_ref() != Some(&values.encoded) =>
+                        AttributeProblemKind::InvalidEncoding,
+                    _ => continue
+                }
+            };
+
+            problems.push(AttributeProblem { attr: attr.clone(), problem });
+        }
+
+        for attr in values.keys().filter(|attr| !schema_attrs.contains(*attr)) {
+            problems.push(AttributeProblem { attr: attr.clone(), problem: AttributeProblemKind::NotInSchema });
+        }
+
+        problems.sort();
+
+        trace!("validate_attributes_for_schema <<< problems: {:?}", problems);
+
+        problems
+    }
+
+    /// Encrypts exported revocation registry state with the key of wallet export derivation.
+    /// Layout: version | key derivation method | salt (argon2i only) | nonce | chacha20poly1305 ciphertext.
+    pub fn seal_revocation_registry_snapshot(snapshot: &[u8], key_data: &KeyDerivationData) -> IndyResult<Vec<u8>> {
+        let key = key_data.calc_master_key()?;
+
+        let (ciphertext, nonce) = chacha20poly1305_ietf::gen_nonce_and_encrypt(snapshot, &key);
+
+        let mut res = vec![SNAPSHOT_VERSION];
+        match *key_data {
+            KeyDerivationData::Raw(_) => res.push(SNAPSHOT_KEY_RAW),
+            KeyDerivationData::Argon2iMod(_, ref salt) => {
+                res.push(SNAPSHOT_KEY_ARGON2I_MOD);
+                res.extend_from_slice(&salt[..]);
+            }
+            KeyDerivationData::Argon2iInt(_, ref salt) => {
+                res.push(SNAPSHOT_KEY_ARGON2I_INT);
+                res.extend_from_slice(&salt[..]);
+            }
+        }
+        res.extend_from_slice(&nonce[..]);
+        res.extend(ciphertext);
+
+        Ok(res)
+    }
+
+    /// Decrypts revocation registry state sealed by `seal_revocation_registry_snapshot`.
+    /// Authenticated encryption rejects both a wrong key and a modified snapshot.
+    pub fn open_revocation_registry_snapshot(sealed: &[u8], key: &str) -> IndyResult<Vec<u8>> {
+        if sealed.len() < 2 || sealed[0] != SNAPSHOT_VERSION {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported revocation registry snapshot format"));
+        }
+
+        let (key_data, nonce_start) = match sealed[1] {
+            SNAPSHOT_KEY_RAW => (KeyDerivationData::Raw(key.to_string()), 2),
+            method @ SNAPSHOT_KEY_ARGON2I_MOD | method @ SNAPSHOT_KEY_ARGON2I_INT => {
+                let salt = sealed.get(2..2 + pwhash_argon2i13::SALTBYTES)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation registry snapshot is corrupted"))?;
+                let salt = pwhash_argon2i13::Salt::from_slice(salt)?;
+
+                let key_data = if method == SNAPSHOT_KEY_ARGON2I_MOD {
+                    KeyDerivationData::Argon2iMod(key.to_string(), salt)
+                } else {
+                    KeyDerivationData::Argon2iInt(key.to_string(), salt)
+                };
+
+                (key_data, 2 + pwhash_argon2i13::SALTBYTES)
+            }
+            _ => return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported revocation registry snapshot format"))
+        };
+
+        let nonce = sealed.get(nonce_start..nonce_start + chacha20poly1305_ietf::NONCEBYTES)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation registry snapshot is corrupted"))?;
+        let nonce = chacha20poly1305_ietf::Nonce::from_slice(nonce)?;
+
+        let key = key_data.calc_master_key()
+            .map_err(|err| err.extend("Revocation registry snapshot key is invalid"))?;
+
+        chacha20poly1305_ietf::decrypt(&sealed[nonce_start + chacha20poly1305_ietf::NONCEBYTES..], &key, &nonce)
+            .map_err(|err| err.extend("Revocation registry snapshot is corrupted or export key is wrong"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use ursa::cl::{new_nonce, SimpleTailsAccessor};
+    use ursa::cl::prover::Prover as CryptoProver;
+
+    use domain::anoncreds::credential_definition::{CredentialDefinitionId, SignatureType};
+    use domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
+    use domain::anoncreds::schema::{AttributeType, SchemaId, MAX_ATTRIBUTES_COUNT};
+    use domain::wallet::KeyDerivationMethod;
+
+    const MAX_CRED_NUM: u32 = 5;
+
+    fn _rev_reg() -> (RevocationRegistry, SimpleTailsAccessor) {
+        let mut attr_names: HashSet<String> = HashSet::new();
+        attr_names.insert("name".to_string());
+        let credential_schema = build_credential_schema(&attr_names).unwrap();
+        let non_credential_schema = build_non_credential_schema().unwrap();
+
+        let (credential_pub_key, _, _) =
+            CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let (_, _, rev_reg, mut rev_tails_generator) =
+            CryptoIssuer::new_revocation_registry_def(&credential_pub_key, MAX_CRED_NUM, true).unwrap();
+
+        let rev_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+        (rev_reg, rev_tails_accessor)
+    }
+
+    #[test]
+    fn compact_revocation_registry_deltas_works() {
+        let issuer = Issuer::new();
+        let (mut rev_reg, rev_tails_accessor) = _rev_reg();
+
+        let deltas = vec![
+            issuer.revoke(&mut rev_reg, MAX_CRED_NUM, 1, &rev_tails_accessor).unwrap(),
+            issuer.revoke(&mut rev_reg, MAX_CRED_NUM, 2, &rev_tails_accessor).unwrap(),
+            issuer.recovery(&mut rev_reg, MAX_CRED_NUM, 1, &rev_tails_accessor).unwrap(),
+        ];
+
+        let compacted = Issuer::compact_revocation_registry_deltas(&deltas).unwrap();
+
+        let compacted_json = serde_json::to_value(&compacted).unwrap();
+        let first_json = serde_json::to_value(&deltas[0]).unwrap();
+        assert_eq!(first_json["prevAccum"], compacted_json["prevAccum"]);
+
+        let expected_rev_reg = serde_json::to_value(&rev_reg).unwrap();
+        let compacted_rev_reg = serde_json::to_value(&RevocationRegistry::from(compacted)).unwrap();
+        assert_eq!(expected_rev_reg, compacted_rev_reg);
+    }
+
+    #[test]
+    fn revoke_batch_works_like_sequential_revokes() {
+        let issuer = Issuer::new();
+        let (rev_reg, rev_tails_accessor) = _rev_reg();
+
+        let mut sequential_rev_reg = rev_reg.clone();
+        issuer.revoke(&mut sequential_rev_reg, MAX_CRED_NUM, 1, &rev_tails_accessor).unwrap();
+        issuer.revoke(&mut sequential_rev_reg, MAX_CRED_NUM, 3, &rev_tails_accessor).unwrap();
+
+        let mut batch_rev_reg = rev_reg.clone();
+        let rev_reg_delta = issuer.revoke_batch(&mut batch_rev_reg, MAX_CRED_NUM, &[1, 3], &rev_tails_accessor).unwrap();
+
+        assert_eq!(serde_json::to_value(&sequential_rev_reg).unwrap(), serde_json::to_value(&batch_rev_reg).unwrap());
+
+        let rev_reg_delta_json = serde_json::to_value(&rev_reg_delta).unwrap();
+        assert_eq!(serde_json::to_value(&rev_reg).unwrap()["accum"], rev_reg_delta_json["prevAccum"]);
+        assert_eq!(serde_json::to_value(&batch_rev_reg).unwrap()["accum"], rev_reg_delta_json["accum"]);
+
+        let revoked: HashSet<u32> = serde_json::from_value(rev_reg_delta_json["revoked"].clone()).unwrap();
+        assert_eq!(vec![1, 3].into_iter().collect::<HashSet<u32>>(), revoked);
+    }
+
+    #[test]
+    fn revoke_batch_fails_for_duplicates() {
+        let issuer = Issuer::new();
+        let (mut rev_reg, rev_tails_accessor) = _rev_reg();
+        let before = serde_json::to_value(&rev_reg).unwrap();
+
+        let res = issuer.revoke_batch(&mut rev_reg, MAX_CRED_NUM, &[1, 1], &rev_tails_accessor);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        assert_eq!(before, serde_json::to_value(&rev_reg).unwrap());
+    }
+
+    #[test]
+    fn new_credential_definition_fails_for_too_many_attributes() {
+        let attr_names: AttributeNames = (0..MAX_ATTRIBUTES_COUNT + 1).map(|i| format!("attr_{}", i)).collect();
+
+        let err = Issuer::new_credential_definition(&attr_names, false).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains(&format!("at most {}", MAX_ATTRIBUTES_COUNT)));
+    }
+
+    #[test]
+    fn new_credential_fails_for_schema_not_matching_cred_def() {
+        let (cred_def_data, cred_priv_key, key_correctness_proof) = Issuer::new_credential_definition(&_schema().attr_names, false).unwrap();
+        let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def_data.primary, None).unwrap();
+
+        let master_secret = CryptoProver::new_master_secret().unwrap();
+        let mut master_secret_values_builder = CryptoIssuer::new_credential_values_builder().unwrap();
+        master_secret_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+        let master_secret_values = master_secret_values_builder.finalize().unwrap();
+
+        let offer_nonce = new_nonce().unwrap();
+        let (blinded_ms, _, blinded_ms_correctness_proof) =
+            CryptoProver::blind_credential_secrets(&credential_pub_key, &key_correctness_proof, &master_secret_values, &offer_nonce).unwrap();
+
+        let cred_request = CredentialRequest {
+            prover_did: DidValue("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string()),
+            cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            blinded_ms,
+            blinded_ms_correctness_proof,
+            nonce: new_nonce().unwrap(),
+        };
+
+        let cred_def = CredentialDefinition {
+            id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            schema_id: _schema().id,
+            signature_type: SignatureType::CL,
+            tag: "tag".to_string(),
+            value: cred_def_data,
+        };
+
+        let cred_values = _attributes(&[("name", "Alex", "1139481716457488690172217916278103335"),
+                                        ("age", "28", "28"),
+                                        ("height", "175", "175")]);
+
+        let err = Issuer::new().new_credential::<SimpleTailsAccessor>(&cred_def, &cred_priv_key, &offer_nonce, &cred_request, &cred_values,
+                                                                      None, None, None, None, None).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("\"height\""));
+    }
+
+    #[test]
+    fn compact_revocation_registry_deltas_works_for_empty_list() {
+        let res = Issuer::compact_revocation_registry_deltas(&[]);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    fn _rev_reg_info() -> RevocationRegistryInfo {
+        RevocationRegistryInfo {
+            id: RevocationRegistryId("NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag:CL_ACCUM:TAG_1".to_string()),
+            curr_id: 0,
+            used_ids: HashSet::new(),
+            state: RegistryState::Active,
+        }
+    }
+
+    #[test]
+    fn allocate_revocation_index_works() {
+        let mut rev_reg_info = _rev_reg_info();
+
+        for i in 1..MAX_CRED_NUM {
+            assert_eq!(i, Issuer::allocate_revocation_index(&mut rev_reg_info, MAX_CRED_NUM, &IssuanceType::ISSUANCE_ON_DEMAND).unwrap());
+            assert_eq!(RegistryState::Active, rev_reg_info.state);
+        }
+
+        assert_eq!(MAX_CRED_NUM, Issuer::allocate_revocation_index(&mut rev_reg_info, MAX_CRED_NUM, &IssuanceType::ISSUANCE_ON_DEMAND).unwrap());
+        assert_eq!(RegistryState::Full, rev_reg_info.state);
+        assert_eq!(MAX_CRED_NUM as usize, rev_reg_info.used_ids.len());
+
+        let res = Issuer::allocate_revocation_index(&mut rev_reg_info, MAX_CRED_NUM, &IssuanceType::ISSUANCE_ON_DEMAND);
+        assert_kind!(IndyErrorKind::RevocationRegistryFull, res);
+    }
+
+    #[test]
+    fn allocate_revocation_index_works_for_rotated_registry() {
+        let mut rev_reg_info = _rev_reg_info();
+        Issuer::allocate_revocation_index(&mut rev_reg_info, MAX_CRED_NUM, &IssuanceType::ISSUANCE_BY_DEFAULT).unwrap();
+
+        rev_reg_info.state = RegistryState::Rotated;
+
+        let res = Issuer::allocate_revocation_index(&mut rev_reg_info, MAX_CRED_NUM, &IssuanceType::ISSUANCE_BY_DEFAULT);
+        assert_kind!(IndyErrorKind::InvalidState, res);
+        assert_eq!(1, rev_reg_info.curr_id);
+    }
+
+    #[test]
+    fn unused_indices_works_for_revoked_index() {
+        let mut rev_reg_info = _rev_reg_info();
+
+        for _ in 0..3 {
+            Issuer::allocate_revocation_index(&mut rev_reg_info, MAX_CRED_NUM, &IssuanceType::ISSUANCE_ON_DEMAND).unwrap();
+        }
+
+        // revocation of on-demand credential drops it from used ids
+        rev_reg_info.used_ids.remove(&2);
+
+        assert_eq!((4..=MAX_CRED_NUM).collect::<Vec<u32>>(), rev_reg_info.unused_indices(MAX_CRED_NUM));
+    }
+
+    #[test]
+    fn revocation_registry_snapshot_works() {
+        let snapshot = br#"{"rev_reg_info":{"curr_id":1}}"#;
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt("export_key", &KeyDerivationMethod::ARGON2I_INT);
+        let sealed = Issuer::seal_revocation_registry_snapshot(snapshot, &key_data).unwrap();
+        assert_eq!(snapshot.to_vec(), Issuer::open_revocation_registry_snapshot(&sealed, "export_key").unwrap());
+
+        let res = Issuer::open_revocation_registry_snapshot(&sealed, "other_key");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        let mut tampered = sealed.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        let res = Issuer::open_revocation_registry_snapshot(&tampered, "export_key");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        let res = Issuer::open_revocation_registry_snapshot(&sealed[..sealed.len() / 2], "export_key");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn revocation_registry_snapshot_works_for_raw_key() {
+        let snapshot = br#"{"rev_reg_info":{"curr_id":1}}"#;
+        let raw_key = "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw";
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(raw_key, &KeyDerivationMethod::RAW);
+        let sealed = Issuer::seal_revocation_registry_snapshot(snapshot, &key_data).unwrap();
+        assert_eq!(snapshot.to_vec(), Issuer::open_revocation_registry_snapshot(&sealed, raw_key).unwrap());
+
+        let res = Issuer::open_revocation_registry_snapshot(&sealed, "export_key");
+        assert!(res.is_err());
+    }
+
+    fn _schema() -> SchemaV1 {
+        let mut attr_names: HashSet<String> = HashSet::new();
+        attr_names.insert("name".to_string());
+        attr_names.insert("age".to_string());
+
+        SchemaV1 {
+            id: SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+            name: "gvt".to_string(),
+            version: "1.0".to_string(),
+            attr_names,
+            seq_no: None,
+            attribute_types: None,
+        }
+    }
+
+    fn _attributes(values: &[(&str, &str, &str)]) -> CredentialValues {
+        values
+            .iter()
+            .map(|&(attr, raw, encoded)| (attr.to_string(), AttributeValues { raw: raw.to_string(), encoded: encoded.to_string() }))
+            .collect()
+    }
+
+    fn _typed_schema() -> SchemaV1 {
+        let mut schema = _schema();
+        schema.attr_names.insert("birthdate".to_string());
+        schema.attr_names.insert("zip".to_string());
+
+        let mut attribute_types = HashMap::new();
+        attribute_types.insert("age".to_string(), AttributeType::Integer);
+        attribute_types.insert("birthdate".to_string(), AttributeType::Date);
+        attribute_types.insert("zip".to_string(), AttributeType::String);
+        schema.attribute_types = Some(attribute_types);
+
+        schema
+    }
+
+    fn _raw_values(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values.iter().map(|&(attr, raw)| (attr.to_string(), raw.to_string())).collect()
+    }
+
+    #[test]
+    fn encode_attributes_works_for_typed_schema() {
+        let raw_values = _raw_values(&[("name", "Alex"), ("age", "28"), ("birthdate", "2000-02-29"), ("zip", "12345")]);
+
+        let values = Issuer::encode_attributes(&_typed_schema(), &raw_values).unwrap();
+
+        assert_eq!(encode_attribute("Alex").unwrap(), values["name"].encoded);
+        assert_eq!("28", values["age"].encoded);
+        assert_eq!("11016", values["birthdate"].encoded);
+        assert_ne!("12345", values["zip"].encoded);
+        assert_eq!("12345", values["zip"].raw);
+
+        assert!(Issuer::validate_attributes_for_schema(&_typed_schema(), &values).is_empty());
+    }
+
+    #[test]
+    fn encode_attributes_fails_for_value_not_matching_type() {
+        let raw_values = _raw_values(&[("name", "Alex"), ("age", "twenty"), ("birthdate", "2000-02-29"), ("zip", "12345")]);
+
+        let res = Issuer::encode_attributes(&_typed_schema(), &raw_values);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn validate_attributes_for_schema_works() {
+        let attributes = _attributes(&[("name", "Alex", "1139481716457488690172217916278103335"), ("Age", "28", "28")]);
+        assert!(Issuer::validate_attributes_for_schema(&_schema(), &attributes).is_empty());
+    }
+
+    #[test]
+    fn validate_attributes_for_schema_works_for_missing_and_extra_attributes() {
+        let attributes = _attributes(&[("name", "Alex", "1139481716457488690172217916278103335"), ("height", "175", "175")]);
+
+        let problems = Issuer::validate_attributes_for_schema(&_schema(), &attributes);
+        assert_eq!(vec![
+            AttributeProblem { attr: "age".to_string(), problem: AttributeProblemKind::Missing },
+            AttributeProblem { attr: "height".to_string(), problem: AttributeProblemKind::NotInSchema },
+        ], problems);
+    }
+
+    #[test]
+    fn validate_attributes_for_schema_works_for_invalid_encoded_value() {
+        let attributes = _attributes(&[("name", "Alex", "Alex"), ("age", "28", "28")]);
+
+        let problems = Issuer::validate_attributes_for_schema(&_schema(), &attributes);
+        assert_eq!(vec![AttributeProblem { attr: "name".to_string(), problem: AttributeProblemKind::InvalidEncoding }], problems);
+    }
+
+    #[test]
+    fn validate_attributes_for_schema_works_for_value_not_matching_attribute_type() {
+        let mut schema = _schema();
+        let mut attribute_types = HashMap::new();
+        attribute_types.insert("age".to_string(), AttributeType::String);
+        schema.attribute_types = Some(attribute_types);
+
+        let attributes = _attributes(&[("name", "Alex", "1139481716457488690172217916278103335"), ("age", "28", "28")]);
+
+        let problems = Issuer::validate_attributes_for_schema(&schema, &attributes);
+        assert_eq!(vec![AttributeProblem { attr: "age".to_string(), problem: AttributeProblemKind::InvalidEncoding }], problems);
+    }
 }
@@ -1,5 +1,9 @@
+use std::cell::RefCell;
+#[cfg(test)]
+use std::cell::Cell;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
 
 use ursa::cl::{
     BlindedCredentialSecrets,
@@ -7,35 +11,149 @@ use ursa::cl::{
     CredentialPublicKey,
     CredentialSecretsBlindingFactors,
     MasterSecret,
+    RevocationRegistry,
+    RevocationRegistryDelta,
+    RevocationTailsAccessor,
     SubProofRequest,
+    Witness,
 };
 use ursa::cl::issuer::Issuer as CryptoIssuer;
 use ursa::cl::prover::Prover as CryptoProver;
 use ursa::cl::verifier::Verifier as CryptoVerifier;
+use ursa::pair::PointG2;
 
 use domain::anoncreds::credential::{AttributeValues, Credential};
 use domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
 use domain::anoncreds::credential_definition::{CredentialDefinitionV1 as CredentialDefinition, CredentialDefinitionId};
 use domain::anoncreds::credential_offer::CredentialOffer;
 use domain::anoncreds::credential_request::CredentialRequestMetadata;
-use domain::anoncreds::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo, SubProofReferent};
+use domain::anoncreds::proof::{DisclosurePreview, Identifier, PredicatePreview, Proof, RequestedProof, RevealedAttributeInfo, RevealedAttributePreview, SubProofReferent, UnrevealedAttributePreview};
 use domain::anoncreds::proof_request::{PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload, ProofRequestsVersion, RequestedAttributeInfo, RequestedPredicateInfo, ProofRequestExtraQuery};
 use domain::anoncreds::requested_credential::ProvingCredentialKey;
-use domain::anoncreds::requested_credential::RequestedCredentials;
+use domain::anoncreds::requested_credential::{RequestedAttribute, RequestedCredentials};
 use domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
+use domain::anoncreds::revocation_registry_delta::RevocationRegistryDeltaV1;
 use domain::anoncreds::revocation_state::RevocationState;
-use domain::anoncreds::schema::{SchemaV1, SchemaId};
+use domain::anoncreds::schema::{SchemaV1, SchemaId, SchemaVersionRange};
 use errors::prelude::*;
 use services::anoncreds::helpers::*;
+use services::anoncreds::issuer::Issuer;
 use utils::wql::Query;
 
 const ATTRIBUTE_EXISTENCE_MARKER: &str = "1";
 
-pub struct Prover {}
+pub const DEFAULT_WITNESS_CACHE_SIZE: usize = 100;
+
+lazy_static! {
+    static ref WITNESS_CACHE_SIZE: Mutex<usize> = Mutex::new(DEFAULT_WITNESS_CACHE_SIZE);
+}
+
+pub fn set_witness_cache_size(size: usize) {
+    *WITNESS_CACHE_SIZE.lock().unwrap() = size;
+}
+
+fn witness_cache_size() -> usize {
+    *WITNESS_CACHE_SIZE.lock().unwrap()
+}
+
+type WitnessCacheKey = (RevocationRegistryId, u32);
+
+/// Witnesses of credentials keyed by revocation registry and revocation index.
+/// Every witness is stored with the digest of the registry entry it was built for,
+/// so only one registry state per credential is kept, and with the accumulator timestamp
+/// of the revocation state it was built for. Witnesses updated by deltas have no entry digest:
+/// they are kept for their timestamp but never reused for a registry entry.
+struct WitnessCache {
+    witnesses: HashMap<WitnessCacheKey, CachedWitness>,
+    order: VecDeque<WitnessCacheKey>,
+}
+
+struct CachedWitness {
+    entry_digest: Option<String>,
+    witness: Witness,
+    timestamp: u64,
+}
+
+impl WitnessCache {
+    fn new() -> WitnessCache {
+        WitnessCache {
+            witnesses: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    // The same registry entry is still current at a later timestamp, so a hit moves the witness to it
+    fn get(&mut self, key: &WitnessCacheKey, entry_digest: &str, timestamp: u64) -> Option<Witness> {
+        let cached = match self.witnesses.get_mut(key) {
+            Some(cached) => cached,
+            None => return None
+        };
+
+        if cached.entry_digest.as_ref().map(String::as_str) != Some(entry_digest) {
+            return None;
+        }
+
+        if cached.timestamp < timestamp {
+            cached.timestamp = timestamp;
+        }
+
+        Some(cached.witness.clone())
+    }
+
+    // Evicts the oldest witnesses when the cache is full
+    fn insert(&mut self, key: WitnessCacheKey, entry_digest: Option<String>, witness: Witness, timestamp: u64, capacity: usize) {
+        self.remove(&key);
+
+        if capacity == 0 {
+            return;
+        }
+
+        while self.order.len() >= capacity {
+            match self.order.pop_front() {
+                Some(oldest) => { self.witnesses.remove(&oldest); }
+                None => break
+            }
+        }
+
+        self.order.push_back(key.clone());
+        self.witnesses.insert(key, CachedWitness { entry_digest, witness, timestamp });
+    }
+
+    fn remove(&mut self, key: &WitnessCacheKey) {
+        if self.witnesses.remove(key).is_some() {
+            self.order.retain(|cached| cached != key);
+        }
+    }
+
+    fn latest_timestamp(&self, rev_reg_def_id: &RevocationRegistryId) -> Option<u64> {
+        let rev_reg_def_id = rev_reg_def_id.to_unqualified();
+
+        self.witnesses.iter()
+            .filter(|&(&(ref id, _), _)| id.to_unqualified() == rev_reg_def_id)
+            .map(|(_, cached)| cached.timestamp)
+            .max()
+    }
+
+    fn clear(&mut self) {
+        self.witnesses.clear();
+        self.order.clear();
+    }
+}
+
+pub struct Prover {
+    witness_cache: RefCell<WitnessCache>,
+    // number of attribute values checked against predicates, lets tests see whether planning used tags
+    #[cfg(test)]
+    pub predicate_value_checks: Cell<usize>,
+}
 
 impl Prover {
     pub fn new() -> Prover {
-        Prover {}
+        Prover {
+            witness_cache: RefCell::new(WitnessCache::new()),
+            #[cfg(test)]
+            predicate_value_checks: Cell::new(0),
+        }
     }
 
     pub fn new_master_secret(&self) -> IndyResult<MasterSecret> {
@@ -81,7 +199,7 @@ impl Prover {
                               cred_def: &CredentialDefinition,
                               rev_reg_def: Option<&RevocationRegistryDefinitionV1>) -> IndyResult<()> {
         trace!("process_credential >>> credential: {:?}, cred_request_metadata: {:?}, master_secret: {:?}, cred_def: {:?}, rev_reg_def: {:?}",
-               credential, cred_request_metadata, secret!(&master_secret), cred_def, rev_reg_def);
+               sensitive!(&credential), cred_request_metadata, secret!(&master_secret), cred_def, rev_reg_def);
 
         let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
         let credential_values = build_credential_values(&credential.values, Some(master_secret))?;
@@ -94,7 +212,12 @@ impl Prover {
                                                    &cred_request_metadata.nonce,
                                                    rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
                                                    credential.rev_reg.as_ref(),
-                                                   credential.witness.as_ref())?;
+                                                   credential.witness.as_ref())
+            .map_err(IndyError::from)
+            .map_err(|err| match err.kind() {
+                IndyErrorKind::InvalidStructure => err.map(IndyErrorKind::InvalidCredentialSignature, "Credential signature or its correctness proof is invalid"),
+                _ => err
+            })?;
 
         trace!("process_credential <<< ");
 
@@ -108,9 +231,10 @@ impl Prover {
                         master_secret: &MasterSecret,
                         schemas: &HashMap<SchemaId, SchemaV1>,
                         cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinition>,
-                        rev_states: &HashMap<RevocationRegistryId, HashMap<u64, RevocationState>>) -> IndyResult<Proof> {
+                        rev_states: &HashMap<RevocationRegistryId, HashMap<u64, RevocationState>>,
+                        is_cancelled: &dyn Fn() -> bool) -> IndyResult<Proof> {
         trace!("create_proof >>> credentials: {:?}, proof_req: {:?}, requested_credentials: {:?}, master_secret: {:?}, schemas: {:?}, cred_defs: {:?}, rev_states: {:?}",
-               credentials, proof_req, requested_credentials, secret!(&master_secret), schemas, cred_defs, rev_states);
+               sensitive!(&credentials), proof_req, sensitive!(&requested_credentials), secret!(&master_secret), schemas, cred_defs, rev_states);
 
         let proof_req_val = proof_req.value();
         let mut proof_builder = CryptoProver::new_proof_builder()?;
@@ -126,6 +250,8 @@ impl Prover {
 
         let mut identifiers: Vec<Identifier> = Vec::with_capacity(credentials_for_proving.len());
         for (cred_key, (req_attrs_for_cred, req_predicates_for_cred)) in credentials_for_proving {
+            Prover::_check_cancelled(is_cancelled)?;
+
             let credential: &Credential = credentials.get(cred_key.cred_id.as_str())
                 .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id: {:?}", cred_key.cred_id)))?;
 
@@ -147,6 +273,23 @@ impl Prover {
                     .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationInfo not found by timestamp: {:?}", timestamp)))?)
             } else { None };
 
+            let max_bits = predicate_max_bits();
+
+            for predicate in req_predicates_for_cred.iter() {
+                check_predicate_attribute_type(schema, &predicate.predicate_info)?;
+
+                let attr_name = &predicate.predicate_info.name;
+
+                let attr_values = self.get_credential_values_for_attribute(&credential.values, attr_name)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential value not found for attribute {:?}", attr_name)))?;
+
+                check_attribute_bit_length(attr_name, &attr_values.encoded, max_bits)?;
+                // bounds are i32, so this only rejects them when `predicate_max_bits` is configured below 32
+                for (_, p_value) in Prover::_predicate_constraints(&predicate.predicate_info)? {
+                    check_predicate_bit_length(attr_name, &p_value.to_string(), max_bits)?;
+                }
+            }
+
             let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
 
             let credential_schema = build_credential_schema(&schema.attr_names)?;
@@ -193,22 +336,245 @@ impl Prover {
             sub_proof_index += 1;
         }
 
+        proof_req_val.check_allowed_values(&requested_proof)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        Prover::_check_cancelled(is_cancelled)?;
+
         let proof = proof_builder.finalize(&proof_req_val.nonce)?;
 
         let full_proof = Proof {
             proof,
             requested_proof,
             identifiers,
+            nonce: Some(proof_req_val.nonce.to_dec()?),
         };
 
-        trace!("create_proof <<< full_proof: {:?}", full_proof);
+        trace!("create_proof <<< full_proof: {:?}", sensitive!(&full_proof));
 
         Ok(full_proof)
     }
 
+    /// Folds ordered run of published deltas into the witness of credential with `rev_idx`.
+    /// Returns revocation registry (accumulator) the updated witness corresponds to.
+    pub fn update_witness<RTA>(witness: &mut Witness,
+                               rev_reg: &RevocationRegistry,
+                               rev_idx: u32,
+                               max_cred_num: u32,
+                               rev_reg_deltas: &[RevocationRegistryDelta],
+                               rev_tails_accessor: &RTA) -> IndyResult<RevocationRegistry> where RTA: RevocationTailsAccessor {
+        trace!("update_witness >>> witness: {:?}, rev_reg: {:?}, rev_idx: {:?}, max_cred_num: {:?}, rev_reg_deltas: {:?}",
+               witness, rev_reg, secret!(&rev_idx), max_cred_num, rev_reg_deltas);
+
+        Prover::_check_deltas_chain(rev_reg, rev_reg_deltas)?;
+
+        let rev_reg_delta = Issuer::compact_revocation_registry_deltas(rev_reg_deltas)?;
+
+        witness.update(rev_idx, max_cred_num, &rev_reg_delta, rev_tails_accessor)?;
+
+        let rev_reg = RevocationRegistry::from(rev_reg_delta);
+
+        trace!("update_witness <<< rev_reg: {:?}", rev_reg);
+
+        Ok(rev_reg)
+    }
+
+    /// Witness of credential with `rev_idx` for the registry state `rev_reg_delta` ends with,
+    /// the state of the ledger at `timestamp`.
+    /// Repeated calls for an unchanged registry state reuse the cached witness instead of reading tails again;
+    /// a delta ending with another state replaces the cached witness of the credential.
+    pub fn new_witness<RTA>(&self,
+                            rev_reg_def_id: &RevocationRegistryId,
+                            rev_idx: u32,
+                            max_cred_num: u32,
+                            issuance_by_default: bool,
+                            rev_reg_delta: &RevocationRegistryDeltaV1,
+                            timestamp: u64,
+                            rev_tails_accessor: &RTA) -> IndyResult<Witness> where RTA: RevocationTailsAccessor {
+        trace!("new_witness >>> rev_reg_def_id: {:?}, rev_idx: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, rev_reg_delta: {:?}, timestamp: {:?}",
+               rev_reg_def_id, secret!(&rev_idx), max_cred_num, issuance_by_default, rev_reg_delta, timestamp);
+
+        let key = (rev_reg_def_id.clone(), rev_idx);
+
+        let entry_digest = rev_reg_delta.entry_digest()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        if let Some(witness) = self.witness_cache.borrow_mut().get(&key, &entry_digest, timestamp) {
+            trace!("new_witness <<< cached witness: {:?}", secret!(&witness));
+            return Ok(witness);
+        }
+
+        let witness = Witness::new(rev_idx, max_cred_num, issuance_by_default, &rev_reg_delta.value, rev_tails_accessor)?;
+
+        self.witness_cache.borrow_mut().insert(key, Some(entry_digest), witness.clone(), timestamp, witness_cache_size());
+
+        trace!("new_witness <<< witness: {:?}", secret!(&witness));
+
+        Ok(witness)
+    }
+
+    /// Replaces the cached witness of credential with `rev_idx` by the witness updated to the ledger state at `timestamp`.
+    pub fn cache_updated_witness(&self, rev_reg_def_id: &RevocationRegistryId, rev_idx: u32, witness: &Witness, timestamp: u64) {
+        self.witness_cache.borrow_mut().insert((rev_reg_def_id.clone(), rev_idx), None, witness.clone(), timestamp, witness_cache_size());
+    }
+
+    /// Latest accumulator timestamp non-revocation can be proven for with the cached witnesses of the registry,
+    /// None if no witness of the registry is cached.
+    pub fn max_provable_timestamp(&self, rev_reg_def_id: &RevocationRegistryId) -> Option<u64> {
+        self.witness_cache.borrow().latest_timestamp(rev_reg_def_id)
+    }
+
+    pub fn clear_witness_cache(&self) {
+        self.witness_cache.borrow_mut().clear();
+    }
+
+    /// Tails indices the witness omega of credential with `rev_idx` is built from,
+    /// so a prover can fetch only these tails instead of the whole file.
+    /// Mirrors witness construction: `max_cred_num + 1 - j + rev_idx` for every issued `j != rev_idx`.
+    pub fn witness_dependent_indices(rev_idx: u32,
+                                     max_cred_num: u32,
+                                     issuance_by_default: bool,
+                                     rev_reg_delta: &RevocationRegistryDelta) -> IndyResult<HashSet<u32>> {
+        trace!("witness_dependent_indices >>> rev_idx: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, rev_reg_delta: {:?}",
+               secret!(&rev_idx), max_cred_num, issuance_by_default, rev_reg_delta);
+
+        let rev_reg_delta = serde_json::to_value(rev_reg_delta)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        let res: HashSet<u32> = Prover::_issued_indices(max_cred_num, issuance_by_default, &rev_reg_delta)?
+            .into_iter()
+            .filter(|j| *j != rev_idx && *j <= max_cred_num)
+            .map(|j| max_cred_num + 1 - j + rev_idx)
+            .collect();
+
+        trace!("witness_dependent_indices <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    /// Whether credential with `rev_idx` is revoked as of the accumulator `rev_reg_delta` ends with.
+    /// The delta has to cover the registry from its creation (no previous accumulator), as returned by the ledger
+    /// for a request without `from`. Its issued and revoked indices are checked against its accumulator
+    /// recomputed from tails, so a delta that doesn't match the registry is rejected instead of trusted.
+    /// An index never issued in a registry with issuance on demand is an error, not a revoked credential.
+    pub fn is_revoked<RTA>(rev_idx: u32,
+                           max_cred_num: u32,
+                           issuance_by_default: bool,
+                           rev_reg_delta: &RevocationRegistryDelta,
+                           rev_tails_accessor: &RTA) -> IndyResult<bool> where RTA: RevocationTailsAccessor {
+        trace!("is_revoked >>> rev_idx: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, rev_reg_delta: {:?}",
+               rev_idx, max_cred_num, issuance_by_default, rev_reg_delta);
+
+        if rev_idx == 0 || rev_idx > max_cred_num {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Credential revocation id {} is out of revocation registry range 1..{}", rev_idx, max_cred_num)));
+        }
+
+        let rev_reg_delta = serde_json::to_value(rev_reg_delta)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        if rev_reg_delta.get("prevAccum").map(|prev_accum| !prev_accum.is_null()).unwrap_or(false) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               "Revocation status can be determined only from RevocationRegistryDelta starting at registry creation"));
+        }
+
+        let issued = Prover::_issued_indices(max_cred_num, issuance_by_default, &rev_reg_delta)?;
+
+        Prover::_check_accumulator(max_cred_num, &issued, &rev_reg_delta["accum"], rev_tails_accessor)?;
+
+        if !issuance_by_default && !issued.contains(&rev_idx) && !Prover::_delta_indices(&rev_reg_delta, "revoked")?.contains(&rev_idx) {
+            return Err(err_msg(IndyErrorKind::InvalidUserRevocId,
+                               format!("Credential revocation id {} has not been issued", rev_idx)));
+        }
+
+        let res = !issued.contains(&rev_idx);
+
+        trace!("is_revoked <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Indices accumulated in the registry as of the serialized delta
+    fn _issued_indices(max_cred_num: u32,
+                       issuance_by_default: bool,
+                       rev_reg_delta: &serde_json::Value) -> IndyResult<HashSet<u32>> {
+        if issuance_by_default {
+            let revoked = Prover::_delta_indices(rev_reg_delta, "revoked")?;
+            Ok((1..max_cred_num + 1).filter(|j| !revoked.contains(j)).collect())
+        } else {
+            Prover::_delta_indices(rev_reg_delta, "issued")
+        }
+    }
+
+    fn _delta_indices(rev_reg_delta: &serde_json::Value, name: &str) -> IndyResult<HashSet<u32>> {
+        match rev_reg_delta.get(name) {
+            Some(indices) if !indices.is_null() => serde_json::from_value(indices.clone())
+                .to_indy(IndyErrorKind::InvalidStructure, format!("Invalid \"{}\" indices of RevocationRegistryDelta", name)),
+            _ => Ok(HashSet::new())
+        }
+    }
+
+    // Accumulator is the sum of tails `max_cred_num + 1 - j` of all accumulated indices `j`
+    fn _check_accumulator<RTA>(max_cred_num: u32,
+                               issued: &HashSet<u32>,
+                               accum: &serde_json::Value,
+                               rev_tails_accessor: &RTA) -> IndyResult<()> where RTA: RevocationTailsAccessor {
+        let accum: PointG2 = serde_json::from_value(accum.clone())
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid accumulator of RevocationRegistryDelta")?;
+
+        let mut expected = PointG2::new_inf()?;
+
+        for j in issued.iter().filter(|j| **j <= max_cred_num) {
+            let mut sum = None;
+            rev_tails_accessor.access_tail(max_cred_num + 1 - j, &mut |tail| sum = Some(expected.add(tail)))?;
+
+            expected = sum.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Tail not found"))??;
+        }
+
+        if accum != expected {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               "Issued and revoked indices of RevocationRegistryDelta don't match its accumulator"));
+        }
+
+        Ok(())
+    }
+
+    // Every delta must start from the accumulator the previous one (or the current registry) ends with
+    fn _check_deltas_chain(rev_reg: &RevocationRegistry, rev_reg_deltas: &[RevocationRegistryDelta]) -> IndyResult<()> {
+        let mut accum = serde_json::to_value(rev_reg)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?["accum"].clone();
+
+        for (i, rev_reg_delta) in rev_reg_deltas.iter().enumerate() {
+            let rev_reg_delta = serde_json::to_value(rev_reg_delta)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+            match rev_reg_delta.get("prevAccum").filter(|prev_accum| !prev_accum.is_null()) {
+                Some(prev_accum) if *prev_accum != accum =>
+                    return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Revocation registry delta {} doesn't continue previous accumulator", i))),
+                None if i > 0 =>
+                    return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Revocation registry delta {} has no previous accumulator", i))),
+                _ => {}
+            }
+
+            accum = rev_reg_delta["accum"].clone();
+        }
+
+        Ok(())
+    }
+
+    fn _check_cancelled(is_cancelled: &dyn Fn() -> bool) -> IndyResult<()> {
+        if is_cancelled() {
+            return Err(err_msg(IndyErrorKind::Cancelled, "Proof creation has been cancelled"));
+        }
+        Ok(())
+    }
+
     pub fn _prepare_credentials_for_proving(requested_credentials: &RequestedCredentials,
                                             proof_req: &ProofRequestPayload) -> IndyResult<HashMap<ProvingCredentialKey, (Vec<RequestedAttributeInfo>, Vec<RequestedPredicateInfo>)>> {
-        trace!("_prepare_credentials_for_proving >>> requested_credentials: {:?}, proof_req: {:?}", requested_credentials, proof_req);
+        trace!("_prepare_credentials_for_proving >>> requested_credentials: {:?}, proof_req: {:?}", sensitive!(&requested_credentials), proof_req);
+
+        Prover::_check_attribute_name_collisions(requested_credentials, proof_req)?;
+        Prover::_check_linked_reveal(requested_credentials, proof_req)?;
 
         let mut credentials_for_proving: HashMap<ProvingCredentialKey, (Vec<RequestedAttributeInfo>, Vec<RequestedPredicateInfo>)> = HashMap::new();
 
@@ -255,26 +621,213 @@ impl Prover {
             };
         }
 
-        trace!("_prepare_credentials_for_proving <<< credentials_for_proving: {:?}", credentials_for_proving);
+        trace!("_prepare_credentials_for_proving <<< credentials_for_proving: {:?}", sensitive!(&credentials_for_proving));
 
         Ok(credentials_for_proving)
     }
 
+    // Attributes with the same name taken from different credentials are conflated by verifier
+    // if proof request binds both referents to the same restrictions. Referents without restrictions
+    // accept any credential, so there is nothing to disambiguate.
+    fn _check_attribute_name_collisions(requested_credentials: &RequestedCredentials,
+                                        proof_req: &ProofRequestPayload) -> IndyResult<()> {
+        let mut attrs_by_name: HashMap<String, Vec<(&str, &str, Option<Query>)>> = HashMap::new();
+
+        for (attr_referent, requested_attr) in requested_credentials.requested_attributes.iter() {
+            if let Some(attr_info) = proof_req.requested_attributes.get(attr_referent.as_str()) {
+                attrs_by_name
+                    .entry(attr_common_view(&attr_info.name))
+                    .or_insert_with(Vec::new)
+                    .push((attr_referent.as_str(), requested_attr.cred_id.as_str(),
+                           attr_info.restrictions.as_ref().map(Prover::_canonical_restrictions)));
+            }
+        }
+
+        for (name, attrs) in attrs_by_name {
+            for (i, &(referent, cred_id, ref restrictions)) in attrs.iter().enumerate() {
+                for &(other_referent, other_cred_id, ref other_restrictions) in attrs[i + 1..].iter() {
+                    let conflicting = match (restrictions, other_restrictions) {
+                        (&Some(ref restrictions), &Some(ref other_restrictions)) => restrictions == other_restrictions,
+                        _ => false
+                    };
+
+                    if cred_id != other_cred_id && conflicting {
+                        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                           format!("Attribute \"{}\" is requested from different credentials for referents \"{}\" and \"{}\" \
+                                                   bound to the same restrictions", name, referent, other_referent)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Restrictions in canonical form: nested conjunctions and disjunctions are flattened,
+    // their operands and `$in` values are sorted and deduplicated.
+    fn _canonical_restrictions(query: &Query) -> Query {
+        match *query {
+            Query::And(ref operands) => {
+                let mut operands = Prover::_canonical_operands(operands, true);
+                if operands.len() == 1 { operands.remove(0) } else { Query::And(operands) }
+            }
+            Query::Or(ref operands) => {
+                let mut operands = Prover::_canonical_operands(operands, false);
+                if operands.len() == 1 { operands.remove(0) } else { Query::Or(operands) }
+            }
+            Query::Not(ref operand) => Query::Not(Box::new(Prover::_canonical_restrictions(operand))),
+            Query::In(ref name, ref values) => {
+                let mut values = values.clone();
+                values.sort();
+                values.dedup();
+                Query::In(name.clone(), values)
+            }
+            ref query => query.clone()
+        }
+    }
+
+    fn _canonical_operands(operands: &[Query], conjunction: bool) -> Vec<Query> {
+        let mut canonical: Vec<Query> = Vec::new();
+
+        for operand in operands.iter().map(Prover::_canonical_restrictions) {
+            match (conjunction, operand) {
+                (true, Query::And(nested)) | (false, Query::Or(nested)) => canonical.extend(nested),
+                (_, operand) => canonical.push(operand)
+            }
+        }
+
+        canonical.sort_by_key(|operand| operand.to_string());
+        canonical.dedup();
+        canonical
+    }
+
+    // Members of a `linked_reveal` group end up in the same sub proof request, so they have to be
+    // requested from the same credential and be either all revealed or all unrevealed.
+    fn _check_linked_reveal(requested_credentials: &RequestedCredentials,
+                            proof_req: &ProofRequestPayload) -> IndyResult<()> {
+        for (group, referents) in proof_req.linked_reveal.iter().flatten() {
+            let mut group_attr: Option<&RequestedAttribute> = None;
+
+            for referent in referents {
+                let requested_attr = requested_credentials.requested_attributes.get(referent)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure,
+                                           format!("Referent \"{}\" from `linked_reveal` group \"{}\" must be proven by a credential", referent, group)))?;
+
+                match group_attr {
+                    Some(group_attr) if group_attr.cred_id != requested_attr.cred_id || group_attr.timestamp != requested_attr.timestamp =>
+                        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                           format!("Referents from `linked_reveal` group \"{}\" must be proven by the same credential", group))),
+                    Some(group_attr) if group_attr.revealed != requested_attr.revealed =>
+                        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                           format!("Referents from `linked_reveal` group \"{}\" must be all revealed or all unrevealed", group))),
+                    Some(_) => {}
+                    None => group_attr = Some(requested_attr)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn preview_disclosure(&self,
+                              credentials: &HashMap<String, Credential>,
+                              proof_req: &ProofRequestPayload,
+                              requested_credentials: &RequestedCredentials) -> IndyResult<DisclosurePreview> {
+        trace!("preview_disclosure >>> credentials: {:?}, proof_req: {:?}, requested_credentials: {:?}",
+               sensitive!(&credentials), proof_req, sensitive!(&requested_credentials));
+
+        let mut preview = DisclosurePreview::default();
+
+        for (attr_referent, requested_attr) in requested_credentials.requested_attributes.iter() {
+            let attr_info = proof_req.requested_attributes.get(attr_referent)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("AttributeInfo not found in ProofRequest for referent \"{}\"", attr_referent)))?;
+
+            if requested_attr.revealed {
+                let credential = credentials.get(&requested_attr.cred_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id {:?}", requested_attr.cred_id)))?;
+
+                let values = self.get_credential_values_for_attribute(&credential.values, &attr_info.name)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential value not found for attribute {:?}", attr_info.name)))?;
+
+                preview.revealed_attrs.insert(attr_referent.clone(), RevealedAttributePreview {
+                    cred_id: requested_attr.cred_id.clone(),
+                    name: attr_info.name.clone(),
+                    raw: values.raw,
+                });
+            } else {
+                preview.unrevealed_attrs.insert(attr_referent.clone(), UnrevealedAttributePreview {
+                    cred_id: requested_attr.cred_id.clone(),
+                    name: attr_info.name.clone(),
+                });
+            }
+        }
+
+        for (attr_referent, value) in requested_credentials.self_attested_attributes.iter() {
+            preview.self_attested_attrs.insert(attr_referent.clone(), value.clone());
+        }
+
+        for (predicate_referent, proving_cred_key) in requested_credentials.requested_predicates.iter() {
+            let predicate_info = proof_req.requested_predicates.get(predicate_referent)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("PredicateInfo not found in ProofRequest for referent \"{}\"", predicate_referent)))?;
+
+            let credential = credentials.get(&proving_cred_key.cred_id)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id {:?}", proving_cred_key.cred_id)))?;
+
+            let values = self.get_credential_values_for_attribute(&credential.values, &predicate_info.name)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential value not found for attribute {:?}", predicate_info.name)))?;
+
+            if !self.attribute_satisfy_predicate(predicate_info, &values.encoded)? {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Credential {:?} doesn't satisfy predicate for referent \"{}\"", proving_cred_key.cred_id, predicate_referent)));
+            }
+
+            preview.predicates.insert(predicate_referent.clone(), PredicatePreview {
+                cred_id: proving_cred_key.cred_id.clone(),
+                name: predicate_info.name.clone(),
+                p_type: predicate_info.p_type.clone(),
+                p_value: predicate_info.p_value,
+                p_value_max: predicate_info.p_value_max,
+                scale: predicate_info.scale,
+            });
+        }
+
+        trace!("preview_disclosure <<< preview: {:?}", sensitive!(&preview));
+
+        Ok(preview)
+    }
+
     pub fn get_credential_values_for_attribute(&self, credential_attrs: &HashMap<String, AttributeValues>,
                                                requested_attr: &str) -> Option<AttributeValues> {
-        trace!("get_credential_values_for_attribute >>> credential_attrs: {:?}, requested_attr: {:?}", credential_attrs, requested_attr);
+        trace!("get_credential_values_for_attribute >>> credential_attrs: {:?}, requested_attr: {:?}", RedactedAttributes(credential_attrs), requested_attr);
 
         let res = credential_attrs.iter()
             .find(|&(ref key, _)| attr_common_view(key) == attr_common_view(&requested_attr))
             .map(|(_, values)| values.clone());
 
-        trace!("get_credential_values_for_attribute <<< res: {:?}", res);
+        trace!("get_credential_values_for_attribute <<< res: {:?}", sensitive!(&res));
 
         res
     }
 
+    /// Stable identifier of the credential: hex encoded SHA-256 of the canonical json
+    /// of its identifiers, values and primary signature. Witness and registry state are excluded,
+    /// so the fingerprint doesn't change when the same credential is stored again or updated.
+    pub fn credential_fingerprint(credential: &Credential) -> IndyResult<String> {
+        let signature = serde_json::to_value(&credential.signature)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialSignature")?;
+
+        let canonical = ::utils::canonical_json::to_string_from_value(json!({
+            "schema_id": credential.schema_id,
+            "cred_def_id": credential.cred_def_id,
+            "values": credential.values,
+            "p_credential": signature["p_credential"],
+        })).to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        Ok(hex::encode(::utils::crypto::hash::hash(canonical.as_bytes())?))
+    }
+
     pub fn build_credential_tags(&self, credential: &Credential, catpol: Option<&CredentialAttrTagPolicy>) -> IndyResult<HashMap<String, String>> {
-        trace!("build_credential_tags >>> credential: {:?}, catpol: {:?}", credential, catpol);
+        trace!("build_credential_tags >>> credential: {:?}, catpol: {:?}", sensitive!(&credential), catpol);
 
         let mut res: HashMap<String, String> = HashMap::new();
 
@@ -291,6 +844,7 @@ impl Prover {
         res.insert("issuer_did".to_string(), issuer_did.0.to_string());
         res.insert("cred_def_id".to_string(), credential.cred_def_id.0.to_string());
         res.insert("rev_reg_id".to_string(), credential.rev_reg_id.as_ref().map(|rev_reg_id| rev_reg_id.0.clone()).unwrap_or_else(|| "None".to_string()));
+        res.insert(Credential::TAGS_VERSION_TAG.to_string(), Credential::TAGS_VERSION.to_string());
 
         if credential.cred_def_id.is_fully_qualified() {
             res.insert(Credential::add_extra_tag_suffix("schema_id"), credential.schema_id.to_unqualified().0);
@@ -307,42 +861,149 @@ impl Prover {
                     // abstain for attrs policy marks untaggable
                     res.insert(format!("attr::{}::marker", attr_common_view(&attr)), ATTRIBUTE_EXISTENCE_MARKER.to_string());
                     res.insert(format!("attr::{}::value", attr_common_view(&attr)), values.raw.clone());
+
+                    // plain tags are not encrypted, so only attrs policy marks range queryable expose their integer values
+                    if catpol.map(|cp| cp.is_range_queryable(attr.as_str())).unwrap_or(false) {
+                        if let Ok(value) = values.raw.parse::<i32>() {
+                            res.insert(Prover::_numeric_tag_name(&attr), Prover::_numeric_tag_value(value));
+                        }
+                    }
                 }
             });
 
-        trace!("build_credential_tags <<< res: {:?}", res);
+        trace!("build_credential_tags <<< res: {:?}", sensitive!(&res));
 
         Ok(res)
     }
 
-    pub fn attribute_satisfy_predicate(&self,
-                                       predicate: &PredicateInfo,
-                                       attribute_value: &str) -> IndyResult<bool> {
-        trace!("attribute_satisfy_predicate >>> predicate: {:?}, attribute_value: {:?}", predicate, attribute_value);
+    pub fn transform_credential_query(&self, query: Query) -> IndyResult<Query> {
+        trace!("transform_credential_query >>> query: {:?}", query);
 
-        let res = match predicate.p_type {
-            PredicateTypes::GE => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value >= predicate.p_value)
-            }
-            PredicateTypes::GT => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value > predicate.p_value)
+        let res = match query {
+            Query::Gt(tag_name, tag_value) => self._transform_numeric_operator(tag_name, tag_value, Query::Gt)?,
+            Query::Gte(tag_name, tag_value) => self._transform_numeric_operator(tag_name, tag_value, Query::Gte)?,
+            Query::Lt(tag_name, tag_value) => self._transform_numeric_operator(tag_name, tag_value, Query::Lt)?,
+            Query::Lte(tag_name, tag_value) => self._transform_numeric_operator(tag_name, tag_value, Query::Lte)?,
+            Query::And(operators) => {
+                Query::And(
+                    operators
+                        .into_iter()
+                        .map(|op| self.transform_credential_query(op))
+                        .collect::<IndyResult<Vec<Query>>>()?
+                )
             }
-            PredicateTypes::LE => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value <= predicate.p_value)
+            Query::Or(operators) => {
+                Query::Or(
+                    operators
+                        .into_iter()
+                        .map(|op| self.transform_credential_query(op))
+                        .collect::<IndyResult<Vec<Query>>>()?
+                )
             }
-            PredicateTypes::LT => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value < predicate.p_value)
+            Query::Not(operator) => {
+                Query::Not(::std::boxed::Box::new(self.transform_credential_query(*operator)?))
             }
+            query => query
+        };
+
+        trace!("transform_credential_query <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Credentials stored with the current tags version and a numeric value tag for the attribute
+    // (see `build_credential_tags`) are filtered by that tag, the rest must be checked for
+    // satisfying the predicate by their values.
+    pub fn build_predicate_queries(&self, query: &Query, predicate: &PredicateInfo) -> IndyResult<(Query, Query)> {
+        trace!("build_predicate_queries >>> query: {:?}, predicate: {:?}", query, predicate);
+
+        let tag_name = Prover::_numeric_tag_name(&predicate.name);
+
+        let predicate_queries = Prover::_predicate_constraints(predicate)?
+            .into_iter()
+            .map(|(p_type, p_value)| {
+                let tag_value = Prover::_numeric_tag_value(p_value);
+                match p_type {
+                    PredicateTypes::GE => Query::Gte(tag_name.clone(), tag_value),
+                    PredicateTypes::GT => Query::Gt(tag_name.clone(), tag_value),
+                    PredicateTypes::LE => Query::Lte(tag_name.clone(), tag_value),
+                    PredicateTypes::LT => Query::Lt(tag_name.clone(), tag_value),
+                }
+            });
+
+        let tags_version_query = Query::Eq(Credential::TAGS_VERSION_TAG.to_string(), Credential::TAGS_VERSION.to_string());
+
+        let mut tagged_queries = vec![query.clone(), tags_version_query.clone()];
+        tagged_queries.extend(predicate_queries);
+
+        // every numeric tag value is not less than the one of i32::MIN, so this matches any present tag
+        let numeric_tag_query = Query::Gte(tag_name, Prover::_numeric_tag_value(::std::i32::MIN));
+
+        let tagged_query = Query::And(tagged_queries);
+        let untagged_query = Query::And(vec![query.clone(), Query::Not(Box::new(Query::And(vec![tags_version_query, numeric_tag_query])))]);
+
+        trace!("build_predicate_queries <<< tagged_query: {:?}, untagged_query: {:?}", tagged_query, untagged_query);
+
+        Ok((tagged_query, untagged_query))
+    }
+
+    fn _predicate_constraints(predicate: &PredicateInfo) -> IndyResult<Vec<(PredicateTypes, i32)>> {
+        predicate.constraints()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))
+    }
+
+    fn _transform_numeric_operator<F>(&self, tag_name: String, tag_value: String, operator: F) -> IndyResult<Query>
+        where F: Fn(String, String) -> Query {
+        let attr = match Prover::_attr_name_from_value_tag(&tag_name) {
+            Some(attr) => attr,
+            None => return Ok(operator(tag_name, tag_value))
         };
 
+        let value = tag_value.parse::<i32>()
+            .to_indy(IndyErrorKind::InvalidStructure, format!("Value \"{}\" of tag \"{}\" must be an integer for comparison", tag_value, tag_name))?;
+
+        Ok(operator(Prover::_numeric_tag_name(attr), Prover::_numeric_tag_value(value)))
+    }
+
+    fn _attr_name_from_value_tag(tag_name: &str) -> Option<&str> {
+        if tag_name.starts_with("attr::") && tag_name.ends_with("::value") && tag_name.len() > "attr::::value".len() {
+            Some(&tag_name["attr::".len()..tag_name.len() - "::value".len()])
+        } else {
+            None
+        }
+    }
+
+    fn _numeric_tag_name(attr: &str) -> String {
+        format!("~attr::{}::value", attr_common_view(attr))
+    }
+
+    // Plain tags are compared as strings, so shift the value into the non-negative range and pad it
+    fn _numeric_tag_value(value: i32) -> String {
+        format!("{:010}", i64::from(value) - i64::from(::std::i32::MIN))
+    }
+
+    pub fn attribute_satisfy_predicate(&self,
+                                       predicate: &PredicateInfo,
+                                       attribute_value: &str) -> IndyResult<bool> {
+        trace!("attribute_satisfy_predicate >>> predicate: {:?}, attribute_value: {:?}", predicate, sensitive!(&attribute_value));
+
+        #[cfg(test)]
+        self.predicate_value_checks.set(self.predicate_value_checks.get() + 1);
+
+        let constraints = Prover::_predicate_constraints(predicate)?;
+
+        let attribute_value = attribute_value.parse::<i32>()
+            .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
+
+        let res = Ok(constraints
+            .into_iter()
+            .all(|(p_type, p_value)| match p_type {
+                PredicateTypes::GE => attribute_value >= p_value,
+                PredicateTypes::GT => attribute_value > p_value,
+                PredicateTypes::LE => attribute_value <= p_value,
+                PredicateTypes::LT => attribute_value < p_value,
+            }));
+
         trace!("attribute_satisfy_predicate <<< res: {:?}", res);
         res
     }
@@ -355,7 +1016,7 @@ impl Prover {
                                requested_proof: &mut RequestedProof) -> IndyResult<()> {
         trace!("_update_requested_proof >>> req_attrs_for_credential: {:?}, req_predicates_for_credential: {:?}, proof_req: {:?}, credential: {:?}, \
                sub_proof_index: {:?}, requested_proof: {:?}",
-               req_attrs_for_credential, req_predicates_for_credential, proof_req, credential, sub_proof_index, requested_proof);
+               req_attrs_for_credential, req_predicates_for_credential, proof_req, sensitive!(&credential), sub_proof_index, sensitive!(&requested_proof));
 
         for attr_info in req_attrs_for_credential {
             if attr_info.revealed {
@@ -398,9 +1059,9 @@ impl Prover {
         }
 
         for predicate in req_predicates_for_credential {
-            let p_type = format!("{}", predicate.predicate_info.p_type);
-
-            sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.predicate_info.name), &p_type, predicate.predicate_info.p_value)?;
+            for (p_type, p_value) in Prover::_predicate_constraints(&predicate.predicate_info)? {
+                sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.predicate_info.name), &format!("{}", p_type), p_value)?;
+            }
         }
 
         let sub_proof_request = sub_proof_request_builder.finalize()?;
@@ -439,6 +1100,71 @@ impl Prover {
         Ok(Query::And(queries))
     }
 
+    pub fn has_schema_version_range(query: &Query) -> bool {
+        match query {
+            Query::Eq(ref tag_name, _) | Query::Neq(ref tag_name, _) | Query::In(ref tag_name, _) => tag_name == SchemaVersionRange::TAG,
+            Query::And(ref queries) | Query::Or(ref queries) => queries.iter().any(Prover::has_schema_version_range),
+            Query::Not(ref query) => Prover::has_schema_version_range(query),
+            _ => false
+        }
+    }
+
+    // Wallet search can't compare versions, so `schema_version_range` restrictions are replaced
+    // with the versions of stored credentials' schemas that fall into the range.
+    pub fn resolve_schema_version_ranges(&self, query: Query, schema_versions: &HashSet<String>) -> IndyResult<Query> {
+        Ok(match query {
+            Query::Eq(tag_name, tag_value) if tag_name == SchemaVersionRange::TAG => {
+                Prover::_schema_versions_in_range(&tag_value, schema_versions)?
+            }
+            Query::Neq(tag_name, tag_value) if tag_name == SchemaVersionRange::TAG => {
+                Query::Not(Box::new(Prover::_schema_versions_in_range(&tag_value, schema_versions)?))
+            }
+            Query::In(tag_name, tag_values) if tag_name == SchemaVersionRange::TAG => {
+                Query::Or(tag_values.iter()
+                    .map(|tag_value| Prover::_schema_versions_in_range(tag_value, schema_versions))
+                    .collect::<IndyResult<Vec<Query>>>()?)
+            }
+            Query::And(queries) => {
+                Query::And(queries.into_iter()
+                    .map(|query| self.resolve_schema_version_ranges(query, schema_versions))
+                    .collect::<IndyResult<Vec<Query>>>()?)
+            }
+            Query::Or(queries) => {
+                Query::Or(queries.into_iter()
+                    .map(|query| self.resolve_schema_version_ranges(query, schema_versions))
+                    .collect::<IndyResult<Vec<Query>>>()?)
+            }
+            Query::Not(query) => Query::Not(Box::new(self.resolve_schema_version_ranges(*query, schema_versions)?)),
+            query => query
+        })
+    }
+
+    fn _schema_versions_in_range(range: &str, schema_versions: &HashSet<String>) -> IndyResult<Query> {
+        let parsed_range = SchemaVersionRange::parse(range)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        let mut versions: Vec<String> = schema_versions.iter()
+            .filter(|version| parsed_range.contains(version))
+            .cloned()
+            .collect();
+        versions.sort();
+
+        Ok(match versions.len() {
+            // No credential is tagged with the range itself, so the query matches nothing
+            0 => Query::Eq(SchemaVersionRange::TAG.to_string(), range.to_string()),
+            1 => Query::Eq("schema_version".to_string(), versions.remove(0)),
+            _ => Query::In("schema_version".to_string(), versions)
+        })
+    }
+
+    // Credentials revealing a value out of the public list can't satisfy the referent
+    pub fn restrict_to_allowed_values(&self, query: Query, name: &str, allowed_values: Option<&Vec<String>>) -> Query {
+        match allowed_values {
+            Some(allowed_values) => Query::And(vec![query, Query::In(format!("attr::{}::value", attr_common_view(name)), allowed_values.clone())]),
+            None => query
+        }
+    }
+
     fn double_restrictions(&self, operator: Query) -> IndyResult<Query> {
         Ok(match operator {
             Query::Eq(tag_name, tag_value) => {
@@ -550,6 +1276,7 @@ mod tests {
                     "issuer_did".to_string() => ISSUER_DID.to_string(),
                     "cred_def_id".to_string() => CRED_DEF_ID.to_string(),
                     "rev_reg_id".to_string() => NO_REV_REG_ID.to_string(),
+                    "tags_version".to_string() => Credential::TAGS_VERSION.to_string(),
                     "attr::name::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
                     "attr::name::value".to_string() => "Alex".to_string(),
                     "attr::age::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
@@ -573,6 +1300,7 @@ mod tests {
                     "issuer_did".to_string() => ISSUER_DID.to_string(),
                     "cred_def_id".to_string() => CRED_DEF_ID.to_string(),
                     "rev_reg_id".to_string() => NO_REV_REG_ID.to_string(),
+                    "tags_version".to_string() => Credential::TAGS_VERSION.to_string(),
                     "attr::name::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
                     "attr::name::value".to_string() => "Alex".to_string()
                  );
@@ -581,7 +1309,18 @@ mod tests {
         }
 
         #[test]
-        fn build_credential_tags_works_for_rev_reg_id() {
+        fn build_credential_tags_works_for_range_queryable_catpol() {
+            let ps = Prover::new();
+            let catpol: CredentialAttrTagPolicy = serde_json::from_str(r#"{"taggable": ["name", "age"], "range_queryable": ["name", "age"]}"#).unwrap();
+            let tags = ps.build_credential_tags(&_credential(), Some(&catpol)).unwrap();
+
+            assert_eq!("25", tags["attr::age::value"]);
+            assert_eq!("2147483673", tags["~attr::age::value"]);
+            assert!(!tags.contains_key("~attr::name::value"));
+        }
+
+        #[test]
+        fn build_credential_tags_works_for_rev_reg_id() {
             let ps = Prover::new();
             let mut credential = _credential();
             credential.rev_reg_id = Some(RevocationRegistryId(REV_REG_ID.to_string()));
@@ -595,6 +1334,7 @@ mod tests {
                     "issuer_did".to_string() => ISSUER_DID.to_string(),
                     "cred_def_id".to_string() => CRED_DEF_ID.to_string(),
                     "rev_reg_id".to_string() => REV_REG_ID.to_string(),
+                    "tags_version".to_string() => Credential::TAGS_VERSION.to_string(),
                     "attr::name::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
                     "attr::name::value".to_string() => "Alex".to_string(),
                     "attr::age::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
@@ -633,6 +1373,7 @@ mod tests {
                     "cred_def_id_short".to_string() => CRED_DEF_ID.to_string(),
                     "rev_reg_id".to_string() => rev_reg_id.to_string(),
                     "rev_reg_id_short".to_string() => REV_REG_ID.to_string(),
+                    "tags_version".to_string() => Credential::TAGS_VERSION.to_string(),
                     "attr::name::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
                     "attr::name::value".to_string() => "Alex".to_string(),
                     "attr::age::marker".to_string() => ATTRIBUTE_EXISTENCE_MARKER.to_string(),
@@ -643,6 +1384,196 @@ mod tests {
         }
     }
 
+    mod credential_fingerprint {
+        use super::*;
+
+        fn _credential(age: &str) -> Credential {
+            serde_json::from_value(json!({
+                "schema_id": SCHEMA_ID,
+                "cred_def_id": CRED_DEF_ID,
+                "values": {
+                    "name": {"raw": "Alex", "encoded": "1139481716457488690172217916278103335"},
+                    "age": {"raw": age, "encoded": age}
+                },
+                "signature": {
+                    "p_credential": {"m_2": "1234567890", "a": "98765432109876543210", "e": "1", "v": "123456789012345678901234567890"}
+                },
+                "signature_correctness_proof": {"se": "1234567890", "c": "987654321"}
+            })).unwrap()
+        }
+
+        #[test]
+        fn credential_fingerprint_works() {
+            let fingerprint = Prover::credential_fingerprint(&_credential("28")).unwrap();
+
+            assert_eq!(64, fingerprint.len());
+            assert_eq!(fingerprint, Prover::credential_fingerprint(&_credential("28")).unwrap());
+        }
+
+        #[test]
+        fn credential_fingerprint_works_for_different_credentials() {
+            assert_ne!(Prover::credential_fingerprint(&_credential("28")).unwrap(),
+                       Prover::credential_fingerprint(&_credential("29")).unwrap());
+        }
+    }
+
+    mod resolve_schema_version_ranges {
+        use super::*;
+
+        fn _schema_versions() -> HashSet<String> {
+            vec!["1.0", "1.2", "2.1"].into_iter().map(String::from).collect()
+        }
+
+        #[test]
+        fn resolve_schema_version_ranges_works() {
+            let ps = Prover::new();
+
+            let query = Query::And(vec![
+                Query::Eq("schema_name".to_string(), "gvt".to_string()),
+                Query::Eq(SchemaVersionRange::TAG.to_string(), ">=1.0,<2.0".to_string()),
+            ]);
+            let query = ps.resolve_schema_version_ranges(query, &_schema_versions()).unwrap();
+
+            let expected = Query::And(vec![
+                Query::Eq("schema_name".to_string(), "gvt".to_string()),
+                Query::In("schema_version".to_string(), vec!["1.0".to_string(), "1.2".to_string()]),
+            ]);
+            assert_eq!(expected, query);
+        }
+
+        #[test]
+        fn resolve_schema_version_ranges_works_for_single_version() {
+            let ps = Prover::new();
+
+            let query = Query::Neq(SchemaVersionRange::TAG.to_string(), ">=2.0".to_string());
+            let query = ps.resolve_schema_version_ranges(query, &_schema_versions()).unwrap();
+
+            assert_eq!(Query::Not(Box::new(Query::Eq("schema_version".to_string(), "2.1".to_string()))), query);
+        }
+
+        #[test]
+        fn resolve_schema_version_ranges_works_for_no_matching_version() {
+            let ps = Prover::new();
+
+            let query = Query::Eq(SchemaVersionRange::TAG.to_string(), ">=3.0".to_string());
+            let query = ps.resolve_schema_version_ranges(query, &_schema_versions()).unwrap();
+
+            assert_eq!(Query::Eq(SchemaVersionRange::TAG.to_string(), ">=3.0".to_string()), query);
+        }
+
+        #[test]
+        fn resolve_schema_version_ranges_fails_for_malformed_range() {
+            let ps = Prover::new();
+
+            let query = Query::Eq(SchemaVersionRange::TAG.to_string(), "1.0".to_string());
+            let res = ps.resolve_schema_version_ranges(query, &_schema_versions());
+
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn has_schema_version_range_works() {
+            assert!(Prover::has_schema_version_range(&Query::Not(Box::new(Query::Eq(SchemaVersionRange::TAG.to_string(), ">=1.0".to_string())))));
+            assert!(!Prover::has_schema_version_range(&Query::Eq("schema_version".to_string(), "1.0".to_string())));
+        }
+    }
+
+    mod transform_credential_query {
+        use super::*;
+
+        #[test]
+        fn transform_credential_query_works_for_attr_value_comparison() {
+            let ps = Prover::new();
+
+            let query = Query::Gte("attr::age::value".to_string(), "18".to_string());
+            let query = ps.transform_credential_query(query).unwrap();
+
+            assert_eq!(Query::Gte("~attr::age::value".to_string(), "2147483666".to_string()), query);
+        }
+
+        #[test]
+        fn transform_credential_query_works_for_negative_value() {
+            let ps = Prover::new();
+
+            let query = Query::Lt("attr::age::value".to_string(), "-1".to_string());
+            let query = ps.transform_credential_query(query).unwrap();
+
+            assert_eq!(Query::Lt("~attr::age::value".to_string(), "2147483647".to_string()), query);
+        }
+
+        #[test]
+        fn transform_credential_query_works_for_combination() {
+            let ps = Prover::new();
+
+            let query = Query::And(vec![
+                Query::Eq("attr::age::value".to_string(), "28".to_string()),
+                Query::Not(Box::new(Query::Lt("attr::age::value".to_string(), "18".to_string()))),
+                Query::Gt("~other".to_string(), "1".to_string())
+            ]);
+            let query = ps.transform_credential_query(query).unwrap();
+
+            let expected_query = Query::And(vec![
+                Query::Eq("attr::age::value".to_string(), "28".to_string()),
+                Query::Not(Box::new(Query::Lt("~attr::age::value".to_string(), "2147483666".to_string()))),
+                Query::Gt("~other".to_string(), "1".to_string())
+            ]);
+
+            assert_eq!(expected_query, query);
+        }
+
+        #[test]
+        fn transform_credential_query_works_for_not_integer_value() {
+            let ps = Prover::new();
+
+            let query = Query::Gte("attr::age::value".to_string(), "eighteen".to_string());
+            let res = ps.transform_credential_query(query);
+
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn build_predicate_queries_works() {
+            let ps = Prover::new();
+
+            let query = Query::Eq("attr::age::marker".to_string(), ATTRIBUTE_EXISTENCE_MARKER.to_string());
+            let predicate = PredicateInfo {
+                name: "Age".to_string(),
+                p_type: PredicateTypes::GE,
+                p_value: 18,
+                p_value_max: None,
+                scale: None,
+                restrictions: None,
+                non_revoked: None,
+            };
+
+            let (tagged_query, untagged_query) = ps.build_predicate_queries(&query, &predicate).unwrap();
+
+            let tags_version_query = Query::Eq(Credential::TAGS_VERSION_TAG.to_string(), Credential::TAGS_VERSION.to_string());
+
+            assert_eq!(Query::And(vec![query.clone(),
+                                       tags_version_query.clone(),
+                                       Query::Gte("~attr::age::value".to_string(), "2147483666".to_string())]),
+                       tagged_query);
+
+            assert_eq!(Query::And(vec![query,
+                                       Query::Not(Box::new(Query::And(vec![tags_version_query,
+                                                                           Query::Gte("~attr::age::value".to_string(), "0000000000".to_string())])))]),
+                       untagged_query);
+        }
+
+        #[test]
+        fn numeric_tag_value_preserves_order() {
+            let values = vec![::std::i32::MIN, -100, -1, 0, 9, 18, 100, ::std::i32::MAX];
+
+            let tags: Vec<String> = values.iter().map(|value| Prover::_numeric_tag_value(*value)).collect();
+
+            let mut sorted_tags = tags.clone();
+            sorted_tags.sort();
+
+            assert_eq!(tags, sorted_tags);
+        }
+    }
+
     mod attribute_satisfy_predicate {
         use super::*;
 
@@ -651,6 +1582,8 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 8,
+                p_value_max: None,
+                scale: None,
                 restrictions: None,
                 non_revoked: None,
             }
@@ -663,6 +1596,44 @@ mod tests {
             assert!(res);
         }
 
+        #[test]
+        fn attribute_satisfy_predicate_works_for_scale() {
+            let ps = Prover::new();
+            let predicate = PredicateInfo { scale: Some(2), ..predicate_info() };
+
+            assert!(ps.attribute_satisfy_predicate(&predicate, "800").unwrap());
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "799").unwrap());
+        }
+
+        #[test]
+        fn attribute_satisfy_predicate_works_for_scale_overflow() {
+            let ps = Prover::new();
+            let predicate = PredicateInfo { scale: Some(9), ..predicate_info() };
+
+            let res = ps.attribute_satisfy_predicate(&predicate, "10");
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn attribute_satisfy_predicate_works_for_range() {
+            let ps = Prover::new();
+            let predicate = PredicateInfo { p_value: 18, p_value_max: Some(65), ..predicate_info() };
+
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "17").unwrap());
+            assert!(ps.attribute_satisfy_predicate(&predicate, "18").unwrap());
+            assert!(ps.attribute_satisfy_predicate(&predicate, "64").unwrap());
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "65").unwrap());
+        }
+
+        #[test]
+        fn attribute_satisfy_predicate_works_for_strict_range() {
+            let ps = Prover::new();
+            let predicate = PredicateInfo { p_type: PredicateTypes::GT, p_value: 18, p_value_max: Some(65), ..predicate_info() };
+
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "18").unwrap());
+            assert!(ps.attribute_satisfy_predicate(&predicate, "19").unwrap());
+        }
+
         #[test]
         fn attribute_satisfy_predicate_works_for_false() {
             let ps = Prover::new();
@@ -701,6 +1672,8 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 8,
+                p_value_max: None,
+                scale: None,
                 restrictions: None,
                 non_revoked: None,
             }
@@ -708,7 +1681,6 @@ mod tests {
 
         fn _proof_req() -> ProofRequestPayload {
             ProofRequestPayload {
-                nonce: ursa::cl::new_nonce().unwrap(),
                 name: "Job-Application".to_string(),
                 version: "0.1".to_string(),
                 requested_attributes: hashmap!(
@@ -717,7 +1689,7 @@ mod tests {
                 requested_predicates: hashmap!(
                     PREDICATE_REFERENT.to_string() => _predicate_info()
                 ),
-                non_revoked: None,
+                ..Default::default()
             }
         }
 
@@ -780,6 +1752,69 @@ mod tests {
             assert_eq!(1, req_pred_info.len());
         }
 
+        fn _same_name_req(restrictions_1: Option<Query>, restrictions_2: Option<Query>) -> (RequestedCredentials, ProofRequestPayload) {
+            let mut req_cred = _req_cred();
+            let mut proof_req = _proof_req();
+
+            proof_req.requested_attributes.get_mut(ATTRIBUTE_REFERENT).unwrap().restrictions = restrictions_1;
+            proof_req.requested_attributes.insert("attribute_referent_2".to_string(), AttributeInfo {
+                name: "Name".to_string(),
+                restrictions: restrictions_2,
+                non_revoked: None,
+            });
+
+            req_cred.requested_attributes.insert("attribute_referent_2".to_string(), RequestedAttribute {
+                cred_id: "cred_id_2".to_string(),
+                timestamp: None,
+                revealed: true,
+            });
+
+            (req_cred, proof_req)
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_same_attribute_name_with_distinct_restrictions() {
+            let (req_cred, proof_req) = _same_name_req(Some(Query::Eq("cred_def_id".to_string(), "cred_def_1".to_string())),
+                                                       Some(Query::Eq("cred_def_id".to_string(), "cred_def_2".to_string())));
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req).unwrap();
+            assert_eq!(2, res.len());
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_same_attribute_name_without_restrictions() {
+            let (req_cred, proof_req) = _same_name_req(None, None);
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req).unwrap();
+            assert_eq!(2, res.len());
+
+            let (req_cred, proof_req) = _same_name_req(Some(Query::Eq("cred_def_id".to_string(), "cred_def_1".to_string())), None);
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req).unwrap();
+            assert_eq!(2, res.len());
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_ambiguous_attribute_name() {
+            let (req_cred, proof_req) = _same_name_req(Some(Query::Eq("cred_def_id".to_string(), "cred_def_1".to_string())),
+                                                       Some(Query::Eq("cred_def_id".to_string(), "cred_def_1".to_string())));
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_ambiguous_attribute_name_with_reordered_restrictions() {
+            let schema_id = Query::Eq("schema_id".to_string(), "schema_1".to_string());
+            let issuer_did = Query::Eq("issuer_did".to_string(), "issuer_1".to_string());
+
+            let (req_cred, proof_req) = _same_name_req(Some(Query::And(vec![schema_id.clone(), issuer_did.clone()])),
+                                                       Some(Query::And(vec![Query::And(vec![issuer_did]), schema_id])));
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
         #[test]
         fn prepare_credentials_for_proving_works_for_missed_attribute() {
             let req_cred = _req_cred();
@@ -801,6 +1836,159 @@ mod tests {
             let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req);
             assert_kind!(IndyErrorKind::InvalidStructure, res);
         }
+
+        fn _linked_reveal_req(cred_id_2: &str, revealed_2: bool) -> (RequestedCredentials, ProofRequestPayload) {
+            let mut req_cred = _req_cred();
+            let mut proof_req = _proof_req();
+
+            req_cred.requested_attributes.insert("attribute_referent_2".to_string(), RequestedAttribute {
+                cred_id: cred_id_2.to_string(),
+                timestamp: None,
+                revealed: revealed_2,
+            });
+
+            proof_req.requested_attributes.insert("attribute_referent_2".to_string(), AttributeInfo {
+                name: "last_name".to_string(),
+                restrictions: None,
+                non_revoked: None,
+            });
+            proof_req.linked_reveal = Some(hashmap!(
+                "full_name".to_string() => vec![ATTRIBUTE_REFERENT.to_string(), "attribute_referent_2".to_string()]
+            ));
+
+            (req_cred, proof_req)
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_linked_reveal() {
+            let (req_cred, proof_req) = _linked_reveal_req(CRED_ID, false);
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req).unwrap();
+            assert_eq!(1, res.len());
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_linked_reveal_from_different_credentials() {
+            let (req_cred, proof_req) = _linked_reveal_req("other_cred_id", false);
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_linked_reveal_partially_revealed() {
+            let (req_cred, proof_req) = _linked_reveal_req(CRED_ID, true);
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn prepare_credentials_for_proving_works_for_linked_reveal_self_attested() {
+            let (mut req_cred, proof_req) = _linked_reveal_req(CRED_ID, false);
+            req_cred.requested_attributes.remove("attribute_referent_2");
+            req_cred.self_attested_attributes.insert("attribute_referent_2".to_string(), "Smith".to_string());
+
+            let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod preview_disclosure {
+        use domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo};
+        use domain::anoncreds::requested_credential::RequestedAttribute;
+
+        use super::*;
+
+        const CRED_ID: &str = "8591bcac-ee7d-4bef-ba7e-984696440b30";
+
+        fn _credential() -> Credential {
+            let mut attr_values: HashMap<String, AttributeValues> = HashMap::new();
+            attr_values.insert("name".to_string(), AttributeValues { raw: "Alex".to_string(), encoded: "12345".to_string() });
+            attr_values.insert("age".to_string(), AttributeValues { raw: "25".to_string(), encoded: "25".to_string() });
+
+            serde_json::from_str::<Credential>(
+                &json!({
+                    "schema_id": SCHEMA_ID,
+                    "cred_def_id": CRED_DEF_ID,
+                    "values": attr_values,
+                    "signature": json!({
+                        "p_credential": json!({"m_2": "0","a": "0","e": "0","v": "0"})
+                    }),
+                    "signature_correctness_proof": json!({"se":"0", "c":"0"})
+                }).to_string()
+            ).unwrap()
+        }
+
+        fn _proof_req(p_value: i32) -> ProofRequestPayload {
+            ProofRequestPayload {
+                name: "Job-Application".to_string(),
+                version: "0.1".to_string(),
+                requested_attributes: hashmap!(
+                    "attr1_referent".to_string() => AttributeInfo { name: "name".to_string(), restrictions: None, non_revoked: None }
+                ),
+                requested_predicates: hashmap!(
+                    "predicate1_referent".to_string() => PredicateInfo {
+                        name: "age".to_string(),
+                        p_type: PredicateTypes::GE,
+                        p_value,
+                        p_value_max: None,
+                        scale: None,
+                        restrictions: None,
+                        non_revoked: None,
+                    }
+                ),
+                ..Default::default()
+            }
+        }
+
+        fn _req_cred() -> RequestedCredentials {
+            RequestedCredentials {
+                self_attested_attributes: HashMap::new(),
+                requested_attributes: hashmap!(
+                    "attr1_referent".to_string() => RequestedAttribute { cred_id: CRED_ID.to_string(), timestamp: None, revealed: true }
+                ),
+                requested_predicates: hashmap!(
+                    "predicate1_referent".to_string() => ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None }
+                ),
+            }
+        }
+
+        #[test]
+        fn preview_disclosure_works() {
+            let ps = Prover::new();
+            let credentials = hashmap!(CRED_ID.to_string() => _credential());
+
+            let preview = ps.preview_disclosure(&credentials, &_proof_req(18), &_req_cred()).unwrap();
+
+            assert_eq!(json!({
+                "revealed_attrs": {
+                    "attr1_referent": {"cred_id": CRED_ID, "name": "name", "raw": "Alex"}
+                },
+                "unrevealed_attrs": {},
+                "self_attested_attrs": {},
+                "predicates": {
+                    "predicate1_referent": {"cred_id": CRED_ID, "name": "age", "p_type": ">=", "p_value": 18}
+                }
+            }), serde_json::to_value(&preview).unwrap());
+        }
+
+        #[test]
+        fn preview_disclosure_works_for_not_satisfied_predicate() {
+            let ps = Prover::new();
+            let credentials = hashmap!(CRED_ID.to_string() => _credential());
+
+            let res = ps.preview_disclosure(&credentials, &_proof_req(65), &_req_cred());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn preview_disclosure_works_for_missed_credential() {
+            let ps = Prover::new();
+
+            let res = ps.preview_disclosure(&HashMap::new(), &_proof_req(18), &_req_cred());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
     }
 
     mod get_credential_values_for_attribute {
@@ -1090,4 +2278,540 @@ mod tests {
             assert_eq!(expected_query, query);
         }
     }
+
+    mod create_proof {
+        use super::*;
+
+        use std::cell::Cell;
+
+        use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey, new_nonce};
+
+        use domain::anoncreds::credential::CredentialValues;
+        use domain::anoncreds::credential_definition::SignatureType;
+        use domain::anoncreds::proof_request::AttributeInfo;
+        use domain::anoncreds::requested_credential::RequestedAttribute;
+        use domain::anoncreds::schema::AttributeNames;
+        use services::anoncreds::issuer::Issuer;
+
+        const PROVER_DID: &str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+
+        fn _credential(cred_def: &CredentialDefinition,
+                       cred_priv_key: &CredentialPrivateKey,
+                       key_correctness_proof: &CredentialKeyCorrectnessProof,
+                       master_secret: &MasterSecret,
+                       name: &str) -> Credential {
+            let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, None).unwrap();
+
+            let mut master_secret_values_builder = CryptoIssuer::new_credential_values_builder().unwrap();
+            master_secret_values_builder.add_value_hidden("master_secret", &master_secret.value().unwrap()).unwrap();
+            let master_secret_values = master_secret_values_builder.finalize().unwrap();
+
+            let offer_nonce = new_nonce().unwrap();
+            let (blinded_ms, blinding_factors, blinded_ms_correctness_proof) =
+                CryptoProver::blind_credential_secrets(&credential_pub_key, key_correctness_proof, &master_secret_values, &offer_nonce).unwrap();
+
+            let values: CredentialValues = hashmap!(
+                "name".to_string() => AttributeValues { raw: name.to_string(), encoded: (name.len() + 1).to_string() }
+            );
+
+            let request_nonce = new_nonce().unwrap();
+            let (signature, signature_correctness_proof) =
+                CryptoIssuer::sign_credential(PROVER_DID,
+                                              &blinded_ms,
+                                              &blinded_ms_correctness_proof,
+                                              &offer_nonce,
+                                              &request_nonce,
+                                              &build_credential_values(&values, None).unwrap(),
+                                              &credential_pub_key,
+                                              cred_priv_key).unwrap();
+
+            let mut credential = Credential {
+                schema_id: SchemaId(SCHEMA_ID.to_string()),
+                cred_def_id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+                rev_reg_id: None,
+                values,
+                signature,
+                signature_correctness_proof,
+                rev_reg: None,
+                witness: None,
+            };
+
+            let cred_request_metadata = CredentialRequestMetadata {
+                master_secret_blinding_data: blinding_factors,
+                nonce: request_nonce,
+                master_secret_name: "master_secret".to_string(),
+            };
+
+            Prover::new().process_credential(&mut credential, &cred_request_metadata, master_secret, cred_def, None).unwrap();
+
+            credential
+        }
+
+        fn _create_proof(is_cancelled: &dyn Fn() -> bool) -> IndyResult<Proof> {
+            let prover = Prover::new();
+
+            let mut attr_names = AttributeNames::new();
+            attr_names.insert("name".to_string());
+
+            let (cred_def_data, cred_priv_key, key_correctness_proof) = Issuer::new_credential_definition(&attr_names, false).unwrap();
+
+            let cred_def = CredentialDefinition {
+                id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+                schema_id: SchemaId(SCHEMA_ID.to_string()),
+                signature_type: SignatureType::CL,
+                tag: "tag".to_string(),
+                value: cred_def_data,
+            };
+
+            let schema = SchemaV1 {
+                id: SchemaId(SCHEMA_ID.to_string()),
+                name: SCHEMA_NAME.to_string(),
+                version: SCHEMA_VERSION.to_string(),
+                attr_names,
+                seq_no: None,
+                attribute_types: None,
+            };
+
+            let master_secret = prover.new_master_secret().unwrap();
+
+            let credentials = hashmap!(
+                "cred_1".to_string() => _credential(&cred_def, &cred_priv_key, &key_correctness_proof, &master_secret, "Alex"),
+                "cred_2".to_string() => _credential(&cred_def, &cred_priv_key, &key_correctness_proof, &master_secret, "Alexander")
+            );
+
+            let proof_req = ProofRequest::ProofRequestV1(ProofRequestPayload {
+                requested_attributes: hashmap!(
+                    "attr1_referent".to_string() => AttributeInfo { name: "name".to_string(), restrictions: None, non_revoked: None },
+                    "attr2_referent".to_string() => AttributeInfo { name: "name".to_string(), restrictions: None, non_revoked: None }
+                ),
+                ..Default::default()
+            });
+
+            let requested_credentials = RequestedCredentials {
+                self_attested_attributes: HashMap::new(),
+                requested_attributes: hashmap!(
+                    "attr1_referent".to_string() => RequestedAttribute { cred_id: "cred_1".to_string(), timestamp: None, revealed: true },
+                    "attr2_referent".to_string() => RequestedAttribute { cred_id: "cred_2".to_string(), timestamp: None, revealed: true }
+                ),
+                requested_predicates: HashMap::new(),
+            };
+
+            prover.create_proof(&credentials,
+                                &proof_req,
+                                &requested_credentials,
+                                &master_secret,
+                                &hashmap!(SchemaId(SCHEMA_ID.to_string()) => schema),
+                                &hashmap!(CredentialDefinitionId(CRED_DEF_ID.to_string()) => cred_def),
+                                &HashMap::new(),
+                                is_cancelled)
+        }
+
+        #[test]
+        fn create_proof_works() {
+            let proof = _create_proof(&|| false).unwrap();
+            assert_eq!(2, proof.identifiers.len());
+        }
+
+        #[test]
+        fn create_proof_works_for_cancellation_after_first_sub_proof() {
+            let checks = Cell::new(0);
+
+            let res = _create_proof(&|| {
+                checks.set(checks.get() + 1);
+                checks.get() > 1
+            });
+
+            assert_kind!(IndyErrorKind::Cancelled, res);
+            assert_eq!(2, checks.get());
+        }
+    }
+
+    mod update_witness {
+        use super::*;
+
+        use ursa::cl::{new_nonce, SimpleTailsAccessor};
+
+        const PROVER_DID: &str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+        const MAX_CRED_NUM: u32 = 5;
+        const REV_IDX: u32 = 1;
+
+        #[test]
+        fn update_witness_works_for_two_deltas() {
+            let mut attr_names = HashSet::new();
+            attr_names.insert("name".to_string());
+            let credential_schema = build_credential_schema(&attr_names).unwrap();
+            let non_credential_schema = build_non_credential_schema().unwrap();
+
+            let (credential_pub_key, credential_priv_key, key_correctness_proof) =
+                CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+            let (rev_key_pub, rev_key_priv, mut rev_reg, mut rev_tails_generator) =
+                CryptoIssuer::new_revocation_registry_def(&credential_pub_key, MAX_CRED_NUM, true).unwrap();
+            let rev_tails_accessor = SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap();
+
+            let master_secret = CryptoProver::new_master_secret().unwrap();
+            let values = hashmap!("name".to_string() => AttributeValues { raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string() });
+            let credential_values = build_credential_values(&values, Some(&master_secret)).unwrap();
+
+            let credential_nonce = new_nonce().unwrap();
+            let (blinded_credential_secrets, credential_secrets_blinding_factors, blinded_credential_secrets_correctness_proof) =
+                CryptoProver::blind_credential_secrets(&credential_pub_key, &key_correctness_proof, &credential_values, &credential_nonce).unwrap();
+
+            let credential_issuance_nonce = new_nonce().unwrap();
+            let (mut credential_signature, signature_correctness_proof, _) =
+                CryptoIssuer::sign_credential_with_revoc(PROVER_DID,
+                                                         &blinded_credential_secrets,
+                                                         &blinded_credential_secrets_correctness_proof,
+                                                         &credential_nonce,
+                                                         &credential_issuance_nonce,
+                                                         &build_credential_values(&values, None).unwrap(),
+                                                         &credential_pub_key,
+                                                         &credential_priv_key,
+                                                         REV_IDX,
+                                                         MAX_CRED_NUM,
+                                                         true,
+                                                         &mut rev_reg,
+                                                         &rev_key_priv,
+                                                         &rev_tails_accessor).unwrap();
+
+            let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &HashSet::new(), &HashSet::new());
+            let mut witness = Witness::new(REV_IDX, MAX_CRED_NUM, true, &rev_reg_delta, &rev_tails_accessor).unwrap();
+
+            CryptoProver::process_credential_signature(&mut credential_signature,
+                                                       &credential_values,
+                                                       &signature_correctness_proof,
+                                                       &credential_secrets_blinding_factors,
+                                                       &credential_pub_key,
+                                                       &credential_issuance_nonce,
+                                                       Some(&rev_key_pub),
+                                                       Some(&rev_reg),
+                                                       Some(&witness)).unwrap();
+
+            let prover_rev_reg = rev_reg.clone();
+
+            let issuer = Issuer::new();
+            let rev_reg_delta_1 = issuer.revoke(&mut rev_reg, MAX_CRED_NUM, 2, &rev_tails_accessor).unwrap();
+            let rev_reg_delta_2 = issuer.revoke(&mut rev_reg, MAX_CRED_NUM, 3, &rev_tails_accessor).unwrap();
+
+            let res = Prover::update_witness(&mut witness, &prover_rev_reg, REV_IDX, MAX_CRED_NUM,
+                                             &[rev_reg_delta_2.clone(), rev_reg_delta_1.clone()], &rev_tails_accessor);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let updated_rev_reg = Prover::update_witness(&mut witness, &prover_rev_reg, REV_IDX, MAX_CRED_NUM,
+                                                         &[rev_reg_delta_1, rev_reg_delta_2], &rev_tails_accessor).unwrap();
+            assert_eq!(serde_json::to_value(&rev_reg).unwrap(), serde_json::to_value(&updated_rev_reg).unwrap());
+
+            let mut sub_proof_request_builder = CryptoVerifier::new_sub_proof_request_builder().unwrap();
+            sub_proof_request_builder.add_revealed_attr("name").unwrap();
+            let sub_proof_request = sub_proof_request_builder.finalize().unwrap();
+
+            let proof_nonce = new_nonce().unwrap();
+
+            let mut proof_builder = CryptoProver::new_proof_builder().unwrap();
+            proof_builder.add_common_attribute("master_secret").unwrap();
+            proof_builder.add_sub_proof_request(&sub_proof_request,
+                                                &credential_schema,
+                                                &non_credential_schema,
+                                                &credential_signature,
+                                                &credential_values,
+                                                &credential_pub_key,
+                                                Some(&updated_rev_reg),
+                                                Some(&witness)).unwrap();
+            let proof = proof_builder.finalize(&proof_nonce).unwrap();
+
+            let mut proof_verifier = CryptoVerifier::new_proof_verifier().unwrap();
+            proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                                 &credential_schema,
+                                                 &non_credential_schema,
+                                                 &credential_pub_key,
+                                                 Some(&rev_key_pub),
+                                                 Some(&rev_reg)).unwrap();
+            assert!(proof_verifier.verify(&proof, &proof_nonce).unwrap());
+        }
+    }
+
+    struct RecordingTailsAccessor {
+        inner: ::ursa::cl::SimpleTailsAccessor,
+        accessed: RefCell<HashSet<u32>>,
+    }
+
+    impl RevocationTailsAccessor for RecordingTailsAccessor {
+        fn access_tail(&self, tail_id: u32, accessor: &mut dyn FnMut(&::ursa::cl::Tail)) -> Result<(), ::ursa::errors::prelude::UrsaCryptoError> {
+            self.accessed.borrow_mut().insert(tail_id);
+            self.inner.access_tail(tail_id, accessor)
+        }
+    }
+
+    fn _rev_reg(max_cred_num: u32, issuance_by_default: bool) -> (RevocationRegistry, RecordingTailsAccessor) {
+        let mut attr_names = HashSet::new();
+        attr_names.insert("name".to_string());
+        let credential_schema = build_credential_schema(&attr_names).unwrap();
+        let non_credential_schema = build_non_credential_schema().unwrap();
+
+        let (credential_pub_key, _, _) =
+            CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, true).unwrap();
+
+        let (_, _, rev_reg, mut rev_tails_generator) =
+            CryptoIssuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default).unwrap();
+
+        let rev_tails_accessor = RecordingTailsAccessor {
+            inner: ::ursa::cl::SimpleTailsAccessor::new(&mut rev_tails_generator).unwrap(),
+            accessed: RefCell::new(HashSet::new()),
+        };
+
+        (rev_reg, rev_tails_accessor)
+    }
+
+    mod witness_dependent_indices {
+        use super::*;
+
+        const MAX_CRED_NUM: u32 = 5;
+        const REV_IDX: u32 = 2;
+
+        fn _check(issuance_by_default: bool, issued: HashSet<u32>, revoked: HashSet<u32>) {
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, issuance_by_default);
+            let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &issued, &revoked);
+
+            Witness::new(REV_IDX, MAX_CRED_NUM, issuance_by_default, &rev_reg_delta, &rev_tails_accessor).unwrap();
+
+            let indices = Prover::witness_dependent_indices(REV_IDX, MAX_CRED_NUM, issuance_by_default, &rev_reg_delta).unwrap();
+            assert_eq!(*rev_tails_accessor.accessed.borrow(), indices);
+        }
+
+        #[test]
+        fn witness_dependent_indices_works_for_issuance_on_demand() {
+            _check(false, vec![1, 2, 4].into_iter().collect(), HashSet::new());
+        }
+
+        #[test]
+        fn witness_dependent_indices_works_for_issuance_by_default() {
+            _check(true, HashSet::new(), vec![3].into_iter().collect());
+        }
+
+        #[test]
+        fn witness_dependent_indices_works_for_only_own_index() {
+            let (rev_reg, _) = _rev_reg(MAX_CRED_NUM, false);
+            let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &vec![REV_IDX].into_iter().collect(), &HashSet::new());
+
+            let indices = Prover::witness_dependent_indices(REV_IDX, MAX_CRED_NUM, false, &rev_reg_delta).unwrap();
+            assert!(indices.is_empty());
+        }
+    }
+
+    mod is_revoked {
+        use super::*;
+
+        const MAX_CRED_NUM: u32 = 5;
+
+        // registry with `issued` indices issued on demand and then `revoked` ones revoked
+        fn _rev_reg_delta(issuance_by_default: bool, issued: Vec<u32>, revoked: Vec<u32>) -> (RevocationRegistryDelta, RecordingTailsAccessor) {
+            let (mut rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, issuance_by_default);
+
+            for rev_idx in issued.iter() {
+                CryptoIssuer::recovery_credential(&mut rev_reg, MAX_CRED_NUM, *rev_idx, &rev_tails_accessor).unwrap();
+            }
+            for rev_idx in revoked.iter() {
+                CryptoIssuer::revoke_credential(&mut rev_reg, MAX_CRED_NUM, *rev_idx, &rev_tails_accessor).unwrap();
+            }
+
+            let revoked: HashSet<u32> = revoked.into_iter().collect();
+            let issued: HashSet<u32> = issued.into_iter().filter(|rev_idx| !revoked.contains(rev_idx)).collect();
+
+            (RevocationRegistryDelta::from_parts(None, &rev_reg, &issued, &revoked), rev_tails_accessor)
+        }
+
+        #[test]
+        fn is_revoked_works_for_issuance_on_demand() {
+            let (rev_reg_delta, rev_tails_accessor) = _rev_reg_delta(false, vec![1, 2, 3], vec![3]);
+
+            assert!(!Prover::is_revoked(1, MAX_CRED_NUM, false, &rev_reg_delta, &rev_tails_accessor).unwrap());
+            assert!(Prover::is_revoked(3, MAX_CRED_NUM, false, &rev_reg_delta, &rev_tails_accessor).unwrap());
+        }
+
+        #[test]
+        fn is_revoked_works_for_issuance_by_default() {
+            let (rev_reg_delta, rev_tails_accessor) = _rev_reg_delta(true, vec![], vec![2]);
+
+            assert!(!Prover::is_revoked(1, MAX_CRED_NUM, true, &rev_reg_delta, &rev_tails_accessor).unwrap());
+            assert!(Prover::is_revoked(2, MAX_CRED_NUM, true, &rev_reg_delta, &rev_tails_accessor).unwrap());
+        }
+
+        #[test]
+        fn is_revoked_works_for_not_issued_index() {
+            let (rev_reg_delta, rev_tails_accessor) = _rev_reg_delta(false, vec![1, 2], vec![]);
+
+            let res = Prover::is_revoked(4, MAX_CRED_NUM, false, &rev_reg_delta, &rev_tails_accessor);
+            assert_kind!(IndyErrorKind::InvalidUserRevocId, res);
+        }
+
+        #[test]
+        fn is_revoked_works_for_delta_not_matching_accumulator() {
+            let (mut rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+            CryptoIssuer::recovery_credential(&mut rev_reg, MAX_CRED_NUM, 1, &rev_tails_accessor).unwrap();
+
+            // index 2 is claimed to be issued, but isn't in the accumulator
+            let rev_reg_delta = RevocationRegistryDelta::from_parts(None, &rev_reg, &vec![1, 2].into_iter().collect(), &HashSet::new());
+
+            let res = Prover::is_revoked(2, MAX_CRED_NUM, false, &rev_reg_delta, &rev_tails_accessor);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn is_revoked_works_for_index_out_of_range() {
+            let (rev_reg_delta, rev_tails_accessor) = _rev_reg_delta(false, vec![1], vec![]);
+
+            let res = Prover::is_revoked(MAX_CRED_NUM + 1, MAX_CRED_NUM, false, &rev_reg_delta, &rev_tails_accessor);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn is_revoked_works_for_partial_delta() {
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+            let rev_reg_delta = RevocationRegistryDelta::from_parts(Some(&rev_reg), &rev_reg, &vec![1].into_iter().collect(), &HashSet::new());
+
+            let res = Prover::is_revoked(1, MAX_CRED_NUM, false, &rev_reg_delta, &rev_tails_accessor);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod new_witness {
+        use super::*;
+
+        const MAX_CRED_NUM: u32 = 5;
+        const REV_IDX: u32 = 2;
+
+        fn _rev_reg_delta(rev_reg: &RevocationRegistry, issued: Vec<u32>) -> RevocationRegistryDeltaV1 {
+            RevocationRegistryDeltaV1 {
+                value: RevocationRegistryDelta::from_parts(None, rev_reg, &issued.into_iter().collect(), &HashSet::new())
+            }
+        }
+
+        fn _witness_json(witness: &Witness) -> serde_json::Value {
+            serde_json::to_value(witness).unwrap()
+        }
+
+        #[test]
+        fn new_witness_works_for_cached_witness() {
+            let prover = Prover::new();
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+            let rev_reg_delta = _rev_reg_delta(&rev_reg, vec![1, 2, 4]);
+
+            let witness = prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                                             &rev_reg_delta, 100, &rev_tails_accessor).unwrap();
+            assert!(!rev_tails_accessor.accessed.borrow().is_empty());
+
+            rev_tails_accessor.accessed.borrow_mut().clear();
+
+            let cached_witness = prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                                                    &rev_reg_delta, 100, &rev_tails_accessor).unwrap();
+            assert!(rev_tails_accessor.accessed.borrow().is_empty());
+            assert_eq!(_witness_json(&witness), _witness_json(&cached_witness));
+        }
+
+        #[test]
+        fn new_witness_works_for_changed_registry() {
+            let prover = Prover::new();
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+
+            prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                               &_rev_reg_delta(&rev_reg, vec![1, 2]), 100, &rev_tails_accessor).unwrap();
+
+            rev_tails_accessor.accessed.borrow_mut().clear();
+
+            prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                               &_rev_reg_delta(&rev_reg, vec![1, 2, 4]), 200, &rev_tails_accessor).unwrap();
+            assert!(!rev_tails_accessor.accessed.borrow().is_empty());
+        }
+
+        #[test]
+        fn new_witness_works_after_cache_cleared() {
+            let prover = Prover::new();
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+            let rev_reg_delta = _rev_reg_delta(&rev_reg, vec![1, 2, 4]);
+
+            prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                               &rev_reg_delta, 100, &rev_tails_accessor).unwrap();
+
+            prover.clear_witness_cache();
+            rev_tails_accessor.accessed.borrow_mut().clear();
+
+            prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                               &rev_reg_delta, 100, &rev_tails_accessor).unwrap();
+            assert!(!rev_tails_accessor.accessed.borrow().is_empty());
+        }
+
+        #[test]
+        fn max_provable_timestamp_works_for_stale_witness() {
+            let prover = Prover::new();
+            let rev_reg_id = RevocationRegistryId(REV_REG_ID.to_string());
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+
+            assert_eq!(None, prover.max_provable_timestamp(&rev_reg_id));
+
+            prover.new_witness(&rev_reg_id, REV_IDX, MAX_CRED_NUM, false,
+                               &_rev_reg_delta(&rev_reg, vec![1, 2]), 100, &rev_tails_accessor).unwrap();
+
+            // the ledger has moved on to another accumulator at 200, the witness still supports only 100
+            assert_eq!(Some(100), prover.max_provable_timestamp(&rev_reg_id));
+        }
+
+        #[test]
+        fn max_provable_timestamp_works_for_unchanged_registry() {
+            let prover = Prover::new();
+            let rev_reg_id = RevocationRegistryId(REV_REG_ID.to_string());
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+            let rev_reg_delta = _rev_reg_delta(&rev_reg, vec![1, 2]);
+
+            prover.new_witness(&rev_reg_id, REV_IDX, MAX_CRED_NUM, false, &rev_reg_delta, 100, &rev_tails_accessor).unwrap();
+            prover.new_witness(&rev_reg_id, REV_IDX, MAX_CRED_NUM, false, &rev_reg_delta, 200, &rev_tails_accessor).unwrap();
+
+            assert_eq!(Some(200), prover.max_provable_timestamp(&rev_reg_id));
+        }
+
+        #[test]
+        fn max_provable_timestamp_works_for_updated_witness() {
+            let prover = Prover::new();
+            let rev_reg_id = RevocationRegistryId(REV_REG_ID.to_string());
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+
+            let witness = prover.new_witness(&rev_reg_id, REV_IDX, MAX_CRED_NUM, false,
+                                             &_rev_reg_delta(&rev_reg, vec![1, 2]), 100, &rev_tails_accessor).unwrap();
+
+            prover.cache_updated_witness(&rev_reg_id, REV_IDX, &witness, 200);
+            assert_eq!(Some(200), prover.max_provable_timestamp(&rev_reg_id));
+
+            prover.clear_witness_cache();
+            assert_eq!(None, prover.max_provable_timestamp(&rev_reg_id));
+        }
+
+        #[test]
+        fn max_provable_timestamp_works_for_other_registry() {
+            let prover = Prover::new();
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+
+            prover.new_witness(&RevocationRegistryId(REV_REG_ID.to_string()), REV_IDX, MAX_CRED_NUM, false,
+                               &_rev_reg_delta(&rev_reg, vec![1, 2]), 100, &rev_tails_accessor).unwrap();
+
+            assert_eq!(None, prover.max_provable_timestamp(&RevocationRegistryId("other_rev_reg_id".to_string())));
+        }
+
+        #[test]
+        fn witness_cache_evicts_oldest_witness() {
+            let (rev_reg, rev_tails_accessor) = _rev_reg(MAX_CRED_NUM, false);
+            let rev_reg_delta = _rev_reg_delta(&rev_reg, vec![1, 2, 4]);
+            let witness = Witness::new(REV_IDX, MAX_CRED_NUM, false, &rev_reg_delta.value, &rev_tails_accessor).unwrap();
+
+            let key = |rev_idx: u32| (RevocationRegistryId(REV_REG_ID.to_string()), rev_idx);
+
+            let mut cache = WitnessCache::new();
+            cache.insert(key(1), Some("digest".to_string()), witness.clone(), 100, 2);
+            cache.insert(key(2), Some("digest".to_string()), witness.clone(), 100, 2);
+            cache.insert(key(4), Some("digest".to_string()), witness.clone(), 100, 2);
+
+            assert!(cache.get(&key(1), "digest", 100).is_none());
+            assert!(cache.get(&key(2), "digest", 100).is_some());
+            assert!(cache.get(&key(4), "digest", 100).is_some());
+            assert!(cache.get(&key(4), "other digest", 100).is_none());
+        }
+    }
 }
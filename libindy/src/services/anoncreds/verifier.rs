@@ -1,14 +1,18 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use domain::anoncreds::credential_definition::{CredentialDefinitionV1, CredentialDefinitionId};
-use domain::anoncreds::proof::{Proof, RequestedProof, Identifier};
-use domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
+use domain::anoncreds::proof::{Identifier, Proof, ProofLimits, ReferentSatisfaction, ReferentVerification, RequestedProof, SatisfactionMode, SubProofReferent, VerificationAuditRecord, VerificationFailure, VerificationReport, VerificationResult};
+use domain::anoncreds::proof_request::{AttributeInfo, Normalization, PredicateInfo, ProofRequestPayload, NonRevocedInterval, NonRevocedIntervals};
 use domain::anoncreds::revocation_registry::RevocationRegistryV1;
 use domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
-use domain::anoncreds::schema::{SchemaV1, SchemaId};
+use domain::anoncreds::schema::{SchemaV1, SchemaId, SchemaVersionRange};
 use errors::prelude::*;
 use services::anoncreds::helpers::*;
 
+use serde_json;
+use serde_json::Value;
 
 use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
 use ursa::cl::verifier::Verifier as CryptoVerifier;
@@ -24,6 +28,29 @@ pub struct Filter {
     cred_def_id: String,
 }
 
+/// Receives a record of every successful proof verification, e.g. to persist an audit trail.
+/// It's called on the command thread while verification waits, so it should return quickly.
+pub trait VerificationAuditSink: Send + Sync {
+    fn record(&self, record: &VerificationAuditRecord);
+}
+
+lazy_static! {
+    static ref PROOF_LIMITS: Mutex<ProofLimits> = Mutex::new(ProofLimits::default());
+    static ref AUDIT_SINK: Mutex<Option<Arc<dyn VerificationAuditSink>>> = Mutex::new(None);
+}
+
+pub fn set_proof_limits(limits: ProofLimits) {
+    *PROOF_LIMITS.lock().unwrap() = limits;
+}
+
+pub fn set_verification_audit_sink(sink: Option<Box<dyn VerificationAuditSink>>) {
+    *AUDIT_SINK.lock().unwrap() = sink.map(Arc::from);
+}
+
+fn proof_limits() -> ProofLimits {
+    PROOF_LIMITS.lock().unwrap().clone()
+}
+
 pub struct Verifier {}
 
 impl Verifier {
@@ -41,6 +68,50 @@ impl Verifier {
         trace!("verify >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
                full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
 
+        let valid = Verifier::_verify(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs, false)?;
+
+        if valid {
+            Verifier::_audit(full_proof, proof_req)?;
+        }
+
+        trace!("verify <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    /// Runs every check of `verify` except the cryptographic proof verification itself,
+    /// so missing or inconsistent inputs are told apart from a proof that doesn't verify.
+    pub fn verify_inputs(&self,
+                         full_proof: &Proof,
+                         proof_req: &ProofRequestPayload,
+                         schemas: &HashMap<SchemaId, SchemaV1>,
+                         cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                         rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                         rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<()> {
+        trace!("verify_inputs >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
+               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        Verifier::_verify(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs, true)?;
+
+        trace!("verify_inputs <<<");
+
+        Ok(())
+    }
+
+    fn _verify(full_proof: &Proof,
+               proof_req: &ProofRequestPayload,
+               schemas: &HashMap<SchemaId, SchemaV1>,
+               cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+               rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+               rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+               dry_run: bool) -> IndyResult<bool> {
+
+        Verifier::_check_proof_limits(&full_proof.requested_proof,
+                                      full_proof.identifiers.len().max(full_proof.proof.proofs.len()),
+                                      &proof_limits())?;
+
+        Verifier::validate_nonce(proof_req, full_proof)?;
+
         let received_revealed_attrs: HashMap<String, Identifier> = Verifier::_received_revealed_attrs(&full_proof)?;
         let received_unrevealed_attrs: HashMap<String, Identifier> = Verifier::_received_unrevealed_attrs(&full_proof)?;
         let received_predicates: HashMap<String, Identifier> = Verifier::_received_predicates(&full_proof)?;
@@ -54,6 +125,9 @@ impl Verifier {
 
         Verifier::_verify_revealed_attribute_values(&proof_req, &full_proof)?;
 
+        proof_req.check_allowed_values(&full_proof.requested_proof)
+            .map_err(|err| err_msg(IndyErrorKind::ProofRejected, err))?;
+
         Verifier::_verify_requested_restrictions(&proof_req,
                                                  &full_proof.requested_proof,
                                                  &received_revealed_attrs,
@@ -61,12 +135,22 @@ impl Verifier {
                                                  &received_predicates,
                                                  &received_self_attested_attrs)?;
 
+        Verifier::_verify_same_issuer(&proof_req,
+                                      &received_revealed_attrs,
+                                      &received_unrevealed_attrs,
+                                      &received_predicates,
+                                      cred_defs)?;
+
+        Verifier::_verify_linked_reveal(&proof_req, &full_proof.requested_proof)?;
+
         Verifier::_compare_timestamps_from_proof_and_request(proof_req,
                                                              &received_revealed_attrs,
                                                              &received_unrevealed_attrs,
                                                              &received_self_attested_attrs,
                                                              &received_predicates)?;
 
+        Verifier::_verify_accumulator_freshness(proof_req, &full_proof.identifiers, Verifier::_now()?)?;
+
         let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
         let non_credential_schema = build_non_credential_schema()?;
 
@@ -85,9 +169,14 @@ impl Verifier {
                         .clone()
                         .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
 
-                    let rev_reg_def = Some(rev_reg_defs
+                    let rev_reg_def = rev_reg_defs
                         .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?;
+
+                    rev_reg_def.validate_cred_def(cred_def)
+                        .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+                    let rev_reg_def = Some(rev_reg_def);
 
                     let rev_regs_for_cred = rev_regs
                         .get(&rev_reg_id)
@@ -103,6 +192,10 @@ impl Verifier {
             let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
             let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
 
+            for predicate in predicates_for_credential.iter() {
+                check_predicate_attribute_type(schema, predicate)?;
+            }
+
             let credential_schema = build_credential_schema(&schema.attr_names)?;
             let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
 
@@ -116,11 +209,215 @@ impl Verifier {
                                                  rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
         }
 
-        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
+        if dry_run {
+            return Ok(true);
+        }
 
-        trace!("verify <<< valid: {:?}", valid);
+        proof_verifier.verify(&full_proof.proof, &proof_req.nonce).map_err(IndyError::from)
+    }
 
-        Ok(valid)
+    pub fn verify_with_result(&self,
+                              full_proof: &Proof,
+                              proof_req: &ProofRequestPayload,
+                              schemas: &HashMap<SchemaId, SchemaV1>,
+                              cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                              rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                              rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<VerificationResult> {
+        trace!("verify_with_result >>> full_proof: {:?}, proof_req: {:?}", full_proof, proof_req);
+
+        let valid = self.verify(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs)?;
+
+        let (referents, revealed_attrs) = if valid {
+            (Verifier::_referent_satisfactions(&full_proof.requested_proof, &full_proof.identifiers),
+             Verifier::_normalized_revealed_attrs(proof_req, &full_proof.requested_proof))
+        } else {
+            (HashMap::new(), HashMap::new())
+        };
+
+        let result = VerificationResult { valid, referents, revealed_attrs };
+
+        trace!("verify_with_result <<< result: {:?}", result);
+
+        Ok(result)
+    }
+
+    /// Verifies proof like `verify` and tells for each requested attribute and predicate whether it
+    /// passed and why it failed. Report is built only from data the proof already discloses.
+    /// The proof is checked as a whole by `verify`, only if every referent passed the other checks,
+    /// so missing or inconsistent inputs are errors the same as for `verify` and `verify_inputs`.
+    pub fn verify_detailed(&self,
+                           full_proof: &Proof,
+                           proof_req: &ProofRequestPayload,
+                           schemas: &HashMap<SchemaId, SchemaV1>,
+                           cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                           rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                           rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<VerificationReport> {
+        trace!("verify_detailed >>> full_proof: {:?}, proof_req: {:?}", full_proof, proof_req);
+
+        let received_revealed_attrs = Verifier::_received_revealed_attrs(&full_proof)?;
+        let received_unrevealed_attrs = Verifier::_received_unrevealed_attrs(&full_proof)?;
+        let received_predicates = Verifier::_received_predicates(&full_proof)?;
+        let received_self_attested_attrs = Verifier::_received_self_attested_attrs(&full_proof);
+
+        let mut crypto_proof = serde_json::to_value(&full_proof.proof)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CryptoProof")?;
+        let sub_proofs = crypto_proof["proofs"].take();
+
+        let mut referents: HashMap<String, (ReferentVerification, Option<u32>)> = HashMap::new();
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            let verification = if let Some(attr) = full_proof.requested_proof.revealed_attrs.get(referent) {
+                let verification = Verifier::_check_referent(referent, &info.name, &info.restrictions, &info.non_revoked,
+                                                             &received_revealed_attrs, Some(&attr.raw), proof_req)?
+                    .or_else(|| Verifier::_check_revealed_value(&info.name, &attr.encoded, &sub_proofs[attr.sub_proof_index as usize]));
+                (verification, Some(attr.sub_proof_index))
+            } else if let Some(sub_proof) = full_proof.requested_proof.unrevealed_attrs.get(referent) {
+                let verification = Verifier::_check_referent(referent, &info.name, &info.restrictions, &info.non_revoked,
+                                                             &received_unrevealed_attrs, None, proof_req)?;
+                (verification, Some(sub_proof.sub_proof_index))
+            } else if received_self_attested_attrs.contains(referent) {
+                let verification = if Verifier::_is_self_attested(referent, info, &received_self_attested_attrs) {
+                    None
+                } else {
+                    Some(ReferentVerification::failed(VerificationFailure::RestrictionsUnsatisfied,
+                                                      "Self attested value is given for attribute with restrictions".to_string()))
+                };
+                (verification, None)
+            } else {
+                (Some(ReferentVerification::failed(VerificationFailure::NotRevealed, "Attribute is missing in proof".to_string())), None)
+            };
+
+            referents.insert(referent.clone(), (verification.0.unwrap_or_else(ReferentVerification::passed), verification.1));
+        }
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            let verification = match full_proof.requested_proof.predicates.get(referent) {
+                Some(sub_proof) => {
+                    let verification = Verifier::_check_referent(referent, &info.name, &info.restrictions, &info.non_revoked,
+                                                                 &received_predicates, None, proof_req)?;
+                    let verification = match verification {
+                        Some(verification) => Some(verification),
+                        None => Verifier::_check_predicate(info, &sub_proofs[sub_proof.sub_proof_index as usize])?
+                    };
+                    (verification, Some(sub_proof.sub_proof_index))
+                }
+                None => (Some(ReferentVerification::failed(VerificationFailure::NotRevealed, "Predicate is missing in proof".to_string())), None)
+            };
+
+            referents.insert(referent.clone(), (verification.0.unwrap_or_else(ReferentVerification::passed), verification.1));
+        }
+
+        let mut error = None;
+
+        let valid = if referents.values().all(|&(ref verification, _)| verification.valid) {
+            match self.verify(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs) {
+                Ok(true) => true,
+                Ok(false) => {
+                    // the proof is verified as a whole, so every credential is suspected; the verifier
+                    // can't tell a witness outdated by revocation from a forged or tampered credential
+                    for referent in referents.values_mut() {
+                        if let Some(sub_proof_index) = referent.1 {
+                            let details = if sub_proofs[sub_proof_index as usize]["non_revoc_proof"].is_null() {
+                                "Cryptographic proof doesn't verify"
+                            } else {
+                                "Cryptographic proof doesn't verify, credential may be forged or revoked"
+                            };
+                            referent.0 = ReferentVerification::failed(VerificationFailure::SignatureInvalid, details.to_string());
+                        }
+                    }
+                    false
+                }
+                Err(ref err) if err.kind() == IndyErrorKind::ProofRejected => {
+                    error = Some(err.to_string());
+                    false
+                }
+                Err(err) => return Err(err)
+            }
+        } else {
+            false
+        };
+
+        if valid {
+            let satisfactions = Verifier::_referent_satisfactions(&full_proof.requested_proof, &full_proof.identifiers);
+
+            for (referent, verification) in referents.iter_mut() {
+                verification.0.mode = satisfactions.get(referent).map(|satisfaction| satisfaction.mode);
+            }
+        }
+
+        let report = VerificationReport {
+            valid,
+            referents: referents.into_iter().map(|(referent, (verification, _))| (referent, verification)).collect(),
+            error,
+        };
+
+        trace!("verify_detailed <<< report: {:?}", report);
+
+        Ok(report)
+    }
+
+    // `None` if referent passes restrictions and non-revocation checks
+    fn _check_referent(referent: &str,
+                       name: &str,
+                       restrictions: &Option<Query>,
+                       non_revoked: &Option<NonRevocedIntervals>,
+                       received: &HashMap<String, Identifier>,
+                       revealed_value: Option<&str>,
+                       proof_req: &ProofRequestPayload) -> IndyResult<Option<ReferentVerification>> {
+        if let Some(ref query) = *restrictions {
+            let filter = Verifier::_gather_filter_info(referent, received)?;
+
+            if let Err(err) = Verifier::_process_operator(name, query, &filter, revealed_value) {
+                return Ok(Some(ReferentVerification::failed(VerificationFailure::RestrictionsUnsatisfied, err.to_string())));
+            }
+        }
+
+        if let Err(err) = Verifier::_validate_timestamp(received, referent, &proof_req.non_revoked, non_revoked) {
+            let details = if err.kind() == IndyErrorKind::ProofRejected {
+                err.to_string()
+            } else {
+                "Non-revocation proof is missing".to_string()
+            };
+            return Ok(Some(ReferentVerification::failed(VerificationFailure::Revoked, details)));
+        }
+
+        Ok(None)
+    }
+
+    fn _check_revealed_value(name: &str, encoded: &str, sub_proof: &Value) -> Option<ReferentVerification> {
+        let signed = sub_proof["primary_proof"]["eq_proof"]["revealed_attrs"].as_object()
+            .and_then(|revealed_attrs| revealed_attrs.iter()
+                .find(|&(key, _)| attr_common_view(key) == attr_common_view(name))
+                .and_then(|(_, value)| value.as_str().map(String::from)));
+
+        if signed.as_ref().map(String::as_str) == Some(encoded) {
+            None
+        } else {
+            Some(ReferentVerification::failed(VerificationFailure::SignatureInvalid,
+                                              format!("Revealed value of \"{}\" doesn't match signed one", name)))
+        }
+    }
+
+    fn _check_predicate(predicate: &PredicateInfo, sub_proof: &Value) -> IndyResult<Option<ReferentVerification>> {
+        let sub_proof_request = build_sub_proof_request(&[], &[predicate.clone()])?;
+
+        let requested = serde_json::to_value(&sub_proof_request)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize SubProofRequest")?;
+
+        let proven: Vec<&Value> = sub_proof["primary_proof"]["ge_proofs"].as_array()
+            .map(|ge_proofs| ge_proofs.iter().map(|ge_proof| &ge_proof["predicate"]).collect())
+            .unwrap_or_default();
+
+        let satisfied = requested["predicates"].as_array()
+            .map(|predicates| predicates.iter().all(|predicate| proven.contains(&predicate)))
+            .unwrap_or(false);
+
+        if satisfied {
+            Ok(None)
+        } else {
+            Ok(Some(ReferentVerification::failed(VerificationFailure::PredicateUnsatisfied,
+                                                 format!("Proof doesn't prove requested predicate on \"{}\"", predicate.name))))
+        }
     }
 
     pub fn generate_nonce(&self) -> IndyResult<Nonce> {
@@ -171,6 +468,30 @@ impl Verifier {
         Ok(predicates_for_credential)
     }
 
+    /// Checks that proof was built for the nonce of this proof request. Proofs of older provers
+    /// don't carry the nonce and are left to the cryptographic check, which binds it into the challenge.
+    pub fn validate_nonce(proof_req: &ProofRequestPayload, full_proof: &Proof) -> IndyResult<()> {
+        match full_proof.nonce {
+            Some(ref nonce) => Verifier::_check_nonce(&proof_req.nonce.to_dec()?, nonce),
+            None => Ok(())
+        }
+    }
+
+    fn _check_nonce(expected: &str, nonce: &str) -> IndyResult<()> {
+        if nonce.is_empty() || !nonce.chars().all(|c| c.is_ascii_digit()) {
+            return Err(err_msg(IndyErrorKind::ProofRejected, format!("Invalid proof nonce: \"{}\"", nonce)));
+        }
+
+        let nonce = Nonce::from_dec(nonce)
+            .map_err(|_| err_msg(IndyErrorKind::ProofRejected, format!("Invalid proof nonce: \"{}\"", nonce)))?;
+
+        if nonce.to_dec()? != expected {
+            return Err(err_msg(IndyErrorKind::ProofRejected, "Nonce mismatch"));
+        }
+
+        Ok(())
+    }
+
     fn _compare_attr_from_proof_and_request(proof_req: &ProofRequestPayload,
                                             received_revealed_attrs: &HashMap<String, Identifier>,
                                             received_unrevealed_attrs: &HashMap<String, Identifier>,
@@ -235,16 +556,22 @@ impl Verifier {
     }
 
     fn _validate_timestamp(received_: &HashMap<String, Identifier>, referent: &str,
-                           global_interval: &Option<NonRevocedInterval>, local_interval: &Option<NonRevocedInterval>) -> IndyResult<()> {
-        if get_non_revoc_interval(global_interval, local_interval).is_none() {
+                           global_interval: &Option<NonRevocedInterval>, local_intervals: &Option<NonRevocedIntervals>) -> IndyResult<()> {
+        if get_non_revoc_intervals(global_interval, local_intervals).is_empty() {
             return Ok(());
         }
 
-        if !received_
+        let timestamp = received_
             .get(referent)
-            .map(|attr| attr.timestamp.is_some())
-            .unwrap_or(false) {
-            return Err(IndyError::from(IndyErrorKind::InvalidStructure));
+            .and_then(|attr| attr.timestamp)
+            .ok_or_else(|| IndyError::from(IndyErrorKind::InvalidStructure))?;
+
+        // a list of alternative intervals is satisfied by any of them containing the timestamp
+        if let Some(NonRevocedIntervals::Any(ref intervals)) = *local_intervals {
+            if !intervals.iter().any(|interval| interval.contains(timestamp)) {
+                return Err(err_msg(IndyErrorKind::ProofRejected,
+                                   format!("Timestamp {} of \"{}\" is not within any of requested non-revocation intervals", timestamp, referent)));
+            }
         }
 
         Ok(())
@@ -300,17 +627,36 @@ impl Verifier {
             ))
     }
 
+    fn _check_proof_limits(requested_proof: &RequestedProof,
+                           sub_proofs: usize,
+                           limits: &ProofLimits) -> IndyResult<()> {
+        let checks = [
+            ("max_sub_proofs", sub_proofs, limits.max_sub_proofs),
+            ("max_revealed_attrs", requested_proof.revealed_attrs.len(), limits.max_revealed_attrs),
+            ("max_predicates", requested_proof.predicates.len(), limits.max_predicates),
+        ];
+
+        for &(limit_name, count, limit) in checks.iter() {
+            if count > limit {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Proof exceeds {} limit: {} > {}", limit_name, count, limit)));
+            }
+        }
+
+        Ok(())
+    }
+
     fn _verify_revealed_attribute_values(proof_req: &ProofRequestPayload,
                                          proof: &Proof) -> IndyResult<()> {
         for (attr_referent, attr_info) in proof.requested_proof.revealed_attrs.iter() {
-            let reveal_attr_encoded = attr_info.encoded.to_string();
-            let sub_proof_index = attr_info.sub_proof_index as usize;
-
             let attr_name = proof_req.requested_attributes.get(attr_referent.as_str())
                 .as_ref()
                 .map(|attr_info| attr_info.name.as_str())
                 .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
 
+            let reveal_attr_encoded = attr_info.encoded.to_string();
+            let sub_proof_index = attr_info.sub_proof_index as usize;
+
             let crypto_proof_encoded = proof.proof.proofs
                 .get(sub_proof_index)
                 .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("CryptoProof not found by index \"{}\"", sub_proof_index)))?
@@ -325,6 +671,7 @@ impl Verifier {
                                                format!("Encoded Values for \"{}\" are different in RequestedProof \"{}\" and CryptoProof \"{}\"", attr_name, reveal_attr_encoded, crypto_proof_encoded)));
             }
         }
+
         Ok(())
     }
 
@@ -368,6 +715,167 @@ impl Verifier {
         Ok(())
     }
 
+    fn _verify_same_issuer(proof_req: &ProofRequestPayload,
+                           received_revealed_attrs: &HashMap<String, Identifier>,
+                           received_unrevealed_attrs: &HashMap<String, Identifier>,
+                           received_predicates: &HashMap<String, Identifier>,
+                           cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>) -> IndyResult<()> {
+        for group in proof_req.same_issuer.iter().flatten() {
+            let mut group_issuer_key: Option<String> = None;
+
+            for referent in group {
+                let identifier = received_revealed_attrs.get(referent)
+                    .or_else(|| received_unrevealed_attrs.get(referent))
+                    .or_else(|| received_predicates.get(referent))
+                    .ok_or_else(|| err_msg(IndyErrorKind::ProofRejected,
+                                           format!("Referent \"{}\" from `same_issuer` group is not proven by a credential", referent)))?;
+
+                let cred_def = cred_defs.get(&identifier.cred_def_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+
+                let issuer_key = Verifier::_issuer_key_modulus(cred_def)?;
+
+                match group_issuer_key {
+                    Some(ref group_issuer_key) if *group_issuer_key != issuer_key =>
+                        return Err(err_msg(IndyErrorKind::ProofRejected,
+                                           format!("Credentials for `same_issuer` group {:?} are signed with different issuer keys", group))),
+                    Some(_) => {}
+                    None => group_issuer_key = Some(issuer_key)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // sub-proofs are verified against the primary public key, so its modulus `n` identifies the signing key
+    fn _issuer_key_modulus(cred_def: &CredentialDefinitionV1) -> IndyResult<String> {
+        let primary = serde_json::to_value(&cred_def.value.primary)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialPrimaryPublicKey")?;
+
+        primary["n"].as_str()
+            .map(String::from)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential Definition {} has no primary key modulus", cred_def.id.0)))
+    }
+
+    fn _verify_linked_reveal(proof_req: &ProofRequestPayload,
+                             requested_proof: &RequestedProof) -> IndyResult<()> {
+        for (group, referents) in proof_req.linked_reveal.iter().flatten() {
+            let sub_proof_indexes: HashSet<u32> = referents.iter()
+                .filter_map(|referent| requested_proof.revealed_attrs.get(referent))
+                .map(|attr| attr.sub_proof_index)
+                .collect();
+
+            let revealed_count = referents.iter()
+                .filter(|referent| requested_proof.revealed_attrs.contains_key(*referent))
+                .count();
+
+            if revealed_count != 0 && revealed_count != referents.len() {
+                return Err(err_msg(IndyErrorKind::ProofRejected,
+                                   format!("Only some referents from `linked_reveal` group \"{}\" are revealed", group)));
+            }
+
+            if sub_proof_indexes.len() > 1 {
+                return Err(err_msg(IndyErrorKind::ProofRejected,
+                                   format!("Referents from `linked_reveal` group \"{}\" are revealed from different credentials", group)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _verify_accumulator_freshness(proof_req: &ProofRequestPayload,
+                                     identifiers: &[Identifier],
+                                     now: u64) -> IndyResult<()> {
+        let max_accum_age_secs = match proof_req.max_accum_age_secs {
+            Some(max_accum_age_secs) => max_accum_age_secs,
+            None => return Ok(())
+        };
+
+        for timestamp in identifiers.iter().filter_map(|identifier| identifier.timestamp) {
+            if now.saturating_sub(timestamp) > max_accum_age_secs {
+                return Err(err_msg(IndyErrorKind::ProofRejected,
+                                   format!("Accumulator of timestamp {} is older than {} seconds", timestamp, max_accum_age_secs)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _audit(full_proof: &Proof, proof_req: &ProofRequestPayload) -> IndyResult<()> {
+        // the lock isn't held while the sink runs, so the sink may be replaced meanwhile
+        let sink = AUDIT_SINK.lock().unwrap().clone();
+
+        if let Some(sink) = sink {
+            let record = VerificationAuditRecord {
+                nonce: proof_req.nonce.to_dec()?,
+                revealed_attrs: full_proof.requested_proof.revealed_attrs.keys().cloned().collect(),
+                predicates: full_proof.requested_proof.predicates.keys().cloned().collect(),
+                issuer_dids: full_proof.identifiers.iter()
+                    .filter_map(|identifier| identifier.cred_def_id.issuer_did())
+                    .map(|did| did.0)
+                    .collect(),
+                timestamp: Verifier::_now()?,
+            };
+
+            sink.record(&record);
+        }
+
+        Ok(())
+    }
+
+    fn _now() -> IndyResult<u64> {
+        SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .to_indy(IndyErrorKind::InvalidState, "Time has gone backwards")
+    }
+
+    fn _normalized_revealed_attrs(proof_req: &ProofRequestPayload,
+                                  requested_proof: &RequestedProof) -> HashMap<String, String> {
+        requested_proof.revealed_attrs
+            .iter()
+            .map(|(referent, attr_info)| {
+                let raw = match proof_req.normalize.as_ref().and_then(|normalize| normalize.get(referent)) {
+                    Some(steps) => Normalization::apply_all(steps, &attr_info.raw),
+                    None => attr_info.raw.clone()
+                };
+                (referent.clone(), raw)
+            })
+            .collect()
+    }
+
+    fn _referent_satisfactions(requested_proof: &RequestedProof,
+                               identifiers: &[Identifier]) -> HashMap<String, ReferentSatisfaction> {
+        let satisfaction = |mode: SatisfactionMode, sub_proofs: &[&SubProofReferent]| ReferentSatisfaction {
+            mode,
+            non_revoked_at: sub_proofs.iter()
+                .filter_map(|sub_proof| identifiers.get(sub_proof.sub_proof_index as usize))
+                .filter_map(|identifier| identifier.timestamp)
+                .collect(),
+        };
+
+        let mut referents: HashMap<String, ReferentSatisfaction> = HashMap::new();
+
+        for (referent, info) in requested_proof.revealed_attrs.iter() {
+            let sub_proof = SubProofReferent { sub_proof_index: info.sub_proof_index };
+            referents.insert(referent.clone(), satisfaction(SatisfactionMode::Revealed, &[&sub_proof]));
+        }
+
+        for (referent, sub_proof) in requested_proof.unrevealed_attrs.iter() {
+            referents.insert(referent.clone(), satisfaction(SatisfactionMode::Unrevealed, &[sub_proof]));
+        }
+
+        for (referent, sub_proof) in requested_proof.predicates.iter() {
+            referents.insert(referent.clone(), satisfaction(SatisfactionMode::Predicate, &[sub_proof]));
+        }
+
+        for referent in requested_proof.self_attested_attrs.keys() {
+            referents.insert(referent.clone(), satisfaction(SatisfactionMode::SelfAttested, &[]));
+        }
+
+        referents
+    }
+
     fn _is_self_attested(referent: &str, info: &AttributeInfo, self_attested_attrs: &HashSet<String>) -> bool {
         match info.restrictions.as_ref() {
             Some(&Query::And(ref array)) | Some(&Query::Or(ref array)) if array.is_empty() =>
@@ -469,6 +977,7 @@ impl Verifier {
             tag_ @ "schema_issuer_did" => Verifier::_precess_filed(tag_, &filter.schema_issuer_did, tag_value),
             tag_ @ "schema_name" => Verifier::_precess_filed(tag_, &filter.schema_name, tag_value),
             tag_ @ "schema_version" => Verifier::_precess_filed(tag_, &filter.schema_version, tag_value),
+            SchemaVersionRange::TAG => Verifier::_process_schema_version_range(&filter.schema_version, tag_value),
             tag_ @ "cred_def_id" => Verifier::_precess_filed(tag_, &filter.cred_def_id, tag_value),
             tag_ @ "issuer_did" => Verifier::_precess_filed(tag_, &filter.issuer_did, tag_value),
             x if Verifier::_is_attr_internal_tag(x, attr) => Verifier::_check_internal_tag_revealed_value(x, attr, tag_value, revealed_value),
@@ -485,6 +994,17 @@ impl Verifier {
         }
     }
 
+    fn _process_schema_version_range(schema_version: &str, range: &str) -> IndyResult<()> {
+        let range = SchemaVersionRange::parse(range)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        if range.contains(schema_version) {
+            Ok(())
+        } else {
+            Err(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("\"{}\" value \"{}\" is out of range", SchemaVersionRange::TAG, schema_version)))
+        }
+    }
+
     fn _is_attr_internal_tag(key: &str, attr: &str) -> bool {
         key == format!("attr::{}::value", attr) || key == format!("attr::{}::marker", attr)
     }
@@ -778,6 +1298,31 @@ mod tests {
         assert!(Verifier::_process_operator("zip", &op, &filter, Some("NOT HERE")).is_err());
     }
 
+    #[test]
+    fn test_process_op_eq_schema_version_range() {
+        let mut filter = filter();
+        let op = Query::Eq(SchemaVersionRange::TAG.to_string(), ">=1.0,<2.0".to_string());
+
+        filter.schema_version = "1.2".to_string();
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        filter.schema_version = "2.1".to_string();
+        let res = Verifier::_process_operator("zip", &op, &filter, None);
+        assert_kind!(IndyErrorKind::ProofRejected, res);
+    }
+
+    #[test]
+    fn test_process_op_in_revealed_value() {
+        let filter = filter();
+        let allowed_values = vec!["10001".to_string(), "10002".to_string(), "10003".to_string()];
+
+        let op = Query::In(attr_tag_value(), allowed_values.clone());
+        Verifier::_process_operator("zip", &op, &filter, Some("10002")).unwrap();
+
+        let res = Verifier::_process_operator("zip", &op, &filter, Some("20001"));
+        assert_kind!(IndyErrorKind::ProofRejected, res);
+    }
+
     fn _received() -> HashMap<String, Identifier> {
         let mut res: HashMap<String, Identifier> = HashMap::new();
         res.insert("referent_1".to_string(), Identifier { timestamp: Some(1234), schema_id: SchemaId(String::new()), cred_def_id: CredentialDefinitionId(String::new()), rev_reg_id: Some(RevocationRegistryId(String::new())) });
@@ -802,4 +1347,344 @@ mod tests {
         Verifier::_validate_timestamp(&_received(), "referent_2", &None, &Some(_interval())).unwrap_err();
         Verifier::_validate_timestamp(&_received(), "referent_3", &None, &Some(_interval())).unwrap_err();
     }
+
+    mod verify_same_issuer {
+        use super::*;
+
+        const CRED_DEF_ID_ISSUER_1: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag";
+        const CRED_DEF_ID_ISSUER_1_XYZ: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:xyz:1.0:tag";
+        const CRED_DEF_ID_ISSUER_1_ROTATED: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:rotated";
+
+        fn _identifier(cred_def_id: &str) -> Identifier {
+            Identifier { timestamp: None, schema_id: SchemaId(String::new()), cred_def_id: CredentialDefinitionId(cred_def_id.to_string()), rev_reg_id: None }
+        }
+
+        fn _cred_def(cred_def_id: &str, n: &str) -> CredentialDefinitionV1 {
+            serde_json::from_value(json!({
+                "id": cred_def_id,
+                "schemaId": "1",
+                "type": "CL",
+                "tag": "tag",
+                "value": {
+                    "primary": {"n": n, "s": "2", "r": {"master_secret": "3", "name": "4"}, "rctxt": "5", "z": "6"}
+                }
+            })).unwrap()
+        }
+
+        // gvt and xyz credential definitions share the issuer key, the rotated one doesn't
+        fn _cred_defs() -> HashMap<CredentialDefinitionId, CredentialDefinitionV1> {
+            let mut cred_defs: HashMap<CredentialDefinitionId, CredentialDefinitionV1> = HashMap::new();
+            cred_defs.insert(CredentialDefinitionId(CRED_DEF_ID_ISSUER_1.to_string()), _cred_def(CRED_DEF_ID_ISSUER_1, "1001"));
+            cred_defs.insert(CredentialDefinitionId(CRED_DEF_ID_ISSUER_1_XYZ.to_string()), _cred_def(CRED_DEF_ID_ISSUER_1_XYZ, "1001"));
+            cred_defs.insert(CredentialDefinitionId(CRED_DEF_ID_ISSUER_1_ROTATED.to_string()), _cred_def(CRED_DEF_ID_ISSUER_1_ROTATED, "2003"));
+            cred_defs
+        }
+
+        fn _proof_req() -> ProofRequestPayload {
+            ProofRequestPayload {
+                same_issuer: Some(vec![vec!["attr_referent".to_string(), "predicate_referent".to_string()]]),
+                ..Default::default()
+            }
+        }
+
+        fn _received(cred_def_id: &str) -> HashMap<String, Identifier> {
+            let mut res: HashMap<String, Identifier> = HashMap::new();
+            res.insert("attr_referent".to_string(), _identifier(cred_def_id));
+            res
+        }
+
+        fn _predicates(cred_def_id: &str) -> HashMap<String, Identifier> {
+            let mut res: HashMap<String, Identifier> = HashMap::new();
+            res.insert("predicate_referent".to_string(), _identifier(cred_def_id));
+            res
+        }
+
+        #[test]
+        fn verify_same_issuer_works() {
+            Verifier::_verify_same_issuer(&_proof_req(),
+                                          &_received(CRED_DEF_ID_ISSUER_1),
+                                          &HashMap::new(),
+                                          &_predicates(CRED_DEF_ID_ISSUER_1_XYZ),
+                                          &_cred_defs()).unwrap();
+        }
+
+        #[test]
+        fn verify_same_issuer_works_for_different_keys_of_same_issuer_did() {
+            let res = Verifier::_verify_same_issuer(&_proof_req(),
+                                                    &_received(CRED_DEF_ID_ISSUER_1),
+                                                    &HashMap::new(),
+                                                    &_predicates(CRED_DEF_ID_ISSUER_1_ROTATED),
+                                                    &_cred_defs());
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+
+        #[test]
+        fn verify_same_issuer_works_for_missed_referent() {
+            let res = Verifier::_verify_same_issuer(&_proof_req(),
+                                                    &_received(CRED_DEF_ID_ISSUER_1),
+                                                    &HashMap::new(),
+                                                    &HashMap::new(),
+                                                    &_cred_defs());
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+
+        #[test]
+        fn verify_same_issuer_works_for_missed_cred_def() {
+            let res = Verifier::_verify_same_issuer(&_proof_req(),
+                                                    &_received(CRED_DEF_ID_ISSUER_1),
+                                                    &HashMap::new(),
+                                                    &_predicates(CRED_DEF_ID_ISSUER_1_XYZ),
+                                                    &HashMap::new());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    mod verify_linked_reveal {
+        use super::*;
+
+        use domain::anoncreds::proof::RevealedAttributeInfo;
+
+        fn _proof_req() -> ProofRequestPayload {
+            let mut linked_reveal: HashMap<String, Vec<String>> = HashMap::new();
+            linked_reveal.insert("full_name".to_string(), vec!["first_name_referent".to_string(), "last_name_referent".to_string()]);
+
+            ProofRequestPayload {
+                linked_reveal: Some(linked_reveal),
+                ..Default::default()
+            }
+        }
+
+        fn _requested_proof(revealed: &[(&str, u32)]) -> RequestedProof {
+            let mut requested_proof = RequestedProof::default();
+            for &(referent, sub_proof_index) in revealed {
+                requested_proof.revealed_attrs.insert(referent.to_string(),
+                                                      RevealedAttributeInfo { sub_proof_index, raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string() });
+            }
+            requested_proof
+        }
+
+        #[test]
+        fn verify_linked_reveal_works() {
+            Verifier::_verify_linked_reveal(&_proof_req(),
+                                            &_requested_proof(&[("first_name_referent", 0), ("last_name_referent", 0)])).unwrap();
+        }
+
+        #[test]
+        fn verify_linked_reveal_works_for_nothing_revealed() {
+            Verifier::_verify_linked_reveal(&_proof_req(), &_requested_proof(&[])).unwrap();
+        }
+
+        #[test]
+        fn verify_linked_reveal_works_for_proper_subset_revealed() {
+            let res = Verifier::_verify_linked_reveal(&_proof_req(), &_requested_proof(&[("first_name_referent", 0)]));
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+
+        #[test]
+        fn verify_linked_reveal_works_for_different_credentials() {
+            let res = Verifier::_verify_linked_reveal(&_proof_req(),
+                                                      &_requested_proof(&[("first_name_referent", 0), ("last_name_referent", 1)]));
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+    }
+
+    mod normalized_revealed_attrs {
+        use super::*;
+        use domain::anoncreds::proof::RevealedAttributeInfo;
+
+        fn _proof_req(normalize: Option<HashMap<String, Vec<Normalization>>>) -> ProofRequestPayload {
+            ProofRequestPayload {
+                normalize,
+                ..Default::default()
+            }
+        }
+
+        fn _requested_proof() -> RequestedProof {
+            let mut requested_proof = RequestedProof::default();
+            requested_proof.revealed_attrs.insert("email_referent".to_string(),
+                                                  RevealedAttributeInfo { sub_proof_index: 0, raw: "  Alex@X.COM ".to_string(), encoded: "1".to_string() });
+            requested_proof.revealed_attrs.insert("name_referent".to_string(),
+                                                  RevealedAttributeInfo { sub_proof_index: 0, raw: " Alex ".to_string(), encoded: "2".to_string() });
+            requested_proof
+        }
+
+        #[test]
+        fn normalized_revealed_attrs_works() {
+            let mut normalize: HashMap<String, Vec<Normalization>> = HashMap::new();
+            normalize.insert("email_referent".to_string(), vec![Normalization::Lowercase, Normalization::Trim]);
+
+            let revealed_attrs = Verifier::_normalized_revealed_attrs(&_proof_req(Some(normalize)), &_requested_proof());
+
+            assert_eq!("alex@x.com", revealed_attrs["email_referent"]);
+            assert_eq!(" Alex ", revealed_attrs["name_referent"]);
+        }
+
+        #[test]
+        fn normalized_revealed_attrs_works_without_spec() {
+            let revealed_attrs = Verifier::_normalized_revealed_attrs(&_proof_req(None), &_requested_proof());
+
+            assert_eq!("  Alex@X.COM ", revealed_attrs["email_referent"]);
+        }
+    }
+
+    mod validate_nonce {
+        use super::*;
+
+        const NONCE: &str = "123432421212";
+
+        #[test]
+        fn check_nonce_works() {
+            Verifier::_check_nonce(NONCE, NONCE).unwrap();
+            Verifier::_check_nonce(NONCE, &format!("00{}", NONCE)).unwrap();
+        }
+
+        #[test]
+        fn check_nonce_works_for_mismatch() {
+            let res = Verifier::_check_nonce(NONCE, "123432421213");
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+
+        #[test]
+        fn check_nonce_works_for_empty_nonce() {
+            let res = Verifier::_check_nonce(NONCE, "");
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+
+        #[test]
+        fn check_nonce_works_for_not_a_number() {
+            let res = Verifier::_check_nonce(NONCE, "12343242121a");
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+
+            let res = Verifier::_check_nonce(NONCE, "-123432421212");
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+    }
+
+    mod check_proof_limits {
+        use super::*;
+        use domain::anoncreds::proof::RevealedAttributeInfo;
+
+        fn _limits() -> ProofLimits {
+            ProofLimits { max_sub_proofs: 2, max_revealed_attrs: 2, max_predicates: 2 }
+        }
+
+        fn _requested_proof(revealed_attrs: usize, predicates: usize) -> RequestedProof {
+            let mut requested_proof = RequestedProof::default();
+            for i in 0..revealed_attrs {
+                requested_proof.revealed_attrs.insert(format!("attr{}_referent", i),
+                                                      RevealedAttributeInfo { sub_proof_index: 0, raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string() });
+            }
+            for i in 0..predicates {
+                requested_proof.predicates.insert(format!("predicate{}_referent", i), SubProofReferent { sub_proof_index: 0 });
+            }
+            requested_proof
+        }
+
+        #[test]
+        fn check_proof_limits_works() {
+            Verifier::_check_proof_limits(&_requested_proof(2, 2), 2, &_limits()).unwrap();
+        }
+
+        #[test]
+        fn check_proof_limits_works_for_too_many_sub_proofs() {
+            let res = Verifier::_check_proof_limits(&_requested_proof(1, 1), 3, &_limits());
+            let err = res.unwrap_err();
+            assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+            assert!(err.to_string().contains("max_sub_proofs"));
+        }
+
+        #[test]
+        fn check_proof_limits_works_for_too_many_revealed_attrs() {
+            let res = Verifier::_check_proof_limits(&_requested_proof(3, 1), 1, &_limits());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_proof_limits_works_for_too_many_predicates() {
+            let res = Verifier::_check_proof_limits(&_requested_proof(1, 3), 1, &_limits());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn proof_limits_deserialize_works_for_partial_config() {
+            let limits: ProofLimits = serde_json::from_str(r#"{"max_sub_proofs": 10}"#).unwrap();
+            assert_eq!(10, limits.max_sub_proofs);
+            assert_eq!(ProofLimits::default().max_predicates, limits.max_predicates);
+        }
+    }
+
+    mod verify_accumulator_freshness {
+        use super::*;
+
+        const NOW: u64 = 1_600_000_000;
+
+        fn _identifier(timestamp: Option<u64>) -> Identifier {
+            Identifier {
+                timestamp,
+                schema_id: SchemaId(SCHEMA_ID.to_string()),
+                cred_def_id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+                rev_reg_id: None,
+            }
+        }
+
+        fn _proof_req(max_accum_age_secs: Option<u64>) -> ProofRequestPayload {
+            ProofRequestPayload {
+                max_accum_age_secs,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn verify_accumulator_freshness_works() {
+            Verifier::_verify_accumulator_freshness(&_proof_req(Some(60)),
+                                                    &[_identifier(Some(NOW - 60)), _identifier(None)],
+                                                    NOW).unwrap();
+        }
+
+        #[test]
+        fn verify_accumulator_freshness_works_for_no_bound() {
+            Verifier::_verify_accumulator_freshness(&_proof_req(None), &[_identifier(Some(0))], NOW).unwrap();
+        }
+
+        #[test]
+        fn verify_accumulator_freshness_works_for_stale_accumulator() {
+            let res = Verifier::_verify_accumulator_freshness(&_proof_req(Some(60)),
+                                                              &[_identifier(Some(NOW)), _identifier(Some(NOW - 61))],
+                                                              NOW);
+            assert_kind!(IndyErrorKind::ProofRejected, res);
+        }
+    }
+
+    mod referent_satisfactions {
+        use super::*;
+
+        use domain::anoncreds::proof::RevealedAttributeInfo;
+
+        fn _identifier(timestamp: Option<u64>) -> Identifier {
+            Identifier {
+                timestamp,
+                schema_id: SchemaId(SCHEMA_ID.to_string()),
+                cred_def_id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+                rev_reg_id: None,
+            }
+        }
+
+        #[test]
+        fn referent_satisfactions_works_for_mixed_request() {
+            let mut requested_proof = RequestedProof::default();
+            requested_proof.revealed_attrs.insert("attr1_referent".to_string(),
+                                                  RevealedAttributeInfo { sub_proof_index: 0, raw: "Alex".to_string(), encoded: "1".to_string() });
+            requested_proof.unrevealed_attrs.insert("attr2_referent".to_string(), SubProofReferent { sub_proof_index: 1 });
+            requested_proof.self_attested_attrs.insert("attr3_referent".to_string(), "8-800-300".to_string());
+            requested_proof.predicates.insert("predicate1_referent".to_string(), SubProofReferent { sub_proof_index: 1 });
+
+            let referents = Verifier::_referent_satisfactions(&requested_proof, &[_identifier(None), _identifier(Some(100))]);
+
+            assert_eq!(4, referents.len());
+            assert_eq!(ReferentSatisfaction { mode: SatisfactionMode::Revealed, non_revoked_at: vec![] }, referents["attr1_referent"]);
+            assert_eq!(ReferentSatisfaction { mode: SatisfactionMode::Unrevealed, non_revoked_at: vec![100] }, referents["attr2_referent"]);
+            assert_eq!(ReferentSatisfaction { mode: SatisfactionMode::SelfAttested, non_revoked_at: vec![] }, referents["attr3_referent"]);
+            assert_eq!(ReferentSatisfaction { mode: SatisfactionMode::Predicate, non_revoked_at: vec![100] }, referents["predicate1_referent"]);
+        }
+    }
 }
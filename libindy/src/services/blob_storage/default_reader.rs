@@ -63,13 +63,11 @@ impl ReadableBlob for DefaultReader {
         Ok(())
     }
 
-    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
-        let mut buf = vec![0u8; size];
+    fn read_range(&mut self, offset: usize, len: usize) -> IndyResult<Vec<u8>> {
+        let mut buf = Vec::with_capacity(len);
 
         self.file.seek(SeekFrom::Start(offset as u64))?;
-        let act_size = self.file.read(buf.as_mut_slice())?;
-
-        buf.truncate(act_size);
+        (&mut self.file).take(len as u64).read_to_end(&mut buf)?;
 
         Ok(buf)
     }
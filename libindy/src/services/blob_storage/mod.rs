@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use errors::prelude::*;
 use utils::sequence;
@@ -32,7 +32,8 @@ trait Reader {
 }
 
 trait ReadableBlob {
-    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>>;
+    /// Returns `len` bytes starting at `offset`, or less if the blob ends earlier.
+    fn read_range(&mut self, offset: usize, len: usize) -> IndyResult<Vec<u8>>;
     fn verify(&mut self) -> IndyResult<bool>;
     fn close(&self) -> IndyResult<()>;
 }
@@ -45,6 +46,8 @@ pub struct BlobStorageService {
     reader_types: RefCell<HashMap<String, Box<dyn ReaderType>>>,
     reader_configs: RefCell<HashMap<i32, Box<dyn Reader>>>,
     reader_blobs: RefCell<HashMap<i32, Box<dyn ReadableBlob>>>,
+    // (reader config handle, location, hash) of blobs whose content already matched their hash
+    verified_blobs: RefCell<HashSet<(i32, String, Vec<u8>)>>,
 }
 
 impl BlobStorageService {
@@ -62,6 +65,7 @@ impl BlobStorageService {
             reader_types: RefCell::new(reader_types),
             reader_configs: RefCell::new(HashMap::new()),
             reader_blobs: RefCell::new(HashMap::new()),
+            verified_blobs: RefCell::new(HashSet::new()),
         }
     }
 }
@@ -135,10 +139,34 @@ impl BlobStorageService {
         Ok(reader_handle)
     }
 
+    /// Opens the blob like `open_blob`, but first checks its content against `hash` recorded on write,
+    /// so a corrupted tails file is reported here instead of as a failed proof later.
+    /// Content is hashed only on the first open through the reader config, later opens trust that result.
+    pub fn open_verified_blob(&self, config_handle: i32, location: &str, hash: &[u8]) -> IndyResult<i32> {
+        let reader_handle = self.open_blob(config_handle, location, hash)?;
+
+        let key = (config_handle, location.to_string(), hash.to_vec());
+
+        if self.verified_blobs.try_borrow()?.contains(&key) {
+            return Ok(reader_handle);
+        }
+
+        match self._verify(reader_handle) {
+            Ok(true) => {
+                self.verified_blobs.try_borrow_mut()?.insert(key);
+                Ok(reader_handle)
+            }
+            res => {
+                self.close(reader_handle)?;
+                res.and_then(|_| Err(err_msg(IndyErrorKind::InvalidStructure, "Tails file hash mismatch")))
+            }
+        }
+    }
+
     pub fn read(&self, handle: i32, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
         self.reader_blobs.try_borrow_mut()?
             .get_mut(&handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage handle"))? // FIXME: Review error kind
-            .read(size, offset)
+            .read_range(offset, size)
     }
 
     pub fn _verify(&self, handle: i32) -> IndyResult<bool> {
@@ -153,3 +181,113 @@ impl BlobStorageService {
             .close()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use utils::environment;
+    use utils::test;
+
+    fn _blob(name: &str, content: &[u8]) -> (BlobStorageService, i32) {
+        let (service, config, location, hash) = _write_blob(name, content);
+
+        let reader_handle = service.open_reader("default", &config).unwrap();
+        let blob_handle = service.open_blob(reader_handle, &location, &hash).unwrap();
+
+        (service, blob_handle)
+    }
+
+    fn _content() -> Vec<u8> {
+        (0..1000).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn _write_blob(name: &str, content: &[u8]) -> (BlobStorageService, String, String, Vec<u8>) {
+        test::cleanup_temp(name);
+
+        let base_dir = environment::tmp_path().join(name);
+        let config = json!({"base_dir": base_dir.to_str().unwrap(), "uri_pattern": ""}).to_string();
+
+        let service = BlobStorageService::new();
+
+        let writer_handle = service.open_writer("default", &config).unwrap();
+        let blob_handle = service.create_blob(writer_handle).unwrap();
+        service.append(blob_handle, content).unwrap();
+        let (location, hash) = service.finalize(blob_handle).unwrap();
+
+        (service, config, location, hash)
+    }
+
+    #[test]
+    fn open_verified_blob_works() {
+        let content = _content();
+        let (service, config, location, hash) = _write_blob("open_verified_blob_works", &content);
+
+        let reader_handle = service.open_reader("default", &config).unwrap();
+        let blob_handle = service.open_verified_blob(reader_handle, &location, &hash).unwrap();
+
+        assert_eq!(content, service.read(blob_handle, content.len(), 0).unwrap());
+    }
+
+    #[test]
+    fn open_verified_blob_works_for_flipped_byte() {
+        let content = _content();
+        let (service, config, location, hash) = _write_blob("open_verified_blob_works_for_flipped_byte", &content);
+
+        let mut corrupted = content.clone();
+        corrupted[500] ^= 0x01;
+        ::std::fs::write(&location, &corrupted).unwrap();
+
+        let reader_handle = service.open_reader("default", &config).unwrap();
+        let res = service.open_verified_blob(reader_handle, &location, &hash);
+
+        let err = res.unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("Tails file hash mismatch"));
+    }
+
+    #[test]
+    fn open_verified_blob_works_for_already_verified_blob() {
+        let content = _content();
+        let (service, config, location, hash) = _write_blob("open_verified_blob_works_for_already_verified_blob", &content);
+
+        let reader_handle = service.open_reader("default", &config).unwrap();
+        let blob_handle = service.open_verified_blob(reader_handle, &location, &hash).unwrap();
+        service.close(blob_handle).unwrap();
+
+        // content is not hashed again, so a later change is not noticed through the same reader
+        let mut corrupted = content.clone();
+        corrupted[500] ^= 0x01;
+        ::std::fs::write(&location, &corrupted).unwrap();
+
+        let blob_handle = service.open_verified_blob(reader_handle, &location, &hash).unwrap();
+        assert_eq!(corrupted, service.read(blob_handle, corrupted.len(), 0).unwrap());
+
+        let other_reader_handle = service.open_reader("default", &config).unwrap();
+        let res = service.open_verified_blob(other_reader_handle, &location, &hash);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn read_works_for_three_ranges() {
+        let content = _content();
+        let (service, handle) = _blob("read_works_for_three_ranges", &content);
+
+        let full = service.read(handle, content.len(), 0).unwrap();
+        assert_eq!(content, full);
+
+        let mut assembled = service.read(handle, 400, 0).unwrap();
+        assembled.extend(service.read(handle, 400, 400).unwrap());
+        assembled.extend(service.read(handle, 400, 800).unwrap());
+
+        assert_eq!(full, assembled);
+    }
+
+    #[test]
+    fn read_works_for_range_after_end() {
+        let content = _content();
+        let (service, handle) = _blob("read_works_for_range_after_end", &content);
+
+        assert!(service.read(handle, 10, content.len()).unwrap().is_empty());
+    }
+}
@@ -103,6 +103,7 @@ impl LedgerService {
 
     #[logfn(Info)]
     pub fn build_schema_request(&self, identifier: &DidValue, schema: SchemaV1) -> IndyResult<String> {
+        // Attribute types have no place in SCHEMA transaction, they are dropped here.
         let schema_data = SchemaOperationData::new(schema.name, schema.version, schema.attr_names);
         build_result!(SchemaOperation, Some(identifier), schema_data)
     }
@@ -222,6 +223,7 @@ impl LedgerService {
     pub fn parse_get_schema_response(&self, get_schema_response: &str, method_name: Option<&str>) -> IndyResult<(String, String)> {
         let reply: Reply<GetSchemaReplyResult> = LedgerService::parse_response(get_schema_response)?;
 
+        // SCHEMA transactions don't carry attribute types, so schemas read from the ledger are untyped.
         let schema = match reply.result() {
             GetSchemaReplyResult::GetSchemaReplyResultV0(res) => SchemaV1 {
                 id: SchemaId::new(&DidValue::new(&res.dest.0, method_name), &res.data.name, &res.data.version),
@@ -229,6 +231,7 @@ impl LedgerService {
                 version: res.data.version,
                 attr_names: res.data.attr_names,
                 seq_no: Some(res.seq_no),
+                attribute_types: None,
             },
             GetSchemaReplyResult::GetSchemaReplyResultV1(res) => {
                 SchemaV1 {
@@ -240,6 +243,7 @@ impl LedgerService {
                         None => res.txn.data.id
                     },
                     seq_no: Some(res.txn_metadata.seq_no),
+                    attribute_types: None,
                 }
             }
         };
@@ -670,6 +674,7 @@ mod tests {
             version: "1.0".to_string(),
             attr_names,
             seq_no: None,
+            attribute_types: None,
         };
 
         let expected_result = json!({
@@ -13,13 +13,14 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::Write;
 use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde_json;
 use serde::de::DeserializeOwned;
 
 use api::ledger::{CustomFree, CustomTransactionParser};
 use domain::{
-    pool::{PoolConfig, PoolOpenConfig},
+    pool::{PoolConfig, PoolOpenConfig, RetryPolicy},
     ledger::response::{
         Message,
         Reply,
@@ -40,9 +41,12 @@ mod merkle_tree_factory;
 mod networker;
 mod pool;
 mod request_handler;
+mod retry;
 mod state_proof;
 mod types;
 
+pub use self::retry::Retry;
+
 lazy_static! {
     static ref REGISTERED_SP_PARSERS: Mutex<HashMap<String, (CustomTransactionParser, CustomFree)>> = Mutex::new(HashMap::new());
 }
@@ -52,6 +56,7 @@ type Nodes = HashMap<String, Option<VerKey>>;
 pub struct PoolService {
     open_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
     pending_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
+    retry_policies: RefCell<HashMap<PoolHandle, RetryPolicy>>,
 }
 
 impl PoolService {
@@ -59,6 +64,7 @@ impl PoolService {
         PoolService {
             open_pools: RefCell::new(HashMap::new()),
             pending_pools: RefCell::new(HashMap::new()),
+            retry_policies: RefCell::new(HashMap::new()),
         }
     }
 
@@ -143,6 +149,8 @@ impl PoolService {
     }
 
     pub fn open(&self, name: &str, config: Option<PoolOpenConfig>) -> IndyResult<PoolHandle> {
+        _check_online()?;
+
         for ref pool in self.open_pools.try_borrow()?.values() {
             if name.eq(pool.pool.get_name()) {
                 //TODO change error
@@ -153,6 +161,7 @@ impl PoolService {
         let config = config.unwrap_or_default();
 
         let pool_handle: PoolHandle = next_pool_handle();
+        self.retry_policies.try_borrow_mut()?.insert(pool_handle, config.retry.clone());
         let mut new_pool = Pool::new(name, pool_handle, config);
 
         let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets(&format!("pool_{}", name));
@@ -181,6 +190,8 @@ impl PoolService {
     }
 
     pub fn send_action(&self, handle: PoolHandle, msg: &str, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<CommandHandle> {
+        _check_online()?;
+
         let pools = self.open_pools.try_borrow()?;
 
         if let Some(ref pool) = pools.get(&handle) {
@@ -192,6 +203,24 @@ impl PoolService {
         }
     }
 
+    /// Attempts of request to be sent with `send_tx` following retry policy of the pool.
+    pub fn retry(&self, handle: PoolHandle, msg: &str) -> IndyResult<Retry> {
+        let policy = self.retry_policies.try_borrow()?
+            .get(&handle)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(Retry::new(&policy, PoolService::is_read_request(msg)))
+    }
+
+    fn is_read_request(msg: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(msg).ok()
+            .and_then(|request| request["operation"]["type"].as_str().map(String::from))
+            .map(|txn_type| events::REQUESTS_FOR_STATE_PROOFS.contains(&txn_type.as_str())
+                || PoolService::get_sp_parser(&txn_type).is_some())
+            .unwrap_or(false)
+    }
+
     pub fn register_sp_parser(txn_type: &str,
                               parser: CustomTransactionParser, free: CustomFree) -> IndyResult<()> {
         if events::REQUESTS_FOR_STATE_PROOFS.contains(&txn_type) {
@@ -218,6 +247,8 @@ impl PoolService {
 
         let mut pools = self.open_pools.try_borrow_mut()?;
 
+        self.retry_policies.try_borrow_mut()?.remove(&handle);
+
         match pools.remove(&handle) {
             Some(ref pool) => self._send_msg(cmd_id, COMMAND_EXIT, &pool.cmd_socket, None, None)?,
             None => return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))
@@ -263,6 +294,22 @@ impl PoolService {
 
 lazy_static! {
     static ref THRESHOLD: Mutex<u64> = Mutex::new(600);
+    static ref OFFLINE: Mutex<bool> = Mutex::new(false);
+}
+
+/// In offline mode pools can't be opened and requests aren't sent to already opened ones.
+/// Anoncreds commands take schemas, credential definitions and revocation entries from caller,
+/// so issuance, proving and verification keep working.
+pub fn set_offline(offline: bool) {
+    *OFFLINE.lock().unwrap() = offline;
+}
+
+fn _check_online() -> IndyResult<()> {
+    if *OFFLINE.lock().unwrap() {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Ledger can't be accessed in offline mode"));
+    }
+
+    Ok(())
 }
 
 pub fn set_freshness_threshold(threshold: u64) {
@@ -295,6 +342,28 @@ pub fn get_last_signed_time(response: &str) -> Option<u64> {
     c.ok().and_then(|resp| resp.last_txn_time)
 }
 
+/// Fails with `LedgerStaleState` if state proof of `response` was signed more than
+/// `freshness_threshold` seconds ago. No threshold means any age is accepted.
+pub fn check_freshness(response: &str, freshness_threshold: Option<u64>) -> IndyResult<()> {
+    let freshness_threshold = match freshness_threshold {
+        Some(freshness_threshold) => freshness_threshold,
+        None => return Ok(())
+    };
+
+    let last_signed_time = get_last_signed_time(response)
+        .ok_or_else(|| err_msg(IndyErrorKind::LedgerStaleState, "Ledger reply doesn't contain state proof timestamp"))?;
+
+    let cur_time = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time has gone backwards").as_secs();
+
+    if last_signed_time.saturating_add(freshness_threshold) < cur_time {
+        return Err(err_msg(IndyErrorKind::LedgerStaleState,
+                           format!("State proof was signed at {}, {} seconds ago exceeds freshness threshold {}",
+                                   last_signed_time, cur_time.saturating_sub(last_signed_time), freshness_threshold)));
+    }
+
+    Ok(())
+}
+
 fn _handle_response_message_type<T>(message: Message<T>) -> IndyResult<Reply<T>> where T: DeserializeOwned + ::std::fmt::Debug {
     trace!("handle_response_message_type >>> message {:?}", message);
 
@@ -603,6 +672,67 @@ mod tests {
         test::cleanup_storage("pool_drop_works_for_after_close");
     }
 
+    mod check_freshness {
+        use super::*;
+
+        fn _cur_time() -> u64 {
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+        }
+
+        // reply of mock node with state proof signed at `timestamp`
+        fn _reply(timestamp: u64) -> String {
+            json!({
+                "op": "REPLY",
+                "result": {
+                    "type": "105",
+                    "seqNo": 1,
+                    "txnTime": timestamp,
+                    "state_proof": {
+                        "multi_signature": {
+                            "value": {
+                                "timestamp": timestamp
+                            }
+                        }
+                    }
+                }
+            }).to_string()
+        }
+
+        #[test]
+        fn check_freshness_works_for_fresh_reply() {
+            check_freshness(&_reply(_cur_time() - 10), Some(60)).unwrap();
+        }
+
+        #[test]
+        fn check_freshness_works_for_stale_reply() {
+            let res = check_freshness(&_reply(_cur_time() - 600), Some(60));
+            assert_kind!(IndyErrorKind::LedgerStaleState, res);
+        }
+
+        #[test]
+        fn check_freshness_works_for_no_threshold() {
+            check_freshness(&_reply(0), None).unwrap();
+        }
+
+        #[test]
+        fn check_freshness_works_for_huge_threshold() {
+            check_freshness(&_reply(_cur_time() - 10), Some(u64::max_value())).unwrap();
+        }
+
+        #[test]
+        fn check_freshness_works_for_timestamp_in_future() {
+            check_freshness(&_reply(_cur_time() + 600), Some(60)).unwrap();
+        }
+
+        #[test]
+        fn check_freshness_works_for_reply_without_state_proof() {
+            let reply = json!({"op": "REPLY", "result": {"type": "105", "seqNo": 1}}).to_string();
+
+            let res = check_freshness(&reply, Some(60));
+            assert_kind!(IndyErrorKind::LedgerStaleState, res);
+        }
+    }
+
     pub mod nodes_emulator {
         extern crate sodiumoxide;
 
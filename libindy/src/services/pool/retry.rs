@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use rand::{thread_rng, Rng};
+
+use domain::pool::{MAX_RETRY_DELAY_MS, RetryPolicy};
+use errors::prelude::*;
+
+/// Attempts of a single request sent to the pool.
+/// Tells after each result whether the request has to be sent once more and when.
+pub struct Retry {
+    policy: RetryPolicy,
+    attempt: u32,
+}
+
+impl Retry {
+    pub fn new(policy: &RetryPolicy, is_read: bool) -> Retry {
+        let mut policy = policy.clone();
+
+        if !is_read && !policy.retry_writes {
+            policy.max_attempts = 1;
+        }
+
+        Retry { policy, attempt: 1 }
+    }
+
+    /// Delay before the next attempt or `None` if `result` has to be returned to caller.
+    pub fn next_delay(&mut self, result: &IndyResult<String>) -> Option<Duration> {
+        match *result {
+            Err(ref err) if _is_transient(err) && self.attempt < self.policy.max_attempts => {
+                let jitter = if self.policy.jitter_ms > 0 { thread_rng().gen_range(0, self.policy.jitter_ms + 1) } else { 0 };
+                let delay = self.delay_ms().saturating_add(jitter);
+
+                self.attempt += 1;
+
+                Some(Duration::from_millis(delay))
+            }
+            _ => None
+        }
+    }
+
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    fn delay_ms(&self) -> u64 {
+        let delay = self.policy.base_delay_ms as f64 * self.policy.multiplier.powi(self.attempt as i32 - 1);
+        delay.min(MAX_RETRY_DELAY_MS as f64) as u64
+    }
+}
+
+// pool didn't reply in time, e.g. nodes were unreachable or didn't reach consensus
+fn _is_transient(err: &IndyError) -> bool {
+    err.kind() == IndyErrorKind::PoolTimeout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 100,
+            multiplier: 2.0,
+            jitter_ms: 0,
+            retry_writes: false,
+        }
+    }
+
+    fn _timeout() -> IndyResult<String> {
+        Err(err_msg(IndyErrorKind::PoolTimeout, "Timeout"))
+    }
+
+    // sends request to mock pool replying with `replies` in turn until retry gives up
+    fn _submit(retry: &mut Retry, replies: Vec<IndyResult<String>>) -> (IndyResult<String>, Vec<Duration>) {
+        let mut replies = replies.into_iter();
+        let mut delays = Vec::new();
+
+        loop {
+            let result = replies.next().unwrap();
+
+            match retry.next_delay(&result) {
+                Some(delay) => delays.push(delay),
+                None => return (result, delays)
+            }
+        }
+    }
+
+    #[test]
+    fn retry_works_for_read_failed_twice() {
+        let mut retry = Retry::new(&_policy(), true);
+
+        let (result, delays) = _submit(&mut retry, vec![_timeout(), _timeout(), Ok("good".to_string())]);
+
+        assert_eq!("good", result.unwrap());
+        assert_eq!(vec![Duration::from_millis(100), Duration::from_millis(200)], delays);
+        assert_eq!(3, retry.attempt());
+    }
+
+    #[test]
+    fn retry_returns_last_error_for_exhausted_attempts() {
+        let mut retry = Retry::new(&_policy(), true);
+
+        let (result, delays) = _submit(&mut retry, vec![_timeout(), _timeout(), _timeout(), Ok("good".to_string())]);
+
+        assert_kind!(IndyErrorKind::PoolTimeout, result);
+        assert_eq!(2, delays.len());
+    }
+
+    #[test]
+    fn retry_skips_write() {
+        let mut retry = Retry::new(&_policy(), false);
+
+        let (result, delays) = _submit(&mut retry, vec![_timeout(), Ok("good".to_string())]);
+
+        assert_kind!(IndyErrorKind::PoolTimeout, result);
+        assert!(delays.is_empty());
+    }
+
+    #[test]
+    fn retry_works_for_write_opted_in() {
+        let mut policy = _policy();
+        policy.retry_writes = true;
+        let mut retry = Retry::new(&policy, false);
+
+        let (result, _) = _submit(&mut retry, vec![_timeout(), Ok("good".to_string())]);
+
+        assert_eq!("good", result.unwrap());
+    }
+
+    #[test]
+    fn retry_skips_non_transient_error() {
+        let mut retry = Retry::new(&_policy(), true);
+
+        let (result, delays) = _submit(&mut retry, vec![Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid")), Ok("good".to_string())]);
+
+        assert_kind!(IndyErrorKind::InvalidStructure, result);
+        assert!(delays.is_empty());
+    }
+
+    #[test]
+    fn retry_adds_jitter() {
+        let mut policy = _policy();
+        policy.jitter_ms = 50;
+        let mut retry = Retry::new(&policy, true);
+
+        let delay = retry.next_delay(&_timeout()).unwrap();
+
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn retry_caps_delay_for_large_multiplier() {
+        let mut policy = _policy();
+        policy.max_attempts = 100;
+        policy.multiplier = 1000.0;
+        let mut retry = Retry::new(&policy, true);
+
+        let (_, delays) = _submit(&mut retry, (0..100).map(|_| _timeout()).collect());
+
+        assert_eq!(Some(&Duration::from_millis(MAX_RETRY_DELAY_MS)), delays.last());
+    }
+
+    #[test]
+    fn retry_is_disabled_by_default() {
+        let mut retry = Retry::new(&RetryPolicy::default(), true);
+
+        let (result, delays) = _submit(&mut retry, vec![_timeout(), Ok("good".to_string())]);
+
+        assert_kind!(IndyErrorKind::PoolTimeout, result);
+        assert!(delays.is_empty());
+    }
+}
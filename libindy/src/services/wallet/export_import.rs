@@ -5,7 +5,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use rmp_serde;
 
-use domain::wallet::export_import::{EncryptionMethod, Header, Record};
+use domain::wallet::export_import::{ConflictPolicy, EncryptionMethod, Header, Record};
 use domain::wallet::KeyDerivationMethod;
 use errors::prelude::*;
 use services::wallet::encryption::KeyDerivationData;
@@ -16,7 +16,39 @@ use super::{Wallet, WalletRecord};
 
 const CHUNK_SIZE: usize = 1024;
 
-pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+// Version 1 adds the number of exported records to the header
+pub const EXPORT_VERSION: u32 = 1;
+
+// Reports (processed, total) after every `step` records and once more when done.
+// Total is unknown on import of files of version 0.
+pub struct Progress<'a> {
+    pub step: usize,
+    pub callback: &'a dyn Fn(usize, Option<usize>),
+}
+
+impl<'a> Progress<'a> {
+    fn processed(&self, processed: usize, total: Option<usize>) {
+        if self._is_step(processed) {
+            (self.callback)(processed, total)
+        }
+    }
+
+    fn finished(&self, processed: usize, total: Option<usize>) {
+        if processed == 0 || !self._is_step(processed) {
+            (self.callback)(processed, total)
+        }
+    }
+
+    fn _is_step(&self, processed: usize) -> bool {
+        self.step != 0 && processed % self.step == 0
+    }
+}
+
+pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, progress: Option<&Progress>) -> IndyResult<()> {
+    if version > EXPORT_VERSION {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
+    }
+
     let nonce = chacha20poly1305_ietf::gen_nonce();
     let chunk_size = CHUNK_SIZE;
 
@@ -37,10 +69,22 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
         }
     };
 
+    let mut records = wallet.get_all()?;
+
+    let record_count = if version > 0 || progress.is_some() {
+        Some(match records.get_total_count()? {
+            Some(total_count) => total_count,
+            None => wallet.count_all()?
+        })
+    } else {
+        None
+    };
+
     let header = Header {
         encryption_method,
         time: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-        version
+        version,
+        record_count: if version > 0 { record_count } else { None },
     };
 
     let header = rmp_serde::to_vec(&header)
@@ -59,7 +103,7 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
     writer.write_all(&hash(&header)?)?;
 
-    let mut records = wallet.get_all()?;
+    let mut processed = 0;
 
     while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
         let record = Record {
@@ -74,18 +118,32 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
         writer.write_u32::<LittleEndian>(record.len() as u32)?;
         writer.write_all(&record)?;
+
+        processed += 1;
+        if let Some(progress) = progress {
+            progress.processed(processed, record_count);
+        }
     }
 
     writer.write_u32::<LittleEndian>(0)?; // END message
     writer.flush()?;
+
+    if let Some(progress) = progress {
+        progress.finished(processed, record_count);
+    }
     Ok(())
 }
 
 #[cfg(test)]
 fn import<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> where T: Read {
+    import_with_policy(wallet, reader, passphrase, ConflictPolicy::Error, None)
+}
+
+#[cfg(test)]
+fn import_with_policy<T>(wallet: &Wallet, reader: T, passphrase: &str, on_conflict: ConflictPolicy, progress: Option<&Progress>) -> IndyResult<()> where T: Read {
     let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase)?;
     let import_key = import_key_derivation_data.calc_master_key()?;
-    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes)
+    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes, on_conflict, progress)
 }
 
 pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyResult<(BufReader<T>, KeyDerivationData, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)> where T: Read {
@@ -104,7 +162,7 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     let header: Header = rmp_serde::from_slice(&header_bytes)
         .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
 
-    if header.version != 0 {
+    if header.version > EXPORT_VERSION {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported version"));
     }
 
@@ -147,7 +205,17 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     Ok((reader, import_key_derivation_data, nonce, chunk_size, header_bytes))
 }
 
-pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>) -> IndyResult<()> where T: Read {
+pub(super) fn finish_import<T>(wallet: &Wallet,
+                               reader: BufReader<T>,
+                               key: chacha20poly1305_ietf::Key,
+                               nonce: chacha20poly1305_ietf::Nonce,
+                               chunk_size: usize,
+                               header_bytes: Vec<u8>,
+                               on_conflict: ConflictPolicy,
+                               progress: Option<&Progress>) -> IndyResult<()> where T: Read {
+    let header: Header = rmp_serde::from_slice(&header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
+
     // Reads encrypted
     let mut reader = chacha20poly1305_ietf::Reader::new(reader, key, nonce, chunk_size);
 
@@ -158,6 +226,8 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header hash"));
     }
 
+    let mut processed = 0;
+
     loop {
         let record_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
 
@@ -171,12 +241,42 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
         let record: Record = rmp_serde::from_slice(&record)
             .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
 
-        wallet.add(&record.type_, &record.id, &record.value, &record.tags)?;
+        _add_record(wallet, &record, on_conflict)?;
+
+        processed += 1;
+        if let Some(progress) = progress {
+            progress.processed(processed, header.record_count);
+        }
+    }
+
+    if let Some(record_count) = header.record_count {
+        if record_count != processed {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Export file declares {} records but contains {}", record_count, processed)));
+        }
     }
 
+    if let Some(progress) = progress {
+        progress.finished(processed, header.record_count);
+    }
     Ok(())
 }
 
+fn _add_record(wallet: &Wallet, record: &Record, on_conflict: ConflictPolicy) -> IndyResult<()> {
+    match wallet.add(&record.type_, &record.id, &record.value, &record.tags) {
+        Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists => match on_conflict {
+            ConflictPolicy::Error => Err(err_msg(IndyErrorKind::WalletItemAlreadyExists,
+                                                 format!("Record {}:{} already exists in the wallet", record.type_, record.id))),
+            ConflictPolicy::Skip => Ok(()),
+            ConflictPolicy::Overwrite => {
+                wallet.update(&record.type_, &record.id, &record.value)?;
+                wallet.update_tags(&record.type_, &record.id, &record.tags)
+            }
+        },
+        res => res
+    }
+}
+
 fn _map_io_err(e: io::Error) -> IndyError {
     match e {
         ref e if e.kind() == io::ErrorKind::UnexpectedEof
@@ -187,6 +287,7 @@ fn _map_io_err(e: io::Error) -> IndyError {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
     use std::collections::HashMap;
     use std::rc::Rc;
 
@@ -203,14 +304,14 @@ mod tests {
     use super::*;
 
     fn export(wallet: &Wallet, writer: &mut dyn Write, passphrase: &str, version: u32, key_derivation_method: &KeyDerivationMethod) -> IndyResult<()> {
-        if version != 0 {
-            Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"))?;
-        }
+        export_with_progress(wallet, writer, passphrase, version, key_derivation_method, None)
+    }
 
+    fn export_with_progress(wallet: &Wallet, writer: &mut dyn Write, passphrase: &str, version: u32, key_derivation_method: &KeyDerivationMethod, progress: Option<&Progress>) -> IndyResult<()> {
         let key_data = KeyDerivationData::from_passphrase_with_new_salt(passphrase, key_derivation_method);
         let key = key_data.calc_master_key()?;
 
-        export_continue(wallet, writer, version, key, &key_data)
+        export_continue(wallet, writer, version, key, &key_data, progress)
     }
 
     #[test]
@@ -407,6 +508,114 @@ mod tests {
         _cleanup("import_works_for_data_extended2");
     }
 
+    #[test]
+    fn export_import_works_for_record_count_and_progress() {
+        _cleanup("export_import_works_for_record_count_and_progress1");
+        _cleanup("export_import_works_for_record_count_and_progress2");
+        {
+            let exported = RefCell::new(Vec::new());
+            let export_callback = |processed: usize, total: Option<usize>| exported.borrow_mut().push((processed, total));
+
+            let mut output: Vec<u8> = Vec::new();
+            export_with_progress(&_add_300_records(_wallet("export_import_works_for_record_count_and_progress1")), &mut output, _passphrase(), _version2(),
+                                 &KeyDerivationMethod::ARGON2I_MOD, Some(&Progress { step: 100, callback: &export_callback })).unwrap();
+
+            assert_eq!(vec![(100, Some(300)), (200, Some(300)), (300, Some(300))], *exported.borrow());
+
+            let imported = RefCell::new(Vec::new());
+            let import_callback = |processed: usize, total: Option<usize>| imported.borrow_mut().push((processed, total));
+
+            let wallet = _wallet("export_import_works_for_record_count_and_progress2");
+            import_with_policy(&wallet, &mut output.as_slice(), _passphrase(), ConflictPolicy::Error,
+                               Some(&Progress { step: 128, callback: &import_callback })).unwrap();
+
+            assert_eq!(vec![(128, Some(300)), (256, Some(300)), (300, Some(300))], *imported.borrow());
+            _assert_has_300_records(&wallet);
+        }
+        _cleanup("export_import_works_for_record_count_and_progress1");
+        _cleanup("export_import_works_for_record_count_and_progress2");
+    }
+
+    #[test]
+    fn import_works_for_progress_of_version_without_record_count() {
+        _cleanup("import_works_for_progress_of_version_without_record_count1");
+        _cleanup("import_works_for_progress_of_version_without_record_count2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_2_records(_wallet("import_works_for_progress_of_version_without_record_count1")), &mut output, _passphrase(), _version1(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let imported = RefCell::new(Vec::new());
+            let import_callback = |processed: usize, total: Option<usize>| imported.borrow_mut().push((processed, total));
+
+            let wallet = _wallet("import_works_for_progress_of_version_without_record_count2");
+            import_with_policy(&wallet, &mut output.as_slice(), _passphrase(), ConflictPolicy::Error,
+                               Some(&Progress { step: 1, callback: &import_callback })).unwrap();
+
+            assert_eq!(vec![(1, None), (2, None)], *imported.borrow());
+            _assert_has_2_records(&wallet);
+        }
+        _cleanup("import_works_for_progress_of_version_without_record_count1");
+        _cleanup("import_works_for_progress_of_version_without_record_count2");
+    }
+
+    #[test]
+    fn import_works_for_resume_after_interruption() {
+        _cleanup("import_works_for_resume_after_interruption1");
+        _cleanup("import_works_for_resume_after_interruption2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_300_records(_wallet("import_works_for_resume_after_interruption1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let interrupted = output[..output.len() / 2].to_vec();
+
+            let wallet = _wallet("import_works_for_resume_after_interruption2");
+
+            let res = import(&wallet, &mut interrupted.as_slice(), _passphrase());
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+            let res = import(&wallet, &mut output.as_slice(), _passphrase());
+            assert_eq!(IndyErrorKind::WalletItemAlreadyExists, res.unwrap_err().kind());
+
+            import_with_policy(&wallet, &mut output.as_slice(), _passphrase(), ConflictPolicy::Skip, None).unwrap();
+            _assert_has_300_records(&wallet);
+        }
+        _cleanup("import_works_for_resume_after_interruption1");
+        _cleanup("import_works_for_resume_after_interruption2");
+    }
+
+    #[test]
+    fn import_works_for_overwrite_policy() {
+        _cleanup("import_works_for_overwrite_policy1");
+        _cleanup("import_works_for_overwrite_policy2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_2_records(_wallet("import_works_for_overwrite_policy1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let wallet = _wallet("import_works_for_overwrite_policy2");
+            wallet.add(&_type1(), &_id1(), &_value2(), &_tags2()).unwrap();
+
+            import_with_policy(&wallet, &mut output.as_slice(), _passphrase(), ConflictPolicy::Skip, None).unwrap();
+            let record = wallet.get(&_type1(), &_id1(), _options()).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+
+            import_with_policy(&wallet, &mut output.as_slice(), _passphrase(), ConflictPolicy::Overwrite, None).unwrap();
+            _assert_has_2_records(&wallet);
+        }
+        _cleanup("import_works_for_overwrite_policy1");
+        _cleanup("import_works_for_overwrite_policy2");
+    }
+
+    #[test]
+    fn export_works_for_unsupported_version() {
+        _cleanup("export_works_for_unsupported_version");
+
+        let mut output: Vec<u8> = Vec::new();
+        let res = export(&_add_2_records(_wallet("export_works_for_unsupported_version")), &mut output, _passphrase(), EXPORT_VERSION + 1, &KeyDerivationMethod::ARGON2I_MOD);
+        assert_eq!(IndyErrorKind::InvalidState, res.unwrap_err().kind());
+
+        _cleanup("export_works_for_unsupported_version");
+    }
+
     fn _cleanup(name: &str) {
         test::cleanup_storage(name)
     }
@@ -515,6 +724,10 @@ mod tests {
         0
     }
 
+    fn _version2() -> u32 {
+        EXPORT_VERSION
+    }
+
     fn _id(suffix: usize) -> String {
         format!("id_{}", suffix)
     }
@@ -12,12 +12,14 @@ use serde_json::Value as SValue;
 use api::wallet::*;
 
 use domain::wallet::{Config, Credentials, ExportConfig, Metadata, MetadataArgon, MetadataRaw, Tags};
+use domain::wallet::export_import::ConflictPolicy;
 use errors::prelude::*;
 pub use services::wallet::encryption::KeyDerivationData;
 use utils::crypto::chacha20poly1305_ietf;
 use utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 
-use self::export_import::{export_continue, finish_import, preparse_file_to_import};
+pub use self::export_import::EXPORT_VERSION;
+use self::export_import::{export_continue, finish_import, preparse_file_to_import, Progress};
 use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
 use self::storage::plugged::PluggedStorageType;
@@ -37,7 +39,19 @@ pub struct WalletService {
     storage_types: RefCell<HashMap<String, Box<dyn WalletStorageType>>>,
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
     pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
-    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    pending_for_import: RefCell<HashMap<WalletHandle, PendingImport>>,
+}
+
+struct PendingImport {
+    reader: BufReader<::std::fs::File>,
+    nonce: chacha20poly1305_ietf::Nonce,
+    chunk_size: usize,
+    header_bytes: Vec<u8>,
+    key_data: KeyDerivationData,
+    // metadata of the wallet records are imported into, None if the wallet is created by import
+    metadata: Option<Metadata>,
+    on_conflict: ConflictPolicy,
+    progress_step: usize,
 }
 
 impl WalletService {
@@ -45,6 +59,8 @@ impl WalletService {
         let storage_types = {
             let mut map: HashMap<String, Box<dyn WalletStorageType>> = HashMap::new();
             map.insert("default".to_string(), Box::new(SQLiteStorageType::new()));
+            #[cfg(feature = "memory_storage")]
+            map.insert("memory".to_string(), Box::new(self::storage::memory::MemoryStorageType::new()));
             RefCell::new(map)
         };
 
@@ -418,10 +434,11 @@ impl WalletService {
         }
     }
 
-    pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+    pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey),
+                         progress: Option<&dyn Fn(usize, Option<usize>)>) -> IndyResult<()> {
         trace!("export_wallet >>> wallet_handle: {:?}, export_config: {:?}, version: {:?}", wallet_handle, secret!(export_config), version);
 
-        if version != 0 {
+        if version > EXPORT_VERSION {
             return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
         }
 
@@ -446,7 +463,9 @@ impl WalletService {
                 .create_new(true)
                 .open(export_config.path.clone())?;
 
-        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data);
+        let progress = progress.map(|callback| Progress { step: export_config.progress_step, callback });
+
+        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data, progress.as_ref());
 
         trace!("export_wallet <<<");
 
@@ -465,23 +484,54 @@ impl WalletService {
                 .open(&export_config.path)?;
 
         let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+
+        // records may be imported into an existing wallet, e.g. to resume an interrupted import
+        let existing = match export_config.on_conflict {
+            ConflictPolicy::Error => None,
+            ConflictPolicy::Skip | ConflictPolicy::Overwrite => match self._open_storage_and_fetch_metadata(config, credentials) {
+                Ok((_, metadata, key_data)) => Some((metadata, key_data)),
+                Err(ref err) if err.kind() == IndyErrorKind::WalletNotFound => None,
+                Err(err) => return Err(err)
+            }
+        };
+
+        let (metadata, key_data) = match existing {
+            Some((metadata, key_data)) => (Some(metadata), key_data),
+            None => (None, KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method))
+        };
 
         let wallet_handle = next_wallet_handle();
 
         let stashed_key_data = key_data.clone();
 
-        self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, stashed_key_data));
+        self.pending_for_import.borrow_mut().insert(wallet_handle, PendingImport {
+            reader,
+            nonce,
+            chunk_size,
+            header_bytes,
+            key_data: stashed_key_data,
+            metadata,
+            on_conflict: export_config.on_conflict,
+            progress_step: export_config.progress_step,
+        });
 
         Ok((wallet_handle, key_data, import_key_derivation_data))
     }
 
-    pub fn import_wallet_continue(&self, wallet_handle: WalletHandle, config: &Config, credentials: &Credentials, key: (MasterKey, MasterKey)) -> IndyResult<()> {
-        let (reader, nonce, chunk_size, header_bytes, key_data) = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
+    pub fn import_wallet_continue(&self, wallet_handle: WalletHandle, config: &Config, credentials: &Credentials, key: (MasterKey, MasterKey),
+                                  progress: Option<&dyn Fn(usize, Option<usize>)>) -> IndyResult<()> {
+        let PendingImport { reader, nonce, chunk_size, header_bytes, key_data, metadata, on_conflict, progress_step } =
+            self.pending_for_import.borrow_mut().remove(&wallet_handle)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Import data not found"))?;
 
         let (import_key, master_key) = key;
 
-        let keys = self._create_wallet(config, credentials, (&key_data, &master_key))?;
+        let is_created = metadata.is_none();
+
+        let keys = match metadata {
+            Some(metadata) => self._restore_keys(&metadata, &master_key)?,
+            None => self._create_wallet(config, credentials, (&key_data, &master_key))?
+        };
 
         self._is_id_from_config_not_used(config)?;
         let storage = self._open_storage(config, credentials)?;
@@ -489,11 +539,13 @@ impl WalletService {
 
         let res = {
             let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys));
+            let progress = progress.map(|callback| Progress { step: progress_step, callback });
 
-            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes)
+            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes, on_conflict, progress.as_ref())
         };
 
-        if res.is_err() {
+        // records imported into an existing wallet are kept, so the import can be resumed
+        if res.is_err() && is_created {
             let metadata: Metadata = serde_json::from_slice(&metadata)
                 .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
 
@@ -726,6 +778,18 @@ impl SearchOptions {
 
         serde_json::to_string(&options).unwrap()
     }
+
+    pub fn id_tags() -> String {
+        let options = SearchOptions {
+            retrieve_records: true,
+            retrieve_total_count: false,
+            retrieve_type: false,
+            retrieve_value: false,
+            retrieve_tags: true,
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
 }
 
 impl Default for SearchOptions {
@@ -785,24 +849,12 @@ mod tests {
                              config: &Config,
                              credentials: &Credentials,
                              export_config: &ExportConfig) -> IndyResult<()> {
-            trace!("import_wallet_prepare >>> config: {:?}, credentials: {:?}, export_config: {:?}", config, secret!(export_config), secret!(export_config));
-
-            let exported_file_to_import =
-                fs::OpenOptions::new()
-                    .read(true)
-                    .open(&export_config.path)?;
-
-            let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-            let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
-
-            let wallet_handle = next_wallet_handle();
+            let (wallet_handle, key_data, import_key_derivation_data) = self.import_wallet_prepare(config, credentials, export_config)?;
 
             let import_key = import_key_derivation_data.calc_master_key()?;
             let master_key = key_data.calc_master_key()?;
 
-            self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, key_data));
-
-            self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key))
+            self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key), None)
         }
 
         pub fn delete_wallet(&self, config: &Config, credentials: &Credentials) -> IndyResult<()> {
@@ -1840,7 +1892,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_when_empty");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
 
             assert!(export_path.exists());
         }
@@ -1862,7 +1914,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_1_item");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1883,7 +1935,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_wallet_1_item_interactive_method");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1904,7 +1956,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key("wallet_service_export_wallet_1_item_raw_method");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(&export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1929,7 +1981,7 @@ mod tests {
             let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_returns_error_if_file_exists"), &RAW_CREDENTIAL).unwrap();
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_file_exists");
-            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key));
+            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None);
             assert_eq!(IndyErrorKind::IOError, res.unwrap_err().kind());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1947,7 +1999,7 @@ mod tests {
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_wrong_handle");
             let export_config = _export_config_raw("wallet_service_export_wallet_returns_error_if_wrong_handle");
             let export_path = remove_exported_wallet(&export_config);
-            let res = wallet_service.export_wallet(INVALID_WALLET_HANDLE, &export_config, 0, (&kdd, &master_key));
+            let res = wallet_service.export_wallet(INVALID_WALLET_HANDLE, &export_config, 0, (&kdd, &master_key), None);
             assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
             assert!(!export_path.exists());
         }
@@ -1968,7 +2020,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_1_item");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -1997,7 +2049,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_interactive_method");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2025,7 +2077,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_1_item_for_moderate_method");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2054,7 +2106,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_export_interactive_import_as_raw");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2083,7 +2135,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_export_raw_import_as_interactive");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2109,7 +2161,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key("wallet_service_export_import_wallet_if_empty");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), None).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2122,6 +2174,42 @@ mod tests {
         test::cleanup_wallet("wallet_service_export_import_wallet_if_empty");
     }
 
+    #[test]
+    fn wallet_service_export_import_wallet_works_for_resume_into_existing_wallet() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_resume_into_existing_wallet");
+        let export_config = _export_config_raw("wallet_service_export_import_wallet_works_for_resume_into_existing_wallet");
+        {
+            let wallet_service = WalletService::new();
+            let config: &Config = &_config("wallet_service_export_import_wallet_works_for_resume_into_existing_wallet");
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key("wallet_service_export_import_wallet_works_for_resume_into_existing_wallet");
+            let export_path = remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, 1, (&kdd, &master_key), None).unwrap();
+            assert!(export_path.exists());
+
+            // the interrupted import has left the first record only
+            wallet_service.delete_record(wallet_handle, "type", "key2").unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let res = wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config);
+            assert_eq!(IndyErrorKind::WalletAlreadyExists, res.unwrap_err().kind());
+
+            let export_config = ExportConfig { on_conflict: ConflictPolicy::Skip, ..export_config.clone() };
+            wallet_service.import_wallet(config, &RAW_CREDENTIAL, &export_config).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key2", "{}").unwrap();
+        }
+        let _export_path = remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_resume_into_existing_wallet");
+    }
+
     #[test]
     fn wallet_service_export_import_returns_error_if_path_missing() {
         _cleanup("wallet_service_export_import_returns_error_if_path_missing");
@@ -2323,6 +2411,8 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            on_conflict: ConflictPolicy::Error,
+            progress_step: 0,
         }
     }
 
@@ -2341,6 +2431,8 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            on_conflict: ConflictPolicy::Error,
+            progress_step: 0,
         }
     }
 
@@ -2353,6 +2445,8 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            on_conflict: ConflictPolicy::Error,
+            progress_step: 0,
         }
     }
 
@@ -0,0 +1,261 @@
+//! Conformance suite for `WalletStorageType` implementations.
+//!
+//! Every storage backend must behave the same way as seen from `WalletService`,
+//! so a new backend should pass `run` before it is registered:
+//!
+//! ```ignore
+//! conformance::run(&MyStorageType::new(), "my_storage_conformance", Some(config), Some(credentials));
+//! ```
+//!
+//! The suite creates and deletes wallets with ids derived from the given one.
+
+use errors::prelude::*;
+use services::wallet::language::{Operator, TagName as QueryTagName, TargetValue};
+
+use super::{EncryptedValue, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+
+const RETRIEVE_ALL: &str = r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#;
+
+/// Runs all checks against the storage type, panicking on the first mismatch.
+pub fn run(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    let checks: Vec<(&str, fn(&dyn WalletStorageType, &str, Option<&str>, Option<&str>))> = vec![
+        ("lifecycle", check_lifecycle),
+        ("records", check_records),
+        ("tags", check_tags),
+        ("metadata", check_metadata),
+        ("get_all", check_get_all),
+        ("search", check_search),
+    ];
+
+    for (name, check) in checks {
+        let id = format!("{}_{}", id, name);
+
+        let _ = storage_type.delete_storage(&id, config, credentials);
+        check(storage_type, &id, config, credentials);
+        let _ = storage_type.delete_storage(&id, config, credentials);
+    }
+}
+
+fn check_lifecycle(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    let res = storage_type.open_storage(id, config, credentials);
+    assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+    storage_type.create_storage(id, config, credentials, &_metadata()).unwrap();
+
+    let res = storage_type.create_storage(id, config, credentials, &_metadata());
+    assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+    storage_type.open_storage(id, config, credentials).unwrap().close().unwrap();
+
+    storage_type.delete_storage(id, config, credentials).unwrap();
+
+    let res = storage_type.delete_storage(id, config, credentials);
+    assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+    let res = storage_type.open_storage(id, config, credentials);
+    assert_kind!(IndyErrorKind::WalletNotFound, res);
+}
+
+fn check_records(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    let storage = _storage(storage_type, id, config, credentials);
+
+    storage.add(&_type(1), &_id(1), &_value(1), &_tags()).unwrap();
+
+    let record = storage.get(&_type(1), &_id(1), RETRIEVE_ALL).unwrap();
+    assert_eq!(_id(1), record.id);
+    assert_eq!(Some(_type(1)), record.type_);
+    assert_eq!(Some(_value(1)), record.value);
+    assert_eq!(_sort(_tags()), _sort(record.tags.unwrap()));
+
+    let record = storage.get(&_type(1), &_id(1), "{}").unwrap();
+    assert_eq!(Some(_value(1)), record.value);
+    assert!(record.type_.is_none());
+    assert!(record.tags.is_none());
+
+    let res = storage.add(&_type(1), &_id(1), &_value(2), &_tags());
+    assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+    // the same id under another type is another record
+    storage.add(&_type(2), &_id(1), &_value(2), &[]).unwrap();
+    assert_eq!(Some(_value(2)), storage.get(&_type(2), &_id(1), "{}").unwrap().value);
+
+    let res = storage.get(&_type(1), &_id(2), "{}");
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+    storage.update(&_type(1), &_id(1), &_value(3)).unwrap();
+    assert_eq!(Some(_value(3)), storage.get(&_type(1), &_id(1), "{}").unwrap().value);
+
+    let res = storage.update(&_type(1), &_id(2), &_value(3));
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+    storage.delete(&_type(1), &_id(1)).unwrap();
+
+    let res = storage.get(&_type(1), &_id(1), "{}");
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+    let res = storage.delete(&_type(1), &_id(1));
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+    assert!(storage.get(&_type(2), &_id(1), "{}").is_ok());
+}
+
+fn check_tags(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    let storage = _storage(storage_type, id, config, credentials);
+
+    storage.add(&_type(1), &_id(1), &_value(1), &_tags()).unwrap();
+
+    // adding a tag with an existing name replaces its value
+    let replaced_tag = Tag::PlainText(vec![1, 5, 8, 1], "New plain value".to_string());
+    storage.add_tags(&_type(1), &_id(1), &[replaced_tag.clone(), _new_tag()]).unwrap();
+    assert_eq!(_sort(vec![_tags()[0].clone(), replaced_tag, _new_tag()]), _sort(_get_tags(&*storage, &_id(1))));
+
+    storage.update_tags(&_type(1), &_id(1), &[_new_tag()]).unwrap();
+    assert_eq!(vec![_new_tag()], _get_tags(&*storage, &_id(1)));
+
+    storage.update_tags(&_type(1), &_id(1), &_tags()).unwrap();
+    storage.delete_tags(&_type(1), &_id(1), &[TagName::OfEncrypted(vec![1, 5, 8]), TagName::OfPlain(vec![9, 9, 9])]).unwrap();
+    assert_eq!(vec![_tags()[1].clone()], _get_tags(&*storage, &_id(1)));
+
+    let res = storage.add_tags(&_type(1), &_id(2), &_tags());
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+    let res = storage.update_tags(&_type(1), &_id(2), &_tags());
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+    let res = storage.delete_tags(&_type(1), &_id(2), &[TagName::OfPlain(vec![1, 5, 8, 1])]);
+    assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+}
+
+fn check_metadata(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    {
+        let storage = _storage(storage_type, id, config, credentials);
+
+        assert_eq!(_metadata(), storage.get_storage_metadata().unwrap());
+
+        storage.set_storage_metadata(&[4, 5, 6]).unwrap();
+        assert_eq!(vec![4, 5, 6], storage.get_storage_metadata().unwrap());
+    }
+
+    // metadata is persisted, not kept by the opened storage
+    let storage = storage_type.open_storage(id, config, credentials).unwrap();
+    assert_eq!(vec![4, 5, 6], storage.get_storage_metadata().unwrap());
+}
+
+fn check_get_all(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    let storage = _storage(storage_type, id, config, credentials);
+
+    assert!(storage.get_all().unwrap().next().unwrap().is_none());
+
+    storage.add(&_type(1), &_id(1), &_value(1), &_tags()).unwrap();
+    storage.add(&_type(2), &_id(2), &_value(2), &[]).unwrap();
+
+    let mut records = _collect(storage.get_all().unwrap());
+    records.sort_by_key(|record| record.id.clone());
+
+    assert_eq!(2, records.len());
+    assert_eq!((Some(_type(1)), Some(_value(1))), (records[0].type_.clone(), records[0].value.clone()));
+    assert_eq!(_sort(_tags()), _sort(records[0].tags.clone().unwrap()));
+    assert_eq!((Some(_type(2)), Some(_value(2))), (records[1].type_.clone(), records[1].value.clone()));
+    assert_eq!(Vec::<Tag>::new(), records[1].tags.clone().unwrap());
+}
+
+fn check_search(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) {
+    let storage = _storage(storage_type, id, config, credentials);
+
+    for i in 1..4 {
+        storage.add(&_type(1), &_id(i), &_value(i), &[
+            Tag::Encrypted(vec![1], vec![i]),
+            Tag::PlainText(vec![2], format!("value_{}", i)),
+        ]).unwrap();
+    }
+    storage.add(&_type(2), &_id(4), &_value(4), &[Tag::Encrypted(vec![1], vec![1])]).unwrap();
+
+    let search = |query: Operator| -> Vec<Vec<u8>> {
+        let mut ids: Vec<Vec<u8>> = _collect(storage.search(&_type(1), &query, None).unwrap())
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        ids.sort();
+        ids
+    };
+
+    let encrypted = |value: u8| Operator::Eq(QueryTagName::EncryptedTagName(vec![1]), TargetValue::Encrypted(vec![value]));
+    let plain = |value: &str| TargetValue::Unencrypted(value.to_string());
+    let plain_name = || QueryTagName::PlainTagName(vec![2]);
+
+    assert_eq!(vec![_id(1), _id(2), _id(3)], search(Operator::And(vec![])));
+    assert_eq!(vec![_id(1)], search(encrypted(1)));
+    assert_eq!(vec![_id(1), _id(3)], search(Operator::Or(vec![encrypted(1), encrypted(3)])));
+    assert_eq!(vec![_id(2)], search(Operator::And(vec![encrypted(2), Operator::Eq(plain_name(), plain("value_2"))])));
+    assert_eq!(vec![_id(2), _id(3)], search(Operator::Not(Box::new(encrypted(1)))));
+    assert_eq!(vec![_id(1), _id(3)], search(Operator::Neq(plain_name(), plain("value_2"))));
+    assert_eq!(vec![_id(2), _id(3)], search(Operator::Gt(plain_name(), plain("value_1"))));
+    assert_eq!(vec![_id(1), _id(2)], search(Operator::Lte(plain_name(), plain("value_2"))));
+    assert_eq!(vec![_id(1), _id(2), _id(3)], search(Operator::Like(plain_name(), plain("value%"))));
+    assert_eq!(vec![_id(1), _id(3)], search(Operator::In(plain_name(), vec![plain("value_1"), plain("value_3")])));
+    assert_eq!(Vec::<Vec<u8>>::new(), search(Operator::Eq(QueryTagName::EncryptedTagName(vec![3]), TargetValue::Encrypted(vec![1]))));
+
+    let res = storage.search(&_type(1), &Operator::Gt(QueryTagName::EncryptedTagName(vec![1]), TargetValue::Encrypted(vec![1])), None);
+    assert_kind!(IndyErrorKind::WalletQueryError, res);
+
+    let options = r#"{"retrieveRecords": false, "retrieveTotalCount": true}"#;
+    let mut iterator = storage.search(&_type(1), &encrypted(1), Some(options)).unwrap();
+    assert_eq!(Some(1), iterator.get_total_count().unwrap());
+    assert!(iterator.next().unwrap().is_none());
+
+    let options = r#"{"retrieveRecords": true, "retrieveTotalCount": true, "retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#;
+    let mut iterator = storage.search(&_type(1), &encrypted(2), Some(options)).unwrap();
+    assert_eq!(Some(1), iterator.get_total_count().unwrap());
+    let record = iterator.next().unwrap().unwrap();
+    assert_eq!((Some(_type(1)), Some(_value(2))), (record.type_, record.value));
+    assert_eq!(2, record.tags.unwrap().len());
+}
+
+fn _storage(storage_type: &dyn WalletStorageType, id: &str, config: Option<&str>, credentials: Option<&str>) -> Box<dyn WalletStorage> {
+    storage_type.create_storage(id, config, credentials, &_metadata()).unwrap();
+    storage_type.open_storage(id, config, credentials).unwrap()
+}
+
+fn _collect(mut iterator: Box<dyn super::StorageIterator>) -> Vec<StorageRecord> {
+    let mut records = Vec::new();
+    while let Some(record) = iterator.next().unwrap() {
+        records.push(record);
+    }
+    records
+}
+
+fn _get_tags(storage: &dyn WalletStorage, id: &[u8]) -> Vec<Tag> {
+    storage.get(&_type(1), id, RETRIEVE_ALL).unwrap().tags.unwrap()
+}
+
+fn _metadata() -> Vec<u8> {
+    vec![1, 2, 3, 4, 5, 6, 7, 8]
+}
+
+fn _type(i: u8) -> Vec<u8> {
+    vec![i, 1 + i, 2 + i]
+}
+
+fn _id(i: u8) -> Vec<u8> {
+    vec![3 + i, 4 + i, 5 + i]
+}
+
+fn _value(i: u8) -> EncryptedValue {
+    EncryptedValue { data: vec![6 + i, 7 + i, 8 + i], key: vec![9 + i, 10 + i, 11 + i] }
+}
+
+fn _tags() -> Vec<Tag> {
+    vec![
+        Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+        Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string())
+    ]
+}
+
+fn _new_tag() -> Tag {
+    Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))
+}
+
+fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+    v.sort();
+    v
+}
@@ -515,8 +515,14 @@ impl WalletStorage for SQLiteStorage {
             retrieve_tags: true,
         };
 
+        let total_count: i64 = self.conn.query_row(
+            "SELECT count(*) FROM items;",
+            rusqlite::NO_PARAMS,
+            |row| { row.get(0) },
+        )?;
+
         let tag_retriever = Some(TagRetriever::new_owned(self.conn.clone())?);
-        let storage_iterator = SQLiteStorageIterator::new(Some(statement), &[], fetch_options, tag_retriever, None)?;
+        let storage_iterator = SQLiteStorageIterator::new(Some(statement), &[], fetch_options, tag_retriever, Some(total_count as usize))?;
 
         Ok(Box::new(storage_iterator))
     }
@@ -762,9 +768,14 @@ mod tests {
     use utils::test;
 
     use super::*;
-    use super::super::Tag;
+    use super::super::{conformance, Tag};
     use std::path::Path;
 
+    #[test]
+    fn sqlite_storage_passes_conformance_suite() {
+        conformance::run(&SQLiteStorageType::new(), "sqlite_storage_passes_conformance_suite", None, None);
+    }
+
     #[test]
     fn sqlite_storage_type_create_works() {
         _cleanup("sqlite_storage_type_create_works");
@@ -1030,6 +1041,7 @@ mod tests {
             storage.add(&_type2(), &_id2(), &_value2(), &_tags()).unwrap();
 
             let mut storage_iterator = storage.get_all().unwrap();
+            assert_eq!(Some(2), storage_iterator.get_total_count().unwrap());
 
             let record = storage_iterator.next().unwrap().unwrap();
             assert_eq!(record.type_.unwrap(), _type1());
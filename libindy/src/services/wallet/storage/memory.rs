@@ -0,0 +1,357 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::rc::Rc;
+
+use serde_json;
+
+use errors::prelude::*;
+use services::wallet::language::{self, Operator, TargetValue};
+
+use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::super::{RecordOptions, SearchOptions};
+
+/// Reference `WalletStorageType` that keeps all wallets in process memory.
+///
+/// Registered as `memory` storage type. Wallets live until they are deleted or the process exits,
+/// config and credentials are ignored.
+pub struct MemoryStorageType {
+    wallets: RefCell<HashMap<String, Rc<RefCell<MemoryWallet>>>>
+}
+
+struct MemoryWallet {
+    metadata: Vec<u8>,
+    records: BTreeMap<(Vec<u8>, Vec<u8>), MemoryRecord>
+}
+
+struct MemoryRecord {
+    value: EncryptedValue,
+    tags: Vec<Tag>
+}
+
+pub struct MemoryStorage {
+    wallet: Rc<RefCell<MemoryWallet>>
+}
+
+struct MemoryStorageIterator {
+    records: VecDeque<StorageRecord>,
+    total_count: Option<usize>
+}
+
+impl MemoryStorageType {
+    pub fn new() -> MemoryStorageType {
+        MemoryStorageType {
+            wallets: RefCell::new(HashMap::new())
+        }
+    }
+}
+
+impl WalletStorageType for MemoryStorageType {
+    fn create_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
+        let mut wallets = self.wallets.borrow_mut();
+
+        if wallets.contains_key(id) {
+            return Err(err_msg(IndyErrorKind::WalletAlreadyExists, format!("Wallet {} already exists", id)));
+        }
+
+        wallets.insert(id.to_string(), Rc::new(RefCell::new(MemoryWallet {
+            metadata: metadata.to_vec(),
+            records: BTreeMap::new(),
+        })));
+
+        Ok(())
+    }
+
+    fn open_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
+        let wallet = self.wallets.borrow().get(id).cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletNotFound, format!("Wallet {} not found", id)))?;
+
+        Ok(Box::new(MemoryStorage { wallet }))
+    }
+
+    fn delete_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<()> {
+        self.wallets.borrow_mut().remove(id)
+            .map(|_| ())
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletNotFound, format!("Wallet {} not found", id)))
+    }
+}
+
+impl WalletStorage for MemoryStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let options: RecordOptions = serde_json::from_str(options)
+            .to_indy(IndyErrorKind::InvalidStructure, "RecordOptions is malformed json")?;
+
+        let wallet = self.wallet.borrow();
+
+        let record = wallet.records.get(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        Ok(record.to_storage_record(type_, id, &options))
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        let mut wallet = self.wallet.borrow_mut();
+        let key = (type_.to_vec(), id.to_vec());
+
+        if wallet.records.contains_key(&key) {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists"));
+        }
+
+        let mut record = MemoryRecord { value: value.clone(), tags: Vec::new() };
+        record.add_tags(tags);
+
+        wallet.records.insert(key, record);
+
+        Ok(())
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        self._with_record(type_, id, |record| record.value = value.clone())
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self._with_record(type_, id, |record| record.add_tags(tags))
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self._with_record(type_, id, |record| {
+            record.tags.clear();
+            record.add_tags(tags);
+        })
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        self._with_record(type_, id, |record| {
+            record.tags.retain(|tag| !tag_names.iter().any(|tag_name| match (tag, tag_name) {
+                (&Tag::Encrypted(ref name, _), &TagName::OfEncrypted(ref deleted_name)) => name == deleted_name,
+                (&Tag::PlainText(ref name, _), &TagName::OfPlain(ref deleted_name)) => name == deleted_name,
+                _ => false
+            }))
+        })
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        self.wallet.borrow_mut().records.remove(&(type_.to_vec(), id.to_vec()))
+            .map(|_| ())
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        Ok(self.wallet.borrow().metadata.clone())
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.wallet.borrow_mut().metadata = metadata.to_vec();
+        Ok(())
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+        let options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let records = self.wallet.borrow().records.iter()
+            .map(|(&(ref type_, ref id), record)| record.to_storage_record(type_, id, &options))
+            .collect();
+
+        Ok(Box::new(MemoryStorageIterator { records, total_count: None }))
+    }
+
+    fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        let record_options = RecordOptions {
+            retrieve_type: search_options.retrieve_type,
+            retrieve_value: search_options.retrieve_value,
+            retrieve_tags: search_options.retrieve_tags,
+        };
+
+        let wallet = self.wallet.borrow();
+
+        let mut records = VecDeque::new();
+
+        for (&(ref record_type, ref id), record) in wallet.records.iter() {
+            if record_type.as_slice() == type_ && _matches(query, &record.tags)? {
+                records.push_back(record.to_storage_record(record_type, id, &record_options));
+            }
+        }
+
+        let total_count = if search_options.retrieve_total_count { Some(records.len()) } else { None };
+
+        if !search_options.retrieve_records {
+            records.clear();
+        }
+
+        Ok(Box::new(MemoryStorageIterator { records, total_count }))
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        Ok(())
+    }
+}
+
+impl MemoryStorage {
+    fn _with_record<F>(&self, type_: &[u8], id: &[u8], f: F) -> IndyResult<()> where F: FnOnce(&mut MemoryRecord) {
+        let mut wallet = self.wallet.borrow_mut();
+
+        let record = wallet.records.get_mut(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        f(record);
+
+        Ok(())
+    }
+}
+
+impl MemoryRecord {
+    // replaces existing tags of the same name, as SQLite storage does
+    fn add_tags(&mut self, tags: &[Tag]) {
+        for tag in tags {
+            self.tags.retain(|existing| !_same_tag_name(existing, tag));
+            self.tags.push(tag.clone());
+        }
+    }
+
+    fn to_storage_record(&self, type_: &[u8], id: &[u8], options: &RecordOptions) -> StorageRecord {
+        StorageRecord::new(id.to_vec(),
+                           if options.retrieve_value { Some(self.value.clone()) } else { None },
+                           if options.retrieve_type { Some(type_.to_vec()) } else { None },
+                           if options.retrieve_tags { Some(self.tags.clone()) } else { None })
+    }
+}
+
+impl StorageIterator for MemoryStorageIterator {
+    fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        Ok(self.records.pop_front())
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        Ok(self.total_count)
+    }
+}
+
+fn _same_tag_name(tag: &Tag, other: &Tag) -> bool {
+    match (tag, other) {
+        (&Tag::Encrypted(ref name, _), &Tag::Encrypted(ref other_name, _)) => name == other_name,
+        (&Tag::PlainText(ref name, _), &Tag::PlainText(ref other_name, _)) => name == other_name,
+        _ => false
+    }
+}
+
+// Evaluates Wallet Query Language against record tags with the same semantics as SQL translation of SQLite storage
+fn _matches(query: &Operator, tags: &[Tag]) -> IndyResult<bool> {
+    match *query {
+        Operator::And(ref operators) => {
+            for operator in operators {
+                if !_matches(operator, tags)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        Operator::Or(ref operators) => {
+            for operator in operators {
+                if _matches(operator, tags)? {
+                    return Ok(true);
+                }
+            }
+            Ok(operators.is_empty())
+        }
+        Operator::Not(ref operator) => _matches(operator, tags).map(|matches| !matches),
+        Operator::Eq(ref name, ref value) => _match_tag(name, value, tags, "equality", |tag_value, value| tag_value == value),
+        Operator::Neq(ref name, ref value) => _match_tag(name, value, tags, "$neq", |tag_value, value| tag_value != value),
+        Operator::Gt(ref name, ref value) => _match_plain_tag(name, value, tags, "$gt", |tag_value, value| tag_value > value),
+        Operator::Gte(ref name, ref value) => _match_plain_tag(name, value, tags, "$gte", |tag_value, value| tag_value >= value),
+        Operator::Lt(ref name, ref value) => _match_plain_tag(name, value, tags, "$lt", |tag_value, value| tag_value < value),
+        Operator::Lte(ref name, ref value) => _match_plain_tag(name, value, tags, "$lte", |tag_value, value| tag_value <= value),
+        Operator::Like(ref name, ref value) => _match_plain_tag(name, value, tags, "$like", _like),
+        Operator::In(ref name, ref values) => {
+            for value in values {
+                if _match_tag(name, value, tags, "$in", |tag_value, value| tag_value == value)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+    }
+}
+
+fn _match_tag<F>(name: &language::TagName, value: &TargetValue, tags: &[Tag], operator: &str, f: F) -> IndyResult<bool>
+    where F: Fn(&[u8], &[u8]) -> bool {
+    match (name, value) {
+        (&language::TagName::EncryptedTagName(ref name), &TargetValue::Encrypted(ref value)) =>
+            Ok(tags.iter().any(|tag| match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_value) => tag_name == name && f(tag_value, value),
+                _ => false
+            })),
+        (&language::TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) =>
+            _match_plain_tag(name, value, tags, operator, |tag_value, value| f(tag_value.as_bytes(), value.as_bytes())),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {} operator", operator)))
+    }
+}
+
+fn _match_plain_tag<F>(name: &language::TagName, value: &TargetValue, tags: &[Tag], operator: &str, f: F) -> IndyResult<bool>
+    where F: Fn(&str, &str) -> bool {
+    match (name, value) {
+        (&language::TagName::PlainTagName(ref name), &TargetValue::Unencrypted(ref value)) =>
+            Ok(tags.iter().any(|tag| match *tag {
+                Tag::PlainText(ref tag_name, ref tag_value) => tag_name == name && f(tag_value, value),
+                _ => false
+            })),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {} operator", operator)))
+    }
+}
+
+// SQL LIKE: `%` matches any sequence of characters, `_` any single character, ASCII letters match case-insensitively
+fn _like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    fn _like_from(value: &[char], pattern: &[char]) -> bool {
+        match pattern.split_first() {
+            None => value.is_empty(),
+            Some((&'%', rest)) => (0..=value.len()).any(|skip| _like_from(&value[skip..], rest)),
+            Some((&'_', rest)) => !value.is_empty() && _like_from(&value[1..], rest),
+            Some((c, rest)) => value.first().map_or(false, |v| v.eq_ignore_ascii_case(c)) && _like_from(&value[1..], rest)
+        }
+    }
+
+    _like_from(&value, &pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::conformance;
+
+    #[test]
+    fn memory_storage_passes_conformance_suite() {
+        conformance::run(&MemoryStorageType::new(), "memory_storage_passes_conformance_suite", None, None);
+    }
+
+    #[test]
+    fn memory_storage_type_open_works_for_deleted() {
+        let storage_type = MemoryStorageType::new();
+        storage_type.create_storage("wallet", None, None, &[1, 2, 3]).unwrap();
+        storage_type.delete_storage("wallet", None, None).unwrap();
+
+        let res = storage_type.open_storage("wallet", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn like_works() {
+        assert!(_like("tag_value", "tag%"));
+        assert!(_like("tag_value", "%value"));
+        assert!(_like("tag_value", "tag_v_lue"));
+        assert!(_like("tag_value", "%"));
+        assert!(_like("tag_value", "TAG%"));
+        assert!(!_like("tag_value", "value%"));
+        assert!(!_like("tag_value", "tag_valu"));
+    }
+}
@@ -1,5 +1,9 @@
 pub mod default;
 pub mod plugged;
+#[cfg(feature = "memory_storage")]
+pub mod memory;
+#[cfg(test)]
+pub mod conformance;
 
 use errors::prelude::*;
 use services::wallet::language;
@@ -36,11 +40,22 @@ impl StorageRecord {
     }
 }
 
+/// Iterator over records returned by `WalletStorage::get_all` and `WalletStorage::search`.
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
 }
 
+/// Opened wallet storage.
+///
+/// Storage only keeps opaque data: type, id and tag names are encrypted by `WalletService`
+/// (except names of plain tags), values are encrypted with per-record keys.
+/// A record is identified by type and id together. Missing records are reported as
+/// `WalletItemNotFound`, duplicate ones as `WalletItemAlreadyExists`.
+/// `add_tags` replaces values of existing tags with the same name, `update_tags` replaces all tags.
+/// `search` must evaluate the query with the same semantics as `default` (SQLite) storage does.
+///
+/// `conformance::run` checks all of these requirements for an implementation.
 pub trait WalletStorage {
     fn get(&self, type_: &[u8], id: &[u8], options: &str) -> Result<StorageRecord, IndyError>;
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> Result<(), IndyError>;
@@ -56,6 +71,11 @@ pub trait WalletStorage {
     fn close(&mut self) -> Result<(), IndyError>;
 }
 
+/// Storage backend registered by name in `WalletService`.
+///
+/// The wallet config `storage_type` selects the backend, `storage_config` and `storage_credentials`
+/// are passed through as is. Besides built-in `default` storage, backends can be registered
+/// through `indy_register_wallet_storage` (see `plugged` module).
 pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError>;
@@ -209,6 +209,23 @@ impl Wallet {
         Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)))
     }
 
+    // Counts stored records without decrypting them, for storages that don't report the total count
+    pub fn count_all(&self) -> IndyResult<usize> {
+        let mut all_items = self.storage.get_all()?;
+
+        if let Some(total_count) = all_items.get_total_count()? {
+            return Ok(total_count);
+        }
+
+        let mut count = 0;
+
+        while let Some(_) = all_items.next()? {
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
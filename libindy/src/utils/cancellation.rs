@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use api::CommandHandle;
+
+lazy_static! {
+    static ref CANCELLATION_FLAGS: Mutex<HashMap<CommandHandle, Arc<AtomicBool>>> = Mutex::new(HashMap::new());
+}
+
+/// Cancellation flag of a long running command addressed by the command handle passed by the caller.
+/// The flag is registered until the token is dropped.
+#[derive(Debug)]
+pub struct CancellationToken {
+    command_handle: CommandHandle,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn register(command_handle: CommandHandle) -> CancellationToken {
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        CANCELLATION_FLAGS.lock().unwrap().insert(command_handle, cancelled.clone());
+
+        CancellationToken { command_handle, cancelled }
+    }
+
+    /// Returns false if there is no running command for the handle.
+    pub fn cancel(command_handle: CommandHandle) -> bool {
+        match CANCELLATION_FLAGS.lock().unwrap().get(&command_handle) {
+            Some(cancelled) => {
+                cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for CancellationToken {
+    fn drop(&mut self) {
+        let mut flags = CANCELLATION_FLAGS.lock().unwrap();
+
+        let registered = flags.get(&self.command_handle)
+            .map(|cancelled| Arc::ptr_eq(cancelled, &self.cancelled))
+            .unwrap_or(false);
+
+        if registered {
+            flags.remove(&self.command_handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use api::next_command_handle;
+
+    #[test]
+    fn cancellation_token_works() {
+        let command_handle = next_command_handle();
+        let token = CancellationToken::register(command_handle);
+
+        assert!(!token.is_cancelled());
+        assert!(CancellationToken::cancel(command_handle));
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn cancellation_token_works_for_finished_command() {
+        let command_handle = next_command_handle();
+
+        {
+            let _token = CancellationToken::register(command_handle);
+        }
+
+        assert!(!CancellationToken::cancel(command_handle));
+    }
+}
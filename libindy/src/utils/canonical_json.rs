@@ -0,0 +1,47 @@
+use serde::Serialize;
+use serde_json::{self, Value};
+
+/// Serializes value to json with object keys sorted recursively,
+/// so equal values always produce the same string.
+pub fn to_string<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    to_string_from_value(serde_json::to_value(value)?)
+}
+
+pub fn to_string_from_value(value: Value) -> serde_json::Result<String> {
+    serde_json::to_string(&sort_keys(value))
+}
+
+pub fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|&(ref a, _), &(ref b, _)| a.cmp(b));
+
+            Value::Object(entries
+                .into_iter()
+                .map(|(key, value)| (key, sort_keys(value)))
+                .collect())
+        }
+        Value::Array(array) => Value::Array(array.into_iter().map(sort_keys).collect()),
+        value => value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_string_works_for_different_keys_order() {
+        let value_1 = json!({"b": 1, "a": {"d": [{"f": 1, "e": 2}], "c": null}});
+        let value_2 = json!({"a": {"c": null, "d": [{"e": 2, "f": 1}]}, "b": 1});
+
+        assert_eq!(r#"{"a":{"c":null,"d":[{"e":2,"f":1}]},"b":1}"#, to_string(&value_1).unwrap());
+        assert_eq!(to_string(&value_1).unwrap(), to_string(&value_2).unwrap());
+    }
+
+    #[test]
+    fn to_string_keeps_array_order() {
+        assert_eq!("[3,1,2]", to_string(&json!([3, 1, 2])).unwrap());
+    }
+}
@@ -223,3 +223,15 @@ macro_rules! boxed_callback_string {
         })
     }
 }
+
+// Same as `boxed_callback_string` for results carrying credential attribute values
+macro_rules! boxed_callback_sensitive_string {
+    ($method_name: expr, $cb: ident, $command_handle: ident) => {
+        Box::new(move |result| {
+            let (err, result_string) = prepare_result_1!(result, String::new());
+            trace!("{}: result: {:?}", $method_name, sensitive!(&result_string));
+            let result_string = ctypes::string_to_cstring(result_string);
+            $cb($command_handle, err, result_string.as_ptr())
+        })
+    }
+}
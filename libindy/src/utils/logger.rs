@@ -15,6 +15,7 @@ use log::{Record, Metadata};
 use libc::{c_void, c_char};
 use std::ffi::CString;
 use std::ptr;
+use std::fmt;
 
 use errors::prelude::*;
 use utils::ctypes;
@@ -279,4 +280,45 @@ macro_rules! secret {
 #[macro_export]
 macro_rules! secret {
     ($val:expr) => {{ "_" }};
+}
+
+// Values derived from credential attributes (PII) are hidden in any build unless `log_sensitive` feature is enabled
+#[cfg(feature = "log_sensitive")]
+#[macro_export]
+macro_rules! sensitive {
+    ($val:expr) => {{ $val }};
+}
+
+#[cfg(not(feature = "log_sensitive"))]
+#[macro_export]
+macro_rules! sensitive {
+    ($val:expr) => {{ $crate::utils::logger::Redacted::new(&$val) }};
+}
+
+/// Debug view that replaces a logged value with a prefix of sha256 hash of its debug representation.
+/// Equal values produce equal prefixes, so they can still be correlated across log lines.
+#[cfg(not(feature = "log_sensitive"))]
+pub struct Redacted(String);
+
+#[cfg(not(feature = "log_sensitive"))]
+impl Redacted {
+    pub fn new<T: fmt::Debug + ?Sized>(val: &T) -> Redacted {
+        Redacted(redact(&format!("{:?}", val)))
+    }
+}
+
+#[cfg(not(feature = "log_sensitive"))]
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(not(feature = "log_sensitive"))]
+pub fn redact(value: &str) -> String {
+    use utils::crypto::hash::hash;
+
+    hash(value.as_bytes())
+        .map(|hash| format!("sha256:{}", ::hex::encode(&hash[..4])))
+        .unwrap_or_else(|_| "_".to_string())
 }
\ No newline at end of file
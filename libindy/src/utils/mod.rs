@@ -1,3 +1,7 @@
+pub mod cancellation;
+
+pub mod canonical_json;
+
 pub mod environment;
 
 #[macro_use]
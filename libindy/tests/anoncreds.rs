@@ -14,7 +14,7 @@ use indy::ErrorCode;
 use utils::constants::*;
 use utils::Setup;
 
-use utils::domain::anoncreds::credential::CredentialInfo;
+use utils::domain::anoncreds::credential::{Credential, CredentialInfo};
 use utils::domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest, RequestedCredential};
 use utils::domain::anoncreds::proof::Proof;
 use utils::domain::crypto::did::DidValue;
@@ -45,13 +45,76 @@ mod high_cases {
         }
     }
 
+    mod issuer_create_typed_schema {
+        use super::*;
+
+        #[test]
+        fn issuer_create_typed_schema_works() {
+            Setup::empty();
+
+            let (schema_id, schema_json) = anoncreds::issuer_create_typed_schema(ISSUER_DID,
+                                                                                 GVT_SCHEMA_NAME,
+                                                                                 SCHEMA_VERSION,
+                                                                                 GVT_SCHEMA_ATTRIBUTES,
+                                                                                 r#"{"age": "integer", "name": "string"}"#).unwrap();
+            assert_eq!(anoncreds::gvt_schema_id(), schema_id);
+
+            let schema: serde_json::Value = serde_json::from_str(&schema_json).unwrap();
+            assert_eq!(json!({"age": "integer", "name": "string"}), schema["attributeTypes"]);
+        }
+
+        #[test]
+        fn issuer_create_typed_schema_works_for_type_of_unknown_attribute() {
+            Setup::empty();
+
+            let res = anoncreds::issuer_create_typed_schema(ISSUER_DID,
+                                                            GVT_SCHEMA_NAME,
+                                                            SCHEMA_VERSION,
+                                                            GVT_SCHEMA_ATTRIBUTES,
+                                                            r#"{"status": "integer"}"#);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
     mod issuer_create_and_store_credential_def {
         use super::*;
+        use std::ffi::CString;
+        use std::ptr::null;
+        use utils::{callback, timeout};
 
         #[test]
         fn issuer_create_and_store_credential_def_works() {
             anoncreds::init_common_wallet();
         }
+
+        #[test]
+        fn issuer_create_and_store_credential_def_works_without_blocking_other_commands() {
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet("issuer_create_and_store_credential_def_works_without_blocking_other_commands").unwrap();
+
+            // key generation for revocable credential definition runs in the crypto thread pool
+            let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string_string();
+
+            let err = unsafe {
+                indy_sys::anoncreds::indy_issuer_create_and_store_credential_def(command_handle,
+                                                                                 wallet_handle,
+                                                                                 CString::new(ISSUER_DID).unwrap().as_ptr(),
+                                                                                 CString::new(anoncreds::gvt_schema_json()).unwrap().as_ptr(),
+                                                                                 CString::new(TAG_1).unwrap().as_ptr(),
+                                                                                 null(),
+                                                                                 CString::new(anoncreds::revocation_cred_def_config()).unwrap().as_ptr(),
+                                                                                 cb)
+            };
+            assert_eq!(ErrorCode::Success, ErrorCode::from(err));
+
+            // verifier command is served while key generation is still in progress
+            anoncreds::generate_nonce().unwrap();
+            assert!(receiver.try_recv().is_err());
+
+            let (err, _, _) = receiver.recv_timeout(timeout::long_timeout()).unwrap();
+            assert_eq!(ErrorCode::Success, ErrorCode::from(err));
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
     }
 
     mod issuer_create_credential_offer {
@@ -81,6 +144,184 @@ mod high_cases {
         }
     }
 
+    mod prover_create_credential_reqs {
+        use super::*;
+
+        #[test]
+        fn prover_create_credential_reqs_works() {
+            let (credential_def, _, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let offered_credentials = (0..3)
+                .map(|_| json!({
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(
+                        &anoncreds::issuer_create_credential_offer(wallet_handle, &anoncreds::issuer_1_gvt_cred_def_id()).unwrap()).unwrap(),
+                    "cred_def": serde_json::from_str::<serde_json::Value>(credential_def).unwrap(),
+                }))
+                .collect::<Vec<serde_json::Value>>();
+
+            let cred_reqs_json = anoncreds::prover_create_credential_reqs(wallet_handle,
+                                                                          DID_MY1,
+                                                                          &json!(offered_credentials).to_string(),
+                                                                          COMMON_MASTER_SECRET).unwrap();
+            let cred_reqs: Vec<serde_json::Value> = serde_json::from_str(&cred_reqs_json).unwrap();
+
+            assert_eq!(3, cred_reqs.len());
+            for cred_req in cred_reqs.iter() {
+                assert_eq!(json!(anoncreds::issuer_1_gvt_cred_def_id()), cred_req["cred_req"]["cred_def_id"]);
+                assert_eq!(json!(COMMON_MASTER_SECRET), cred_req["cred_req_metadata"]["master_secret_name"]);
+            }
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_create_credential_reqs_works_for_partial_failure() {
+            let (credential_def, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let credential_offer: serde_json::Value = serde_json::from_str(credential_offer).unwrap();
+            let mut other_credential_offer = credential_offer.clone();
+            other_credential_offer["key_correctness_proof"]["c"] = serde_json::Value::String("11111111".to_string());
+
+            let credential_def: serde_json::Value = serde_json::from_str(credential_def).unwrap();
+            let offered_credentials = json!([
+                {"cred_offer": other_credential_offer, "cred_def": credential_def},
+                {"cred_offer": credential_offer, "cred_def": credential_def},
+            ]);
+
+            let cred_reqs_json = anoncreds::prover_create_credential_reqs(wallet_handle,
+                                                                          DID_MY1,
+                                                                          &offered_credentials.to_string(),
+                                                                          COMMON_MASTER_SECRET).unwrap();
+            let cred_reqs: Vec<serde_json::Value> = serde_json::from_str(&cred_reqs_json).unwrap();
+
+            assert_eq!(2, cred_reqs.len());
+            assert_eq!(json!(ErrorCode::CommonInvalidStructure as i32), cred_reqs[0]["error"]["code"]);
+            assert!(cred_reqs[0]["cred_req"].is_null());
+            assert!(cred_reqs[1]["error"].is_null());
+            assert_eq!(json!(anoncreds::issuer_1_gvt_cred_def_id()), cred_reqs[1]["cred_req"]["cred_def_id"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_create_credential_reqs_works_for_invalid_master_secret() {
+            let (credential_def, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let offered_credentials = json!([{
+                "cred_offer": serde_json::from_str::<serde_json::Value>(credential_offer).unwrap(),
+                "cred_def": serde_json::from_str::<serde_json::Value>(credential_def).unwrap(),
+            }]);
+
+            let res = anoncreds::prover_create_credential_reqs(wallet_handle,
+                                                               DID_MY1,
+                                                               &offered_credentials.to_string(),
+                                                               "invalid_master_secret_name");
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_validate_attributes_for_schema {
+        use super::*;
+
+        #[test]
+        fn issuer_validate_attributes_for_schema_works() {
+            Setup::empty();
+
+            let problems = anoncreds::issuer_validate_attributes_for_schema(&anoncreds::gvt_schema_json(),
+                                                                            &anoncreds::gvt_credential_values_json()).unwrap();
+            assert_eq!(json!([]), serde_json::from_str::<serde_json::Value>(&problems).unwrap());
+        }
+
+        #[test]
+        fn issuer_validate_attributes_for_schema_works_for_missing_and_extra_attributes() {
+            Setup::empty();
+
+            let mut cred_values = anoncreds::gvt_credential_values();
+            cred_values.remove("height");
+            cred_values.insert("status".to_string(), cred_values["age"].clone());
+
+            let problems = anoncreds::issuer_validate_attributes_for_schema(&anoncreds::gvt_schema_json(),
+                                                                            &serde_json::to_string(&cred_values).unwrap()).unwrap();
+            assert_eq!(json!([
+                {"attr": "height", "problem": "missing"},
+                {"attr": "status", "problem": "not_in_schema"}
+            ]), serde_json::from_str::<serde_json::Value>(&problems).unwrap());
+        }
+
+        #[test]
+        fn issuer_validate_attributes_for_schema_works_for_value_not_matching_attribute_type() {
+            Setup::empty();
+
+            let (_, schema_json) = anoncreds::issuer_create_typed_schema(ISSUER_DID,
+                                                                         GVT_SCHEMA_NAME,
+                                                                         SCHEMA_VERSION,
+                                                                         GVT_SCHEMA_ATTRIBUTES,
+                                                                         r#"{"age": "string"}"#).unwrap();
+
+            let problems = anoncreds::issuer_validate_attributes_for_schema(&schema_json,
+                                                                            &anoncreds::gvt_credential_values_json()).unwrap();
+            assert_eq!(json!([
+                {"attr": "age", "problem": "invalid_encoding"}
+            ]), serde_json::from_str::<serde_json::Value>(&problems).unwrap());
+        }
+    }
+
+    mod issuer_encode_credential_values {
+        use super::*;
+
+        fn _typed_schema_json() -> String {
+            anoncreds::issuer_create_typed_schema(ISSUER_DID,
+                                                  GVT_SCHEMA_NAME,
+                                                  SCHEMA_VERSION,
+                                                  r#"["name", "age", "birthdate", "zip"]"#,
+                                                  r#"{"age": "integer", "birthdate": "date", "zip": "string"}"#).unwrap().1
+        }
+
+        #[test]
+        fn issuer_encode_credential_values_works() {
+            Setup::empty();
+
+            let raw_values = json!({"name": "Alex", "age": "28", "birthdate": "2000-02-29", "zip": "12345"}).to_string();
+
+            let cred_values = anoncreds::issuer_encode_credential_values(&_typed_schema_json(), &raw_values).unwrap();
+            let cred_values: serde_json::Value = serde_json::from_str(&cred_values).unwrap();
+
+            assert_eq!(json!({"raw": "Alex", "encoded": "1139481716457488690172217916278103335"}), cred_values["name"]);
+            assert_eq!(json!({"raw": "28", "encoded": "28"}), cred_values["age"]);
+            assert_eq!(json!({"raw": "2000-02-29", "encoded": "11016"}), cred_values["birthdate"]);
+            assert_eq!("12345", cred_values["zip"]["raw"]);
+            assert_ne!("12345", cred_values["zip"]["encoded"]);
+        }
+
+        #[test]
+        fn issuer_encode_credential_values_works_for_invalid_integer() {
+            Setup::empty();
+
+            let raw_values = json!({"name": "Alex", "age": "twenty eight", "birthdate": "2000-02-29", "zip": "12345"}).to_string();
+
+            let res = anoncreds::issuer_encode_credential_values(&_typed_schema_json(), &raw_values);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+
+        #[test]
+        fn issuer_encode_credential_values_works_for_invalid_date() {
+            Setup::empty();
+
+            let raw_values = json!({"name": "Alex", "age": "28", "birthdate": "29.02.2000", "zip": "12345"}).to_string();
+
+            let res = anoncreds::issuer_encode_credential_values(&_typed_schema_json(), &raw_values);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
     mod issuer_create_credential {
         use super::*;
 
@@ -90,6 +331,18 @@ mod high_cases {
         }
     }
 
+    mod issuer_cancel_credentials {
+        use super::*;
+
+        #[test]
+        fn issuer_cancel_credentials_works_for_unknown_handle() {
+            Setup::empty();
+
+            let res = anoncreds::issuer_cancel_credentials(-1);
+            assert_eq!(ErrorCode::CommonInvalidParam1, res.unwrap_err());
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -99,6 +352,58 @@ mod high_cases {
         }
     }
 
+    mod estimate_credential_storage {
+        use super::*;
+
+        #[test]
+        fn estimate_credential_storage_works_for_issued_credentials() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let cred_values = vec![anoncreds::gvt_credential_values_json(),
+                                   anoncreds::gvt2_credential_values_json(),
+                                   anoncreds::gvt3_credential_values_json()];
+
+            let mut stored_size = 0;
+            for cred_values_json in cred_values.iter() {
+                let (cred_json, _, _) = anoncreds::issuer_create_credential(wallet_handle,
+                                                                            &credential_offer,
+                                                                            &credential_req,
+                                                                            cred_values_json,
+                                                                            None,
+                                                                            None).unwrap();
+                let credential: Credential = serde_json::from_str(&cred_json).unwrap();
+                stored_size += serde_json::to_string(&credential).unwrap().len() as u64;
+            }
+
+            let attr_count = serde_json::from_str::<Vec<String>>(GVT_SCHEMA_ATTRIBUTES).unwrap().len();
+            let estimate = anoncreds::estimate_credential_storage(attr_count as u32, false, cred_values.len() as u32).unwrap();
+
+            // estimate is documented to follow the stored credential json length within 25%
+            assert!(estimate * 4 > stored_size * 3, "estimate {} is too low for {}", estimate, stored_size);
+            assert!(estimate * 4 < stored_size * 5, "estimate {} is too high for {}", estimate, stored_size);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod build_non_revoked_interval {
+        use super::*;
+
+        #[test]
+        fn build_non_revoked_interval_works() {
+            let interval_json = anoncreds::build_non_revoked_interval(100, 10).unwrap();
+            assert_eq!(json!({"from": 90, "to": 110}), serde_json::from_str::<serde_json::Value>(&interval_json).unwrap());
+        }
+
+        #[test]
+        fn build_non_revoked_interval_works_for_timestamp_less_than_tolerance() {
+            let interval_json = anoncreds::build_non_revoked_interval(5, 10).unwrap();
+            assert_eq!(json!({"from": 0, "to": 15}), serde_json::from_str::<serde_json::Value>(&interval_json).unwrap());
+        }
+    }
+
     mod prover_get_credentials {
         use super::*;
 
@@ -223,6 +528,22 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn prover_get_credentials_works_for_attr_value_comparison_without_range_queryable_policy() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let filter = json!({"attr::age::value": {"$gte": "18"}}).to_string();
+
+            let credentials = anoncreds::prover_get_credentials(wallet_handle, &filter).unwrap();
+            let credentials: Vec<CredentialInfo> = serde_json::from_str(&credentials).unwrap();
+
+            assert_eq!(credentials.len(), 0);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn prover_get_credentials_works_for_empty_result() {
             anoncreds::init_common_wallet();
@@ -267,6 +588,19 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn prover_get_credential_works_for_attribute_names() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let credential = anoncreds::prover_get_credential(wallet_handle, CREDENTIAL1_ID).unwrap();
+            let credential: serde_json::Value = serde_json::from_str(&credential).unwrap();
+            assert_eq!(json!(["age", "height", "name", "sex"]), credential["attribute_names"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn prover_get_credential_works_for_not_found() {
             anoncreds::init_common_wallet();
@@ -280,6 +614,74 @@ mod high_cases {
         }
     }
 
+    mod prover_get_credential_fingerprint {
+        use super::*;
+
+        #[test]
+        fn prover_get_credential_fingerprint_works() {
+            let (credential_def_json, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let issuer_wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("prover_get_credential_fingerprint_works").unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let (credential_req, credential_req_meta) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                                DID_MY1,
+                                                                                                &credential_offer,
+                                                                                                credential_def_json,
+                                                                                                COMMON_MASTER_SECRET).unwrap();
+
+            let cred_values = vec![anoncreds::gvt_credential_values_json(), anoncreds::gvt2_credential_values_json()];
+            let mut credentials = Vec::new();
+
+            for cred_values_json in cred_values.iter() {
+                let (credential_json, _, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                                  &credential_offer,
+                                                                                  &credential_req,
+                                                                                  cred_values_json,
+                                                                                  None,
+                                                                                  None).unwrap();
+                credentials.push(credential_json);
+            }
+
+            // the same credential is stored twice
+            let stored = vec![(CREDENTIAL1_ID, &credentials[0]), (anoncreds::CREDENTIAL2_ID, &credentials[0]), (anoncreds::CREDENTIAL3_ID, &credentials[1])];
+
+            for &(cred_id, credential_json) in stored.iter() {
+                anoncreds::prover_store_credential(prover_wallet_handle,
+                                                   cred_id,
+                                                   &credential_req_meta,
+                                                   credential_json,
+                                                   credential_def_json,
+                                                   None).unwrap();
+            }
+
+            let fingerprint_1 = anoncreds::prover_get_credential_fingerprint(prover_wallet_handle, CREDENTIAL1_ID).unwrap();
+            let fingerprint_2 = anoncreds::prover_get_credential_fingerprint(prover_wallet_handle, anoncreds::CREDENTIAL2_ID).unwrap();
+            let fingerprint_3 = anoncreds::prover_get_credential_fingerprint(prover_wallet_handle, anoncreds::CREDENTIAL3_ID).unwrap();
+
+            assert_eq!(64, fingerprint_1.len());
+            assert_eq!(fingerprint_1, fingerprint_2);
+            assert_ne!(fingerprint_1, fingerprint_3);
+
+            wallet::close_wallet(issuer_wallet_handle).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn prover_get_credential_fingerprint_works_for_not_found() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::prover_get_credential_fingerprint(wallet_handle, "other_cred_id");
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_credentials_search {
         use super::*;
 
@@ -2303,6 +2705,107 @@ mod high_cases {
         }
     }
 
+    mod prover_explain_unsatisfiable_proof_req {
+        use super::*;
+
+        #[test]
+        fn prover_explain_unsatisfiable_proof_req_works_for_not_satisfied_predicate() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({ "name":"name" })
+               }),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({ "name":"age", "p_type":">=", "p_value":65 })
+               }),
+            }).to_string();
+
+            let explanation_json = anoncreds::prover_explain_unsatisfiable_proof_req(wallet_handle, &proof_req, "{}").unwrap();
+            let explanation: serde_json::Value = serde_json::from_str(&explanation_json).unwrap();
+
+            assert_eq!(json!({
+                "attrs": {},
+                "predicates": {
+                    "predicate1_referent": "no credential satisfies predicate age>=65"
+                }
+            }), explanation);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_explain_unsatisfiable_proof_req_works_for_not_found_attribute() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({ "name":"some_attr" })
+               }),
+               "requested_predicates": json!({}),
+            }).to_string();
+
+            let explanation_json = anoncreds::prover_explain_unsatisfiable_proof_req(wallet_handle, &proof_req, "{}").unwrap();
+            let explanation: serde_json::Value = serde_json::from_str(&explanation_json).unwrap();
+
+            assert_eq!("no credential contains attribute \"some_attr\"", explanation["attrs"]["attr1_referent"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_explain_unsatisfiable_proof_req_works_for_non_revoked_and_not_revocable_credentials() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({ "name":"name" }),
+                   "attr2_referent": json!({ "name":"name", "non_revoked": json!({ "to": 100 }) })
+               }),
+               "requested_predicates": json!({}),
+            }).to_string();
+
+            let explanation_json = anoncreds::prover_explain_unsatisfiable_proof_req(wallet_handle, &proof_req, "{}").unwrap();
+            let explanation: serde_json::Value = serde_json::from_str(&explanation_json).unwrap();
+
+            assert_eq!(json!({
+                "attrs": {
+                    "attr2_referent": "non-revocation is requested, but no matching credential is revocable"
+                },
+                "predicates": {}
+            }), explanation);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_explain_unsatisfiable_proof_req_works_for_invalid_rev_reg_deltas() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::prover_explain_unsatisfiable_proof_req(wallet_handle, &anoncreds::proof_request_attr(), "[]");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_search_credentials_for_proof_req {
         use super::*;
 
@@ -2868,11 +3371,17 @@ mod high_cases {
         }
     }
 
-    mod prover_create_proof_works {
+    mod prover_get_proof_plan {
         use super::*;
 
+        fn _with_nonce(proof_req_json: &str, nonce: &str) -> String {
+            let mut proof_req: serde_json::Value = serde_json::from_str(proof_req_json).unwrap();
+            proof_req["nonce"] = json!(nonce);
+            proof_req.to_string()
+        }
+
         #[test]
-        fn prover_create_proof_works() {
+        fn prover_get_proof_plan_works_for_repeated_proof_request() {
             anoncreds::init_common_wallet();
 
             let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
@@ -2895,21 +3404,233 @@ mod high_cases {
                                            &anoncreds::cred_defs_for_proof(),
                                            "{}").unwrap();
 
+            let proof_req_json = _with_nonce(&anoncreds::proof_request_attr_and_predicate(), "987654321");
+
+            let plan_json = anoncreds::prover_get_proof_plan(wallet_handle, &proof_req_json, "{}").unwrap();
+            let plan: serde_json::Value = serde_json::from_str(&plan_json).unwrap();
+            assert_eq!(json!(CREDENTIAL1_ID), plan["requested_attributes"]["attr1_referent"]["cred_id"]);
+            assert_eq!(json!(true), plan["requested_attributes"]["attr1_referent"]["revealed"]);
+            assert_eq!(json!(CREDENTIAL1_ID), plan["requested_predicates"]["predicate1_referent"]["cred_id"]);
+
+            let proof_json = anoncreds::prover_create_proof(wallet_handle,
+                                                            &proof_req_json,
+                                                            &plan_json,
+                                                            COMMON_MASTER_SECRET,
+                                                            &anoncreds::schemas_for_proof(),
+                                                            &anoncreds::cred_defs_for_proof(),
+                                                            "{}").unwrap();
+            let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+            assert_eq!("Alex", proof.requested_proof.revealed_attrs.get("attr1_referent").unwrap().raw);
+
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
         #[test]
-        fn prover_create_proof_works_for_using_not_satisfy_credential() {
+        fn prover_get_proof_plan_works_for_unknown_proof_request() {
             anoncreds::init_common_wallet();
 
             let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
 
-            let proof_req = json!({
-               "nonce":"123432421212",
-               "name":"proof_req_1",
-               "version":"0.1",
-               "requested_attributes": json!({
-                   "attr1_referent": json!({
+            let plan_json = anoncreds::prover_get_proof_plan(wallet_handle, &anoncreds::proof_request_attr(), "{}").unwrap();
+            assert_eq!("null", plan_json);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_get_proof_plan_works_for_self_attested_attribute() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req_json = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({ "name":"name" }),
+                   "attr2_referent": json!({ "name":"phone" })
+               }),
+               "requested_predicates": json!({}),
+            }).to_string();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({ "attr2_referent": "8-800-300" }),
+                 "requested_attributes": json!({
+                    "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":true })
+                 }),
+                 "requested_predicates": json!({})
+            }).to_string();
+
+            anoncreds::prover_create_proof(wallet_handle,
+                                           &proof_req_json,
+                                           &requested_credentials_json,
+                                           COMMON_MASTER_SECRET,
+                                           &anoncreds::schemas_for_proof(),
+                                           &anoncreds::cred_defs_for_proof(),
+                                           "{}").unwrap();
+
+            let plan_json = anoncreds::prover_get_proof_plan(wallet_handle, &proof_req_json, "{}").unwrap();
+            let plan: serde_json::Value = serde_json::from_str(&plan_json).unwrap();
+            assert_eq!(json!({}), plan["self_attested_attributes"]);
+            assert_eq!(json!(CREDENTIAL1_ID), plan["requested_attributes"]["attr1_referent"]["cred_id"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_get_proof_plan_works_after_wallet_reopen() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({}),
+                 "requested_attributes": json!({
+                    "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":true })
+                 }),
+                 "requested_predicates": json!({})
+            }).to_string();
+
+            anoncreds::prover_create_proof(wallet_handle,
+                                           &anoncreds::proof_request_attr(),
+                                           &requested_credentials_json,
+                                           COMMON_MASTER_SECRET,
+                                           &anoncreds::schemas_for_proof(),
+                                           &anoncreds::cred_defs_for_proof(),
+                                           "{}").unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let plan_json = anoncreds::prover_get_proof_plan(wallet_handle, &anoncreds::proof_request_attr(), "{}").unwrap();
+            assert_eq!("null", plan_json);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod prover_create_proof_works {
+        use super::*;
+
+        #[test]
+        fn prover_create_proof_works() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({}),
+                 "requested_attributes": json!({
+                    "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":true })
+                 }),
+                 "requested_predicates": json!({
+                    "predicate1_referent": json!({ "cred_id": CREDENTIAL1_ID })
+                 })
+            }).to_string();
+
+            anoncreds::prover_create_proof(wallet_handle,
+                                           &anoncreds::proof_request_attr_and_predicate(),
+                                           &requested_credentials_json,
+                                           COMMON_MASTER_SECRET,
+                                           &anoncreds::schemas_for_proof(),
+                                           &anoncreds::cred_defs_for_proof(),
+                                           "{}").unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        fn _proof_req_for_age_range(p_value: i32, p_value_max: i32) -> String {
+            json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({}),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({ "name":"age", "p_type":">=", "p_value":p_value, "p_value_max":p_value_max })
+               }),
+            }).to_string()
+        }
+
+        #[test]
+        fn prover_create_proof_works_for_range_predicate() {
+            let (credential_def_json, _, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = _proof_req_for_age_range(18, 65);
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({}),
+                 "requested_attributes": json!({}),
+                 "requested_predicates": json!({
+                    "predicate1_referent": json!({ "cred_id": CREDENTIAL1_ID })
+                 })
+            }).to_string();
+
+            let cred_defs_json = json!({
+                anoncreds::issuer_1_gvt_cred_def_id(): serde_json::from_str::<serde_json::Value>(credential_def_json).unwrap()
+            }).to_string();
+
+            let proof_json = anoncreds::prover_create_proof(wallet_handle,
+                                                            &proof_req,
+                                                            &requested_credentials_json,
+                                                            COMMON_MASTER_SECRET,
+                                                            &anoncreds::schemas_for_proof(),
+                                                            &cred_defs_json,
+                                                            "{}").unwrap();
+
+            let valid = anoncreds::verifier_verify_proof(&proof_req,
+                                                         &proof_json,
+                                                         &anoncreds::schemas_for_proof(),
+                                                         &cred_defs_json,
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(valid);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_create_proof_works_for_range_predicate_not_satisfied() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({}),
+                 "requested_attributes": json!({}),
+                 "requested_predicates": json!({
+                    "predicate1_referent": json!({ "cred_id": CREDENTIAL1_ID })
+                 })
+            }).to_string();
+
+            // age of the credential is 28
+            let res = anoncreds::prover_create_proof(wallet_handle,
+                                                     &_proof_req_for_age_range(18, 28),
+                                                     &requested_credentials_json,
+                                                     COMMON_MASTER_SECRET,
+                                                     &anoncreds::schemas_for_proof(),
+                                                     &anoncreds::cred_defs_for_proof(),
+                                                     "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_create_proof_works_for_using_not_satisfy_credential() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
                        "name":"some_attr"
                    })
                }),
@@ -2937,6 +3658,94 @@ mod high_cases {
         }
     }
 
+    mod prover_clear_witness_cache {
+        use super::*;
+
+        #[test]
+        fn prover_clear_witness_cache_works() {
+            Setup::empty();
+
+            anoncreds::prover_clear_witness_cache().unwrap();
+        }
+    }
+
+    mod merge_proof_requests {
+        use super::*;
+
+        #[test]
+        fn merge_proof_requests_works_for_colliding_referents() {
+            let merged_json = anoncreds::merge_proof_requests(&anoncreds::proof_request_attr(),
+                                                              &anoncreds::proof_request_attr()).unwrap();
+            let merged: serde_json::Value = serde_json::from_str(&merged_json).unwrap();
+
+            let requested_attributes = merged["requested_attributes"].as_object().unwrap();
+            assert_eq!(2, requested_attributes.len());
+            assert_eq!("name", requested_attributes["attr1_referent"]["name"]);
+            assert_eq!("name", requested_attributes["attr1_referent_2"]["name"]);
+            assert_eq!("123432421212", merged["nonce"]);
+        }
+
+        #[test]
+        fn merge_proof_requests_works_for_different_nonces() {
+            let other_proof_req_json = anoncreds::proof_request_attr().replace("123432421212", "123432421213");
+
+            let res = anoncreds::merge_proof_requests(&anoncreds::proof_request_attr(), &other_proof_req_json);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
+    mod proof_request_could_be_satisfied_by_cred_def {
+        use super::*;
+
+        #[test]
+        fn proof_request_could_be_satisfied_by_cred_def_works() {
+            let satisfiable = anoncreds::proof_request_could_be_satisfied_by_cred_def(&anoncreds::proof_request_attr_and_predicate(),
+                                                                                      &anoncreds::credential_def_json(),
+                                                                                      &anoncreds::gvt_schema_json()).unwrap();
+            assert!(satisfiable);
+        }
+
+        #[test]
+        fn proof_request_could_be_satisfied_by_cred_def_works_for_predicate_attribute_not_in_schema() {
+            let proof_req_json = anoncreds::proof_request_attr_and_predicate().replace("\"age\"", "\"period\"");
+
+            let satisfiable = anoncreds::proof_request_could_be_satisfied_by_cred_def(&proof_req_json,
+                                                                                      &anoncreds::credential_def_json(),
+                                                                                      &anoncreds::gvt_schema_json()).unwrap();
+            assert!(!satisfiable);
+        }
+    }
+
+    mod compare_schemas {
+        use super::*;
+        use utils::domain::anoncreds::schema::Schema;
+
+        fn _upgraded_gvt_schema_json(version: &str) -> String {
+            let mut schema = anoncreds::gvt_schema();
+            schema.version = version.to_string();
+            schema.attr_names = vec!["Name", "age", "height", "weight"].into_iter().map(String::from).collect();
+            serde_json::to_string(&Schema::SchemaV1(schema)).unwrap()
+        }
+
+        #[test]
+        fn compare_schemas_works() {
+            let schema_diff_json = anoncreds::compare_schemas(&anoncreds::gvt_schema_json(), &_upgraded_gvt_schema_json("1.1")).unwrap();
+            let schema_diff: serde_json::Value = serde_json::from_str(&schema_diff_json).unwrap();
+
+            assert_eq!(json!(["weight"]), schema_diff["added"]);
+            assert_eq!(json!(["sex"]), schema_diff["removed"]);
+            assert_eq!(json!(["age", "height", "name"]), schema_diff["unchanged"]);
+            assert_eq!(json!(false), schema_diff["backward_compatible"]);
+            assert_eq!(json!(-1), schema_diff["version_order"]);
+        }
+
+        #[test]
+        fn compare_schemas_works_for_invalid_version() {
+            let res = anoncreds::compare_schemas(&anoncreds::gvt_schema_json(), &_upgraded_gvt_schema_json("1.x"));
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
     mod verifier_verify_proof {
         use super::*;
 
@@ -2976,6 +3785,31 @@ mod high_cases {
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
 
+        #[test]
+        fn verifier_verify_proof_works_for_proof_built_for_other_nonce() {
+            let mut proof: serde_json::Value = serde_json::from_str(&anoncreds::proof_json()).unwrap();
+            proof["nonce"] = json!("123432421212");
+            let proof_json = proof.to_string();
+
+            let valid = anoncreds::verifier_verify_proof(&anoncreds::proof_request_attr(),
+                                                         &proof_json,
+                                                         &anoncreds::schemas_for_proof(),
+                                                         &anoncreds::cred_defs_for_proof(),
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(valid);
+
+            let other_proof_req_json = anoncreds::proof_request_attr().replace("123432421212", "123432421213");
+
+            let res = anoncreds::verifier_verify_proof(&other_proof_req_json,
+                                                       &proof_json,
+                                                       &anoncreds::schemas_for_proof(),
+                                                       &anoncreds::cred_defs_for_proof(),
+                                                       "{}",
+                                                       "{}");
+            assert_code!(ErrorCode::AnoncredsProofRejected, res);
+        }
+
         #[test]
         fn verifier_verify_proof_works_for_proof_does_not_correspond_to_request_attribute() {
             let other_proof_req_json = json!({
@@ -3052,6 +3886,66 @@ mod high_cases {
         }
     }
 
+    mod verifier_set_audit_callback {
+        use super::*;
+
+        use std::collections::BTreeSet;
+        use std::ffi::CStr;
+        use std::sync::Mutex;
+
+        use utils::domain::anoncreds::proof::VerificationAuditRecord;
+
+        lazy_static! {
+            static ref AUDIT_RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        }
+
+        extern fn _audit_callback(_context: *const ::std::os::raw::c_void, audit_record_json: *const ::std::os::raw::c_char) {
+            let audit_record_json = unsafe { CStr::from_ptr(audit_record_json) }.to_str().unwrap().to_string();
+            AUDIT_RECORDS.lock().unwrap().push(audit_record_json);
+        }
+
+        fn _find_record(nonce: &str, revealed_attr: &str) -> Option<VerificationAuditRecord> {
+            AUDIT_RECORDS.lock().unwrap().iter()
+                .map(|record| serde_json::from_str::<VerificationAuditRecord>(record).unwrap())
+                .find(|record| record.nonce == nonce && record.revealed_attrs.contains(revealed_attr))
+        }
+
+        // a single test, so no other test unsets the callback meanwhile
+        #[test]
+        fn verifier_set_audit_callback_works_for_verified_proof() {
+            assert_eq!(ErrorCode::Success, anoncreds::verifier_set_audit_callback(Some(_audit_callback)));
+
+            let valid = anoncreds::verifier_verify_proof(&anoncreds::proof_request_attr(),
+                                                         &anoncreds::proof_json(),
+                                                         &anoncreds::schemas_for_proof(),
+                                                         &anoncreds::cred_defs_for_proof(),
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(valid);
+
+            let record = _find_record("123432421212", "attr1_referent").unwrap();
+
+            assert!(record.predicates.is_empty());
+            assert_eq!(vec![ISSUER_DID.to_string()].into_iter().collect::<BTreeSet<String>>(), record.issuer_dids);
+            assert!(record.timestamp > 0);
+
+            AUDIT_RECORDS.lock().unwrap().clear();
+
+            let report_json = anoncreds::verifier_verify_proof_detailed(&anoncreds::proof_request_attr(),
+                                                                        &anoncreds::proof_json(),
+                                                                        &anoncreds::schemas_for_proof(),
+                                                                        &anoncreds::cred_defs_for_proof(),
+                                                                        "{}",
+                                                                        "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+            assert_eq!(json!(true), report["valid"]);
+
+            assert!(_find_record("123432421212", "attr1_referent").is_some());
+
+            assert_eq!(ErrorCode::Success, anoncreds::verifier_set_audit_callback(None));
+        }
+    }
+
     mod verifier_verify_proof_with_proof_req_restrictions {
         use super::*;
 
@@ -3450,6 +4344,262 @@ mod high_cases {
         }
     }
 
+    mod issuer_list_revocation_registries {
+        use super::*;
+        use utils::blob_storage;
+
+        #[test]
+        fn issuer_list_revocation_registries_works() {
+            let setup = Setup::wallet();
+
+            let (cred_def_id, _) = anoncreds::issuer_create_credential_definition(setup.wallet_handle,
+                                                                                  ISSUER_DID,
+                                                                                  &anoncreds::gvt_schema_json(),
+                                                                                  TAG_1,
+                                                                                  None,
+                                                                                  Some(&anoncreds::revocation_cred_def_config()))
+                .unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (rev_reg_id_1, _, _) = anoncreds::issuer_create_and_store_revoc_reg(setup.wallet_handle, ISSUER_DID, None, TAG_1, &cred_def_id,
+                                                                                    &anoncreds::issuance_on_demand_rev_reg_config(),
+                                                                                    tails_writer_handle).unwrap();
+            let (rev_reg_id_2, _, _) = anoncreds::issuer_create_and_store_revoc_reg(setup.wallet_handle, ISSUER_DID, None, TAG_2, &cred_def_id,
+                                                                                    &anoncreds::issuance_by_default_rev_reg_config(),
+                                                                                    tails_writer_handle).unwrap();
+
+            anoncreds::issuer_mark_revocation_registry_rotated(setup.wallet_handle, &rev_reg_id_1).unwrap();
+
+            let rev_regs_json = anoncreds::issuer_list_revocation_registries(setup.wallet_handle, &cred_def_id).unwrap();
+            let rev_regs: Vec<serde_json::Value> = serde_json::from_str(&rev_regs_json).unwrap();
+
+            assert_eq!(2, rev_regs.len());
+
+            let rev_reg_1 = rev_regs.iter().find(|rev_reg| rev_reg["rev_reg_id"] == json!(rev_reg_id_1)).unwrap();
+            assert_eq!(json!("Rotated"), rev_reg_1["state"]);
+            assert_eq!(json!(0), rev_reg_1["issued"]);
+            assert_eq!(json!(5), rev_reg_1["max_cred_num"]);
+
+            let rev_reg_2 = rev_regs.iter().find(|rev_reg| rev_reg["rev_reg_id"] == json!(rev_reg_id_2)).unwrap();
+            assert_eq!(json!("Active"), rev_reg_2["state"]);
+            assert_eq!(json!(0), rev_reg_2["issued"]);
+            assert_eq!(json!(0.0), rev_reg_2["utilization"]);
+        }
+
+        #[test]
+        fn issuer_list_revocation_registries_works_for_unknown_cred_def() {
+            let setup = Setup::wallet();
+
+            let rev_regs_json = anoncreds::issuer_list_revocation_registries(setup.wallet_handle, &anoncreds::issuer_1_gvt_cred_def_id()).unwrap();
+            assert_eq!("[]", rev_regs_json);
+        }
+    }
+
+    mod issuer_get_revocation_registry_capacity {
+        use super::*;
+        use utils::blob_storage;
+
+        fn _rev_reg_id(wallet_handle: i32) -> String {
+            let (cred_def_id, _) = anoncreds::issuer_create_credential_definition(wallet_handle,
+                                                                                  ISSUER_DID,
+                                                                                  &anoncreds::gvt_schema_json(),
+                                                                                  TAG_1,
+                                                                                  None,
+                                                                                  Some(&anoncreds::revocation_cred_def_config()))
+                .unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (rev_reg_id, _, _) = anoncreds::issuer_create_and_store_revoc_reg(wallet_handle, ISSUER_DID, None, TAG_1, &cred_def_id,
+                                                                                  &anoncreds::issuance_on_demand_rev_reg_config(),
+                                                                                  tails_writer_handle).unwrap();
+            rev_reg_id
+        }
+
+        #[test]
+        fn issuer_get_revocation_registry_capacity_works() {
+            let setup = Setup::wallet();
+
+            let rev_reg_id = _rev_reg_id(setup.wallet_handle);
+
+            let capacity_json = anoncreds::issuer_get_revocation_registry_capacity(setup.wallet_handle, &rev_reg_id, 0.8).unwrap();
+            let capacity: serde_json::Value = serde_json::from_str(&capacity_json).unwrap();
+
+            assert_eq!(json!(rev_reg_id), capacity["rev_reg_id"]);
+            assert_eq!(json!("Active"), capacity["state"]);
+            assert_eq!(json!(0), capacity["issued"]);
+            assert_eq!(json!(5), capacity["capacity_remaining"]);
+            assert_eq!(json!(false), capacity["nearly_full"]);
+        }
+
+        #[test]
+        fn issuer_get_revocation_registry_capacity_works_for_invalid_threshold() {
+            let setup = Setup::wallet();
+
+            let rev_reg_id = _rev_reg_id(setup.wallet_handle);
+
+            let res = anoncreds::issuer_get_revocation_registry_capacity(setup.wallet_handle, &rev_reg_id, 1.5);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+
+        #[test]
+        fn issuer_get_revocation_registry_capacity_works_for_unknown_registry() {
+            let setup = Setup::wallet();
+
+            let res = anoncreds::issuer_get_revocation_registry_capacity(setup.wallet_handle, &anoncreds::gvt_rev_reg_id(), 0.8);
+            assert_eq!(ErrorCode::WalletItemNotFound, res.unwrap_err());
+        }
+    }
+
+    mod get_revocation_status {
+        use super::*;
+
+        pub(super) fn _issue_credential(revoc_reg_def_config: &str) -> (i32, i32, String, String, String, String) {
+            let (issuer_wallet_handle, _) = wallet::create_and_open_default_wallet("get_revocation_status_issuer").unwrap();
+            let (prover_wallet_handle, _) = wallet::create_and_open_default_wallet("get_revocation_status_prover").unwrap();
+
+            let (_, _, cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json, revoc_reg_entry_json, blob_storage_reader_handle) =
+                anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                     ISSUER_DID,
+                                                                     GVT_SCHEMA_NAME,
+                                                                     GVT_SCHEMA_ATTRIBUTES,
+                                                                     revoc_reg_def_config);
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let (cred_rev_id, revoc_reg_delta_json) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                                         prover_wallet_handle,
+                                                                                                         issuer_wallet_handle,
+                                                                                                         CREDENTIAL1_ID,
+                                                                                                         &anoncreds::gvt_credential_values_json(),
+                                                                                                         &cred_def_id,
+                                                                                                         &cred_def_json,
+                                                                                                         &rev_reg_id,
+                                                                                                         &revoc_reg_def_json,
+                                                                                                         blob_storage_reader_handle);
+
+            // registry state from its creation up to the issuance
+            let revoc_reg_delta_json = match revoc_reg_delta_json {
+                Some(revoc_reg_delta_json) => anoncreds::issuer_merge_revocation_registry_deltas(&revoc_reg_entry_json, &revoc_reg_delta_json).unwrap(),
+                None => revoc_reg_entry_json
+            };
+
+            (issuer_wallet_handle, blob_storage_reader_handle, rev_reg_id, revoc_reg_def_json, revoc_reg_delta_json, cred_rev_id)
+        }
+
+        fn _check(revoc_reg_def_config: &str) {
+            Setup::empty();
+
+            let (issuer_wallet_handle, blob_storage_reader_handle, rev_reg_id, revoc_reg_def_json, revoc_reg_delta_json, cred_rev_id) =
+                _issue_credential(revoc_reg_def_config);
+
+            let revoked = anoncreds::get_revocation_status(blob_storage_reader_handle, &revoc_reg_def_json, &revoc_reg_delta_json, &cred_rev_id).unwrap();
+            assert!(!revoked);
+
+            let revoke_delta_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        &cred_rev_id).unwrap();
+            let revoc_reg_delta_json = anoncreds::issuer_merge_revocation_registry_deltas(&revoc_reg_delta_json, &revoke_delta_json).unwrap();
+
+            let revoked = anoncreds::get_revocation_status(blob_storage_reader_handle, &revoc_reg_def_json, &revoc_reg_delta_json, &cred_rev_id).unwrap();
+            assert!(revoked);
+        }
+
+        #[test]
+        fn get_revocation_status_works_for_issuance_on_demand() {
+            _check(&anoncreds::issuance_on_demand_rev_reg_config());
+        }
+
+        #[test]
+        fn get_revocation_status_works_for_issuance_by_default() {
+            _check(&anoncreds::issuance_by_default_rev_reg_config());
+        }
+
+        #[test]
+        fn get_revocation_status_works_for_partial_delta() {
+            Setup::empty();
+
+            let (issuer_wallet_handle, blob_storage_reader_handle, rev_reg_id, revoc_reg_def_json, _, cred_rev_id) =
+                _issue_credential(&anoncreds::issuance_on_demand_rev_reg_config());
+
+            let revoke_delta_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle,
+                                                                        blob_storage_reader_handle,
+                                                                        &rev_reg_id,
+                                                                        &cred_rev_id).unwrap();
+
+            let res = anoncreds::get_revocation_status(blob_storage_reader_handle, &revoc_reg_def_json, &revoke_delta_json, &cred_rev_id);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+
+        #[test]
+        fn get_revocation_status_works_for_invalid_cred_rev_id() {
+            Setup::empty();
+
+            let (_, blob_storage_reader_handle, _, revoc_reg_def_json, revoc_reg_delta_json, _) =
+                _issue_credential(&anoncreds::issuance_on_demand_rev_reg_config());
+
+            let res = anoncreds::get_revocation_status(blob_storage_reader_handle, &revoc_reg_def_json, &revoc_reg_delta_json, "not_a_number");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+
+        #[test]
+        fn get_revocation_status_works_for_not_issued_credential() {
+            Setup::empty();
+
+            let (_, blob_storage_reader_handle, _, revoc_reg_def_json, revoc_reg_delta_json, cred_rev_id) =
+                _issue_credential(&anoncreds::issuance_on_demand_rev_reg_config());
+
+            let not_issued_rev_id = (cred_rev_id.parse::<u32>().unwrap() + 1).to_string();
+
+            let res = anoncreds::get_revocation_status(blob_storage_reader_handle, &revoc_reg_def_json, &revoc_reg_delta_json, &not_issued_rev_id);
+            assert_eq!(ErrorCode::AnoncredsInvalidUserRevocId, res.unwrap_err());
+        }
+    }
+
+    mod get_witness_dependent_indices {
+        use super::*;
+        use super::get_revocation_status::_issue_credential;
+
+        #[test]
+        fn get_witness_dependent_indices_works_for_issuance_on_demand() {
+            Setup::empty();
+
+            let (_, _, _, revoc_reg_def_json, revoc_reg_delta_json, cred_rev_id) =
+                _issue_credential(&anoncreds::issuance_on_demand_rev_reg_config());
+
+            // the only issued credential is the prover's own one
+            let indices_json = anoncreds::get_witness_dependent_indices(&revoc_reg_def_json, &revoc_reg_delta_json, &cred_rev_id).unwrap();
+            let indices: Vec<u32> = serde_json::from_str(&indices_json).unwrap();
+            assert!(indices.is_empty());
+        }
+
+        #[test]
+        fn get_witness_dependent_indices_works_for_issuance_by_default() {
+            Setup::empty();
+
+            let (_, _, _, revoc_reg_def_json, revoc_reg_delta_json, cred_rev_id) =
+                _issue_credential(&anoncreds::issuance_by_default_rev_reg_config());
+
+            // max_cred_num + 1 - j + rev_idx for every other issued j
+            let indices_json = anoncreds::get_witness_dependent_indices(&revoc_reg_def_json, &revoc_reg_delta_json, &cred_rev_id).unwrap();
+            let indices: Vec<u32> = serde_json::from_str(&indices_json).unwrap();
+            assert_eq!(vec![2, 3, 4, 5], indices);
+        }
+
+        #[test]
+        fn get_witness_dependent_indices_works_for_invalid_cred_rev_id() {
+            Setup::empty();
+
+            let (_, _, _, revoc_reg_def_json, revoc_reg_delta_json, _) =
+                _issue_credential(&anoncreds::issuance_on_demand_rev_reg_config());
+
+            let res = anoncreds::get_witness_dependent_indices(&revoc_reg_def_json, &revoc_reg_delta_json, "not_a_number");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
     mod to_unqualified {
         use super::*;
 
@@ -3837,6 +4987,28 @@ mod medium_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn issuer_create_credential_works_for_not_canonical_encoded_value() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            for encoded in &["0028", " 28"] {
+                let mut cred_values: serde_json::Value = serde_json::from_str(&anoncreds::gvt_credential_values_json()).unwrap();
+                cred_values["age"]["encoded"] = json!(encoded);
+
+                let res = anoncreds::issuer_create_credential(wallet_handle,
+                                                              &credential_offer,
+                                                              &credential_req,
+                                                              &cred_values.to_string(),
+                                                              None,
+                                                              None);
+                assert_code!(ErrorCode::CommonInvalidStructure, res);
+            }
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn issuer_create_credential_works_for_for_invalid_wallet_handle() {
             let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
@@ -3877,6 +5049,26 @@ mod medium_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn issuer_create_credential_works_for_empty_prover_did() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let mut credential_req: serde_json::Value = serde_json::from_str(&credential_req).unwrap();
+            credential_req["prover_did"] = json!("");
+
+            let res = anoncreds::issuer_create_credential(wallet_handle,
+                                                          &credential_offer,
+                                                          &credential_req.to_string(),
+                                                          &anoncreds::gvt_credential_values_json(),
+                                                          None,
+                                                          None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn issuer_create_credential_works_for_for_invalid_credential_values_json() {
             let (_, credential_offer, credential_request, _) = anoncreds::init_common_wallet();
@@ -4256,6 +5448,126 @@ mod medium_cases {
         }
     }
 
+    mod verifier_verify_proof_dry_run {
+        use super::*;
+
+        #[test]
+        fn verifier_verify_proof_dry_run_works_for_tampered_proof() {
+            let proof_json = anoncreds::proof_json().replace("1139481716457488690172217916278103335", "1111111111111111111111111111111111111");
+
+            anoncreds::verifier_verify_proof_dry_run(&anoncreds::proof_request_attr(),
+                                                     &proof_json,
+                                                     &anoncreds::schemas_for_proof(),
+                                                     &anoncreds::cred_defs_for_proof(),
+                                                     "{}",
+                                                     "{}").unwrap();
+
+            let valid = anoncreds::verifier_verify_proof(&anoncreds::proof_request_attr(),
+                                                         &proof_json,
+                                                         &anoncreds::schemas_for_proof(),
+                                                         &anoncreds::cred_defs_for_proof(),
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn verifier_verify_proof_dry_run_works_for_missing_credential_def() {
+            let res = anoncreds::verifier_verify_proof_dry_run(&anoncreds::proof_request_attr(),
+                                                               &anoncreds::proof_json(),
+                                                               &anoncreds::schemas_for_proof(),
+                                                               "{}",
+                                                               "{}",
+                                                               "{}");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
+    mod verifier_verify_proof_detailed {
+        use super::*;
+
+        #[test]
+        fn verifier_verify_proof_detailed_works() {
+            let report_json = anoncreds::verifier_verify_proof_detailed(&anoncreds::proof_request_attr(),
+                                                                        &anoncreds::proof_json(),
+                                                                        &anoncreds::schemas_for_proof(),
+                                                                        &anoncreds::cred_defs_for_proof(),
+                                                                        "{}",
+                                                                        "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(true), report["valid"]);
+            assert_eq!(json!(true), report["referents"]["attr1_referent"]["valid"]);
+            assert!(report["referents"]["attr1_referent"]["reason"].is_null());
+            assert_eq!(json!("revealed"), report["referents"]["attr1_referent"]["mode"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_detailed_works_for_missing_non_revocation_proof() {
+            let mut proof_req: serde_json::Value = serde_json::from_str(&anoncreds::proof_request_attr()).unwrap();
+            proof_req["requested_attributes"]["attr1_referent"]["non_revoked"] = json!({"to": 100});
+
+            let report_json = anoncreds::verifier_verify_proof_detailed(&proof_req.to_string(),
+                                                                        &anoncreds::proof_json(),
+                                                                        &anoncreds::schemas_for_proof(),
+                                                                        &anoncreds::cred_defs_for_proof(),
+                                                                        "{}",
+                                                                        "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(false), report["valid"]);
+            assert_eq!(json!("revoked"), report["referents"]["attr1_referent"]["reason"]);
+            assert!(report["referents"]["attr1_referent"]["mode"].is_null());
+        }
+
+        #[test]
+        fn verifier_verify_proof_detailed_works_for_tampered_revealed_value() {
+            let proof_json = anoncreds::proof_json().replace(r#""encoded":"1139481716457488690172217916278103335""#,
+                                                             r#""encoded":"1111111111111111111111111111111111111""#);
+
+            let report_json = anoncreds::verifier_verify_proof_detailed(&anoncreds::proof_request_attr(),
+                                                                        &proof_json,
+                                                                        &anoncreds::schemas_for_proof(),
+                                                                        &anoncreds::cred_defs_for_proof(),
+                                                                        "{}",
+                                                                        "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(false), report["valid"]);
+            assert_eq!(json!(false), report["referents"]["attr1_referent"]["valid"]);
+            assert_eq!(json!("signature_invalid"), report["referents"]["attr1_referent"]["reason"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_detailed_works_for_missing_referent() {
+            let mut proof_req: serde_json::Value = serde_json::from_str(&anoncreds::proof_request_attr()).unwrap();
+            proof_req["requested_attributes"]["attr2_referent"] = json!({"name":"sex"});
+
+            let report_json = anoncreds::verifier_verify_proof_detailed(&proof_req.to_string(),
+                                                                        &anoncreds::proof_json(),
+                                                                        &anoncreds::schemas_for_proof(),
+                                                                        &anoncreds::cred_defs_for_proof(),
+                                                                        "{}",
+                                                                        "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(false), report["valid"]);
+            assert_eq!(json!(true), report["referents"]["attr1_referent"]["valid"]);
+            assert_eq!(json!("not_revealed"), report["referents"]["attr2_referent"]["reason"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_detailed_works_for_missing_credential_def() {
+            let res = anoncreds::verifier_verify_proof_detailed(&anoncreds::proof_request_attr(),
+                                                                &anoncreds::proof_json(),
+                                                                &anoncreds::schemas_for_proof(),
+                                                                "{}",
+                                                                "{}",
+                                                                "{}");
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+    }
+
     mod verifier_verify_proof_with_proof_req_restrictions {
         use super::*;
 
@@ -10,7 +10,7 @@ extern crate indyrs as api;
 mod demos {
     use super::*;
 
-    use utils::{wallet, anoncreds};
+    use utils::{wallet, anoncreds, pool};
     use utils::anoncreds::{COMMON_MASTER_SECRET, CREDENTIAL1_ID, CREDENTIAL2_ID, CREDENTIAL3_ID};
 
     use indy::ErrorCode;
@@ -27,6 +27,7 @@ mod demos {
     use utils::domain::anoncreds::revocation_state::RevocationState;
     use utils::domain::anoncreds::revocation_registry::RevocationRegistry;
     use utils::domain::anoncreds::credential_offer::CredentialOffer;
+    use utils::domain::anoncreds::credential::CredentialInfo;
 
     #[test]
     fn anoncreds_works_for_single_issuer_single_prover() {
@@ -127,6 +128,168 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_detailed_verification_of_unsatisfied_predicate() {
+        Setup::empty();
+
+        //1. Create Issuer and Prover wallets
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_detailed_verification_of_unsatisfied_predicate").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_detailed_verification_of_unsatisfied_predicate").unwrap();
+
+        //2. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             GVT_SCHEMA_ATTRIBUTES);
+
+        //3. Issuance credential for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //4. Prover creates Proof for age >= 18
+        let proof_req_json = json!({
+                                       "nonce":"123432421212",
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{"name":"name"}
+                                       },
+                                       "requested_predicates":{
+                                            "predicate1_referent":{"name":"age","p_type":">=","p_value":18}
+                                       }
+                                    }).to_string();
+
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credential = anoncreds::get_credential_for_attr_referent(&credentials_json, "attr1_referent");
+
+        let requested_credentials_json = json!({
+                                                   "self_attested_attributes":{},
+                                                   "requested_attributes":{
+                                                        "attr1_referent":{"cred_id":credential.referent, "revealed":true}
+                                                   },
+                                                   "requested_predicates":{
+                                                        "predicate1_referent":{"cred_id":credential.referent}
+                                                   }
+                                                }).to_string();
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        "{}").unwrap();
+
+        //5. Verifier checks Proof against request for age >= 21
+        let verifier_proof_req_json = proof_req_json.replace(r#""p_value":18"#, r#""p_value":21"#);
+
+        let report_json = anoncreds::verifier_verify_proof_detailed(&verifier_proof_req_json,
+                                                                    &proof_json,
+                                                                    &schemas_json,
+                                                                    &cred_defs_json,
+                                                                    "{}",
+                                                                    "{}").unwrap();
+        let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+        assert_eq!(json!(false), report["valid"]);
+        assert_eq!(json!(true), report["referents"]["attr1_referent"]["valid"]);
+        assert_eq!(json!("predicate_unsatisfied"), report["referents"]["predicate1_referent"]["reason"]);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_offline_mode() {
+        Setup::empty();
+
+        assert_eq!(ErrorCode::Success, indy::set_runtime_config(r#"{"offline": true}"#));
+
+        //1. Ledger can't be accessed
+        let res = pool::open_pool_ledger("anoncreds_works_for_offline_mode", None);
+        assert_code!(ErrorCode::CommonInvalidState, res);
+
+        //2. Create Issuer and Prover wallets
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_offline_mode").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_offline_mode").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Issuance credential for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //5. Prover creates Proof with Schema and Credential Definition supplied by caller
+        let proof_req_json = json!({
+                                       "nonce": anoncreds::generate_nonce().unwrap(),
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{"name":"name"}
+                                       },
+                                       "requested_predicates":{
+                                            "predicate1_referent":{"name":"age","p_type":">=","p_value":18}
+                                       }
+                                    }).to_string();
+
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credential = anoncreds::get_credential_for_attr_referent(&credentials_json, "attr1_referent");
+
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {
+                "attr1_referent": {"cred_id": credential.referent, "revealed": true}
+            },
+            "requested_predicates": {
+                "predicate1_referent": {"cred_id": credential.referent}
+            }
+        }).to_string();
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        "{}").unwrap();
+
+        //6. Verifier verifies Proof
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     "{}",
+                                                     "{}").unwrap();
+        assert!(valid);
+
+        assert_eq!(ErrorCode::Success, indy::set_runtime_config(r#"{"offline": false}"#));
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_plugged_wallet() {
         Setup::empty();
@@ -361,6 +524,130 @@ mod demos {
         wallet::close_and_delete_wallet(issuer_xyz_wallet_handle, &issuer_xyz_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_same_issuer_key() {
+        Setup::empty();
+
+        //1. Issuer1 creates wallet, gets wallet handles
+        let (issuer_1_wallet_handle, issuer_1_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_same_issuer_key").unwrap();
+
+        //2. Issuer2 creates wallet, gets wallet handles
+        let (issuer_2_wallet_handle, issuer_2_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_same_issuer_key").unwrap();
+
+        //3. Prover creates wallet, gets wallet handles
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_same_issuer_key").unwrap();
+
+        //4. Issuer1 and Issuer2 create GVT Schema and Credential Definition
+        let (schema_id, schema_json,
+            issuer_1_cred_def_id, issuer_1_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_1_wallet_handle,
+                                                                                                      ISSUER_DID,
+                                                                                                      GVT_SCHEMA_NAME,
+                                                                                                      GVT_SCHEMA_ATTRIBUTES);
+
+        let (_, _,
+            issuer_2_cred_def_id, issuer_2_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_2_wallet_handle,
+                                                                                                      DID_MY2,
+                                                                                                      GVT_SCHEMA_NAME,
+                                                                                                      GVT_SCHEMA_ATTRIBUTES);
+
+        //5. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //6. Issuer1 issues two GVT Credentials, Issuer2 issues one
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_1_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &issuer_1_cred_def_id,
+                                                 &issuer_1_cred_def_json);
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_1_wallet_handle,
+                                                 CREDENTIAL2_ID,
+                                                 &anoncreds::gvt2_credential_values_json(),
+                                                 &issuer_1_cred_def_id,
+                                                 &issuer_1_cred_def_json);
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_2_wallet_handle,
+                                                 CREDENTIAL3_ID,
+                                                 &anoncreds::gvt3_credential_values_json(),
+                                                 &issuer_2_cred_def_id,
+                                                 &issuer_2_cred_def_json);
+
+        //7. Proof request asking name and age to be proven with the same issuer key
+        let proof_req_json = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": {
+               "attr1_referent": {"name":"name"}
+           },
+           "requested_predicates": {
+               "predicate1_referent": {"name":"age", "p_type":">=", "p_value":18}
+           },
+           "same_issuer": [["attr1_referent", "predicate1_referent"]]
+        }).to_string();
+
+        let schemas_json = json!({
+            schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            issuer_1_cred_def_id: serde_json::from_str::<CredentialDefinition>(&issuer_1_cred_def_json).unwrap(),
+            issuer_2_cred_def_id: serde_json::from_str::<CredentialDefinition>(&issuer_2_cred_def_json).unwrap()
+        }).to_string();
+
+        let create_proof = |predicate_cred_id: &str| {
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": {},
+                 "requested_attributes": {
+                    "attr1_referent": { "cred_id": CREDENTIAL1_ID, "revealed":true }
+                 },
+                 "requested_predicates": {
+                    "predicate1_referent": { "cred_id": predicate_cred_id }
+                 }
+            }).to_string();
+
+            anoncreds::prover_create_proof(prover_wallet_handle,
+                                           &proof_req_json,
+                                           &requested_credentials_json,
+                                           COMMON_MASTER_SECRET,
+                                           &schemas_json,
+                                           &credential_defs_json,
+                                           "{}").unwrap()
+        };
+
+        //8. Verifier accepts proof built from two credentials signed with the same key
+        let proof_json = create_proof(CREDENTIAL2_ID);
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     "{}",
+                                                     "{}").unwrap();
+        assert!(valid);
+
+        //9. Verifier rejects proof built from credentials of different issuers
+        let proof_json = create_proof(CREDENTIAL3_ID);
+
+        let res = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                   &proof_json,
+                                                   &schemas_json,
+                                                   &credential_defs_json,
+                                                   "{}",
+                                                   "{}");
+        assert_code!(ErrorCode::AnoncredsProofRejected, res);
+
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_1_wallet_handle, &issuer_1_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_2_wallet_handle, &issuer_2_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_single_issuer_multiple_credentials_single_prover() {
         Setup::empty();
@@ -1690,31 +1977,31 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
-    #[cfg(feature = "revocation_tests")]
     #[test]
-    fn anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credential() {
+    fn anoncreds_works_for_detailed_verification_of_revoked_credential() {
         Setup::empty();
 
         //1. Issuer creates wallet, gets wallet handle
-        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credential").unwrap();
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_detailed_verification_of_revoked_credential").unwrap();
 
         //2. Prover creates wallet, gets wallet handle
-        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credential").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_detailed_verification_of_revoked_credential").unwrap();
 
         //3 Issuer creates Schema, Credential Definition and Revocation Registry
         let (schema_id, schema_json,
             cred_def_id, cred_def_json,
-            rev_reg_id, revoc_reg_def_json, rev_reg_entry_json,
+            rev_reg_id, revoc_reg_def_json, _,
             blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
                                                                                                ISSUER_DID,
                                                                                                GVT_SCHEMA_NAME,
                                                                                                GVT_SCHEMA_ATTRIBUTES,
-                                                                                               &anoncreds::issuance_by_default_rev_reg_config());
-        //4. Prover creates Master Secret
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Issuance Credential for Prover
+        // Prover creates Master Secret
         anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
 
-        //5. Issuance Credential for Prover
-        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(
+        let (cred_rev_id, revoc_reg_delta_json) = anoncreds::multi_steps_create_revocation_credential(
             COMMON_MASTER_SECRET,
             prover_wallet_handle,
             issuer_wallet_handle,
@@ -1726,8 +2013,9 @@ mod demos {
             &revoc_reg_def_json,
             blob_storage_reader_handle,
         );
+        let revoc_reg_delta_json = revoc_reg_delta_json.unwrap();
 
-        //6. Prover gets Credentials for Proof Request
+        //5. Prover gets Credentials for Proof Request
         let proof_request = json!({
            "nonce":"123432421212",
            "name":"proof_req_1",
@@ -1746,15 +2034,15 @@ mod demos {
         let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_request).unwrap();
         let credential = anoncreds::get_credential_for_attr_referent(&credentials_json, "attr1_referent");
 
-        //7. Prover creates RevocationState
+        //6. Prover creates RevocationState
         let timestamp = 100;
         let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
                                                                 &revoc_reg_def_json,
-                                                                &rev_reg_entry_json,
+                                                                &revoc_reg_delta_json,
                                                                 timestamp,
                                                                 &cred_rev_id).unwrap();
 
-        //8. Prover creates Proof
+        //7. Prover creates Proof
         let requested_credentials_json = json!({
              "self_attested_attributes": json!({}),
              "requested_attributes": json!({
@@ -1787,14 +2075,14 @@ mod demos {
                                                         &credential_defs_json,
                                                         &rev_states_json).unwrap();
 
-        //9. Verifier verifies proof before it will be revoked
+        //8. Verifier verifies proof before it will be revoked
         let rev_reg_defs_json = json!({
             rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
         }).to_string();
 
         let rev_regs_json = json!({
             rev_reg_id.clone(): json!({
-                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&rev_reg_entry_json).unwrap()
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
             })
         }).to_string();
 
@@ -1806,13 +2094,13 @@ mod demos {
                                                      &rev_regs_json).unwrap();
         assert!(valid);
 
-        //10. Issuer revokes credential
+        //9. Issuer revokes credential
         let revoc_reg_delta_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle,
                                                                        blob_storage_reader_handle,
                                                                        &rev_reg_id,
                                                                        &cred_rev_id).unwrap();
 
-        //11. Verifier verifies proof after that was revoked
+        //10. Verifier gets detailed report for proof after that was revoked
         let rev_reg_defs_json = json!({
             rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
         }).to_string();
@@ -1823,47 +2111,684 @@ mod demos {
             })
         }).to_string();
 
-        let valid = anoncreds::verifier_verify_proof(&proof_request,
-                                                     &proof_json,
-                                                     &schemas_json,
-                                                     &credential_defs_json,
-                                                     &rev_reg_defs_json,
-                                                     &rev_regs_json).unwrap();
-        assert!(!valid);
+        let report_json = anoncreds::verifier_verify_proof_detailed(&proof_request,
+                                                                    &proof_json,
+                                                                    &schemas_json,
+                                                                    &credential_defs_json,
+                                                                    &rev_reg_defs_json,
+                                                                    &rev_regs_json).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+        assert_eq!(json!(false), report["valid"]);
+        assert_eq!(json!("signature_invalid"), report["referents"]["attr1_referent"]["reason"]);
+        assert_eq!(json!("signature_invalid"), report["referents"]["predicate1_referent"]["reason"]);
+        assert!(report["referents"]["attr1_referent"]["details"].as_str().unwrap().contains("revoked"));
 
         wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
-    #[cfg(feature = "revocation_tests")]
     #[test]
-    fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry() {
+    fn anoncreds_works_for_credential_reissue_under_new_credential_definition() {
         Setup::empty();
 
-        //1. Issuer creates wallet, gets wallet handle
-        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_reissue_under_new_credential_definition").unwrap();
 
-        //2. Prover creates wallet, gets wallet handle
-        let (prover_1_wallet_handle, prover_1_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_reissue_under_new_credential_definition").unwrap();
 
-        //3. Prover creates wallet, gets wallet handle
-        let (prover_2_wallet_handle, prover_2_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+        //3. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             GVT_SCHEMA_ATTRIBUTES);
 
-        //4. Prover creates wallet, gets wallet handle
-        let (prover_3_wallet_handle, prover_3_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
 
-        //5 Issuer creates Schema, Credential Definition and Revocation Registry
-        let (_, _,
-            cred_def_id, cred_def_json,
-            rev_reg_id, revoc_reg_def_json, _,
-            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
-                                                                                               ISSUER_DID,
-                                                                                               GVT_SCHEMA_NAME,
-                                                                                               GVT_SCHEMA_ATTRIBUTES,
-                                                                                               r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+        //5. Issuer issues Credential under the first Credential Definition
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
 
-        //6. Prover1 creates Master Secret
-        let prover_1_master_secret = "prover1_master_secret";
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_json, _, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                    &cred_offer_json,
+                                                                    &cred_req_json,
+                                                                    &anoncreds::gvt_credential_values_json(),
+                                                                    None,
+                                                                    None).unwrap();
+
+        //6. Issuer rotates Credential Definition
+        let (new_cred_def_id, new_cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                                  ISSUER_DID,
+                                                                                                  &schema_json,
+                                                                                                  TAG_2,
+                                                                                                  None,
+                                                                                                  Some(&anoncreds::default_cred_def_config())).unwrap();
+
+        //7. Prover requests Credential for the new Credential Definition
+        let new_cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &new_cred_def_id).unwrap();
+
+        let (new_cred_req_json, new_cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                                      DID_MY1,
+                                                                                                      &new_cred_offer_json,
+                                                                                                      &new_cred_def_json,
+                                                                                                      COMMON_MASTER_SECRET).unwrap();
+
+        //8. Issuer re-issues the Credential without attribute values
+        let new_cred_json = anoncreds::issuer_reissue_credential(issuer_wallet_handle,
+                                                                 &new_cred_offer_json,
+                                                                 &new_cred_req_json,
+                                                                 &cred_json).unwrap();
+
+        //9. Prover stores migrated Credential
+        anoncreds::prover_store_credential(prover_wallet_handle,
+                                           CREDENTIAL1_ID,
+                                           &new_cred_req_metadata_json,
+                                           &new_cred_json,
+                                           &new_cred_def_json,
+                                           None).unwrap();
+
+        //10. Prover proves attribute from migrated Credential
+        let nonce = anoncreds::generate_nonce().unwrap();
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{
+                                                "name":"name",
+                                                "restrictions": {"cred_def_id": new_cred_def_id}
+                                            }
+                                       },
+                                       "requested_predicates":{}
+                                    }).to_string();
+
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {
+                "attr1_referent": {"cred_id": CREDENTIAL1_ID, "revealed": true}
+            },
+            "requested_predicates": {}
+        }).to_string();
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({new_cred_def_id.clone(): serde_json::from_str::<CredentialDefinition>(&new_cred_def_json).unwrap()}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        "{}").unwrap();
+
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+        assert_eq!("Alex", proof.requested_proof.revealed_attrs.get("attr1_referent").unwrap().raw);
+        assert_eq!(new_cred_def_id, proof.identifiers[0].cred_def_id.0);
+
+        //11. Verifier verifies proof
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     "{}",
+                                                     "{}").unwrap();
+        assert!(valid);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_credential_reissue_for_tampered_values() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_reissue_for_tampered_values").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_reissue_for_tampered_values").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                     ISSUER_DID,
+                                                                                                     GVT_SCHEMA_NAME,
+                                                                                                     GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer issues Credential under the first Credential Definition
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                         DID_MY1,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_json, _, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                    &cred_offer_json,
+                                                                    &cred_req_json,
+                                                                    &anoncreds::gvt_credential_values_json(),
+                                                                    None,
+                                                                    None).unwrap();
+
+        //6. Issuer rotates Credential Definition
+        let (new_cred_def_id, new_cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                                  ISSUER_DID,
+                                                                                                  &schema_json,
+                                                                                                  TAG_2,
+                                                                                                  None,
+                                                                                                  Some(&anoncreds::default_cred_def_config())).unwrap();
+
+        //7. Prover requests Credential for the new Credential Definition
+        let new_cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &new_cred_def_id).unwrap();
+
+        let (new_cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                             DID_MY1,
+                                                                             &new_cred_offer_json,
+                                                                             &new_cred_def_json,
+                                                                             COMMON_MASTER_SECRET).unwrap();
+
+        //8. Prover tampers with the values of the Credential
+        let mut tampered_cred: serde_json::Value = serde_json::from_str(&cred_json).unwrap();
+        tampered_cred["values"]["age"] = json!({"raw": "99", "encoded": "99"});
+
+        //9. Issuer refuses to re-issue the tampered Credential
+        let res = anoncreds::issuer_reissue_credential(issuer_wallet_handle,
+                                                       &new_cred_offer_json,
+                                                       &new_cred_req_json,
+                                                       &tampered_cred.to_string());
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+        //10. Issuer of another wallet refuses to re-issue the Credential
+        let (other_issuer_wallet_handle, other_issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_reissue_for_tampered_values_other").unwrap();
+
+        let (other_cred_def_id, other_cred_def_json) = anoncreds::issuer_create_credential_definition(other_issuer_wallet_handle,
+                                                                                                      ISSUER_DID,
+                                                                                                      &schema_json,
+                                                                                                      TAG_1,
+                                                                                                      None,
+                                                                                                      Some(&anoncreds::default_cred_def_config())).unwrap();
+        assert_eq!(cred_def_id, other_cred_def_id);
+
+        let other_cred_offer_json = anoncreds::issuer_create_credential_offer(other_issuer_wallet_handle, &other_cred_def_id).unwrap();
+
+        let (other_cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                               DID_MY1,
+                                                                               &other_cred_offer_json,
+                                                                               &other_cred_def_json,
+                                                                               COMMON_MASTER_SECRET).unwrap();
+
+        let res = anoncreds::issuer_reissue_credential(other_issuer_wallet_handle,
+                                                       &other_cred_offer_json,
+                                                       &other_cred_req_json,
+                                                       &cred_json);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+        //11. Issuer re-issues the untouched Credential
+        anoncreds::issuer_reissue_credential(issuer_wallet_handle,
+                                             &new_cred_offer_json,
+                                             &new_cred_req_json,
+                                             &cred_json).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(other_issuer_wallet_handle, &other_issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_revocation_registry_export_import() {
+        Setup::empty();
+
+        //1. Issuer and Prover create wallets
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_export_import").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_export_import").unwrap();
+
+        //2. Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //3. Issuer issues first Credential
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL1_ID,
+            &anoncreds::gvt_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+        assert_eq!("1", cred_rev_id);
+
+        //4. Issuer backs up the whole wallet and exports Revocation Registry
+        let backup_path = wallet::export_wallet_path("anoncreds_works_for_revocation_registry_export_import");
+        let backup_config = wallet::prepare_export_wallet_config(&backup_path);
+        wallet::export_wallet(issuer_wallet_handle, &backup_config).unwrap();
+
+        let export_config = json!({"key": "export_key"}).to_string();
+        let outdated_snapshot = anoncreds::issuer_export_revocation_registry(issuer_wallet_handle, &rev_reg_id, &export_config).unwrap();
+
+        //5. Issuer issues second Credential and exports Revocation Registry again
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL2_ID,
+            &anoncreds::gvt2_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+        assert_eq!("2", cred_rev_id);
+
+        let snapshot = anoncreds::issuer_export_revocation_registry(issuer_wallet_handle, &rev_reg_id, &export_config).unwrap();
+
+        //6. Import fails for wrong key, for truncated and for modified snapshot
+        let res = anoncreds::issuer_import_revocation_registry(issuer_wallet_handle, &json!({"key": "other_key"}).to_string(), &snapshot);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+        // prefix of whole base64 quads is still valid base64, so it is decryption that rejects it
+        let truncated = &snapshot[..snapshot.len() / 8 * 4];
+        let res = anoncreds::issuer_import_revocation_registry(issuer_wallet_handle, &export_config, truncated);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+        let middle = snapshot.len() / 2;
+        let replacement = if &snapshot[middle..middle + 1] == "A" { "B" } else { "A" };
+        let modified = format!("{}{}{}", &snapshot[..middle], replacement, &snapshot[middle + 1..]);
+        let res = anoncreds::issuer_import_revocation_registry(issuer_wallet_handle, &export_config, &modified);
+        assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+
+        //7. Import of the state older than stored one is rejected, the same state is accepted
+        let res = anoncreds::issuer_import_revocation_registry(issuer_wallet_handle, &export_config, &outdated_snapshot);
+        assert_eq!(ErrorCode::CommonInvalidState, res.unwrap_err());
+
+        let restored_rev_reg_id = anoncreds::issuer_import_revocation_registry(issuer_wallet_handle, &export_config, &snapshot).unwrap();
+        assert_eq!(rev_reg_id, restored_rev_reg_id);
+
+        //8. Issuer restores wallet backup into a new wallet, it holds registry state of the first Credential only
+        let restored_wallet_config = json!({"id": "anoncreds_works_for_revocation_registry_export_import_restored"}).to_string();
+        wallet::import_wallet(&restored_wallet_config, WALLET_CREDENTIALS, &backup_config).unwrap();
+        let restored_wallet_handle = wallet::open_wallet(&restored_wallet_config, WALLET_CREDENTIALS).unwrap();
+
+        //9. Issuer imports the latest Revocation Registry snapshot into restored wallet
+        let restored_rev_reg_id = anoncreds::issuer_import_revocation_registry(restored_wallet_handle, &export_config, &snapshot).unwrap();
+        assert_eq!(rev_reg_id, restored_rev_reg_id);
+
+        //10. Issuance continues from the exported state
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            restored_wallet_handle,
+            CREDENTIAL3_ID,
+            &anoncreds::gvt3_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+        assert_eq!("3", cred_rev_id);
+
+        //11. Restored Revocation Registry still allows revocation
+        anoncreds::issuer_revoke_credential(restored_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id).unwrap();
+
+        wallet::close_and_delete_wallet(restored_wallet_handle, &restored_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        ::std::fs::remove_file(&backup_path).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_verification_against_past_revocation_registry_state() {
+        Setup::empty();
+
+        //1. Issuer and Prover create wallets
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_verification_against_past_revocation_registry_state").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_verification_against_past_revocation_registry_state").unwrap();
+
+        //2. Issuer creates Schema, Credential Definition and Revocation Registry
+        let (schema_id, schema_json,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //3. Issuer issues Credential for Prover
+        let (cred_rev_id, past_rev_reg_json) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL1_ID,
+            &anoncreds::gvt_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+        let past_rev_reg_json = past_rev_reg_json.unwrap();
+
+        //4. Prover creates RevocationState and Proof at the past timestamp
+        let past_timestamp = 100;
+        let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                                                &revoc_reg_def_json,
+                                                                &past_rev_reg_json,
+                                                                past_timestamp,
+                                                                &cred_rev_id).unwrap();
+
+        let proof_request = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name"
+               })
+           }),
+           "requested_predicates": json!({}),
+           "non_revoked": json!({ "from":80, "to":100 })
+        }).to_string();
+
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "timestamp":past_timestamp, "revealed":true })
+             }),
+             "requested_predicates": json!({})
+        }).to_string();
+
+        let schemas_json = json!({
+            schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            cred_def_id.clone(): serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()
+        }).to_string();
+
+        let rev_states_json = json!({
+            rev_reg_id.clone(): json!({
+                past_timestamp.to_string(): serde_json::from_str::<RevocationState>(&rev_state_json).unwrap()
+            })
+        }).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_request,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        //5. Issuer issues another Credential, so current accumulator differs from the past one
+        let (_, current_rev_reg_json) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL2_ID,
+            &anoncreds::gvt2_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+        let current_rev_reg_json = current_rev_reg_json.unwrap();
+
+        let rev_reg_defs_json = json!({
+            rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
+        }).to_string();
+
+        //6. Verifier holding the accumulator history verifies Proof against the state at proof timestamp
+        let current_timestamp = 200;
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                past_timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&past_rev_reg_json).unwrap(),
+                current_timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&current_rev_reg_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //7. Current accumulator doesn't match the Proof
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                past_timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&current_rev_reg_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(!valid);
+
+        //8. Accumulator for the Proof timestamp is required
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                current_timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&current_rev_reg_json).unwrap()
+            })
+        }).to_string();
+
+        let res = anoncreds::verifier_verify_proof(&proof_request,
+                                                   &proof_json,
+                                                   &schemas_json,
+                                                   &credential_defs_json,
+                                                   &rev_reg_defs_json,
+                                                   &rev_regs_json);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credential() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credential").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_revoke_credential").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (schema_id, schema_json,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, rev_reg_entry_json,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               &anoncreds::issuance_by_default_rev_reg_config());
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuance Credential for Prover
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(
+            COMMON_MASTER_SECRET,
+            prover_wallet_handle,
+            issuer_wallet_handle,
+            CREDENTIAL1_ID,
+            &anoncreds::gvt_credential_values_json(),
+            &cred_def_id,
+            &cred_def_json,
+            &rev_reg_id,
+            &revoc_reg_def_json,
+            blob_storage_reader_handle,
+        );
+
+        //6. Prover gets Credentials for Proof Request
+        let proof_request = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name"
+               })
+           }),
+           "requested_predicates": json!({
+               "predicate1_referent": json!({ "name":"age", "p_type":">=", "p_value":18 })
+           }),
+           "non_revoked": json!({ "from":80, "to":100 })
+        }).to_string();
+
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_request).unwrap();
+        let credential = anoncreds::get_credential_for_attr_referent(&credentials_json, "attr1_referent");
+
+        //7. Prover creates RevocationState
+        let timestamp = 100;
+        let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                                                &revoc_reg_def_json,
+                                                                &rev_reg_entry_json,
+                                                                timestamp,
+                                                                &cred_rev_id).unwrap();
+
+        //8. Prover creates Proof
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": credential.referent, "timestamp":timestamp, "revealed":true })
+             }),
+             "requested_predicates": json!({
+                "predicate1_referent": json!({ "cred_id": credential.referent, "timestamp":timestamp })
+             })
+        }).to_string();
+
+        let schemas_json = json!({
+            schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()
+        }).to_string();
+
+        let rev_states_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationState>(&rev_state_json).unwrap()
+            })
+        }).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_request,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        //9. Verifier verifies proof before it will be revoked
+        let rev_reg_defs_json = json!({
+            rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
+        }).to_string();
+
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&rev_reg_entry_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //10. Issuer revokes credential
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle,
+                                                                       blob_storage_reader_handle,
+                                                                       &rev_reg_id,
+                                                                       &cred_rev_id).unwrap();
+
+        //11. Verifier verifies proof after that was revoked
+        let rev_reg_defs_json = json!({
+            rev_reg_id.clone(): serde_json::from_str::<RevocationRegistryDefinition>(&revoc_reg_def_json).unwrap()
+        }).to_string();
+
+        let rev_regs_json = json!({
+            rev_reg_id.clone(): json!({
+                timestamp.to_string(): serde_json::from_str::<RevocationRegistry>(&revoc_reg_delta_json).unwrap()
+            })
+        }).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_request,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(!valid);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_1_wallet_handle, prover_1_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //3. Prover creates wallet, gets wallet handle
+        let (prover_2_wallet_handle, prover_2_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //4. Prover creates wallet, gets wallet handle
+        let (prover_3_wallet_handle, prover_3_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //5 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //6. Prover1 creates Master Secret
+        let prover_1_master_secret = "prover1_master_secret";
         anoncreds::prover_create_master_secret(prover_1_wallet_handle, prover_1_master_secret).unwrap();
 
 
@@ -1878,7 +2803,174 @@ mod demos {
                                                             &revoc_reg_def_json,
                                                             blob_storage_reader_handle);
 
-        //7. Issuance Credential for Prover2
+        //7. Issuance Credential for Prover2
+        // Prover2 creates Master Secret
+        let prover_2_master_secret = "prover2_master_secret";
+        anoncreds::prover_create_master_secret(prover_2_wallet_handle, prover_2_master_secret).unwrap();
+
+        anoncreds::multi_steps_create_revocation_credential(prover_2_master_secret,
+                                                            prover_2_wallet_handle,
+                                                            issuer_wallet_handle,
+                                                            CREDENTIAL2_ID,
+                                                            &anoncreds::gvt2_credential_values_json(),
+                                                            &cred_def_id,
+                                                            &cred_def_json,
+                                                            &rev_reg_id,
+                                                            &revoc_reg_def_json,
+                                                            blob_storage_reader_handle);
+
+        //8. Issuance Credential for Prover3
+        let prover_3_master_secret = "prover_3_master_secret";
+        anoncreds::prover_create_master_secret(prover_3_wallet_handle, prover_3_master_secret).unwrap();
+
+        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_3_wallet_handle,
+                                                                         DID_MY2,
+                                                                         &cred_offer_json,
+                                                                         &cred_def_json,
+                                                                         prover_3_master_secret).unwrap();
+
+        let res = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                      &cred_offer_json,
+                                                      &cred_req_json,
+                                                      &anoncreds::gvt_credential_values_json(),
+                                                      Some(&rev_reg_id),
+                                                      Some(blob_storage_reader_handle));
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_1_wallet_handle, &prover_1_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_2_wallet_handle, &prover_2_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_3_wallet_handle, &prover_3_wallet_config).unwrap();
+    }
+
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_batch_issuance_by_demand_revocation_strategy() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_issuance_by_demand_revocation_strategy").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_issuance_by_demand_revocation_strategy").unwrap();
+
+        //3. Issuer creates Schema, Credential Definition and Revocation Registry for two Credentials
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer creates Credential Offers and Prover creates Credential Requests
+        let cred_values = vec![anoncreds::gvt_credential_values_json(), anoncreds::gvt2_credential_values_json(), anoncreds::gvt3_credential_values_json()];
+
+        let mut issuance_requests = Vec::new();
+        let mut cred_req_metadatas = Vec::new();
+
+        for values in cred_values.iter() {
+            let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+            let (cred_req_json, cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                                  DID_MY1,
+                                                                                                  &cred_offer_json,
+                                                                                                  &cred_def_json,
+                                                                                                  COMMON_MASTER_SECRET).unwrap();
+
+            issuance_requests.push(json!({
+                "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_json).unwrap(),
+                "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_json).unwrap(),
+                "cred_values": serde_json::from_str::<serde_json::Value>(values).unwrap(),
+            }));
+            cred_req_metadatas.push(cred_req_metadata_json);
+        }
+
+        //6. Issuer fails to issue three Credentials at once as the registry holds only two
+        let res = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                       &json!(issuance_requests).to_string(),
+                                                       Some(&rev_reg_id),
+                                                       Some(blob_storage_reader_handle));
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+
+        //7. Issuer issues first two Credentials, failed batch has left no index allocated
+        let credentials_json = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                                    &json!(&issuance_requests[..2]).to_string(),
+                                                                    Some(&rev_reg_id),
+                                                                    Some(blob_storage_reader_handle)).unwrap();
+        let credentials: serde_json::Value = serde_json::from_str(&credentials_json).unwrap();
+
+        let issued = credentials["credentials"].as_array().unwrap();
+        assert_eq!(2, issued.len());
+        assert_eq!(json!("1"), issued[0]["cred_rev_id"]);
+        assert_eq!(json!("2"), issued[1]["cred_rev_id"]);
+        assert!(credentials["rev_reg_delta"].is_object());
+        assert_eq!(json!(false), credentials["cancelled"]);
+
+        //8. Prover stores both Credentials
+        for (i, (credential, cred_id)) in issued.iter().zip(&[CREDENTIAL1_ID, CREDENTIAL2_ID]).enumerate() {
+            anoncreds::prover_store_credential(prover_wallet_handle,
+                                               cred_id,
+                                               &cred_req_metadatas[i],
+                                               &credential["cred"].to_string(),
+                                               &cred_def_json,
+                                               Some(&revoc_reg_def_json)).unwrap();
+        }
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_1_wallet_handle, prover_1_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //3. Prover creates wallet, gets wallet handle
+        let (prover_2_wallet_handle, prover_2_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //4. Prover creates wallet, gets wallet handle
+        let (prover_3_wallet_handle, prover_3_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
+
+        //5 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_BY_DEFAULT"}"#);
+
+        // Prover1 creates Master Secret
+        let prover_1_master_secret = "prover1_master_secret";
+        anoncreds::prover_create_master_secret(prover_1_wallet_handle, prover_1_master_secret).unwrap();
+
+        anoncreds::multi_steps_create_revocation_credential(prover_1_master_secret,
+                                                            prover_1_wallet_handle,
+                                                            issuer_wallet_handle,
+                                                            CREDENTIAL1_ID,
+                                                            &anoncreds::gvt_credential_values_json(),
+                                                            &cred_def_id,
+                                                            &cred_def_json,
+                                                            &rev_reg_id,
+                                                            &revoc_reg_def_json,
+                                                            blob_storage_reader_handle);
+
+        //9. Issuance Credential for Prover2
         // Prover2 creates Master Secret
         let prover_2_master_secret = "prover2_master_secret";
         anoncreds::prover_create_master_secret(prover_2_wallet_handle, prover_2_master_secret).unwrap();
@@ -1894,7 +2986,7 @@ mod demos {
                                                             &revoc_reg_def_json,
                                                             blob_storage_reader_handle);
 
-        //8. Issuance Credential for Prover3
+        //10. Issuance Credential for Prover3
         let prover_3_master_secret = "prover_3_master_secret";
         anoncreds::prover_create_master_secret(prover_3_wallet_handle, prover_3_master_secret).unwrap();
 
@@ -1920,25 +3012,350 @@ mod demos {
         wallet::close_and_delete_wallet(prover_3_wallet_handle, &prover_3_wallet_config).unwrap();
     }
 
-
     #[cfg(feature = "revocation_tests")]
     #[test]
-    fn anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry() {
+    fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_not_issued_credential_id() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_not_issued_credential_id").unwrap();
+
+        //2. Issuer creates schema
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        //3. Issuer creates credential definition
+        let (cred_def_id, _) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              &schema_json,
+                                                                              TAG_1,
+                                                                              None,
+                                                                              Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+        //4. Issuer creates revocation registry for 2 Credentials
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let tails_writer_handle = utils::blob_storage::open_writer("default", &tails_writer_config).unwrap();
+
+        let (rev_reg_id, _, _) =
+            anoncreds::issuer_create_and_store_revoc_reg(issuer_wallet_handle,
+                                                         &ISSUER_DID,
+                                                         None,
+                                                         TAG_1,
+                                                         &cred_def_id,
+                                                         r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_ON_DEMAND"}"#,
+                                                         tails_writer_handle).unwrap();
+
+        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config).unwrap();
+
+        //5. Issuer revokes Credential by not issued id
+        let cred_rev_id = "100";
+        let res = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, cred_rev_id);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_already_revoked_credential_id() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_already_revoked_credential_id").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_already_revoked_credential_id").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer issues two Credentials
+        let (cred_rev_id_1, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                     prover_wallet_handle,
+                                                                                     issuer_wallet_handle,
+                                                                                     CREDENTIAL1_ID,
+                                                                                     &anoncreds::gvt_credential_values_json(),
+                                                                                     &cred_def_id,
+                                                                                     &cred_def_json,
+                                                                                     &rev_reg_id,
+                                                                                     &revoc_reg_def_json,
+                                                                                     blob_storage_reader_handle);
+
+        let (cred_rev_id_2, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                     prover_wallet_handle,
+                                                                                     issuer_wallet_handle,
+                                                                                     CREDENTIAL2_ID,
+                                                                                     &anoncreds::gvt2_credential_values_json(),
+                                                                                     &cred_def_id,
+                                                                                     &cred_def_json,
+                                                                                     &rev_reg_id,
+                                                                                     &revoc_reg_def_json,
+                                                                                     blob_storage_reader_handle);
+
+        //6. Issuer revokes first Credential
+        let rev_reg_delta_1_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id_1).unwrap();
+
+        //7. Issuer revokes first Credential again
+        let res = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id_1);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        //8. Issuer revokes second Credential starting from the accumulator left by the first revocation
+        let rev_reg_delta_2_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id_2).unwrap();
+
+        let rev_reg_delta_1: serde_json::Value = serde_json::from_str(&rev_reg_delta_1_json).unwrap();
+        let rev_reg_delta_2: serde_json::Value = serde_json::from_str(&rev_reg_delta_2_json).unwrap();
+        assert_eq!(rev_reg_delta_1["value"]["accum"], rev_reg_delta_2["value"]["prevAccum"]);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_credentials_batch() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_credentials_batch").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_credentials_batch").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer issues two Credentials
+        let (cred_rev_id_1, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                     prover_wallet_handle,
+                                                                                     issuer_wallet_handle,
+                                                                                     CREDENTIAL1_ID,
+                                                                                     &anoncreds::gvt_credential_values_json(),
+                                                                                     &cred_def_id,
+                                                                                     &cred_def_json,
+                                                                                     &rev_reg_id,
+                                                                                     &revoc_reg_def_json,
+                                                                                     blob_storage_reader_handle);
+
+        let (cred_rev_id_2, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                     prover_wallet_handle,
+                                                                                     issuer_wallet_handle,
+                                                                                     CREDENTIAL2_ID,
+                                                                                     &anoncreds::gvt2_credential_values_json(),
+                                                                                     &cred_def_id,
+                                                                                     &cred_def_json,
+                                                                                     &rev_reg_id,
+                                                                                     &revoc_reg_def_json,
+                                                                                     blob_storage_reader_handle);
+
+        //6. Issuer tries to revoke both Credentials together with a not issued one
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id,
+                                                       &json!([cred_rev_id_1, cred_rev_id_2, "5"]).to_string());
+        assert_eq!(ErrorCode::AnoncredsInvalidUserRevocId, res.unwrap_err());
+
+        //7. Issuer revokes both Credentials in one step
+        let rev_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id,
+                                                                      &json!([cred_rev_id_1, cred_rev_id_2]).to_string()).unwrap();
+
+        let rev_reg_delta: serde_json::Value = serde_json::from_str(&rev_reg_delta_json).unwrap();
+        let mut revoked: Vec<u64> = rev_reg_delta["value"]["revoked"].as_array().unwrap()
+            .iter().map(|idx| idx.as_u64().unwrap()).collect();
+        revoked.sort();
+        assert_eq!(vec![cred_rev_id_1.parse::<u64>().unwrap(), cred_rev_id_2.parse::<u64>().unwrap()], revoked);
+
+        //8. Issuer revokes first Credential again
+        let res = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id_1);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revoke_credentials_batch_with_id_ranges() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revoke_credentials_batch_with_id_ranges").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revoke_credentials_batch_with_id_ranges").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer issues three Credentials
+        let cred_rev_ids: Vec<String> = [(CREDENTIAL1_ID, anoncreds::gvt_credential_values_json()),
+            (CREDENTIAL2_ID, anoncreds::gvt2_credential_values_json()),
+            (CREDENTIAL3_ID, anoncreds::gvt3_credential_values_json())]
+            .iter()
+            .map(|&(cred_id, ref cred_values)| {
+                let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                           prover_wallet_handle,
+                                                                                           issuer_wallet_handle,
+                                                                                           cred_id,
+                                                                                           cred_values,
+                                                                                           &cred_def_id,
+                                                                                           &cred_def_json,
+                                                                                           &rev_reg_id,
+                                                                                           &revoc_reg_def_json,
+                                                                                           blob_storage_reader_handle);
+                cred_rev_id
+            })
+            .collect();
+        assert_eq!(vec!["1", "2", "3"], cred_rev_ids);
+
+        //6. Issuer tries to revoke a range exceeding the Revocation Registry
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["1-6"]"#);
+        assert_eq!(ErrorCode::AnoncredsInvalidUserRevocId, res.unwrap_err());
+
+        //7. Issuer revokes first two Credentials by overlapping range and id
+        let rev_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["1-2", "2"]"#).unwrap();
+
+        let rev_reg_delta: serde_json::Value = serde_json::from_str(&rev_reg_delta_json).unwrap();
+        let mut revoked: Vec<u64> = rev_reg_delta["value"]["revoked"].as_array().unwrap()
+            .iter().map(|idx| idx.as_u64().unwrap()).collect();
+        revoked.sort();
+        assert_eq!(vec![1, 2], revoked);
+
+        //8. Third Credential has not been revoked by the batch and can be revoked separately
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_ids[2]).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_detect_revoked_credentials() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_detect_revoked_credentials").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_detect_revoked_credentials").unwrap();
+
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuer issues two Credentials
+        let (cred_rev_id_1, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                     prover_wallet_handle,
+                                                                                     issuer_wallet_handle,
+                                                                                     CREDENTIAL1_ID,
+                                                                                     &anoncreds::gvt_credential_values_json(),
+                                                                                     &cred_def_id,
+                                                                                     &cred_def_json,
+                                                                                     &rev_reg_id,
+                                                                                     &revoc_reg_def_json,
+                                                                                     blob_storage_reader_handle);
+
+        let (cred_rev_id_2, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                     prover_wallet_handle,
+                                                                                     issuer_wallet_handle,
+                                                                                     CREDENTIAL2_ID,
+                                                                                     &anoncreds::gvt2_credential_values_json(),
+                                                                                     &cred_def_id,
+                                                                                     &cred_def_json,
+                                                                                     &rev_reg_id,
+                                                                                     &revoc_reg_def_json,
+                                                                                     blob_storage_reader_handle);
+
+        //6. Prover detects no revoked Credentials before revocation
+        let revoked_json = anoncreds::prover_detect_revoked_credentials(prover_wallet_handle, "{}").unwrap();
+        let revoked: Vec<CredentialInfo> = serde_json::from_str(&revoked_json).unwrap();
+        assert!(revoked.is_empty());
+
+        //7. Issuer revokes first Credential
+        let rev_reg_delta_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id_1).unwrap();
+
+        //8. Prover detects first Credential as revoked
+        let rev_reg_deltas_json = json!({rev_reg_id: serde_json::from_str::<serde_json::Value>(&rev_reg_delta_json).unwrap()}).to_string();
+
+        let revoked_json = anoncreds::prover_detect_revoked_credentials(prover_wallet_handle, &rev_reg_deltas_json).unwrap();
+        let revoked: Vec<CredentialInfo> = serde_json::from_str(&revoked_json).unwrap();
+
+        assert_eq!(1, revoked.len());
+        assert_eq!(CREDENTIAL1_ID, revoked[0].referent);
+        assert_eq!(Some(cred_rev_id_1), revoked[0].cred_rev_id);
+
+        //9. Issuer revokes second Credential
+        let rev_reg_delta_2_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id_2).unwrap();
+
+        //10. Prover detects both Credentials as revoked by the ordered list of deltas
+        let rev_reg_deltas_json = json!({
+            rev_reg_id: [
+                serde_json::from_str::<serde_json::Value>(&rev_reg_delta_json).unwrap(),
+                serde_json::from_str::<serde_json::Value>(&rev_reg_delta_2_json).unwrap()
+            ]
+        }).to_string();
+
+        let revoked_json = anoncreds::prover_detect_revoked_credentials(prover_wallet_handle, &rev_reg_deltas_json).unwrap();
+        let revoked: Vec<CredentialInfo> = serde_json::from_str(&revoked_json).unwrap();
+
+        let mut revoked_referents = revoked.into_iter().map(|cred_info| cred_info.referent).collect::<Vec<String>>();
+        revoked_referents.sort();
+        assert_eq!(vec![CREDENTIAL1_ID.to_string(), CREDENTIAL2_ID.to_string()], revoked_referents);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_explain_unsatisfiable_proof_req_for_revoked_credential() {
         Setup::empty();
 
         //1. Issuer creates wallet, gets wallet handle
-        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_explain_unsatisfiable_proof_req_for_revoked_credential").unwrap();
 
         //2. Prover creates wallet, gets wallet handle
-        let (prover_1_wallet_handle, prover_1_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
-
-        //3. Prover creates wallet, gets wallet handle
-        let (prover_2_wallet_handle, prover_2_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
-
-        //4. Prover creates wallet, gets wallet handle
-        let (prover_3_wallet_handle, prover_3_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_explain_unsatisfiable_proof_req_for_revoked_credential").unwrap();
 
-        //5 Issuer creates Schema, Credential Definition and Revocation Registry
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry
         let (_, _,
             cred_def_id, cred_def_json,
             rev_reg_id, revoc_reg_def_json, _,
@@ -1946,108 +3363,135 @@ mod demos {
                                                                                                ISSUER_DID,
                                                                                                GVT_SCHEMA_NAME,
                                                                                                GVT_SCHEMA_ATTRIBUTES,
-                                                                                               r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_BY_DEFAULT"}"#);
-
-        // Prover1 creates Master Secret
-        let prover_1_master_secret = "prover1_master_secret";
-        anoncreds::prover_create_master_secret(prover_1_wallet_handle, prover_1_master_secret).unwrap();
-
-        anoncreds::multi_steps_create_revocation_credential(prover_1_master_secret,
-                                                            prover_1_wallet_handle,
-                                                            issuer_wallet_handle,
-                                                            CREDENTIAL1_ID,
-                                                            &anoncreds::gvt_credential_values_json(),
-                                                            &cred_def_id,
-                                                            &cred_def_json,
-                                                            &rev_reg_id,
-                                                            &revoc_reg_def_json,
-                                                            blob_storage_reader_handle);
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
 
-        //9. Issuance Credential for Prover2
-        // Prover2 creates Master Secret
-        let prover_2_master_secret = "prover2_master_secret";
-        anoncreds::prover_create_master_secret(prover_2_wallet_handle, prover_2_master_secret).unwrap();
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
 
-        anoncreds::multi_steps_create_revocation_credential(prover_2_master_secret,
-                                                            prover_2_wallet_handle,
-                                                            issuer_wallet_handle,
-                                                            CREDENTIAL2_ID,
-                                                            &anoncreds::gvt2_credential_values_json(),
-                                                            &cred_def_id,
-                                                            &cred_def_json,
-                                                            &rev_reg_id,
-                                                            &revoc_reg_def_json,
-                                                            blob_storage_reader_handle);
+        //5. Issuer issues Credential
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                   prover_wallet_handle,
+                                                                                   issuer_wallet_handle,
+                                                                                   CREDENTIAL1_ID,
+                                                                                   &anoncreds::gvt_credential_values_json(),
+                                                                                   &cred_def_id,
+                                                                                   &cred_def_json,
+                                                                                   &rev_reg_id,
+                                                                                   &revoc_reg_def_json,
+                                                                                   blob_storage_reader_handle);
+
+        //6. Proof request asks for non-revoked Credential
+        let proof_req_json = json!({
+                                       "nonce":"123432421212",
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{"name":"name"}
+                                       },
+                                       "requested_predicates":{
+                                            "predicate1_referent":{"name":"age","p_type":">=","p_value":18}
+                                       },
+                                       "non_revoked": {"to": 100}
+                                    }).to_string();
 
-        //10. Issuance Credential for Prover3
-        let prover_3_master_secret = "prover_3_master_secret";
-        anoncreds::prover_create_master_secret(prover_3_wallet_handle, prover_3_master_secret).unwrap();
+        //7. Proof request can be satisfied before revocation
+        let explanation_json = anoncreds::prover_explain_unsatisfiable_proof_req(prover_wallet_handle, &proof_req_json, "{}").unwrap();
+        let explanation: serde_json::Value = serde_json::from_str(&explanation_json).unwrap();
+        assert_eq!(json!({"attrs": {}, "predicates": {}}), explanation);
 
-        let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+        //8. Issuer revokes Credential
+        let rev_reg_delta_json = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id).unwrap();
+        let rev_reg_deltas_json = json!({rev_reg_id.clone(): serde_json::from_str::<serde_json::Value>(&rev_reg_delta_json).unwrap()}).to_string();
 
-        let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_3_wallet_handle,
-                                                                         DID_MY2,
-                                                                         &cred_offer_json,
-                                                                         &cred_def_json,
-                                                                         prover_3_master_secret).unwrap();
+        //9. Prover is told the only matching Credential is revoked
+        let explanation_json = anoncreds::prover_explain_unsatisfiable_proof_req(prover_wallet_handle, &proof_req_json, &rev_reg_deltas_json).unwrap();
+        let explanation: serde_json::Value = serde_json::from_str(&explanation_json).unwrap();
 
-        let res = anoncreds::issuer_create_credential(issuer_wallet_handle,
-                                                      &cred_offer_json,
-                                                      &cred_req_json,
-                                                      &anoncreds::gvt_credential_values_json(),
-                                                      Some(&rev_reg_id),
-                                                      Some(blob_storage_reader_handle));
-        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+        let reason = format!("registry all revoked: every matching credential is revoked in {}", rev_reg_id);
+        assert_eq!(json!({
+            "attrs": {"attr1_referent": reason},
+            "predicates": {"predicate1_referent": reason}
+        }), explanation);
 
         wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
-        wallet::close_and_delete_wallet(prover_1_wallet_handle, &prover_1_wallet_config).unwrap();
-        wallet::close_and_delete_wallet(prover_2_wallet_handle, &prover_2_wallet_config).unwrap();
-        wallet::close_and_delete_wallet(prover_3_wallet_handle, &prover_3_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
     #[cfg(feature = "revocation_tests")]
     #[test]
-    fn anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_not_issued_credential_id() {
+    fn anoncreds_works_for_max_provable_timestamp_of_stale_witness() {
         Setup::empty();
 
         //1. Issuer creates wallet, gets wallet handle
-        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_issuance_by_demand_revocation_strategy_for_revoke_not_issued_credential_id").unwrap();
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_max_provable_timestamp_of_stale_witness").unwrap();
 
-        //2. Issuer creates schema
-        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
-                                                               GVT_SCHEMA_NAME,
-                                                               SCHEMA_VERSION,
-                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_max_provable_timestamp_of_stale_witness").unwrap();
 
-        //3. Issuer creates credential definition
-        let (cred_def_id, _) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
-                                                                              ISSUER_DID,
-                                                                              &schema_json,
-                                                                              TAG_1,
-                                                                              None,
-                                                                              Some(&anoncreds::revocation_cred_def_config())).unwrap();
+        //3 Issuer creates Schema, Credential Definition and Revocation Registry (own DID, so no other test builds witnesses for it)
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               DID_MY2,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
 
-        //4. Issuer creates revocation registry for 2 Credentials
-        let tails_writer_config = anoncreds::tails_writer_config();
-        let tails_writer_handle = utils::blob_storage::open_writer("default", &tails_writer_config).unwrap();
+        //4. Prover hasn't built any witness for the registry yet
+        assert_eq!("null", anoncreds::prover_get_max_provable_timestamp(&rev_reg_id).unwrap());
 
-        let (rev_reg_id, _, _) =
-            anoncreds::issuer_create_and_store_revoc_reg(issuer_wallet_handle,
-                                                         &ISSUER_DID,
-                                                         None,
-                                                         TAG_1,
-                                                         &cred_def_id,
-                                                         r#"{"max_cred_num":2, "issuance_type":"ISSUANCE_ON_DEMAND"}"#,
-                                                         tails_writer_handle).unwrap();
+        //5. Issuer issues Credential for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
 
-        let blob_storage_reader_handle = utils::blob_storage::open_reader(TYPE, &tails_writer_config).unwrap();
+        let (cred_rev_id, rev_reg_delta_json) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                                    prover_wallet_handle,
+                                                                                                    issuer_wallet_handle,
+                                                                                                    CREDENTIAL1_ID,
+                                                                                                    &anoncreds::gvt_credential_values_json(),
+                                                                                                    &cred_def_id,
+                                                                                                    &cred_def_json,
+                                                                                                    &rev_reg_id,
+                                                                                                    &revoc_reg_def_json,
+                                                                                                    blob_storage_reader_handle);
+        let rev_reg_delta_json = rev_reg_delta_json.unwrap();
+
+        //6. Prover creates Revocation State for the accumulator at timestamp 100
+        let rev_state_json = anoncreds::create_revocation_state(blob_storage_reader_handle,
+                                                                &revoc_reg_def_json,
+                                                                &rev_reg_delta_json,
+                                                                100,
+                                                                &cred_rev_id).unwrap();
 
-        //5. Issuer revokes Credential by not issued id
-        let cred_rev_id = "100";
-        let res = anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, cred_rev_id);
-        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+        //7. Issuer issues another Credential, accumulator changes at timestamp 200
+        let (_, rev_reg_delta_2_json) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                            prover_wallet_handle,
+                                                                                            issuer_wallet_handle,
+                                                                                            CREDENTIAL2_ID,
+                                                                                            &anoncreds::gvt2_credential_values_json(),
+                                                                                            &cred_def_id,
+                                                                                            &cred_def_json,
+                                                                                            &rev_reg_id,
+                                                                                            &revoc_reg_def_json,
+                                                                                            blob_storage_reader_handle);
+
+        //8. Verifier requests non-revocation up to 200, Prover's witness supports only 100
+        let max_provable_timestamp: u64 = serde_json::from_str(&anoncreds::prover_get_max_provable_timestamp(&rev_reg_id).unwrap()).unwrap();
+        assert_eq!(100, max_provable_timestamp);
+        assert!(max_provable_timestamp < 200);
+
+        //9. Prover updates Revocation State and can prove non-revocation at 200
+        anoncreds::update_revocation_state(blob_storage_reader_handle,
+                                           &rev_state_json,
+                                           &revoc_reg_def_json,
+                                           &rev_reg_delta_2_json.unwrap(),
+                                           200,
+                                           &cred_rev_id).unwrap();
+
+        assert_eq!("200", anoncreds::prover_get_max_provable_timestamp(&rev_reg_id).unwrap());
 
         wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
     #[cfg(feature = "revocation_tests")]
@@ -2282,6 +3726,61 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_credential_attr_tag_policy_with_range_queryable_attribute() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_attr_tag_policy_with_range_queryable_attribute").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_attr_tag_policy_with_range_queryable_attribute").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_schema_id, _schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                               ISSUER_DID,
+                                                                                                               GVT_SCHEMA_NAME,
+                                                                                                               GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Prover sets credential attr tag policy enabling range queries on age
+        let catpol_json = r#"{"taggable": ["name", "age"], "range_queryable": ["age"]}"#;
+        anoncreds::prover_set_credential_attr_tag_policy(prover_wallet_handle, &cred_def_id, Some(catpol_json), false).unwrap();
+
+        //6. Issuance credential for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //7. Prover searches by age comparison
+        let filter_json = json!({"attr::age::value": {"$gte": "18"}}).to_string();
+        let (search_handle, count) = anoncreds::prover_search_credentials(prover_wallet_handle, &filter_json).unwrap();
+        assert_eq!(count, 1);
+        anoncreds::prover_close_credentials_search(search_handle).unwrap();
+
+        let filter_json = json!({"attr::age::value": {"$lt": "18"}}).to_string();
+        let (search_handle, count) = anoncreds::prover_search_credentials(prover_wallet_handle, &filter_json).unwrap();
+        assert_eq!(count, 0);
+        anoncreds::prover_close_credentials_search(search_handle).unwrap();
+
+        //8. Prover resets policy retroactively, dropping unencrypted age tag
+        anoncreds::prover_set_credential_attr_tag_policy(prover_wallet_handle, &cred_def_id, Some(r#"["name", "age"]"#), true).unwrap();
+
+        let filter_json = json!({"attr::age::value": {"$gte": "18"}}).to_string();
+        let (search_handle, count) = anoncreds::prover_search_credentials(prover_wallet_handle, &filter_json).unwrap();
+        assert_eq!(count, 0);
+        anoncreds::prover_close_credentials_search(search_handle).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_credential_deletion() {
         Setup::empty();
@@ -2648,25 +4147,122 @@ mod demos {
         //2. Create Prover wallet, gets wallet handle
         let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_single_issuer_single_prover").unwrap();
 
-        let schema_attributes = r#"["age", "height", "weight", "salary"]"#;
+        let schema_attributes = r#"["age", "height", "weight", "salary"]"#;
+
+        //3. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             schema_attributes);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_values = json!({
+            "age": {"raw": "28", "encoded": "28"},
+            "height": {"raw": "175", "encoded": "175"},
+            "weight": {"raw": "78", "encoded": "78"},
+            "salary": {"raw": "2000", "encoded": "2000"}
+        }).to_string();
+
+        //5. Issuance credential for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &cred_values,
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //6. Proof request
+        let nonce = anoncreds::generate_nonce().unwrap();
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{},
+                                       "requested_predicates":{
+                                            "predicate1_referent":{
+                                                "name":"age","p_type":">=","p_value":18
+                                            },
+                                            "predicate2_referent":{
+                                                "name":"height","p_type":">","p_value":170
+                                            },
+                                            "predicate3_referent":{
+                                                "name":"weight","p_type":"<","p_value":90
+                                            },
+                                            "predicate4_referent":{
+                                                "name":"salary","p_type":"<=","p_value":2000
+                                            }
+                                       }
+                                    }).to_string();
+
+        //7. Prover gets Credentials for Proof Request
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credential = anoncreds::get_credential_for_predicate_referent(&credentials_json, "predicate1_referent");
+
+        //8. Prover creates Proof
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {},
+            "requested_predicates": {
+                "predicate1_referent": {"cred_id": credential.referent},
+                "predicate2_referent": {"cred_id": credential.referent},
+                "predicate3_referent": {"cred_id": credential.referent},
+                "predicate4_referent": {"cred_id": credential.referent}
+            },
+        }).to_string();
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
+        let rev_states_json = json!({}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        //9. Verifier verifies proof
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_scaled_predicate() {
+        Setup::empty();
+
+        //1. Create Issuer and Prover wallets
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_scaled_predicate").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_scaled_predicate").unwrap();
 
-        //3. Issuer creates Schema and Credential Definition
+        //2. Issuer creates Schema and Credential Definition
         let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
                                                                                                              ISSUER_DID,
                                                                                                              GVT_SCHEMA_NAME,
-                                                                                                             schema_attributes);
+                                                                                                             r#"["balance"]"#);
 
-        //4. Prover creates Master Secret
+        //3. Prover creates Master Secret
         anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
 
+        //4. Issuer issues Credential with balance of $10.50 stored in cents
         let cred_values = json!({
-            "age": {"raw": "28", "encoded": "28"},
-            "height": {"raw": "175", "encoded": "175"},
-            "weight": {"raw": "78", "encoded": "78"},
-            "salary": {"raw": "2000", "encoded": "2000"}
+            "balance": {"raw": "1050", "encoded": "1050"}
         }).to_string();
 
-        //5. Issuance credential for Prover
         anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
                                                  prover_wallet_handle,
                                                  issuer_wallet_handle,
@@ -2675,48 +4271,34 @@ mod demos {
                                                  &cred_def_id,
                                                  &cred_def_json);
 
-        //6. Proof request
-        let nonce = anoncreds::generate_nonce().unwrap();
+        //5. Verifier asks for balance >= $10
         let proof_req_json = json!({
-                                       "nonce": nonce,
-                                       "name":"proof_req_1",
-                                       "version":"0.1",
-                                       "requested_attributes":{},
-                                       "requested_predicates":{
-                                            "predicate1_referent":{
-                                                "name":"age","p_type":">=","p_value":18
-                                            },
-                                            "predicate2_referent":{
-                                                "name":"height","p_type":">","p_value":170
-                                            },
-                                            "predicate3_referent":{
-                                                "name":"weight","p_type":"<","p_value":90
-                                            },
-                                            "predicate4_referent":{
-                                                "name":"salary","p_type":"<=","p_value":2000
-                                            }
-                                       }
-                                    }).to_string();
+            "nonce": anoncreds::generate_nonce().unwrap(),
+            "name":"proof_req_1",
+            "version":"0.1",
+            "requested_attributes":{},
+            "requested_predicates":{
+                "predicate1_referent":{
+                    "name":"balance","p_type":">=","p_value":10,"scale":2
+                }
+            }
+        }).to_string();
 
-        //7. Prover gets Credentials for Proof Request
+        //6. Prover gets Credentials for Proof Request
         let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
         let credential = anoncreds::get_credential_for_predicate_referent(&credentials_json, "predicate1_referent");
 
-        //8. Prover creates Proof
+        //7. Prover creates Proof
         let requested_credentials_json = json!({
             "self_attested_attributes": {},
             "requested_attributes": {},
             "requested_predicates": {
-                "predicate1_referent": {"cred_id": credential.referent},
-                "predicate2_referent": {"cred_id": credential.referent},
-                "predicate3_referent": {"cred_id": credential.referent},
-                "predicate4_referent": {"cred_id": credential.referent}
+                "predicate1_referent": {"cred_id": credential.referent}
             },
         }).to_string();
 
         let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
         let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
-        let rev_states_json = json!({}).to_string();
 
         let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
                                                         &proof_req_json,
@@ -2724,20 +4306,34 @@ mod demos {
                                                         COMMON_MASTER_SECRET,
                                                         &schemas_json,
                                                         &cred_defs_json,
-                                                        &rev_states_json).unwrap();
-
-        //9. Verifier verifies proof
-        let rev_reg_defs_json = json!({}).to_string();
-        let rev_regs_json = json!({}).to_string();
+                                                        "{}").unwrap();
 
+        //8. Verifier verifies Proof
         let valid = anoncreds::verifier_verify_proof(&proof_req_json,
                                                      &proof_json,
                                                      &schemas_json,
                                                      &cred_defs_json,
-                                                     &rev_reg_defs_json,
-                                                     &rev_regs_json).unwrap();
+                                                     "{}",
+                                                     "{}").unwrap();
         assert!(valid);
 
+        //9. Prover has no Credentials for balance >= $11
+        let proof_req_json = json!({
+            "nonce": anoncreds::generate_nonce().unwrap(),
+            "name":"proof_req_1",
+            "version":"0.1",
+            "requested_attributes":{},
+            "requested_predicates":{
+                "predicate1_referent":{
+                    "name":"balance","p_type":">=","p_value":11,"scale":2
+                }
+            }
+        }).to_string();
+
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+        assert!(credentials.predicates["predicate1_referent"].is_empty());
+
         wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
@@ -2924,6 +4520,149 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_allowed_values() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_allowed_values").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_allowed_values").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuance 2 credentials for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL3_ID,
+                                                 &anoncreds::gvt2_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //6. Proof request restricting attr value to public list
+        let nonce = anoncreds::generate_nonce().unwrap();
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{
+                                                "name":"name"
+                                            }
+                                       },
+                                       "requested_predicates":{
+                                       },
+                                       "allowed_values":{
+                                            "attr1_referent": ["Alex", "Bob"]
+                                       }
+                                    }).to_string();
+
+        //7. Prover is offered only the credential with in-list value
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+        assert_eq!(1, credentials.attrs["attr1_referent"].len());
+        assert_eq!(CREDENTIAL1_ID, credentials.attrs["attr1_referent"][0].cred_info.referent);
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
+        let rev_states_json = json!({}).to_string();
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        //8. Prover creates Proof for in-list value and Verifier accepts it
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {
+                "attr1_referent": {"cred_id": CREDENTIAL1_ID, "revealed":true}
+            },
+            "requested_predicates": {}
+        }).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //9. Prover is not able to create Proof for out-of-list value
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {
+                "attr1_referent": {"cred_id": CREDENTIAL3_ID, "revealed":true}
+            },
+            "requested_predicates": {}
+        }).to_string();
+
+        let res = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                 &proof_req_json,
+                                                 &requested_credentials_json,
+                                                 COMMON_MASTER_SECRET,
+                                                 &schemas_json,
+                                                 &cred_defs_json,
+                                                 &rev_states_json);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        //10. Verifier rejects Proof for out-of-list value built for the request without the list
+        let unrestricted_proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{
+                                                "name":"name"
+                                            }
+                                       },
+                                       "requested_predicates":{
+                                       }
+                                    }).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &unrestricted_proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        let res = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                   &proof_json,
+                                                   &schemas_json,
+                                                   &cred_defs_json,
+                                                   &rev_reg_defs_json,
+                                                   &rev_regs_json);
+        assert_code!(ErrorCode::AnoncredsProofRejected, res);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_single_issuer_single_prover_fully_qualified_ids() {
         Setup::empty();
@@ -3453,4 +5192,122 @@ mod demos {
         wallet::close_and_delete_wallet(issuer_gvt_wallet_handle, &issuer_gvt_wallet_config).unwrap();
         wallet::close_and_delete_wallet(issuer_xyz_wallet_handle, &issuer_xyz_wallet_config).unwrap();
     }
+
+    #[test]
+    fn anoncreds_works_for_schema_version_range() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_schema_version_range").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_schema_version_range").unwrap();
+
+        //3. Issuer creates Schemas of versions 1.2 and 2.1 and Credential Definitions for them
+        let (schema_1_2_id, schema_1_2_json) = anoncreds::issuer_create_schema(ISSUER_DID, GVT_SCHEMA_NAME, "1.2", GVT_SCHEMA_ATTRIBUTES).unwrap();
+        let (cred_def_1_2_id, cred_def_1_2_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                                 ISSUER_DID,
+                                                                                                 &schema_1_2_json,
+                                                                                                 TAG_1,
+                                                                                                 None,
+                                                                                                 Some(&anoncreds::default_cred_def_config())).unwrap();
+
+        let (_, schema_2_1_json) = anoncreds::issuer_create_schema(ISSUER_DID, GVT_SCHEMA_NAME, "2.1", GVT_SCHEMA_ATTRIBUTES).unwrap();
+        let (cred_def_2_1_id, cred_def_2_1_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                                 ISSUER_DID,
+                                                                                                 &schema_2_1_json,
+                                                                                                 TAG_1,
+                                                                                                 None,
+                                                                                                 Some(&anoncreds::default_cred_def_config())).unwrap();
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuance of a credential of each schema version for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_1_2_id,
+                                                 &cred_def_1_2_json);
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL2_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_2_1_id,
+                                                 &cred_def_2_1_json);
+
+        //6. Prover searches credentials by schema version range
+        let credentials_json = anoncreds::prover_get_credentials(prover_wallet_handle,
+                                                                 &json!({"schema_version_range": ">=1.0,<2.0"}).to_string()).unwrap();
+        let credentials: Vec<CredentialInfo> = serde_json::from_str(&credentials_json).unwrap();
+        assert_eq!(1, credentials.len());
+        assert_eq!(CREDENTIAL1_ID, credentials[0].referent);
+
+        //7. Proof request accepting any 1.x version of the schema
+        let nonce = anoncreds::generate_nonce().unwrap();
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{
+                                            "attr1_referent":{
+                                                "name":"name",
+                                                "restrictions": {"schema_name": GVT_SCHEMA_NAME, "schema_version_range": ">=1.0,<2.0"}
+                                            }
+                                       },
+                                       "requested_predicates":{
+                                            "predicate1_referent":{
+                                                "name":"age",
+                                                "p_type":">=",
+                                                "p_value":18,
+                                                "restrictions": {"schema_version_range": ">=1.0,<2.0"}
+                                            }
+                                       }
+                                    }).to_string();
+
+        //8. Prover is offered only the credential of schema version 1.2
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+        assert_eq!(1, credentials.attrs["attr1_referent"].len());
+        assert_eq!(CREDENTIAL1_ID, credentials.attrs["attr1_referent"][0].cred_info.referent);
+        assert_eq!(1, credentials.predicates["predicate1_referent"].len());
+        assert_eq!(CREDENTIAL1_ID, credentials.predicates["predicate1_referent"][0].cred_info.referent);
+
+        //9. Prover creates Proof and Verifier accepts it
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {
+                "attr1_referent": {"cred_id": CREDENTIAL1_ID, "revealed":true}
+            },
+            "requested_predicates": {
+                "predicate1_referent": {"cred_id": CREDENTIAL1_ID}
+            }
+        }).to_string();
+
+        let schemas_json = json!({schema_1_2_id: serde_json::from_str::<Schema>(&schema_1_2_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_1_2_id: serde_json::from_str::<CredentialDefinition>(&cred_def_1_2_json).unwrap()}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        "{}").unwrap();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     "{}",
+                                                     "{}").unwrap();
+        assert!(valid);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
 }
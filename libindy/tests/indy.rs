@@ -3,4 +3,9 @@ extern crate indyrs as indy;
 #[test]
 fn set_runtime_config_works() {
     indy::set_runtime_config(r#"{"crypto_thread_pool_size": 2}"#);
-}
\ No newline at end of file
+}
+#[test]
+fn set_runtime_config_fails_for_predicate_max_bits_out_of_range() {
+    assert_eq!(indy::ErrorCode::CommonInvalidStructure, indy::set_runtime_config(r#"{"predicate_max_bits": 0}"#));
+    assert_eq!(indy::ErrorCode::CommonInvalidStructure, indy::set_runtime_config(r#"{"predicate_max_bits": 129}"#));
+}
@@ -0,0 +1,107 @@
+#![cfg(not(feature = "log_sensitive"))]
+
+#[macro_use]
+mod utils;
+
+inject_indy_dependencies!();
+
+extern crate indyrs as indy;
+extern crate indyrs as api;
+
+use utils::{wallet, anoncreds, logger, test};
+use utils::anoncreds::{COMMON_MASTER_SECRET, CREDENTIAL1_ID};
+use utils::constants::*;
+
+use std::sync::Mutex;
+
+const RAW_NAME: &str = "Quentin Xylophonist";
+const SELF_ATTESTED_PHONE: &str = "8-800-777-31337";
+
+lazy_static! {
+    static ref MESSAGES: Mutex<Vec<String>> = Default::default();
+}
+
+struct CollectingLogger;
+
+impl log::Log for CollectingLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        MESSAGES.lock().unwrap().push(format!("{}", record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+static COLLECTING_LOGGER: CollectingLogger = CollectingLogger;
+
+#[test]
+fn raw_attribute_values_never_reach_log_by_default() {
+    logger::set_logger(&COLLECTING_LOGGER);
+    test::cleanup_storage("raw_attribute_values_never_reach_log_by_default");
+
+    let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("raw_attribute_values_never_reach_log_by_default").unwrap();
+    let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("raw_attribute_values_never_reach_log_by_default").unwrap();
+
+    let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                         ISSUER_DID,
+                                                                                                         GVT_SCHEMA_NAME,
+                                                                                                         GVT_SCHEMA_ATTRIBUTES);
+
+    let raw_values_json = json!({"name": RAW_NAME, "sex": "male", "height": "175", "age": "28"}).to_string();
+    let cred_values_json = anoncreds::issuer_encode_credential_values(&schema_json, &raw_values_json).unwrap();
+
+    anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+    anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                             prover_wallet_handle,
+                                             issuer_wallet_handle,
+                                             CREDENTIAL1_ID,
+                                             &cred_values_json,
+                                             &cred_def_id,
+                                             &cred_def_json);
+
+    let credential_json = anoncreds::prover_get_credential(prover_wallet_handle, CREDENTIAL1_ID).unwrap();
+    assert!(credential_json.contains(RAW_NAME));
+
+    let proof_req_json = json!({
+        "nonce": anoncreds::generate_nonce().unwrap(),
+        "name": "proof_req_1",
+        "version": "0.1",
+        "requested_attributes": {
+            "attr1_referent": {"name": "name"},
+            "attr2_referent": {"name": "phone"}
+        },
+        "requested_predicates": {}
+    }).to_string();
+
+    let requested_credentials_json = json!({
+        "self_attested_attributes": {"attr2_referent": SELF_ATTESTED_PHONE},
+        "requested_attributes": {"attr1_referent": {"cred_id": CREDENTIAL1_ID, "revealed": true}},
+        "requested_predicates": {}
+    }).to_string();
+
+    let schemas_json = json!({schema_id: serde_json::from_str::<serde_json::Value>(&schema_json).unwrap()}).to_string();
+    let cred_defs_json = json!({cred_def_id: serde_json::from_str::<serde_json::Value>(&cred_def_json).unwrap()}).to_string();
+
+    anoncreds::prover_create_proof(prover_wallet_handle,
+                                   &proof_req_json,
+                                   &requested_credentials_json,
+                                   COMMON_MASTER_SECRET,
+                                   &schemas_json,
+                                   &cred_defs_json,
+                                   "{}").unwrap();
+
+    wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+    wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+
+    let messages = MESSAGES.lock().unwrap();
+    assert!(messages.iter().any(|message| message.contains("sha256:")));
+
+    for message in messages.iter() {
+        assert!(!message.contains(RAW_NAME), "raw attribute value logged: {}", message);
+        assert!(!message.contains(SELF_ATTESTED_PHONE), "self-attested value logged: {}", message);
+    }
+}
@@ -5,7 +5,6 @@ inject_indy_dependencies!();
 
 extern crate indyrs as indy;
 extern crate indyrs as api;
-extern crate sha2;
 
 use self::indy::ErrorCode;
 use utils::payments;
@@ -1,15 +1,19 @@
 extern crate futures;
+extern crate indy_sys;
 
-use indy::IndyError;
+use self::indy_sys::Error;
+use indy::{ErrorCode, IndyError};
 use indy::anoncreds;
 use self::futures::Future;
 use serde_json;
 
-use utils::{environment, wallet, blob_storage, test};
+use utils::{environment, wallet, blob_storage, test, callback};
 use utils::types::CredentialOfferInfo;
 
 use std::sync::Once;
 use std::mem;
+use std::ffi::CString;
+use super::libc::{c_char, c_void};
 use utils::constants::*;
 
 use std::collections::{HashSet, HashMap};
@@ -50,6 +54,23 @@ pub fn issuer_create_schema(issuer_did: &str, name: &str, version: &str, attr_na
     anoncreds::issuer_create_schema(issuer_did, name, version, attr_names).wait()
 }
 
+pub fn issuer_create_typed_schema(issuer_did: &str, name: &str, version: &str, attr_names: &str, attr_types_json: &str) -> Result<(String, String), ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string_string();
+
+    let issuer_did = CString::new(issuer_did).unwrap();
+    let name = CString::new(name).unwrap();
+    let version = CString::new(version).unwrap();
+    let attr_names = CString::new(attr_names).unwrap();
+    let attr_types_json = CString::new(attr_types_json).unwrap();
+
+    let err = unsafe {
+        indy_issuer_create_typed_schema(command_handle, issuer_did.as_ptr(), name.as_ptr(), version.as_ptr(),
+                                        attr_names.as_ptr(), attr_types_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_string(err, receiver)
+}
+
 pub fn issuer_create_credential_definition(wallet_handle: i32, issuer_did: &str, schema: &str, tag: &str,
                                            signature_type: Option<&str>, config: Option<&str>) -> Result<(String, String), IndyError> {
     anoncreds::issuer_create_and_store_credential_def(wallet_handle, issuer_did, schema, tag, signature_type, config.unwrap_or("{}")).wait() // TODO: FIXME OPTIONAL CONFIG
@@ -78,6 +99,34 @@ pub fn issuer_create_credential(wallet_handle: i32, cred_offer_json: &str, cred_
     anoncreds::issuer_create_credential(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait() // TODO OPTIONAL blob_storage_reader_handle
 }
 
+pub fn issuer_create_credentials(wallet_handle: i32, issuance_requests_json: &str,
+                                 rev_reg_id: Option<&str>, blob_storage_reader_handle: Option<i32>) -> Result<String, IndyError> {
+    anoncreds::issuer_create_credentials(wallet_handle, issuance_requests_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
+pub fn issuer_cancel_credentials(issuance_command_handle: i32) -> Result<(), ErrorCode> {
+    let err = unsafe { indy_issuer_cancel_credentials(issuance_command_handle) };
+
+    match ErrorCode::from(err as i32) {
+        ErrorCode::Success => Ok(()),
+        err => Err(err)
+    }
+}
+
+pub fn issuer_reissue_credential(wallet_handle: i32, cred_offer_json: &str, cred_req_json: &str, old_cred_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let cred_offer_json = CString::new(cred_offer_json).unwrap();
+    let cred_req_json = CString::new(cred_req_json).unwrap();
+    let old_cred_json = CString::new(old_cred_json).unwrap();
+
+    let err = unsafe {
+        indy_issuer_reissue_credential(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_req_json.as_ptr(), old_cred_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
 pub fn issuer_revoke_credential(wallet_handle: i32, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
@@ -86,6 +135,285 @@ pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta: &str, other_rev_re
     anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta, other_rev_reg_delta).wait()
 }
 
+pub fn issuer_mark_revocation_registry_rotated(wallet_handle: i32, rev_reg_id: &str) -> Result<(), ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let rev_reg_id = CString::new(rev_reg_id).unwrap();
+
+    let err = unsafe {
+        indy_issuer_mark_revocation_registry_rotated(command_handle, wallet_handle, rev_reg_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_empty(err, receiver)
+}
+
+pub fn issuer_list_revocation_registries(wallet_handle: i32, cred_def_id: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let cred_def_id = CString::new(cred_def_id).unwrap();
+
+    let err = unsafe {
+        indy_issuer_list_revocation_registries(command_handle, wallet_handle, cred_def_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn issuer_get_revocation_registry_capacity(wallet_handle: i32, rev_reg_id: &str, threshold: f32) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let rev_reg_id = CString::new(rev_reg_id).unwrap();
+
+    let err = unsafe {
+        indy_issuer_get_revocation_registry_capacity(command_handle, wallet_handle, rev_reg_id.as_ptr(), threshold, cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn issuer_validate_attributes_for_schema(schema_json: &str, cred_values_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let schema_json = CString::new(schema_json).unwrap();
+    let cred_values_json = CString::new(cred_values_json).unwrap();
+
+    let err = unsafe {
+        indy_issuer_validate_attributes_for_schema(command_handle, schema_json.as_ptr(), cred_values_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn issuer_encode_credential_values(schema_json: &str, raw_values_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let schema_json = CString::new(schema_json).unwrap();
+    let raw_values_json = CString::new(raw_values_json).unwrap();
+
+    let err = unsafe {
+        indy_issuer_encode_credential_values(command_handle, schema_json.as_ptr(), raw_values_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn issuer_export_revocation_registry(wallet_handle: i32, rev_reg_id: &str, export_config_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let rev_reg_id = CString::new(rev_reg_id).unwrap();
+    let export_config_json = CString::new(export_config_json).unwrap();
+
+    let err = unsafe {
+        indy_issuer_export_revocation_registry(command_handle, wallet_handle, rev_reg_id.as_ptr(), export_config_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn issuer_import_revocation_registry(wallet_handle: i32, import_config_json: &str, rev_reg_snapshot: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let import_config_json = CString::new(import_config_json).unwrap();
+    let rev_reg_snapshot = CString::new(rev_reg_snapshot).unwrap();
+
+    let err = unsafe {
+        indy_issuer_import_revocation_registry(command_handle, wallet_handle, import_config_json.as_ptr(), rev_reg_snapshot.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+extern {
+    #[no_mangle]
+    fn indy_prover_get_credential_fingerprint(command_handle: i32,
+                                              wallet_handle: i32,
+                                              cred_id: *const c_char,
+                                              cb: Option<extern fn(command_handle_: i32, err: Error, fingerprint: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_validate_attributes_for_schema(command_handle: i32,
+                                                  schema_json: *const c_char,
+                                                  cred_values_json: *const c_char,
+                                                  cb: Option<extern fn(command_handle_: i32, err: Error, problems_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_create_typed_schema(command_handle: i32,
+                                       issuer_did: *const c_char,
+                                       name: *const c_char,
+                                       version: *const c_char,
+                                       attr_names: *const c_char,
+                                       attr_types_json: *const c_char,
+                                       cb: Option<extern fn(command_handle_: i32, err: Error,
+                                                            id: *const c_char,
+                                                            schema_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_encode_credential_values(command_handle: i32,
+                                            schema_json: *const c_char,
+                                            raw_values_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: i32, err: Error, cred_values_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_export_revocation_registry(command_handle: i32,
+                                              wallet_handle: i32,
+                                              rev_reg_id: *const c_char,
+                                              export_config_json: *const c_char,
+                                              cb: Option<extern fn(command_handle_: i32, err: Error, rev_reg_snapshot: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_import_revocation_registry(command_handle: i32,
+                                              wallet_handle: i32,
+                                              import_config_json: *const c_char,
+                                              rev_reg_snapshot: *const c_char,
+                                              cb: Option<extern fn(command_handle_: i32, err: Error, rev_reg_id: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_cancel_credentials(issuance_command_handle: i32) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_mark_revocation_registry_rotated(command_handle: i32,
+                                                    wallet_handle: i32,
+                                                    rev_reg_id: *const c_char,
+                                                    cb: Option<extern fn(command_handle_: i32, err: Error)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_list_revocation_registries(command_handle: i32,
+                                              wallet_handle: i32,
+                                              cred_def_id: *const c_char,
+                                              cb: Option<extern fn(command_handle_: i32, err: Error, rev_regs_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_get_revocation_registry_capacity(command_handle: i32,
+                                                    wallet_handle: i32,
+                                                    rev_reg_id: *const c_char,
+                                                    threshold: f32,
+                                                    cb: Option<extern fn(command_handle_: i32, err: Error, capacity_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_verifier_verify_proof_dry_run(command_handle: i32,
+                                          proof_request_json: *const c_char,
+                                          proof_json: *const c_char,
+                                          schemas_json: *const c_char,
+                                          credential_defs_json: *const c_char,
+                                          rev_reg_defs_json: *const c_char,
+                                          rev_regs_json: *const c_char,
+                                          cb: Option<extern fn(command_handle_: i32, err: Error)>) -> Error;
+
+    #[no_mangle]
+    fn indy_verifier_verify_proof_detailed(command_handle: i32,
+                                           proof_request_json: *const c_char,
+                                           proof_json: *const c_char,
+                                           schemas_json: *const c_char,
+                                           credential_defs_json: *const c_char,
+                                           rev_reg_defs_json: *const c_char,
+                                           rev_regs_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: i32, err: Error, report_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_get_revocation_status(command_handle: i32,
+                                  blob_storage_reader_handle: i32,
+                                  rev_reg_def_json: *const c_char,
+                                  rev_reg_delta_json: *const c_char,
+                                  cred_rev_id: *const c_char,
+                                  cb: Option<extern fn(command_handle_: i32, err: Error, revoked: bool)>) -> Error;
+
+    #[no_mangle]
+    fn indy_get_witness_dependent_indices(command_handle: i32,
+                                          rev_reg_def_json: *const c_char,
+                                          rev_reg_delta_json: *const c_char,
+                                          cred_rev_id: *const c_char,
+                                          cb: Option<extern fn(command_handle_: i32, err: Error, indices_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_proof_request_could_be_satisfied_by_cred_def(command_handle: i32,
+                                                         proof_request_json: *const c_char,
+                                                         cred_def_json: *const c_char,
+                                                         schema_json: *const c_char,
+                                                         cb: Option<extern fn(command_handle_: i32, err: Error, satisfiable: bool)>) -> Error;
+
+    #[no_mangle]
+    fn indy_estimate_credential_storage(command_handle: i32,
+                                        schema_attr_count: u32,
+                                        revocable: bool,
+                                        count: u32,
+                                        cb: Option<extern fn(command_handle_: i32, err: Error, size: u64)>) -> Error;
+
+    #[no_mangle]
+    fn indy_compare_schemas(command_handle: i32,
+                            schema_json: *const c_char,
+                            other_schema_json: *const c_char,
+                            cb: Option<extern fn(command_handle_: i32, err: Error, schema_diff_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_build_non_revoked_interval(command_handle: i32,
+                                       timestamp: u64,
+                                       tolerance: u64,
+                                       cb: Option<extern fn(command_handle_: i32, err: Error, interval_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_merge_proof_requests(command_handle: i32,
+                                 proof_request_json: *const c_char,
+                                 other_proof_request_json: *const c_char,
+                                 cb: Option<extern fn(command_handle_: i32, err: Error, proof_request_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_revoke_credentials(command_handle: i32,
+                                      wallet_handle: i32,
+                                      blob_storage_reader_cfg_handle: i32,
+                                      rev_reg_id: *const c_char,
+                                      cred_revoc_ids_json: *const c_char,
+                                      cb: Option<extern fn(command_handle_: i32, err: Error, revoc_reg_delta_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_prover_create_credential_reqs(command_handle: i32,
+                                          wallet_handle: i32,
+                                          prover_did: *const c_char,
+                                          offered_credentials_json: *const c_char,
+                                          master_secret_id: *const c_char,
+                                          cb: Option<extern fn(command_handle_: i32, err: Error, cred_reqs_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_prover_clear_witness_cache(command_handle: i32,
+                                       cb: Option<extern fn(command_handle_: i32, err: Error)>) -> Error;
+
+    #[no_mangle]
+    fn indy_prover_get_max_provable_timestamp(command_handle: i32,
+                                              rev_reg_id: *const c_char,
+                                              cb: Option<extern fn(command_handle_: i32, err: Error, timestamp_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_verifier_set_audit_callback(context: *const c_void,
+                                        cb: Option<extern fn(context: *const c_void, audit_record_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_issuer_reissue_credential(command_handle: i32,
+                                      wallet_handle: i32,
+                                      cred_offer_json: *const c_char,
+                                      cred_req_json: *const c_char,
+                                      old_cred_json: *const c_char,
+                                      cb: Option<extern fn(command_handle_: i32, err: Error, cred_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_prover_explain_unsatisfiable_proof_req(command_handle: i32,
+                                                   wallet_handle: i32,
+                                                   proof_request_json: *const c_char,
+                                                   rev_reg_deltas_json: *const c_char,
+                                                   cb: Option<extern fn(command_handle_: i32, err: Error, explanation_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_prover_get_proof_plan(command_handle: i32,
+                                  wallet_handle: i32,
+                                  proof_request_json: *const c_char,
+                                  rev_reg_deltas_json: *const c_char,
+                                  cb: Option<extern fn(command_handle_: i32, err: Error, requested_credentials_json: *const c_char)>) -> Error;
+
+    #[no_mangle]
+    fn indy_prover_detect_revoked_credentials(command_handle: i32,
+                                              wallet_handle: i32,
+                                              rev_reg_deltas_json: *const c_char,
+                                              cb: Option<extern fn(command_handle_: i32, err: Error, revoked_credentials_json: *const c_char)>) -> Error;
+}
+
 pub fn prover_create_master_secret(wallet_handle: i32, master_secret_id: &str) -> Result<String, IndyError> {
     anoncreds::prover_create_master_secret(wallet_handle, Some(master_secret_id)).wait()
 }
@@ -139,6 +467,56 @@ pub fn prover_get_credentials_for_proof_req(wallet_handle: i32, proof_request_js
     anoncreds::prover_get_credentials_for_proof_req(wallet_handle, proof_request_json).wait()
 }
 
+pub fn prover_get_credential_fingerprint(wallet_handle: i32, cred_id: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let cred_id = CString::new(cred_id).unwrap();
+
+    let err = unsafe {
+        indy_prover_get_credential_fingerprint(command_handle, wallet_handle, cred_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn prover_explain_unsatisfiable_proof_req(wallet_handle: i32, proof_request_json: &str, rev_reg_deltas_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let proof_request_json = CString::new(proof_request_json).unwrap();
+    let rev_reg_deltas_json = CString::new(rev_reg_deltas_json).unwrap();
+
+    let err = unsafe {
+        indy_prover_explain_unsatisfiable_proof_req(command_handle, wallet_handle, proof_request_json.as_ptr(), rev_reg_deltas_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn prover_get_proof_plan(wallet_handle: i32, proof_request_json: &str, rev_reg_deltas_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let proof_request_json = CString::new(proof_request_json).unwrap();
+    let rev_reg_deltas_json = CString::new(rev_reg_deltas_json).unwrap();
+
+    let err = unsafe {
+        indy_prover_get_proof_plan(command_handle, wallet_handle, proof_request_json.as_ptr(), rev_reg_deltas_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn prover_detect_revoked_credentials(wallet_handle: i32, rev_reg_deltas_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let rev_reg_deltas_json = CString::new(rev_reg_deltas_json).unwrap();
+
+    let err = unsafe {
+        indy_prover_detect_revoked_credentials(command_handle, wallet_handle, rev_reg_deltas_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
 pub fn prover_search_credentials_for_proof_req(wallet_handle: i32, proof_request_json: &str, extra_query_json: Option<&str>) -> Result<i32, IndyError> {
     anoncreds::prover_search_credentials_for_proof_req(wallet_handle, proof_request_json, extra_query_json).wait()
 }
@@ -163,6 +541,187 @@ pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_verify_proof_dry_run(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                     cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<(), ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let proof_request_json = CString::new(proof_request_json).unwrap();
+    let proof_json = CString::new(proof_json).unwrap();
+    let schemas_json = CString::new(schemas_json).unwrap();
+    let cred_defs_json = CString::new(cred_defs_json).unwrap();
+    let rev_reg_defs_json = CString::new(rev_reg_defs_json).unwrap();
+    let rev_regs_json = CString::new(rev_regs_json).unwrap();
+
+    let err = unsafe {
+        indy_verifier_verify_proof_dry_run(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(),
+                                           cred_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_empty(err, receiver)
+}
+
+pub fn verifier_verify_proof_detailed(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                      cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let proof_request_json = CString::new(proof_request_json).unwrap();
+    let proof_json = CString::new(proof_json).unwrap();
+    let schemas_json = CString::new(schemas_json).unwrap();
+    let cred_defs_json = CString::new(cred_defs_json).unwrap();
+    let rev_reg_defs_json = CString::new(rev_reg_defs_json).unwrap();
+    let rev_regs_json = CString::new(rev_regs_json).unwrap();
+
+    let err = unsafe {
+        indy_verifier_verify_proof_detailed(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(),
+                                            cred_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn get_revocation_status(blob_storage_reader_handle: i32, rev_reg_def_json: &str, rev_reg_delta_json: &str, cred_rev_id: &str) -> Result<bool, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_bool();
+
+    let rev_reg_def_json = CString::new(rev_reg_def_json).unwrap();
+    let rev_reg_delta_json = CString::new(rev_reg_delta_json).unwrap();
+    let cred_rev_id = CString::new(cred_rev_id).unwrap();
+
+    let err = unsafe {
+        indy_get_revocation_status(command_handle, blob_storage_reader_handle, rev_reg_def_json.as_ptr(), rev_reg_delta_json.as_ptr(), cred_rev_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_bool(err, receiver)
+}
+
+pub fn proof_request_could_be_satisfied_by_cred_def(proof_request_json: &str, cred_def_json: &str, schema_json: &str) -> Result<bool, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_bool();
+
+    let proof_request_json = CString::new(proof_request_json).unwrap();
+    let cred_def_json = CString::new(cred_def_json).unwrap();
+    let schema_json = CString::new(schema_json).unwrap();
+
+    let err = unsafe {
+        indy_proof_request_could_be_satisfied_by_cred_def(command_handle, proof_request_json.as_ptr(), cred_def_json.as_ptr(), schema_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_bool(err, receiver)
+}
+
+pub fn estimate_credential_storage(schema_attr_count: u32, revocable: bool, count: u32) -> Result<u64, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_u64();
+
+    let err = unsafe {
+        indy_estimate_credential_storage(command_handle, schema_attr_count, revocable, count, cb)
+    };
+
+    super::results::result_to_u64(err, receiver)
+}
+
+pub fn build_non_revoked_interval(timestamp: u64, tolerance: u64) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let err = unsafe {
+        indy_build_non_revoked_interval(command_handle, timestamp, tolerance, cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn compare_schemas(schema_json: &str, other_schema_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let schema_json = CString::new(schema_json).unwrap();
+    let other_schema_json = CString::new(other_schema_json).unwrap();
+
+    let err = unsafe {
+        indy_compare_schemas(command_handle, schema_json.as_ptr(), other_schema_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn get_witness_dependent_indices(rev_reg_def_json: &str, rev_reg_delta_json: &str, cred_rev_id: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let rev_reg_def_json = CString::new(rev_reg_def_json).unwrap();
+    let rev_reg_delta_json = CString::new(rev_reg_delta_json).unwrap();
+    let cred_rev_id = CString::new(cred_rev_id).unwrap();
+
+    let err = unsafe {
+        indy_get_witness_dependent_indices(command_handle, rev_reg_def_json.as_ptr(), rev_reg_delta_json.as_ptr(), cred_rev_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn merge_proof_requests(proof_request_json: &str, other_proof_request_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let proof_request_json = CString::new(proof_request_json).unwrap();
+    let other_proof_request_json = CString::new(other_proof_request_json).unwrap();
+
+    let err = unsafe {
+        indy_merge_proof_requests(command_handle, proof_request_json.as_ptr(), other_proof_request_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn issuer_revoke_credentials(wallet_handle: i32, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_ids_json: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let rev_reg_id = CString::new(rev_reg_id).unwrap();
+    let cred_revoc_ids_json = CString::new(cred_revoc_ids_json).unwrap();
+
+    let err = unsafe {
+        indy_issuer_revoke_credentials(command_handle, wallet_handle, blob_storage_reader_handle, rev_reg_id.as_ptr(), cred_revoc_ids_json.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn prover_create_credential_reqs(wallet_handle: i32, prover_did: &str, offered_credentials_json: &str, master_secret_id: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let prover_did = CString::new(prover_did).unwrap();
+    let offered_credentials_json = CString::new(offered_credentials_json).unwrap();
+    let master_secret_id = CString::new(master_secret_id).unwrap();
+
+    let err = unsafe {
+        indy_prover_create_credential_reqs(command_handle, wallet_handle, prover_did.as_ptr(), offered_credentials_json.as_ptr(), master_secret_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn prover_clear_witness_cache() -> Result<(), ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let err = unsafe {
+        indy_prover_clear_witness_cache(command_handle, cb)
+    };
+
+    super::results::result_to_empty(err, receiver)
+}
+
+pub fn prover_get_max_provable_timestamp(rev_reg_id: &str) -> Result<String, ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec_string();
+
+    let rev_reg_id = CString::new(rev_reg_id).unwrap();
+
+    let err = unsafe {
+        indy_prover_get_max_provable_timestamp(command_handle, rev_reg_id.as_ptr(), cb)
+    };
+
+    super::results::result_to_string_ec(err, receiver)
+}
+
+pub fn verifier_set_audit_callback(cb: Option<extern fn(context: *const c_void, audit_record_json: *const c_char)>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        indy_verifier_set_audit_callback(::std::ptr::null(), cb)
+    })
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -235,7 +794,8 @@ pub fn gvt_schema() -> SchemaV1 {
         version: SCHEMA_VERSION.to_string(),
         name: GVT_SCHEMA_NAME.to_string(),
         attr_names: serde_json::from_str::<HashSet<String>>(GVT_SCHEMA_ATTRIBUTES).unwrap(),
-        seq_no: None
+        seq_no: None,
+        attribute_types: None
     }
 }
 
@@ -253,7 +813,8 @@ pub fn gvt_schema_issuer2() -> SchemaV1 {
         version: SCHEMA_VERSION.to_string(),
         name: GVT_SCHEMA_NAME.to_string(),
         attr_names: serde_json::from_str::<HashSet<String>>(GVT_SCHEMA_ATTRIBUTES).unwrap(),
-        seq_no: None
+        seq_no: None,
+        attribute_types: None
     }
 }
 
@@ -272,7 +833,8 @@ pub fn xyz_schema() -> SchemaV1 {
         version: SCHEMA_VERSION.to_string(),
         name: XYZ_SCHEMA_NAME.to_string(),
         attr_names: serde_json::from_str::<HashSet<String>>(XYZ_SCHEMA_ATTRIBUTES).unwrap(),
-        seq_no: None
+        seq_no: None,
+        attribute_types: None
     }
 }
 
@@ -290,7 +852,8 @@ pub fn xyz_schema_tag2() -> SchemaV1 {
         version: SCHEMA_VERSION.to_string(),
         name: format!("{}{}", XYZ_SCHEMA_NAME, TAG_2),
         attr_names: serde_json::from_str::<HashSet<String>>(XYZ_SCHEMA_ATTRIBUTES).unwrap(),
-        seq_no: None
+        seq_no: None,
+        attribute_types: None
     }
 }
 
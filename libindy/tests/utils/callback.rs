@@ -118,6 +118,32 @@ pub fn _closure_to_cb_ec_bool() -> (Receiver<(ErrorCode, bool)>, i32,
     (receiver, command_handle, Some(_callback))
 }
 
+pub fn _closure_to_cb_ec_u64() -> (Receiver<(ErrorCode, u64)>, i32,
+                                   Option<extern fn(command_handle: i32, err: ErrorCode,
+                                                    val: u64)>) {
+    let (sender, receiver) = channel();
+
+    lazy_static! {
+        static ref CALLBACKS: Mutex<HashMap<i32, Box<dyn FnMut(ErrorCode, u64) + Send>>> = Default::default();
+    }
+
+    let closure = Box::new(move |err, val| {
+        sender.send((err, val)).unwrap();
+    });
+
+    extern "C" fn _callback(command_handle: i32, err: ErrorCode, val: u64) {
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        let mut cb = callbacks.remove(&command_handle).unwrap();
+        cb(err, val)
+    }
+
+    let mut callbacks = CALLBACKS.lock().unwrap();
+    let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+    callbacks.insert(command_handle, closure);
+
+    (receiver, command_handle, Some(_callback))
+}
+
 pub fn _closure_to_cb_ec_string() -> (Receiver<(ErrorCode, String)>, i32,
                                       Option<extern fn(command_handle: i32,
                                                        err: ErrorCode,
@@ -86,10 +86,12 @@ macro_rules! inject_indy_dependencies {
         extern crate openssl;
         extern crate rmp_serde;
         extern crate rust_base58;
+        extern crate sha2;
         extern crate sodiumoxide;
         extern crate rand;
         extern crate regex;
         extern crate time;
+        extern crate unicode_normalization;
         extern crate libc;
     }
 }
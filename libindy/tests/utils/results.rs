@@ -22,6 +22,22 @@ pub fn result_to_empty(err: Error, receiver: Receiver<Error>) -> Result<(), Erro
     Ok(())
 }
 
+pub fn result_to_string_ec(err: Error, receiver: Receiver<(Error, String)>) -> Result<String, ErrorCode> {
+    let err = ErrorCode::from(err as i32);
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    let (err, val) = receiver.recv().unwrap();
+
+    let err = ErrorCode::from(err as i32);
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    Ok(val)
+}
+
 pub fn result_to_int(err: ErrorCode, receiver: Receiver<(ErrorCode, i32)>) -> Result<i32, ErrorCode> {
     if err != ErrorCode::Success {
         return Err(err);
@@ -64,6 +80,20 @@ pub fn result_to_bool(err: ErrorCode, receiver: Receiver<(ErrorCode, bool)>) ->
     Ok(val)
 }
 
+pub fn result_to_u64(err: ErrorCode, receiver: Receiver<(ErrorCode, u64)>) -> Result<u64, ErrorCode> {
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    let (err, val) = receiver.recv().unwrap();
+
+    if err != ErrorCode::Success {
+        return Err(err);
+    }
+
+    Ok(val)
+}
+
 pub fn result_to_string(err: ErrorCode, receiver: Receiver<(ErrorCode, String)>) -> Result<String, ErrorCode> {
     if err != ErrorCode::Success {
         return Err(err);
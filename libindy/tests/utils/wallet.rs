@@ -1,4 +1,5 @@
 extern crate futures;
+extern crate indy_sys;
 
 use serde_json;
 
@@ -7,10 +8,12 @@ use indy::wallet;
 
 use self::futures::Future;
 
+use self::indy_sys::Error;
+
 use utils::{callback, sequence, environment};
 use utils::inmem_wallet::InmemWallet;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use std::ffi::CString;
 use super::libc::c_char;
@@ -125,6 +128,31 @@ pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Re
     wallet::import_wallet(config, credentials, import_config).wait()
 }
 
+// Returns (processed, total) of every progress report
+pub fn import_wallet_with_progress(config: &str, credentials: &str, import_config: &str) -> Result<Vec<(u64, i64)>, ErrorCode> {
+    lazy_static! {
+        static ref PROGRESS: Mutex<HashMap<CommandHandle, Vec<(u64, i64)>>> = Default::default();
+    }
+
+    extern "C" fn _progress_callback(command_handle: CommandHandle, processed: u64, total: i64) {
+        PROGRESS.lock().unwrap().entry(command_handle).or_insert_with(Vec::new).push((processed, total));
+    }
+
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let config = CString::new(config).unwrap();
+    let credentials = CString::new(credentials).unwrap();
+    let import_config = CString::new(import_config).unwrap();
+
+    let err = unsafe {
+        indy_import_wallet_with_progress(command_handle, config.as_ptr(), credentials.as_ptr(), import_config.as_ptr(), Some(_progress_callback), cb)
+    };
+
+    super::results::result_to_empty(err, receiver)?;
+
+    Ok(PROGRESS.lock().unwrap().remove(&command_handle).unwrap_or_default())
+}
+
 pub fn export_wallet_path(name: &str) -> PathBuf {
     environment::tmp_file_path(name)
 }
@@ -170,6 +198,14 @@ extern {
                                         fetch_search_next_record: Option<WalletFetchSearchNextRecord>,
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> ErrorCode;
+
+    #[no_mangle]
+    fn indy_import_wallet_with_progress(command_handle: CommandHandle,
+                                        config: *const c_char,
+                                        credentials: *const c_char,
+                                        import_config: *const c_char,
+                                        progress_cb: Option<extern fn(command_handle_: CommandHandle, processed: u64, total: i64)>,
+                                        cb: Option<extern fn(command_handle_: CommandHandle, err: Error)>) -> Error;
 }
 
 pub type WalletCreate = extern fn(name: *const c_char,
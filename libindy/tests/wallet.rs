@@ -7,7 +7,7 @@ extern crate indyrs as indy;
 extern crate indyrs as api;
 
 use utils::inmem_wallet::InmemWallet;
-use utils::{environment, wallet, test, did};
+use utils::{environment, wallet, test, did, non_secrets};
 use utils::constants::*;
 use utils::Setup;
 
@@ -281,6 +281,44 @@ mod high_cases {
             wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
             cleanup_file(&path);
         }
+
+        #[test]
+        fn indy_import_wallet_works_for_resume_with_progress() {
+            let setup = Setup::empty();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let export_config_json = wallet::prepare_export_wallet_config(&path);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            non_secrets::add_wallet_record(wallet_handle, "TestType", "RecordId1", "RecordValue1", None).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, "TestType", "RecordId2", "RecordValue2", None).unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &export_config_json).unwrap();
+
+            // the interrupted import has left the first record only
+            non_secrets::delete_wallet_record(wallet_handle, "TestType", "RecordId2").unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let import_config_json = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "on_conflict": "skip",
+                "progress_step": 1,
+            }).to_string();
+
+            let progress = wallet::import_wallet_with_progress(&wallet_config, WALLET_CREDENTIALS, &import_config_json).unwrap();
+            assert_eq!(vec![(1, 2), (2, 2)], progress);
+
+            let wallet_handle = wallet::open_wallet(&wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            non_secrets::get_wallet_record(wallet_handle, "TestType", "RecordId1", "{}").unwrap();
+            non_secrets::get_wallet_record(wallet_handle, "TestType", "RecordId2", "{}").unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+            cleanup_file(&path);
+        }
     }
 
     mod generate_wallet_key {
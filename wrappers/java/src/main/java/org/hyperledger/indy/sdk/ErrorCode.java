@@ -100,6 +100,11 @@ public enum ErrorCode {
 	 */
 	CommonInvalidParam14(116),
 
+	/**
+	 * Operation was cancelled by the caller before completion
+	 */
+	CommonCancelled(130),
+
 	// Wallet errors
 	 
 	/**
@@ -230,6 +235,11 @@ public enum ErrorCode {
 	 */
 	LedgerNotFound(309),
 
+	/**
+	 * State proof of ledger reply is older than requested freshness threshold.
+	 */
+	LedgerStaleState(310),
+
 	// Crypto errors
 
 	/**
@@ -238,7 +248,7 @@ public enum ErrorCode {
 	AnoncredsRevocationRegistryFullError(400),
 
 	/**
-	 * ???
+	 * Revocation index is out of registry bounds or its tail is missing in tails file.
 	 */
 	AnoncredsInvalidUserRevocId(401),
 
@@ -262,6 +272,11 @@ public enum ErrorCode {
 	 */
 	AnoncredsCredDefAlreadyExistsError(407),
 
+	/**
+	 * Signature of received credential or its correctness proof doesn't match credential values.
+	 */
+	AnoncredsInvalidCredentialSignature(408),
+
 	// Crypto errors
 	
 	/**
@@ -53,6 +53,9 @@ class ErrorCode(IntEnum):
     # IO Error
     CommonIOError = 114
 
+    # Operation was cancelled by the caller before completion
+    CommonCancelled = 130
+
     # Wallet errors
     # Caller passed invalid wallet handle
     WalletInvalidHandle = 200
@@ -131,9 +134,13 @@ class ErrorCode(IntEnum):
     # Item not found on ledger.
     LedgerNotFound = 309
 
+    # State proof of ledger reply is older than requested freshness threshold
+    LedgerStaleState = 310
+
     # Revocation registry is full and creation of new registry is necessary
     AnoncredsRevocationRegistryFullError = 400
 
+    # Revocation index is out of registry bounds or its tail is missing in tails file
     AnoncredsInvalidUserRevocId = 401
 
     # Attempt to generate master secret with duplicated name
@@ -146,6 +153,9 @@ class ErrorCode(IntEnum):
     # Attempt to create credential definition with duplicated did schema pair
     AnoncredsCredDefAlreadyExistsError = 407
 
+    # Signature of received credential or its correctness proof doesn't match credential values
+    AnoncredsInvalidCredentialSignature = 408
+
     # Crypto errors
     # Unknown format of DID entity keys
     UnknownCryptoTypeError = 500
@@ -239,6 +249,9 @@ class CommonInvalidStructure(IndyError):
 class CommonIOError(IndyError):
     """ IO Error """
 
+class CommonCancelled(IndyError):
+    """ Operation was cancelled by the caller before completion """
+
 # Wallet errors
 class WalletInvalidHandle(IndyError):
     """ Caller passed invalid wallet handle """
@@ -320,6 +333,9 @@ class PoolIncompatibleProtocolVersion(IndyError):
 class LedgerNotFound(IndyError):
     """ Item not found on ledger. """
 
+class LedgerStaleState(IndyError):
+    """ State proof of ledger reply is older than requested freshness threshold. """
+
 class AnoncredsRevocationRegistryFullError(IndyError):
     """ Revocation registry is full and creation of new registry is necessary """
 
@@ -338,6 +354,9 @@ class AnoncredsCredentialRevoked(IndyError):
 class AnoncredsCredDefAlreadyExistsError(IndyError):
     """ Attempt to create credential definition with duplicated did schema pair """
 
+class AnoncredsInvalidCredentialSignature(IndyError):
+    """ Signature of received credential or its correctness proof doesn't match credential values """
+
 # Crypto errors
 class UnknownCryptoTypeError(IndyError):
     """ Unknown format of DID entity keys """
@@ -383,6 +402,7 @@ def errorcode_to_exception(errorcode):
         ErrorCode.CommonInvalidState: CommonInvalidState,
         ErrorCode.CommonInvalidStructure: CommonInvalidStructure,
         ErrorCode.CommonIOError: CommonIOError,
+        ErrorCode.CommonCancelled: CommonCancelled,
         # Wallet Errors
         ErrorCode.WalletInvalidHandle: WalletInvalidHandle,
         ErrorCode.WalletUnknownTypeError: WalletUnknownTypeError,
@@ -410,6 +430,7 @@ def errorcode_to_exception(errorcode):
         ErrorCode.PoolLedgerTimeout: PoolLedgerTimeout,
         ErrorCode.PoolIncompatibleProtocolVersion: PoolIncompatibleProtocolVersion,
         ErrorCode.LedgerNotFound: LedgerNotFound,
+        ErrorCode.LedgerStaleState: LedgerStaleState,
         # Anoncreds Errors
         ErrorCode.AnoncredsRevocationRegistryFullError: AnoncredsRevocationRegistryFullError,
         ErrorCode.AnoncredsInvalidUserRevocId: AnoncredsInvalidUserRevocId,
@@ -417,6 +438,7 @@ def errorcode_to_exception(errorcode):
         ErrorCode.AnoncredsProofRejected: AnoncredsProofRejected,
         ErrorCode.AnoncredsCredentialRevoked: AnoncredsCredentialRevoked,
         ErrorCode.AnoncredsCredDefAlreadyExistsError: AnoncredsCredDefAlreadyExistsError,
+        ErrorCode.AnoncredsInvalidCredentialSignature: AnoncredsInvalidCredentialSignature,
         # Crypto Errors
         ErrorCode.UnknownCryptoTypeError: UnknownCryptoTypeError,
         ErrorCode.DidAlreadyExistsError: DidAlreadyExistsError,
@@ -62,6 +62,14 @@ extern {
                                          blob_storage_reader_handle: BlobStorageReaderHandle,
                                          cb: Option<ResponseStringStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          issuance_requests_json: CString,
+                                          rev_reg_id: CString,
+                                          blob_storage_reader_handle: BlobStorageReaderHandle,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_revoke_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
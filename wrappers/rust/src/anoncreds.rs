@@ -405,6 +405,53 @@ fn _issuer_create_credential(
     })
 }
 
+/// Issues a batch of credentials of one credential definition the same way as `issuer_create_credential`.
+///
+/// The batch is atomic: if any credential can't be issued (e.g. revocation registry gets full),
+/// no credential is returned and the revocation registry stored in the wallet stays unchanged.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `issuance_requests_json`: credentials to issue
+///     [{
+///         "cred_offer": <cred offer created by issuer_create_credential_offer>,
+///         "cred_req": <credential request created by prover_create_credential_req>,
+///         "cred_values": <credential values, see issuer_create_credential>
+///     }]
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `credentials_json`: issued credentials in the order of requests
+///     {
+///         "credentials": [{"cred": <credential json>, "cred_rev_id": Optional<string>}],
+///         "rev_reg_delta": Optional<revocation registry delta json with all newly issued credentials>
+///     }
+pub fn issuer_create_credentials(wallet_handle: WalletHandle,
+                                 issuance_requests_json: &str,
+                                 rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_create_credentials(command_handle, wallet_handle, issuance_requests_json, rev_reg_id, blob_storage_reader_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_create_credentials(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              issuance_requests_json: &str,
+                              rev_reg_id: Option<&str>,
+                              blob_storage_reader_handle: BlobStorageReaderHandle,
+                              cb: Option<ResponseStringCB>) -> ErrorCode {
+    let issuance_requests_json = c_str!(issuance_requests_json);
+    let rev_reg_id_str = opt_c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credentials(command_handle, wallet_handle, issuance_requests_json.as_ptr(), opt_c_ptr!(rev_reg_id, rev_reg_id_str), blob_storage_reader_handle, cb)
+    })
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
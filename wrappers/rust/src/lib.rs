@@ -177,6 +177,9 @@ pub enum ErrorCode
     // Caller passed invalid value as param 27 (null, invalid json and etc..)
     #[fail(display = "CommonInvalidParam27")]
     CommonInvalidParam27 = 129,
+    // Operation was cancelled by the caller before completion
+    #[fail(display = "CommonCancelled")]
+    CommonCancelled = 130,
     // Wallet errors
     // Caller passed invalid wallet handle
     #[fail(display = "WalletInvalidHandle")]
@@ -261,6 +264,10 @@ pub enum ErrorCode
     #[fail(display = "LedgerNotFound")]
     LedgerNotFound = 309,
 
+    // State proof of ledger reply is older than requested freshness threshold
+    #[fail(display = "LedgerStaleState")]
+    LedgerStaleState = 310,
+
     // Revocation registry is full and creation of new registry is necessary
     #[fail(display = "AnoncredsRevocationRegistryFullError")]
     AnoncredsRevocationRegistryFullError = 400,
@@ -277,6 +284,8 @@ pub enum ErrorCode
     // Attempt to create credential definition with duplicated did schema pair
     #[fail(display = "AnoncredsCredDefAlreadyExistsError")]
     AnoncredsCredDefAlreadyExistsError = 407,
+    #[fail(display = "AnoncredsInvalidCredentialSignature")]
+    AnoncredsInvalidCredentialSignature = 408,
     // Signus errors
     // Unknown format of DID entity keys
     #[fail(display = "UnknownCryptoTypeError")]